@@ -0,0 +1,61 @@
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+use crate::CliError;
+
+/// Prefix of hardware wallet paths, e.g. `usb://ledger?key=0/0`
+const USB_PREFIX: &str = "usb://";
+
+/// Load the signer at `path`: a keypair file, or a Ledger given as `usb://ledger`
+/// with an optional `?key=<account>/<change>` derivation path
+pub fn load_signer(path: &str, name: &str) -> Result<Box<dyn Signer>, CliError> {
+    if path.starts_with(USB_PREFIX) {
+        return load_ledger(path, name);
+    }
+    let path = expand_home(path);
+    read_keypair_file(&path)
+        .map(|keypair| Box::new(keypair) as Box<dyn Signer>)
+        .map_err(|error| CliError::Signer(format!("{} keypair {}: {}", name, path, error)))
+}
+
+#[cfg(feature = "ledger")]
+fn load_ledger(path: &str, name: &str) -> Result<Box<dyn Signer>, CliError> {
+    use solana_remote_wallet::locator::Locator;
+    use solana_remote_wallet::remote_keypair::generate_remote_keypair;
+    use solana_remote_wallet::remote_wallet::maybe_wallet_manager;
+    use solana_sdk::derivation_path::DerivationPath;
+
+    let signer_error = |error: String| CliError::Signer(format!("{} on {}: {}", name, path, error));
+    let (device, key) = match path.split_once("?key=") {
+        Some((device, key)) => (device, Some(key)),
+        None => (path, None),
+    };
+    let locator = Locator::new_from_path(device).map_err(|error| signer_error(error.to_string()))?;
+    let derivation_path = match key {
+        Some(key) => DerivationPath::from_key_str(key).map_err(|error| signer_error(error.to_string()))?,
+        None => DerivationPath::default(),
+    };
+    let wallet_manager = maybe_wallet_manager()
+        .map_err(|error| signer_error(error.to_string()))?
+        .ok_or_else(|| signer_error("no hardware wallet connected".to_string()))?;
+    // Ledger keys confirm every signature on the device, the key itself needs no
+    // extra confirmation
+    let keypair = generate_remote_keypair(locator, derivation_path, &wallet_manager, false, name)
+        .map_err(|error| signer_error(error.to_string()))?;
+    Ok(Box::new(keypair))
+}
+
+#[cfg(not(feature = "ledger"))]
+fn load_ledger(path: &str, name: &str) -> Result<Box<dyn Signer>, CliError> {
+    Err(CliError::Signer(format!(
+        "{} on {}: built without Ledger support, rebuild with `--features ledger`",
+        name, path
+    )))
+}
+
+// Expand a leading `~/` the shell left in a default path
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
@@ -0,0 +1,467 @@
+//! `mica-eur-cli`: issuer and compliance operations on a MiCA EUR mint.
+//!
+//! Every command builds its instructions with `mica_eur_client`, signs them with
+//! the key given by `--keypair` (a keypair file or a Ledger as `usb://ledger`)
+//! and sends them to the cluster at `--url`. Reports only read chain state.
+
+mod keys;
+mod report;
+mod reserve;
+mod rpc;
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use mica_eur::{freeze_reasons, KycStatus, ReserveAsset, ReserveAssetCategory};
+use mica_eur_client::{AccountFetcher, ClientError, ComplianceClient, KycSigner};
+use reserve_merkle::{to_hex, ReserveDataset};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::rpc::RpcFetcher;
+
+#[derive(Parser)]
+#[command(name = "mica-eur-cli", version, about = "Issuer and compliance operations on a MiCA EUR mint")]
+struct Cli {
+    /// JSON RPC URL of the cluster
+    #[arg(long, short = 'u', env = "MICA_EUR_RPC_URL", default_value = "http://localhost:8899", global = true)]
+    url: String,
+
+    /// Signing key: a keypair file, or `usb://ledger[?key=<account>/<change>]`
+    #[arg(long, short = 'k', env = "MICA_EUR_KEYPAIR", default_value = "~/.config/solana/id.json", global = true)]
+    keypair: String,
+
+    /// The EUR mint, required by every command but `init-mint` and `kyc`
+    #[arg(long, short = 'm', env = "MICA_EUR_MINT", global = true)]
+    mint: Option<Pubkey>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the EUR mint, with the signing key as issuer
+    InitMint {
+        /// URI of the crypto-asset white paper
+        #[arg(long)]
+        whitepaper_uri: String,
+        /// Decimals of the mint: 2, 6 or 9
+        #[arg(long, default_value_t = mica_eur::EUR_DECIMALS)]
+        decimals: u8,
+        /// Keypair file of the new mint, a fresh keypair if omitted
+        #[arg(long)]
+        mint_keypair: Option<PathBuf>,
+        /// Freeze authority, the issuer if omitted
+        #[arg(long)]
+        freeze_authority: Option<Pubkey>,
+        /// Permanent delegate for seizures, the issuer if omitted
+        #[arg(long)]
+        permanent_delegate: Option<Pubkey>,
+    },
+    /// Register and verify users with the KYC oracle
+    #[command(subcommand)]
+    Kyc(KycCommand),
+    /// Freeze a token account (regulatory action)
+    Freeze {
+        token_account: Pubkey,
+        #[arg(long, value_enum)]
+        reason: FreezeReason,
+        /// Court order, case or regulator reference the freeze is made under
+        #[arg(long)]
+        legal_reference: String,
+        /// Part of the balance to lock, the whole account if omitted
+        #[arg(long, default_value_t = 0)]
+        amount: u64,
+        /// Unix time the freeze lapses at, never if omitted
+        #[arg(long, default_value_t = 0)]
+        expires_at: i64,
+    },
+    /// Thaw a frozen token account
+    Thaw { token_account: Pubkey },
+    /// Thaw a verified holder's new token account (any signer)
+    Activate { token_account: Pubkey },
+    /// Seize tokens from an account (regulatory action)
+    Seize {
+        from_account: Pubkey,
+        to_account: Pubkey,
+        amount: u64,
+        #[command(flatten)]
+        operation: OperationArgs,
+    },
+    /// Publish a reserve proof built from a CSV of deposits
+    PublishReserve(PublishReserveArgs),
+    /// Print a report read from chain state
+    #[command(subcommand)]
+    Report(ReportCommand),
+}
+
+#[derive(Subcommand)]
+enum KycCommand {
+    /// Register a user, pending verification
+    Register {
+        user: Pubkey,
+        /// Bank code (Bankleitzahl) of the user's account
+        #[arg(long)]
+        blz: String,
+        /// IBAN of the user's account, only its SHA-256 hash goes on-chain
+        #[arg(long)]
+        iban: String,
+        /// ISO 3166 country code
+        #[arg(long)]
+        country: String,
+        /// Provider that verified the user
+        #[arg(long)]
+        provider: String,
+        #[arg(long, value_enum, default_value_t = SignerKind::Oracle)]
+        signer: SignerKind,
+    },
+    /// Mark a registered user as verified
+    Verify {
+        user: Pubkey,
+        /// Verification level (0-3)
+        #[arg(long)]
+        level: u8,
+        /// Days until the verification expires
+        #[arg(long, default_value_t = 365)]
+        expiry_days: i64,
+        #[arg(long, value_enum, default_value_t = SignerKind::Oracle)]
+        signer: SignerKind,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommand {
+    /// State, supply and reserve backing of the mint
+    Mint,
+    /// KYC, blacklist and limit state of a user
+    User { user: Pubkey },
+}
+
+#[derive(Args)]
+struct PublishReserveArgs {
+    /// CSV of deposits with a `deposit_id,amount,timestamp` header, amounts in token units
+    deposits: PathBuf,
+    /// IPFS CID the deposits and their proofs are pinned under
+    #[arg(long)]
+    ipfs_cid: String,
+    /// Write the dataset to pin, each deposit with its leaf and inclusion proof, to
+    /// this JSON file
+    #[arg(long)]
+    proofs: Option<PathBuf>,
+    /// CSV of reserve positions with a `category,isin,amount,units` header, categories
+    /// `cash`, `central_bank` or `bond`; the whole total is reported as cash at
+    /// bank if omitted
+    #[arg(long)]
+    composition: Option<PathBuf>,
+    /// Posted Pyth price update for the mint's reserve valuation, EUR/USD first and
+    /// then one per bond position, in composition order
+    #[arg(long = "price-update")]
+    price_updates: Vec<Pubkey>,
+    /// Print the proof without publishing it
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+struct OperationArgs {
+    /// Operation ID as 32 hex characters, rejecting retries of the same operation;
+    /// a fresh one if omitted
+    #[arg(long, value_parser = parse_operation_id)]
+    operation_id: Option<[u8; 16]>,
+}
+
+/// How the signing key is authorized for KYC instructions
+#[derive(Clone, Copy, ValueEnum)]
+enum SignerKind {
+    /// The KYC oracle authority
+    Oracle,
+    /// A registered KYC provider
+    Provider,
+    /// A holder of the KYC_ADMIN role
+    Admin,
+}
+
+impl From<SignerKind> for KycSigner {
+    fn from(kind: SignerKind) -> Self {
+        match kind {
+            SignerKind::Oracle => KycSigner::OracleAuthority,
+            SignerKind::Provider => KycSigner::Provider,
+            SignerKind::Admin => KycSigner::Admin,
+        }
+    }
+}
+
+/// Reason a freeze is made for, see `freeze_reasons`
+#[derive(Clone, Copy, ValueEnum)]
+enum FreezeReason {
+    CourtOrder,
+    Sanctions,
+    AmlInvestigation,
+    Fraud,
+    AccountCompromise,
+    RegulatorRequest,
+}
+
+impl FreezeReason {
+    fn code(self) -> u8 {
+        match self {
+            FreezeReason::CourtOrder => freeze_reasons::COURT_ORDER,
+            FreezeReason::Sanctions => freeze_reasons::SANCTIONS,
+            FreezeReason::AmlInvestigation => freeze_reasons::AML_INVESTIGATION,
+            FreezeReason::Fraud => freeze_reasons::FRAUD,
+            FreezeReason::AccountCompromise => freeze_reasons::ACCOUNT_COMPROMISE,
+            FreezeReason::RegulatorRequest => freeze_reasons::REGULATOR_REQUEST,
+        }
+    }
+}
+
+/// Errors of the CLI
+#[derive(Debug)]
+pub enum CliError {
+    /// Sending a transaction or reading the cluster failed
+    Rpc(String),
+    /// Building an instruction from chain state failed
+    Client(ClientError),
+    /// The signing key could not be loaded or refused to sign
+    Signer(String),
+    /// The deposits CSV could not be read or written
+    Csv(String),
+    /// A required argument is missing
+    MissingArgument(&'static str),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Rpc(error) => write!(f, "RPC error: {}", error),
+            CliError::Client(error) => write!(f, "{}", error),
+            CliError::Signer(error) => write!(f, "signer error: {}", error),
+            CliError::Csv(error) => write!(f, "CSV error: {}", error),
+            CliError::MissingArgument(argument) => write!(f, "missing {}", argument),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<ClientError> for CliError {
+    fn from(error: ClientError) -> Self {
+        CliError::Client(error)
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(error) = run(cli) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<(), CliError> {
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+    let mint = cli.mint.ok_or(CliError::MissingArgument("--mint"));
+
+    match cli.command {
+        Command::InitMint {
+            whitepaper_uri,
+            decimals,
+            mint_keypair,
+            freeze_authority,
+            permanent_delegate,
+        } => {
+            let issuer = keys::load_signer(&cli.keypair, "issuer")?;
+            let mint_keypair: Box<dyn Signer> = match mint_keypair {
+                Some(path) => keys::load_signer(&path.to_string_lossy(), "mint")?,
+                None => Box::new(Keypair::new()),
+            };
+            let issuer_key = issuer.pubkey();
+            let instruction = mica_eur_client::initialize_euro_mint(
+                &issuer_key,
+                &mint_keypair.pubkey(),
+                &freeze_authority.unwrap_or(issuer_key),
+                &permanent_delegate.unwrap_or(issuer_key),
+                whitepaper_uri,
+                decimals,
+            );
+            let signature = rpc::send(&rpc, &[instruction], &[issuer.as_ref(), mint_keypair.as_ref()])?;
+            println!("mint {}", mint_keypair.pubkey());
+            println!("signature {}", signature);
+        }
+        Command::Kyc(command) => {
+            let signer = keys::load_signer(&cli.keypair, "KYC authority")?;
+            let authority = signer.pubkey();
+            let instruction = match command {
+                KycCommand::Register {
+                    user,
+                    blz,
+                    iban,
+                    country,
+                    provider,
+                    signer: kind,
+                } => {
+                    let iban_hash = hash(normalize_iban(&iban).as_bytes()).to_bytes();
+                    mica_eur_client::register_kyc_user(&authority, kind.into(), &user, blz, iban_hash, country, provider)
+                }
+                KycCommand::Verify {
+                    user,
+                    level,
+                    expiry_days,
+                    signer: kind,
+                } => {
+                    let history = mica_eur_client::pda::compliance_history(&user);
+                    let history = RpcFetcher::new(&rpc).account_data(&history)?.map(|_| history);
+                    mica_eur_client::update_kyc_status(
+                        &authority,
+                        kind.into(),
+                        &user,
+                        KycStatus::Verified,
+                        level,
+                        expiry_days,
+                        history,
+                    )
+                }
+            };
+            submit(&rpc, instruction, signer.as_ref())?;
+        }
+        Command::Freeze {
+            token_account,
+            reason,
+            legal_reference,
+            amount,
+            expires_at,
+        } => {
+            let signer = keys::load_signer(&cli.keypair, "freeze authority")?;
+            let client = ComplianceClient::new(RpcFetcher::new(&rpc), mint?);
+            let instruction = client.freeze(
+                &signer.pubkey(),
+                &token_account,
+                reason.code(),
+                legal_reference,
+                amount,
+                expires_at,
+            )?;
+            submit(&rpc, instruction, signer.as_ref())?;
+        }
+        Command::Thaw { token_account } => {
+            let signer = keys::load_signer(&cli.keypair, "freeze authority")?;
+            let client = ComplianceClient::new(RpcFetcher::new(&rpc), mint?);
+            let instruction = client.thaw(&signer.pubkey(), &token_account)?;
+            submit(&rpc, instruction, signer.as_ref())?;
+        }
+        Command::Activate { token_account } => {
+            let signer = keys::load_signer(&cli.keypair, "signer")?;
+            let client = ComplianceClient::new(RpcFetcher::new(&rpc), mint?);
+            let instruction = client.activate_token_account(&signer.pubkey(), &token_account)?;
+            submit(&rpc, instruction, signer.as_ref())?;
+        }
+        Command::Seize {
+            from_account,
+            to_account,
+            amount,
+            operation,
+        } => {
+            let signer = keys::load_signer(&cli.keypair, "permanent delegate")?;
+            let client = ComplianceClient::new(RpcFetcher::new(&rpc), mint?);
+            let operation_id = operation.resolve();
+            let instruction = client.seize(&signer.pubkey(), &from_account, &to_account, amount, operation_id)?;
+            println!("operation {}", hex_operation_id(&operation_id));
+            submit(&rpc, instruction, signer.as_ref())?;
+        }
+        Command::PublishReserve(args) => {
+            let file = File::open(&args.deposits).map_err(|error| CliError::Csv(error.to_string()))?;
+            let tree = reserve::read_tree(file)?;
+            println!("deposits {}", tree.entries().len());
+            println!("reserve total {}", tree.reserve_total());
+            println!("merkle root {}", to_hex(&tree.root()));
+            if let Some(path) = &args.proofs {
+                std::fs::write(path, ReserveDataset::from_tree(&tree).to_json())
+                    .map_err(|error| CliError::Csv(error.to_string()))?;
+            }
+            let composition = match &args.composition {
+                Some(path) => {
+                    let file = File::open(path).map_err(|error| CliError::Csv(error.to_string()))?;
+                    reserve::read_composition(file)?
+                }
+                None => vec![ReserveAsset {
+                    category: ReserveAssetCategory::CashAtBank,
+                    isin_hash: [0; 32],
+                    amount: tree.reserve_total(),
+                    units: 0,
+                }],
+            };
+            println!("reserve positions {}", composition.len());
+            if args.dry_run {
+                return Ok(());
+            }
+
+            let signer = keys::load_signer(&cli.keypair, "reserve publisher")?;
+            let client = ComplianceClient::new(RpcFetcher::new(&rpc), mint?);
+            let instruction = client.publish_reserve_proof(
+                &signer.pubkey(),
+                tree.root(),
+                args.ipfs_cid,
+                tree.reserve_total(),
+                composition,
+                &args.price_updates,
+            )?;
+            submit(&rpc, instruction, signer.as_ref())?;
+        }
+        Command::Report(command) => {
+            let client = ComplianceClient::new(RpcFetcher::new(&rpc), mint?);
+            let report = match command {
+                ReportCommand::Mint => report::mint_report(&client)?,
+                ReportCommand::User { user } => report::user_report(&client, &user, unix_now())?,
+            };
+            print!("{}", report);
+        }
+    }
+    Ok(())
+}
+
+// Send a single instruction signed and paid for by `signer`
+fn submit(rpc: &RpcClient, instruction: Instruction, signer: &dyn Signer) -> Result<(), CliError> {
+    let signature = rpc::send(rpc, &[instruction], &[signer])?;
+    println!("signature {}", signature);
+    Ok(())
+}
+
+impl OperationArgs {
+    fn resolve(&self) -> [u8; 16] {
+        self.operation_id.unwrap_or_else(|| {
+            let seed = Keypair::new().pubkey().to_bytes();
+            seed[..16].try_into().unwrap()
+        })
+    }
+}
+
+fn parse_operation_id(value: &str) -> Result<[u8; 16], String> {
+    if value.len() != 32 || !value.is_ascii() {
+        return Err("expected 32 hex characters".to_string());
+    }
+    let mut operation_id = [0u8; 16];
+    for (i, byte) in operation_id.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).map_err(|error| error.to_string())?;
+    }
+    Ok(operation_id)
+}
+
+fn hex_operation_id(operation_id: &[u8; 16]) -> String {
+    operation_id.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// IBANs are hashed without spaces and in upper case, as printed forms vary
+fn normalize_iban(iban: &str) -> String {
+    iban.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase()
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
@@ -0,0 +1,130 @@
+use anchor_spl::token_interface::Mint;
+use mica_eur::{
+    coverage_bps, is_kyc_verified_at, is_reserve_attested, AuditorAttestation, BlacklistEntry,
+    ReserveAssetCategory, ReserveComposition, UserLimits, FULL_COVERAGE_BPS,
+};
+use mica_eur_client::{pda, AccountFetcher, ClientError, ComplianceClient};
+use reserve_merkle::to_hex;
+use solana_sdk::pubkey::Pubkey;
+
+/// Lines of a report, printed as `label: value`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    pub title: String,
+    pub lines: Vec<(&'static str, String)>,
+}
+
+impl Report {
+    fn new(title: String) -> Self {
+        Self { title, lines: Vec::new() }
+    }
+
+    fn line(&mut self, label: &'static str, value: impl ToString) {
+        self.lines.push((label, value.to_string()));
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.title)?;
+        let width = self.lines.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        for (label, value) in &self.lines {
+            writeln!(f, "  {:width$}  {}", label, value, width = width)?;
+        }
+        Ok(())
+    }
+}
+
+/// State, supply and reserve backing of the client's mint
+pub fn mint_report<F: AccountFetcher>(client: &ComplianceClient<F>) -> Result<Report, ClientError> {
+    let mint = client.mint();
+    let mint_info = client.mint_info()?;
+    let supply = client.fetch::<Mint>(&mint)?.ok_or(ClientError::AccountNotFound(mint))?.supply;
+    let attested = client
+        .fetch::<AuditorAttestation>(&pda::auditor_attestation(&mint))?
+        .is_some_and(|attestation| is_reserve_attested(&mint_info, &attestation));
+    let coverage = coverage_bps(mint_info.reserve_total_eur, supply);
+    let composition = client.fetch::<ReserveComposition>(&pda::reserve_composition(&mint))?;
+
+    let mut report = Report::new(format!("Mint {}", mint));
+    report.line("issuer", mint_info.issuer);
+    report.line("freeze authority", mint_info.freeze_authority);
+    report.line("permanent delegate", mint_info.permanent_delegate);
+    report.line("active", mint_info.is_active);
+    report.line("paused", mint_info.paused);
+    report.line("minting halted", mint_info.minting_halted);
+    if mint_info.wind_down_started_at != 0 {
+        report.line("winding down since", mint_info.wind_down_started_at);
+        report.line("redemption deadline", mint_info.redemption_deadline);
+        report.line("redemption account", mint_info.redemption_account);
+    }
+    report.line("decimals", mint_info.decimals);
+    report.line("associated accounts only", mint_info.require_associated_accounts);
+    report.line("supply", supply);
+    report.line("holders", mint_info.holder_count);
+    report.line("max supply", or_none(mint_info.max_supply));
+    report.line("rule set version", mint_info.rule_set_version);
+    report.line("whitepaper", format!("{} (v{})", mint_info.whitepaper_uri, mint_info.whitepaper_version));
+    report.line("reserve total", mint_info.reserve_total_eur);
+    if let Some(composition) = &composition {
+        report.line("cash at bank", composition.category_total(ReserveAssetCategory::CashAtBank));
+        report.line("central bank deposits", composition.category_total(ReserveAssetCategory::CentralBankDeposit));
+        report.line("government bonds", composition.category_total(ReserveAssetCategory::GovernmentBond));
+        if composition.valuation.valued_at != 0 {
+            report.line("oracle bond value", composition.valuation.oracle_value);
+        }
+    }
+    report.line("reserve root", to_hex(&mint_info.reserve_merkle_root));
+    report.line("reserve CID", &mint_info.reserve_ipfs_cid);
+    report.line("last reserve update", mint_info.last_reserve_update);
+    report.line("max reserve age", or_none(mint_info.max_reserve_age_seconds));
+    report.line("reserve attested", attested);
+    report.line("coverage", match coverage {
+        u64::MAX => "no supply".to_string(),
+        bps => format!("{:.2}%{}", bps as f64 / 100.0, if bps < FULL_COVERAGE_BPS { " (shortfall)" } else { "" }),
+    });
+    Ok(report)
+}
+
+/// KYC, blacklist and limit state of `user` at `now`
+pub fn user_report<F: AccountFetcher>(
+    client: &ComplianceClient<F>,
+    user: &Pubkey,
+    now: i64,
+) -> Result<Report, ClientError> {
+    let mut report = Report::new(format!("User {}", user));
+    match client.kyc_user(user)? {
+        Some(kyc_user) => {
+            report.line("KYC status", format!("{:?}", kyc_user.status));
+            report.line("verified", is_kyc_verified_at(&kyc_user, now));
+            report.line("verification level", kyc_user.verification_level);
+            report.line("expiry", kyc_user.expiry_time);
+            report.line("country", &kyc_user.country_code);
+            report.line("account class", format!("{:?}", kyc_user.account_class));
+            report.line("EDD required", kyc_user.edd_required);
+            report.line("last screening", or_none(kyc_user.sanctions_screen_date));
+        }
+        None => report.line("KYC status", "not registered"),
+    }
+
+    let blacklist_entry = client
+        .fetch::<BlacklistEntry>(&pda::blacklist_entry(user))?
+        .filter(|entry| entry.in_force(now));
+    match blacklist_entry {
+        Some(entry) => report.line("blacklisted", format!("yes, {:?}", entry.reason)),
+        None => report.line("blacklisted", "no"),
+    }
+
+    if let Some(limits) = client.fetch::<UserLimits>(&pda::user_limits(user))? {
+        report.line("minted in window", limits.minted.current(now));
+        report.line("burned in window", limits.burned.current(now));
+    }
+    Ok(report)
+}
+
+fn or_none(value: impl Into<i128> + Copy) -> String {
+    match value.into() {
+        0 => "none".to_string(),
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,51 @@
+use std::io::Read;
+
+use mica_eur::{isin_hash, ReserveAsset, ReserveAssetCategory};
+use reserve_merkle::{ReserveEntry, ReserveTree};
+
+use crate::CliError;
+
+/// Read the deposits CSV, with a `deposit_id,amount,timestamp` header, into the
+/// reserve tree over them in file order
+pub fn read_tree(reader: impl Read) -> Result<ReserveTree, CliError> {
+    let entries = csv::Reader::from_reader(reader)
+        .deserialize()
+        .enumerate()
+        .map(|(row, record)| record.map_err(|error| CliError::Csv(format!("row {}: {}", row + 1, error))))
+        .collect::<Result<Vec<ReserveEntry>, _>>()?;
+    ReserveTree::build(entries).map_err(|error| CliError::Csv(error.to_string()))
+}
+
+/// Read the composition CSV, with a `category,isin,amount,units` header, into the
+/// positions posted with the proof. Categories are `cash`, `central_bank` and
+/// `bond`; only bonds carry an ISIN and, for their valuation, the units held.
+pub fn read_composition(reader: impl Read) -> Result<Vec<ReserveAsset>, CliError> {
+    csv::Reader::from_reader(reader)
+        .records()
+        .enumerate()
+        .map(|(row, record)| {
+            let fail = |error: String| CliError::Csv(format!("row {}: {}", row + 1, error));
+            let record = record.map_err(|error| fail(error.to_string()))?;
+            let field = |index: usize| record.get(index).unwrap_or("").trim();
+            let category = match field(0) {
+                "cash" => ReserveAssetCategory::CashAtBank,
+                "central_bank" => ReserveAssetCategory::CentralBankDeposit,
+                "bond" => ReserveAssetCategory::GovernmentBond,
+                other => return Err(fail(format!("unknown category {:?}", other))),
+            };
+            let isin = field(1);
+            let isin_hash = match (category, isin.is_empty()) {
+                (ReserveAssetCategory::GovernmentBond, false) => isin_hash(isin),
+                (ReserveAssetCategory::GovernmentBond, true) => return Err(fail("bond without ISIN".to_string())),
+                (_, true) => [0; 32],
+                (_, false) => return Err(fail("ISIN on a position that is not a bond".to_string())),
+            };
+            let amount = field(2).parse::<u64>().map_err(|error| fail(error.to_string()))?;
+            let units = match field(3) {
+                "" => 0,
+                units => units.parse::<u64>().map_err(|error| fail(error.to_string()))?,
+            };
+            Ok(ReserveAsset { category, isin_hash, amount, units })
+        })
+        .collect()
+}
@@ -0,0 +1,42 @@
+use mica_eur_client::{AccountFetcher, ClientError};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::CliError;
+
+/// `AccountFetcher` over the cluster's JSON RPC
+pub struct RpcFetcher<'a> {
+    client: &'a RpcClient,
+}
+
+impl<'a> RpcFetcher<'a> {
+    pub fn new(client: &'a RpcClient) -> Self {
+        Self { client }
+    }
+}
+
+impl AccountFetcher for RpcFetcher<'_> {
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        self.client
+            .get_account_with_commitment(address, self.client.commitment())
+            .map(|response| response.value.map(|account| account.data))
+            .map_err(|error| ClientError::Rpc(error.to_string()))
+    }
+}
+
+/// Sign `instructions` into one transaction paid by the first signer, send it and
+/// wait for confirmation
+pub fn send(client: &RpcClient, instructions: &[Instruction], signers: &[&dyn Signer]) -> Result<Signature, CliError> {
+    let payer = signers.first().map(|signer| signer.pubkey());
+    let blockhash = client.get_latest_blockhash().map_err(|error| CliError::Rpc(error.to_string()))?;
+    let mut transaction = Transaction::new_with_payer(instructions, payer.as_ref());
+    transaction
+        .try_sign(&signers.to_vec(), blockhash)
+        .map_err(|error| CliError::Signer(error.to_string()))?;
+    client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(|error| CliError::Rpc(error.to_string()))
+}
@@ -0,0 +1,127 @@
+//! `publish-reserve --dry-run` over the deposit and composition CSVs in
+//! `tests/fixtures`: the printed root and total, the proofs file it writes, and
+//! the row errors of malformed files. A dry run needs neither a cluster nor a key.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use reserve_merkle::{to_hex, ReserveDataset, ReserveEntry, ReserveTree};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+fn publish_reserve(deposits: &str, extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_mica-eur-cli"))
+        .arg("publish-reserve")
+        .arg(fixture(deposits))
+        .args([
+            "--ipfs-cid",
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ])
+        .args([
+            "--url",
+            "http://127.0.0.1:1",
+            "--keypair",
+            "/nonexistent/id.json",
+        ])
+        .arg("--dry-run")
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+fn entry(deposit_id: &str, amount: u64, timestamp: i64) -> ReserveEntry {
+    ReserveEntry {
+        deposit_id: deposit_id.to_string(),
+        amount,
+        timestamp,
+    }
+}
+
+// The tree over `deposits.csv`, built without the CLI
+fn expected_tree() -> ReserveTree {
+    ReserveTree::build(vec![
+        entry("SEPA-2026-000117", 25_000_000_000, 1_776_124_800),
+        entry("COBA-260414-10021", 995_000_000, 1_776_124_800),
+        entry("COBA-260415-10388", 10_000_000_000, 1_776_211_200),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn dry_run_prints_the_root_and_writes_the_proofs() {
+    let proofs = Path::new(env!("CARGO_TARGET_TMPDIR")).join("reserve-proofs.json");
+    let _ = std::fs::remove_file(&proofs);
+
+    let output = publish_reserve("deposits.csv", &["--proofs", proofs.to_str().unwrap()]);
+
+    let tree = expected_tree();
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "deposits 3\nreserve total 35995000000\nmerkle root {}\nreserve positions 1\n",
+            to_hex(&tree.root())
+        )
+    );
+    let dataset = ReserveDataset::from_json(&std::fs::read_to_string(&proofs).unwrap()).unwrap();
+    assert_eq!(dataset, ReserveDataset::from_tree(&tree));
+    let leaf = tree.leaves()[1];
+    assert!(tree.verify(&leaf, &tree.proof(1).unwrap()));
+}
+
+#[test]
+fn composition_is_read_from_its_csv() {
+    let composition = fixture("composition.csv");
+
+    let output = publish_reserve(
+        "deposits.csv",
+        &["--composition", composition.to_str().unwrap()],
+    );
+
+    assert!(stdout(&output).ends_with("reserve positions 3\n"));
+}
+
+#[test]
+fn malformed_deposits_name_the_row() {
+    let report = stderr(&publish_reserve("deposits-decimal.csv", &[]));
+    assert!(report.starts_with("error: "), "{report}");
+    assert!(report.contains("row 1: "), "{report}");
+
+    let report = stderr(&publish_reserve("deposits-duplicate.csv", &[]));
+    assert!(
+        report.contains("duplicate deposit COBA-260414-10021"),
+        "{report}"
+    );
+}
+
+#[test]
+fn isins_are_only_accepted_on_bonds() {
+    let composition = fixture("composition-isin-on-cash.csv");
+
+    let report = stderr(&publish_reserve(
+        "deposits.csv",
+        &["--composition", composition.to_str().unwrap()],
+    ));
+
+    assert!(
+        report.contains("row 2: ISIN on a position that is not a bond"),
+        "{report}"
+    );
+}
@@ -0,0 +1,354 @@
+//! High-level compliance flows on one mint.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensions;
+use anchor_spl::token_2022::spl_token_2022::state::Account as TokenAccountState;
+use mica_eur::{is_kyc_verified_at, KycStatus, KycUser, MintInfo, ReserveAsset, TreasuryVault};
+
+use crate::instructions::{self, BurnTokensOptions, DistributeOptions, KycSigner, MintTokensOptions, ReserveProofOptions};
+use crate::{decode_account, pda, ClientError};
+
+/// Read access to account data, implemented over the deployment's RPC client.
+/// `None` for accounts that do not exist.
+pub trait AccountFetcher {
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError>;
+}
+
+/// Builds the instructions of the issuer's compliance flows on `mint`, reading
+/// the on-chain state each one depends on: the user's country, the optional
+/// accounts a mint's configuration requires, and the minter's role.
+pub struct ComplianceClient<F> {
+    fetcher: F,
+    mint: Pubkey,
+}
+
+impl<F: AccountFetcher> ComplianceClient<F> {
+    pub fn new(fetcher: F, mint: Pubkey) -> Self {
+        Self { fetcher, mint }
+    }
+
+    pub fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    /// The program account at `address`, `None` if it does not exist
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>, ClientError> {
+        self.fetcher
+            .account_data(address)?
+            .map(|data| decode_account(address, &data))
+            .transpose()
+    }
+
+    fn fetch_required<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T, ClientError> {
+        self.fetch(address)?.ok_or(ClientError::AccountNotFound(*address))
+    }
+
+    fn existing(&self, address: Pubkey) -> Result<Option<Pubkey>, ClientError> {
+        Ok(self.fetcher.account_data(&address)?.map(|_| address))
+    }
+
+    // Owner of the token account at `address`
+    fn token_account_owner(&self, address: &Pubkey) -> Result<Pubkey, ClientError> {
+        let data = self.fetcher.account_data(address)?.ok_or(ClientError::AccountNotFound(*address))?;
+        StateWithExtensions::<TokenAccountState>::unpack(&data)
+            .map(|account| account.base.owner)
+            .map_err(|_| ClientError::InvalidAccountData(*address))
+    }
+
+    pub fn mint_info(&self) -> Result<MintInfo, ClientError> {
+        self.fetch_required(&pda::mint_info(&self.mint))
+    }
+
+    /// KYC record of `user`, `None` if the user is not registered
+    pub fn kyc_user(&self, user: &Pubkey) -> Result<Option<KycUser>, ClientError> {
+        self.fetch(&pda::kyc_user(user))
+    }
+
+    /// Whether `user` is verified and not expired at `now`
+    pub fn is_verified(&self, user: &Pubkey, now: i64) -> Result<bool, ClientError> {
+        Ok(self.kyc_user(user)?.is_some_and(|kyc_user| is_kyc_verified_at(&kyc_user, now)))
+    }
+
+    /// Register `user` with the KYC oracle, pending verification
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_user(
+        &self,
+        authority: &Pubkey,
+        signer: KycSigner,
+        user: &Pubkey,
+        blz: String,
+        iban_hash: [u8; 32],
+        country_code: String,
+        verification_provider: String,
+    ) -> Instruction {
+        instructions::register_kyc_user(authority, signer, user, blz, iban_hash, country_code, verification_provider)
+    }
+
+    /// Verify a registered user at `verification_level` for `expiry_days`
+    pub fn verify_user(
+        &self,
+        authority: &Pubkey,
+        signer: KycSigner,
+        user: &Pubkey,
+        verification_level: u8,
+        expiry_days: i64,
+    ) -> Result<Instruction, ClientError> {
+        Ok(instructions::update_kyc_status(
+            authority,
+            signer,
+            user,
+            KycStatus::Verified,
+            verification_level,
+            expiry_days,
+            self.existing(pda::compliance_history(user))?,
+        ))
+    }
+
+    /// Mint `amount` to `token_account` of `user`, signed by the issuer or another
+    /// holder of the MINTER role
+    pub fn mint_to(
+        &self,
+        minter: &Pubkey,
+        user: &Pubkey,
+        token_account: &Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let mint_info = self.mint_info()?;
+        let kyc_user: KycUser = self.fetch_required(&pda::kyc_user(user))?;
+        let delegated = *minter != mint_info.issuer;
+        let options = MintTokensOptions {
+            approval_set: configured(mint_info.approval_set),
+            limit_config: self.existing(pda::limit_config(&self.mint))?,
+            role_assignment: delegated.then(|| pda::role_assignment(&self.mint, minter)),
+            minter_allowance: delegated.then(|| pda::minter_allowance(&self.mint, minter)),
+            issuance_policy: configured(mint_info.issuance_policy),
+        };
+        Ok(instructions::mint_tokens(
+            minter,
+            &self.mint,
+            token_account,
+            user,
+            &kyc_user.country_code,
+            amount,
+            operation_id,
+            options,
+        ))
+    }
+
+    /// Mint `amount` into the treasury vault, signed by the issuer
+    pub fn mint_to_treasury(&self, issuer: &Pubkey, amount: u64, operation_id: [u8; 16]) -> Result<Instruction, ClientError> {
+        let mint_info = self.mint_info()?;
+        let treasury_vault: TreasuryVault = self.fetch_required(&pda::treasury_vault(&self.mint))?;
+        Ok(instructions::mint_to_treasury(
+            issuer,
+            &self.mint,
+            &treasury_vault.vault,
+            amount,
+            operation_id,
+            configured(mint_info.approval_set),
+            configured(mint_info.issuance_policy),
+        ))
+    }
+
+    /// Distribute `amount` from the treasury vault to `token_account` of `user`,
+    /// signed by the issuer or another holder of the DISTRIBUTOR role
+    pub fn distribute(
+        &self,
+        distributor: &Pubkey,
+        user: &Pubkey,
+        token_account: &Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let mint_info = self.mint_info()?;
+        let kyc_user: KycUser = self.fetch_required(&pda::kyc_user(user))?;
+        let treasury_vault_address = pda::treasury_vault(&self.mint);
+        let treasury_vault: TreasuryVault = self.fetch_required(&treasury_vault_address)?;
+        let options = DistributeOptions {
+            limit_config: self.existing(pda::limit_config(&self.mint))?,
+            role_assignment: self.delegated_role(distributor, mint_info.issuer),
+        };
+        let mut instruction = instructions::distribute_from_treasury(
+            distributor,
+            &self.mint,
+            &treasury_vault.vault,
+            token_account,
+            user,
+            &kyc_user.country_code,
+            amount,
+            operation_id,
+            options,
+        );
+        instruction.accounts.extend(instructions::transfer_hook_accounts(
+            &self.mint,
+            &treasury_vault.vault,
+            &treasury_vault_address,
+            token_account,
+            user,
+        ));
+        Ok(instruction)
+    }
+
+    /// Burn `amount` from the owner's `token_account` to redeem it for fiat
+    pub fn redeem(
+        &self,
+        owner: &Pubkey,
+        token_account: &Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let kyc_user: KycUser = self.fetch_required(&pda::kyc_user(owner))?;
+        let options = BurnTokensOptions {
+            limit_config: self.existing(pda::limit_config(&self.mint))?,
+        };
+        Ok(instructions::burn_tokens(
+            owner,
+            &self.mint,
+            token_account,
+            &kyc_user.country_code,
+            amount,
+            operation_id,
+            options,
+        ))
+    }
+
+    /// Freeze `token_account`, signed by the freeze authority or another holder of
+    /// the FREEZER role
+    pub fn freeze(
+        &self,
+        authority: &Pubkey,
+        token_account: &Pubkey,
+        reason_code: u8,
+        legal_reference: String,
+        frozen_amount: u64,
+        expires_at: i64,
+    ) -> Result<Instruction, ClientError> {
+        let mint_info = self.mint_info()?;
+        let owner = self.token_account_owner(token_account)?;
+        Ok(instructions::freeze_account(
+            authority,
+            &self.mint,
+            token_account,
+            reason_code,
+            legal_reference,
+            frozen_amount,
+            expires_at,
+            self.delegated_role(authority, mint_info.freeze_authority),
+            self.existing(pda::compliance_history(&owner))?,
+        ))
+    }
+
+    /// Thaw `token_account`, signed by the freeze authority or another holder of the
+    /// FREEZER role
+    pub fn thaw(&self, authority: &Pubkey, token_account: &Pubkey) -> Result<Instruction, ClientError> {
+        let mint_info = self.mint_info()?;
+        let owner = self.token_account_owner(token_account)?;
+        Ok(instructions::thaw_account(
+            authority,
+            &self.mint,
+            token_account,
+            self.existing(pda::freeze_record(token_account))?,
+            self.delegated_role(authority, mint_info.freeze_authority),
+            self.existing(pda::compliance_history(&owner))?,
+        ))
+    }
+
+    /// Thaw the new token account of a KYC-verified holder; `caller` may be anyone
+    pub fn activate_token_account(&self, caller: &Pubkey, token_account: &Pubkey) -> Result<Instruction, ClientError> {
+        let owner = self.token_account_owner(token_account)?;
+        Ok(instructions::activate_token_account(caller, &self.mint, token_account, &owner))
+    }
+
+    /// Freeze the token account of a holder whose KYC expired; `caller` may be anyone
+    pub fn enforce_kyc_expiry(&self, caller: &Pubkey, token_account: &Pubkey) -> Result<Instruction, ClientError> {
+        let owner = self.token_account_owner(token_account)?;
+        Ok(instructions::enforce_kyc_expiry(
+            caller,
+            &self.mint,
+            token_account,
+            &owner,
+            self.existing(pda::compliance_history(&owner))?,
+        ))
+    }
+
+    /// Open the compliance history of the registered `user`, paid by `payer`
+    pub fn open_compliance_history(&self, payer: &Pubkey, user: &Pubkey) -> Instruction {
+        instructions::open_compliance_history(payer, user)
+    }
+
+    /// Seize `amount` from `from_account` into `to_account`, signed by the permanent
+    /// delegate or another holder of the SEIZER role
+    pub fn seize(
+        &self,
+        authority: &Pubkey,
+        from_account: &Pubkey,
+        to_account: &Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let mint_info = self.mint_info()?;
+        let from_owner = self.token_account_owner(from_account)?;
+        let mut instruction = instructions::seize_tokens(
+            authority,
+            &self.mint,
+            from_account,
+            to_account,
+            amount,
+            operation_id,
+            self.delegated_role(authority, mint_info.permanent_delegate),
+            self.existing(pda::compliance_history(&from_owner))?,
+        );
+        instruction.accounts.extend(instructions::transfer_hook_accounts(
+            &self.mint,
+            from_account,
+            &from_owner,
+            to_account,
+            &self.token_account_owner(to_account)?,
+        ));
+        Ok(instruction)
+    }
+
+    /// Publish a reserve proof with its breakdown by asset category, signed by the
+    /// issuer or another holder of the RESERVE_PUBLISHER role. `price_updates` are
+    /// the posted Pyth updates the mint's reserve valuation needs, if it has one.
+    pub fn publish_reserve_proof(
+        &self,
+        publisher: &Pubkey,
+        merkle_root: [u8; 32],
+        ipfs_cid: String,
+        reserve_total_eur: u64,
+        composition: Vec<ReserveAsset>,
+        price_updates: &[Pubkey],
+    ) -> Result<Instruction, ClientError> {
+        let mint_info = self.mint_info()?;
+        let options = ReserveProofOptions {
+            role_assignment: self.delegated_role(publisher, mint_info.issuer),
+            valuation_config: configured(mint_info.reserve_valuation),
+        };
+        let mut instruction = instructions::update_reserve_proof(
+            publisher,
+            &self.mint,
+            mint_info.reserve_epoch_count,
+            merkle_root,
+            ipfs_cid,
+            reserve_total_eur,
+            composition,
+            options,
+        );
+        instruction.accounts.extend(instructions::price_update_accounts(price_updates));
+        Ok(instruction)
+    }
+
+    // Role assignment of a signer acting for `holder`, the key configured for the role
+    fn delegated_role(&self, signer: &Pubkey, holder: Pubkey) -> Option<Pubkey> {
+        (*signer != holder).then(|| pda::role_assignment(&self.mint, signer))
+    }
+}
+
+// A key recorded in `MintInfo`, the default key meaning none is configured
+fn configured(key: Pubkey) -> Option<Pubkey> {
+    (key != Pubkey::default()).then_some(key)
+}
@@ -0,0 +1,1346 @@
+//! Builders for the MiCA EUR program instructions.
+//!
+//! Each builder derives the program accounts of its instruction; optional
+//! accounts the program expects only in some configurations are passed in by the
+//! caller, or resolved from chain state by `ComplianceClient`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    instruction::{AccountMeta, Instruction},
+    system_program, sysvar,
+};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, ID as ASSOCIATED_TOKEN_ID};
+use anchor_spl::token_2022::ID as TOKEN_2022_ID;
+use mica_eur::{AccountClass, BondPriceFeed, KycStatus, OperationKind, RelayerStatus, ReserveAsset};
+
+use crate::pda;
+
+/// How a key signing a KYC instruction is authorized
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KycSigner {
+    /// The KYC oracle authority itself
+    OracleAuthority,
+    /// A registered KYC provider
+    Provider,
+    /// A holder of the KYC_ADMIN role
+    Admin,
+}
+
+impl KycSigner {
+    fn kyc_provider(self, authority: &Pubkey) -> Option<Pubkey> {
+        (self == KycSigner::Provider).then(|| pda::kyc_provider(authority))
+    }
+
+    fn role_assignment(self, authority: &Pubkey) -> Option<Pubkey> {
+        (self == KycSigner::Admin).then(|| pda::role_assignment(&pda::kyc_oracle_state(), authority))
+    }
+}
+
+/// Optional accounts of `mint_tokens`, `None` where they do not apply
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MintTokensOptions {
+    pub approval_set: Option<Pubkey>,     // Once the mint has an approval set
+    pub limit_config: Option<Pubkey>,     // Once the mint has tier limits
+    pub role_assignment: Option<Pubkey>,  // When a MINTER other than the issuer signs
+    pub minter_allowance: Option<Pubkey>, // When a MINTER other than the issuer signs
+    pub issuance_policy: Option<Pubkey>,  // Once the mint has an issuance schedule
+}
+
+/// Optional accounts of `distribute_from_treasury`, `None` where they do not apply
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DistributeOptions {
+    pub limit_config: Option<Pubkey>,     // Once the mint has tier limits
+    pub role_assignment: Option<Pubkey>,  // When a DISTRIBUTOR other than the issuer signs
+}
+
+/// Optional accounts of `claim_mint_voucher`, `None` where they do not apply
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClaimVoucherOptions {
+    pub limit_config: Option<Pubkey>,     // Once the mint has tier limits
+    pub issuance_policy: Option<Pubkey>,  // Once the mint has an issuance schedule
+}
+
+/// Optional accounts of `update_reserve_proof`, `None` where they do not apply
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReserveProofOptions {
+    pub role_assignment: Option<Pubkey>,  // When a RESERVE_PUBLISHER other than the issuer signs
+    pub valuation_config: Option<Pubkey>, // Once the mint has a reserve valuation
+}
+
+/// Optional accounts of `burn_tokens`, `None` where they do not apply
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BurnTokensOptions {
+    pub limit_config: Option<Pubkey>,     // Once the mint has tier limits
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: mica_eur::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Register `user` with the KYC oracle
+pub fn register_kyc_user(
+    authority: &Pubkey,
+    signer: KycSigner,
+    user: &Pubkey,
+    blz: String,
+    iban_hash: [u8; 32],
+    country_code: String,
+    verification_provider: String,
+) -> Instruction {
+    let accounts = mica_eur::accounts::RegisterKycUser {
+        authority: *authority,
+        kyc_oracle_state: pda::kyc_oracle_state(),
+        user: *user,
+        kyc_user: pda::kyc_user(user),
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+        kyc_provider: signer.kyc_provider(authority),
+        role_assignment: signer.role_assignment(authority),
+        country_policy: pda::country_policy(&country_code.to_ascii_uppercase()),
+        iban_registry: pda::iban_registry(&iban_hash),
+    };
+    let data = mica_eur::instruction::RegisterKycUser {
+        blz,
+        iban_hash,
+        country_code,
+        verification_provider,
+    };
+    instruction(accounts, data)
+}
+
+/// Set the KYC status of `user`; verification lapses after `expiry_days`. Pass the
+/// user's compliance history once it is opened.
+pub fn update_kyc_status(
+    authority: &Pubkey,
+    signer: KycSigner,
+    user: &Pubkey,
+    status: KycStatus,
+    verification_level: u8,
+    expiry_days: i64,
+    compliance_history: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::UpdateKycStatus {
+        authority: *authority,
+        kyc_oracle_state: pda::kyc_oracle_state(),
+        kyc_user: pda::kyc_user(user),
+        kyc_provider: signer.kyc_provider(authority),
+        role_assignment: signer.role_assignment(authority),
+        compliance_history,
+    };
+    let data = mica_eur::instruction::UpdateKycStatus {
+        status,
+        verification_level,
+        expiry_days,
+    };
+    instruction(accounts, data)
+}
+
+/// Move `user` to `new_class`, linking the KYB record `kyb_hash`. `token_account`
+/// is the user's token account and its mint, required when the class needs
+/// incoming memos.
+pub fn reclassify_account(
+    authority: &Pubkey,
+    signer: KycSigner,
+    user: &Pubkey,
+    new_class: AccountClass,
+    kyb_hash: [u8; 32],
+    token_account: Option<(Pubkey, Pubkey)>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::ReclassifyAccount {
+        authority: *authority,
+        kyc_oracle_state: pda::kyc_oracle_state(),
+        kyc_user: pda::kyc_user(user),
+        token_account: token_account.map(|(token_account, _)| token_account),
+        mint_info: token_account.map(|(_, mint)| pda::mint_info(&mint)),
+        kyc_provider: signer.kyc_provider(authority),
+        role_assignment: signer.role_assignment(authority),
+    };
+    instruction(accounts, mica_eur::instruction::ReclassifyAccount { new_class, kyb_hash })
+}
+
+/// Register a sanctions screening provider signing with `signing_key`
+pub fn register_screening_provider(authority: &Pubkey, signing_key: &Pubkey, name: String) -> Instruction {
+    let accounts = mica_eur::accounts::RegisterScreeningProvider {
+        authority: *authority,
+        kyc_oracle_state: pda::kyc_oracle_state(),
+        screening_provider: pda::screening_provider(signing_key),
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::RegisterScreeningProvider { signing_key: *signing_key, name })
+}
+
+/// Record the screening of `user` attested by the provider of `signing_key`. Must
+/// follow the Ed25519 instruction verifying the provider's signature of
+/// `screening_attestation_message`.
+pub fn submit_screening_attestation(
+    submitter: &Pubkey,
+    signing_key: &Pubkey,
+    user: &Pubkey,
+    screened_at: i64,
+    result_hash: [u8; 32],
+) -> Instruction {
+    let accounts = mica_eur::accounts::SubmitScreeningAttestation {
+        submitter: *submitter,
+        screening_provider: pda::screening_provider(signing_key),
+        kyc_user: pda::kyc_user(user),
+        instructions: sysvar::instructions::ID,
+    };
+    instruction(accounts, mica_eur::instruction::SubmitScreeningAttestation { screened_at, result_hash })
+}
+
+/// Mint `amount` to `token_account` of the verified `user` from `country_code`
+#[allow(clippy::too_many_arguments)]
+pub fn mint_tokens(
+    minter: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    user: &Pubkey,
+    country_code: &str,
+    amount: u64,
+    operation_id: [u8; 16],
+    options: MintTokensOptions,
+) -> Instruction {
+    let accounts = mica_eur::accounts::MintTokens {
+        issuer: *minter,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        token_account: *token_account,
+        kyc_user: pda::kyc_user(user),
+        token_program: TOKEN_2022_ID,
+        operation_receipt: pda::operation_receipt(mint, OperationKind::Mint, minter, &operation_id),
+        system_program: system_program::ID,
+        blacklist_entry: pda::blacklist_entry(user),
+        approval_set: options.approval_set,
+        user_limits: pda::user_limits(user),
+        limit_config: options.limit_config,
+        invocation_guard: pda::invocation_guard(mint),
+        role_assignment: options.role_assignment,
+        minter_allowance: options.minter_allowance,
+        issuance_policy: options.issuance_policy,
+        country_policy: pda::country_policy(country_code),
+        freeze_record: pda::freeze_record(token_account),
+    };
+    instruction(accounts, mica_eur::instruction::MintTokens { amount, operation_id })
+}
+
+/// Set up `vault`, a token account owned by the treasury vault PDA, as the
+/// treasury of `mint`
+pub fn configure_treasury_vault(issuer: &Pubkey, mint: &Pubkey, vault: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::ConfigureTreasuryVault {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        treasury_vault: pda::treasury_vault(mint),
+        vault: *vault,
+        token_program: TOKEN_2022_ID,
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::ConfigureTreasuryVault {})
+}
+
+/// Mint `amount` into the treasury `vault` of `mint`
+pub fn mint_to_treasury(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    amount: u64,
+    operation_id: [u8; 16],
+    approval_set: Option<Pubkey>,
+    issuance_policy: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::MintToTreasury {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        treasury_vault: pda::treasury_vault(mint),
+        vault: *vault,
+        operation_receipt: pda::operation_receipt(mint, OperationKind::TreasuryMint, issuer, &operation_id),
+        token_program: TOKEN_2022_ID,
+        system_program: system_program::ID,
+        invocation_guard: pda::invocation_guard(mint),
+        approval_set,
+        issuance_policy,
+    };
+    instruction(accounts, mica_eur::instruction::MintToTreasury { amount, operation_id })
+}
+
+/// Distribute `amount` from the treasury `vault` to `token_account` of the verified
+/// `user` from `country_code`. The transfer runs through the transfer hook: append
+/// `transfer_hook_accounts`.
+#[allow(clippy::too_many_arguments)]
+pub fn distribute_from_treasury(
+    distributor: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    token_account: &Pubkey,
+    user: &Pubkey,
+    country_code: &str,
+    amount: u64,
+    operation_id: [u8; 16],
+    options: DistributeOptions,
+) -> Instruction {
+    let accounts = mica_eur::accounts::DistributeFromTreasury {
+        distributor: *distributor,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        treasury_vault: pda::treasury_vault(mint),
+        vault: *vault,
+        token_account: *token_account,
+        kyc_user: pda::kyc_user(user),
+        blacklist_entry: pda::blacklist_entry(user),
+        country_policy: pda::country_policy(country_code),
+        user_limits: pda::user_limits(user),
+        limit_config: options.limit_config,
+        operation_receipt: pda::operation_receipt(mint, OperationKind::Distribution, distributor, &operation_id),
+        token_program: TOKEN_2022_ID,
+        system_program: system_program::ID,
+        invocation_guard: pda::invocation_guard(mint),
+        role_assignment: options.role_assignment,
+        freeze_record: pda::freeze_record(token_account),
+    };
+    instruction(accounts, mica_eur::instruction::DistributeFromTreasury { amount, operation_id })
+}
+
+/// Record the SEPA deposit hashed in `reference_hash` as a voucher for a mint of
+/// `amount` to `user`
+pub fn create_mint_voucher(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    user: &Pubkey,
+    reference_hash: [u8; 32],
+    amount: u64,
+    approval_set: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::CreateMintVoucher {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        voucher: pda::mint_voucher(mint, &reference_hash),
+        approval_set,
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::CreateMintVoucher { user: *user, reference_hash, amount })
+}
+
+/// Claim the voucher of the deposit hashed in `reference_hash`, minting it to the
+/// user's `token_account`
+pub fn claim_mint_voucher(
+    user: &Pubkey,
+    mint: &Pubkey,
+    reference_hash: &[u8; 32],
+    token_account: &Pubkey,
+    country_code: &str,
+    options: ClaimVoucherOptions,
+) -> Instruction {
+    let accounts = mica_eur::accounts::ClaimMintVoucher {
+        user: *user,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        voucher: pda::mint_voucher(mint, reference_hash),
+        token_account: *token_account,
+        kyc_user: pda::kyc_user(user),
+        blacklist_entry: pda::blacklist_entry(user),
+        country_policy: pda::country_policy(country_code),
+        user_limits: pda::user_limits(user),
+        limit_config: options.limit_config,
+        token_program: TOKEN_2022_ID,
+        system_program: system_program::ID,
+        invocation_guard: pda::invocation_guard(mint),
+        issuance_policy: options.issuance_policy,
+        freeze_record: pda::freeze_record(token_account),
+    };
+    instruction(accounts, mica_eur::instruction::ClaimMintVoucher {})
+}
+
+/// Record the SEPA payout whose bank reference hashes to `reference_hash` for the
+/// redemption `holder` signed with `operation_id`
+pub fn record_payout_reference(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    holder: &Pubkey,
+    operation_id: [u8; 16],
+    reference_hash: [u8; 32],
+) -> Instruction {
+    let accounts = mica_eur::accounts::RecordPayoutReference {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        operation_receipt: pda::operation_receipt(mint, OperationKind::Redemption, holder, &operation_id),
+        payout_reference: pda::payout_reference(mint, holder, &operation_id),
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::RecordPayoutReference { operation_id, reference_hash })
+}
+
+/// Burn `amount` from the owner's `token_account` to redeem it for fiat
+pub fn burn_tokens(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    country_code: &str,
+    amount: u64,
+    operation_id: [u8; 16],
+    options: BurnTokensOptions,
+) -> Instruction {
+    let accounts = mica_eur::accounts::BurnTokens {
+        owner: *owner,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        token_account: *token_account,
+        token_program: TOKEN_2022_ID,
+        operation_receipt: pda::operation_receipt(mint, OperationKind::Redemption, owner, &operation_id),
+        system_program: system_program::ID,
+        blacklist_entry: pda::blacklist_entry(owner),
+        kyc_user: pda::kyc_user(owner),
+        user_limits: pda::user_limits(owner),
+        limit_config: options.limit_config,
+        freeze_record: pda::freeze_record(token_account),
+        country_policy: pda::country_policy(country_code),
+    };
+    instruction(accounts, mica_eur::instruction::BurnTokens { amount, operation_id })
+}
+
+/// Create the EUR mint with its Token-2022 extensions; `mint` signs as the new account.
+/// `decimals` must be one of `SUPPORTED_EUR_DECIMALS`.
+pub fn initialize_euro_mint(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    freeze_authority: &Pubkey,
+    permanent_delegate: &Pubkey,
+    whitepaper_uri: String,
+    decimals: u8,
+) -> Instruction {
+    let accounts = mica_eur::accounts::InitializeEuroMint {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        freeze_authority: *freeze_authority,
+        permanent_delegate: *permanent_delegate,
+        seizure_authority: pda::seizure_authority(mint),
+        system_program: system_program::ID,
+        token_program: TOKEN_2022_ID,
+        rent: sysvar::rent::ID,
+        invocation_guard: pda::invocation_guard(mint),
+    };
+    instruction(accounts, mica_eur::instruction::InitializeEuroMint { whitepaper_uri, decimals })
+}
+
+/// Wind `mint` down: minting stops while holders can still redeem. Mints under a
+/// timelock queue `TimelockAction::DeactivateMint` instead.
+pub fn deactivate_mint(issuer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::SetMintActive {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+    };
+    instruction(accounts, mica_eur::instruction::DeactivateMint {})
+}
+
+/// Resume minting on a deactivated `mint`. Mints under a timelock queue
+/// `TimelockAction::ReactivateMint` instead.
+pub fn reactivate_mint(issuer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::SetMintActive {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+    };
+    instruction(accounts, mica_eur::instruction::ReactivateMint {})
+}
+
+/// Start the orderly redemption of `mint`: minting stops and holders may return
+/// tokens to the issuer's `redemption_account` until `redemption_deadline`
+pub fn initiate_wind_down(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    redemption_account: &Pubkey,
+    redemption_deadline: i64,
+) -> Instruction {
+    let accounts = mica_eur::accounts::InitiateWindDown {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        redemption_account: *redemption_account,
+    };
+    instruction(accounts, mica_eur::instruction::InitiateWindDown { redemption_deadline })
+}
+
+/// Move the Token-2022 mint authority of `mint` from `current_authority` to its
+/// mint info PDA
+pub fn migrate_mint_authority(issuer: &Pubkey, current_authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::MigrateMintAuthority {
+        issuer: *issuer,
+        current_authority: *current_authority,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        token_program: TOKEN_2022_ID,
+    };
+    instruction(accounts, mica_eur::instruction::MigrateMintAuthority {})
+}
+
+/// Move the Token-2022 permanent delegate of `mint` from its mint info PDA to its
+/// seizure authority PDA
+pub fn migrate_permanent_delegate(issuer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::MigratePermanentDelegate {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        seizure_authority: pda::seizure_authority(mint),
+        mint: *mint,
+        token_program: TOKEN_2022_ID,
+    };
+    instruction(accounts, mica_eur::instruction::MigratePermanentDelegate {})
+}
+
+/// Open `owner`'s associated token account for `mint`, frozen until thawed or
+/// minted to, and record it as a holder account
+pub fn create_token_account(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    let token_account = get_associated_token_address_with_program_id(owner, mint, &TOKEN_2022_ID);
+    let accounts = mica_eur::accounts::CreateTokenAccount {
+        owner: *owner,
+        token_account,
+        mint: *mint,
+        mint_info: pda::mint_info(mint),
+        system_program: system_program::ID,
+        token_program: TOKEN_2022_ID,
+        rent: sysvar::rent::ID,
+        holder_record: pda::holder_record(&token_account),
+        associated_token_program: ASSOCIATED_TOKEN_ID,
+    };
+    instruction(accounts, mica_eur::instruction::CreateTokenAccount {})
+}
+
+/// Thaw `token_account` of `owner` once the owner is KYC verified, signed by
+/// `caller`, who may be anyone
+pub fn activate_token_account(caller: &Pubkey, mint: &Pubkey, token_account: &Pubkey, owner: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::ActivateTokenAccount {
+        caller: *caller,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        token_account: *token_account,
+        kyc_user: pda::kyc_user(owner),
+        blacklist_entry: pda::blacklist_entry(owner),
+        freeze_record: pda::freeze_record(token_account),
+        token_program: TOKEN_2022_ID,
+    };
+    instruction(accounts, mica_eur::instruction::ActivateTokenAccount {})
+}
+
+/// Freeze `token_account` of `owner` after the owner's KYC verification expired,
+/// signed by `caller`, who may be anyone
+pub fn enforce_kyc_expiry(
+    caller: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    owner: &Pubkey,
+    compliance_history: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::EnforceKycExpiry {
+        caller: *caller,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        token_account: *token_account,
+        kyc_user: pda::kyc_user(owner),
+        token_program: TOKEN_2022_ID,
+        compliance_history,
+    };
+    instruction(accounts, mica_eur::instruction::EnforceKycExpiry {})
+}
+
+/// Open the compliance history of the registered `user`, paid by `payer`
+pub fn open_compliance_history(payer: &Pubkey, user: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::OpenComplianceHistory {
+        payer: *payer,
+        kyc_user: pda::kyc_user(user),
+        compliance_history: pda::compliance_history(user),
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::OpenComplianceHistory {})
+}
+
+/// Close `owner`'s empty token account for `mint` and its holder record
+pub fn close_token_account(owner: &Pubkey, mint: &Pubkey, token_account: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::CloseTokenAccount {
+        owner: *owner,
+        token_account: *token_account,
+        mint: *mint,
+        mint_info: pda::mint_info(mint),
+        holder_record: pda::holder_record(token_account),
+        token_program: TOKEN_2022_ID,
+    };
+    instruction(accounts, mica_eur::instruction::CloseTokenAccount {})
+}
+
+/// Freeze `token_account` for `reason_code` under `legal_reference`. A
+/// `frozen_amount` of 0 freezes the whole balance, an `expires_at` of 0 never lapses.
+#[allow(clippy::too_many_arguments)]
+pub fn freeze_account(
+    freeze_authority: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    reason_code: u8,
+    legal_reference: String,
+    frozen_amount: u64,
+    expires_at: i64,
+    role_assignment: Option<Pubkey>,
+    compliance_history: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::FreezeAccount {
+        freeze_authority: *freeze_authority,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        token_account: *token_account,
+        token_program: TOKEN_2022_ID,
+        freeze_record: pda::freeze_record(token_account),
+        system_program: system_program::ID,
+        role_assignment,
+        compliance_history,
+    };
+    let data = mica_eur::instruction::FreezeAccount {
+        reason_code,
+        legal_reference,
+        frozen_amount,
+        expires_at,
+    };
+    instruction(accounts, data)
+}
+
+/// Thaw `token_account`, lifting its freeze record if it has one
+pub fn thaw_account(
+    freeze_authority: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    freeze_record: Option<Pubkey>,
+    role_assignment: Option<Pubkey>,
+    compliance_history: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::ThawAccount {
+        freeze_authority: *freeze_authority,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        token_account: *token_account,
+        token_program: TOKEN_2022_ID,
+        freeze_record,
+        role_assignment,
+        compliance_history,
+    };
+    instruction(accounts, mica_eur::instruction::ThawAccount {})
+}
+
+/// Extra accounts of the transfer hook for a transfer on `mint` from `source`,
+/// owned by `sender`, to `destination`, owned by `recipient`: the accounts of its
+/// extra account meta list in order, then the hook program and the list itself.
+/// Appended to instructions that move tokens by CPI.
+pub fn transfer_hook_accounts(
+    mint: &Pubkey,
+    source: &Pubkey,
+    sender: &Pubkey,
+    destination: &Pubkey,
+    recipient: &Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(mica_eur::ID, false),
+        AccountMeta::new_readonly(pda::mint_info(mint), false),
+        AccountMeta::new_readonly(pda::kyc_user(sender), false),
+        AccountMeta::new_readonly(pda::kyc_user(recipient), false),
+        AccountMeta::new_readonly(pda::blacklist_entry(sender), false),
+        AccountMeta::new_readonly(pda::blacklist_entry(recipient), false),
+        AccountMeta::new_readonly(pda::staged_policy(mint), false),
+        AccountMeta::new_readonly(pda::edd_config(mint), false),
+        AccountMeta::new_readonly(pda::limit_config(mint), false),
+        AccountMeta::new(pda::transfer_record(sender), false),
+        AccountMeta::new(pda::transfer_record(recipient), false),
+        AccountMeta::new_readonly(pda::fast_lane_institution(mint, sender), false),
+        AccountMeta::new_readonly(pda::fast_lane_institution(mint, recipient), false),
+        AccountMeta::new_readonly(pda::travel_rule_config(mint), false),
+        AccountMeta::new(pda::travel_rule_record(source, destination), false),
+        AccountMeta::new_readonly(pda::freeze_record(source), false),
+        AccountMeta::new_readonly(pda::freeze_record(destination), false),
+        AccountMeta::new_readonly(pda::approved_counterparty(mint, sender), false),
+        AccountMeta::new_readonly(pda::approved_counterparty(mint, recipient), false),
+        AccountMeta::new_readonly(pda::aml_alert_config(mint), false),
+        AccountMeta::new(pda::velocity_record(sender), false),
+        AccountMeta::new_readonly(pda::program_allowlist(mint), false),
+        AccountMeta::new_readonly(pda::program_escrow(mint, sender), false),
+        AccountMeta::new_readonly(pda::program_escrow(mint, recipient), false),
+        AccountMeta::new_readonly(pda::escrow_vault(mint), false),
+        AccountMeta::new_readonly(pda::seizure_authority(mint), false),
+        AccountMeta::new_readonly(pda::treasury_vault(mint), false),
+        AccountMeta::new_readonly(mica_eur_transfer_hook::ID, false),
+        AccountMeta::new_readonly(pda::extra_account_meta_list(mint), false),
+    ]
+}
+
+/// Let the mint's permit authority move up to `amount` from `token_account` of
+/// `owner` on their signed authorizations. `decimals` are the mint's.
+pub fn approve_permit_authority(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    anchor_spl::token_2022::spl_token_2022::instruction::approve_checked(
+        &TOKEN_2022_ID,
+        token_account,
+        mint,
+        &pda::permit_authority(mint),
+        owner,
+        &[],
+        amount,
+        decimals,
+    )
+    .expect("Token-2022 program id")
+}
+
+/// Ed25519 program instruction verifying `signature` by `signer` over `message`,
+/// with all three inline as the program requires. Precedes the instruction
+/// checking the signature, e.g. `transfer_with_authorization`.
+pub fn ed25519_signature(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    const PUBKEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = PUBKEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+
+    // One signature; u16::MAX points each offset into this instruction
+    let mut data = vec![1, 0];
+    for value in [
+        SIGNATURE_OFFSET,
+        u16::MAX,
+        PUBKEY_OFFSET,
+        u16::MAX,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+}
+
+/// Move `amount` to `recipient` on the owner's signed authorization, submitted and
+/// paid for by `relayer`. Must follow an Ed25519 instruction verifying the owner's
+/// signature over `transfer_authorization_message`, see `ed25519_signature`.
+/// `relayer_record` is required once the mint has a relayer registry. The
+/// transfer runs through the transfer hook: append `transfer_hook_accounts`. The
+/// transaction only fits with lookup tables, see `lookup_tables`.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_with_authorization(
+    relayer: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    source_token: &Pubkey,
+    destination_token: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    nonce: [u8; 32],
+    expiry: i64,
+    relayer_record: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::TransferWithAuthorization {
+        relayer: *relayer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        owner: *owner,
+        source_token: *source_token,
+        destination_token: *destination_token,
+        permit_authority: pda::permit_authority(mint),
+        permit_nonce: pda::permit_nonce(owner, &nonce),
+        instructions: sysvar::instructions::ID,
+        token_program: TOKEN_2022_ID,
+        invocation_guard: pda::invocation_guard(mint),
+        system_program: system_program::ID,
+        relayer_record,
+    };
+    instruction(
+        accounts,
+        mica_eur::instruction::TransferWithAuthorization { recipient: *recipient, amount, nonce, expiry },
+    )
+}
+
+/// Create standing order `order_id` paying `amount` from `payer_token_account` to
+/// `payee_token_account`, owned by `payee`, every `interval_seconds` from
+/// `first_execution_time` until `end_time`. The order becomes the delegate of the
+/// payer's account for the scheduled total.
+#[allow(clippy::too_many_arguments)]
+pub fn create_standing_order(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    payer_token_account: &Pubkey,
+    payee_token_account: &Pubkey,
+    payee: &Pubkey,
+    order_id: u64,
+    amount: u64,
+    interval_seconds: i64,
+    first_execution_time: i64,
+    end_time: i64,
+) -> Instruction {
+    let accounts = mica_eur::accounts::CreateStandingOrder {
+        payer: *payer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        payer_kyc_user: pda::kyc_user(payer),
+        payee_kyc_user: pda::kyc_user(payee),
+        payer_token_account: *payer_token_account,
+        payee_token_account: *payee_token_account,
+        standing_order: pda::standing_order(payer, order_id),
+        token_program: TOKEN_2022_ID,
+        system_program: system_program::ID,
+    };
+    instruction(
+        accounts,
+        mica_eur::instruction::CreateStandingOrder { order_id, amount, interval_seconds, first_execution_time, end_time },
+    )
+}
+
+fn manage_standing_order(payer: &Pubkey, order_id: u64, data: impl InstructionData) -> Instruction {
+    let accounts = mica_eur::accounts::ManageStandingOrder {
+        payer: *payer,
+        standing_order: pda::standing_order(payer, order_id),
+    };
+    instruction(accounts, data)
+}
+
+/// Pause standing order `order_id` of `payer`
+pub fn pause_standing_order(payer: &Pubkey, order_id: u64) -> Instruction {
+    manage_standing_order(payer, order_id, mica_eur::instruction::PauseStandingOrder {})
+}
+
+/// Resume standing order `order_id` of `payer`
+pub fn resume_standing_order(payer: &Pubkey, order_id: u64) -> Instruction {
+    manage_standing_order(payer, order_id, mica_eur::instruction::ResumeStandingOrder {})
+}
+
+/// Skip the next execution of standing order `order_id` of `payer`
+pub fn skip_standing_order(payer: &Pubkey, order_id: u64) -> Instruction {
+    manage_standing_order(payer, order_id, mica_eur::instruction::SkipStandingOrder {})
+}
+
+/// Cancel standing order `order_id` of `payer`, revoking its delegation
+pub fn cancel_standing_order(payer: &Pubkey, order_id: u64, payer_token_account: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::CancelStandingOrder {
+        payer: *payer,
+        standing_order: pda::standing_order(payer, order_id),
+        payer_token_account: *payer_token_account,
+        token_program: TOKEN_2022_ID,
+    };
+    instruction(accounts, mica_eur::instruction::CancelStandingOrder {})
+}
+
+/// Execute the due standing order `order_id` of `payer` as `keeper`. The
+/// transfer runs through the transfer hook: append `transfer_hook_accounts`.
+pub fn execute_standing_order(
+    keeper: &Pubkey,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    order_id: u64,
+    payer_token_account: &Pubkey,
+    payee: &Pubkey,
+    payee_token_account: &Pubkey,
+) -> Instruction {
+    let accounts = mica_eur::accounts::ExecuteStandingOrder {
+        keeper: *keeper,
+        standing_order: pda::standing_order(payer, order_id),
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        payer_kyc_user: pda::kyc_user(payer),
+        payee_kyc_user: pda::kyc_user(payee),
+        payer_token_account: *payer_token_account,
+        payee_token_account: *payee_token_account,
+        token_program: TOKEN_2022_ID,
+        invocation_guard: pda::invocation_guard(mint),
+    };
+    instruction(accounts, mica_eur::instruction::ExecuteStandingOrder {})
+}
+
+/// Set the stake relayers of `mint` must put up
+pub fn configure_relayer_registry(issuer: &Pubkey, mint: &Pubkey, min_stake: u64) -> Instruction {
+    let accounts = mica_eur::accounts::ConfigureRelayerRegistry {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        relayer_registry: pda::relayer_registry(mint),
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::ConfigureRelayerRegistry { min_stake })
+}
+
+/// Register `relayer` for `mint`, staking `stake` lamports
+pub fn register_relayer(relayer: &Pubkey, mint: &Pubkey, stake: u64) -> Instruction {
+    let accounts = mica_eur::accounts::RegisterRelayer {
+        relayer: *relayer,
+        relayer_registry: pda::relayer_registry(mint),
+        relayer_record: pda::relayer(mint, relayer),
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::RegisterRelayer { stake })
+}
+
+/// Suspend or reinstate `relayer`
+pub fn set_relayer_status(issuer: &Pubkey, mint: &Pubkey, relayer: &Pubkey, status: RelayerStatus) -> Instruction {
+    let accounts = mica_eur::accounts::SetRelayerStatus {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        relayer_registry: pda::relayer_registry(mint),
+        relayer_record: pda::relayer(mint, relayer),
+    };
+    instruction(accounts, mica_eur::instruction::SetRelayerStatus { status })
+}
+
+/// Deregister `relayer`, returning its stake
+pub fn deregister_relayer(relayer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::DeregisterRelayer {
+        relayer: *relayer,
+        relayer_registry: pda::relayer_registry(mint),
+        relayer_record: pda::relayer(mint, relayer),
+    };
+    instruction(accounts, mica_eur::instruction::DeregisterRelayer {})
+}
+
+/// Move `amount` from `from_account` to `to_account` as the permanent delegate.
+/// The transfer runs through the transfer hook: append `transfer_hook_accounts`.
+#[allow(clippy::too_many_arguments)]
+pub fn seize_tokens(
+    permanent_delegate: &Pubkey,
+    mint: &Pubkey,
+    from_account: &Pubkey,
+    to_account: &Pubkey,
+    amount: u64,
+    operation_id: [u8; 16],
+    role_assignment: Option<Pubkey>,
+    compliance_history: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::SeizeTokens {
+        permanent_delegate: *permanent_delegate,
+        mint_info: pda::mint_info(mint),
+        seizure_authority: pda::seizure_authority(mint),
+        mint: *mint,
+        from_account: *from_account,
+        to_account: *to_account,
+        token_program: TOKEN_2022_ID,
+        operation_receipt: pda::operation_receipt(mint, OperationKind::Seizure, permanent_delegate, &operation_id),
+        system_program: system_program::ID,
+        role_assignment,
+        compliance_history,
+    };
+    instruction(accounts, mica_eur::instruction::SeizeTokens { amount, operation_id })
+}
+
+/// Publish a reserve proof: the root over the deposit leaves, where the full
+/// tree is pinned, the reserve total and its breakdown by asset category.
+/// `epoch` is the mint's `reserve_epoch_count`, the proof is archived under it.
+/// With a valuation config, extend it with `price_update_accounts`.
+#[allow(clippy::too_many_arguments)]
+pub fn update_reserve_proof(
+    publisher: &Pubkey,
+    mint: &Pubkey,
+    epoch: u64,
+    merkle_root: [u8; 32],
+    ipfs_cid: String,
+    reserve_total_eur: u64,
+    composition: Vec<ReserveAsset>,
+    options: ReserveProofOptions,
+) -> Instruction {
+    let accounts = mica_eur::accounts::UpdateReserveProof {
+        issuer: *publisher,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        role_assignment: options.role_assignment,
+        reserve_composition: pda::reserve_composition(mint),
+        reserve_proof_epoch: pda::reserve_proof_epoch(mint, epoch),
+        valuation_config: options.valuation_config,
+        system_program: system_program::ID,
+    };
+    let data = mica_eur::instruction::UpdateReserveProof {
+        merkle_root,
+        ipfs_cid,
+        reserve_total_eur,
+        composition,
+    };
+    instruction(accounts, data)
+}
+
+/// Remaining accounts of a valued `update_reserve_proof`: the posted Pyth price
+/// updates, EUR/USD first and then one per bond position in composition order
+pub fn price_update_accounts(price_updates: &[Pubkey]) -> Vec<AccountMeta> {
+    price_updates.iter().map(|update| AccountMeta::new_readonly(*update, false)).collect()
+}
+
+/// Check reserve proofs against Pyth prices of EUR/USD and the listed bonds
+pub fn configure_reserve_valuation(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    eur_usd_feed: [u8; 32],
+    bond_feeds: Vec<BondPriceFeed>,
+    tolerance_bps: u16,
+    max_price_age: i64,
+) -> Instruction {
+    let accounts = mica_eur::accounts::ConfigureReserveValuation {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        valuation_config: pda::reserve_valuation(mint),
+        system_program: system_program::ID,
+    };
+    let data = mica_eur::instruction::ConfigureReserveValuation {
+        eur_usd_feed,
+        bond_feeds,
+        tolerance_bps,
+        max_price_age,
+    };
+    instruction(accounts, data)
+}
+
+/// Block minting while the latest reserve proof is older than `max_reserve_age_seconds`,
+/// 0 to never block
+pub fn set_max_reserve_age(issuer: &Pubkey, mint: &Pubkey, max_reserve_age_seconds: i64) -> Instruction {
+    let accounts = mica_eur::accounts::SetMaxReserveAge {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+    };
+    instruction(accounts, mica_eur::instruction::SetMaxReserveAge { max_reserve_age_seconds })
+}
+
+/// Prove a deposit leaf is in the reserve proof archived under `epoch`
+pub fn verify_archived_reserve_inclusion(mint: &Pubkey, epoch: u64, leaf: [u8; 32], proof: Vec<[u8; 32]>) -> Instruction {
+    let accounts = mica_eur::accounts::VerifyArchivedReserveInclusion {
+        reserve_proof_epoch: pda::reserve_proof_epoch(mint, epoch),
+    };
+    instruction(accounts, mica_eur::instruction::VerifyArchivedReserveInclusion { epoch, leaf, proof })
+}
+
+/// Grant a regulator read access to the mint's audit exports, 0 for no expiry
+pub fn grant_regulator_access(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    regulator: &Pubkey,
+    authority_id: String,
+    expires_at: i64,
+) -> Instruction {
+    let accounts = mica_eur::accounts::GrantRegulatorAccess {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        regulator_access: pda::regulator_access(mint, regulator),
+        system_program: system_program::ID,
+    };
+    let data = mica_eur::instruction::GrantRegulatorAccess {
+        regulator: *regulator,
+        authority_id,
+        expires_at,
+    };
+    instruction(accounts, data)
+}
+
+/// Revoke a regulator's read access
+pub fn revoke_regulator_access(issuer: &Pubkey, mint: &Pubkey, regulator: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::RevokeRegulatorAccess {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        regulator_access: pda::regulator_access(mint, regulator),
+    };
+    instruction(accounts, mica_eur::instruction::RevokeRegulatorAccess {})
+}
+
+/// Export the digest of the program events in [from, to) for a regulator, built
+/// with `audit_event_hash` and `calculate_merkle_root`
+pub fn export_audit_window(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    regulator: &Pubkey,
+    from: i64,
+    to: i64,
+    event_count: u64,
+    digest: [u8; 32],
+) -> Instruction {
+    let accounts = mica_eur::accounts::ExportAuditWindow {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        regulator_access: pda::regulator_access(mint, regulator),
+    };
+    instruction(accounts, mica_eur::instruction::ExportAuditWindow { from, to, event_count, digest })
+}
+
+/// Allow the PDAs of `program` to hold and move tokens of `mint`
+pub fn allowlist_program(issuer: &Pubkey, mint: &Pubkey, program: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::AllowlistProgram {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        program_allowlist: pda::program_allowlist(mint),
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::AllowlistProgram { program: *program })
+}
+
+/// Remove `program` from the allowlist of `mint`
+pub fn delist_program(issuer: &Pubkey, mint: &Pubkey, program: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::DelistProgram {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        program_allowlist: pda::program_allowlist(mint),
+    };
+    instruction(accounts, mica_eur::instruction::DelistProgram { program: *program })
+}
+
+/// Register `authority`, derived from an allowlisted `program` with `seeds` (bump
+/// included), as the owner of escrow token accounts
+pub fn register_program_escrow(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    program: &Pubkey,
+    authority: &Pubkey,
+    seeds: &[&[u8]],
+) -> Instruction {
+    let accounts = mica_eur::accounts::RegisterProgramEscrow {
+        payer: *payer,
+        mint_info: pda::mint_info(mint),
+        program_allowlist: pda::program_allowlist(mint),
+        authority: *authority,
+        program_escrow: pda::program_escrow(mint, authority),
+        system_program: system_program::ID,
+    };
+    let seeds = seeds.iter().map(|seed| seed.to_vec()).collect();
+    instruction(accounts, mica_eur::instruction::RegisterProgramEscrow { program: *program, seeds })
+}
+
+/// Check a move of `amount` of `mint` from `source_owner` to `dest_owner` against
+/// the token's transfer rules; integrators CPI it with the same accounts
+pub fn assert_transfer_allowed(mint: &Pubkey, source_owner: &Pubkey, dest_owner: &Pubkey, amount: u64) -> Instruction {
+    let accounts = mica_eur::accounts::AssertTransferAllowed {
+        mint_info: pda::mint_info(mint),
+        sender_kyc_user: pda::kyc_user(source_owner),
+        recipient_kyc_user: pda::kyc_user(dest_owner),
+        sender_blacklist: pda::blacklist_entry(source_owner),
+        recipient_blacklist: pda::blacklist_entry(dest_owner),
+        sender_counterparty: pda::approved_counterparty(mint, source_owner),
+        recipient_counterparty: pda::approved_counterparty(mint, dest_owner),
+        edd_config: pda::edd_config(mint),
+        limit_config: pda::limit_config(mint),
+        sender_institution: pda::fast_lane_institution(mint, source_owner),
+    };
+    instruction(
+        accounts,
+        mica_eur::instruction::AssertTransferAllowed { source_owner: *source_owner, dest_owner: *dest_owner, amount },
+    )
+}
+
+/// Set up `vault`, a token account owned by `pda::escrow_vault`, to hold the
+/// commerce escrows of `mint`
+pub fn configure_escrow_vault(issuer: &Pubkey, mint: &Pubkey, vault: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::ConfigureEscrowVault {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        escrow_vault: pda::escrow_vault(mint),
+        vault: *vault,
+        token_program: TOKEN_2022_ID,
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::ConfigureEscrowVault {})
+}
+
+/// Open escrow `escrow_id` of `buyer` paying `amount` into `vault` for `seller`,
+/// owner of `seller_token_account`, until `expires_at`. `arbiter` may release or
+/// refund it, `Pubkey::default()` for none. The transfer runs through the
+/// transfer hook: append `transfer_hook_accounts`.
+#[allow(clippy::too_many_arguments)]
+pub fn open_escrow(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    buyer_token_account: &Pubkey,
+    seller: &Pubkey,
+    seller_token_account: &Pubkey,
+    escrow_id: u64,
+    amount: u64,
+    arbiter: &Pubkey,
+    terms_hash: [u8; 32],
+    expires_at: i64,
+) -> Instruction {
+    let accounts = mica_eur::accounts::OpenEscrow {
+        buyer: *buyer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        escrow_vault: pda::escrow_vault(mint),
+        vault: *vault,
+        buyer_kyc_user: pda::kyc_user(buyer),
+        seller_kyc_user: pda::kyc_user(seller),
+        buyer_token_account: *buyer_token_account,
+        seller_token_account: *seller_token_account,
+        edd_config: pda::edd_config(mint),
+        limit_config: pda::limit_config(mint),
+        escrow: pda::escrow(buyer, escrow_id),
+        token_program: TOKEN_2022_ID,
+        system_program: system_program::ID,
+        invocation_guard: pda::invocation_guard(mint),
+    };
+    instruction(
+        accounts,
+        mica_eur::instruction::OpenEscrow { escrow_id, amount, arbiter: *arbiter, terms_hash, expires_at },
+    )
+}
+
+fn resolve_escrow(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    buyer: &Pubkey,
+    escrow_id: u64,
+    destination_token_account: &Pubkey,
+    data: impl InstructionData,
+) -> Instruction {
+    let accounts = mica_eur::accounts::ResolveEscrow {
+        authority: *authority,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        escrow_vault: pda::escrow_vault(mint),
+        vault: *vault,
+        escrow: pda::escrow(buyer, escrow_id),
+        destination_token_account: *destination_token_account,
+        token_program: TOKEN_2022_ID,
+        invocation_guard: pda::invocation_guard(mint),
+    };
+    instruction(accounts, data)
+}
+
+/// Release escrow `escrow_id` of `buyer` to `seller_token_account`, signed by the
+/// buyer or the arbiter. Append `transfer_hook_accounts` for the vault -> seller
+/// transfer.
+pub fn release_escrow(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    buyer: &Pubkey,
+    escrow_id: u64,
+    seller_token_account: &Pubkey,
+) -> Instruction {
+    resolve_escrow(authority, mint, vault, buyer, escrow_id, seller_token_account, mica_eur::instruction::ReleaseEscrow {})
+}
+
+/// Refund escrow `escrow_id` of `buyer` to `buyer_token_account`, signed by the
+/// seller, the arbiter or, once expired, the buyer. Append
+/// `transfer_hook_accounts` for the vault -> buyer transfer.
+pub fn refund_escrow(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    buyer: &Pubkey,
+    escrow_id: u64,
+    buyer_token_account: &Pubkey,
+) -> Instruction {
+    resolve_escrow(authority, mint, vault, buyer, escrow_id, buyer_token_account, mica_eur::instruction::RefundEscrow {})
+}
+
+/// Request `amount` into `payee_token_account` of `payee` until `expires_at`,
+/// identified by `memo_hash`, the hash of the invoice's remittance information
+pub fn create_payment_request(
+    payee: &Pubkey,
+    mint: &Pubkey,
+    payee_token_account: &Pubkey,
+    request_id: u64,
+    amount: u64,
+    memo_hash: [u8; 32],
+    expires_at: i64,
+) -> Instruction {
+    let accounts = mica_eur::accounts::CreatePaymentRequest {
+        payee: *payee,
+        mint_info: pda::mint_info(mint),
+        payee_token_account: *payee_token_account,
+        payment_request: pda::payment_request(payee, request_id),
+        system_program: system_program::ID,
+    };
+    instruction(
+        accounts,
+        mica_eur::instruction::CreatePaymentRequest { request_id, amount, memo_hash, expires_at },
+    )
+}
+
+/// Pay request `request_id` of `payee` from `payer_token_account`. The transfer
+/// runs through the transfer hook: append `transfer_hook_accounts`.
+pub fn pay_request(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    payee: &Pubkey,
+    request_id: u64,
+    payer_token_account: &Pubkey,
+    payee_token_account: &Pubkey,
+) -> Instruction {
+    let accounts = mica_eur::accounts::PayRequest {
+        payer: *payer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        payment_request: pda::payment_request(payee, request_id),
+        payer_token_account: *payer_token_account,
+        payee_token_account: *payee_token_account,
+        token_program: TOKEN_2022_ID,
+        invocation_guard: pda::invocation_guard(mint),
+    };
+    instruction(accounts, mica_eur::instruction::PayRequest {})
+}
+
+/// Admit `wallet`, a KYC user in the supervised institution class, to the fast
+/// lane of `mint` with a daily cap of `daily_cap` token units, or change its cap
+pub fn whitelist_institution(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    lei: [u8; 20],
+    daily_cap: u64,
+) -> Instruction {
+    let accounts = mica_eur::accounts::WhitelistInstitution {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        kyc_user: pda::kyc_user(wallet),
+        institution: pda::fast_lane_institution(mint, wallet),
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::WhitelistInstitution { wallet: *wallet, lei, daily_cap })
+}
+
+/// Remove `wallet` from the fast lane of `mint`
+pub fn remove_institution(issuer: &Pubkey, mint: &Pubkey, wallet: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::RemoveInstitution {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        institution: pda::fast_lane_institution(mint, wallet),
+    };
+    instruction(accounts, mica_eur::instruction::RemoveInstitution {})
+}
+
+/// Set the amount from which transfers of `mint` must carry travel-rule data, 0
+/// to disable the requirement
+pub fn configure_travel_rule(issuer: &Pubkey, mint: &Pubkey, threshold: u64) -> Instruction {
+    let accounts = mica_eur::accounts::ConfigureTravelRule {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        travel_rule_config: pda::travel_rule_config(mint),
+        system_program: system_program::ID,
+    };
+    instruction(accounts, mica_eur::instruction::ConfigureTravelRule { threshold })
+}
+
+/// Attach hashed originator and beneficiary data to the next transfer of `amount`
+/// from `source_token`, owned by `originator`, to `destination_token`. A
+/// transfer hook program instruction.
+pub fn attach_travel_rule_info(
+    originator: &Pubkey,
+    source_token: &Pubkey,
+    destination_token: &Pubkey,
+    amount: u64,
+    originator_hash: [u8; 32],
+    beneficiary_hash: [u8; 32],
+) -> Instruction {
+    let accounts = mica_eur_transfer_hook::accounts::AttachTravelRuleInfo {
+        originator: *originator,
+        source_token: *source_token,
+        destination_token: *destination_token,
+        travel_rule_record: pda::travel_rule_record(source_token, destination_token),
+        system_program: system_program::ID,
+    };
+    let data = mica_eur_transfer_hook::instruction::AttachTravelRuleInfo { amount, originator_hash, beneficiary_hash };
+    Instruction {
+        program_id: mica_eur_transfer_hook::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
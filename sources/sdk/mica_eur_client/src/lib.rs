@@ -0,0 +1,50 @@
+//! Client for backend services talking to the MiCA EUR program.
+//!
+//! - `pda`: addresses of the program accounts
+//! - `instructions`: typed builders for the program instructions
+//! - `client`: `ComplianceClient`, which reads the on-chain state a flow depends
+//!   on and builds its instructions (register, verify, mint, redeem)
+//! - `lookup_tables`: address lookup tables and v0 messages for transactions
+//!   too large to list every account
+//!
+//! Nothing here sends transactions: the instructions are signed with the issuer
+//! signer and submitted by the caller's RPC stack, which stays out of this crate.
+
+pub mod client;
+pub mod instructions;
+pub mod lookup_tables;
+pub mod pda;
+
+pub use client::*;
+pub use instructions::*;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+
+/// Errors of the client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientError {
+    /// Fetching an account failed
+    Rpc(String),
+    /// An account the flow needs does not exist
+    AccountNotFound(Pubkey),
+    /// An account exists but does not hold the expected type
+    InvalidAccountData(Pubkey),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Rpc(error) => write!(f, "RPC error: {}", error),
+            ClientError::AccountNotFound(address) => write!(f, "account {} not found", address),
+            ClientError::InvalidAccountData(address) => write!(f, "account {} has unexpected data", address),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// Decode the data of a program account at `address`, checking its discriminator
+pub fn decode_account<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T, ClientError> {
+    T::try_deserialize(&mut &data[..]).map_err(|_| ClientError::InvalidAccountData(*address))
+}
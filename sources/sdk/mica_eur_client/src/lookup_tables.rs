@@ -0,0 +1,94 @@
+//! Address lookup tables for transactions carrying the transfer hook's extra
+//! accounts.
+//!
+//! A relayed `transfer_with_authorization` names more than 40 accounts and does
+//! not fit a legacy transaction. Relayers keep one table of the mint's accounts
+//! and one per sender they submit for, and compile the transaction as a v0
+//! message against both. A table can be used from the slot after it is extended.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::address_lookup_table::{instruction as lookup_table, AddressLookupTableAccount};
+use anchor_lang::solana_program::hash::Hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::message::{v0, CompileError, VersionedMessage};
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_spl::token_2022::ID as TOKEN_2022_ID;
+
+use crate::pda;
+
+/// Largest serialized transaction the network accepts, in bytes
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Accounts shared by the transfers of `mint`: the mint, its configuration
+/// accounts and the programs the transfer hook runs through
+pub fn mint_lookup_table_addresses(mint: &Pubkey) -> Vec<Pubkey> {
+    vec![
+        *mint,
+        pda::mint_info(mint),
+        pda::permit_authority(mint),
+        pda::invocation_guard(mint),
+        pda::staged_policy(mint),
+        pda::edd_config(mint),
+        pda::limit_config(mint),
+        pda::travel_rule_config(mint),
+        pda::aml_alert_config(mint),
+        pda::program_allowlist(mint),
+        pda::escrow_vault(mint),
+        pda::seizure_authority(mint),
+        pda::treasury_vault(mint),
+        pda::extra_account_meta_list(mint),
+        mica_eur_transfer_hook::ID,
+        TOKEN_2022_ID,
+        system_program::ID,
+        sysvar::instructions::ID,
+    ]
+}
+
+/// Accounts the transfer hook reads for `wallet` and its `token_account` when
+/// they send or receive on `mint`
+pub fn party_lookup_table_addresses(mint: &Pubkey, wallet: &Pubkey, token_account: &Pubkey) -> Vec<Pubkey> {
+    vec![
+        *wallet,
+        *token_account,
+        pda::kyc_user(wallet),
+        pda::blacklist_entry(wallet),
+        pda::transfer_record(wallet),
+        pda::velocity_record(wallet),
+        pda::fast_lane_institution(mint, wallet),
+        pda::approved_counterparty(mint, wallet),
+        pda::program_escrow(mint, wallet),
+        pda::freeze_record(token_account),
+    ]
+}
+
+/// Create a lookup table of `authority` holding `addresses`, paid by `payer`.
+/// Returns the create and extend instructions, which fit one transaction for
+/// either table above, and the table's address, derived from `recent_slot`.
+pub fn create_lookup_table(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: u64,
+    addresses: Vec<Pubkey>,
+) -> (Vec<Instruction>, Pubkey) {
+    let (create, table) = lookup_table::create_lookup_table(*authority, *payer, recent_slot);
+    let extend = lookup_table::extend_lookup_table(table, *authority, Some(*payer), addresses);
+    (vec![create, extend], table)
+}
+
+/// Compile `instructions` paid by `payer` into a v0 message, resolving the
+/// accounts found in `lookup_tables` through them
+pub fn compile_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedMessage, CompileError> {
+    v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash).map(VersionedMessage::V0)
+}
+
+/// Size of the signed transaction carrying `message`, in bytes
+pub fn transaction_size(message: &VersionedMessage) -> usize {
+    let signatures = message.header().num_required_signatures as usize;
+    // The signature count is a compact-u16: one byte below 128
+    1 + signatures * 64 + message.serialize().len()
+}
@@ -0,0 +1,239 @@
+//! Addresses of the MiCA EUR and transfer hook program accounts.
+
+use anchor_lang::prelude::Pubkey;
+use mica_eur::{
+    OperationKind, AML_ALERT_CONFIG_SEED, AML_AUTHORITY_SEED, APPROVED_COUNTERPARTY_SEED,
+    AUDITOR_ATTESTATION_SEED, BLACKLIST_SEED, COMPLIANCE_HISTORY_SEED, COUNTRY_POLICY_SEED,
+    EDD_CONFIG_SEED, ESCROW_SEED, ESCROW_VAULT_SEED, FAST_LANE_INSTITUTION_SEED, FREEZE_RECORD_SEED,
+    HOLDER_RECORD_SEED, IBAN_REGISTRY_SEED, INVOCATION_GUARD_SEED, ISSUANCE_POLICY_SEED,
+    KYC_ORACLE_STATE_SEED, KYC_PROVIDER_SEED, KYC_USER_SEED, LIMIT_CONFIG_SEED,
+    MINTER_ALLOWANCE_SEED, MINT_INFO_SEED, MINT_VOUCHER_SEED, OPERATION_RECEIPT_SEED,
+    PAYMENT_REQUEST_SEED, PAYOUT_REFERENCE_SEED, PERMIT_AUTHORITY_SEED, PERMIT_NONCE_SEED,
+    PROGRAM_ALLOWLIST_SEED, PROGRAM_ESCROW_SEED, REGULATOR_ACCESS_SEED, RELAYER_REGISTRY_SEED,
+    RELAYER_SEED, RESERVE_COMPOSITION_SEED, RESERVE_PROOF_EPOCH_SEED, RESERVE_VALUATION_SEED,
+    ROLE_SEED, SCREENING_PROVIDER_SEED, SEIZURE_AUTHORITY_SEED, STAGED_POLICY_SEED,
+    STANDING_ORDER_SEED, TRAVEL_RULE_CONFIG_SEED, TREASURY_VAULT_SEED, USER_LIMITS_SEED,
+};
+use mica_eur_transfer_hook::{EXTRA_ACCOUNT_METAS_SEED, TRANSFER_RECORD_SEED, TRAVEL_RULE_RECORD_SEED, VELOCITY_RECORD_SEED};
+
+fn program_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &mica_eur::ID).0
+}
+
+fn hook_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &mica_eur_transfer_hook::ID).0
+}
+
+pub fn mint_info(mint: &Pubkey) -> Pubkey {
+    program_address(&[MINT_INFO_SEED, mint.as_ref()])
+}
+
+pub fn kyc_oracle_state() -> Pubkey {
+    program_address(&[KYC_ORACLE_STATE_SEED])
+}
+
+pub fn kyc_user(user: &Pubkey) -> Pubkey {
+    program_address(&[KYC_USER_SEED, user.as_ref()])
+}
+
+pub fn kyc_provider(provider: &Pubkey) -> Pubkey {
+    program_address(&[KYC_PROVIDER_SEED, provider.as_ref()])
+}
+
+pub fn screening_provider(signing_key: &Pubkey) -> Pubkey {
+    program_address(&[SCREENING_PROVIDER_SEED, signing_key.as_ref()])
+}
+
+/// Role assignment of `holder` within `scope`: a mint, or the KYC oracle state
+pub fn role_assignment(scope: &Pubkey, holder: &Pubkey) -> Pubkey {
+    program_address(&[ROLE_SEED, scope.as_ref(), holder.as_ref()])
+}
+
+pub fn minter_allowance(mint: &Pubkey, minter: &Pubkey) -> Pubkey {
+    program_address(&[MINTER_ALLOWANCE_SEED, mint.as_ref(), minter.as_ref()])
+}
+
+pub fn issuance_policy(mint: &Pubkey) -> Pubkey {
+    program_address(&[ISSUANCE_POLICY_SEED, mint.as_ref()])
+}
+
+/// Receipt of the operation of `kind` that `actor` signed with `operation_id`
+pub fn operation_receipt(
+    mint: &Pubkey,
+    kind: OperationKind,
+    actor: &Pubkey,
+    operation_id: &[u8; 16],
+) -> Pubkey {
+    program_address(&[OPERATION_RECEIPT_SEED, mint.as_ref(), &[kind as u8], actor.as_ref(), operation_id])
+}
+
+pub fn invocation_guard(mint: &Pubkey) -> Pubkey {
+    program_address(&[INVOCATION_GUARD_SEED, mint.as_ref()])
+}
+
+pub fn aml_authority(authority: &Pubkey) -> Pubkey {
+    program_address(&[AML_AUTHORITY_SEED, authority.as_ref()])
+}
+
+pub fn blacklist_entry(user: &Pubkey) -> Pubkey {
+    program_address(&[BLACKLIST_SEED, user.as_ref()])
+}
+
+pub fn user_limits(user: &Pubkey) -> Pubkey {
+    program_address(&[USER_LIMITS_SEED, user.as_ref()])
+}
+
+pub fn limit_config(mint: &Pubkey) -> Pubkey {
+    program_address(&[LIMIT_CONFIG_SEED, mint.as_ref()])
+}
+
+/// Treasury vault PDA, the owner of the mint's treasury token account
+pub fn treasury_vault(mint: &Pubkey) -> Pubkey {
+    program_address(&[TREASURY_VAULT_SEED, mint.as_ref()])
+}
+
+/// Mint voucher PDA of the SEPA deposit hashed in `reference_hash`
+pub fn mint_voucher(mint: &Pubkey, reference_hash: &[u8; 32]) -> Pubkey {
+    program_address(&[MINT_VOUCHER_SEED, mint.as_ref(), reference_hash])
+}
+
+/// Payout reference PDA of the redemption with `operation_id`
+/// Payout of the redemption `holder` signed with `operation_id`
+pub fn payout_reference(mint: &Pubkey, holder: &Pubkey, operation_id: &[u8; 16]) -> Pubkey {
+    program_address(&[PAYOUT_REFERENCE_SEED, mint.as_ref(), holder.as_ref(), operation_id])
+}
+
+pub fn reserve_composition(mint: &Pubkey) -> Pubkey {
+    program_address(&[RESERVE_COMPOSITION_SEED, mint.as_ref()])
+}
+
+pub fn reserve_proof_epoch(mint: &Pubkey, epoch: u64) -> Pubkey {
+    program_address(&[RESERVE_PROOF_EPOCH_SEED, mint.as_ref(), &epoch.to_le_bytes()])
+}
+
+pub fn reserve_valuation(mint: &Pubkey) -> Pubkey {
+    program_address(&[RESERVE_VALUATION_SEED, mint.as_ref()])
+}
+
+pub fn regulator_access(mint: &Pubkey, regulator: &Pubkey) -> Pubkey {
+    program_address(&[REGULATOR_ACCESS_SEED, mint.as_ref(), regulator.as_ref()])
+}
+
+pub fn program_allowlist(mint: &Pubkey) -> Pubkey {
+    program_address(&[PROGRAM_ALLOWLIST_SEED, mint.as_ref()])
+}
+
+pub fn program_escrow(mint: &Pubkey, authority: &Pubkey) -> Pubkey {
+    program_address(&[PROGRAM_ESCROW_SEED, mint.as_ref(), authority.as_ref()])
+}
+
+/// Delegate owners approve for transfers on their signed authorization
+pub fn permit_authority(mint: &Pubkey) -> Pubkey {
+    program_address(&[PERMIT_AUTHORITY_SEED, mint.as_ref()])
+}
+
+pub fn permit_nonce(owner: &Pubkey, nonce: &[u8; 32]) -> Pubkey {
+    program_address(&[PERMIT_NONCE_SEED, owner.as_ref(), nonce.as_ref()])
+}
+
+pub fn standing_order(payer: &Pubkey, order_id: u64) -> Pubkey {
+    program_address(&[STANDING_ORDER_SEED, payer.as_ref(), &order_id.to_le_bytes()])
+}
+
+pub fn relayer_registry(mint: &Pubkey) -> Pubkey {
+    program_address(&[RELAYER_REGISTRY_SEED, mint.as_ref()])
+}
+
+pub fn relayer(mint: &Pubkey, relayer: &Pubkey) -> Pubkey {
+    program_address(&[RELAYER_SEED, mint.as_ref(), relayer.as_ref()])
+}
+
+/// Owner of the token account holding the commerce escrows of `mint`
+pub fn escrow_vault(mint: &Pubkey) -> Pubkey {
+    program_address(&[ESCROW_VAULT_SEED, mint.as_ref()])
+}
+
+pub fn escrow(buyer: &Pubkey, escrow_id: u64) -> Pubkey {
+    program_address(&[ESCROW_SEED, buyer.as_ref(), &escrow_id.to_le_bytes()])
+}
+
+pub fn payment_request(payee: &Pubkey, request_id: u64) -> Pubkey {
+    program_address(&[PAYMENT_REQUEST_SEED, payee.as_ref(), &request_id.to_le_bytes()])
+}
+
+/// Permanent delegate of `mint`, signing seizures and recoveries
+pub fn seizure_authority(mint: &Pubkey) -> Pubkey {
+    program_address(&[SEIZURE_AUTHORITY_SEED, mint.as_ref()])
+}
+
+pub fn compliance_history(user: &Pubkey) -> Pubkey {
+    program_address(&[COMPLIANCE_HISTORY_SEED, user.as_ref()])
+}
+
+pub fn country_policy(country_code: &str) -> Pubkey {
+    program_address(&[COUNTRY_POLICY_SEED, country_code.as_bytes()])
+}
+
+pub fn iban_registry(iban_hash: &[u8; 32]) -> Pubkey {
+    program_address(&[IBAN_REGISTRY_SEED, iban_hash])
+}
+
+pub fn freeze_record(token_account: &Pubkey) -> Pubkey {
+    program_address(&[FREEZE_RECORD_SEED, token_account.as_ref()])
+}
+
+pub fn auditor_attestation(mint: &Pubkey) -> Pubkey {
+    program_address(&[AUDITOR_ATTESTATION_SEED, mint.as_ref()])
+}
+
+/// Holder record of a token account opened through `create_token_account`
+pub fn holder_record(token_account: &Pubkey) -> Pubkey {
+    program_address(&[HOLDER_RECORD_SEED, token_account.as_ref()])
+}
+
+pub fn staged_policy(mint: &Pubkey) -> Pubkey {
+    program_address(&[STAGED_POLICY_SEED, mint.as_ref()])
+}
+
+pub fn edd_config(mint: &Pubkey) -> Pubkey {
+    program_address(&[EDD_CONFIG_SEED, mint.as_ref()])
+}
+
+/// Approved counterparty entry of `owner` on `mint`
+pub fn approved_counterparty(mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    program_address(&[APPROVED_COUNTERPARTY_SEED, mint.as_ref(), owner.as_ref()])
+}
+
+pub fn aml_alert_config(mint: &Pubkey) -> Pubkey {
+    program_address(&[AML_ALERT_CONFIG_SEED, mint.as_ref()])
+}
+
+/// Fast-lane admission of the institution `wallet` on `mint`
+pub fn fast_lane_institution(mint: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    program_address(&[FAST_LANE_INSTITUTION_SEED, mint.as_ref(), wallet.as_ref()])
+}
+
+pub fn travel_rule_config(mint: &Pubkey) -> Pubkey {
+    program_address(&[TRAVEL_RULE_CONFIG_SEED, mint.as_ref()])
+}
+
+/// Extra account meta list of the transfer hook for `mint`
+pub fn extra_account_meta_list(mint: &Pubkey) -> Pubkey {
+    hook_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()])
+}
+
+/// Daily transfer record the transfer hook keeps for `user`
+pub fn transfer_record(user: &Pubkey) -> Pubkey {
+    hook_address(&[TRANSFER_RECORD_SEED, user.as_ref()])
+}
+
+/// Velocity record the transfer hook keeps for `user`
+pub fn velocity_record(user: &Pubkey) -> Pubkey {
+    hook_address(&[VELOCITY_RECORD_SEED, user.as_ref()])
+}
+
+/// Travel-rule record the transfer hook expects for the next large transfer from
+/// `source` to `destination`
+pub fn travel_rule_record(source: &Pubkey, destination: &Pubkey) -> Pubkey {
+    hook_address(&[TRAVEL_RULE_RECORD_SEED, source.as_ref(), destination.as_ref()])
+}
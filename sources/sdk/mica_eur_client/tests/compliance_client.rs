@@ -0,0 +1,416 @@
+//! `ComplianceClient` over an in-memory account store: each flow must pick the
+//! optional accounts the on-chain state calls for, so its instruction is compared
+//! with the builder called with the options worked out by hand.
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountSerialize, Discriminator};
+use anchor_spl::token_2022::spl_token_2022::state::{Account as TokenAccountState, AccountState};
+use mica_eur::{
+    AccountClass, ComplianceHistory, FreezeRecord, KycStatus, KycUser, LimitConfig, MintInfo,
+    TreasuryVault, EUR_DECIMALS, RULE_SET_VERSION,
+};
+use mica_eur_client::instructions::*;
+use mica_eur_client::{pda, AccountFetcher, ClientError, ComplianceClient};
+
+const NOW: i64 = 1_775_000_000;
+
+#[derive(Default)]
+struct Chain {
+    accounts: HashMap<Pubkey, Vec<u8>>,
+    unreachable: bool,
+}
+
+impl AccountFetcher for Chain {
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        if self.unreachable {
+            return Err(ClientError::Rpc("connection refused".to_string()));
+        }
+        Ok(self.accounts.get(address).cloned())
+    }
+}
+
+impl Chain {
+    fn store(&mut self, address: Pubkey, account: &impl AccountSerialize) {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        self.accounts.insert(address, data);
+    }
+
+    // An existing account whose contents the flow does not read
+    fn touch<T: Discriminator>(&mut self, address: Pubkey) {
+        self.accounts.insert(address, T::DISCRIMINATOR.to_vec());
+    }
+
+    fn token_account(&mut self, address: Pubkey, mint: Pubkey, owner: Pubkey) {
+        let mut data = vec![0; TokenAccountState::LEN];
+        let state = TokenAccountState {
+            mint,
+            owner,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        state.pack_into_slice(&mut data);
+        self.accounts.insert(address, data);
+    }
+}
+
+struct Setup {
+    mint: Pubkey,
+    issuer: Pubkey,
+    user: Pubkey,
+    chain: Chain,
+}
+
+fn setup() -> Setup {
+    let (mint, issuer, user) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut chain = Chain::default();
+    chain.store(pda::mint_info(&mint), &mint_info(mint, issuer));
+    chain.store(pda::kyc_user(&user), &kyc_user(user, "AT", i64::MAX));
+    Setup {
+        mint,
+        issuer,
+        user,
+        chain,
+    }
+}
+
+impl Setup {
+    fn client(self) -> ComplianceClient<Chain> {
+        ComplianceClient::new(self.chain, self.mint)
+    }
+}
+
+fn mint_info(mint: Pubkey, issuer: Pubkey) -> MintInfo {
+    MintInfo {
+        mint,
+        issuer,
+        freeze_authority: issuer,
+        permanent_delegate: issuer,
+        whitepaper_uri: String::new(),
+        is_active: true,
+        creation_time: 0,
+        reserve_merkle_root: [0; 32],
+        reserve_ipfs_cid: String::new(),
+        last_reserve_update: 0,
+        rule_set_version: RULE_SET_VERSION,
+        rule_set_hash: [0; 32],
+        paused: false,
+        pauser: Pubkey::default(),
+        approval_set: Pubkey::default(),
+        reserve_total_eur: 0,
+        coverage_auto_pause: false,
+        minting_halted: false,
+        transfer_fee_bps: 0,
+        max_transfer_fee: 0,
+        fee_treasury: Pubkey::default(),
+        confidential_transfers: false,
+        confidential_auditor: Pubkey::default(),
+        auditor_elgamal_pubkey: [0; 32],
+        max_supply: 0,
+        pending_max_supply: 0,
+        supply_cap_effective_at: 0,
+        issuance_policy: Pubkey::default(),
+        timelock: Pubkey::default(),
+        whitepaper_version: 0,
+        decimals: EUR_DECIMALS,
+        require_associated_accounts: false,
+        holder_count: 0,
+        migrated_to: Pubkey::default(),
+        wind_down_started_at: 0,
+        redemption_deadline: 0,
+        redemption_account: Pubkey::default(),
+        total_minted: 0,
+        total_burned: 0,
+        reserve_epoch_count: 0,
+        max_reserve_age_seconds: 0,
+        reserve_valuation: Pubkey::default(),
+        relayer_registry: Pubkey::default(),
+    }
+}
+
+fn kyc_user(user: Pubkey, country_code: &str, expiry_time: i64) -> KycUser {
+    KycUser {
+        authority: Pubkey::new_unique(),
+        user,
+        status: KycStatus::Verified,
+        verification_level: 2,
+        verification_time: 0,
+        expiry_time,
+        country_code: country_code.to_string(),
+        blz: String::new(),
+        iban_hash: [0; 32],
+        verification_provider: String::new(),
+        account_class: AccountClass::Retail,
+        kyb_hash: [0; 32],
+        class_change_time: 0,
+        sanctions_screen_date: 0,
+        sanctions_screen_hash: [0; 32],
+        sanctions_screen_provider: Pubkey::default(),
+        edd_required: false,
+        provider: Pubkey::default(),
+    }
+}
+
+#[test]
+fn verification_follows_status_and_expiry() {
+    let mut setup = setup();
+    let (expired, pending, unknown) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    setup
+        .chain
+        .store(pda::kyc_user(&expired), &kyc_user(expired, "DE", NOW - 1));
+    let mut pending_user = kyc_user(pending, "DE", 0);
+    pending_user.status = KycStatus::Pending;
+    setup.chain.store(pda::kyc_user(&pending), &pending_user);
+    let user = setup.user;
+    let client = setup.client();
+
+    assert_eq!(client.is_verified(&user, NOW), Ok(true));
+    assert_eq!(client.is_verified(&expired, NOW), Ok(false));
+    assert_eq!(client.is_verified(&pending, NOW), Ok(false));
+    assert_eq!(client.is_verified(&unknown, NOW), Ok(false));
+    assert!(client.kyc_user(&unknown).unwrap().is_none());
+}
+
+#[test]
+fn accounts_of_another_type_are_rejected() {
+    let mut setup = setup();
+    let impostor = Pubkey::new_unique();
+    // A mint info where the KYC record should be
+    let info = mint_info(setup.mint, setup.issuer);
+    setup.chain.store(pda::kyc_user(&impostor), &info);
+    let client = setup.client();
+
+    let address = pda::kyc_user(&impostor);
+    assert!(matches!(
+        client.kyc_user(&impostor),
+        Err(ClientError::InvalidAccountData(invalid)) if invalid == address
+    ));
+}
+
+#[test]
+fn issuer_mints_without_delegation_accounts() {
+    let setup = setup();
+    let (mint, issuer, user, token_account) =
+        (setup.mint, setup.issuer, setup.user, Pubkey::new_unique());
+    let client = setup.client();
+
+    let instruction = client
+        .mint_to(&issuer, &user, &token_account, 1_000, [1; 16])
+        .unwrap();
+
+    let expected = mint_tokens(
+        &issuer,
+        &mint,
+        &token_account,
+        &user,
+        "AT",
+        1_000,
+        [1; 16],
+        MintTokensOptions::default(),
+    );
+    assert_eq!(instruction, expected);
+}
+
+#[test]
+fn delegated_minters_bring_their_role_and_configured_accounts() {
+    let mut setup = setup();
+    let (mint, minter, user, token_account) = (
+        setup.mint,
+        Pubkey::new_unique(),
+        setup.user,
+        Pubkey::new_unique(),
+    );
+    let (approval_set, issuance_policy) = (Pubkey::new_unique(), pda::issuance_policy(&mint));
+    let mut info = mint_info(mint, setup.issuer);
+    info.approval_set = approval_set;
+    info.issuance_policy = issuance_policy;
+    setup.chain.store(pda::mint_info(&mint), &info);
+    setup.chain.touch::<LimitConfig>(pda::limit_config(&mint));
+    let client = setup.client();
+
+    let instruction = client
+        .mint_to(&minter, &user, &token_account, 1_000, [2; 16])
+        .unwrap();
+
+    let options = MintTokensOptions {
+        approval_set: Some(approval_set),
+        limit_config: Some(pda::limit_config(&mint)),
+        role_assignment: Some(pda::role_assignment(&mint, &minter)),
+        minter_allowance: Some(pda::minter_allowance(&mint, &minter)),
+        issuance_policy: Some(issuance_policy),
+    };
+    assert_eq!(
+        instruction,
+        mint_tokens(
+            &minter,
+            &mint,
+            &token_account,
+            &user,
+            "AT",
+            1_000,
+            [2; 16],
+            options
+        )
+    );
+}
+
+#[test]
+fn minting_needs_a_registered_user_and_a_reachable_node() {
+    let setup = setup();
+    let (issuer, stranger) = (setup.issuer, Pubkey::new_unique());
+    let client = setup.client();
+
+    assert_eq!(
+        client.mint_to(&issuer, &stranger, &Pubkey::new_unique(), 1, [3; 16]),
+        Err(ClientError::AccountNotFound(pda::kyc_user(&stranger)))
+    );
+
+    let unreachable = ComplianceClient::new(
+        Chain {
+            unreachable: true,
+            ..Default::default()
+        },
+        Pubkey::new_unique(),
+    );
+    assert_eq!(
+        unreachable.mint_info().err(),
+        Some(ClientError::Rpc("connection refused".to_string()))
+    );
+}
+
+#[test]
+fn thaw_passes_the_freeze_record_and_history_that_exist() {
+    let mut setup = setup();
+    let (mint, issuer, user, token_account) =
+        (setup.mint, setup.issuer, setup.user, Pubkey::new_unique());
+    setup.chain.token_account(token_account, mint, user);
+    setup
+        .chain
+        .touch::<FreezeRecord>(pda::freeze_record(&token_account));
+    setup
+        .chain
+        .touch::<ComplianceHistory>(pda::compliance_history(&user));
+    let client = setup.client();
+
+    let instruction = client.thaw(&issuer, &token_account).unwrap();
+
+    assert_eq!(
+        instruction,
+        thaw_account(
+            &issuer,
+            &mint,
+            &token_account,
+            Some(pda::freeze_record(&token_account)),
+            None,
+            Some(pda::compliance_history(&user)),
+        )
+    );
+    let unknown = Pubkey::new_unique();
+    assert_eq!(
+        client.thaw(&issuer, &unknown),
+        Err(ClientError::AccountNotFound(unknown))
+    );
+}
+
+#[test]
+fn seizure_appends_the_hook_accounts_of_both_holders() {
+    let mut setup = setup();
+    let (mint, issuer, holder) = (setup.mint, setup.issuer, setup.user);
+    let (from_account, to_account, recipient) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    setup.chain.token_account(from_account, mint, holder);
+    setup.chain.token_account(to_account, mint, recipient);
+    let client = setup.client();
+
+    let instruction = client
+        .seize(&issuer, &from_account, &to_account, 500, [4; 16])
+        .unwrap();
+
+    let mut expected = seize_tokens(
+        &issuer,
+        &mint,
+        &from_account,
+        &to_account,
+        500,
+        [4; 16],
+        None,
+        None,
+    );
+    expected.accounts.extend(transfer_hook_accounts(
+        &mint,
+        &from_account,
+        &holder,
+        &to_account,
+        &recipient,
+    ));
+    assert_eq!(instruction, expected);
+}
+
+#[test]
+fn treasury_flows_use_the_configured_vault() {
+    let mut setup = setup();
+    let (mint, issuer, user, token_account, vault) = (
+        setup.mint,
+        setup.issuer,
+        setup.user,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let treasury = TreasuryVault {
+        mint,
+        vault,
+        minted_amount: 0,
+        distributed_amount: 0,
+        bump: 255,
+    };
+    setup.chain.store(pda::treasury_vault(&mint), &treasury);
+    let client = setup.client();
+
+    assert_eq!(
+        client.mint_to_treasury(&issuer, 10_000, [5; 16]).unwrap(),
+        mint_to_treasury(&issuer, &mint, &vault, 10_000, [5; 16], None, None)
+    );
+
+    let distributor = Pubkey::new_unique();
+    let mut expected = distribute_from_treasury(
+        &distributor,
+        &mint,
+        &vault,
+        &token_account,
+        &user,
+        "AT",
+        2_500,
+        [6; 16],
+        DistributeOptions {
+            limit_config: None,
+            role_assignment: Some(pda::role_assignment(&mint, &distributor)),
+        },
+    );
+    expected.accounts.extend(transfer_hook_accounts(
+        &mint,
+        &vault,
+        &pda::treasury_vault(&mint),
+        &token_account,
+        &user,
+    ));
+    assert_eq!(
+        client
+            .distribute(&distributor, &user, &token_account, 2_500, [6; 16])
+            .unwrap(),
+        expected
+    );
+}
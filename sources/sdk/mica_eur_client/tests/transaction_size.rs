@@ -0,0 +1,114 @@
+//! Size of the relayed transfer transaction. Relayers submit the Ed25519
+//! instruction and `transfer_with_authorization` with the transfer hook's extra
+//! accounts in one transaction, which must stay within the packet size.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::address_lookup_table::AddressLookupTableAccount;
+use anchor_lang::solana_program::hash::Hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use mica_eur::transfer_authorization_message;
+use mica_eur_client::instructions;
+use mica_eur_client::lookup_tables::{
+    compile_message, create_lookup_table, mint_lookup_table_addresses,
+    party_lookup_table_addresses, transaction_size, MAX_TRANSACTION_SIZE,
+};
+
+struct Transfer {
+    relayer: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+    source: Pubkey,
+    instructions: [Instruction; 2],
+}
+
+// The largest form of the transfer: registered relayer, hook accounts appended.
+// The signature bytes do not affect the size.
+fn relayed_transfer() -> Transfer {
+    let key = Pubkey::new_unique;
+    let (relayer, mint, owner, source, destination, recipient) =
+        (key(), key(), key(), key(), key(), key());
+    let (amount, nonce, expiry) = (u64::MAX, [7; 32], i64::MAX);
+
+    let message = transfer_authorization_message(&mint, &owner, &recipient, amount, &nonce, expiry);
+    let signature = instructions::ed25519_signature(&owner, &[1; 64], &message);
+    let mut transfer = instructions::transfer_with_authorization(
+        &relayer,
+        &mint,
+        &owner,
+        &source,
+        &destination,
+        &recipient,
+        amount,
+        nonce,
+        expiry,
+        Some(key()),
+    );
+    transfer
+        .accounts
+        .extend(instructions::transfer_hook_accounts(
+            &mint,
+            &source,
+            &owner,
+            &destination,
+            &recipient,
+        ));
+    Transfer {
+        relayer,
+        mint,
+        owner,
+        source,
+        instructions: [signature, transfer],
+    }
+}
+
+fn table(addresses: Vec<Pubkey>) -> AddressLookupTableAccount {
+    AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses,
+    }
+}
+
+#[test]
+fn relayed_transfers_do_not_fit_without_lookup_tables() {
+    let transfer = relayed_transfer();
+    let message = compile_message(
+        &transfer.relayer,
+        &transfer.instructions,
+        &[],
+        Hash::default(),
+    )
+    .unwrap();
+    assert!(transaction_size(&message) > MAX_TRANSACTION_SIZE);
+}
+
+#[test]
+fn relayed_transfers_fit_with_the_mint_and_sender_tables() {
+    let transfer = relayed_transfer();
+    let tables = [
+        table(mint_lookup_table_addresses(&transfer.mint)),
+        table(party_lookup_table_addresses(
+            &transfer.mint,
+            &transfer.owner,
+            &transfer.source,
+        )),
+    ];
+    let message = compile_message(
+        &transfer.relayer,
+        &transfer.instructions,
+        &tables,
+        Hash::default(),
+    )
+    .unwrap();
+    let size = transaction_size(&message);
+    assert!(size <= MAX_TRANSACTION_SIZE, "{size} bytes");
+}
+
+#[test]
+fn lookup_tables_are_created_in_one_transaction() {
+    let (authority, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let addresses = mint_lookup_table_addresses(&mint);
+    let (instructions, table) = create_lookup_table(&authority, &authority, 1, addresses);
+    assert_eq!(instructions[1].accounts[0].pubkey, table);
+    let message = compile_message(&authority, &instructions, &[], Hash::default()).unwrap();
+    assert!(transaction_size(&message) <= MAX_TRANSACTION_SIZE);
+}
@@ -0,0 +1,1938 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    instruction::{AuthorityType, TokenInstruction},
+};
+use mica_eur::instruction as ix;
+use mica_eur_transfer_hook::instruction as hook_ix;
+use mica_eur::{
+    AccountClass, BlacklistReason, BondPriceFeed, CounterpartyKind, KycStatus, MetadataField, MicaEurError, PolicyParams,
+    RegulatoryAction, RelayerStatus, ReserveAsset, TierLimits, TimelockAction,
+};
+
+/// An instruction as found in a transaction, with its account keys resolved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<Pubkey>,
+    pub data: Vec<u8>,
+}
+
+/// Inner (CPI) instructions executed by one top-level instruction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawInnerInstructions {
+    pub index: usize, // Index of the top-level instruction
+    pub instructions: Vec<RawInstruction>,
+}
+
+/// A transaction touching the program, as returned by the RPC history APIs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub instructions: Vec<RawInstruction>,
+    pub inner_instructions: Vec<RawInnerInstructions>,
+}
+
+/// Standing order actions only the payer can take
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StandingOrderAction {
+    Pause,
+    Resume,
+    Skip,
+    Cancel,
+}
+
+/// A decoded MiCA EUR program instruction
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgramOperation {
+    Initialize,
+    InitializeKycOracle { authority: Pubkey },
+    RegisterKycUser {
+        authority: Pubkey,
+        user: Pubkey,
+        country_code: String,
+        verification_provider: String,
+    },
+    UpdateKycStatus {
+        authority: Pubkey,
+        kyc_user: Pubkey,
+        status: KycStatus,
+        verification_level: u8,
+        expiry_days: i64,
+    },
+    ReclassifyAccount {
+        authority: Pubkey,
+        kyc_user: Pubkey,
+        new_class: AccountClass,
+        kyb_hash: [u8; 32],
+    },
+    RegisterScreeningProvider { authority: Pubkey, signing_key: Pubkey, name: String },
+    DeactivateScreeningProvider { authority: Pubkey, screening_provider: Pubkey },
+    SubmitScreeningAttestation {
+        submitter: Pubkey,
+        screening_provider: Pubkey,
+        kyc_user: Pubkey,
+        screened_at: i64,
+        result_hash: [u8; 32],
+    },
+    InitializeEuroMint { issuer: Pubkey, mint: Pubkey, whitepaper_uri: String },
+    CreateTokenAccount { owner: Pubkey, token_account: Pubkey, mint: Pubkey },
+    CloseTokenAccount { owner: Pubkey, token_account: Pubkey, mint: Pubkey },
+    ActivateTokenAccount { caller: Pubkey, mint: Pubkey, token_account: Pubkey },
+    EnforceKycExpiry { caller: Pubkey, mint: Pubkey, token_account: Pubkey },
+    OpenComplianceHistory { payer: Pubkey, kyc_user: Pubkey, compliance_history: Pubkey },
+    MintTokens {
+        issuer: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        kyc_user: Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+    },
+    BurnTokens {
+        owner: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+    },
+    FreezeAccount {
+        freeze_authority: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        reason_code: u8,
+        legal_reference: String,
+        frozen_amount: u64,
+        expires_at: i64,
+    },
+    ThawAccount { freeze_authority: Pubkey, mint: Pubkey, token_account: Pubkey },
+    SeizeTokens {
+        permanent_delegate: Pubkey,
+        mint: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+    },
+    UpdateReserveProof {
+        issuer: Pubkey,
+        mint_info: Pubkey,
+        merkle_root: [u8; 32],
+        ipfs_cid: String,
+        reserve_total_eur: u64,
+        composition: Vec<ReserveAsset>,
+    },
+    SyncRuleSet { issuer: Pubkey, mint_info: Pubkey },
+    RegisterAmlAuthority {
+        authority: Pubkey,
+        approver: Pubkey,
+        authority_id: String,
+        powers: u8,
+        jurisdiction: String,
+        global: bool,
+    },
+    SetRegulator { issuer: Pubkey, regulator_registry: Pubkey, regulator: Pubkey },
+    CreateBlacklistEntry {
+        authority: Pubkey,
+        user: Pubkey,
+        reason: BlacklistReason,
+        legal_reference: String,
+        expiry_time: i64,
+        review_time: i64,
+    },
+    DeactivateAmlAuthority { approver: Pubkey, aml_authority: Pubkey },
+    DeactivateBlacklistEntry { authority: Pubkey, user: Pubkey },
+    ExtendBlacklistEntry {
+        authority: Pubkey,
+        blacklist_entry: Pubkey,
+        expiry_time: i64,
+        review_time: i64,
+    },
+    LapseBlacklistEntry { caller: Pubkey, blacklist_entry: Pubkey },
+    ReclassifyBlacklistEntry {
+        authority: Pubkey,
+        user: Pubkey,
+        reason: BlacklistReason,
+        legal_reference: String,
+    },
+    UpdateAmlAuthorityPowers { approver: Pubkey, aml_authority: Pubkey, new_powers: u8 },
+    CreateStandingOrder {
+        payer: Pubkey,
+        standing_order: Pubkey,
+        payer_token_account: Pubkey,
+        payee_token_account: Pubkey,
+        order_id: u64,
+        amount: u64,
+        interval_seconds: i64,
+        first_execution_time: i64,
+        end_time: i64,
+    },
+    UpdateStandingOrder { payer: Pubkey, standing_order: Pubkey, action: StandingOrderAction },
+    ExecuteStandingOrder {
+        keeper: Pubkey,
+        standing_order: Pubkey,
+        payer_token_account: Pubkey,
+        payee_token_account: Pubkey,
+    },
+    StagePolicy { issuer: Pubkey, rule_set_version: u32, params: PolicyParams },
+    ClearStagedPolicy { issuer: Pubkey, staged_policy: Pubkey },
+    StartMigration { issuer: Pubkey, old_mint_info: Pubkey, new_mint_info: Pubkey },
+    ClaimMigration {
+        holder: Pubkey,
+        old_mint: Pubkey,
+        new_mint: Pubkey,
+        old_token_account: Pubkey,
+        new_token_account: Pubkey,
+    },
+    CompleteMigration { issuer: Pubkey, migration: Pubkey },
+    PauseToken { authority: Pubkey, mint_info: Pubkey },
+    ResumeToken { authority: Pubkey, mint_info: Pubkey },
+    DeactivateMint { issuer: Pubkey, mint_info: Pubkey },
+    ReactivateMint { issuer: Pubkey, mint_info: Pubkey },
+    InitiateWindDown {
+        issuer: Pubkey,
+        mint: Pubkey,
+        redemption_account: Pubkey,
+        redemption_deadline: i64,
+    },
+    MigrateMintAuthority { issuer: Pubkey, current_authority: Pubkey, mint: Pubkey },
+    MigratePermanentDelegate { issuer: Pubkey, mint: Pubkey },
+    CreateMintVoucher {
+        issuer: Pubkey,
+        mint: Pubkey,
+        voucher: Pubkey,
+        user: Pubkey,
+        reference_hash: [u8; 32],
+        amount: u64,
+    },
+    ClaimMintVoucher { user: Pubkey, mint: Pubkey, voucher: Pubkey, token_account: Pubkey },
+    RecordPayoutReference {
+        issuer: Pubkey,
+        payout_reference: Pubkey,
+        operation_id: [u8; 16],
+        reference_hash: [u8; 32],
+    },
+    SetPauser { issuer: Pubkey, mint_info: Pubkey, new_pauser: Pubkey },
+    SetAssociatedAccountRequirement { issuer: Pubkey, mint_info: Pubkey, required: bool },
+    SetEddFlag { authority: Pubkey, kyc_user: Pubkey, edd_required: bool },
+    ConfigureEdd {
+        issuer: Pubkey,
+        mint: Pubkey,
+        vault: Pubkey,
+        transfer_threshold: u64,
+        approval_window: i64,
+    },
+    RequestEddTransfer {
+        sender: Pubkey,
+        mint: Pubkey,
+        sender_token_account: Pubkey,
+        recipient_token_account: Pubkey,
+        pending_approval: Pubkey,
+        request_id: u64,
+        amount: u64,
+    },
+    ApproveEddTransfer { officer: Pubkey, pending_approval: Pubkey, destination_token_account: Pubkey },
+    RejectEddTransfer {
+        officer: Pubkey,
+        pending_approval: Pubkey,
+        destination_token_account: Pubkey,
+        decision_reason: u8,
+    },
+    ExpireEddTransfer { caller: Pubkey, pending_approval: Pubkey, sender_token_account: Pubkey },
+    ConfigureApprovalSet {
+        issuer: Pubkey,
+        approval_set: Pubkey,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+        large_mint_threshold: u64,
+    },
+    ProposeAction { officer: Pubkey, proposal: Pubkey, action: RegulatoryAction },
+    ApproveAction { officer: Pubkey, proposal: Pubkey },
+    CancelAction { officer: Pubkey, proposal: Pubkey },
+    ExecuteAction { authority: Pubkey, proposal: Pubkey, token_account: Pubkey },
+    SetLimits { issuer: Pubkey, limit_config: Pubkey, tiers: [TierLimits; 4] },
+    ConfigureReserveAuditor {
+        issuer: Pubkey,
+        mint_info: Pubkey,
+        auditor: Pubkey,
+        attestation_window: i64,
+    },
+    AttestReserveProof { auditor: Pubkey, mint_info: Pubkey, merkle_root: [u8; 32] },
+    CheckReserveCoverage { mint_info: Pubkey, mint: Pubkey },
+    SetCoverageAutoPause { issuer: Pubkey, mint_info: Pubkey, enabled: bool },
+    GrantRegulatorAccess {
+        issuer: Pubkey,
+        mint_info: Pubkey,
+        regulator_access: Pubkey,
+        regulator: Pubkey,
+        authority_id: String,
+        expires_at: i64,
+    },
+    RevokeRegulatorAccess { issuer: Pubkey, mint_info: Pubkey, regulator_access: Pubkey },
+    ExportAuditWindow {
+        issuer: Pubkey,
+        regulator_access: Pubkey,
+        from: i64,
+        to: i64,
+        event_count: u64,
+        digest: [u8; 32],
+    },
+    AllowlistProgram { issuer: Pubkey, program_allowlist: Pubkey, program: Pubkey },
+    DelistProgram { issuer: Pubkey, program_allowlist: Pubkey, program: Pubkey },
+    RegisterProgramEscrow { payer: Pubkey, authority: Pubkey, program_escrow: Pubkey, program: Pubkey },
+    SetMaxReserveAge { issuer: Pubkey, mint_info: Pubkey, max_reserve_age_seconds: i64 },
+    ConfigureReserveValuation {
+        issuer: Pubkey,
+        mint_info: Pubkey,
+        eur_usd_feed: [u8; 32],
+        bond_feeds: Vec<BondPriceFeed>,
+        tolerance_bps: u16,
+        max_price_age: i64,
+    },
+    CloseKycUser { authority: Pubkey, kyc_user: Pubkey, mint: Pubkey },
+    PublishBalanceSnapshot {
+        issuer: Pubkey,
+        balance_snapshot: Pubkey,
+        merkle_root: [u8; 32],
+        snapshot_slot: u64,
+        holder_count: u32,
+        total_balance: u64,
+    },
+    WhitelistInstitution { issuer: Pubkey, wallet: Pubkey, lei: [u8; 20], daily_cap: u64 },
+    RemoveInstitution { issuer: Pubkey, institution: Pubkey },
+    AddKycProvider { authority: Pubkey, provider: Pubkey, name: String },
+    RemoveKycProvider { authority: Pubkey, kyc_provider: Pubkey },
+    RecordReconciliation {
+        issuer: Pubkey,
+        mint: Pubkey,
+        reconciliation_record: Pubkey,
+        pending_redemptions: u64,
+        registered_deposits: u64,
+        ledger_balance: u64,
+        first_detected_time: i64,
+    },
+    ConfigureTravelRule { issuer: Pubkey, travel_rule_config: Pubkey, threshold: u64 },
+    ConfigureSeizureVault { issuer: Pubkey, mint: Pubkey, vault: Pubkey },
+    SeizeToVault {
+        permanent_delegate: Pubkey,
+        mint: Pubkey,
+        from: Pubkey,
+        seizure_record: Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+        case_hash: [u8; 32],
+    },
+    ReleaseFromVault {
+        permanent_delegate: Pubkey,
+        seizure_record: Pubkey,
+        destination_token_account: Pubkey,
+        resolution_hash: [u8; 32],
+    },
+    ForfeitFromVault { permanent_delegate: Pubkey, seizure_record: Pubkey, resolution_hash: [u8; 32] },
+    ConfigureTreasuryVault { issuer: Pubkey, mint: Pubkey, vault: Pubkey },
+    MintToTreasury { issuer: Pubkey, mint: Pubkey, vault: Pubkey, amount: u64, operation_id: [u8; 16] },
+    DistributeFromTreasury {
+        distributor: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        kyc_user: Pubkey,
+        amount: u64,
+        operation_id: [u8; 16],
+    },
+    AutoThaw { freeze_record: Pubkey },
+    ConfigureTransferFee { issuer: Pubkey, mint: Pubkey, fee_treasury: Pubkey, transfer_fee_bps: u16, max_transfer_fee: u64 },
+    WithdrawWithheldFees { issuer: Pubkey, mint: Pubkey, fee_treasury: Pubkey },
+    HarvestFeesToTreasury { issuer: Pubkey, mint: Pubkey, fee_treasury: Pubkey, sources: Vec<Pubkey> },
+    EnableConfidentialTransfers { issuer: Pubkey, auditor: Pubkey, mint: Pubkey, auditor_elgamal_pubkey: [u8; 32] },
+    ApproveConfidentialAccount { owner: Pubkey, mint: Pubkey, token_account: Pubkey },
+    SetInterestRate { issuer: Pubkey, mint: Pubkey, rate_change: Pubkey, rate: i16 },
+    GrantRole { admin: Pubkey, scope: Pubkey, holder: Pubkey, role: u8 },
+    RevokeRole { admin: Pubkey, role_assignment: Pubkey, role: u8 },
+    SetMinterAllowance { issuer: Pubkey, mint: Pubkey, minter: Pubkey, allowance: u64 },
+    VerifyReserveInclusion { mint_info: Pubkey, leaf: [u8; 32] },
+    ReserveInclusion { mint_info: Pubkey, leaf: [u8; 32] },
+    VerifyArchivedReserveInclusion { reserve_proof_epoch: Pubkey, epoch: u64, leaf: [u8; 32] },
+    BatchMintTokens {
+        issuer: Pubkey,
+        mint: Pubkey,
+        token_accounts: Vec<Pubkey>,
+        amounts: Vec<u64>,
+        operation_id: [u8; 16],
+    },
+    BatchFreezeAccounts {
+        freeze_authority: Pubkey,
+        mint: Pubkey,
+        token_accounts: Vec<Pubkey>,
+        reason_code: u8,
+        legal_reference: String,
+    },
+    BatchThawAccounts { freeze_authority: Pubkey, mint: Pubkey, token_accounts: Vec<Pubkey> },
+    EnforceBlacklist { caller: Pubkey, mint: Pubkey, token_account: Pubkey, blacklist_entry: Pubkey },
+    SetSupplyCap { issuer: Pubkey, mint_info: Pubkey, max_supply: u64 },
+    ApplySupplyCap { mint_info: Pubkey },
+    SetIssuanceSchedule { issuer: Pubkey, mint_info: Pubkey, monthly_limit: u64 },
+    ConfigureTimelock { issuer: Pubkey, mint_info: Pubkey, timelock: Pubkey, delay: i64 },
+    QueueOperation { issuer: Pubkey, timelock: Pubkey, operation: Pubkey, action: TimelockAction },
+    ExecuteOperation { mint: Pubkey, operation: Pubkey },
+    CancelOperation { issuer: Pubkey, operation: Pubkey },
+    UpdateWhitepaperUri {
+        issuer: Pubkey,
+        mint_info: Pubkey,
+        whitepaper_version: Pubkey,
+        uri: String,
+        content_hash: [u8; 32],
+    },
+    InitializeTokenMetadata { issuer: Pubkey, mint: Pubkey, name: String, uri: String },
+    UpdateMetadataField { issuer: Pubkey, mint: Pubkey, field: MetadataField, value: String },
+    PublishComplianceSnapshot {
+        issuer: Pubkey,
+        mint: Pubkey,
+        compliance_snapshot: Pubkey,
+        period: u32,
+        frozen_accounts: u64,
+        seizure_count: u64,
+    },
+    SetCountryPolicy {
+        authority: Pubkey,
+        country_policy: Pubkey,
+        country_code: String,
+        allowed: bool,
+        max_transaction_amount: u64,
+    },
+    RelinkIban {
+        authority: Pubkey,
+        iban_registry: Pubkey,
+        new_user: Pubkey,
+    },
+    MigrateKycUser {
+        authority: Pubkey,
+        old_kyc_user: Pubkey,
+        new_user: Pubkey,
+        mint: Pubkey,
+        old_token_account: Pubkey,
+    },
+    RequestRecovery {
+        issuer: Pubkey,
+        mint: Pubkey,
+        source_token_account: Pubkey,
+        destination_token_account: Pubkey,
+        recovery_request: Pubkey,
+        evidence_hash: [u8; 32],
+    },
+    ApproveRecovery {
+        officer: Pubkey,
+        recovery_request: Pubkey,
+    },
+    RecoverAccount {
+        issuer: Pubkey,
+        mint: Pubkey,
+        recovery_request: Pubkey,
+        source_token_account: Pubkey,
+        destination_token_account: Pubkey,
+    },
+    CancelRecovery {
+        issuer: Pubkey,
+        recovery_request: Pubkey,
+    },
+    RegisterCounterparty {
+        issuer: Pubkey,
+        approved_counterparty: Pubkey,
+        wallet: Pubkey,
+        kind: CounterpartyKind,
+        lei: [u8; 20],
+        daily_limit_exempt: bool,
+        max_transfer_amount: u64,
+    },
+    RemoveCounterparty {
+        issuer: Pubkey,
+        approved_counterparty: Pubkey,
+    },
+    ConfigureAmlAlerts {
+        issuer: Pubkey,
+        aml_alert_config: Pubkey,
+        max_hourly_transfers: u8,
+        max_hourly_volume: u64,
+        structuring_threshold: u64,
+        structuring_margin: u64,
+        structuring_count: u8,
+    },
+    AnchorSarReference {
+        officer: Pubkey,
+        kyc_user: Pubkey,
+        sar_reference: Pubkey,
+        sar_hash: [u8; 32],
+        filing_date: i64,
+    },
+    GetUserComplianceStatus { user: Pubkey },
+    GetMintStatus { mint: Pubkey },
+    GetReserveStatus { mint: Pubkey },
+    GetOutstandingSupply { mint: Pubkey },
+    AssertTransferAllowed { mint_info: Pubkey, source_owner: Pubkey, dest_owner: Pubkey, amount: u64 },
+    TransferWithAuthorization {
+        relayer: Pubkey,
+        owner: Pubkey,
+        source_token: Pubkey,
+        destination_token: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        nonce: [u8; 32],
+        expiry: i64,
+    },
+    ConfigureRelayerRegistry { issuer: Pubkey, relayer_registry: Pubkey, min_stake: u64 },
+    RegisterRelayer { relayer: Pubkey, relayer_record: Pubkey, stake: u64 },
+    SetRelayerStatus { issuer: Pubkey, relayer_record: Pubkey, status: RelayerStatus },
+    DeregisterRelayer { relayer: Pubkey, relayer_record: Pubkey },
+    ConfigureEscrowVault { issuer: Pubkey, mint: Pubkey, vault: Pubkey },
+    OpenEscrow {
+        buyer: Pubkey,
+        mint: Pubkey,
+        seller_token_account: Pubkey,
+        escrow: Pubkey,
+        escrow_id: u64,
+        amount: u64,
+        arbiter: Pubkey,
+        terms_hash: [u8; 32],
+        expires_at: i64,
+    },
+    ReleaseEscrow { authority: Pubkey, escrow: Pubkey, destination_token_account: Pubkey },
+    RefundEscrow { authority: Pubkey, escrow: Pubkey, destination_token_account: Pubkey },
+    CreatePaymentRequest {
+        payee: Pubkey,
+        payment_request: Pubkey,
+        request_id: u64,
+        amount: u64,
+        memo_hash: [u8; 32],
+        expires_at: i64,
+    },
+    PayRequest { payer: Pubkey, payment_request: Pubkey, payee_token_account: Pubkey },
+    /// Program instruction without a typed record yet
+    Unrecognized { discriminator: [u8; 8] },
+}
+
+/// A decoded transfer hook program instruction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HookOperation {
+    InitializeExtraAccountMetaList { issuer: Pubkey, mint: Pubkey, extra_account_meta_list: Pubkey },
+    UpdateExtraAccountMetaList { issuer: Pubkey, mint: Pubkey, extra_account_meta_list: Pubkey },
+    InitializeTransferRecord { payer: Pubkey, user: Pubkey },
+    AttachTravelRuleInfo {
+        originator: Pubkey,
+        source_token: Pubkey,
+        destination_token: Pubkey,
+        amount: u64,
+        originator_hash: [u8; 32],
+        beneficiary_hash: [u8; 32],
+    },
+    InitializeVelocityRecord { payer: Pubkey, user: Pubkey },
+    /// Transfer hook `Execute` invoked by Token-2022 during a transfer
+    Execute {
+        source: Pubkey,
+        mint: Pubkey,
+        destination: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    },
+    Unrecognized { discriminator: [u8; 8] },
+}
+
+/// A decoded Token-2022 instruction
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenOperation {
+    Transfer {
+        source: Pubkey,
+        destination: Pubkey,
+        authority: Pubkey,
+        mint: Option<Pubkey>, // Only known for checked transfers
+        amount: u64,
+    },
+    MintTo { mint: Pubkey, destination: Pubkey, authority: Pubkey, amount: u64 },
+    Burn { source: Pubkey, mint: Pubkey, authority: Pubkey, amount: u64 },
+    Approve { source: Pubkey, delegate: Pubkey, owner: Pubkey, amount: u64 },
+    Revoke { source: Pubkey, owner: Pubkey },
+    FreezeAccount { account: Pubkey, mint: Pubkey, authority: Pubkey },
+    ThawAccount { account: Pubkey, mint: Pubkey, authority: Pubkey },
+    SetAuthority { account: Pubkey, authority_type: AuthorityType, new_authority: Option<Pubkey> },
+    /// Any other Token-2022 instruction (account setup, extensions, ...)
+    Other { name: String },
+}
+
+/// Operation carried by one instruction
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operation {
+    Program(ProgramOperation),
+    Hook(HookOperation),
+    Token(TokenOperation),
+    /// Instruction of a program the decoder does not know about
+    Unknown { program_id: Pubkey },
+}
+
+/// A typed operation with its position in the history
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationRecord {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub instruction_index: usize,   // Top-level instruction index
+    pub inner_index: Option<usize>, // Index within the inner instructions, if a CPI
+    pub operation: Operation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The instruction data could not be deserialized
+    InvalidData { program_id: Pubkey },
+    /// The instruction references fewer accounts than its layout requires
+    MissingAccount { program_id: Pubkey, index: usize },
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::InvalidData { program_id } => {
+                write!(f, "invalid instruction data for program {}", program_id)
+            }
+            DecodeError::MissingAccount { program_id, index } => {
+                write!(f, "missing account {} for program {}", index, program_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Subsystem raising a program error
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCategory {
+    Kyc,            // KYC registration and status management
+    Aml,            // AML authorities and blacklists
+    Screening,      // Sanctions screening attestations and signatures
+    Policy,         // Compliance rule failures
+    TransferHook,   // Transfer hook invocation and accounts
+    Token,          // Mint, burn, freeze and pause
+    StandingOrder,  // Standing orders
+    Operation,      // Operation IDs and arithmetic
+    Migration,      // Mint migration
+    Edd,            // Enhanced due diligence queue
+    Approval,       // N-of-M approval sets
+    Limit,          // Daily tier limits
+    Guard,          // Reentrancy and CPI depth guards
+    Reserve,        // Reserve proofs and their attestation
+    Snapshot,       // Holder-balance snapshots
+    TravelRule,     // Travel-rule records
+    Seizure,        // Seizure escrow vault
+    Role,           // Role registry
+    Supply,         // Supply cap and issuance schedule
+    Timelock,       // Timelocked parameter changes
+    Recovery,       // Lost-key account recovery
+    Escrow,         // Commerce escrows
+    PaymentRequest, // Request-to-pay invoices
+}
+
+/// A documented program error code
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorCodeInfo {
+    pub code: u32,
+    pub error: MicaEurError,
+    pub category: ErrorCategory,
+}
+
+const fn code(code: u32, error: MicaEurError, category: ErrorCategory) -> ErrorCodeInfo {
+    ErrorCodeInfo { code, error, category }
+}
+
+/// Every error code returned by the MiCA EUR and transfer hook programs (both raise
+/// `MicaEurError`). Codes are stable: new errors get new codes at the end, existing
+/// codes never change meaning.
+pub const ERROR_CODES: [ErrorCodeInfo; 199] = [
+    code(6000, MicaEurError::UserNotVerified, ErrorCategory::Kyc),
+    code(6001, MicaEurError::UserVerificationExpired, ErrorCategory::Kyc),
+    code(6002, MicaEurError::InsufficientVerificationLevel, ErrorCategory::Kyc),
+    code(6003, MicaEurError::TransactionAmountExceedsMaximum, ErrorCategory::Policy),
+    code(6004, MicaEurError::UnsupportedCountry, ErrorCategory::Kyc),
+    code(6005, MicaEurError::AccountAlreadyFrozen, ErrorCategory::Token),
+    code(6006, MicaEurError::AccountNotFrozen, ErrorCategory::Token),
+    code(6007, MicaEurError::NotMintAuthority, ErrorCategory::Token),
+    code(6008, MicaEurError::InvalidKycStatus, ErrorCategory::Kyc),
+    code(6009, MicaEurError::InvalidVerificationLevel, ErrorCategory::Kyc),
+    code(6010, MicaEurError::InvalidExpiryDate, ErrorCategory::Kyc),
+    code(6011, MicaEurError::UserAlreadyRegistered, ErrorCategory::Kyc),
+    code(6012, MicaEurError::InvalidCountryCode, ErrorCategory::Kyc),
+    code(6013, MicaEurError::TokenAccountOwnerMismatch, ErrorCategory::Token),
+    code(6014, MicaEurError::AmlAuthorityInactive, ErrorCategory::Aml),
+    code(6015, MicaEurError::UnauthorizedAmlAuthority, ErrorCategory::Aml),
+    code(6016, MicaEurError::BlacklistEntryAlreadyExists, ErrorCategory::Aml),
+    code(6017, MicaEurError::InvalidStandingOrderSchedule, ErrorCategory::StandingOrder),
+    code(6018, MicaEurError::StandingOrderNotActive, ErrorCategory::StandingOrder),
+    code(6019, MicaEurError::StandingOrderNotPaused, ErrorCategory::StandingOrder),
+    code(6020, MicaEurError::StandingOrderNotDue, ErrorCategory::StandingOrder),
+    code(6021, MicaEurError::TokenAccountMintMismatch, ErrorCategory::Token),
+    code(6022, MicaEurError::ArithmeticOverflow, ErrorCategory::Operation),
+    code(6023, MicaEurError::AccountClassUnchanged, ErrorCategory::Kyc),
+    code(6024, MicaEurError::KybLinkRequired, ErrorCategory::Policy),
+    code(6025, MicaEurError::IncomingMemoRequired, ErrorCategory::Policy),
+    code(6026, MicaEurError::ScreeningProviderInactive, ErrorCategory::Screening),
+    code(6027, MicaEurError::StaleScreeningAttestation, ErrorCategory::Screening),
+    code(6028, MicaEurError::SanctionsScreeningOutdated, ErrorCategory::Policy),
+    code(6029, MicaEurError::MissingSignatureInstruction, ErrorCategory::Screening),
+    code(6030, MicaEurError::InvalidSignatureInstruction, ErrorCategory::Screening),
+    code(6031, MicaEurError::SignatureMismatch, ErrorCategory::Screening),
+    code(6032, MicaEurError::InvalidOperationId, ErrorCategory::Operation),
+    code(6033, MicaEurError::OperationAlreadyProcessed, ErrorCategory::Operation),
+    code(6034, MicaEurError::UserBlacklisted, ErrorCategory::Policy),
+    code(6035, MicaEurError::NotTransferring, ErrorCategory::TransferHook),
+    code(6036, MicaEurError::InvalidStagedPolicy, ErrorCategory::Policy),
+    code(6037, MicaEurError::MintInactive, ErrorCategory::Token),
+    code(6038, MicaEurError::InvalidMigrationTarget, ErrorCategory::Migration),
+    code(6039, MicaEurError::MigrationClosed, ErrorCategory::Migration),
+    code(6040, MicaEurError::NothingToMigrate, ErrorCategory::Migration),
+    code(6041, MicaEurError::TokenPaused, ErrorCategory::Token),
+    code(6042, MicaEurError::PauseStateUnchanged, ErrorCategory::Token),
+    code(6043, MicaEurError::InvalidAmount, ErrorCategory::Token),
+    code(6044, MicaEurError::EddApprovalRequired, ErrorCategory::Edd),
+    code(6045, MicaEurError::EddNotRequired, ErrorCategory::Edd),
+    code(6046, MicaEurError::InvalidEddConfig, ErrorCategory::Edd),
+    code(6047, MicaEurError::EddApprovalNotPending, ErrorCategory::Edd),
+    code(6048, MicaEurError::EddApprovalExpired, ErrorCategory::Edd),
+    code(6049, MicaEurError::EddApprovalNotExpired, ErrorCategory::Edd),
+    code(6050, MicaEurError::EddDestinationMismatch, ErrorCategory::Edd),
+    code(6051, MicaEurError::InvalidApprovalSet, ErrorCategory::Approval),
+    code(6052, MicaEurError::MultisigApprovalRequired, ErrorCategory::Approval),
+    code(6053, MicaEurError::NotApprovalSigner, ErrorCategory::Approval),
+    code(6054, MicaEurError::ActionAlreadyApproved, ErrorCategory::Approval),
+    code(6055, MicaEurError::ProposalNotOpen, ErrorCategory::Approval),
+    code(6056, MicaEurError::ProposalExpired, ErrorCategory::Approval),
+    code(6057, MicaEurError::ApprovalSetChanged, ErrorCategory::Approval),
+    code(6058, MicaEurError::ApprovalThresholdNotMet, ErrorCategory::Approval),
+    code(6059, MicaEurError::ActionAccountMismatch, ErrorCategory::Approval),
+    code(6060, MicaEurError::DailyMintLimitExceeded, ErrorCategory::Limit),
+    code(6061, MicaEurError::DailyBurnLimitExceeded, ErrorCategory::Limit),
+    code(6062, MicaEurError::DailyTransferLimitExceeded, ErrorCategory::Limit),
+    code(6063, MicaEurError::TransferRecordRequired, ErrorCategory::TransferHook),
+    code(6064, MicaEurError::ReentrantInvocation, ErrorCategory::Guard),
+    code(6065, MicaEurError::InvocationTooDeep, ErrorCategory::Guard),
+    code(6066, MicaEurError::HookInvokedDirectly, ErrorCategory::TransferHook),
+    code(6067, MicaEurError::HookAccountOwnerMismatch, ErrorCategory::TransferHook),
+    code(6068, MicaEurError::KycRegistrationMissing, ErrorCategory::Policy),
+    code(6069, MicaEurError::StagedPolicyVersionNotNewer, ErrorCategory::Policy),
+    code(6070, MicaEurError::MigrationTargetInactive, ErrorCategory::Migration),
+    code(6071, MicaEurError::MigrationIssuerMismatch, ErrorCategory::Migration),
+    code(6072, MicaEurError::MigrationMintMismatch, ErrorCategory::Migration),
+    code(6073, MicaEurError::InvalidReserveAuditor, ErrorCategory::Reserve),
+    code(6074, MicaEurError::NotReserveAuditor, ErrorCategory::Reserve),
+    code(6075, MicaEurError::ReserveRootMismatch, ErrorCategory::Reserve),
+    code(6076, MicaEurError::AttestationWindowElapsed, ErrorCategory::Reserve),
+    code(6077, MicaEurError::MintingHalted, ErrorCategory::Reserve),
+    code(6078, MicaEurError::ReserveShortfall, ErrorCategory::Reserve),
+    code(6079, MicaEurError::InvalidSnapshot, ErrorCategory::Snapshot),
+    code(6080, MicaEurError::KycUserNotClosable, ErrorCategory::Kyc),
+    code(6081, MicaEurError::KycUserHasBalance, ErrorCategory::Kyc),
+    code(6082, MicaEurError::NotASupervisedInstitution, ErrorCategory::Policy),
+    code(6083, MicaEurError::InvalidInstitution, ErrorCategory::Policy),
+    code(6084, MicaEurError::UnsupportedTokenExtension, ErrorCategory::Token),
+    code(6085, MicaEurError::NotKycAuthority, ErrorCategory::Kyc),
+    code(6086, MicaEurError::KycProviderMismatch, ErrorCategory::Kyc),
+    code(6087, MicaEurError::InvalidKycProvider, ErrorCategory::Kyc),
+    code(6088, MicaEurError::InvalidReconciliationRecord, ErrorCategory::Reserve),
+    code(6089, MicaEurError::TravelRuleRecordRequired, ErrorCategory::TravelRule),
+    code(6090, MicaEurError::TravelRuleRecordMismatch, ErrorCategory::TravelRule),
+    code(6091, MicaEurError::InvalidTravelRuleData, ErrorCategory::TravelRule),
+    code(6092, MicaEurError::SeizureVaultInUse, ErrorCategory::Seizure),
+    code(6093, MicaEurError::SeizureNotHeld, ErrorCategory::Seizure),
+    code(6094, MicaEurError::SeizureDestinationMissing, ErrorCategory::Seizure),
+    code(6095, MicaEurError::InvalidSeizureResolution, ErrorCategory::Seizure),
+    code(6096, MicaEurError::InvalidFreezeRecord, ErrorCategory::Token),
+    code(6097, MicaEurError::FreezeNotExpired, ErrorCategory::Token),
+    code(6098, MicaEurError::AccountFrozenByRecord, ErrorCategory::Token),
+    code(6099, MicaEurError::FrozenAmountLocked, ErrorCategory::Token),
+    code(6100, MicaEurError::InvalidTransferFee, ErrorCategory::Token),
+    code(6101, MicaEurError::FeeTreasuryMismatch, ErrorCategory::Token),
+    code(6102, MicaEurError::ConfidentialTransfersDisabled, ErrorCategory::Token),
+    code(6103, MicaEurError::InvalidAuditorKey, ErrorCategory::Token),
+    code(6104, MicaEurError::ConfidentialTransferFeeConflict, ErrorCategory::Token),
+    code(6105, MicaEurError::MissingRole, ErrorCategory::Role),
+    code(6106, MicaEurError::NotRoleAdmin, ErrorCategory::Role),
+    code(6107, MicaEurError::InvalidRole, ErrorCategory::Role),
+    code(6108, MicaEurError::MinterAllowanceRequired, ErrorCategory::Role),
+    code(6109, MicaEurError::MinterAllowanceExceeded, ErrorCategory::Role),
+    code(6110, MicaEurError::ReserveLeafNotIncluded, ErrorCategory::Reserve),
+    code(6111, MicaEurError::InvalidBatchMint, ErrorCategory::Token),
+    code(6112, MicaEurError::BatchAccountMismatch, ErrorCategory::Token),
+    code(6113, MicaEurError::InvalidBatchFreeze, ErrorCategory::Token),
+    code(6114, MicaEurError::BlacklistEntryInactive, ErrorCategory::Aml),
+    code(6115, MicaEurError::SupplyCapExceeded, ErrorCategory::Supply),
+    code(6116, MicaEurError::IssuanceScheduleExceeded, ErrorCategory::Supply),
+    code(6117, MicaEurError::IssuancePolicyRequired, ErrorCategory::Supply),
+    code(6118, MicaEurError::SupplyCapChangeNotDue, ErrorCategory::Supply),
+    code(6119, MicaEurError::TimelockRequired, ErrorCategory::Timelock),
+    code(6120, MicaEurError::InvalidTimelockDelay, ErrorCategory::Timelock),
+    code(6121, MicaEurError::InvalidTimelockAction, ErrorCategory::Timelock),
+    code(6122, MicaEurError::TimelockOperationNotQueued, ErrorCategory::Timelock),
+    code(6123, MicaEurError::TimelockOperationNotReady, ErrorCategory::Timelock),
+    code(6124, MicaEurError::TimelockAccountMismatch, ErrorCategory::Timelock),
+    code(6125, MicaEurError::InvalidWhitepaper, ErrorCategory::Token),
+    code(6126, MicaEurError::InvalidMetadataField, ErrorCategory::Token),
+    code(6127, MicaEurError::InvalidComplianceSnapshot, ErrorCategory::Snapshot),
+    code(6128, MicaEurError::CountryPolicyMismatch, ErrorCategory::Kyc),
+    code(6129, MicaEurError::IbanAlreadyLinked, ErrorCategory::Kyc),
+    code(6130, MicaEurError::InvalidIbanHash, ErrorCategory::Kyc),
+    code(6131, MicaEurError::InvalidIbanRelink, ErrorCategory::Kyc),
+    code(6132, MicaEurError::RecoveryAlreadyRequested, ErrorCategory::Recovery),
+    code(6133, MicaEurError::RecoveryNotPending, ErrorCategory::Recovery),
+    code(6134, MicaEurError::RecoveryNotApproved, ErrorCategory::Recovery),
+    code(6135, MicaEurError::RecoveryNotDue, ErrorCategory::Recovery),
+    code(6136, MicaEurError::RecoveryAccountMismatch, ErrorCategory::Recovery),
+    code(6137, MicaEurError::RecoveryBlockedByFreeze, ErrorCategory::Recovery),
+    code(6138, MicaEurError::CounterpartyTransferLimitExceeded, ErrorCategory::Policy),
+    code(6139, MicaEurError::InvalidCounterparty, ErrorCategory::Policy),
+    code(6140, MicaEurError::InvalidAmlAlertConfig, ErrorCategory::Aml),
+    code(6141, MicaEurError::VelocityRecordRequired, ErrorCategory::TransferHook),
+    code(6142, MicaEurError::InvalidSarReference, ErrorCategory::Aml),
+    code(6143, MicaEurError::InvalidBlacklistReason, ErrorCategory::Aml),
+    code(6144, MicaEurError::InvalidBlacklistSchedule, ErrorCategory::Aml),
+    code(6145, MicaEurError::BlacklistEntryNotExpired, ErrorCategory::Aml),
+    code(6146, MicaEurError::AmlAuthorityOutOfJurisdiction, ErrorCategory::Aml),
+    code(6147, MicaEurError::InvalidAmlPowers, ErrorCategory::Aml),
+    code(6148, MicaEurError::UnauthorizedAmlAdmin, ErrorCategory::Aml),
+    code(6149, MicaEurError::InvalidRegulator, ErrorCategory::Aml),
+    code(6150, MicaEurError::UnsupportedDecimals, ErrorCategory::Token),
+    code(6151, MicaEurError::AssociatedTokenAccountRequired, ErrorCategory::Token),
+    code(6152, MicaEurError::KycNotExpired, ErrorCategory::Kyc),
+    code(6153, MicaEurError::StringTooLong, ErrorCategory::Operation),
+    code(6154, MicaEurError::InvalidBlz, ErrorCategory::Kyc),
+    code(6155, MicaEurError::InvalidVerificationProvider, ErrorCategory::Kyc),
+    code(6156, MicaEurError::InvalidIpfsCid, ErrorCategory::Reserve),
+    code(6157, MicaEurError::InvalidKycStatusTransition, ErrorCategory::Kyc),
+    code(6158, MicaEurError::ComplianceHistoryMismatch, ErrorCategory::Aml),
+    code(6159, MicaEurError::MintAlreadyActive, ErrorCategory::Token),
+    code(6160, MicaEurError::MintMigrated, ErrorCategory::Migration),
+    code(6161, MicaEurError::WindDownAlreadyInitiated, ErrorCategory::Token),
+    code(6162, MicaEurError::InvalidRedemptionDeadline, ErrorCategory::Token),
+    code(6163, MicaEurError::MintWindingDown, ErrorCategory::Token),
+    code(6164, MicaEurError::TreasuryVaultInUse, ErrorCategory::Token),
+    code(6165, MicaEurError::MintVoucherClaimed, ErrorCategory::Token),
+    code(6166, MicaEurError::InvalidSepaReference, ErrorCategory::Token),
+    code(6167, MicaEurError::NotARedemption, ErrorCategory::Operation),
+    code(6168, MicaEurError::InvalidReserveComposition, ErrorCategory::Reserve),
+    code(6169, MicaEurError::ReserveCompositionMismatch, ErrorCategory::Reserve),
+    code(6170, MicaEurError::ReserveProofStale, ErrorCategory::Reserve),
+    code(6171, MicaEurError::InvalidMaxReserveAge, ErrorCategory::Reserve),
+    code(6172, MicaEurError::InvalidReserveValuation, ErrorCategory::Reserve),
+    code(6173, MicaEurError::ReserveValuationRequired, ErrorCategory::Reserve),
+    code(6174, MicaEurError::InvalidPriceUpdate, ErrorCategory::Reserve),
+    code(6175, MicaEurError::StalePriceUpdate, ErrorCategory::Reserve),
+    code(6176, MicaEurError::UnpricedReserveAsset, ErrorCategory::Reserve),
+    code(6177, MicaEurError::ReserveValuationDivergence, ErrorCategory::Reserve),
+    code(6178, MicaEurError::RegulatorAccessInactive, ErrorCategory::Aml),
+    code(6179, MicaEurError::InvalidAuditWindow, ErrorCategory::Aml),
+    code(6180, MicaEurError::InvalidAllowlistedProgram, ErrorCategory::Policy),
+    code(6181, MicaEurError::ProgramAlreadyAllowlisted, ErrorCategory::Policy),
+    code(6182, MicaEurError::ProgramAllowlistFull, ErrorCategory::Policy),
+    code(6183, MicaEurError::ProgramNotAllowlisted, ErrorCategory::Policy),
+    code(6184, MicaEurError::InvalidProgramEscrow, ErrorCategory::Policy),
+    code(6185, MicaEurError::TransferAuthorizationExpired, ErrorCategory::Token),
+    code(6186, MicaEurError::RelayerStakeTooLow, ErrorCategory::Operation),
+    code(6187, MicaEurError::RelayerNotRegistered, ErrorCategory::Operation),
+    code(6188, MicaEurError::RelayerSuspended, ErrorCategory::Operation),
+    code(6189, MicaEurError::RelayerStatusUnchanged, ErrorCategory::Operation),
+    code(6190, MicaEurError::InvalidEscrow, ErrorCategory::Escrow),
+    code(6191, MicaEurError::EscrowVaultInUse, ErrorCategory::Escrow),
+    code(6192, MicaEurError::EscrowNotOpen, ErrorCategory::Escrow),
+    code(6193, MicaEurError::UnauthorizedEscrowResolution, ErrorCategory::Escrow),
+    code(6194, MicaEurError::EscrowDestinationMismatch, ErrorCategory::Escrow),
+    code(6195, MicaEurError::InvalidPaymentRequest, ErrorCategory::PaymentRequest),
+    code(6196, MicaEurError::PaymentRequestNotOpen, ErrorCategory::PaymentRequest),
+    code(6197, MicaEurError::PaymentRequestExpired, ErrorCategory::PaymentRequest),
+    code(6198, MicaEurError::InvalidTierLimits, ErrorCategory::Limit),
+];
+
+// Fail the build if a code in the table drifts from the program's enum
+const _: () = {
+    let mut index = 0;
+    while index < ERROR_CODES.len() {
+        let info = &ERROR_CODES[index];
+        assert!(info.error as u32 + anchor_lang::error::ERROR_CODE_OFFSET == info.code);
+        index += 1;
+    }
+};
+
+/// Look up a custom program error code, e.g. from `InstructionError::Custom`
+pub fn error_info(code: u32) -> Option<&'static ErrorCodeInfo> {
+    ERROR_CODES.iter().find(|info| info.code == code)
+}
+
+/// Decode every instruction of a transaction, including inner CPIs, in execution order
+pub fn decode_transaction(tx: &RawTransaction) -> Result<Vec<OperationRecord>, DecodeError> {
+    let mut records = Vec::new();
+    for (index, instruction) in tx.instructions.iter().enumerate() {
+        records.push(record(tx, index, None, decode_instruction(instruction)?));
+
+        let inner = tx.inner_instructions.iter().filter(|inner| inner.index == index);
+        for (inner_index, instruction) in inner.flat_map(|i| i.instructions.iter()).enumerate() {
+            let operation = decode_instruction(instruction)?;
+            records.push(record(tx, index, Some(inner_index), operation));
+        }
+    }
+    Ok(records)
+}
+
+/// Decode a batch of transactions, e.g. a page of signature history
+pub fn decode_history(txs: &[RawTransaction]) -> Result<Vec<OperationRecord>, DecodeError> {
+    let mut records = Vec::new();
+    for tx in txs {
+        records.extend(decode_transaction(tx)?);
+    }
+    Ok(records)
+}
+
+/// Decode a single instruction of any program
+pub fn decode_instruction(instruction: &RawInstruction) -> Result<Operation, DecodeError> {
+    if instruction.program_id == mica_eur::ID {
+        decode_program_instruction(instruction).map(Operation::Program)
+    } else if instruction.program_id == mica_eur_transfer_hook::ID {
+        decode_hook_instruction(instruction).map(Operation::Hook)
+    } else if instruction.program_id == spl_token_2022::ID {
+        decode_token_instruction(instruction).map(Operation::Token)
+    } else {
+        Ok(Operation::Unknown { program_id: instruction.program_id })
+    }
+}
+
+fn record(
+    tx: &RawTransaction,
+    instruction_index: usize,
+    inner_index: Option<usize>,
+    operation: Operation,
+) -> OperationRecord {
+    OperationRecord {
+        signature: tx.signature.clone(),
+        slot: tx.slot,
+        block_time: tx.block_time,
+        instruction_index,
+        inner_index,
+        operation,
+    }
+}
+
+fn account(instruction: &RawInstruction, index: usize) -> Result<Pubkey, DecodeError> {
+    instruction.accounts.get(index).copied().ok_or(DecodeError::MissingAccount {
+        program_id: instruction.program_id,
+        index,
+    })
+}
+
+fn args<T: AnchorDeserialize>(instruction: &RawInstruction) -> Result<T, DecodeError> {
+    let invalid = DecodeError::InvalidData { program_id: instruction.program_id };
+    let mut data = instruction.data.get(8..).ok_or(invalid.clone())?;
+    T::deserialize(&mut data).map_err(|_| invalid)
+}
+
+/// Decode a MiCA EUR instruction. Account positions follow the field order of the
+/// program's `Accounts` structs.
+pub fn decode_program_instruction(
+    instruction: &RawInstruction,
+) -> Result<ProgramOperation, DecodeError> {
+    let discriminator = instruction
+        .data
+        .get(..8)
+        .ok_or(DecodeError::InvalidData { program_id: instruction.program_id })?;
+    let a = |index| account(instruction, index);
+
+    macro_rules! is {
+        ($t:ty) => {
+            discriminator == <$t as Discriminator>::DISCRIMINATOR
+        };
+    }
+
+    let operation = if is!(ix::Initialize) {
+        ProgramOperation::Initialize
+    } else if is!(ix::InitializeKycOracle) {
+        ProgramOperation::InitializeKycOracle { authority: a(0)? }
+    } else if is!(ix::RegisterKycUser) {
+        let args: ix::RegisterKycUser = args(instruction)?;
+        ProgramOperation::RegisterKycUser {
+            authority: a(0)?,
+            user: a(2)?,
+            country_code: args.country_code,
+            verification_provider: args.verification_provider,
+        }
+    } else if is!(ix::UpdateKycStatus) {
+        let args: ix::UpdateKycStatus = args(instruction)?;
+        ProgramOperation::UpdateKycStatus {
+            authority: a(0)?,
+            kyc_user: a(2)?,
+            status: args.status,
+            verification_level: args.verification_level,
+            expiry_days: args.expiry_days,
+        }
+    } else if is!(ix::ReclassifyAccount) {
+        let args: ix::ReclassifyAccount = args(instruction)?;
+        ProgramOperation::ReclassifyAccount {
+            authority: a(0)?,
+            kyc_user: a(2)?,
+            new_class: args.new_class,
+            kyb_hash: args.kyb_hash,
+        }
+    } else if is!(ix::RegisterScreeningProvider) {
+        let args: ix::RegisterScreeningProvider = args(instruction)?;
+        ProgramOperation::RegisterScreeningProvider {
+            authority: a(0)?,
+            signing_key: args.signing_key,
+            name: args.name,
+        }
+    } else if is!(ix::DeactivateScreeningProvider) {
+        ProgramOperation::DeactivateScreeningProvider {
+            authority: a(0)?,
+            screening_provider: a(2)?,
+        }
+    } else if is!(ix::SubmitScreeningAttestation) {
+        let args: ix::SubmitScreeningAttestation = args(instruction)?;
+        ProgramOperation::SubmitScreeningAttestation {
+            submitter: a(0)?,
+            screening_provider: a(1)?,
+            kyc_user: a(2)?,
+            screened_at: args.screened_at,
+            result_hash: args.result_hash,
+        }
+    } else if is!(ix::InitializeEuroMint) {
+        let args: ix::InitializeEuroMint = args(instruction)?;
+        ProgramOperation::InitializeEuroMint {
+            issuer: a(0)?,
+            mint: a(2)?,
+            whitepaper_uri: args.whitepaper_uri,
+        }
+    } else if is!(ix::CreateTokenAccount) {
+        ProgramOperation::CreateTokenAccount { owner: a(0)?, token_account: a(1)?, mint: a(2)? }
+    } else if is!(ix::CloseTokenAccount) {
+        ProgramOperation::CloseTokenAccount { owner: a(0)?, token_account: a(1)?, mint: a(2)? }
+    } else if is!(ix::ActivateTokenAccount) {
+        ProgramOperation::ActivateTokenAccount { caller: a(0)?, mint: a(2)?, token_account: a(3)? }
+    } else if is!(ix::EnforceKycExpiry) {
+        ProgramOperation::EnforceKycExpiry { caller: a(0)?, mint: a(2)?, token_account: a(3)? }
+    } else if is!(ix::OpenComplianceHistory) {
+        ProgramOperation::OpenComplianceHistory { payer: a(0)?, kyc_user: a(1)?, compliance_history: a(2)? }
+    } else if is!(ix::MintTokens) {
+        let args: ix::MintTokens = args(instruction)?;
+        ProgramOperation::MintTokens {
+            issuer: a(0)?,
+            mint: a(2)?,
+            token_account: a(3)?,
+            kyc_user: a(4)?,
+            amount: args.amount,
+            operation_id: args.operation_id,
+        }
+    } else if is!(ix::BurnTokens) {
+        let args: ix::BurnTokens = args(instruction)?;
+        ProgramOperation::BurnTokens {
+            owner: a(0)?,
+            mint: a(2)?,
+            token_account: a(3)?,
+            amount: args.amount,
+            operation_id: args.operation_id,
+        }
+    } else if is!(ix::FreezeAccount) {
+        let args: ix::FreezeAccount = args(instruction)?;
+        ProgramOperation::FreezeAccount {
+            freeze_authority: a(0)?,
+            mint: a(2)?,
+            token_account: a(3)?,
+            reason_code: args.reason_code,
+            legal_reference: args.legal_reference,
+            frozen_amount: args.frozen_amount,
+            expires_at: args.expires_at,
+        }
+    } else if is!(ix::ThawAccount) {
+        ProgramOperation::ThawAccount {
+            freeze_authority: a(0)?,
+            mint: a(2)?,
+            token_account: a(3)?,
+        }
+    } else if is!(ix::SeizeTokens) {
+        let args: ix::SeizeTokens = args(instruction)?;
+        ProgramOperation::SeizeTokens {
+            permanent_delegate: a(0)?,
+            mint: a(3)?,
+            from: a(4)?,
+            to: a(5)?,
+            amount: args.amount,
+            operation_id: args.operation_id,
+        }
+    } else if is!(ix::UpdateReserveProof) {
+        let args: ix::UpdateReserveProof = args(instruction)?;
+        ProgramOperation::UpdateReserveProof {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            merkle_root: args.merkle_root,
+            ipfs_cid: args.ipfs_cid,
+            reserve_total_eur: args.reserve_total_eur,
+            composition: args.composition,
+        }
+    } else if is!(ix::SyncRuleSet) {
+        ProgramOperation::SyncRuleSet { issuer: a(0)?, mint_info: a(1)? }
+    } else if is!(ix::RegisterAmlAuthority) {
+        let args: ix::RegisterAmlAuthority = args(instruction)?;
+        ProgramOperation::RegisterAmlAuthority {
+            authority: a(0)?,
+            approver: a(3)?,
+            authority_id: args.authority_id,
+            powers: args.powers,
+            jurisdiction: args.jurisdiction,
+            global: args.global,
+        }
+    } else if is!(ix::SetRegulator) {
+        let args: ix::SetRegulator = args(instruction)?;
+        ProgramOperation::SetRegulator { issuer: a(0)?, regulator_registry: a(2)?, regulator: args.regulator }
+    } else if is!(ix::CreateBlacklistEntry) {
+        let args: ix::CreateBlacklistEntry = args(instruction)?;
+        ProgramOperation::CreateBlacklistEntry {
+            authority: a(0)?,
+            user: a(2)?,
+            reason: args.reason,
+            legal_reference: args.legal_reference,
+            expiry_time: args.expiry_time,
+            review_time: args.review_time,
+        }
+    } else if is!(ix::DeactivateAmlAuthority) {
+        ProgramOperation::DeactivateAmlAuthority { approver: a(0)?, aml_authority: a(1)? }
+    } else if is!(ix::DeactivateBlacklistEntry) {
+        ProgramOperation::DeactivateBlacklistEntry { authority: a(0)?, user: a(2)? }
+    } else if is!(ix::ExtendBlacklistEntry) {
+        let args: ix::ExtendBlacklistEntry = args(instruction)?;
+        ProgramOperation::ExtendBlacklistEntry {
+            authority: a(0)?,
+            blacklist_entry: a(2)?,
+            expiry_time: args.expiry_time,
+            review_time: args.review_time,
+        }
+    } else if is!(ix::LapseBlacklistEntry) {
+        ProgramOperation::LapseBlacklistEntry { caller: a(0)?, blacklist_entry: a(1)? }
+    } else if is!(ix::ReclassifyBlacklistEntry) {
+        let args: ix::ReclassifyBlacklistEntry = args(instruction)?;
+        ProgramOperation::ReclassifyBlacklistEntry {
+            authority: a(0)?,
+            user: a(2)?,
+            reason: args.reason,
+            legal_reference: args.legal_reference,
+        }
+    } else if is!(ix::UpdateAmlAuthorityPowers) {
+        let args: ix::UpdateAmlAuthorityPowers = args(instruction)?;
+        ProgramOperation::UpdateAmlAuthorityPowers {
+            approver: a(0)?,
+            aml_authority: a(1)?,
+            new_powers: args.new_powers,
+        }
+    } else if is!(ix::CreateStandingOrder) {
+        let args: ix::CreateStandingOrder = args(instruction)?;
+        ProgramOperation::CreateStandingOrder {
+            payer: a(0)?,
+            standing_order: a(7)?,
+            payer_token_account: a(5)?,
+            payee_token_account: a(6)?,
+            order_id: args.order_id,
+            amount: args.amount,
+            interval_seconds: args.interval_seconds,
+            first_execution_time: args.first_execution_time,
+            end_time: args.end_time,
+        }
+    } else if is!(ix::PauseStandingOrder) {
+        standing_order_update(instruction, StandingOrderAction::Pause)?
+    } else if is!(ix::ResumeStandingOrder) {
+        standing_order_update(instruction, StandingOrderAction::Resume)?
+    } else if is!(ix::SkipStandingOrder) {
+        standing_order_update(instruction, StandingOrderAction::Skip)?
+    } else if is!(ix::CancelStandingOrder) {
+        standing_order_update(instruction, StandingOrderAction::Cancel)?
+    } else if is!(ix::ExecuteStandingOrder) {
+        ProgramOperation::ExecuteStandingOrder {
+            keeper: a(0)?,
+            standing_order: a(1)?,
+            payer_token_account: a(6)?,
+            payee_token_account: a(7)?,
+        }
+    } else if is!(ix::StagePolicy) {
+        let args: ix::StagePolicy = args(instruction)?;
+        ProgramOperation::StagePolicy {
+            issuer: a(0)?,
+            rule_set_version: args.rule_set_version,
+            params: args.params,
+        }
+    } else if is!(ix::ClearStagedPolicy) {
+        ProgramOperation::ClearStagedPolicy { issuer: a(0)?, staged_policy: a(2)? }
+    } else if is!(ix::StartMigration) {
+        ProgramOperation::StartMigration {
+            issuer: a(0)?,
+            old_mint_info: a(1)?,
+            new_mint_info: a(2)?,
+        }
+    } else if is!(ix::ClaimMigration) {
+        ProgramOperation::ClaimMigration {
+            holder: a(0)?,
+            old_mint: a(3)?,
+            new_mint: a(4)?,
+            old_token_account: a(6)?,
+            new_token_account: a(7)?,
+        }
+    } else if is!(ix::CompleteMigration) {
+        ProgramOperation::CompleteMigration { issuer: a(0)?, migration: a(1)? }
+    } else if is!(ix::PauseToken) {
+        ProgramOperation::PauseToken { authority: a(0)?, mint_info: a(1)? }
+    } else if is!(ix::ResumeToken) {
+        ProgramOperation::ResumeToken { authority: a(0)?, mint_info: a(1)? }
+    } else if is!(ix::DeactivateMint) {
+        ProgramOperation::DeactivateMint { issuer: a(0)?, mint_info: a(1)? }
+    } else if is!(ix::ReactivateMint) {
+        ProgramOperation::ReactivateMint { issuer: a(0)?, mint_info: a(1)? }
+    } else if is!(ix::InitiateWindDown) {
+        let args: ix::InitiateWindDown = args(instruction)?;
+        ProgramOperation::InitiateWindDown {
+            issuer: a(0)?,
+            mint: a(2)?,
+            redemption_account: a(3)?,
+            redemption_deadline: args.redemption_deadline,
+        }
+    } else if is!(ix::MigrateMintAuthority) {
+        ProgramOperation::MigrateMintAuthority { issuer: a(0)?, current_authority: a(1)?, mint: a(3)? }
+    } else if is!(ix::MigratePermanentDelegate) {
+        ProgramOperation::MigratePermanentDelegate { issuer: a(0)?, mint: a(3)? }
+    } else if is!(ix::CreateMintVoucher) {
+        let args: ix::CreateMintVoucher = args(instruction)?;
+        ProgramOperation::CreateMintVoucher {
+            issuer: a(0)?,
+            mint: a(2)?,
+            voucher: a(3)?,
+            user: args.user,
+            reference_hash: args.reference_hash,
+            amount: args.amount,
+        }
+    } else if is!(ix::ClaimMintVoucher) {
+        ProgramOperation::ClaimMintVoucher { user: a(0)?, mint: a(2)?, voucher: a(3)?, token_account: a(4)? }
+    } else if is!(ix::RecordPayoutReference) {
+        let args: ix::RecordPayoutReference = args(instruction)?;
+        ProgramOperation::RecordPayoutReference {
+            issuer: a(0)?,
+            payout_reference: a(3)?,
+            operation_id: args.operation_id,
+            reference_hash: args.reference_hash,
+        }
+    } else if is!(ix::GrantRegulatorAccess) {
+        let args: ix::GrantRegulatorAccess = args(instruction)?;
+        ProgramOperation::GrantRegulatorAccess {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            regulator_access: a(2)?,
+            regulator: args.regulator,
+            authority_id: args.authority_id,
+            expires_at: args.expires_at,
+        }
+    } else if is!(ix::RevokeRegulatorAccess) {
+        ProgramOperation::RevokeRegulatorAccess { issuer: a(0)?, mint_info: a(1)?, regulator_access: a(2)? }
+    } else if is!(ix::ExportAuditWindow) {
+        let args: ix::ExportAuditWindow = args(instruction)?;
+        ProgramOperation::ExportAuditWindow {
+            issuer: a(0)?,
+            regulator_access: a(2)?,
+            from: args.from,
+            to: args.to,
+            event_count: args.event_count,
+            digest: args.digest,
+        }
+    } else if is!(ix::AllowlistProgram) {
+        let args: ix::AllowlistProgram = args(instruction)?;
+        ProgramOperation::AllowlistProgram { issuer: a(0)?, program_allowlist: a(2)?, program: args.program }
+    } else if is!(ix::DelistProgram) {
+        let args: ix::DelistProgram = args(instruction)?;
+        ProgramOperation::DelistProgram { issuer: a(0)?, program_allowlist: a(2)?, program: args.program }
+    } else if is!(ix::RegisterProgramEscrow) {
+        let args: ix::RegisterProgramEscrow = args(instruction)?;
+        ProgramOperation::RegisterProgramEscrow {
+            payer: a(0)?,
+            authority: a(3)?,
+            program_escrow: a(4)?,
+            program: args.program,
+        }
+    } else if is!(ix::SetPauser) {
+        let args: ix::SetPauser = args(instruction)?;
+        ProgramOperation::SetPauser {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            new_pauser: args.new_pauser,
+        }
+    } else if is!(ix::SetAssociatedAccountRequirement) {
+        let args: ix::SetAssociatedAccountRequirement = args(instruction)?;
+        ProgramOperation::SetAssociatedAccountRequirement { issuer: a(0)?, mint_info: a(1)?, required: args.required }
+    } else if is!(ix::SetEddFlag) {
+        let args: ix::SetEddFlag = args(instruction)?;
+        ProgramOperation::SetEddFlag {
+            authority: a(0)?,
+            kyc_user: a(2)?,
+            edd_required: args.edd_required,
+        }
+    } else if is!(ix::ConfigureEdd) {
+        let args: ix::ConfigureEdd = args(instruction)?;
+        ProgramOperation::ConfigureEdd {
+            issuer: a(0)?,
+            mint: a(2)?,
+            vault: a(4)?,
+            transfer_threshold: args.transfer_threshold,
+            approval_window: args.approval_window,
+        }
+    } else if is!(ix::RequestEddTransfer) {
+        let args: ix::RequestEddTransfer = args(instruction)?;
+        ProgramOperation::RequestEddTransfer {
+            sender: a(0)?,
+            mint: a(2)?,
+            sender_token_account: a(6)?,
+            recipient_token_account: a(7)?,
+            pending_approval: a(9)?,
+            request_id: args.request_id,
+            amount: args.amount,
+        }
+    } else if is!(ix::ApproveEddTransfer) {
+        ProgramOperation::ApproveEddTransfer {
+            officer: a(0)?,
+            pending_approval: a(6)?,
+            destination_token_account: a(7)?,
+        }
+    } else if is!(ix::RejectEddTransfer) {
+        let args: ix::RejectEddTransfer = args(instruction)?;
+        ProgramOperation::RejectEddTransfer {
+            officer: a(0)?,
+            pending_approval: a(6)?,
+            destination_token_account: a(7)?,
+            decision_reason: args.decision_reason,
+        }
+    } else if is!(ix::ExpireEddTransfer) {
+        ProgramOperation::ExpireEddTransfer {
+            caller: a(0)?,
+            pending_approval: a(5)?,
+            sender_token_account: a(6)?,
+        }
+    } else if is!(ix::ConfigureApprovalSet) {
+        let args: ix::ConfigureApprovalSet = args(instruction)?;
+        ProgramOperation::ConfigureApprovalSet {
+            issuer: a(0)?,
+            approval_set: a(2)?,
+            signers: args.signers,
+            threshold: args.threshold,
+            large_mint_threshold: args.large_mint_threshold,
+        }
+    } else if is!(ix::ProposeAction) {
+        let args: ix::ProposeAction = args(instruction)?;
+        ProgramOperation::ProposeAction { officer: a(0)?, proposal: a(2)?, action: args.action }
+    } else if is!(ix::ApproveAction) {
+        ProgramOperation::ApproveAction { officer: a(0)?, proposal: a(2)? }
+    } else if is!(ix::CancelAction) {
+        ProgramOperation::CancelAction { officer: a(0)?, proposal: a(2)? }
+    } else if is!(ix::ExecuteAction) {
+        ProgramOperation::ExecuteAction {
+            authority: a(0)?,
+            proposal: a(5)?,
+            token_account: a(6)?,
+        }
+    } else if is!(ix::SetLimits) {
+        let args: ix::SetLimits = args(instruction)?;
+        ProgramOperation::SetLimits { issuer: a(0)?, limit_config: a(2)?, tiers: args.tiers }
+    } else if is!(ix::ConfigureReserveAuditor) {
+        let args: ix::ConfigureReserveAuditor = args(instruction)?;
+        ProgramOperation::ConfigureReserveAuditor {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            auditor: args.auditor,
+            attestation_window: args.attestation_window,
+        }
+    } else if is!(ix::AttestReserveProof) {
+        let args: ix::AttestReserveProof = args(instruction)?;
+        ProgramOperation::AttestReserveProof {
+            auditor: a(0)?,
+            mint_info: a(1)?,
+            merkle_root: args.merkle_root,
+        }
+    } else if is!(ix::CheckReserveCoverage) {
+        ProgramOperation::CheckReserveCoverage { mint_info: a(0)?, mint: a(1)? }
+    } else if is!(ix::SetCoverageAutoPause) {
+        let args: ix::SetCoverageAutoPause = args(instruction)?;
+        ProgramOperation::SetCoverageAutoPause { issuer: a(0)?, mint_info: a(1)?, enabled: args.enabled }
+    } else if is!(ix::SetMaxReserveAge) {
+        let args: ix::SetMaxReserveAge = args(instruction)?;
+        ProgramOperation::SetMaxReserveAge {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            max_reserve_age_seconds: args.max_reserve_age_seconds,
+        }
+    } else if is!(ix::ConfigureReserveValuation) {
+        let args: ix::ConfigureReserveValuation = args(instruction)?;
+        ProgramOperation::ConfigureReserveValuation {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            eur_usd_feed: args.eur_usd_feed,
+            bond_feeds: args.bond_feeds,
+            tolerance_bps: args.tolerance_bps,
+            max_price_age: args.max_price_age,
+        }
+    } else if is!(ix::CloseKycUser) {
+        ProgramOperation::CloseKycUser { authority: a(0)?, kyc_user: a(2)?, mint: a(4)? }
+    } else if is!(ix::PublishBalanceSnapshot) {
+        let args: ix::PublishBalanceSnapshot = args(instruction)?;
+        ProgramOperation::PublishBalanceSnapshot {
+            issuer: a(0)?,
+            balance_snapshot: a(3)?,
+            merkle_root: args.merkle_root,
+            snapshot_slot: args.snapshot_slot,
+            holder_count: args.holder_count,
+            total_balance: args.total_balance,
+        }
+    } else if is!(ix::WhitelistInstitution) {
+        let args: ix::WhitelistInstitution = args(instruction)?;
+        ProgramOperation::WhitelistInstitution { issuer: a(0)?, wallet: args.wallet, lei: args.lei, daily_cap: args.daily_cap }
+    } else if is!(ix::RemoveInstitution) {
+        ProgramOperation::RemoveInstitution { issuer: a(0)?, institution: a(2)? }
+    } else if is!(ix::AddKycProvider) {
+        let args: ix::AddKycProvider = args(instruction)?;
+        ProgramOperation::AddKycProvider {
+            authority: a(0)?,
+            provider: args.provider,
+            name: args.name,
+        }
+    } else if is!(ix::RemoveKycProvider) {
+        ProgramOperation::RemoveKycProvider { authority: a(0)?, kyc_provider: a(2)? }
+    } else if is!(ix::RecordReconciliation) {
+        let args: ix::RecordReconciliation = args(instruction)?;
+        ProgramOperation::RecordReconciliation {
+            issuer: a(0)?,
+            mint: a(2)?,
+            reconciliation_record: a(4)?,
+            pending_redemptions: args.pending_redemptions,
+            registered_deposits: args.registered_deposits,
+            ledger_balance: args.ledger_balance,
+            first_detected_time: args.first_detected_time,
+        }
+    } else if is!(ix::ConfigureTravelRule) {
+        let args: ix::ConfigureTravelRule = args(instruction)?;
+        ProgramOperation::ConfigureTravelRule { issuer: a(0)?, travel_rule_config: a(2)?, threshold: args.threshold }
+    } else if is!(ix::ConfigureSeizureVault) {
+        ProgramOperation::ConfigureSeizureVault { issuer: a(0)?, mint: a(2)?, vault: a(4)? }
+    } else if is!(ix::SeizeToVault) {
+        let args: ix::SeizeToVault = args(instruction)?;
+        ProgramOperation::SeizeToVault {
+            permanent_delegate: a(0)?,
+            mint: a(3)?,
+            from: a(6)?,
+            seizure_record: a(7)?,
+            amount: args.amount,
+            operation_id: args.operation_id,
+            case_hash: args.case_hash,
+        }
+    } else if is!(ix::ConfigureTreasuryVault) {
+        ProgramOperation::ConfigureTreasuryVault { issuer: a(0)?, mint: a(2)?, vault: a(4)? }
+    } else if is!(ix::MintToTreasury) {
+        let args: ix::MintToTreasury = args(instruction)?;
+        ProgramOperation::MintToTreasury {
+            issuer: a(0)?,
+            mint: a(2)?,
+            vault: a(4)?,
+            amount: args.amount,
+            operation_id: args.operation_id,
+        }
+    } else if is!(ix::DistributeFromTreasury) {
+        let args: ix::DistributeFromTreasury = args(instruction)?;
+        ProgramOperation::DistributeFromTreasury {
+            distributor: a(0)?,
+            mint: a(2)?,
+            token_account: a(5)?,
+            kyc_user: a(6)?,
+            amount: args.amount,
+            operation_id: args.operation_id,
+        }
+    } else if is!(ix::ReleaseFromVault) {
+        let args: ix::ReleaseFromVault = args(instruction)?;
+        ProgramOperation::ReleaseFromVault {
+            permanent_delegate: a(0)?,
+            seizure_record: a(6)?,
+            destination_token_account: a(7)?,
+            resolution_hash: args.resolution_hash,
+        }
+    } else if is!(ix::ForfeitFromVault) {
+        let args: ix::ForfeitFromVault = args(instruction)?;
+        ProgramOperation::ForfeitFromVault {
+            permanent_delegate: a(0)?,
+            seizure_record: a(6)?,
+            resolution_hash: args.resolution_hash,
+        }
+    } else if is!(ix::AutoThaw) {
+        ProgramOperation::AutoThaw { freeze_record: a(0)? }
+    } else if is!(ix::ConfigureTransferFee) {
+        let args: ix::ConfigureTransferFee = args(instruction)?;
+        ProgramOperation::ConfigureTransferFee {
+            issuer: a(0)?,
+            mint: a(2)?,
+            fee_treasury: a(3)?,
+            transfer_fee_bps: args.transfer_fee_bps,
+            max_transfer_fee: args.max_transfer_fee,
+        }
+    } else if is!(ix::WithdrawWithheldFees) {
+        ProgramOperation::WithdrawWithheldFees { issuer: a(0)?, mint: a(2)?, fee_treasury: a(3)? }
+    } else if is!(ix::HarvestFeesToTreasury) {
+        ProgramOperation::HarvestFeesToTreasury {
+            issuer: a(0)?,
+            mint: a(2)?,
+            fee_treasury: a(3)?,
+            sources: instruction.accounts.iter().skip(5).copied().collect(),
+        }
+    } else if is!(ix::EnableConfidentialTransfers) {
+        let args: ix::EnableConfidentialTransfers = args(instruction)?;
+        ProgramOperation::EnableConfidentialTransfers {
+            issuer: a(0)?,
+            auditor: a(1)?,
+            mint: a(4)?,
+            auditor_elgamal_pubkey: args.auditor_elgamal_pubkey,
+        }
+    } else if is!(ix::ApproveConfidentialAccount) {
+        ProgramOperation::ApproveConfidentialAccount { owner: a(0)?, mint: a(2)?, token_account: a(3)? }
+    } else if is!(ix::SetInterestRate) {
+        let args: ix::SetInterestRate = args(instruction)?;
+        ProgramOperation::SetInterestRate { issuer: a(0)?, mint: a(2)?, rate_change: a(4)?, rate: args.rate }
+    } else if is!(ix::GrantRole) {
+        let args: ix::GrantRole = args(instruction)?;
+        ProgramOperation::GrantRole { admin: a(0)?, scope: a(1)?, holder: a(4)?, role: args.role }
+    } else if is!(ix::RevokeRole) {
+        let args: ix::RevokeRole = args(instruction)?;
+        ProgramOperation::RevokeRole { admin: a(0)?, role_assignment: a(3)?, role: args.role }
+    } else if is!(ix::SetMinterAllowance) {
+        let args: ix::SetMinterAllowance = args(instruction)?;
+        ProgramOperation::SetMinterAllowance { issuer: a(0)?, mint: a(2)?, minter: a(3)?, allowance: args.allowance }
+    } else if is!(ix::VerifyReserveInclusion) {
+        let args: ix::VerifyReserveInclusion = args(instruction)?;
+        ProgramOperation::VerifyReserveInclusion { mint_info: a(0)?, leaf: args.leaf }
+    } else if is!(ix::ReserveInclusion) {
+        let args: ix::ReserveInclusion = args(instruction)?;
+        ProgramOperation::ReserveInclusion { mint_info: a(0)?, leaf: args.leaf }
+    } else if is!(ix::VerifyArchivedReserveInclusion) {
+        let args: ix::VerifyArchivedReserveInclusion = args(instruction)?;
+        ProgramOperation::VerifyArchivedReserveInclusion { reserve_proof_epoch: a(0)?, epoch: args.epoch, leaf: args.leaf }
+    } else if is!(ix::BatchMintTokens) {
+        let args: ix::BatchMintTokens = args(instruction)?;
+        ProgramOperation::BatchMintTokens {
+            issuer: a(0)?,
+            mint: a(2)?,
+            // Each recipient's group of remaining accounts starts with its token account
+            token_accounts: instruction
+                .accounts
+                .iter()
+                .skip(12)
+                .step_by(mica_eur::BATCH_MINT_ACCOUNTS_PER_RECIPIENT)
+                .copied()
+                .collect(),
+            amounts: args.amounts,
+            operation_id: args.operation_id,
+        }
+    } else if is!(ix::BatchFreezeAccounts) {
+        let args: ix::BatchFreezeAccounts = args(instruction)?;
+        ProgramOperation::BatchFreezeAccounts {
+            freeze_authority: a(0)?,
+            mint: a(2)?,
+            // Each pair of remaining accounts starts with the token account
+            token_accounts: instruction
+                .accounts
+                .iter()
+                .skip(6)
+                .step_by(mica_eur::BATCH_FREEZE_ACCOUNTS_PER_TARGET)
+                .copied()
+                .collect(),
+            reason_code: args.reason_code,
+            legal_reference: args.legal_reference,
+        }
+    } else if is!(ix::BatchThawAccounts) {
+        ProgramOperation::BatchThawAccounts {
+            freeze_authority: a(0)?,
+            mint: a(2)?,
+            token_accounts: instruction
+                .accounts
+                .iter()
+                .skip(5)
+                .step_by(mica_eur::BATCH_FREEZE_ACCOUNTS_PER_TARGET)
+                .copied()
+                .collect(),
+        }
+    } else if is!(ix::EnforceBlacklist) {
+        ProgramOperation::EnforceBlacklist {
+            caller: a(0)?,
+            mint: a(2)?,
+            token_account: a(3)?,
+            blacklist_entry: a(4)?,
+        }
+    } else if is!(ix::SetSupplyCap) {
+        let args: ix::SetSupplyCap = args(instruction)?;
+        ProgramOperation::SetSupplyCap {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            max_supply: args.max_supply,
+        }
+    } else if is!(ix::ApplySupplyCap) {
+        ProgramOperation::ApplySupplyCap { mint_info: a(0)? }
+    } else if is!(ix::SetIssuanceSchedule) {
+        let args: ix::SetIssuanceSchedule = args(instruction)?;
+        ProgramOperation::SetIssuanceSchedule {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            monthly_limit: args.monthly_limit,
+        }
+    } else if is!(ix::ConfigureTimelock) {
+        let args: ix::ConfigureTimelock = args(instruction)?;
+        ProgramOperation::ConfigureTimelock {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            timelock: a(2)?,
+            delay: args.delay,
+        }
+    } else if is!(ix::QueueOperation) {
+        let args: ix::QueueOperation = args(instruction)?;
+        ProgramOperation::QueueOperation {
+            issuer: a(0)?,
+            timelock: a(2)?,
+            operation: a(3)?,
+            action: args.action,
+        }
+    } else if is!(ix::ExecuteOperation) {
+        ProgramOperation::ExecuteOperation { mint: a(1)?, operation: a(3)? }
+    } else if is!(ix::CancelOperation) {
+        ProgramOperation::CancelOperation { issuer: a(0)?, operation: a(3)? }
+    } else if is!(ix::UpdateWhitepaperUri) {
+        let args: ix::UpdateWhitepaperUri = args(instruction)?;
+        ProgramOperation::UpdateWhitepaperUri {
+            issuer: a(0)?,
+            mint_info: a(1)?,
+            whitepaper_version: a(2)?,
+            uri: args.uri,
+            content_hash: args.content_hash,
+        }
+    } else if is!(ix::InitializeTokenMetadata) {
+        let args: ix::InitializeTokenMetadata = args(instruction)?;
+        ProgramOperation::InitializeTokenMetadata {
+            issuer: a(0)?,
+            mint: a(2)?,
+            name: args.name,
+            uri: args.uri,
+        }
+    } else if is!(ix::UpdateMetadataField) {
+        let args: ix::UpdateMetadataField = args(instruction)?;
+        ProgramOperation::UpdateMetadataField {
+            issuer: a(0)?,
+            mint: a(2)?,
+            field: args.field,
+            value: args.value,
+        }
+    } else if is!(ix::PublishComplianceSnapshot) {
+        let args: ix::PublishComplianceSnapshot = args(instruction)?;
+        ProgramOperation::PublishComplianceSnapshot {
+            issuer: a(0)?,
+            mint: a(2)?,
+            compliance_snapshot: a(4)?,
+            period: args.period,
+            frozen_accounts: args.frozen_accounts,
+            seizure_count: args.seizure_count,
+        }
+    } else if is!(ix::SetCountryPolicy) {
+        let args: ix::SetCountryPolicy = args(instruction)?;
+        ProgramOperation::SetCountryPolicy {
+            authority: a(0)?,
+            country_policy: a(2)?,
+            country_code: args.country_code,
+            allowed: args.allowed,
+            max_transaction_amount: args.max_transaction_amount,
+        }
+    } else if is!(ix::RelinkIban) {
+        let args: ix::RelinkIban = args(instruction)?;
+        ProgramOperation::RelinkIban {
+            authority: a(0)?,
+            iban_registry: a(2)?,
+            new_user: args.new_user,
+        }
+    } else if is!(ix::MigrateKycUser) {
+        ProgramOperation::MigrateKycUser {
+            authority: a(0)?,
+            old_kyc_user: a(3)?,
+            new_user: a(1)?,
+            mint: a(10)?,
+            old_token_account: a(11)?,
+        }
+    } else if is!(ix::RequestRecovery) {
+        let args: ix::RequestRecovery = args(instruction)?;
+        ProgramOperation::RequestRecovery {
+            issuer: a(0)?,
+            mint: a(2)?,
+            source_token_account: a(3)?,
+            destination_token_account: a(4)?,
+            recovery_request: a(6)?,
+            evidence_hash: args.evidence_hash,
+        }
+    } else if is!(ix::ApproveRecovery) {
+        ProgramOperation::ApproveRecovery { officer: a(0)?, recovery_request: a(2)? }
+    } else if is!(ix::RecoverAccount) {
+        ProgramOperation::RecoverAccount {
+            issuer: a(0)?,
+            mint: a(3)?,
+            recovery_request: a(4)?,
+            source_token_account: a(5)?,
+            destination_token_account: a(6)?,
+        }
+    } else if is!(ix::CancelRecovery) {
+        ProgramOperation::CancelRecovery { issuer: a(0)?, recovery_request: a(2)? }
+    } else if is!(ix::RegisterCounterparty) {
+        let args: ix::RegisterCounterparty = args(instruction)?;
+        ProgramOperation::RegisterCounterparty {
+            issuer: a(0)?,
+            approved_counterparty: a(2)?,
+            wallet: args.wallet,
+            kind: args.kind,
+            lei: args.lei,
+            daily_limit_exempt: args.daily_limit_exempt,
+            max_transfer_amount: args.max_transfer_amount,
+        }
+    } else if is!(ix::RemoveCounterparty) {
+        ProgramOperation::RemoveCounterparty { issuer: a(0)?, approved_counterparty: a(2)? }
+    } else if is!(ix::ConfigureAmlAlerts) {
+        let args: ix::ConfigureAmlAlerts = args(instruction)?;
+        ProgramOperation::ConfigureAmlAlerts {
+            issuer: a(0)?,
+            aml_alert_config: a(2)?,
+            max_hourly_transfers: args.max_hourly_transfers,
+            max_hourly_volume: args.max_hourly_volume,
+            structuring_threshold: args.structuring_threshold,
+            structuring_margin: args.structuring_margin,
+            structuring_count: args.structuring_count,
+        }
+    } else if is!(ix::AnchorSarReference) {
+        let args: ix::AnchorSarReference = args(instruction)?;
+        ProgramOperation::AnchorSarReference {
+            officer: a(0)?,
+            kyc_user: a(2)?,
+            sar_reference: a(3)?,
+            sar_hash: args.sar_hash,
+            filing_date: args.filing_date,
+        }
+    } else if is!(ix::GetUserComplianceStatus) {
+        ProgramOperation::GetUserComplianceStatus { user: a(0)? }
+    } else if is!(ix::GetMintStatus) {
+        ProgramOperation::GetMintStatus { mint: a(1)? }
+    } else if is!(ix::GetReserveStatus) {
+        ProgramOperation::GetReserveStatus { mint: a(1)? }
+    } else if is!(ix::GetOutstandingSupply) {
+        ProgramOperation::GetOutstandingSupply { mint: a(1)? }
+    } else if is!(ix::AssertTransferAllowed) {
+        let args: ix::AssertTransferAllowed = args(instruction)?;
+        ProgramOperation::AssertTransferAllowed {
+            mint_info: a(0)?,
+            source_owner: args.source_owner,
+            dest_owner: args.dest_owner,
+            amount: args.amount,
+        }
+    } else if is!(ix::TransferWithAuthorization) {
+        let args: ix::TransferWithAuthorization = args(instruction)?;
+        ProgramOperation::TransferWithAuthorization {
+            relayer: a(0)?,
+            owner: a(3)?,
+            source_token: a(4)?,
+            destination_token: a(5)?,
+            recipient: args.recipient,
+            amount: args.amount,
+            nonce: args.nonce,
+            expiry: args.expiry,
+        }
+    } else if is!(ix::ConfigureRelayerRegistry) {
+        let args: ix::ConfigureRelayerRegistry = args(instruction)?;
+        ProgramOperation::ConfigureRelayerRegistry { issuer: a(0)?, relayer_registry: a(2)?, min_stake: args.min_stake }
+    } else if is!(ix::RegisterRelayer) {
+        let args: ix::RegisterRelayer = args(instruction)?;
+        ProgramOperation::RegisterRelayer { relayer: a(0)?, relayer_record: a(2)?, stake: args.stake }
+    } else if is!(ix::SetRelayerStatus) {
+        let args: ix::SetRelayerStatus = args(instruction)?;
+        ProgramOperation::SetRelayerStatus { issuer: a(0)?, relayer_record: a(3)?, status: args.status }
+    } else if is!(ix::DeregisterRelayer) {
+        ProgramOperation::DeregisterRelayer { relayer: a(0)?, relayer_record: a(2)? }
+    } else if is!(ix::ConfigureEscrowVault) {
+        ProgramOperation::ConfigureEscrowVault { issuer: a(0)?, mint: a(2)?, vault: a(4)? }
+    } else if is!(ix::OpenEscrow) {
+        let args: ix::OpenEscrow = args(instruction)?;
+        ProgramOperation::OpenEscrow {
+            buyer: a(0)?,
+            mint: a(2)?,
+            seller_token_account: a(8)?,
+            escrow: a(11)?,
+            escrow_id: args.escrow_id,
+            amount: args.amount,
+            arbiter: args.arbiter,
+            terms_hash: args.terms_hash,
+            expires_at: args.expires_at,
+        }
+    } else if is!(ix::ReleaseEscrow) {
+        ProgramOperation::ReleaseEscrow { authority: a(0)?, escrow: a(5)?, destination_token_account: a(6)? }
+    } else if is!(ix::RefundEscrow) {
+        ProgramOperation::RefundEscrow { authority: a(0)?, escrow: a(5)?, destination_token_account: a(6)? }
+    } else if is!(ix::CreatePaymentRequest) {
+        let args: ix::CreatePaymentRequest = args(instruction)?;
+        ProgramOperation::CreatePaymentRequest {
+            payee: a(0)?,
+            payment_request: a(3)?,
+            request_id: args.request_id,
+            amount: args.amount,
+            memo_hash: args.memo_hash,
+            expires_at: args.expires_at,
+        }
+    } else if is!(ix::PayRequest) {
+        ProgramOperation::PayRequest { payer: a(0)?, payment_request: a(3)?, payee_token_account: a(5)? }
+    } else {
+        let mut unrecognized = [0u8; 8];
+        unrecognized.copy_from_slice(discriminator);
+        ProgramOperation::Unrecognized { discriminator: unrecognized }
+    };
+
+    Ok(operation)
+}
+
+/// Decode a transfer hook program instruction
+pub fn decode_hook_instruction(instruction: &RawInstruction) -> Result<HookOperation, DecodeError> {
+    let discriminator = instruction
+        .data
+        .get(..8)
+        .ok_or(DecodeError::InvalidData { program_id: instruction.program_id })?;
+    let a = |index| account(instruction, index);
+
+    macro_rules! is {
+        ($t:ty) => {
+            discriminator == <$t as Discriminator>::DISCRIMINATOR
+        };
+    }
+
+    let operation = if is!(hook_ix::InitializeExtraAccountMetaList) {
+        HookOperation::InitializeExtraAccountMetaList {
+            issuer: a(0)?,
+            mint: a(2)?,
+            extra_account_meta_list: a(3)?,
+        }
+    } else if is!(hook_ix::UpdateExtraAccountMetaList) {
+        HookOperation::UpdateExtraAccountMetaList {
+            issuer: a(0)?,
+            mint: a(2)?,
+            extra_account_meta_list: a(3)?,
+        }
+    } else if is!(hook_ix::InitializeTransferRecord) {
+        HookOperation::InitializeTransferRecord { payer: a(0)?, user: a(1)? }
+    } else if is!(hook_ix::AttachTravelRuleInfo) {
+        let args: hook_ix::AttachTravelRuleInfo = args(instruction)?;
+        HookOperation::AttachTravelRuleInfo {
+            originator: a(0)?,
+            source_token: a(1)?,
+            destination_token: a(2)?,
+            amount: args.amount,
+            originator_hash: args.originator_hash,
+            beneficiary_hash: args.beneficiary_hash,
+        }
+    } else if is!(hook_ix::InitializeVelocityRecord) {
+        HookOperation::InitializeVelocityRecord { payer: a(0)?, user: a(1)? }
+    } else if is!(hook_ix::TransferHook) {
+        let args: hook_ix::TransferHook = args(instruction)?;
+        HookOperation::Execute {
+            source: a(0)?,
+            mint: a(1)?,
+            destination: a(2)?,
+            owner: a(3)?,
+            amount: args.amount,
+        }
+    } else {
+        let mut unrecognized = [0u8; 8];
+        unrecognized.copy_from_slice(discriminator);
+        HookOperation::Unrecognized { discriminator: unrecognized }
+    };
+
+    Ok(operation)
+}
+
+fn standing_order_update(
+    instruction: &RawInstruction,
+    action: StandingOrderAction,
+) -> Result<ProgramOperation, DecodeError> {
+    Ok(ProgramOperation::UpdateStandingOrder {
+        payer: account(instruction, 0)?,
+        standing_order: account(instruction, 1)?,
+        action,
+    })
+}
+
+/// Decode a Token-2022 instruction
+pub fn decode_token_instruction(
+    instruction: &RawInstruction,
+) -> Result<TokenOperation, DecodeError> {
+    let token_instruction = TokenInstruction::unpack(&instruction.data)
+        .map_err(|_| DecodeError::InvalidData { program_id: instruction.program_id })?;
+    let a = |index| account(instruction, index);
+
+    // Plain transfers are deprecated but still appear in history
+    #[allow(deprecated)]
+    let operation = match token_instruction {
+        TokenInstruction::Transfer { amount } => TokenOperation::Transfer {
+            source: a(0)?,
+            destination: a(1)?,
+            authority: a(2)?,
+            mint: None,
+            amount,
+        },
+        TokenInstruction::TransferChecked { amount, .. } => TokenOperation::Transfer {
+            source: a(0)?,
+            destination: a(2)?,
+            authority: a(3)?,
+            mint: Some(a(1)?),
+            amount,
+        },
+        TokenInstruction::MintTo { amount } | TokenInstruction::MintToChecked { amount, .. } => {
+            TokenOperation::MintTo { mint: a(0)?, destination: a(1)?, authority: a(2)?, amount }
+        }
+        TokenInstruction::Burn { amount } | TokenInstruction::BurnChecked { amount, .. } => {
+            TokenOperation::Burn { source: a(0)?, mint: a(1)?, authority: a(2)?, amount }
+        }
+        TokenInstruction::Approve { amount } => TokenOperation::Approve {
+            source: a(0)?,
+            delegate: a(1)?,
+            owner: a(2)?,
+            amount,
+        },
+        TokenInstruction::ApproveChecked { amount, .. } => TokenOperation::Approve {
+            source: a(0)?,
+            delegate: a(2)?,
+            owner: a(3)?,
+            amount,
+        },
+        TokenInstruction::Revoke => TokenOperation::Revoke { source: a(0)?, owner: a(1)? },
+        TokenInstruction::FreezeAccount => TokenOperation::FreezeAccount {
+            account: a(0)?,
+            mint: a(1)?,
+            authority: a(2)?,
+        },
+        TokenInstruction::ThawAccount => TokenOperation::ThawAccount {
+            account: a(0)?,
+            mint: a(1)?,
+            authority: a(2)?,
+        },
+        TokenInstruction::SetAuthority { authority_type, new_authority } => {
+            TokenOperation::SetAuthority {
+                account: a(0)?,
+                authority_type,
+                new_authority: new_authority.into(),
+            }
+        }
+        other => TokenOperation::Other { name: token_instruction_name(&other) },
+    };
+
+    Ok(operation)
+}
+
+// Variant name of a token instruction, without its fields
+fn token_instruction_name(instruction: &TokenInstruction) -> String {
+    let debug = format!("{:?}", instruction);
+    debug
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
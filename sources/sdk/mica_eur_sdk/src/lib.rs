@@ -0,0 +1,11 @@
+//! Client SDK for the MiCA EUR program.
+//!
+//! - `decoder`: turns raw transaction history into typed operation records and
+//!   documents the program error codes
+//! - `snapshots`: holder-balance snapshot trees and proofs for loyalty programs
+
+pub mod decoder;
+pub mod snapshots;
+
+pub use decoder::*;
+pub use snapshots::*;
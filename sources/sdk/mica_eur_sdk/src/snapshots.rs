@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Pubkey;
+use mica_eur::{create_snapshot_leaf, merkle_levels, merkle_proof, verify_merkle_proof};
+
+/// Merkle proof that a holder had a balance in a snapshot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotProof {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Holder-balance tree in the layout `publish_balance_snapshot` expects: one leaf per
+/// wallet, ordered by wallet address, an unpaired last node moving up unchanged
+#[derive(Clone, Debug)]
+pub struct SnapshotTree {
+    balances: Vec<(Pubkey, u64)>,
+    levels: Vec<Vec<[u8; 32]>>,   // Leaves first, root last
+}
+
+impl SnapshotTree {
+    /// Build the tree from token account balances. Balances of the same wallet are
+    /// added up and zero balances left out.
+    pub fn new(balances: impl IntoIterator<Item = (Pubkey, u64)>) -> Self {
+        let mut by_owner = BTreeMap::new();
+        for (owner, balance) in balances {
+            let total: &mut u64 = by_owner.entry(owner).or_default();
+            *total = total.saturating_add(balance);
+        }
+        let balances: Vec<(Pubkey, u64)> =
+            by_owner.into_iter().filter(|(_, balance)| *balance > 0).collect();
+
+        let leaves: Vec<[u8; 32]> =
+            balances.iter().map(|(owner, balance)| create_snapshot_leaf(owner, *balance)).collect();
+        let levels = merkle_levels(&leaves);
+        Self { balances, levels }
+    }
+
+    /// Root to publish, all zeros for an empty tree
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().and_then(|level| level.first().copied()).unwrap_or([0; 32])
+    }
+
+    /// Number of holders in the tree
+    pub fn holder_count(&self) -> u32 {
+        self.balances.len() as u32
+    }
+
+    /// Sum of all balances in the tree
+    pub fn total_balance(&self) -> u64 {
+        self.balances.iter().fold(0u64, |total, (_, balance)| total.saturating_add(*balance))
+    }
+
+    /// Proof for a wallet, `None` if it held no tokens at the snapshot
+    pub fn proof(&self, owner: &Pubkey) -> Option<SnapshotProof> {
+        let index = self.balances.binary_search_by_key(owner, |(owner, _)| *owner).ok()?;
+        let balance = self.balances[index].1;
+        let proof = merkle_proof(&self.levels, index);
+        Some(SnapshotProof { owner: *owner, balance, proof })
+    }
+}
+
+/// Check a holder's proof against a published snapshot root
+pub fn verify_snapshot_proof(merkle_root: &[u8; 32], proof: &SnapshotProof) -> bool {
+    let leaf = create_snapshot_leaf(&proof.owner, proof.balance);
+    verify_merkle_proof(&proof.proof, merkle_root, &leaf)
+}
@@ -0,0 +1,3278 @@
+//! Round trips of every instruction through the decoder: each case builds the
+//! instruction, with the client builder where there is one, decodes it and checks
+//! the decoded accounts are the ones it was built with. The decoder reads accounts
+//! at fixed positions, so reordering a program's `Accounts` struct fails here.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::ID as TOKEN_2022_ID;
+use mica_eur::instruction as ix;
+use mica_eur::{
+    AccountClass, BlacklistReason, BondPriceFeed, CounterpartyKind, KycStatus, MetadataField,
+    RegulatoryAction, RelayerStatus, ReserveAsset, ReserveAssetCategory, TimelockAction,
+    ACTIVE_POLICY_PARAMS, DEFAULT_TIER_LIMITS,
+};
+use mica_eur_client::{self as client, pda, KycSigner};
+use mica_eur_sdk::*;
+use mica_eur_transfer_hook::instruction as hook_ix;
+
+fn key() -> Pubkey {
+    Pubkey::new_unique()
+}
+
+fn raw(instruction: Instruction) -> RawInstruction {
+    RawInstruction {
+        program_id: instruction.program_id,
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect(),
+        data: instruction.data,
+    }
+}
+
+fn round_trip(instruction: Instruction) -> ProgramOperation {
+    decode_program_instruction(&raw(instruction)).unwrap()
+}
+
+// Builds the instruction from its `Accounts` struct, for instructions without a
+// client builder; `remaining` accounts go after the struct's
+fn decode_with_remaining(
+    accounts: &impl ToAccountMetas,
+    remaining: &[Pubkey],
+    data: impl InstructionData,
+) -> ProgramOperation {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining.iter().map(|key| AccountMeta::new(*key, false)));
+    round_trip(Instruction {
+        program_id: mica_eur::ID,
+        accounts: metas,
+        data: data.data(),
+    })
+}
+
+fn decode(accounts: &impl ToAccountMetas, data: impl InstructionData) -> ProgramOperation {
+    decode_with_remaining(accounts, &[], data)
+}
+
+fn decode_hook(accounts: &impl ToAccountMetas, data: impl InstructionData) -> HookOperation {
+    let instruction = Instruction {
+        program_id: mica_eur_transfer_hook::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    };
+    decode_hook_instruction(&raw(instruction)).unwrap()
+}
+
+#[test]
+fn unknown_discriminators_are_unrecognized() {
+    let instruction = RawInstruction {
+        program_id: mica_eur::ID,
+        accounts: vec![key()],
+        data: vec![0xff; 8],
+    };
+    assert_eq!(
+        decode_program_instruction(&instruction),
+        Ok(ProgramOperation::Unrecognized {
+            discriminator: [0xff; 8]
+        })
+    );
+    let instruction = RawInstruction {
+        program_id: mica_eur_transfer_hook::ID,
+        ..instruction
+    };
+    assert_eq!(
+        decode_hook_instruction(&instruction),
+        Ok(HookOperation::Unrecognized {
+            discriminator: [0xff; 8]
+        })
+    );
+}
+
+// ---------------- Instructions with a client builder ----------------
+
+#[test]
+fn register_kyc_user() {
+    let (authority, user) = (key(), key());
+    let instruction = client::register_kyc_user(
+        &authority,
+        KycSigner::Admin,
+        &user,
+        "10010010".to_string(),
+        [1; 32],
+        "DE".to_string(),
+        "idnow".to_string(),
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::RegisterKycUser {
+            authority,
+            user,
+            country_code: "DE".to_string(),
+            verification_provider: "idnow".to_string(),
+        }
+    );
+}
+
+#[test]
+fn update_kyc_status() {
+    let (authority, user) = (key(), key());
+    let instruction = client::update_kyc_status(
+        &authority,
+        KycSigner::Provider,
+        &user,
+        KycStatus::Verified,
+        2,
+        365,
+        Some(key()),
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::UpdateKycStatus {
+            authority,
+            kyc_user: pda::kyc_user(&user),
+            status: KycStatus::Verified,
+            verification_level: 2,
+            expiry_days: 365,
+        }
+    );
+}
+
+#[test]
+fn reclassify_account() {
+    let (authority, user) = (key(), key());
+    let instruction = client::reclassify_account(
+        &authority,
+        KycSigner::OracleAuthority,
+        &user,
+        AccountClass::Merchant,
+        [1; 32],
+        Some((key(), key())),
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ReclassifyAccount {
+            authority,
+            kyc_user: pda::kyc_user(&user),
+            new_class: AccountClass::Merchant,
+            kyb_hash: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn register_screening_provider() {
+    let (authority, signing_key) = (key(), key());
+    let instruction =
+        client::register_screening_provider(&authority, &signing_key, "screener".to_string());
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::RegisterScreeningProvider {
+            authority,
+            signing_key,
+            name: "screener".to_string()
+        }
+    );
+}
+
+#[test]
+fn submit_screening_attestation() {
+    let (submitter, signing_key, user) = (key(), key(), key());
+    let instruction =
+        client::submit_screening_attestation(&submitter, &signing_key, &user, 1_000, [1; 32]);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::SubmitScreeningAttestation {
+            submitter,
+            screening_provider: pda::screening_provider(&signing_key),
+            kyc_user: pda::kyc_user(&user),
+            screened_at: 1_000,
+            result_hash: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn mint_tokens() {
+    let (minter, mint, token_account, user) = (key(), key(), key(), key());
+    let options = client::MintTokensOptions {
+        approval_set: Some(key()),
+        limit_config: Some(key()),
+        role_assignment: Some(key()),
+        minter_allowance: Some(key()),
+        issuance_policy: Some(key()),
+    };
+    let instruction = client::mint_tokens(
+        &minter,
+        &mint,
+        &token_account,
+        &user,
+        "DE",
+        1_000,
+        [1; 16],
+        options,
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::MintTokens {
+            issuer: minter,
+            mint,
+            token_account,
+            kyc_user: pda::kyc_user(&user),
+            amount: 1_000,
+            operation_id: [1; 16],
+        }
+    );
+}
+
+#[test]
+fn configure_treasury_vault() {
+    let (issuer, mint, vault) = (key(), key(), key());
+    let instruction = client::configure_treasury_vault(&issuer, &mint, &vault);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ConfigureTreasuryVault {
+            issuer,
+            mint,
+            vault
+        }
+    );
+}
+
+#[test]
+fn mint_to_treasury() {
+    let (issuer, mint, vault) = (key(), key(), key());
+    let instruction = client::mint_to_treasury(
+        &issuer,
+        &mint,
+        &vault,
+        1_000,
+        [1; 16],
+        Some(key()),
+        Some(key()),
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::MintToTreasury {
+            issuer,
+            mint,
+            vault,
+            amount: 1_000,
+            operation_id: [1; 16]
+        }
+    );
+}
+
+#[test]
+fn distribute_from_treasury() {
+    let (distributor, mint, vault, token_account, user) = (key(), key(), key(), key(), key());
+    let options = client::DistributeOptions {
+        limit_config: Some(key()),
+        role_assignment: Some(key()),
+    };
+    let instruction = client::distribute_from_treasury(
+        &distributor,
+        &mint,
+        &vault,
+        &token_account,
+        &user,
+        "DE",
+        1_000,
+        [1; 16],
+        options,
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::DistributeFromTreasury {
+            distributor,
+            mint,
+            token_account,
+            kyc_user: pda::kyc_user(&user),
+            amount: 1_000,
+            operation_id: [1; 16],
+        }
+    );
+}
+
+#[test]
+fn create_mint_voucher() {
+    let (issuer, mint, user) = (key(), key(), key());
+    let instruction =
+        client::create_mint_voucher(&issuer, &mint, &user, [1; 32], 1_000, Some(key()));
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::CreateMintVoucher {
+            issuer,
+            mint,
+            voucher: pda::mint_voucher(&mint, &[1; 32]),
+            user,
+            reference_hash: [1; 32],
+            amount: 1_000,
+        }
+    );
+}
+
+#[test]
+fn claim_mint_voucher() {
+    let (user, mint, token_account) = (key(), key(), key());
+    let options = client::ClaimVoucherOptions {
+        limit_config: Some(key()),
+        issuance_policy: Some(key()),
+    };
+    let instruction =
+        client::claim_mint_voucher(&user, &mint, &[1; 32], &token_account, "DE", options);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ClaimMintVoucher {
+            user,
+            mint,
+            voucher: pda::mint_voucher(&mint, &[1; 32]),
+            token_account,
+        }
+    );
+}
+
+#[test]
+fn record_payout_reference() {
+    let (issuer, mint, holder) = (key(), key(), key());
+    let instruction = client::record_payout_reference(&issuer, &mint, &holder, [1; 16], [2; 32]);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::RecordPayoutReference {
+            issuer,
+            payout_reference: pda::payout_reference(&mint, &holder, &[1; 16]),
+            operation_id: [1; 16],
+            reference_hash: [2; 32],
+        }
+    );
+}
+
+#[test]
+fn burn_tokens() {
+    let (owner, mint, token_account) = (key(), key(), key());
+    let options = client::BurnTokensOptions {
+        limit_config: Some(key()),
+    };
+    let instruction =
+        client::burn_tokens(&owner, &mint, &token_account, "DE", 1_000, [1; 16], options);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::BurnTokens {
+            owner,
+            mint,
+            token_account,
+            amount: 1_000,
+            operation_id: [1; 16]
+        }
+    );
+}
+
+#[test]
+fn initialize_euro_mint() {
+    let (issuer, mint) = (key(), key());
+    let instruction = client::initialize_euro_mint(
+        &issuer,
+        &mint,
+        &key(),
+        &key(),
+        "https://issuer.example/wp.pdf".to_string(),
+        6,
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::InitializeEuroMint {
+            issuer,
+            mint,
+            whitepaper_uri: "https://issuer.example/wp.pdf".to_string(),
+        }
+    );
+}
+
+#[test]
+fn deactivate_mint() {
+    let (issuer, mint) = (key(), key());
+    let instruction = client::deactivate_mint(&issuer, &mint);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::DeactivateMint {
+            issuer,
+            mint_info: pda::mint_info(&mint)
+        }
+    );
+}
+
+#[test]
+fn reactivate_mint() {
+    let (issuer, mint) = (key(), key());
+    let instruction = client::reactivate_mint(&issuer, &mint);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ReactivateMint {
+            issuer,
+            mint_info: pda::mint_info(&mint)
+        }
+    );
+}
+
+#[test]
+fn initiate_wind_down() {
+    let (issuer, mint, redemption_account) = (key(), key(), key());
+    let instruction = client::initiate_wind_down(&issuer, &mint, &redemption_account, 1_000);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::InitiateWindDown {
+            issuer,
+            mint,
+            redemption_account,
+            redemption_deadline: 1_000
+        }
+    );
+}
+
+#[test]
+fn migrate_mint_authority() {
+    let (issuer, current_authority, mint) = (key(), key(), key());
+    let instruction = client::migrate_mint_authority(&issuer, &current_authority, &mint);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::MigrateMintAuthority {
+            issuer,
+            current_authority,
+            mint
+        }
+    );
+}
+
+#[test]
+fn migrate_permanent_delegate() {
+    let (issuer, mint) = (key(), key());
+    let instruction = client::migrate_permanent_delegate(&issuer, &mint);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::MigratePermanentDelegate { issuer, mint }
+    );
+}
+
+#[test]
+fn create_token_account() {
+    let (owner, mint) = (key(), key());
+    let instruction = client::create_token_account(&owner, &mint);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::CreateTokenAccount {
+            owner,
+            token_account: get_associated_token_address_with_program_id(
+                &owner,
+                &mint,
+                &TOKEN_2022_ID
+            ),
+            mint,
+        }
+    );
+}
+
+#[test]
+fn activate_token_account() {
+    let (caller, mint, token_account) = (key(), key(), key());
+    let instruction = client::activate_token_account(&caller, &mint, &token_account, &key());
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ActivateTokenAccount {
+            caller,
+            mint,
+            token_account
+        }
+    );
+}
+
+#[test]
+fn enforce_kyc_expiry() {
+    let (caller, mint, token_account) = (key(), key(), key());
+    let instruction =
+        client::enforce_kyc_expiry(&caller, &mint, &token_account, &key(), Some(key()));
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::EnforceKycExpiry {
+            caller,
+            mint,
+            token_account
+        }
+    );
+}
+
+#[test]
+fn open_compliance_history() {
+    let (payer, user) = (key(), key());
+    let instruction = client::open_compliance_history(&payer, &user);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::OpenComplianceHistory {
+            payer,
+            kyc_user: pda::kyc_user(&user),
+            compliance_history: pda::compliance_history(&user),
+        }
+    );
+}
+
+#[test]
+fn close_token_account() {
+    let (owner, mint, token_account) = (key(), key(), key());
+    let instruction = client::close_token_account(&owner, &mint, &token_account);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::CloseTokenAccount {
+            owner,
+            token_account,
+            mint
+        }
+    );
+}
+
+#[test]
+fn freeze_account() {
+    let (freeze_authority, mint, token_account) = (key(), key(), key());
+    let instruction = client::freeze_account(
+        &freeze_authority,
+        &mint,
+        &token_account,
+        2,
+        "AZ 123/26".to_string(),
+        1_000,
+        2_000,
+        Some(key()),
+        Some(key()),
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::FreezeAccount {
+            freeze_authority,
+            mint,
+            token_account,
+            reason_code: 2,
+            legal_reference: "AZ 123/26".to_string(),
+            frozen_amount: 1_000,
+            expires_at: 2_000,
+        }
+    );
+}
+
+#[test]
+fn thaw_account() {
+    let (freeze_authority, mint, token_account) = (key(), key(), key());
+    let instruction = client::thaw_account(
+        &freeze_authority,
+        &mint,
+        &token_account,
+        Some(pda::freeze_record(&token_account)),
+        Some(key()),
+        Some(key()),
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ThawAccount {
+            freeze_authority,
+            mint,
+            token_account
+        }
+    );
+}
+
+#[test]
+fn transfer_with_authorization() {
+    let (relayer, mint, owner, source_token, destination_token, recipient) =
+        (key(), key(), key(), key(), key(), key());
+    let instruction = client::transfer_with_authorization(
+        &relayer,
+        &mint,
+        &owner,
+        &source_token,
+        &destination_token,
+        &recipient,
+        1_000,
+        [1; 32],
+        2_000,
+        Some(key()),
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::TransferWithAuthorization {
+            relayer,
+            owner,
+            source_token,
+            destination_token,
+            recipient,
+            amount: 1_000,
+            nonce: [1; 32],
+            expiry: 2_000,
+        }
+    );
+}
+
+#[test]
+fn create_standing_order() {
+    let (payer, mint, payer_token_account, payee_token_account) = (key(), key(), key(), key());
+    let instruction = client::create_standing_order(
+        &payer,
+        &mint,
+        &payer_token_account,
+        &payee_token_account,
+        &key(),
+        7,
+        1_000,
+        86_400,
+        2_000,
+        3_000,
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::CreateStandingOrder {
+            payer,
+            standing_order: pda::standing_order(&payer, 7),
+            payer_token_account,
+            payee_token_account,
+            order_id: 7,
+            amount: 1_000,
+            interval_seconds: 86_400,
+            first_execution_time: 2_000,
+            end_time: 3_000,
+        }
+    );
+}
+
+#[test]
+fn pause_standing_order() {
+    let payer = key();
+    assert_eq!(
+        round_trip(client::pause_standing_order(&payer, 7)),
+        ProgramOperation::UpdateStandingOrder {
+            payer,
+            standing_order: pda::standing_order(&payer, 7),
+            action: StandingOrderAction::Pause,
+        }
+    );
+}
+
+#[test]
+fn resume_standing_order() {
+    let payer = key();
+    assert_eq!(
+        round_trip(client::resume_standing_order(&payer, 7)),
+        ProgramOperation::UpdateStandingOrder {
+            payer,
+            standing_order: pda::standing_order(&payer, 7),
+            action: StandingOrderAction::Resume,
+        }
+    );
+}
+
+#[test]
+fn skip_standing_order() {
+    let payer = key();
+    assert_eq!(
+        round_trip(client::skip_standing_order(&payer, 7)),
+        ProgramOperation::UpdateStandingOrder {
+            payer,
+            standing_order: pda::standing_order(&payer, 7),
+            action: StandingOrderAction::Skip,
+        }
+    );
+}
+
+#[test]
+fn cancel_standing_order() {
+    let payer = key();
+    assert_eq!(
+        round_trip(client::cancel_standing_order(&payer, 7, &key())),
+        ProgramOperation::UpdateStandingOrder {
+            payer,
+            standing_order: pda::standing_order(&payer, 7),
+            action: StandingOrderAction::Cancel,
+        }
+    );
+}
+
+#[test]
+fn execute_standing_order() {
+    let (keeper, mint, payer, payer_token_account, payee_token_account) =
+        (key(), key(), key(), key(), key());
+    let instruction = client::execute_standing_order(
+        &keeper,
+        &mint,
+        &payer,
+        7,
+        &payer_token_account,
+        &key(),
+        &payee_token_account,
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ExecuteStandingOrder {
+            keeper,
+            standing_order: pda::standing_order(&payer, 7),
+            payer_token_account,
+            payee_token_account,
+        }
+    );
+}
+
+#[test]
+fn configure_relayer_registry() {
+    let (issuer, mint) = (key(), key());
+    let instruction = client::configure_relayer_registry(&issuer, &mint, 1_000);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ConfigureRelayerRegistry {
+            issuer,
+            relayer_registry: pda::relayer_registry(&mint),
+            min_stake: 1_000,
+        }
+    );
+}
+
+#[test]
+fn register_relayer() {
+    let (relayer, mint) = (key(), key());
+    let instruction = client::register_relayer(&relayer, &mint, 1_000);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::RegisterRelayer {
+            relayer,
+            relayer_record: pda::relayer(&mint, &relayer),
+            stake: 1_000
+        }
+    );
+}
+
+#[test]
+fn set_relayer_status() {
+    let (issuer, mint, relayer) = (key(), key(), key());
+    let instruction =
+        client::set_relayer_status(&issuer, &mint, &relayer, RelayerStatus::Suspended);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::SetRelayerStatus {
+            issuer,
+            relayer_record: pda::relayer(&mint, &relayer),
+            status: RelayerStatus::Suspended,
+        }
+    );
+}
+
+#[test]
+fn deregister_relayer() {
+    let (relayer, mint) = (key(), key());
+    let instruction = client::deregister_relayer(&relayer, &mint);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::DeregisterRelayer {
+            relayer,
+            relayer_record: pda::relayer(&mint, &relayer)
+        }
+    );
+}
+
+#[test]
+fn seize_tokens() {
+    let (permanent_delegate, mint, from, to) = (key(), key(), key(), key());
+    let instruction = client::seize_tokens(
+        &permanent_delegate,
+        &mint,
+        &from,
+        &to,
+        1_000,
+        [1; 16],
+        Some(key()),
+        Some(key()),
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::SeizeTokens {
+            permanent_delegate,
+            mint,
+            from,
+            to,
+            amount: 1_000,
+            operation_id: [1; 16]
+        }
+    );
+}
+
+#[test]
+fn update_reserve_proof() {
+    let (publisher, mint) = (key(), key());
+    let composition = vec![ReserveAsset {
+        category: ReserveAssetCategory::GovernmentBond,
+        isin_hash: [2; 32],
+        amount: 1_000,
+        units: 10,
+    }];
+    let options = client::ReserveProofOptions {
+        role_assignment: Some(key()),
+        valuation_config: Some(key()),
+    };
+    let instruction = client::update_reserve_proof(
+        &publisher,
+        &mint,
+        3,
+        [1; 32],
+        "bafy".to_string(),
+        1_000,
+        composition.clone(),
+        options,
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::UpdateReserveProof {
+            issuer: publisher,
+            mint_info: pda::mint_info(&mint),
+            merkle_root: [1; 32],
+            ipfs_cid: "bafy".to_string(),
+            reserve_total_eur: 1_000,
+            composition,
+        }
+    );
+}
+
+#[test]
+fn configure_reserve_valuation() {
+    let (issuer, mint) = (key(), key());
+    let bond_feeds = vec![BondPriceFeed {
+        isin_hash: [2; 32],
+        feed_id: [3; 32],
+    }];
+    let instruction =
+        client::configure_reserve_valuation(&issuer, &mint, [1; 32], bond_feeds.clone(), 50, 60);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ConfigureReserveValuation {
+            issuer,
+            mint_info: pda::mint_info(&mint),
+            eur_usd_feed: [1; 32],
+            bond_feeds,
+            tolerance_bps: 50,
+            max_price_age: 60,
+        }
+    );
+}
+
+#[test]
+fn set_max_reserve_age() {
+    let (issuer, mint) = (key(), key());
+    let instruction = client::set_max_reserve_age(&issuer, &mint, 86_400);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::SetMaxReserveAge {
+            issuer,
+            mint_info: pda::mint_info(&mint),
+            max_reserve_age_seconds: 86_400,
+        }
+    );
+}
+
+#[test]
+fn verify_archived_reserve_inclusion() {
+    let mint = key();
+    let instruction = client::verify_archived_reserve_inclusion(&mint, 3, [1; 32], vec![[2; 32]]);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::VerifyArchivedReserveInclusion {
+            reserve_proof_epoch: pda::reserve_proof_epoch(&mint, 3),
+            epoch: 3,
+            leaf: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn grant_regulator_access() {
+    let (issuer, mint, regulator) = (key(), key(), key());
+    let instruction =
+        client::grant_regulator_access(&issuer, &mint, &regulator, "BaFin".to_string(), 1_000);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::GrantRegulatorAccess {
+            issuer,
+            mint_info: pda::mint_info(&mint),
+            regulator_access: pda::regulator_access(&mint, &regulator),
+            regulator,
+            authority_id: "BaFin".to_string(),
+            expires_at: 1_000,
+        }
+    );
+}
+
+#[test]
+fn revoke_regulator_access() {
+    let (issuer, mint, regulator) = (key(), key(), key());
+    let instruction = client::revoke_regulator_access(&issuer, &mint, &regulator);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::RevokeRegulatorAccess {
+            issuer,
+            mint_info: pda::mint_info(&mint),
+            regulator_access: pda::regulator_access(&mint, &regulator),
+        }
+    );
+}
+
+#[test]
+fn export_audit_window() {
+    let (issuer, mint, regulator) = (key(), key(), key());
+    let instruction =
+        client::export_audit_window(&issuer, &mint, &regulator, 1_000, 2_000, 3, [1; 32]);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ExportAuditWindow {
+            issuer,
+            regulator_access: pda::regulator_access(&mint, &regulator),
+            from: 1_000,
+            to: 2_000,
+            event_count: 3,
+            digest: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn allowlist_program() {
+    let (issuer, mint, program) = (key(), key(), key());
+    let instruction = client::allowlist_program(&issuer, &mint, &program);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::AllowlistProgram {
+            issuer,
+            program_allowlist: pda::program_allowlist(&mint),
+            program
+        }
+    );
+}
+
+#[test]
+fn delist_program() {
+    let (issuer, mint, program) = (key(), key(), key());
+    let instruction = client::delist_program(&issuer, &mint, &program);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::DelistProgram {
+            issuer,
+            program_allowlist: pda::program_allowlist(&mint),
+            program
+        }
+    );
+}
+
+#[test]
+fn register_program_escrow() {
+    let (payer, mint, program, authority) = (key(), key(), key(), key());
+    let instruction =
+        client::register_program_escrow(&payer, &mint, &program, &authority, &[b"escrow", &[255]]);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::RegisterProgramEscrow {
+            payer,
+            authority,
+            program_escrow: pda::program_escrow(&mint, &authority),
+            program,
+        }
+    );
+}
+
+#[test]
+fn assert_transfer_allowed() {
+    let (mint, source_owner, dest_owner) = (key(), key(), key());
+    let instruction = client::assert_transfer_allowed(&mint, &source_owner, &dest_owner, 1_000);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::AssertTransferAllowed {
+            mint_info: pda::mint_info(&mint),
+            source_owner,
+            dest_owner,
+            amount: 1_000,
+        }
+    );
+}
+
+#[test]
+fn configure_escrow_vault() {
+    let (issuer, mint, vault) = (key(), key(), key());
+    let instruction = client::configure_escrow_vault(&issuer, &mint, &vault);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ConfigureEscrowVault {
+            issuer,
+            mint,
+            vault
+        }
+    );
+}
+
+#[test]
+fn open_escrow() {
+    let (buyer, mint, seller_token_account, arbiter) = (key(), key(), key(), key());
+    let instruction = client::open_escrow(
+        &buyer,
+        &mint,
+        &key(),
+        &key(),
+        &key(),
+        &seller_token_account,
+        7,
+        1_000,
+        &arbiter,
+        [1; 32],
+        2_000,
+    );
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::OpenEscrow {
+            buyer,
+            mint,
+            seller_token_account,
+            escrow: pda::escrow(&buyer, 7),
+            escrow_id: 7,
+            amount: 1_000,
+            arbiter,
+            terms_hash: [1; 32],
+            expires_at: 2_000,
+        }
+    );
+}
+
+#[test]
+fn release_escrow() {
+    let (authority, mint, buyer, seller_token_account) = (key(), key(), key(), key());
+    let instruction =
+        client::release_escrow(&authority, &mint, &key(), &buyer, 7, &seller_token_account);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ReleaseEscrow {
+            authority,
+            escrow: pda::escrow(&buyer, 7),
+            destination_token_account: seller_token_account,
+        }
+    );
+}
+
+#[test]
+fn refund_escrow() {
+    let (authority, mint, buyer, buyer_token_account) = (key(), key(), key(), key());
+    let instruction =
+        client::refund_escrow(&authority, &mint, &key(), &buyer, 7, &buyer_token_account);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::RefundEscrow {
+            authority,
+            escrow: pda::escrow(&buyer, 7),
+            destination_token_account: buyer_token_account,
+        }
+    );
+}
+
+#[test]
+fn create_payment_request() {
+    let (payee, mint) = (key(), key());
+    let instruction =
+        client::create_payment_request(&payee, &mint, &key(), 7, 1_000, [1; 32], 2_000);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::CreatePaymentRequest {
+            payee,
+            payment_request: pda::payment_request(&payee, 7),
+            request_id: 7,
+            amount: 1_000,
+            memo_hash: [1; 32],
+            expires_at: 2_000,
+        }
+    );
+}
+
+#[test]
+fn pay_request() {
+    let (payer, mint, payee, payee_token_account) = (key(), key(), key(), key());
+    let instruction = client::pay_request(&payer, &mint, &payee, 7, &key(), &payee_token_account);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::PayRequest {
+            payer,
+            payment_request: pda::payment_request(&payee, 7),
+            payee_token_account,
+        }
+    );
+}
+
+#[test]
+fn whitelist_institution() {
+    let (issuer, mint, wallet) = (key(), key(), key());
+    let instruction = client::whitelist_institution(&issuer, &mint, &wallet, [1; 20], 1_000);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::WhitelistInstitution {
+            issuer,
+            wallet,
+            lei: [1; 20],
+            daily_cap: 1_000
+        }
+    );
+}
+
+#[test]
+fn remove_institution() {
+    let (issuer, mint, wallet) = (key(), key(), key());
+    let instruction = client::remove_institution(&issuer, &mint, &wallet);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::RemoveInstitution {
+            issuer,
+            institution: pda::fast_lane_institution(&mint, &wallet)
+        }
+    );
+}
+
+#[test]
+fn configure_travel_rule() {
+    let (issuer, mint) = (key(), key());
+    let instruction = client::configure_travel_rule(&issuer, &mint, 1_000);
+    assert_eq!(
+        round_trip(instruction),
+        ProgramOperation::ConfigureTravelRule {
+            issuer,
+            travel_rule_config: pda::travel_rule_config(&mint),
+            threshold: 1_000,
+        }
+    );
+}
+
+#[test]
+fn attach_travel_rule_info() {
+    let (originator, source_token, destination_token) = (key(), key(), key());
+    let instruction = client::attach_travel_rule_info(
+        &originator,
+        &source_token,
+        &destination_token,
+        1_000,
+        [1; 32],
+        [2; 32],
+    );
+    assert_eq!(
+        decode_hook_instruction(&raw(instruction)).unwrap(),
+        HookOperation::AttachTravelRuleInfo {
+            originator,
+            source_token,
+            destination_token,
+            amount: 1_000,
+            originator_hash: [1; 32],
+            beneficiary_hash: [2; 32],
+        }
+    );
+}
+
+// ---------------- Instructions without a client builder ----------------
+
+#[test]
+fn batch_mint_tokens() {
+    let accounts = mica_eur::accounts::BatchMintTokens {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        token_program: key(),
+        operation_receipt: key(),
+        system_program: key(),
+        approval_set: Some(key()),
+        limit_config: Some(key()),
+        invocation_guard: key(),
+        role_assignment: Some(key()),
+        minter_allowance: Some(key()),
+        issuance_policy: Some(key()),
+    };
+    let recipients: Vec<Vec<Pubkey>> = (0..2)
+        .map(|_| {
+            (0..mica_eur::BATCH_MINT_ACCOUNTS_PER_RECIPIENT)
+                .map(|_| key())
+                .collect()
+        })
+        .collect();
+    let data = ix::BatchMintTokens {
+        amounts: vec![1_000, 2_000],
+        operation_id: [1; 16],
+    };
+    assert_eq!(
+        decode_with_remaining(&accounts, &recipients.concat(), data),
+        ProgramOperation::BatchMintTokens {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            token_accounts: vec![recipients[0][0], recipients[1][0]],
+            amounts: vec![1_000, 2_000],
+            operation_id: [1; 16],
+        }
+    );
+}
+
+#[test]
+fn batch_freeze_accounts() {
+    let accounts = mica_eur::accounts::BatchFreezeAccounts {
+        freeze_authority: key(),
+        mint_info: key(),
+        mint: key(),
+        token_program: key(),
+        system_program: key(),
+        role_assignment: Some(key()),
+    };
+    let targets: Vec<Vec<Pubkey>> = (0..2)
+        .map(|_| {
+            (0..mica_eur::BATCH_FREEZE_ACCOUNTS_PER_TARGET)
+                .map(|_| key())
+                .collect()
+        })
+        .collect();
+    let data = ix::BatchFreezeAccounts {
+        reason_code: 2,
+        legal_reference: "AZ 123/26".to_string(),
+    };
+    assert_eq!(
+        decode_with_remaining(&accounts, &targets.concat(), data),
+        ProgramOperation::BatchFreezeAccounts {
+            freeze_authority: accounts.freeze_authority,
+            mint: accounts.mint,
+            token_accounts: vec![targets[0][0], targets[1][0]],
+            reason_code: 2,
+            legal_reference: "AZ 123/26".to_string(),
+        }
+    );
+}
+
+#[test]
+fn batch_thaw_accounts() {
+    let accounts = mica_eur::accounts::BatchThawAccounts {
+        freeze_authority: key(),
+        mint_info: key(),
+        mint: key(),
+        token_program: key(),
+        role_assignment: Some(key()),
+    };
+    let targets: Vec<Vec<Pubkey>> = (0..2)
+        .map(|_| {
+            (0..mica_eur::BATCH_FREEZE_ACCOUNTS_PER_TARGET)
+                .map(|_| key())
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        decode_with_remaining(&accounts, &targets.concat(), ix::BatchThawAccounts {}),
+        ProgramOperation::BatchThawAccounts {
+            freeze_authority: accounts.freeze_authority,
+            mint: accounts.mint,
+            token_accounts: vec![targets[0][0], targets[1][0]],
+        }
+    );
+}
+
+#[test]
+fn harvest_fees_to_treasury() {
+    let accounts = mica_eur::accounts::WithdrawWithheldFees {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        fee_treasury: key(),
+        token_program: key(),
+    };
+    let sources = vec![key(), key()];
+    assert_eq!(
+        decode_with_remaining(&accounts, &sources, ix::HarvestFeesToTreasury {}),
+        ProgramOperation::HarvestFeesToTreasury {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            fee_treasury: accounts.fee_treasury,
+            sources,
+        }
+    );
+}
+
+#[test]
+fn initialize() {
+    let accounts = mica_eur::accounts::Initialize {};
+    let data = ix::Initialize {};
+    assert_eq!(decode(&accounts, data), ProgramOperation::Initialize);
+}
+
+#[test]
+fn initialize_kyc_oracle() {
+    let accounts = mica_eur::accounts::InitializeKycOracle {
+        authority: key(),
+        kyc_oracle_state: key(),
+        system_program: key(),
+        rent: key(),
+    };
+    let data = ix::InitializeKycOracle {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::InitializeKycOracle {
+            authority: accounts.authority,
+        }
+    );
+}
+
+#[test]
+fn close_kyc_user() {
+    let accounts = mica_eur::accounts::CloseKycUser {
+        authority: key(),
+        kyc_oracle_state: key(),
+        kyc_user: key(),
+        mint_info: key(),
+        mint: key(),
+        token_account: key(),
+        kyc_provider: Some(key()),
+        role_assignment: Some(key()),
+    };
+    let data = ix::CloseKycUser {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::CloseKycUser {
+            authority: accounts.authority,
+            kyc_user: accounts.kyc_user,
+            mint: accounts.mint,
+        }
+    );
+}
+
+#[test]
+fn add_kyc_provider() {
+    let provider = key();
+    let accounts = mica_eur::accounts::AddKycProvider {
+        authority: key(),
+        kyc_oracle_state: key(),
+        kyc_provider: key(),
+        system_program: key(),
+    };
+    let data = ix::AddKycProvider {
+        provider,
+        name: "name".to_string(),
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::AddKycProvider {
+            authority: accounts.authority,
+            provider,
+            name: "name".to_string(),
+        }
+    );
+}
+
+#[test]
+fn remove_kyc_provider() {
+    let accounts = mica_eur::accounts::RemoveKycProvider {
+        authority: key(),
+        kyc_oracle_state: key(),
+        kyc_provider: key(),
+    };
+    let data = ix::RemoveKycProvider {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RemoveKycProvider {
+            authority: accounts.authority,
+            kyc_provider: accounts.kyc_provider,
+        }
+    );
+}
+
+#[test]
+fn deactivate_screening_provider() {
+    let accounts = mica_eur::accounts::DeactivateScreeningProvider {
+        authority: key(),
+        kyc_oracle_state: key(),
+        screening_provider: key(),
+    };
+    let data = ix::DeactivateScreeningProvider {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::DeactivateScreeningProvider {
+            authority: accounts.authority,
+            screening_provider: accounts.screening_provider,
+        }
+    );
+}
+
+#[test]
+fn enforce_blacklist() {
+    let accounts = mica_eur::accounts::EnforceBlacklist {
+        caller: key(),
+        mint_info: key(),
+        mint: key(),
+        token_account: key(),
+        blacklist_entry: key(),
+        freeze_record: key(),
+        token_program: key(),
+        system_program: key(),
+        compliance_history: Some(key()),
+    };
+    let data = ix::EnforceBlacklist {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::EnforceBlacklist {
+            caller: accounts.caller,
+            mint: accounts.mint,
+            token_account: accounts.token_account,
+            blacklist_entry: accounts.blacklist_entry,
+        }
+    );
+}
+
+#[test]
+fn set_supply_cap() {
+    let accounts = mica_eur::accounts::SetSupplyCap {
+        issuer: key(),
+        mint_info: key(),
+    };
+    let data = ix::SetSupplyCap { max_supply: 1000 };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetSupplyCap {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+            max_supply: 1000,
+        }
+    );
+}
+
+#[test]
+fn apply_supply_cap() {
+    let accounts = mica_eur::accounts::ApplySupplyCap { mint_info: key() };
+    let data = ix::ApplySupplyCap {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ApplySupplyCap {
+            mint_info: accounts.mint_info,
+        }
+    );
+}
+
+#[test]
+fn set_issuance_schedule() {
+    let accounts = mica_eur::accounts::SetIssuanceSchedule {
+        issuer: key(),
+        mint_info: key(),
+        issuance_policy: key(),
+        system_program: key(),
+    };
+    let data = ix::SetIssuanceSchedule {
+        monthly_limit: 1000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetIssuanceSchedule {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+            monthly_limit: 1000,
+        }
+    );
+}
+
+#[test]
+fn configure_timelock() {
+    let accounts = mica_eur::accounts::ConfigureTimelock {
+        issuer: key(),
+        mint_info: key(),
+        timelock: key(),
+        system_program: key(),
+    };
+    let data = ix::ConfigureTimelock { delay: 1000 };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ConfigureTimelock {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+            timelock: accounts.timelock,
+            delay: 1000,
+        }
+    );
+}
+
+#[test]
+fn queue_operation() {
+    let accounts = mica_eur::accounts::QueueOperation {
+        issuer: key(),
+        mint_info: key(),
+        timelock: key(),
+        operation: key(),
+        system_program: key(),
+    };
+    let data = ix::QueueOperation {
+        action: TimelockAction::SetSupplyCap { max_supply: 1_000 },
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::QueueOperation {
+            issuer: accounts.issuer,
+            timelock: accounts.timelock,
+            operation: accounts.operation,
+            action: TimelockAction::SetSupplyCap { max_supply: 1_000 },
+        }
+    );
+}
+
+#[test]
+fn execute_operation() {
+    let accounts = mica_eur::accounts::ExecuteOperation {
+        mint_info: key(),
+        mint: key(),
+        timelock: key(),
+        operation: key(),
+        token_program: key(),
+        fee_treasury: Some(key()),
+    };
+    let data = ix::ExecuteOperation {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ExecuteOperation {
+            mint: accounts.mint,
+            operation: accounts.operation,
+        }
+    );
+}
+
+#[test]
+fn cancel_operation() {
+    let accounts = mica_eur::accounts::CancelOperation {
+        issuer: key(),
+        mint_info: key(),
+        timelock: key(),
+        operation: key(),
+    };
+    let data = ix::CancelOperation {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::CancelOperation {
+            issuer: accounts.issuer,
+            operation: accounts.operation,
+        }
+    );
+}
+
+#[test]
+fn update_whitepaper_uri() {
+    let accounts = mica_eur::accounts::UpdateWhitepaperUri {
+        issuer: key(),
+        mint_info: key(),
+        whitepaper_version: key(),
+        system_program: key(),
+        timelock_operation: Some(key()),
+    };
+    let data = ix::UpdateWhitepaperUri {
+        uri: "uri".to_string(),
+        content_hash: [2; 32],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::UpdateWhitepaperUri {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+            whitepaper_version: accounts.whitepaper_version,
+            uri: "uri".to_string(),
+            content_hash: [2; 32],
+        }
+    );
+}
+
+#[test]
+fn initialize_token_metadata() {
+    let accounts = mica_eur::accounts::WriteTokenMetadata {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        token_program: key(),
+        system_program: key(),
+    };
+    let data = ix::InitializeTokenMetadata {
+        name: "name".to_string(),
+        uri: "uri".to_string(),
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::InitializeTokenMetadata {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            name: "name".to_string(),
+            uri: "uri".to_string(),
+        }
+    );
+}
+
+#[test]
+fn update_metadata_field() {
+    let accounts = mica_eur::accounts::WriteTokenMetadata {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        token_program: key(),
+        system_program: key(),
+    };
+    let data = ix::UpdateMetadataField {
+        field: MetadataField::Key("desk".to_string()),
+        value: "value".to_string(),
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::UpdateMetadataField {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            field: MetadataField::Key("desk".to_string()),
+            value: "value".to_string(),
+        }
+    );
+}
+
+#[test]
+fn publish_compliance_snapshot() {
+    let accounts = mica_eur::accounts::PublishComplianceSnapshot {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        kyc_oracle_state: key(),
+        compliance_snapshot: key(),
+        system_program: key(),
+    };
+    let data = ix::PublishComplianceSnapshot {
+        period: 10,
+        frozen_accounts: 2000,
+        seizure_count: 3000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::PublishComplianceSnapshot {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            compliance_snapshot: accounts.compliance_snapshot,
+            period: 10,
+            frozen_accounts: 2000,
+            seizure_count: 3000,
+        }
+    );
+}
+
+#[test]
+fn set_country_policy() {
+    let accounts = mica_eur::accounts::SetCountryPolicy {
+        authority: key(),
+        kyc_oracle_state: key(),
+        country_policy: key(),
+        system_program: key(),
+    };
+    let data = ix::SetCountryPolicy {
+        country_code: "country_code".to_string(),
+        allowed: true,
+        max_transaction_amount: 3000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetCountryPolicy {
+            authority: accounts.authority,
+            country_policy: accounts.country_policy,
+            country_code: "country_code".to_string(),
+            allowed: true,
+            max_transaction_amount: 3000,
+        }
+    );
+}
+
+#[test]
+fn relink_iban() {
+    let new_user = key();
+    let accounts = mica_eur::accounts::RelinkIban {
+        authority: key(),
+        kyc_oracle_state: key(),
+        iban_registry: key(),
+    };
+    let data = ix::RelinkIban { new_user };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RelinkIban {
+            authority: accounts.authority,
+            iban_registry: accounts.iban_registry,
+            new_user,
+        }
+    );
+}
+
+#[test]
+fn migrate_kyc_user() {
+    let accounts = mica_eur::accounts::MigrateKycUser {
+        authority: key(),
+        new_user: key(),
+        kyc_oracle_state: key(),
+        old_kyc_user: key(),
+        new_kyc_user: key(),
+        blacklist_entry: key(),
+        old_user_limits: key(),
+        new_user_limits: key(),
+        iban_registry: key(),
+        mint_info: key(),
+        mint: key(),
+        old_token_account: key(),
+        freeze_record: key(),
+        token_program: key(),
+        system_program: key(),
+        kyc_provider: Some(key()),
+        role_assignment: Some(key()),
+    };
+    let data = ix::MigrateKycUser {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::MigrateKycUser {
+            authority: accounts.authority,
+            old_kyc_user: accounts.old_kyc_user,
+            new_user: accounts.new_user,
+            mint: accounts.mint,
+            old_token_account: accounts.old_token_account,
+        }
+    );
+}
+
+#[test]
+fn request_recovery() {
+    let accounts = mica_eur::accounts::RequestRecovery {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        source_token_account: key(),
+        destination_token_account: key(),
+        new_kyc_user: key(),
+        recovery_request: key(),
+        system_program: key(),
+    };
+    let data = ix::RequestRecovery {
+        evidence_hash: [1; 32],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RequestRecovery {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            source_token_account: accounts.source_token_account,
+            destination_token_account: accounts.destination_token_account,
+            recovery_request: accounts.recovery_request,
+            evidence_hash: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn approve_recovery() {
+    let accounts = mica_eur::accounts::ApproveRecovery {
+        officer: key(),
+        aml_authority: key(),
+        recovery_request: key(),
+    };
+    let data = ix::ApproveRecovery {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ApproveRecovery {
+            officer: accounts.officer,
+            recovery_request: accounts.recovery_request,
+        }
+    );
+}
+
+#[test]
+fn recover_account() {
+    let accounts = mica_eur::accounts::RecoverAccount {
+        issuer: key(),
+        mint_info: key(),
+        seizure_authority: key(),
+        mint: key(),
+        recovery_request: key(),
+        source_token_account: key(),
+        destination_token_account: key(),
+        new_kyc_user: key(),
+        freeze_record: key(),
+        token_program: key(),
+        invocation_guard: key(),
+    };
+    let data = ix::RecoverAccount {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RecoverAccount {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            recovery_request: accounts.recovery_request,
+            source_token_account: accounts.source_token_account,
+            destination_token_account: accounts.destination_token_account,
+        }
+    );
+}
+
+#[test]
+fn cancel_recovery() {
+    let accounts = mica_eur::accounts::CancelRecovery {
+        issuer: key(),
+        mint_info: key(),
+        recovery_request: key(),
+    };
+    let data = ix::CancelRecovery {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::CancelRecovery {
+            issuer: accounts.issuer,
+            recovery_request: accounts.recovery_request,
+        }
+    );
+}
+
+#[test]
+fn register_counterparty() {
+    let wallet = key();
+    let accounts = mica_eur::accounts::RegisterCounterparty {
+        issuer: key(),
+        mint_info: key(),
+        approved_counterparty: key(),
+        system_program: key(),
+    };
+    let data = ix::RegisterCounterparty {
+        wallet,
+        kind: CounterpartyKind::Exchange,
+        lei: [3; 20],
+        daily_limit_exempt: true,
+        max_transfer_amount: 5000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RegisterCounterparty {
+            issuer: accounts.issuer,
+            approved_counterparty: accounts.approved_counterparty,
+            wallet,
+            kind: CounterpartyKind::Exchange,
+            lei: [3; 20],
+            daily_limit_exempt: true,
+            max_transfer_amount: 5000,
+        }
+    );
+}
+
+#[test]
+fn remove_counterparty() {
+    let accounts = mica_eur::accounts::RemoveCounterparty {
+        issuer: key(),
+        mint_info: key(),
+        approved_counterparty: key(),
+    };
+    let data = ix::RemoveCounterparty {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RemoveCounterparty {
+            issuer: accounts.issuer,
+            approved_counterparty: accounts.approved_counterparty,
+        }
+    );
+}
+
+#[test]
+fn configure_aml_alerts() {
+    let accounts = mica_eur::accounts::ConfigureAmlAlerts {
+        issuer: key(),
+        mint_info: key(),
+        aml_alert_config: key(),
+        system_program: key(),
+    };
+    let data = ix::ConfigureAmlAlerts {
+        max_hourly_transfers: 10,
+        max_hourly_volume: 2000,
+        structuring_threshold: 3000,
+        structuring_margin: 4000,
+        structuring_count: 50,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ConfigureAmlAlerts {
+            issuer: accounts.issuer,
+            aml_alert_config: accounts.aml_alert_config,
+            max_hourly_transfers: 10,
+            max_hourly_volume: 2000,
+            structuring_threshold: 3000,
+            structuring_margin: 4000,
+            structuring_count: 50,
+        }
+    );
+}
+
+#[test]
+fn anchor_sar_reference() {
+    let accounts = mica_eur::accounts::AnchorSarReference {
+        officer: key(),
+        aml_authority: key(),
+        kyc_user: key(),
+        sar_reference: key(),
+        system_program: key(),
+    };
+    let data = ix::AnchorSarReference {
+        sar_hash: [1; 32],
+        filing_date: 2000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::AnchorSarReference {
+            officer: accounts.officer,
+            kyc_user: accounts.kyc_user,
+            sar_reference: accounts.sar_reference,
+            sar_hash: [1; 32],
+            filing_date: 2000,
+        }
+    );
+}
+
+#[test]
+fn get_user_compliance_status() {
+    let accounts = mica_eur::accounts::GetUserComplianceStatus {
+        user: key(),
+        kyc_user: key(),
+        blacklist_entry: key(),
+        user_limits: key(),
+    };
+    let data = ix::GetUserComplianceStatus {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::GetUserComplianceStatus {
+            user: accounts.user,
+        }
+    );
+}
+
+#[test]
+fn get_mint_status() {
+    let accounts = mica_eur::accounts::GetMintStatus {
+        mint_info: key(),
+        mint: key(),
+    };
+    let data = ix::GetMintStatus {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::GetMintStatus {
+            mint: accounts.mint,
+        }
+    );
+}
+
+#[test]
+fn get_outstanding_supply() {
+    let accounts = mica_eur::accounts::GetOutstandingSupply {
+        mint_info: key(),
+        mint: key(),
+    };
+    let data = ix::GetOutstandingSupply {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::GetOutstandingSupply {
+            mint: accounts.mint,
+        }
+    );
+}
+
+#[test]
+fn get_reserve_status() {
+    let accounts = mica_eur::accounts::GetReserveStatus {
+        mint_info: key(),
+        mint: key(),
+        auditor_attestation: key(),
+    };
+    let data = ix::GetReserveStatus {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::GetReserveStatus {
+            mint: accounts.mint,
+        }
+    );
+}
+
+#[test]
+fn auto_thaw() {
+    let accounts = mica_eur::accounts::AutoThaw {
+        freeze_record: key(),
+    };
+    let data = ix::AutoThaw {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::AutoThaw {
+            freeze_record: accounts.freeze_record,
+        }
+    );
+}
+
+#[test]
+fn configure_seizure_vault() {
+    let accounts = mica_eur::accounts::ConfigureSeizureVault {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        seizure_vault: key(),
+        vault: key(),
+        token_program: key(),
+        system_program: key(),
+    };
+    let data = ix::ConfigureSeizureVault {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ConfigureSeizureVault {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            vault: accounts.vault,
+        }
+    );
+}
+
+#[test]
+fn seize_to_vault() {
+    let accounts = mica_eur::accounts::SeizeToVault {
+        permanent_delegate: key(),
+        mint_info: key(),
+        seizure_authority: key(),
+        mint: key(),
+        seizure_vault: key(),
+        vault: key(),
+        from_account: key(),
+        seizure_record: key(),
+        operation_receipt: key(),
+        token_program: key(),
+        system_program: key(),
+        invocation_guard: key(),
+        role_assignment: Some(key()),
+    };
+    let data = ix::SeizeToVault {
+        amount: 1000,
+        operation_id: [2; 16],
+        case_hash: [3; 32],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SeizeToVault {
+            permanent_delegate: accounts.permanent_delegate,
+            mint: accounts.mint,
+            from: accounts.from_account,
+            seizure_record: accounts.seizure_record,
+            amount: 1000,
+            operation_id: [2; 16],
+            case_hash: [3; 32],
+        }
+    );
+}
+
+#[test]
+fn release_from_vault() {
+    let accounts = mica_eur::accounts::ResolveSeizure {
+        permanent_delegate: key(),
+        mint_info: key(),
+        seizure_authority: key(),
+        mint: key(),
+        seizure_vault: key(),
+        vault: key(),
+        seizure_record: key(),
+        destination_token_account: Some(key()),
+        token_program: key(),
+        invocation_guard: key(),
+        role_assignment: Some(key()),
+    };
+    let data = ix::ReleaseFromVault {
+        resolution_hash: [1; 32],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ReleaseFromVault {
+            permanent_delegate: accounts.permanent_delegate,
+            seizure_record: accounts.seizure_record,
+            destination_token_account: accounts.destination_token_account.unwrap(),
+            resolution_hash: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn forfeit_from_vault() {
+    let accounts = mica_eur::accounts::ResolveSeizure {
+        permanent_delegate: key(),
+        mint_info: key(),
+        seizure_authority: key(),
+        mint: key(),
+        seizure_vault: key(),
+        vault: key(),
+        seizure_record: key(),
+        destination_token_account: Some(key()),
+        token_program: key(),
+        invocation_guard: key(),
+        role_assignment: Some(key()),
+    };
+    let data = ix::ForfeitFromVault {
+        resolution_hash: [1; 32],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ForfeitFromVault {
+            permanent_delegate: accounts.permanent_delegate,
+            seizure_record: accounts.seizure_record,
+            resolution_hash: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn configure_transfer_fee() {
+    let accounts = mica_eur::accounts::ConfigureTransferFee {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        fee_treasury: key(),
+        token_program: key(),
+    };
+    let data = ix::ConfigureTransferFee {
+        transfer_fee_bps: 10,
+        max_transfer_fee: 2000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ConfigureTransferFee {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            fee_treasury: accounts.fee_treasury,
+            transfer_fee_bps: 10,
+            max_transfer_fee: 2000,
+        }
+    );
+}
+
+#[test]
+fn withdraw_withheld_fees() {
+    let accounts = mica_eur::accounts::WithdrawWithheldFees {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        fee_treasury: key(),
+        token_program: key(),
+    };
+    let data = ix::WithdrawWithheldFees {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::WithdrawWithheldFees {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            fee_treasury: accounts.fee_treasury,
+        }
+    );
+}
+
+#[test]
+fn enable_confidential_transfers() {
+    let accounts = mica_eur::accounts::EnableConfidentialTransfers {
+        issuer: key(),
+        auditor: key(),
+        aml_authority: key(),
+        mint_info: key(),
+        mint: key(),
+        token_program: key(),
+    };
+    let data = ix::EnableConfidentialTransfers {
+        auditor_elgamal_pubkey: [1; 32],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::EnableConfidentialTransfers {
+            issuer: accounts.issuer,
+            auditor: accounts.auditor,
+            mint: accounts.mint,
+            auditor_elgamal_pubkey: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn approve_confidential_account() {
+    let accounts = mica_eur::accounts::ApproveConfidentialAccount {
+        owner: key(),
+        mint_info: key(),
+        mint: key(),
+        token_account: key(),
+        kyc_user: key(),
+        blacklist_entry: key(),
+        token_program: key(),
+    };
+    let data = ix::ApproveConfidentialAccount {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ApproveConfidentialAccount {
+            owner: accounts.owner,
+            mint: accounts.mint,
+            token_account: accounts.token_account,
+        }
+    );
+}
+
+#[test]
+fn set_interest_rate() {
+    let accounts = mica_eur::accounts::SetInterestRate {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        rate_history: key(),
+        rate_change: key(),
+        token_program: key(),
+        system_program: key(),
+    };
+    let data = ix::SetInterestRate { rate: 10 };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetInterestRate {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            rate_change: accounts.rate_change,
+            rate: 10,
+        }
+    );
+}
+
+#[test]
+fn check_reserve_coverage() {
+    let accounts = mica_eur::accounts::CheckReserveCoverage {
+        mint_info: key(),
+        mint: key(),
+    };
+    let data = ix::CheckReserveCoverage {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::CheckReserveCoverage {
+            mint_info: accounts.mint_info,
+            mint: accounts.mint,
+        }
+    );
+}
+
+#[test]
+fn set_coverage_auto_pause() {
+    let accounts = mica_eur::accounts::SetCoverageAutoPause {
+        issuer: key(),
+        mint_info: key(),
+    };
+    let data = ix::SetCoverageAutoPause { enabled: true };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetCoverageAutoPause {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+            enabled: true,
+        }
+    );
+}
+
+#[test]
+fn publish_balance_snapshot() {
+    let accounts = mica_eur::accounts::PublishBalanceSnapshot {
+        issuer: key(),
+        mint_info: key(),
+        snapshot_registry: key(),
+        balance_snapshot: key(),
+        system_program: key(),
+    };
+    let data = ix::PublishBalanceSnapshot {
+        merkle_root: [1; 32],
+        snapshot_slot: 2000,
+        holder_count: 30,
+        total_balance: 4000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::PublishBalanceSnapshot {
+            issuer: accounts.issuer,
+            balance_snapshot: accounts.balance_snapshot,
+            merkle_root: [1; 32],
+            snapshot_slot: 2000,
+            holder_count: 30,
+            total_balance: 4000,
+        }
+    );
+}
+
+#[test]
+fn record_reconciliation() {
+    let accounts = mica_eur::accounts::RecordReconciliation {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        reconciliation_log: key(),
+        reconciliation_record: key(),
+        system_program: key(),
+    };
+    let data = ix::RecordReconciliation {
+        pending_redemptions: 1000,
+        registered_deposits: 2000,
+        ledger_balance: 3000,
+        ledger_hash: [4; 32],
+        first_detected_time: 5000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RecordReconciliation {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            reconciliation_record: accounts.reconciliation_record,
+            pending_redemptions: 1000,
+            registered_deposits: 2000,
+            ledger_balance: 3000,
+            first_detected_time: 5000,
+        }
+    );
+}
+
+#[test]
+fn configure_reserve_auditor() {
+    let auditor = key();
+    let accounts = mica_eur::accounts::ConfigureReserveAuditor {
+        issuer: key(),
+        mint_info: key(),
+        auditor_attestation: key(),
+        system_program: key(),
+    };
+    let data = ix::ConfigureReserveAuditor {
+        auditor,
+        attestation_window: 2000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ConfigureReserveAuditor {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+            auditor,
+            attestation_window: 2000,
+        }
+    );
+}
+
+#[test]
+fn attest_reserve_proof() {
+    let accounts = mica_eur::accounts::AttestReserveProof {
+        auditor: key(),
+        mint_info: key(),
+        auditor_attestation: key(),
+    };
+    let data = ix::AttestReserveProof {
+        merkle_root: [1; 32],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::AttestReserveProof {
+            auditor: accounts.auditor,
+            mint_info: accounts.mint_info,
+            merkle_root: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn verify_reserve_inclusion() {
+    let accounts = mica_eur::accounts::VerifyReserveInclusion { mint_info: key() };
+    let data = ix::VerifyReserveInclusion {
+        leaf: [1; 32],
+        proof: vec![[2; 32], [3; 32]],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::VerifyReserveInclusion {
+            mint_info: accounts.mint_info,
+            leaf: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn reserve_inclusion() {
+    let accounts = mica_eur::accounts::VerifyReserveInclusion { mint_info: key() };
+    let data = ix::ReserveInclusion {
+        leaf: [1; 32],
+        proof: vec![[2; 32], [3; 32]],
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ReserveInclusion {
+            mint_info: accounts.mint_info,
+            leaf: [1; 32],
+        }
+    );
+}
+
+#[test]
+fn stage_policy() {
+    let accounts = mica_eur::accounts::StagePolicy {
+        issuer: key(),
+        mint_info: key(),
+        staged_policy: key(),
+        system_program: key(),
+    };
+    let data = ix::StagePolicy {
+        rule_set_version: 10,
+        params: ACTIVE_POLICY_PARAMS,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::StagePolicy {
+            issuer: accounts.issuer,
+            rule_set_version: 10,
+            params: ACTIVE_POLICY_PARAMS,
+        }
+    );
+}
+
+#[test]
+fn clear_staged_policy() {
+    let accounts = mica_eur::accounts::ClearStagedPolicy {
+        issuer: key(),
+        mint_info: key(),
+        staged_policy: key(),
+    };
+    let data = ix::ClearStagedPolicy {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ClearStagedPolicy {
+            issuer: accounts.issuer,
+            staged_policy: accounts.staged_policy,
+        }
+    );
+}
+
+#[test]
+fn start_migration() {
+    let accounts = mica_eur::accounts::StartMigration {
+        issuer: key(),
+        old_mint_info: key(),
+        new_mint_info: key(),
+        migration: key(),
+        system_program: key(),
+    };
+    let data = ix::StartMigration {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::StartMigration {
+            issuer: accounts.issuer,
+            old_mint_info: accounts.old_mint_info,
+            new_mint_info: accounts.new_mint_info,
+        }
+    );
+}
+
+#[test]
+fn claim_migration() {
+    let accounts = mica_eur::accounts::ClaimMigration {
+        holder: key(),
+        issuer: key(),
+        migration: key(),
+        old_mint: key(),
+        new_mint: key(),
+        new_mint_info: key(),
+        old_token_account: key(),
+        new_token_account: key(),
+        kyc_user: key(),
+        blacklist_entry: key(),
+        migration_record: key(),
+        token_program: key(),
+        system_program: key(),
+        invocation_guard: key(),
+        old_mint_info: key(),
+        freeze_record: key(),
+    };
+    let data = ix::ClaimMigration {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ClaimMigration {
+            holder: accounts.holder,
+            old_mint: accounts.old_mint,
+            new_mint: accounts.new_mint,
+            old_token_account: accounts.old_token_account,
+            new_token_account: accounts.new_token_account,
+        }
+    );
+}
+
+#[test]
+fn complete_migration() {
+    let accounts = mica_eur::accounts::CompleteMigration {
+        issuer: key(),
+        migration: key(),
+    };
+    let data = ix::CompleteMigration {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::CompleteMigration {
+            issuer: accounts.issuer,
+            migration: accounts.migration,
+        }
+    );
+}
+
+#[test]
+fn set_edd_flag() {
+    let accounts = mica_eur::accounts::SetEddFlag {
+        authority: key(),
+        kyc_oracle_state: key(),
+        kyc_user: key(),
+    };
+    let data = ix::SetEddFlag { edd_required: true };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetEddFlag {
+            authority: accounts.authority,
+            kyc_user: accounts.kyc_user,
+            edd_required: true,
+        }
+    );
+}
+
+#[test]
+fn configure_edd() {
+    let accounts = mica_eur::accounts::ConfigureEdd {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        edd_config: key(),
+        vault: key(),
+        token_program: key(),
+        system_program: key(),
+    };
+    let data = ix::ConfigureEdd {
+        transfer_threshold: 1000,
+        approval_window: 2000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ConfigureEdd {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            vault: accounts.vault,
+            transfer_threshold: 1000,
+            approval_window: 2000,
+        }
+    );
+}
+
+#[test]
+fn request_edd_transfer() {
+    let accounts = mica_eur::accounts::RequestEddTransfer {
+        sender: key(),
+        mint_info: key(),
+        mint: key(),
+        edd_config: key(),
+        sender_kyc_user: key(),
+        recipient_kyc_user: key(),
+        sender_token_account: key(),
+        recipient_token_account: key(),
+        vault: key(),
+        pending_approval: key(),
+        token_program: key(),
+        system_program: key(),
+        invocation_guard: key(),
+    };
+    let data = ix::RequestEddTransfer {
+        request_id: 1000,
+        amount: 2000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RequestEddTransfer {
+            sender: accounts.sender,
+            mint: accounts.mint,
+            sender_token_account: accounts.sender_token_account,
+            recipient_token_account: accounts.recipient_token_account,
+            pending_approval: accounts.pending_approval,
+            request_id: 1000,
+            amount: 2000,
+        }
+    );
+}
+
+#[test]
+fn approve_edd_transfer() {
+    let accounts = mica_eur::accounts::ReviewEddTransfer {
+        officer: key(),
+        aml_authority: key(),
+        mint_info: key(),
+        mint: key(),
+        edd_config: key(),
+        vault: key(),
+        pending_approval: key(),
+        destination_token_account: key(),
+        token_program: key(),
+        invocation_guard: key(),
+    };
+    let data = ix::ApproveEddTransfer {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ApproveEddTransfer {
+            officer: accounts.officer,
+            pending_approval: accounts.pending_approval,
+            destination_token_account: accounts.destination_token_account,
+        }
+    );
+}
+
+#[test]
+fn reject_edd_transfer() {
+    let accounts = mica_eur::accounts::ReviewEddTransfer {
+        officer: key(),
+        aml_authority: key(),
+        mint_info: key(),
+        mint: key(),
+        edd_config: key(),
+        vault: key(),
+        pending_approval: key(),
+        destination_token_account: key(),
+        token_program: key(),
+        invocation_guard: key(),
+    };
+    let data = ix::RejectEddTransfer {
+        decision_reason: 10,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RejectEddTransfer {
+            officer: accounts.officer,
+            pending_approval: accounts.pending_approval,
+            destination_token_account: accounts.destination_token_account,
+            decision_reason: 10,
+        }
+    );
+}
+
+#[test]
+fn expire_edd_transfer() {
+    let accounts = mica_eur::accounts::ExpireEddTransfer {
+        caller: key(),
+        mint_info: key(),
+        mint: key(),
+        edd_config: key(),
+        vault: key(),
+        pending_approval: key(),
+        sender_token_account: key(),
+        token_program: key(),
+        invocation_guard: key(),
+    };
+    let data = ix::ExpireEddTransfer {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ExpireEddTransfer {
+            caller: accounts.caller,
+            pending_approval: accounts.pending_approval,
+            sender_token_account: accounts.sender_token_account,
+        }
+    );
+}
+
+#[test]
+fn set_limits() {
+    let accounts = mica_eur::accounts::SetLimits {
+        issuer: key(),
+        mint_info: key(),
+        limit_config: key(),
+        system_program: key(),
+    };
+    let data = ix::SetLimits {
+        tiers: DEFAULT_TIER_LIMITS,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetLimits {
+            issuer: accounts.issuer,
+            limit_config: accounts.limit_config,
+            tiers: DEFAULT_TIER_LIMITS,
+        }
+    );
+}
+
+#[test]
+fn configure_approval_set() {
+    let signers = vec![key(), key()];
+    let accounts = mica_eur::accounts::ConfigureApprovalSet {
+        issuer: key(),
+        mint_info: key(),
+        approval_set: key(),
+        system_program: key(),
+    };
+    let data = ix::ConfigureApprovalSet {
+        signers: signers.clone(),
+        threshold: 20,
+        large_mint_threshold: 3000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ConfigureApprovalSet {
+            issuer: accounts.issuer,
+            approval_set: accounts.approval_set,
+            signers,
+            threshold: 20,
+            large_mint_threshold: 3000,
+        }
+    );
+}
+
+#[test]
+fn propose_action() {
+    let token_account = key();
+    let accounts = mica_eur::accounts::ProposeAction {
+        officer: key(),
+        approval_set: key(),
+        proposal: key(),
+        system_program: key(),
+    };
+    let data = ix::ProposeAction {
+        action: RegulatoryAction::Mint {
+            token_account,
+            amount: 1_000,
+        },
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ProposeAction {
+            officer: accounts.officer,
+            proposal: accounts.proposal,
+            action: RegulatoryAction::Mint {
+                token_account,
+                amount: 1_000
+            },
+        }
+    );
+}
+
+#[test]
+fn approve_action() {
+    let accounts = mica_eur::accounts::ReviewAction {
+        officer: key(),
+        approval_set: key(),
+        proposal: key(),
+    };
+    let data = ix::ApproveAction {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ApproveAction {
+            officer: accounts.officer,
+            proposal: accounts.proposal,
+        }
+    );
+}
+
+#[test]
+fn cancel_action() {
+    let accounts = mica_eur::accounts::ReviewAction {
+        officer: key(),
+        approval_set: key(),
+        proposal: key(),
+    };
+    let data = ix::CancelAction {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::CancelAction {
+            officer: accounts.officer,
+            proposal: accounts.proposal,
+        }
+    );
+}
+
+#[test]
+fn execute_action() {
+    let accounts = mica_eur::accounts::ExecuteAction {
+        authority: key(),
+        mint_info: key(),
+        seizure_authority: key(),
+        mint: key(),
+        approval_set: key(),
+        proposal: key(),
+        token_account: key(),
+        destination_token_account: Some(key()),
+        kyc_user: Some(key()),
+        blacklist_entry: key(),
+        token_program: key(),
+        invocation_guard: key(),
+        role_assignment: Some(key()),
+        issuance_policy: Some(key()),
+        country_policy: Some(key()),
+        freeze_record: key(),
+        user_limits: key(),
+        limit_config: Some(key()),
+        system_program: key(),
+        minter_allowance: Some(key()),
+    };
+    let data = ix::ExecuteAction {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ExecuteAction {
+            authority: accounts.authority,
+            proposal: accounts.proposal,
+            token_account: accounts.token_account,
+        }
+    );
+}
+
+#[test]
+fn sync_rule_set() {
+    let accounts = mica_eur::accounts::SyncRuleSet {
+        issuer: key(),
+        mint_info: key(),
+    };
+    let data = ix::SyncRuleSet {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SyncRuleSet {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+        }
+    );
+}
+
+#[test]
+fn pause_token() {
+    let accounts = mica_eur::accounts::SetTokenPaused {
+        authority: key(),
+        mint_info: key(),
+        role_assignment: Some(key()),
+    };
+    let data = ix::PauseToken {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::PauseToken {
+            authority: accounts.authority,
+            mint_info: accounts.mint_info,
+        }
+    );
+}
+
+#[test]
+fn resume_token() {
+    let accounts = mica_eur::accounts::SetTokenPaused {
+        authority: key(),
+        mint_info: key(),
+        role_assignment: Some(key()),
+    };
+    let data = ix::ResumeToken {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ResumeToken {
+            authority: accounts.authority,
+            mint_info: accounts.mint_info,
+        }
+    );
+}
+
+#[test]
+fn grant_role() {
+    let accounts = mica_eur::accounts::GrantRole {
+        admin: key(),
+        scope: key(),
+        mint_info: Some(key()),
+        kyc_oracle_state: Some(key()),
+        holder: key(),
+        role_assignment: key(),
+        system_program: key(),
+    };
+    let data = ix::GrantRole { role: 10 };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::GrantRole {
+            admin: accounts.admin,
+            scope: accounts.scope,
+            holder: accounts.holder,
+            role: 10,
+        }
+    );
+}
+
+#[test]
+fn revoke_role() {
+    let accounts = mica_eur::accounts::RevokeRole {
+        admin: key(),
+        mint_info: Some(key()),
+        kyc_oracle_state: Some(key()),
+        role_assignment: key(),
+    };
+    let data = ix::RevokeRole { role: 10 };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RevokeRole {
+            admin: accounts.admin,
+            role_assignment: accounts.role_assignment,
+            role: 10,
+        }
+    );
+}
+
+#[test]
+fn set_minter_allowance() {
+    let accounts = mica_eur::accounts::SetMinterAllowance {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        minter: key(),
+        minter_allowance: key(),
+        system_program: key(),
+    };
+    let data = ix::SetMinterAllowance { allowance: 1000 };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetMinterAllowance {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            minter: accounts.minter,
+            allowance: 1000,
+        }
+    );
+}
+
+#[test]
+fn set_pauser() {
+    let new_pauser = key();
+    let accounts = mica_eur::accounts::SetPauser {
+        issuer: key(),
+        mint_info: key(),
+    };
+    let data = ix::SetPauser { new_pauser };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetPauser {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+            new_pauser,
+        }
+    );
+}
+
+#[test]
+fn set_associated_account_requirement() {
+    let accounts = mica_eur::accounts::SetAssociatedAccountRequirement {
+        issuer: key(),
+        mint_info: key(),
+    };
+    let data = ix::SetAssociatedAccountRequirement { required: true };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetAssociatedAccountRequirement {
+            issuer: accounts.issuer,
+            mint_info: accounts.mint_info,
+            required: true,
+        }
+    );
+}
+
+#[test]
+fn register_aml_authority() {
+    let accounts = mica_eur::accounts::RegisterAmlAuthority {
+        authority: key(),
+        aml_authority: key(),
+        system_program: key(),
+        approver: key(),
+        mint_info: key(),
+        regulator_registry: Some(key()),
+    };
+    let data = ix::RegisterAmlAuthority {
+        authority_id: "authority_id".to_string(),
+        powers: 20,
+        jurisdiction: "jurisdiction".to_string(),
+        global: true,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::RegisterAmlAuthority {
+            authority: accounts.authority,
+            approver: accounts.approver,
+            authority_id: "authority_id".to_string(),
+            powers: 20,
+            jurisdiction: "jurisdiction".to_string(),
+            global: true,
+        }
+    );
+}
+
+#[test]
+fn set_regulator() {
+    let regulator = key();
+    let accounts = mica_eur::accounts::SetRegulator {
+        issuer: key(),
+        mint_info: key(),
+        regulator_registry: key(),
+        system_program: key(),
+    };
+    let data = ix::SetRegulator { regulator };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::SetRegulator {
+            issuer: accounts.issuer,
+            regulator_registry: accounts.regulator_registry,
+            regulator,
+        }
+    );
+}
+
+#[test]
+fn create_blacklist_entry() {
+    let accounts = mica_eur::accounts::CreateBlacklistEntry {
+        authority: key(),
+        aml_authority: key(),
+        user: key(),
+        blacklist_entry: key(),
+        system_program: key(),
+        kyc_user: Some(key()),
+        compliance_history: Some(key()),
+    };
+    let data = ix::CreateBlacklistEntry {
+        reason: BlacklistReason::Fraud,
+        legal_reference: "legal_reference".to_string(),
+        expiry_time: 3000,
+        review_time: 4000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::CreateBlacklistEntry {
+            authority: accounts.authority,
+            user: accounts.user,
+            reason: BlacklistReason::Fraud,
+            legal_reference: "legal_reference".to_string(),
+            expiry_time: 3000,
+            review_time: 4000,
+        }
+    );
+}
+
+#[test]
+fn deactivate_aml_authority() {
+    let accounts = mica_eur::accounts::DeactivateAmlAuthority {
+        approver: key(),
+        aml_authority: key(),
+        mint_info: key(),
+        regulator_registry: Some(key()),
+    };
+    let data = ix::DeactivateAmlAuthority {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::DeactivateAmlAuthority {
+            approver: accounts.approver,
+            aml_authority: accounts.aml_authority,
+        }
+    );
+}
+
+#[test]
+fn deactivate_blacklist_entry() {
+    let accounts = mica_eur::accounts::DeactivateBlacklistEntry {
+        authority: key(),
+        aml_authority: key(),
+        user: key(),
+        blacklist_entry: key(),
+        kyc_user: Some(key()),
+        compliance_history: Some(key()),
+    };
+    let data = ix::DeactivateBlacklistEntry {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::DeactivateBlacklistEntry {
+            authority: accounts.authority,
+            user: accounts.user,
+        }
+    );
+}
+
+#[test]
+fn extend_blacklist_entry() {
+    let accounts = mica_eur::accounts::ExtendBlacklistEntry {
+        authority: key(),
+        aml_authority: key(),
+        blacklist_entry: key(),
+        kyc_user: Some(key()),
+    };
+    let data = ix::ExtendBlacklistEntry {
+        expiry_time: 1000,
+        review_time: 2000,
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ExtendBlacklistEntry {
+            authority: accounts.authority,
+            blacklist_entry: accounts.blacklist_entry,
+            expiry_time: 1000,
+            review_time: 2000,
+        }
+    );
+}
+
+#[test]
+fn lapse_blacklist_entry() {
+    let accounts = mica_eur::accounts::LapseBlacklistEntry {
+        caller: key(),
+        blacklist_entry: key(),
+    };
+    let data = ix::LapseBlacklistEntry {};
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::LapseBlacklistEntry {
+            caller: accounts.caller,
+            blacklist_entry: accounts.blacklist_entry,
+        }
+    );
+}
+
+#[test]
+fn reclassify_blacklist_entry() {
+    let accounts = mica_eur::accounts::ReclassifyBlacklistEntry {
+        authority: key(),
+        aml_authority: key(),
+        user: key(),
+        blacklist_entry: key(),
+        system_program: key(),
+        kyc_user: Some(key()),
+    };
+    let data = ix::ReclassifyBlacklistEntry {
+        reason: BlacklistReason::Fraud,
+        legal_reference: "legal_reference".to_string(),
+    };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::ReclassifyBlacklistEntry {
+            authority: accounts.authority,
+            user: accounts.user,
+            reason: BlacklistReason::Fraud,
+            legal_reference: "legal_reference".to_string(),
+        }
+    );
+}
+
+#[test]
+fn update_aml_authority_powers() {
+    let accounts = mica_eur::accounts::UpdateAmlAuthorityPowers {
+        approver: key(),
+        aml_authority: key(),
+        mint_info: key(),
+        regulator_registry: Some(key()),
+    };
+    let data = ix::UpdateAmlAuthorityPowers { new_powers: 10 };
+    assert_eq!(
+        decode(&accounts, data),
+        ProgramOperation::UpdateAmlAuthorityPowers {
+            approver: accounts.approver,
+            aml_authority: accounts.aml_authority,
+            new_powers: 10,
+        }
+    );
+}
+
+#[test]
+fn initialize_extra_account_meta_list() {
+    let accounts = mica_eur_transfer_hook::accounts::InitializeExtraAccountMetaList {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        extra_account_meta_list: key(),
+        system_program: key(),
+    };
+    let data = hook_ix::InitializeExtraAccountMetaList {};
+    assert_eq!(
+        decode_hook(&accounts, data),
+        HookOperation::InitializeExtraAccountMetaList {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            extra_account_meta_list: accounts.extra_account_meta_list,
+        }
+    );
+}
+
+#[test]
+fn update_extra_account_meta_list() {
+    let accounts = mica_eur_transfer_hook::accounts::UpdateExtraAccountMetaList {
+        issuer: key(),
+        mint_info: key(),
+        mint: key(),
+        extra_account_meta_list: key(),
+        system_program: key(),
+    };
+    let data = hook_ix::UpdateExtraAccountMetaList {};
+    assert_eq!(
+        decode_hook(&accounts, data),
+        HookOperation::UpdateExtraAccountMetaList {
+            issuer: accounts.issuer,
+            mint: accounts.mint,
+            extra_account_meta_list: accounts.extra_account_meta_list,
+        }
+    );
+}
+
+#[test]
+fn initialize_transfer_record() {
+    let accounts = mica_eur_transfer_hook::accounts::InitializeTransferRecord {
+        payer: key(),
+        user: key(),
+        transfer_record: key(),
+        system_program: key(),
+    };
+    let data = hook_ix::InitializeTransferRecord {};
+    assert_eq!(
+        decode_hook(&accounts, data),
+        HookOperation::InitializeTransferRecord {
+            payer: accounts.payer,
+            user: accounts.user,
+        }
+    );
+}
+
+#[test]
+fn initialize_velocity_record() {
+    let accounts = mica_eur_transfer_hook::accounts::InitializeVelocityRecord {
+        payer: key(),
+        user: key(),
+        velocity_record: key(),
+        system_program: key(),
+    };
+    let data = hook_ix::InitializeVelocityRecord {};
+    assert_eq!(
+        decode_hook(&accounts, data),
+        HookOperation::InitializeVelocityRecord {
+            payer: accounts.payer,
+            user: accounts.user,
+        }
+    );
+}
+
+#[test]
+fn transfer_hook() {
+    let accounts = mica_eur_transfer_hook::accounts::TransferHook {
+        source_token: key(),
+        mint: key(),
+        destination_token: key(),
+        owner: key(),
+        extra_account_meta_list: key(),
+        mica_eur_program: key(),
+        mint_info: key(),
+        sender_kyc_user: key(),
+        recipient_kyc_user: key(),
+        sender_blacklist: key(),
+        recipient_blacklist: key(),
+        staged_policy: key(),
+        edd_config: key(),
+        limit_config: key(),
+        sender_transfer_record: key(),
+        recipient_transfer_record: key(),
+        sender_institution: key(),
+        recipient_institution: key(),
+        travel_rule_config: key(),
+        travel_rule_record: key(),
+        source_freeze_record: key(),
+        destination_freeze_record: key(),
+        sender_counterparty: key(),
+        recipient_counterparty: key(),
+        aml_alert_config: key(),
+        sender_velocity_record: key(),
+        program_allowlist: key(),
+        sender_program_escrow: key(),
+        recipient_program_escrow: key(),
+        escrow_vault: key(),
+        seizure_authority: key(),
+        treasury_vault: key(),
+    };
+    let data = hook_ix::TransferHook { amount: 1000 };
+    assert_eq!(
+        decode_hook(&accounts, data),
+        HookOperation::Execute {
+            source: accounts.source_token,
+            mint: accounts.mint,
+            destination: accounts.destination_token,
+            owner: accounts.owner,
+            amount: 1000,
+        }
+    );
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{EventStore, IndexerError};
+
+/// Rows returned when a query gives no `limit`
+pub const DEFAULT_LIMIT: u32 = 100;
+/// Most rows a query returns
+pub const MAX_LIMIT: u32 = 1_000;
+
+/// Answer a `GET` of `url` (path and query string) from `store`, as an HTTP
+/// status and a JSON body:
+///
+/// - `/holders`: token accounts with a non-zero net issuance
+/// - `/frozen-accounts`: accounts under a freeze that has not been lifted
+/// - `/seizures?limit=N`: the latest seizures
+/// - `/reserve-history?limit=N`: the latest reserve proofs
+pub fn handle<S: EventStore>(store: &mut S, url: &str) -> (u16, String) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let limit = match parse_limit(query) {
+        Some(limit) => limit,
+        None => return (400, error_body("limit must be a number")),
+    };
+    let result = match path.trim_end_matches('/') {
+        "/holders" => store.holders().and_then(|rows| to_json(&rows)),
+        "/frozen-accounts" => store.frozen_accounts().and_then(|rows| to_json(&rows)),
+        "/seizures" => store.seizures(limit).and_then(|rows| to_json(&rows)),
+        "/reserve-history" => store.reserve_history(limit).and_then(|rows| to_json(&rows)),
+        _ => return (404, error_body("not found")),
+    };
+    match result {
+        Ok(body) => (200, body),
+        Err(error) => (500, error_body(&error.to_string())),
+    }
+}
+
+/// Serve `handle` over HTTP at `address` (e.g. `127.0.0.1:8080`) until `stop`
+/// returns true
+pub fn serve<S: EventStore>(store: &mut S, address: &str, stop: impl Fn() -> bool) -> Result<(), IndexerError> {
+    let server = Server::http(address).map_err(|error| IndexerError::Api(error.to_string()))?;
+    let content_type = Header::from_bytes("Content-Type", "application/json").expect("static header");
+    while !stop() {
+        let request = match server.recv_timeout(Duration::from_secs(1)) {
+            Ok(Some(request)) => request,
+            Ok(None) => continue,
+            Err(error) => return Err(IndexerError::Api(error.to_string())),
+        };
+        let (status, body) = if *request.method() == Method::Get {
+            handle(store, request.url())
+        } else {
+            (405, error_body("only GET is supported"))
+        };
+        let response = Response::from_string(body).with_status_code(status).with_header(content_type.clone());
+        // A client hanging up only loses its own response
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
+
+// `Some(DEFAULT_LIMIT)` without a limit, capped at MAX_LIMIT, `None` if malformed
+fn parse_limit(query: &str) -> Option<u32> {
+    let value = query.split('&').find_map(|pair| pair.strip_prefix("limit="));
+    match value {
+        Some(value) => value.parse::<u32>().ok().map(|limit| limit.min(MAX_LIMIT)),
+        None => Some(DEFAULT_LIMIT),
+    }
+}
+
+fn to_json<T: Serialize>(rows: &T) -> Result<String, IndexerError> {
+    serde_json::to_string(rows).map_err(|error| IndexerError::Api(error.to_string()))
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use crate::events::records;
+use crate::source::LogSource;
+use crate::store::EventStore;
+use crate::IndexerError;
+
+/// Moves the events of every confirmed program transaction from a log source
+/// into a store
+pub struct Indexer<L, S> {
+    pub source: L,
+    pub store: S,
+    pub poll_timeout: Duration,
+}
+
+impl<L: LogSource, S: EventStore> Indexer<L, S> {
+    /// Index the next transaction, if one arrives within `poll_timeout`. Returns
+    /// the number of events stored.
+    pub fn tick(&mut self) -> Result<usize, IndexerError> {
+        let transaction = match self.source.next(self.poll_timeout)? {
+            Some(transaction) if !transaction.failed => transaction,
+            _ => return Ok(0),
+        };
+        let records = records(&transaction.signature, transaction.slot, &transaction.logs);
+        for record in &records {
+            self.store.insert(record)?;
+        }
+        Ok(records.len())
+    }
+
+    /// Index until `stop` returns true. Fails when the source or the store does;
+    /// the caller resubscribes, and redelivered transactions are stored once.
+    pub fn run(&mut self, stop: impl Fn() -> bool) -> Result<(), IndexerError> {
+        while !stop() {
+            self.tick()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use mica_eur::{FreezeLifted, FreezeRecorded, ReserveProofUpdated, TokensBurned, TokensMinted, TokensSeized};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the log line an Anchor event is emitted in
+const EVENT_LOG_PREFIX: &str = "Program data: ";
+
+/// Direction of an issuance entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssuanceKind {
+    Mint,
+    Burn,
+}
+
+/// Tokens minted to or burned from a token account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Issuance {
+    pub kind: IssuanceKind,
+    pub mint: String,
+    pub token_account: String,
+    pub wallet: String,               // Recipient of a mint, owner for a burn
+    pub amount: u64,
+    pub operation_id: String,         // Hex, all zeros for approval-set executions
+    pub timestamp: i64,
+}
+
+/// A recorded freeze of a token account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Freeze {
+    pub mint: String,
+    pub token_account: String,
+    pub authority: String,
+    pub reason_code: u8,
+    pub legal_reference: String,
+    pub frozen_amount: u64,           // 0 for the whole balance
+    pub expires_at: i64,              // 0 for no expiry
+    pub timestamp: i64,
+}
+
+/// A freeze lifted by a thaw or by its expiry
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreezeLift {
+    pub token_account: String,
+    pub lifted_by: String,            // Default key for expiries
+    pub expired: bool,
+    pub timestamp: i64,
+}
+
+/// Tokens moved by the permanent delegate
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seizure {
+    pub mint: String,
+    pub from_account: String,
+    pub to_account: String,
+    pub amount: u64,
+    pub permanent_delegate: String,
+    pub operation_id: String,
+    pub timestamp: i64,
+}
+
+/// A published reserve proof
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReserveUpdate {
+    pub mint: String,
+    pub merkle_root: String,          // Hex
+    pub ipfs_cid: String,
+    pub reserve_total: u64,
+    pub timestamp: i64,
+}
+
+/// A program event the indexer keeps
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IndexedEvent {
+    Issuance(Issuance),
+    Freeze(Freeze),
+    FreezeLift(FreezeLift),
+    Seizure(Seizure),
+    ReserveUpdate(ReserveUpdate),
+}
+
+/// An event with the transaction it was emitted in
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub signature: String,
+    pub slot: u64,
+    pub index: u32,                   // Position among the transaction's indexed events
+    pub event: IndexedEvent,
+}
+
+/// Events the program emitted in a transaction's logs, in order. Events logged
+/// while another program is executing, including ones forged by a program the
+/// MiCA EUR program calls, are skipped; so are events the indexer does not keep.
+pub fn decode_logs(logs: &[String]) -> Vec<IndexedEvent> {
+    program_event_data(logs).iter().filter_map(|data| decode_event(data)).collect()
+}
+
+/// Leaves of an audit window digest for a transaction's logs: the
+/// `audit_event_hash` of every event the program emitted, kept by the indexer or not
+pub fn audit_event_hashes(logs: &[String]) -> Vec<[u8; 32]> {
+    program_event_data(logs).iter().map(|data| mica_eur::audit_event_hash(data)).collect()
+}
+
+/// Digest `export_audit_window` anchors: the Merkle root over the event hashes of
+/// a window's transactions in slot order, each transaction's in log order
+pub fn audit_window_digest(event_hashes: &[[u8; 32]]) -> [u8; 32] {
+    mica_eur::calculate_merkle_root(event_hashes)
+}
+
+// Raw data of the events the program itself emitted in a transaction's logs
+fn program_event_data(logs: &[String]) -> Vec<Vec<u8>> {
+    let program_id = mica_eur::ID.to_string();
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        if let Some(data) = line.strip_prefix(EVENT_LOG_PREFIX) {
+            if invocations.last() == Some(&program_id.as_str()) {
+                events.extend(BASE64.decode(data).ok());
+            }
+            continue;
+        }
+        // `Program <id> invoke [<depth>]`, `Program <id> success`, `Program <id> failed: ...`
+        let mut words = line.strip_prefix("Program ").unwrap_or_default().split_whitespace();
+        match (words.next(), words.next()) {
+            (Some(id), Some("invoke")) => invocations.push(id),
+            (Some(_), Some("success" | "failed:")) => {
+                invocations.pop();
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Decode one event emitted with `emit!`: its discriminator followed by its fields
+pub fn decode_event(data: &[u8]) -> Option<IndexedEvent> {
+    let (discriminator, mut fields) = (data.get(..8)?, data.get(8..)?);
+
+    macro_rules! is {
+        ($t:ty) => {
+            discriminator == <$t as Discriminator>::DISCRIMINATOR
+        };
+    }
+
+    let event = if is!(TokensMinted) {
+        let event = TokensMinted::deserialize(&mut fields).ok()?;
+        IndexedEvent::Issuance(Issuance {
+            kind: IssuanceKind::Mint,
+            mint: event.mint.to_string(),
+            token_account: event.token_account.to_string(),
+            wallet: event.recipient.to_string(),
+            amount: event.amount,
+            operation_id: hex(&event.operation_id),
+            timestamp: event.timestamp,
+        })
+    } else if is!(TokensBurned) {
+        let event = TokensBurned::deserialize(&mut fields).ok()?;
+        IndexedEvent::Issuance(Issuance {
+            kind: IssuanceKind::Burn,
+            mint: event.mint.to_string(),
+            token_account: event.token_account.to_string(),
+            wallet: event.owner.to_string(),
+            amount: event.amount,
+            operation_id: hex(&event.operation_id),
+            timestamp: event.timestamp,
+        })
+    } else if is!(FreezeRecorded) {
+        let event = FreezeRecorded::deserialize(&mut fields).ok()?;
+        IndexedEvent::Freeze(Freeze {
+            mint: event.mint.to_string(),
+            token_account: event.token_account.to_string(),
+            authority: event.authority.to_string(),
+            reason_code: event.reason_code,
+            legal_reference: event.legal_reference,
+            frozen_amount: event.frozen_amount,
+            expires_at: event.expires_at,
+            timestamp: event.timestamp,
+        })
+    } else if is!(FreezeLifted) {
+        let event = FreezeLifted::deserialize(&mut fields).ok()?;
+        IndexedEvent::FreezeLift(FreezeLift {
+            token_account: event.token_account.to_string(),
+            lifted_by: event.lifted_by.to_string(),
+            expired: event.expired,
+            timestamp: event.timestamp,
+        })
+    } else if is!(TokensSeized) {
+        let event = TokensSeized::deserialize(&mut fields).ok()?;
+        IndexedEvent::Seizure(Seizure {
+            mint: event.mint.to_string(),
+            from_account: event.from.to_string(),
+            to_account: event.to.to_string(),
+            amount: event.amount,
+            permanent_delegate: event.permanent_delegate.to_string(),
+            operation_id: hex(&event.operation_id),
+            timestamp: event.timestamp,
+        })
+    } else if is!(ReserveProofUpdated) {
+        let event = ReserveProofUpdated::deserialize(&mut fields).ok()?;
+        IndexedEvent::ReserveUpdate(ReserveUpdate {
+            mint: event.mint.to_string(),
+            merkle_root: hex(&event.merkle_root),
+            ipfs_cid: event.ipfs_cid,
+            reserve_total: event.reserve_total_eur,
+            timestamp: event.timestamp,
+        })
+    } else {
+        return None;
+    };
+    Some(event)
+}
+
+/// Records of the events in a confirmed transaction's logs
+pub fn records(signature: &str, slot: u64, logs: &[String]) -> Vec<EventRecord> {
+    decode_logs(logs)
+        .into_iter()
+        .enumerate()
+        .map(|(index, event)| EventRecord {
+            signature: signature.to_string(),
+            slot,
+            index: index as u32,
+            event,
+        })
+        .collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,64 @@
+//! Event indexer for MiCA EUR compliance dashboards.
+//!
+//! The program's events are read from the logs of its transactions as they are
+//! confirmed, decoded into typed records and written to a store, which serves
+//! the audit queries compliance teams need through a small REST API:
+//!
+//! - `events`: decoding of the program's events from transaction logs, and the
+//!   event hashes audit window digests are built from
+//! - `source`: transaction logs from an RPC websocket subscription
+//! - `store`: the `EventStore` trait and the SQL both databases share
+//! - `sqlite`: `EventStore` on SQLite (feature `sqlite`, on by default)
+//! - `postgres`: `EventStore` on Postgres (feature `postgres`)
+//! - `api`: the REST query layer over a store
+//! - `daemon`: the loop moving decoded events from a source into a store
+//!
+//! Balances are the net of the program's mints, burns and seizures per token
+//! account. Transfers between holders run through Token-2022 and emit no program
+//! event, so `holders` reports issuance, not current balances.
+
+pub mod api;
+pub mod daemon;
+pub mod events;
+pub mod source;
+pub mod store;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
+pub use api::*;
+pub use daemon::*;
+pub use events::*;
+pub use source::*;
+pub use store::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite::*;
+#[cfg(feature = "postgres")]
+pub use postgres::*;
+
+/// Errors of the indexer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexerError {
+    /// The log subscription failed or was closed
+    Source(String),
+    /// Reading or writing the store failed
+    Store(String),
+    /// The query API could not be served
+    Api(String),
+    /// An amount exceeds the range the store records
+    AmountOutOfRange,
+}
+
+impl std::fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexerError::Source(error) => write!(f, "log source: {}", error),
+            IndexerError::Store(error) => write!(f, "store: {}", error),
+            IndexerError::Api(error) => write!(f, "query API: {}", error),
+            IndexerError::AmountOutOfRange => write!(f, "amount out of range"),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
@@ -0,0 +1,84 @@
+//! `mica-eur-indexer`: indexes the program's events and serves the audit
+//! queries. Configured through the environment:
+//!
+//! - `MICA_EUR_WS_URL`: websocket URL of the RPC node (required)
+//! - `INDEXER_DATABASE`: SQLite file, `indexer.db` by default
+//! - `INDEXER_POSTGRES`: Postgres connection string, used instead of SQLite when
+//!   set (feature `postgres`)
+//! - `INDEXER_LISTEN`: address of the query API, `127.0.0.1:8080` by default
+
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use indexer::{serve, EventStore, Indexer, IndexerError, PubsubLogSource};
+
+// Wait before resubscribing after the subscription fails
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), IndexerError> {
+    let url = std::env::var("MICA_EUR_WS_URL")
+        .map_err(|_| IndexerError::Source("MICA_EUR_WS_URL is not set".to_string()))?;
+    let listen = std::env::var("INDEXER_LISTEN").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
+
+    #[cfg(feature = "postgres")]
+    if let Ok(params) = std::env::var("INDEXER_POSTGRES") {
+        return index(&url, &listen, || indexer::PostgresStore::connect(&params));
+    }
+    #[cfg(feature = "sqlite")]
+    {
+        let path = std::env::var("INDEXER_DATABASE").unwrap_or_else(|_| "indexer.db".to_string());
+        index(&url, &listen, || indexer::SqliteStore::open(&path))
+    }
+    #[cfg(not(feature = "sqlite"))]
+    Err(IndexerError::Store("no store configured, set INDEXER_POSTGRES".to_string()))
+}
+
+// Serve the query API on its own connection and index on another, resubscribing
+// whenever the subscription fails
+fn index<S: EventStore + Send + 'static>(
+    url: &str,
+    listen: &str,
+    open: impl Fn() -> Result<S, IndexerError>,
+) -> Result<(), IndexerError> {
+    let mut api_store = open()?;
+    let listen = listen.to_string();
+    let api = thread::spawn(move || serve(&mut api_store, &listen, || false));
+
+    let mut store = open()?;
+    while !api.is_finished() {
+        let source = match PubsubLogSource::subscribe(url) {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("{}", error);
+                thread::sleep(RESUBSCRIBE_DELAY);
+                continue;
+            }
+        };
+        let mut indexer = Indexer {
+            source,
+            store,
+            poll_timeout: Duration::from_secs(1),
+        };
+        match indexer.run(|| api.is_finished()) {
+            Err(IndexerError::Source(error)) => {
+                eprintln!("log source: {}, resubscribing", error);
+                thread::sleep(RESUBSCRIBE_DELAY);
+            }
+            result => result?,
+        }
+        store = indexer.store;
+    }
+    api.join()
+        .map_err(|_| IndexerError::Api("query API thread panicked".to_string()))?
+}
@@ -0,0 +1,169 @@
+use postgres::types::ToSql;
+use postgres::{Client, NoTls, Row};
+
+use crate::store::*;
+use crate::{EventRecord, Freeze, IndexedEvent, IndexerError, ReserveUpdate, Seizure};
+
+/// `EventStore` in a Postgres database
+pub struct PostgresStore {
+    client: Client,
+}
+
+impl PostgresStore {
+    /// Connect with a connection string such as `host=localhost user=indexer dbname=mica_eur`,
+    /// creating the tables if needed. TLS is left to the network, e.g. a sidecar proxy.
+    pub fn connect(params: &str) -> Result<Self, IndexerError> {
+        let mut client = Client::connect(params, NoTls).map_err(store_error)?;
+        client.batch_execute(SCHEMA).map_err(store_error)?;
+        Ok(Self { client })
+    }
+}
+
+impl EventStore for PostgresStore {
+    fn insert(&mut self, record: &EventRecord) -> Result<(), IndexerError> {
+        let signature = &record.signature;
+        let index = record.index as i64;
+        let slot = to_db(record.slot)?;
+        let result = match &record.event {
+            IndexedEvent::Issuance(issuance) => self.client.execute(
+                INSERT_ISSUANCE,
+                &[
+                    signature,
+                    &index,
+                    &slot,
+                    &kind_name(issuance.kind),
+                    &issuance.mint,
+                    &issuance.token_account,
+                    &issuance.wallet,
+                    &to_db(issuance.amount)?,
+                    &issuance.operation_id,
+                    &issuance.timestamp,
+                ],
+            ),
+            IndexedEvent::Freeze(freeze) => self.client.execute(
+                UPSERT_FREEZE,
+                &[
+                    &freeze.token_account,
+                    &slot,
+                    &freeze.mint,
+                    &freeze.authority,
+                    &(freeze.reason_code as i64),
+                    &freeze.legal_reference,
+                    &to_db(freeze.frozen_amount)?,
+                    &freeze.expires_at,
+                    &freeze.timestamp,
+                ],
+            ),
+            IndexedEvent::FreezeLift(lift) => {
+                self.client.execute(LIFT_FREEZE, &[&lift.timestamp, &lift.token_account, &slot])
+            }
+            IndexedEvent::Seizure(seizure) => self.client.execute(
+                INSERT_SEIZURE,
+                &[
+                    signature,
+                    &index,
+                    &slot,
+                    &seizure.mint,
+                    &seizure.from_account,
+                    &seizure.to_account,
+                    &to_db(seizure.amount)?,
+                    &seizure.permanent_delegate,
+                    &seizure.operation_id,
+                    &seizure.timestamp,
+                ],
+            ),
+            IndexedEvent::ReserveUpdate(update) => self.client.execute(
+                INSERT_RESERVE_UPDATE,
+                &[
+                    signature,
+                    &index,
+                    &slot,
+                    &update.mint,
+                    &update.merkle_root,
+                    &update.ipfs_cid,
+                    &to_db(update.reserve_total)?,
+                    &update.timestamp,
+                ],
+            ),
+        };
+        result.map(|_| ()).map_err(store_error)
+    }
+
+    fn holders(&mut self) -> Result<Vec<Holder>, IndexerError> {
+        self.query(SELECT_HOLDERS, &[])?
+            .iter()
+            .map(|row| {
+                Ok(Holder {
+                    token_account: row.get(0),
+                    wallet: row.get(1),
+                    net_issued: row.get(2),
+                })
+            })
+            .collect()
+    }
+
+    fn frozen_accounts(&mut self) -> Result<Vec<Freeze>, IndexerError> {
+        self.query(SELECT_FROZEN, &[])?
+            .iter()
+            .map(|row| {
+                Ok(Freeze {
+                    mint: row.get(0),
+                    token_account: row.get(1),
+                    authority: row.get(2),
+                    reason_code: u8::try_from(row.get::<_, i64>(3)).map_err(|_| IndexerError::AmountOutOfRange)?,
+                    legal_reference: row.get(4),
+                    frozen_amount: from_db(row.get(5))?,
+                    expires_at: row.get(6),
+                    timestamp: row.get(7),
+                })
+            })
+            .collect()
+    }
+
+    fn seizures(&mut self, limit: u32) -> Result<Vec<Seizure>, IndexerError> {
+        self.query(SELECT_SEIZURES, &[&(limit as i64)])?
+            .iter()
+            .map(|row| {
+                Ok(Seizure {
+                    mint: row.get(0),
+                    from_account: row.get(1),
+                    to_account: row.get(2),
+                    amount: from_db(row.get(3))?,
+                    permanent_delegate: row.get(4),
+                    operation_id: row.get(5),
+                    timestamp: row.get(6),
+                })
+            })
+            .collect()
+    }
+
+    fn reserve_history(&mut self, limit: u32) -> Result<Vec<ReserveUpdate>, IndexerError> {
+        self.query(SELECT_RESERVE_UPDATES, &[&(limit as i64)])?
+            .iter()
+            .map(|row| {
+                Ok(ReserveUpdate {
+                    mint: row.get(0),
+                    merkle_root: row.get(1),
+                    ipfs_cid: row.get(2),
+                    reserve_total: from_db(row.get(3))?,
+                    timestamp: row.get(4),
+                })
+            })
+            .collect()
+    }
+}
+
+impl PostgresStore {
+    fn query(&mut self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, IndexerError> {
+        self.client.query(sql, params).map_err(store_error)
+    }
+}
+
+// Convert a stored amount back
+fn from_db(value: i64) -> Result<u64, IndexerError> {
+    u64::try_from(value).map_err(|_| IndexerError::AmountOutOfRange)
+}
+
+fn store_error(error: postgres::Error) -> IndexerError {
+    IndexerError::Store(error.to_string())
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use solana_pubsub_client::pubsub_client::{LogsSubscription, PubsubClient};
+use solana_rpc_client_api::config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use crate::IndexerError;
+
+/// Logs of one transaction that invoked the program
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionLogs {
+    pub signature: String,
+    pub slot: u64,
+    pub failed: bool,                 // Failed transactions leave no state behind
+    pub logs: Vec<String>,
+}
+
+/// Stream of the program's transaction logs
+pub trait LogSource {
+    /// The next transaction, `None` if none arrived within `timeout`
+    fn next(&mut self, timeout: Duration) -> Result<Option<TransactionLogs>, IndexerError>;
+}
+
+/// Confirmed transactions mentioning the program, from a `logsSubscribe`
+/// websocket subscription. Only transactions confirmed after subscribing are
+/// delivered.
+pub struct PubsubLogSource {
+    subscription: LogsSubscription,
+}
+
+impl PubsubLogSource {
+    /// Subscribe at the websocket URL of an RPC node, e.g. `wss://api.mainnet-beta.solana.com`
+    pub fn subscribe(url: &str) -> Result<Self, IndexerError> {
+        let subscription = PubsubClient::logs_subscribe(
+            url,
+            RpcTransactionLogsFilter::Mentions(vec![mica_eur::ID.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .map_err(|error| IndexerError::Source(error.to_string()))?;
+        Ok(Self { subscription })
+    }
+}
+
+impl LogSource for PubsubLogSource {
+    fn next(&mut self, timeout: Duration) -> Result<Option<TransactionLogs>, IndexerError> {
+        use crossbeam_channel::RecvTimeoutError;
+
+        match self.subscription.1.recv_timeout(timeout) {
+            Ok(response) => Ok(Some(TransactionLogs {
+                signature: response.value.signature,
+                slot: response.context.slot,
+                failed: response.value.err.is_some(),
+                logs: response.value.logs,
+            })),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(IndexerError::Source("subscription closed".to_string()))
+            }
+        }
+    }
+}
+
+impl Drop for PubsubLogSource {
+    fn drop(&mut self) {
+        let _ = self.subscription.0.send_unsubscribe();
+    }
+}
@@ -0,0 +1,157 @@
+use rusqlite::{params, Connection, Row};
+
+use crate::store::*;
+use crate::{EventRecord, Freeze, IndexedEvent, IndexerError, ReserveUpdate, Seizure};
+
+/// `EventStore` in an SQLite database file
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+impl SqliteStore {
+    /// Open the database at `path`, creating its tables if needed
+    pub fn open(path: &str) -> Result<Self, IndexerError> {
+        let connection = Connection::open(path).map_err(store_error)?;
+        connection.execute_batch(SCHEMA).map_err(store_error)?;
+        Ok(Self { connection })
+    }
+}
+
+impl EventStore for SqliteStore {
+    fn insert(&mut self, record: &EventRecord) -> Result<(), IndexerError> {
+        let signature = &record.signature;
+        let index = record.index as i64;
+        let slot = to_db(record.slot)?;
+        let result = match &record.event {
+            IndexedEvent::Issuance(issuance) => self.connection.execute(
+                INSERT_ISSUANCE,
+                params![
+                    signature,
+                    index,
+                    slot,
+                    kind_name(issuance.kind),
+                    issuance.mint,
+                    issuance.token_account,
+                    issuance.wallet,
+                    to_db(issuance.amount)?,
+                    issuance.operation_id,
+                    issuance.timestamp,
+                ],
+            ),
+            IndexedEvent::Freeze(freeze) => self.connection.execute(
+                UPSERT_FREEZE,
+                params![
+                    freeze.token_account,
+                    slot,
+                    freeze.mint,
+                    freeze.authority,
+                    freeze.reason_code as i64,
+                    freeze.legal_reference,
+                    to_db(freeze.frozen_amount)?,
+                    freeze.expires_at,
+                    freeze.timestamp,
+                ],
+            ),
+            IndexedEvent::FreezeLift(lift) => {
+                self.connection.execute(LIFT_FREEZE, params![lift.timestamp, lift.token_account, slot])
+            }
+            IndexedEvent::Seizure(seizure) => self.connection.execute(
+                INSERT_SEIZURE,
+                params![
+                    signature,
+                    index,
+                    slot,
+                    seizure.mint,
+                    seizure.from_account,
+                    seizure.to_account,
+                    to_db(seizure.amount)?,
+                    seizure.permanent_delegate,
+                    seizure.operation_id,
+                    seizure.timestamp,
+                ],
+            ),
+            IndexedEvent::ReserveUpdate(update) => self.connection.execute(
+                INSERT_RESERVE_UPDATE,
+                params![
+                    signature,
+                    index,
+                    slot,
+                    update.mint,
+                    update.merkle_root,
+                    update.ipfs_cid,
+                    to_db(update.reserve_total)?,
+                    update.timestamp,
+                ],
+            ),
+        };
+        result.map(|_| ()).map_err(store_error)
+    }
+
+    fn holders(&mut self) -> Result<Vec<Holder>, IndexerError> {
+        self.query(SELECT_HOLDERS, &[], |row| {
+            Ok(Holder {
+                token_account: row.get(0)?,
+                wallet: row.get(1)?,
+                net_issued: row.get(2)?,
+            })
+        })
+    }
+
+    fn frozen_accounts(&mut self) -> Result<Vec<Freeze>, IndexerError> {
+        self.query(SELECT_FROZEN, &[], |row| {
+            Ok(Freeze {
+                mint: row.get(0)?,
+                token_account: row.get(1)?,
+                authority: row.get(2)?,
+                reason_code: row.get(3)?,
+                legal_reference: row.get(4)?,
+                frozen_amount: row.get(5)?,
+                expires_at: row.get(6)?,
+                timestamp: row.get(7)?,
+            })
+        })
+    }
+
+    fn seizures(&mut self, limit: u32) -> Result<Vec<Seizure>, IndexerError> {
+        self.query(SELECT_SEIZURES, &[&limit], |row| {
+            Ok(Seizure {
+                mint: row.get(0)?,
+                from_account: row.get(1)?,
+                to_account: row.get(2)?,
+                amount: row.get(3)?,
+                permanent_delegate: row.get(4)?,
+                operation_id: row.get(5)?,
+                timestamp: row.get(6)?,
+            })
+        })
+    }
+
+    fn reserve_history(&mut self, limit: u32) -> Result<Vec<ReserveUpdate>, IndexerError> {
+        self.query(SELECT_RESERVE_UPDATES, &[&limit], |row| {
+            Ok(ReserveUpdate {
+                mint: row.get(0)?,
+                merkle_root: row.get(1)?,
+                ipfs_cid: row.get(2)?,
+                reserve_total: row.get(3)?,
+                timestamp: row.get(4)?,
+            })
+        })
+    }
+}
+
+impl SqliteStore {
+    fn query<T>(
+        &self,
+        sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+        map: impl FnMut(&Row) -> rusqlite::Result<T>,
+    ) -> Result<Vec<T>, IndexerError> {
+        let mut statement = self.connection.prepare(sql).map_err(store_error)?;
+        let rows = statement.query_map(params, map).map_err(store_error)?;
+        rows.collect::<Result<_, _>>().map_err(store_error)
+    }
+}
+
+fn store_error(error: rusqlite::Error) -> IndexerError {
+    IndexerError::Store(error.to_string())
+}
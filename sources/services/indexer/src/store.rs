@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{EventRecord, Freeze, IndexerError, IssuanceKind, ReserveUpdate, Seizure};
+
+/// A token account with the net of the program's mints, burns and seizures
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Holder {
+    pub token_account: String,
+    pub wallet: Option<String>,       // Owner seen in a mint or burn, none for seizure-only accounts
+    pub net_issued: i64,
+}
+
+/// Where indexed events are kept and queried. Writing the same record twice has
+/// no effect, so redelivered transactions can be inserted again.
+pub trait EventStore {
+    fn insert(&mut self, record: &EventRecord) -> Result<(), IndexerError>;
+    /// Token accounts with a non-zero net issuance
+    fn holders(&mut self) -> Result<Vec<Holder>, IndexerError>;
+    /// Token accounts whose latest recorded freeze has not been lifted
+    fn frozen_accounts(&mut self) -> Result<Vec<Freeze>, IndexerError>;
+    /// The latest `limit` seizures, newest first
+    fn seizures(&mut self, limit: u32) -> Result<Vec<Seizure>, IndexerError>;
+    /// The latest `limit` reserve proofs, newest first
+    fn reserve_history(&mut self, limit: u32) -> Result<Vec<ReserveUpdate>, IndexerError>;
+}
+
+// The statements below run unchanged on SQLite and Postgres. Parameters are
+// numbered in order of first appearance, which SQLite relies on to bind `$n`
+// positionally.
+
+/// Tables of the store
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS issuance (
+    signature TEXT NOT NULL,
+    idx BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    token_account TEXT NOT NULL,
+    wallet TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    operation_id TEXT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+CREATE TABLE IF NOT EXISTS freezes (
+    token_account TEXT PRIMARY KEY,
+    slot BIGINT NOT NULL,
+    mint TEXT NOT NULL,
+    authority TEXT NOT NULL,
+    reason_code BIGINT NOT NULL,
+    legal_reference TEXT NOT NULL,
+    frozen_amount BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    lifted BOOLEAN NOT NULL,
+    lifted_at BIGINT
+);
+CREATE TABLE IF NOT EXISTS seizures (
+    signature TEXT NOT NULL,
+    idx BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    mint TEXT NOT NULL,
+    from_account TEXT NOT NULL,
+    to_account TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    permanent_delegate TEXT NOT NULL,
+    operation_id TEXT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+CREATE TABLE IF NOT EXISTS reserve_updates (
+    signature TEXT NOT NULL,
+    idx BIGINT NOT NULL,
+    slot BIGINT NOT NULL,
+    mint TEXT NOT NULL,
+    merkle_root TEXT NOT NULL,
+    ipfs_cid TEXT NOT NULL,
+    reserve_total BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+";
+
+pub(crate) const INSERT_ISSUANCE: &str = "INSERT INTO issuance \
+    (signature, idx, slot, kind, mint, token_account, wallet, amount, operation_id, timestamp) \
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) ON CONFLICT DO NOTHING";
+
+// A redelivered older freeze must not replace a newer one
+pub(crate) const UPSERT_FREEZE: &str = "INSERT INTO freezes \
+    (token_account, slot, mint, authority, reason_code, legal_reference, frozen_amount, expires_at, timestamp, lifted) \
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, FALSE) \
+    ON CONFLICT (token_account) DO UPDATE SET slot = excluded.slot, mint = excluded.mint, \
+    authority = excluded.authority, reason_code = excluded.reason_code, \
+    legal_reference = excluded.legal_reference, frozen_amount = excluded.frozen_amount, \
+    expires_at = excluded.expires_at, timestamp = excluded.timestamp, lifted = FALSE, lifted_at = NULL \
+    WHERE excluded.slot >= freezes.slot";
+
+pub(crate) const LIFT_FREEZE: &str =
+    "UPDATE freezes SET lifted = TRUE, lifted_at = $1 WHERE token_account = $2 AND slot <= $3";
+
+pub(crate) const INSERT_SEIZURE: &str = "INSERT INTO seizures \
+    (signature, idx, slot, mint, from_account, to_account, amount, permanent_delegate, operation_id, timestamp) \
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) ON CONFLICT DO NOTHING";
+
+pub(crate) const INSERT_RESERVE_UPDATE: &str = "INSERT INTO reserve_updates \
+    (signature, idx, slot, mint, merkle_root, ipfs_cid, reserve_total, timestamp) \
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT DO NOTHING";
+
+pub(crate) const SELECT_HOLDERS: &str = "SELECT token_account, MAX(wallet), CAST(SUM(delta) AS BIGINT) FROM ( \
+    SELECT token_account, wallet, CASE WHEN kind = 'mint' THEN amount ELSE -amount END AS delta FROM issuance \
+    UNION ALL SELECT to_account, NULL, amount FROM seizures \
+    UNION ALL SELECT from_account, NULL, -amount FROM seizures \
+    ) AS entries GROUP BY token_account HAVING SUM(delta) <> 0 ORDER BY token_account";
+
+pub(crate) const SELECT_FROZEN: &str = "SELECT mint, token_account, authority, reason_code, legal_reference, \
+    frozen_amount, expires_at, timestamp FROM freezes WHERE NOT lifted ORDER BY timestamp DESC";
+
+pub(crate) const SELECT_SEIZURES: &str = "SELECT mint, from_account, to_account, amount, permanent_delegate, \
+    operation_id, timestamp FROM seizures ORDER BY slot DESC, idx DESC LIMIT $1";
+
+pub(crate) const SELECT_RESERVE_UPDATES: &str = "SELECT mint, merkle_root, ipfs_cid, reserve_total, timestamp \
+    FROM reserve_updates ORDER BY slot DESC, idx DESC LIMIT $1";
+
+/// Convert an amount or slot to the signed 64-bit integers both databases store
+pub(crate) fn to_db(value: u64) -> Result<i64, IndexerError> {
+    i64::try_from(value).map_err(|_| IndexerError::AmountOutOfRange)
+}
+
+/// Name of an issuance kind in the `kind` column
+pub(crate) fn kind_name(kind: IssuanceKind) -> &'static str {
+    match kind {
+        IssuanceKind::Mint => "mint",
+        IssuanceKind::Burn => "burn",
+    }
+}
@@ -0,0 +1,263 @@
+//! Indexing the transactions in `tests/fixtures/transactions.json`, program logs
+//! of a mint, a burn, a freeze with a seizure, a failed mint, a reserve proof and
+//! a thaw, into an in-memory SQLite store, and the audit queries over it.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use indexer::*;
+use serde_json::Value;
+
+const TRANSACTIONS: &str = include_str!("fixtures/transactions.json");
+
+struct FixtureSource(VecDeque<TransactionLogs>);
+
+impl LogSource for FixtureSource {
+    fn next(&mut self, _timeout: Duration) -> Result<Option<TransactionLogs>, IndexerError> {
+        Ok(self.0.pop_front())
+    }
+}
+
+fn transactions() -> Vec<TransactionLogs> {
+    let transactions: Vec<Value> = serde_json::from_str(TRANSACTIONS).unwrap();
+    transactions
+        .iter()
+        .map(|transaction| TransactionLogs {
+            signature: transaction["signature"].as_str().unwrap().to_string(),
+            slot: transaction["slot"].as_u64().unwrap(),
+            failed: transaction["failed"].as_bool().unwrap(),
+            logs: serde_json::from_value(transaction["logs"].clone()).unwrap(),
+        })
+        .collect()
+}
+
+// Keys of the fixture are 32 repeated bytes
+fn key(byte: u8) -> String {
+    Pubkey::new_from_array([byte; 32]).to_string()
+}
+
+fn indexer(transactions: Vec<TransactionLogs>) -> Indexer<FixtureSource, SqliteStore> {
+    Indexer {
+        source: FixtureSource(transactions.into()),
+        store: SqliteStore::open(":memory:").unwrap(),
+        poll_timeout: Duration::ZERO,
+    }
+}
+
+fn index_all(indexer: &mut Indexer<FixtureSource, SqliteStore>) -> Vec<usize> {
+    let mut stored = Vec::new();
+    while !indexer.source.0.is_empty() {
+        stored.push(indexer.tick().unwrap());
+    }
+    stored
+}
+
+#[test]
+fn only_the_programs_own_events_are_decoded() {
+    let transactions = transactions();
+
+    let records = records(
+        &transactions[0].signature,
+        transactions[0].slot,
+        &transactions[0].logs,
+    );
+
+    // The mint event logged inside the hook program's invocation is not the program's
+    let amounts: Vec<(String, u64, u32)> = records
+        .iter()
+        .map(|record| match &record.event {
+            IndexedEvent::Issuance(issuance) => (
+                issuance.token_account.clone(),
+                issuance.amount,
+                record.index,
+            ),
+            other => panic!("{other:?}"),
+        })
+        .collect();
+    assert_eq!(amounts, [(key(2), 250_000_000, 0), (key(3), 40_000_000, 1)]);
+    assert_eq!(
+        records[0].event,
+        IndexedEvent::Issuance(Issuance {
+            kind: IssuanceKind::Mint,
+            mint: key(1),
+            token_account: key(2),
+            wallet: key(12),
+            amount: 250_000_000,
+            operation_id: "7f".repeat(16),
+            timestamp: 1_776_124_800,
+        })
+    );
+}
+
+#[test]
+fn events_the_indexer_does_not_keep_still_enter_the_audit_digest() {
+    let freeze = &transactions()[2];
+
+    // `AccountFrozen` is not indexed, but every program event is audited
+    assert_eq!(
+        decode_logs(&freeze.logs)
+            .iter()
+            .map(|event| match event {
+                IndexedEvent::Freeze(_) => "freeze",
+                IndexedEvent::Seizure(_) => "seizure",
+                other => panic!("{other:?}"),
+            })
+            .collect::<Vec<_>>(),
+        ["freeze", "seizure"]
+    );
+    let hashes = audit_event_hashes(&freeze.logs);
+    assert_eq!(hashes.len(), 3);
+    assert_eq!(
+        audit_window_digest(&hashes),
+        mica_eur::calculate_merkle_root(&hashes)
+    );
+    assert_eq!(decode_event(&[0; 8]), None);
+    assert_eq!(decode_event(&[1, 2, 3]), None);
+}
+
+#[test]
+fn stores_the_fixture_transactions() {
+    let mut indexer = indexer(transactions());
+
+    // The failed mint leaves nothing behind
+    assert_eq!(index_all(&mut indexer), [2, 1, 2, 0, 1, 1]);
+
+    let mut expected = vec![
+        Holder {
+            token_account: key(2),
+            wallet: Some(key(12)),
+            net_issued: 200_000_000,
+        },
+        Holder {
+            token_account: key(3),
+            wallet: Some(key(13)),
+            net_issued: 25_000_000,
+        },
+        Holder {
+            token_account: key(4),
+            wallet: None,
+            net_issued: 15_000_000,
+        },
+    ];
+    expected.sort_by(|a, b| a.token_account.cmp(&b.token_account));
+    assert_eq!(indexer.store.holders().unwrap(), expected);
+    assert_eq!(
+        indexer.store.seizures(10).unwrap(),
+        [Seizure {
+            mint: key(1),
+            from_account: key(3),
+            to_account: key(4),
+            amount: 15_000_000,
+            permanent_delegate: key(6),
+            operation_id: "22".repeat(16),
+            timestamp: 1_776_126_060,
+        }]
+    );
+    assert_eq!(
+        indexer.store.reserve_history(10).unwrap(),
+        [ReserveUpdate {
+            mint: key(1),
+            merkle_root: "ab".repeat(32),
+            ipfs_cid: "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+            reserve_total: 290_000_000,
+            timestamp: 1_776_127_000,
+        }]
+    );
+    // Thawed by the last transaction
+    assert_eq!(indexer.store.frozen_accounts().unwrap(), []);
+}
+
+#[test]
+fn freezes_stay_listed_until_lifted() {
+    let mut transactions = transactions();
+    let thaw = transactions.pop().unwrap();
+    let mut indexer = indexer(transactions);
+    index_all(&mut indexer);
+
+    assert_eq!(
+        indexer.store.frozen_accounts().unwrap(),
+        [Freeze {
+            mint: key(1),
+            token_account: key(3),
+            authority: key(6),
+            reason_code: 2,
+            legal_reference: "StA Frankfurt 7410 Js 1234/26".to_string(),
+            frozen_amount: 0,
+            expires_at: 0,
+            timestamp: 1_776_126_000,
+        }]
+    );
+
+    // A lift seen before a later freeze of the same account leaves that freeze in place
+    let mut late = thaw.clone();
+    late.slot = 319;
+    indexer.source.0.push_back(late);
+    index_all(&mut indexer);
+    assert_eq!(indexer.store.frozen_accounts().unwrap().len(), 1);
+
+    indexer.source.0.push_back(thaw);
+    index_all(&mut indexer);
+    assert_eq!(indexer.store.frozen_accounts().unwrap(), []);
+}
+
+#[test]
+fn redelivered_transactions_are_stored_once() {
+    let transactions = transactions();
+    let mut indexer = indexer(transactions.iter().chain(&transactions).cloned().collect());
+
+    index_all(&mut indexer);
+
+    assert_eq!(indexer.store.holders().unwrap().len(), 3);
+    assert_eq!(indexer.store.seizures(10).unwrap().len(), 1);
+    assert_eq!(indexer.store.reserve_history(10).unwrap().len(), 1);
+}
+
+#[test]
+fn api_answers_the_audit_queries() {
+    let mut indexer = indexer(transactions());
+    index_all(&mut indexer);
+    let store = &mut indexer.store;
+
+    let (status, body) = handle(store, "/holders");
+    assert_eq!(status, 200);
+    let holders: Vec<Holder> = serde_json::from_str(&body).unwrap();
+    assert_eq!(holders, store.holders().unwrap());
+
+    let (status, body) = handle(store, "/reserve-history/?limit=1");
+    assert_eq!(status, 200);
+    assert_eq!(
+        serde_json::from_str::<Vec<ReserveUpdate>>(&body).unwrap(),
+        store.reserve_history(1).unwrap()
+    );
+
+    assert_eq!(handle(store, "/seizures?limit=0"), (200, "[]".to_string()));
+    assert_eq!(handle(store, "/frozen-accounts"), (200, "[]".to_string()));
+    assert_eq!(
+        handle(store, "/seizures?limit=ten"),
+        (400, r#"{"error":"limit must be a number"}"#.to_string())
+    );
+    assert_eq!(
+        handle(store, "/balances"),
+        (404, r#"{"error":"not found"}"#.to_string())
+    );
+}
+
+#[test]
+fn amounts_beyond_the_database_range_are_rejected() {
+    let mut store = SqliteStore::open(":memory:").unwrap();
+    let record = EventRecord {
+        signature: "5vXlarge".to_string(),
+        slot: 1,
+        index: 0,
+        event: IndexedEvent::ReserveUpdate(ReserveUpdate {
+            mint: key(1),
+            merkle_root: "00".repeat(32),
+            ipfs_cid: String::new(),
+            reserve_total: u64::MAX,
+            timestamp: 0,
+        }),
+    };
+
+    assert_eq!(store.insert(&record), Err(IndexerError::AmountOutOfRange));
+}
@@ -0,0 +1,80 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::{pubkey_from_bytes, signature_from_bytes, SigningBackend, SigningError};
+
+/// KMS key spec of Ed25519 keys
+pub const KMS_ED25519_KEY_SPEC: &str = "ECC_NIST_EDWARDS25519";
+
+/// KMS signing algorithm for pure Ed25519 over a `RAW` message
+pub const KMS_ED25519_SIGNING_ALGORITHM: &str = "ED25519_SHA_512";
+
+/// DER prefix of an Ed25519 `SubjectPublicKeyInfo` (RFC 8410), followed by the key
+const ED25519_SPKI_PREFIX: [u8; 12] =
+    [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
+
+/// Public key as returned by KMS `GetPublicKey`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KmsPublicKey {
+    pub key_spec: String,
+    pub public_key_der: Vec<u8>,      // DER SubjectPublicKeyInfo
+}
+
+/// KMS API calls signing needs. Implemented over the AWS SDK by the deployment,
+/// which also owns credentials, region and retries.
+pub trait KmsClient {
+    /// `GetPublicKey`
+    fn get_public_key(&self, key_id: &str) -> Result<KmsPublicKey, String>;
+    /// `Sign` with `MessageType::Raw`
+    fn sign(&self, key_id: &str, message: &[u8], algorithm: &str) -> Result<Vec<u8>, String>;
+}
+
+/// Ed25519 key in AWS KMS
+pub struct KmsBackend<C: KmsClient> {
+    client: C,
+    key_id: String,
+    public_key: Pubkey,
+}
+
+impl<C: KmsClient> KmsBackend<C> {
+    /// Use the key with this ID, ARN or alias
+    pub fn new(client: C, key_id: &str) -> Result<Self, SigningError> {
+        let public_key = client.get_public_key(key_id).map_err(SigningError::Backend)?;
+        if public_key.key_spec != KMS_ED25519_KEY_SPEC {
+            return Err(SigningError::InvalidPublicKey(format!(
+                "key spec {} is not {}",
+                public_key.key_spec, KMS_ED25519_KEY_SPEC
+            )));
+        }
+        let public_key = parse_ed25519_spki(&public_key.public_key_der)?;
+        Ok(Self { client, key_id: key_id.to_string(), public_key })
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+}
+
+impl<C: KmsClient> SigningBackend for KmsBackend<C> {
+    fn public_key(&self) -> Result<Pubkey, SigningError> {
+        Ok(self.public_key)
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Signature, SigningError> {
+        let signature = self
+            .client
+            .sign(&self.key_id, message, KMS_ED25519_SIGNING_ALGORITHM)
+            .map_err(SigningError::Backend)?;
+        signature_from_bytes(&signature)
+    }
+}
+
+/// Decode a DER `SubjectPublicKeyInfo` holding an Ed25519 key
+pub fn parse_ed25519_spki(der: &[u8]) -> Result<Pubkey, SigningError> {
+    match der.strip_prefix(&ED25519_SPKI_PREFIX[..]) {
+        Some(key) if key.len() == 32 => pubkey_from_bytes(key),
+        _ => Err(SigningError::InvalidPublicKey(
+            "not an Ed25519 SubjectPublicKeyInfo".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,164 @@
+//! Transaction signing for the MiCA EUR issuer and oracle keys.
+//!
+//! Production keys never leave a hardware security module or a cloud KMS. Each
+//! backend only exposes "give me the public key" and "sign these bytes", and
+//! `IssuerSigner` wraps a backend into a `solana_sdk::signer::Signer`, so
+//! transactions built from the regular instruction builders are signed the same
+//! way as with a local keypair:
+//!
+//! - `pkcs11`: PKCS#11 tokens such as AWS CloudHSM, using `CKM_EDDSA`
+//! - `kms`: AWS KMS Ed25519 keys
+//! - `local`: in-memory keypairs for development and tests
+
+pub mod kms;
+pub mod local;
+pub mod pkcs11;
+
+pub use kms::*;
+pub use local::*;
+pub use pkcs11::*;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::{Signer, SignerError};
+
+/// On-chain role a signing key holds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyRole {
+    /// `MintInfo.issuer`: minting, burning and regulatory actions
+    Issuer,
+    /// `KycOracle.authority`: KYC registrations and status updates
+    KycOracle,
+    /// `AmlAuthority.authority`: blacklist and screening updates
+    AmlAuthority,
+}
+
+impl std::fmt::Display for KeyRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyRole::Issuer => write!(f, "issuer"),
+            KeyRole::KycOracle => write!(f, "KYC oracle"),
+            KeyRole::AmlAuthority => write!(f, "AML authority"),
+        }
+    }
+}
+
+/// Errors of the signing backends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SigningError {
+    /// The HSM or KMS call failed
+    Backend(String),
+    /// No key with this label or ID exists
+    KeyNotFound(String),
+    /// The key is not an Ed25519 key or its public key could not be decoded
+    InvalidPublicKey(String),
+    /// The backend returned a signature that does not verify against the key
+    InvalidSignature,
+    /// The key is not the one the program expects for the role
+    KeyMismatch { role: KeyRole, expected: Pubkey, actual: Pubkey },
+}
+
+impl std::fmt::Display for SigningError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SigningError::Backend(error) => write!(f, "signing backend error: {}", error),
+            SigningError::KeyNotFound(key) => write!(f, "signing key {:?} not found", key),
+            SigningError::InvalidPublicKey(error) => write!(f, "invalid public key: {}", error),
+            SigningError::InvalidSignature => write!(f, "backend returned an invalid signature"),
+            SigningError::KeyMismatch { role, expected, actual } => {
+                write!(f, "{} key is {}, expected {}", role, actual, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SigningError {}
+
+impl From<SigningError> for SignerError {
+    fn from(error: SigningError) -> Self {
+        match error {
+            SigningError::Backend(error) => SignerError::Connection(error),
+            error => SignerError::Custom(error.to_string()),
+        }
+    }
+}
+
+/// A key held outside the process that produces Ed25519 signatures
+pub trait SigningBackend {
+    /// Public key of the signing key
+    fn public_key(&self) -> Result<Pubkey, SigningError>;
+    /// Sign a serialized transaction message
+    fn sign(&self, message: &[u8]) -> Result<Signature, SigningError>;
+}
+
+/// Signer for an issuer or oracle key held by a `SigningBackend`
+pub struct IssuerSigner<B: SigningBackend> {
+    role: KeyRole,
+    backend: B,
+    pubkey: Pubkey,
+}
+
+impl<B: SigningBackend> IssuerSigner<B> {
+    /// Wrap a backend, reading its public key once
+    pub fn new(role: KeyRole, backend: B) -> Result<Self, SigningError> {
+        let pubkey = backend.public_key()?;
+        Ok(Self { role, backend, pubkey })
+    }
+
+    /// Wrap a backend, failing unless its key is the one configured on-chain for the
+    /// role (e.g. `MintInfo.issuer`)
+    pub fn with_expected_key(
+        role: KeyRole,
+        backend: B,
+        expected: &Pubkey,
+    ) -> Result<Self, SigningError> {
+        let signer = Self::new(role, backend)?;
+        if signer.pubkey != *expected {
+            return Err(SigningError::KeyMismatch {
+                role,
+                expected: *expected,
+                actual: signer.pubkey,
+            });
+        }
+        Ok(signer)
+    }
+
+    pub fn role(&self) -> KeyRole {
+        self.role
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+}
+
+impl<B: SigningBackend> Signer for IssuerSigner<B> {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey)
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let signature = self.backend.sign(message)?;
+        // A misconfigured key or mechanism must not reach the cluster as a rejected
+        // transaction, so the signature is checked before it is handed out
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(SigningError::InvalidSignature.into());
+        }
+        Ok(signature)
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// Convert a raw 64 byte signature returned by a backend
+pub(crate) fn signature_from_bytes(bytes: &[u8]) -> Result<Signature, SigningError> {
+    Signature::try_from(bytes).map_err(|_| SigningError::InvalidSignature)
+}
+
+/// Convert a raw 32 byte Ed25519 public key returned by a backend
+pub(crate) fn pubkey_from_bytes(bytes: &[u8]) -> Result<Pubkey, SigningError> {
+    Pubkey::try_from(bytes)
+        .map_err(|_| SigningError::InvalidPublicKey(format!("{} byte key", bytes.len())))
+}
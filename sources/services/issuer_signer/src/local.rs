@@ -0,0 +1,27 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer;
+
+use crate::{SigningBackend, SigningError};
+
+/// Keypair held in process memory. Only meant for development and tests; the
+/// production issuer and oracle keys live in an HSM or KMS.
+pub struct LocalBackend {
+    keypair: Keypair,
+}
+
+impl LocalBackend {
+    pub fn new(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+}
+
+impl SigningBackend for LocalBackend {
+    fn public_key(&self) -> Result<Pubkey, SigningError> {
+        Ok(self.keypair.pubkey())
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Signature, SigningError> {
+        Ok(self.keypair.sign_message(message))
+    }
+}
@@ -0,0 +1,100 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::{pubkey_from_bytes, signature_from_bytes, SigningBackend, SigningError};
+
+/// `CKM_EDDSA` mechanism (PKCS#11 v3.0), pure Ed25519 without prehashing
+pub const CKM_EDDSA: u64 = 0x0000_1057;
+
+/// PKCS#11 library of the AWS CloudHSM client SDK 5
+pub const CLOUDHSM_PKCS11_LIBRARY: &str = "/opt/cloudhsm/lib/libcloudhsm_pkcs11.so";
+
+/// PKCS#11 object handle
+pub type ObjectHandle = u64;
+
+/// Logged-in PKCS#11 session. Implemented over the vendor library (e.g. with the
+/// `cryptoki` crate) by the deployment; only the calls signing needs are listed.
+pub trait Pkcs11Session {
+    /// Find the private key object (`CKO_PRIVATE_KEY`) with this `CKA_LABEL`
+    fn find_private_key(&self, label: &str) -> Result<Option<ObjectHandle>, String>;
+    /// Find the public key object (`CKO_PUBLIC_KEY`) with this `CKA_LABEL`
+    fn find_public_key(&self, label: &str) -> Result<Option<ObjectHandle>, String>;
+    /// Read the `CKA_EC_POINT` attribute of a public key
+    fn ec_point(&self, public_key: ObjectHandle) -> Result<Vec<u8>, String>;
+    /// `C_SignInit` + `C_Sign` with the given mechanism
+    fn sign(&self, key: ObjectHandle, mechanism: u64, data: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// How to reach a key on a PKCS#11 token
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pkcs11Config {
+    pub library_path: String,
+    pub slot: u64,
+    pub key_label: String,
+    pub pin_env: String,              // Environment variable holding the user PIN
+}
+
+impl Pkcs11Config {
+    /// CloudHSM key, logged in as the crypto user whose `user:password` PIN is in
+    /// `CLOUDHSM_PIN`
+    pub fn cloudhsm(key_label: &str) -> Self {
+        Self {
+            library_path: CLOUDHSM_PKCS11_LIBRARY.to_string(),
+            slot: 0,
+            key_label: key_label.to_string(),
+            pin_env: "CLOUDHSM_PIN".to_string(),
+        }
+    }
+}
+
+/// Ed25519 key on a PKCS#11 token
+pub struct Pkcs11Backend<S: Pkcs11Session> {
+    session: S,
+    private_key: ObjectHandle,
+    public_key: Pubkey,
+}
+
+impl<S: Pkcs11Session> Pkcs11Backend<S> {
+    /// Look up the key pair labelled `key_label` on the session's token
+    pub fn new(session: S, key_label: &str) -> Result<Self, SigningError> {
+        let not_found = || SigningError::KeyNotFound(key_label.to_string());
+        let private_key = session
+            .find_private_key(key_label)
+            .map_err(SigningError::Backend)?
+            .ok_or_else(not_found)?;
+        let public_handle = session
+            .find_public_key(key_label)
+            .map_err(SigningError::Backend)?
+            .ok_or_else(not_found)?;
+        let ec_point = session.ec_point(public_handle).map_err(SigningError::Backend)?;
+        let public_key = parse_ec_point(&ec_point)?;
+        Ok(Self { session, private_key, public_key })
+    }
+}
+
+impl<S: Pkcs11Session> SigningBackend for Pkcs11Backend<S> {
+    fn public_key(&self) -> Result<Pubkey, SigningError> {
+        Ok(self.public_key)
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Signature, SigningError> {
+        let signature = self
+            .session
+            .sign(self.private_key, CKM_EDDSA, message)
+            .map_err(SigningError::Backend)?;
+        signature_from_bytes(&signature)
+    }
+}
+
+/// Decode `CKA_EC_POINT` of an Ed25519 key. Tokens return either the DER
+/// `OCTET STRING` the standard prescribes or the raw 32 key bytes.
+pub fn parse_ec_point(ec_point: &[u8]) -> Result<Pubkey, SigningError> {
+    match ec_point {
+        [0x04, 0x20, key @ ..] if key.len() == 32 => pubkey_from_bytes(key),
+        key if key.len() == 32 => pubkey_from_bytes(key),
+        _ => Err(SigningError::InvalidPublicKey(format!(
+            "unexpected CKA_EC_POINT of {} bytes",
+            ec_point.len()
+        ))),
+    }
+}
@@ -0,0 +1,330 @@
+//! Signing backends over in-memory stand-ins for KMS and a PKCS#11 token. The
+//! key material is the RFC 8032 section 7.1 "TEST 1" vector and the public key
+//! encoding the RFC 8410 section 10.1 example, so the bytes the backends parse
+//! and return are checked against published values rather than against the code.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use issuer_signer::*;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::{Signer, SignerError};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+
+const RFC8032_SECRET: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const RFC8032_PUBLIC: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+// Signature of the empty message
+const RFC8032_SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+                                 5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+// `MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7lshQhqowtrbLDFw4rXAxZuE=`
+const RFC8410_SPKI: &str =
+    "302a300506032b657003210019bf44096984cdfe8541bac167dc3bb6c85086aa30b6b6cb0c5c38ad703166e1";
+const RFC8410_PUBLIC: &str = "19bf44096984cdfe8541bac167dc3bb6c85086aa30b6b6cb0c5c38ad703166e1";
+
+fn hex(text: &str) -> Vec<u8> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn rfc8032_keypair() -> Keypair {
+    Keypair::from_bytes(&[hex(RFC8032_SECRET), hex(RFC8032_PUBLIC)].concat()).unwrap()
+}
+
+fn rfc8032_public() -> Pubkey {
+    Pubkey::try_from(hex(RFC8032_PUBLIC).as_slice()).unwrap()
+}
+
+fn rfc8032_spki() -> Vec<u8> {
+    [&hex(RFC8410_SPKI)[..12], &hex(RFC8032_PUBLIC)].concat()
+}
+
+// KMS holding one Ed25519 key, recording the signing algorithms it is asked for
+struct FakeKms {
+    keypair: Keypair,
+    key_spec: &'static str,
+    algorithms: Rc<RefCell<Vec<String>>>,
+}
+
+impl FakeKms {
+    fn new(keypair: Keypair) -> Self {
+        Self {
+            keypair,
+            key_spec: KMS_ED25519_KEY_SPEC,
+            algorithms: Rc::default(),
+        }
+    }
+}
+
+impl KmsClient for FakeKms {
+    fn get_public_key(&self, key_id: &str) -> Result<KmsPublicKey, String> {
+        if key_id != "alias/mica-eur-issuer" {
+            return Err(format!("NotFoundException: {key_id}"));
+        }
+        let public_key_der = [&hex(RFC8410_SPKI)[..12], self.keypair.pubkey().as_ref()].concat();
+        Ok(KmsPublicKey {
+            key_spec: self.key_spec.to_string(),
+            public_key_der,
+        })
+    }
+
+    fn sign(&self, _key_id: &str, message: &[u8], algorithm: &str) -> Result<Vec<u8>, String> {
+        self.algorithms.borrow_mut().push(algorithm.to_string());
+        Ok(self.keypair.sign_message(message).as_ref().to_vec())
+    }
+}
+
+// Token holding one key pair labelled `issuer`
+struct FakeToken {
+    keypair: Keypair,
+    ec_point: Vec<u8>,
+    mechanisms: Rc<RefCell<Vec<u64>>>,
+}
+
+impl FakeToken {
+    fn new(keypair: Keypair, ec_point: Vec<u8>) -> Self {
+        Self {
+            keypair,
+            ec_point,
+            mechanisms: Rc::default(),
+        }
+    }
+}
+
+const PRIVATE_HANDLE: ObjectHandle = 7;
+const PUBLIC_HANDLE: ObjectHandle = 8;
+
+impl Pkcs11Session for FakeToken {
+    fn find_private_key(&self, label: &str) -> Result<Option<ObjectHandle>, String> {
+        Ok((label == "issuer").then_some(PRIVATE_HANDLE))
+    }
+
+    fn find_public_key(&self, label: &str) -> Result<Option<ObjectHandle>, String> {
+        Ok((label == "issuer").then_some(PUBLIC_HANDLE))
+    }
+
+    fn ec_point(&self, public_key: ObjectHandle) -> Result<Vec<u8>, String> {
+        assert_eq!(public_key, PUBLIC_HANDLE);
+        Ok(self.ec_point.clone())
+    }
+
+    fn sign(&self, key: ObjectHandle, mechanism: u64, data: &[u8]) -> Result<Vec<u8>, String> {
+        if key != PRIVATE_HANDLE {
+            return Err("CKR_KEY_HANDLE_INVALID".to_string());
+        }
+        self.mechanisms.borrow_mut().push(mechanism);
+        Ok(self.keypair.sign_message(data).as_ref().to_vec())
+    }
+}
+
+// Backend whose signatures come from a different key than the one it reports
+struct MismatchedBackend;
+
+impl SigningBackend for MismatchedBackend {
+    fn public_key(&self) -> Result<Pubkey, SigningError> {
+        Ok(rfc8032_public())
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Signature, SigningError> {
+        Ok(Keypair::new().sign_message(message))
+    }
+}
+
+struct UnreachableBackend;
+
+impl SigningBackend for UnreachableBackend {
+    fn public_key(&self) -> Result<Pubkey, SigningError> {
+        Ok(rfc8032_public())
+    }
+
+    fn sign(&self, _message: &[u8]) -> Result<Signature, SigningError> {
+        Err(SigningError::Backend("connection refused".to_string()))
+    }
+}
+
+#[test]
+fn backends_reproduce_the_rfc8032_signature() {
+    let expected = Signature::try_from(hex(RFC8032_SIGNATURE).as_slice()).unwrap();
+
+    let local = IssuerSigner::new(KeyRole::Issuer, LocalBackend::new(rfc8032_keypair())).unwrap();
+    let kms = IssuerSigner::new(
+        KeyRole::Issuer,
+        KmsBackend::new(FakeKms::new(rfc8032_keypair()), "alias/mica-eur-issuer").unwrap(),
+    )
+    .unwrap();
+    let hsm = IssuerSigner::new(
+        KeyRole::Issuer,
+        Pkcs11Backend::new(
+            FakeToken::new(rfc8032_keypair(), hex(RFC8032_PUBLIC)),
+            "issuer",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(local.try_sign_message(b"").unwrap(), expected);
+    assert_eq!(kms.try_sign_message(b"").unwrap(), expected);
+    assert_eq!(hsm.try_sign_message(b"").unwrap(), expected);
+    for pubkey in [local.pubkey(), kms.pubkey(), hsm.pubkey()] {
+        assert_eq!(pubkey, rfc8032_public());
+    }
+    assert_eq!(kms.backend().key_id(), "alias/mica-eur-issuer");
+}
+
+#[test]
+fn spki_and_ec_point_encodings_are_decoded() {
+    let rfc8410 = Pubkey::try_from(hex(RFC8410_PUBLIC).as_slice()).unwrap();
+    assert_eq!(parse_ed25519_spki(&hex(RFC8410_SPKI)), Ok(rfc8410));
+    assert_eq!(parse_ed25519_spki(&rfc8032_spki()), Ok(rfc8032_public()));
+
+    // DER OCTET STRING as the standard prescribes, or the raw key
+    let octet_string = [&[0x04, 0x20][..], &hex(RFC8032_PUBLIC)].concat();
+    assert_eq!(parse_ec_point(&octet_string), Ok(rfc8032_public()));
+    assert_eq!(parse_ec_point(&hex(RFC8032_PUBLIC)), Ok(rfc8032_public()));
+}
+
+#[test]
+fn other_key_encodings_are_rejected() {
+    let spki = rfc8032_spki();
+    // X25519 (1.3.101.110) instead of Ed25519 (1.3.101.112)
+    let mut x25519 = spki.clone();
+    x25519[8] = 0x6e;
+
+    for der in [&spki[..43], &[&spki[..], &[0]].concat(), &x25519] {
+        assert!(matches!(
+            parse_ed25519_spki(der),
+            Err(SigningError::InvalidPublicKey(_))
+        ));
+    }
+    for ec_point in [&[0x04, 0x20, 1, 2, 3][..], &[0x04; 65], &[]] {
+        assert!(matches!(
+            parse_ec_point(ec_point),
+            Err(SigningError::InvalidPublicKey(_))
+        ));
+    }
+}
+
+#[test]
+fn kms_signs_raw_messages_with_ed25519() {
+    let kms = FakeKms::new(Keypair::new());
+    let algorithms = kms.algorithms.clone();
+    let backend = KmsBackend::new(kms, "alias/mica-eur-issuer").unwrap();
+
+    let signature = backend.sign(b"message").unwrap();
+
+    assert!(signature.verify(backend.public_key().unwrap().as_ref(), b"message"));
+    assert_eq!(*algorithms.borrow(), [KMS_ED25519_SIGNING_ALGORITHM]);
+}
+
+#[test]
+fn kms_keys_must_be_ed25519() {
+    let kms = FakeKms {
+        key_spec: "ECC_NIST_P256",
+        ..FakeKms::new(Keypair::new())
+    };
+    assert!(matches!(
+        KmsBackend::new(kms, "alias/mica-eur-issuer"),
+        Err(SigningError::InvalidPublicKey(_))
+    ));
+
+    assert_eq!(
+        KmsBackend::new(FakeKms::new(Keypair::new()), "alias/unknown").err(),
+        Some(SigningError::Backend(
+            "NotFoundException: alias/unknown".to_string()
+        ))
+    );
+}
+
+#[test]
+fn pkcs11_signs_with_ckm_eddsa() {
+    let keypair = Keypair::new();
+    let ec_point = [&[0x04, 0x20][..], keypair.pubkey().as_ref()].concat();
+    let token = FakeToken::new(keypair, ec_point);
+    let mechanisms = token.mechanisms.clone();
+
+    assert_eq!(
+        Pkcs11Backend::new(FakeToken::new(Keypair::new(), vec![]), "oracle").err(),
+        Some(SigningError::KeyNotFound("oracle".to_string()))
+    );
+
+    let backend = Pkcs11Backend::new(token, "issuer").unwrap();
+    let signature = backend.sign(b"message").unwrap();
+    assert!(signature.verify(backend.public_key().unwrap().as_ref(), b"message"));
+    assert_eq!(*mechanisms.borrow(), [CKM_EDDSA]);
+}
+
+#[test]
+fn signatures_are_verified_before_they_are_handed_out() {
+    let signer = IssuerSigner::new(KeyRole::KycOracle, MismatchedBackend).unwrap();
+    assert_eq!(
+        signer.try_sign_message(b"message"),
+        Err(SignerError::Custom(
+            SigningError::InvalidSignature.to_string()
+        ))
+    );
+
+    let signer = IssuerSigner::new(KeyRole::KycOracle, UnreachableBackend).unwrap();
+    assert_eq!(
+        signer.try_sign_message(b"message"),
+        Err(SignerError::Connection("connection refused".to_string()))
+    );
+}
+
+#[test]
+fn the_key_must_be_the_one_configured_on_chain() {
+    let configured = Pubkey::new_unique();
+
+    let result = IssuerSigner::with_expected_key(
+        KeyRole::AmlAuthority,
+        LocalBackend::new(rfc8032_keypair()),
+        &configured,
+    );
+
+    let error = result.err().unwrap();
+    assert_eq!(
+        error,
+        SigningError::KeyMismatch {
+            role: KeyRole::AmlAuthority,
+            expected: configured,
+            actual: rfc8032_public(),
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "AML authority key is {}, expected {}",
+            rfc8032_public(),
+            configured
+        )
+    );
+}
+
+#[test]
+fn transactions_are_signed_like_with_a_keypair() {
+    let backend =
+        KmsBackend::new(FakeKms::new(rfc8032_keypair()), "alias/mica-eur-issuer").unwrap();
+    let signer =
+        IssuerSigner::with_expected_key(KeyRole::Issuer, backend, &rfc8032_public()).unwrap();
+    let transfer = system_instruction::transfer(&signer.pubkey(), &Pubkey::new_unique(), 1);
+
+    let transaction = Transaction::new_signed_with_payer(
+        std::slice::from_ref(&transfer),
+        Some(&signer.pubkey()),
+        &[&signer],
+        Hash::new_unique(),
+    );
+    let reference = Transaction::new_signed_with_payer(
+        &[transfer],
+        Some(&signer.pubkey()),
+        &[&rfc8032_keypair()],
+        transaction.message.recent_blockhash,
+    );
+
+    transaction.verify().unwrap();
+    assert_eq!(transaction.signatures, reference.signatures);
+}
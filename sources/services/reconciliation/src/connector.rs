@@ -0,0 +1,59 @@
+use mica_eur::EUR_DECIMALS;
+
+use crate::ReconciliationError;
+
+/// Booked balance of the reserve account in the core-banking ledger
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LedgerBalance {
+    pub balance_cents: u64,
+    pub currency: String,
+    pub as_of: i64,                   // Unix time the balance was booked at
+    pub extract_hash: [u8; 32],       // Hash of the ledger extract, recorded on-chain
+}
+
+impl LedgerBalance {
+    /// The balance in token units
+    pub fn balance_units(&self) -> Result<u64, ReconciliationError> {
+        if self.currency != "EUR" {
+            return Err(ReconciliationError::UnsupportedCurrency(self.currency.clone()));
+        }
+        cents_to_units(self.balance_cents)
+    }
+}
+
+/// Convert euro cents to token units
+pub fn cents_to_units(cents: u64) -> Result<u64, ReconciliationError> {
+    cents
+        .checked_mul(10u64.pow(EUR_DECIMALS as u32 - 2))
+        .ok_or(ReconciliationError::AmountOutOfRange)
+}
+
+/// Access to the issuer's core-banking system. Implemented per bank (API, database
+/// view or statement drop) by the deployment.
+pub trait CoreBankingConnector {
+    /// Current booked balance of the reserve account
+    fn reserve_balance(&self) -> Result<LedgerBalance, ReconciliationError>;
+}
+
+/// What the issuer owes in fiat according to the chain and its own records, in
+/// token units
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IssuerPosition {
+    pub supply: u64,                  // Token-2022 mint supply
+    pub pending_redemptions: u64,     // Redemptions burnt on-chain, payout not yet settled
+    pub registered_deposits: u64,     // Deposits matched to customers, not yet minted
+}
+
+impl IssuerPosition {
+    /// Fiat the reserve account must hold for this position
+    pub fn expected_units(&self) -> u128 {
+        self.supply as u128 + self.pending_redemptions as u128 + self.registered_deposits as u128
+    }
+}
+
+/// Source of the issuer position: the mint supply from an RPC node, pending
+/// redemptions from the payout scheduler and registered deposits from statement
+/// ingestion
+pub trait IssuerState {
+    fn position(&self) -> Result<IssuerPosition, ReconciliationError>;
+}
@@ -0,0 +1,50 @@
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::connector::{CoreBankingConnector, IssuerState};
+use crate::reconciler::{AlertSink, Reconciler};
+use crate::record::RecordSubmitter;
+use crate::ReconciliationError;
+
+/// Polls both sides at a fixed interval and records persisting discrepancies
+pub struct ReconciliationDaemon<C, S, A, R> {
+    pub connector: C,
+    pub issuer_state: S,
+    pub alerts: A,
+    pub submitter: R,
+    pub reconciler: Reconciler,
+    pub interval: Duration,
+}
+
+impl<C, S, A, R> ReconciliationDaemon<C, S, A, R>
+where
+    C: CoreBankingConnector,
+    S: IssuerState,
+    A: AlertSink,
+    R: RecordSubmitter,
+{
+    /// Run one reconciliation at `now`
+    pub fn tick(&mut self, now: i64) -> Result<(), ReconciliationError> {
+        let ledger = self.connector.reserve_balance()?;
+        let position = self.issuer_state.position()?;
+        if let Some(request) = self.reconciler.reconcile(&position, &ledger, now, &mut self.alerts)? {
+            self.submitter.submit(&request)?;
+        }
+        Ok(())
+    }
+
+    /// Reconcile every `interval` until `stop` returns true. Failed runs are
+    /// reported to the alert sink and retried on the next tick.
+    pub fn run(&mut self, stop: impl Fn() -> bool) {
+        while !stop() {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs() as i64)
+                .unwrap_or_default();
+            if let Err(error) = self.tick(now) {
+                self.alerts.failure(&error);
+            }
+            thread::sleep(self.interval);
+        }
+    }
+}
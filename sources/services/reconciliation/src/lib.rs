@@ -0,0 +1,54 @@
+//! Off-chain reconciliation of MiCA EUR chain state against the issuer's
+//! core-banking ledger.
+//!
+//! The fiat in the reserve account must cover the token supply, redemptions burnt
+//! on-chain but not yet paid out, and deposits received but not yet minted. The
+//! daemon compares both sides on every tick, raises alerts as a discrepancy opens
+//! and closes, and writes an on-chain `ReconciliationRecord` once a discrepancy
+//! has persisted beyond the configured threshold:
+//!
+//! - `connector`: the core-banking connector and issuer-side position traits
+//! - `reconciler`: discrepancy tracking, thresholds and alerts
+//! - `record`: `record_reconciliation` instructions and their submission
+//! - `daemon`: the polling loop
+
+pub mod connector;
+pub mod daemon;
+pub mod reconciler;
+pub mod record;
+
+pub use connector::*;
+pub use daemon::*;
+pub use reconciler::*;
+pub use record::*;
+
+/// Errors of reconciliation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReconciliationError {
+    /// The core-banking connector failed
+    Connector(String),
+    /// Chain or issuer-side figures could not be read
+    IssuerState(String),
+    /// The reconciliation record could not be submitted
+    Submission(String),
+    /// The ledger is in a currency other than EUR
+    UnsupportedCurrency(String),
+    /// Amounts exceed the range the program records
+    AmountOutOfRange,
+}
+
+impl std::fmt::Display for ReconciliationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReconciliationError::Connector(error) => write!(f, "core-banking connector: {}", error),
+            ReconciliationError::IssuerState(error) => write!(f, "issuer state: {}", error),
+            ReconciliationError::Submission(error) => write!(f, "record submission: {}", error),
+            ReconciliationError::UnsupportedCurrency(currency) => {
+                write!(f, "unsupported ledger currency {}", currency)
+            }
+            ReconciliationError::AmountOutOfRange => write!(f, "amount out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ReconciliationError {}
@@ -0,0 +1,123 @@
+use crate::connector::{IssuerPosition, LedgerBalance};
+use crate::record::RecordRequest;
+use crate::ReconciliationError;
+
+/// Settings of the reconciler
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReconcilerConfig {
+    pub tolerance_units: u64,         // Differences up to this many token units count as balanced
+    pub persistence_threshold: i64,   // Seconds a discrepancy must persist before it is recorded
+}
+
+/// Stage of a discrepancy an alert reports
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlertKind {
+    /// The figures stopped matching
+    Opened,
+    /// The discrepancy persisted beyond the threshold and is recorded on-chain
+    Persisting,
+    /// The figures match again
+    Resolved,
+}
+
+/// Alert raised as a discrepancy opens, persists and resolves
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub discrepancy: i64,             // Ledger minus expected, token units
+    pub position: IssuerPosition,
+    pub ledger_balance: u64,          // Token units
+    pub first_detected_time: i64,
+    pub timestamp: i64,
+}
+
+/// Receiver of alerts, e.g. the issuer's paging or ticketing system
+pub trait AlertSink {
+    fn raise(&mut self, alert: &Alert);
+
+    /// A reconciliation run failed before the figures could be compared
+    fn failure(&mut self, error: &ReconciliationError);
+}
+
+/// A discrepancy that has not been resolved yet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpenDiscrepancy {
+    pub discrepancy: i64,             // Latest difference, token units
+    pub first_detected_time: i64,
+    pub recorded: bool,               // Whether it was recorded on-chain
+}
+
+/// Tracks discrepancies across reconciliation runs. A discrepancy changing sign
+/// (shortfall to surplus or back) counts as a new one.
+#[derive(Clone, Debug)]
+pub struct Reconciler {
+    pub config: ReconcilerConfig,
+    open: Option<OpenDiscrepancy>,
+}
+
+impl Reconciler {
+    pub fn new(config: ReconcilerConfig) -> Self {
+        Self { config, open: None }
+    }
+
+    pub fn open_discrepancy(&self) -> Option<&OpenDiscrepancy> {
+        self.open.as_ref()
+    }
+
+    /// Compare the issuer position with the ledger at `now`. Returns the record to
+    /// write on-chain once a discrepancy crosses the persistence threshold.
+    pub fn reconcile(
+        &mut self,
+        position: &IssuerPosition,
+        ledger: &LedgerBalance,
+        now: i64,
+        alerts: &mut impl AlertSink,
+    ) -> Result<Option<RecordRequest>, ReconciliationError> {
+        let ledger_balance = ledger.balance_units()?;
+        let discrepancy = i64::try_from(ledger_balance as i128 - position.expected_units() as i128)
+            .map_err(|_| ReconciliationError::AmountOutOfRange)?;
+        let mut alert = |kind, first_detected_time| {
+            alerts.raise(&Alert {
+                kind,
+                discrepancy,
+                position: *position,
+                ledger_balance,
+                first_detected_time,
+                timestamp: now,
+            })
+        };
+
+        if discrepancy.unsigned_abs() <= self.config.tolerance_units {
+            if let Some(open) = self.open.take() {
+                alert(AlertKind::Resolved, open.first_detected_time);
+            }
+            return Ok(None);
+        }
+
+        let open = match self.open.as_mut() {
+            Some(open) if open.discrepancy.signum() == discrepancy.signum() => open,
+            _ => {
+                alert(AlertKind::Opened, now);
+                self.open.insert(OpenDiscrepancy {
+                    discrepancy,
+                    first_detected_time: now,
+                    recorded: false,
+                })
+            }
+        };
+        open.discrepancy = discrepancy;
+
+        if open.recorded || now - open.first_detected_time < self.config.persistence_threshold {
+            return Ok(None);
+        }
+        open.recorded = true;
+        alert(AlertKind::Persisting, open.first_detected_time);
+        Ok(Some(RecordRequest {
+            pending_redemptions: position.pending_redemptions,
+            registered_deposits: position.registered_deposits,
+            ledger_balance,
+            ledger_hash: ledger.extract_hash,
+            first_detected_time: open.first_detected_time,
+        }))
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use mica_eur::{MINT_INFO_SEED, RECONCILIATION_LOG_SEED, RECONCILIATION_RECORD_SEED};
+
+use crate::ReconciliationError;
+
+/// Figures of a persisting discrepancy to record on-chain, in token units. The
+/// program reads the supply itself when the record is written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordRequest {
+    pub pending_redemptions: u64,
+    pub registered_deposits: u64,
+    pub ledger_balance: u64,
+    pub ledger_hash: [u8; 32],
+    pub first_detected_time: i64,
+}
+
+/// Signs and sends `record_reconciliation` transactions with the issuer key,
+/// e.g. through `issuer_signer` and an RPC client
+pub trait RecordSubmitter {
+    fn submit(&mut self, request: &RecordRequest) -> Result<(), ReconciliationError>;
+}
+
+/// Address of a mint's reconciliation log
+pub fn reconciliation_log_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[RECONCILIATION_LOG_SEED, mint.as_ref()], &mica_eur::ID).0
+}
+
+/// Address of the reconciliation record with this ID
+pub fn reconciliation_record_address(mint: &Pubkey, record_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[RECONCILIATION_RECORD_SEED, mint.as_ref(), &record_id.to_le_bytes()],
+        &mica_eur::ID,
+    )
+    .0
+}
+
+/// `record_reconciliation` instruction writing record `record_id`, the log's
+/// current record count
+pub fn record_reconciliation_instruction(
+    issuer: &Pubkey,
+    mint: &Pubkey,
+    record_id: u64,
+    request: &RecordRequest,
+) -> Instruction {
+    let accounts = mica_eur::accounts::RecordReconciliation {
+        issuer: *issuer,
+        mint_info: Pubkey::find_program_address(&[MINT_INFO_SEED, mint.as_ref()], &mica_eur::ID).0,
+        mint: *mint,
+        reconciliation_log: reconciliation_log_address(mint),
+        reconciliation_record: reconciliation_record_address(mint, record_id),
+        system_program: system_program::ID,
+    };
+    let data = mica_eur::instruction::RecordReconciliation {
+        pending_redemptions: request.pending_redemptions,
+        registered_deposits: request.registered_deposits,
+        ledger_balance: request.ledger_balance,
+        ledger_hash: request.ledger_hash,
+        first_detected_time: request.first_detected_time,
+    };
+    Instruction {
+        program_id: mica_eur::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
@@ -0,0 +1,261 @@
+//! The reconciliation daemon over an in-memory ledger and issuer position: a
+//! discrepancy opening, persisting past the threshold into one on-chain record,
+//! and resolving, plus the `record_reconciliation` instruction it submits.
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use mica_eur::{MINT_INFO_SEED, RECONCILIATION_LOG_SEED};
+use mica_eur_sdk::decoder::{decode_program_instruction, ProgramOperation, RawInstruction};
+use reconciliation::*;
+
+const HOUR: i64 = 3600;
+const EXTRACT_HASH: [u8; 32] = [0xe7; 32];
+
+struct Ledger(LedgerBalance);
+
+impl CoreBankingConnector for Ledger {
+    fn reserve_balance(&self) -> Result<LedgerBalance, ReconciliationError> {
+        Ok(self.0.clone())
+    }
+}
+
+struct Chain(IssuerPosition);
+
+impl IssuerState for Chain {
+    fn position(&self) -> Result<IssuerPosition, ReconciliationError> {
+        Ok(self.0)
+    }
+}
+
+#[derive(Default)]
+struct Alerts {
+    raised: Vec<Alert>,
+    failures: Vec<ReconciliationError>,
+}
+
+impl AlertSink for Alerts {
+    fn raise(&mut self, alert: &Alert) {
+        self.raised.push(alert.clone());
+    }
+
+    fn failure(&mut self, error: &ReconciliationError) {
+        self.failures.push(error.clone());
+    }
+}
+
+#[derive(Default)]
+struct Records(Vec<RecordRequest>);
+
+impl RecordSubmitter for Records {
+    fn submit(&mut self, request: &RecordRequest) -> Result<(), ReconciliationError> {
+        self.0.push(request.clone());
+        Ok(())
+    }
+}
+
+type Daemon = ReconciliationDaemon<Ledger, Chain, Alerts, Records>;
+
+// A reserve of EUR 1,000,000.00 covering its supply, pending redemptions and
+// registered deposits exactly
+fn daemon() -> Daemon {
+    ReconciliationDaemon {
+        connector: Ledger(LedgerBalance {
+            balance_cents: 100_000_000,
+            currency: "EUR".to_string(),
+            as_of: 0,
+            extract_hash: EXTRACT_HASH,
+        }),
+        issuer_state: Chain(IssuerPosition {
+            supply: 999_000_000_000,
+            pending_redemptions: 600_000_000,
+            registered_deposits: 400_000_000,
+        }),
+        alerts: Alerts::default(),
+        submitter: Records::default(),
+        reconciler: Reconciler::new(ReconcilerConfig {
+            tolerance_units: 10_000, // One cent
+            persistence_threshold: 4 * HOUR,
+        }),
+        interval: Duration::ZERO,
+    }
+}
+
+fn kinds(daemon: &Daemon) -> Vec<AlertKind> {
+    daemon
+        .alerts
+        .raised
+        .iter()
+        .map(|alert| alert.kind)
+        .collect()
+}
+
+#[test]
+fn balanced_books_raise_nothing() {
+    let mut daemon = daemon();
+    daemon.tick(0).unwrap();
+
+    // A one cent difference is within tolerance
+    daemon.connector.0.balance_cents += 1;
+    daemon.tick(HOUR).unwrap();
+
+    assert!(daemon.alerts.raised.is_empty());
+    assert!(daemon.submitter.0.is_empty());
+    assert_eq!(daemon.reconciler.open_discrepancy(), None);
+}
+
+#[test]
+fn persisting_discrepancies_are_recorded_once() {
+    let mut daemon = daemon();
+    let start = 1_775_000_000;
+
+    // A EUR 500.00 shortfall appears
+    daemon.connector.0.balance_cents -= 50_000;
+    daemon.tick(start).unwrap();
+    daemon.tick(start + 4 * HOUR - 1).unwrap();
+    assert_eq!(kinds(&daemon), [AlertKind::Opened]);
+    assert!(daemon.submitter.0.is_empty());
+
+    daemon.tick(start + 4 * HOUR).unwrap();
+    daemon.tick(start + 5 * HOUR).unwrap();
+    assert_eq!(kinds(&daemon), [AlertKind::Opened, AlertKind::Persisting]);
+    assert_eq!(
+        daemon.submitter.0,
+        [RecordRequest {
+            pending_redemptions: 600_000_000,
+            registered_deposits: 400_000_000,
+            ledger_balance: 999_500_000_000,
+            ledger_hash: EXTRACT_HASH,
+            first_detected_time: start,
+        }]
+    );
+    let persisting = &daemon.alerts.raised[1];
+    assert_eq!(persisting.discrepancy, -500_000_000);
+    assert_eq!(persisting.first_detected_time, start);
+    assert_eq!(persisting.timestamp, start + 4 * HOUR);
+
+    // The payout settles and the books balance again
+    daemon.issuer_state.0.pending_redemptions -= 500_000_000;
+    daemon.tick(start + 6 * HOUR).unwrap();
+    assert_eq!(
+        kinds(&daemon),
+        [
+            AlertKind::Opened,
+            AlertKind::Persisting,
+            AlertKind::Resolved
+        ]
+    );
+    assert_eq!(daemon.alerts.raised[2].first_detected_time, start);
+    assert_eq!(daemon.reconciler.open_discrepancy(), None);
+    assert_eq!(daemon.submitter.0.len(), 1);
+}
+
+#[test]
+fn a_discrepancy_changing_sign_starts_over() {
+    let mut daemon = daemon();
+
+    daemon.connector.0.balance_cents -= 100;
+    daemon.tick(0).unwrap();
+    daemon.connector.0.balance_cents += 200;
+    daemon.tick(3 * HOUR).unwrap();
+    // Four hours after the shortfall, but only one after the surplus appeared
+    daemon.tick(4 * HOUR).unwrap();
+
+    assert_eq!(kinds(&daemon), [AlertKind::Opened, AlertKind::Opened]);
+    assert_eq!(daemon.alerts.raised[1].discrepancy, 1_000_000);
+    let open = daemon.reconciler.open_discrepancy().unwrap();
+    assert_eq!(open.first_detected_time, 3 * HOUR);
+    assert!(!open.recorded);
+    assert!(daemon.submitter.0.is_empty());
+}
+
+#[test]
+fn failed_runs_are_reported_and_retried() {
+    let mut daemon = daemon();
+    daemon.connector.0.currency = "USD".to_string();
+    assert_eq!(
+        daemon.tick(0),
+        Err(ReconciliationError::UnsupportedCurrency("USD".to_string()))
+    );
+
+    let ticks = Cell::new(0);
+    daemon.run(|| {
+        ticks.set(ticks.get() + 1);
+        ticks.get() > 2
+    });
+
+    assert_eq!(
+        daemon.alerts.failures,
+        vec![ReconciliationError::UnsupportedCurrency("USD".to_string()); 2]
+    );
+    assert!(daemon.alerts.raised.is_empty());
+}
+
+#[test]
+fn amounts_beyond_the_token_range_are_rejected() {
+    assert_eq!(cents_to_units(1), Ok(10_000));
+    assert_eq!(
+        cents_to_units(u64::MAX / 10_000 + 1),
+        Err(ReconciliationError::AmountOutOfRange)
+    );
+
+    let mut daemon = daemon();
+    daemon.issuer_state.0 = IssuerPosition {
+        supply: u64::MAX,
+        pending_redemptions: u64::MAX,
+        registered_deposits: 0,
+    };
+    daemon.connector.0.balance_cents = 0;
+    assert_eq!(daemon.tick(0), Err(ReconciliationError::AmountOutOfRange));
+}
+
+#[test]
+fn record_instruction_targets_the_logged_record() {
+    let (issuer, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let request = RecordRequest {
+        pending_redemptions: 600_000_000,
+        registered_deposits: 400_000_000,
+        ledger_balance: 999_500_000_000,
+        ledger_hash: EXTRACT_HASH,
+        first_detected_time: 1_775_000_000,
+    };
+
+    let instruction = record_reconciliation_instruction(&issuer, &mint, 3, &request);
+
+    let accounts: Vec<Pubkey> = instruction
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect();
+    let program = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &mica_eur::ID).0;
+    assert_eq!(accounts[1], program(&[MINT_INFO_SEED, mint.as_ref()]));
+    assert_eq!(
+        accounts[3],
+        program(&[RECONCILIATION_LOG_SEED, mint.as_ref()])
+    );
+    assert_eq!(accounts[3], reconciliation_log_address(&mint));
+    assert_ne!(
+        reconciliation_record_address(&mint, 3),
+        reconciliation_record_address(&mint, 4)
+    );
+    assert!(instruction.accounts[0].is_signer);
+
+    let raw = RawInstruction {
+        program_id: instruction.program_id,
+        accounts,
+        data: instruction.data,
+    };
+    assert_eq!(
+        decode_program_instruction(&raw).unwrap(),
+        ProgramOperation::RecordReconciliation {
+            issuer,
+            mint,
+            reconciliation_record: reconciliation_record_address(&mint, 3),
+            pending_redemptions: 600_000_000,
+            registered_deposits: 400_000_000,
+            ledger_balance: 999_500_000_000,
+            first_detected_time: 1_775_000_000,
+        }
+    );
+}
@@ -0,0 +1,74 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/// SEPA business-day calendar: TARGET2 closing days plus any bank-specific holidays
+#[derive(Clone, Debug, Default)]
+pub struct BankCalendar {
+    extra_holidays: Vec<NaiveDate>, // Closing days of the paying bank on top of TARGET2
+}
+
+impl BankCalendar {
+    /// Calendar with only the TARGET2 closing days
+    pub fn target2() -> Self {
+        Self::default()
+    }
+
+    /// Add bank-specific closing days
+    pub fn with_holidays(mut self, holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.extra_holidays.extend(holidays);
+        self
+    }
+
+    /// Whether SEPA credit transfers settle on `date`
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            && !is_target2_holiday(date)
+            && !self.extra_holidays.contains(&date)
+    }
+
+    /// First business day on or after `date`
+    pub fn next_business_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut date = date;
+        while !self.is_business_day(date) {
+            date += Duration::days(1);
+        }
+        date
+    }
+
+    /// Execution date for a payout requested at `requested_at` (UTC): the same day
+    /// when requested on a business day before `cutoff`, the next business day otherwise
+    pub fn execution_date(&self, requested_at: NaiveDateTime, cutoff: NaiveTime) -> NaiveDate {
+        let date = requested_at.date();
+        if self.is_business_day(date) && requested_at.time() < cutoff {
+            date
+        } else {
+            self.next_business_day(date + Duration::days(1))
+        }
+    }
+}
+
+// TARGET2 is closed on New Year's Day, Good Friday, Easter Monday, 1 May and 25-26 December
+fn is_target2_holiday(date: NaiveDate) -> bool {
+    let easter = easter_sunday(date.year());
+    matches!((date.month(), date.day()), (1, 1) | (5, 1) | (12, 25) | (12, 26))
+        || date == easter - Duration::days(2)
+        || date == easter + Duration::days(1)
+}
+
+// Gregorian Easter Sunday (anonymous Gregorian algorithm)
+fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).expect("valid Easter date")
+}
\ No newline at end of file
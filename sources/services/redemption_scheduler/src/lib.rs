@@ -0,0 +1,54 @@
+//! Off-chain payout scheduler for MiCA EUR redemptions.
+//!
+//! Redemptions burnt on-chain leave an `OperationReceipt` of kind `Redemption`.
+//! The scheduler turns them into SEPA credit transfers paid from the reserve
+//! account, respecting the TARGET2 calendar:
+//!
+//! - `calendar`: SEPA business days and same-day cut-off handling
+//! - `request`: redemption requests, their payout status and storage
+//! - `pain001`: pain.001 credit transfer batch files
+//! - `reports`: pain.002 status report and camt.053 statement parsing
+//! - `scheduler`: batching open requests and closing them from bank reports
+//!
+//! Settled payouts keep the bank's reference, whose hash the issuer records
+//! on-chain with `record_payout_reference`.
+
+pub mod calendar;
+pub mod pain001;
+pub mod reports;
+pub mod request;
+pub mod scheduler;
+
+pub use calendar::*;
+pub use pain001::*;
+pub use reports::*;
+pub use request::*;
+pub use scheduler::*;
+
+/// Errors of the payout scheduler
+#[derive(Debug)]
+pub enum SchedulerError {
+    /// The operation receipt is not a redemption
+    NotARedemption,
+    /// No request with this operation ID is stored
+    UnknownRequest,
+    /// A request carries a timestamp outside the supported range
+    InvalidTimestamp(i64),
+    /// A bank report could not be parsed
+    InvalidReport(String),
+}
+
+impl std::fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulerError::NotARedemption => write!(f, "operation receipt is not a redemption"),
+            SchedulerError::UnknownRequest => write!(f, "unknown redemption request"),
+            SchedulerError::InvalidTimestamp(timestamp) => {
+                write!(f, "invalid request timestamp {}", timestamp)
+            }
+            SchedulerError::InvalidReport(error) => write!(f, "invalid bank report: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for SchedulerError {}
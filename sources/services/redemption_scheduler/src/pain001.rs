@@ -0,0 +1,112 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::request::{format_cents, RedemptionRequest};
+
+/// Reserve bank account payouts are debited from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebtorAccount {
+    pub name: String,
+    pub iban: String,
+    pub bic: String,
+}
+
+/// Redemptions paid out together in one SEPA credit transfer file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentBatch {
+    pub message_id: String,          // Unique ID of the pain.001 message
+    pub created_at: NaiveDateTime,   // UTC creation time
+    pub execution_date: NaiveDate,   // Requested execution date, always a business day
+    pub requests: Vec<RedemptionRequest>,
+}
+
+impl PaymentBatch {
+    /// Sum of the payouts in euro cents
+    pub fn total_cents(&self) -> u64 {
+        self.requests.iter().map(RedemptionRequest::amount_cents).sum()
+    }
+
+    /// Render the batch as an ISO 20022 pain.001.001.09 customer credit transfer initiation
+    pub fn to_pain001(&self, debtor: &DebtorAccount) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<Document xmlns=\"urn:iso:std:iso:20022:tech:xsd:pain.001.001.09\">\n");
+        xml.push_str("<CstmrCdtTrfInitn>\n");
+
+        xml.push_str("<GrpHdr>\n");
+        element(&mut xml, "MsgId", &self.message_id);
+        element(&mut xml, "CreDtTm", &self.created_at.format("%Y-%m-%dT%H:%M:%S").to_string());
+        element(&mut xml, "NbOfTxs", &self.requests.len().to_string());
+        element(&mut xml, "CtrlSum", &format_cents(self.total_cents()));
+        xml.push_str("<InitgPty>");
+        element(&mut xml, "Nm", &debtor.name);
+        xml.push_str("</InitgPty>\n");
+        xml.push_str("</GrpHdr>\n");
+
+        xml.push_str("<PmtInf>\n");
+        element(&mut xml, "PmtInfId", &self.message_id);
+        element(&mut xml, "PmtMtd", "TRF");
+        element(&mut xml, "NbOfTxs", &self.requests.len().to_string());
+        element(&mut xml, "CtrlSum", &format_cents(self.total_cents()));
+        xml.push_str("<PmtTpInf><SvcLvl><Cd>SEPA</Cd></SvcLvl></PmtTpInf>\n");
+        xml.push_str("<ReqdExctnDt>");
+        element(&mut xml, "Dt", &self.execution_date.format("%Y-%m-%d").to_string());
+        xml.push_str("</ReqdExctnDt>\n");
+        xml.push_str("<Dbtr>");
+        element(&mut xml, "Nm", &debtor.name);
+        xml.push_str("</Dbtr>\n");
+        xml.push_str("<DbtrAcct><Id>");
+        element(&mut xml, "IBAN", &debtor.iban);
+        xml.push_str("</Id></DbtrAcct>\n");
+        xml.push_str("<DbtrAgt><FinInstnId>");
+        element(&mut xml, "BICFI", &debtor.bic);
+        xml.push_str("</FinInstnId></DbtrAgt>\n");
+        element(&mut xml, "ChrgBr", "SLEV");
+
+        for request in &self.requests {
+            credit_transfer(&mut xml, request);
+        }
+
+        xml.push_str("</PmtInf>\n");
+        xml.push_str("</CstmrCdtTrfInitn>\n");
+        xml.push_str("</Document>\n");
+        xml
+    }
+}
+
+// One CdtTrfTxInf block
+fn credit_transfer(xml: &mut String, request: &RedemptionRequest) {
+    let end_to_end_id = request.end_to_end_id();
+
+    xml.push_str("<CdtTrfTxInf>\n");
+    xml.push_str("<PmtId>");
+    element(xml, "EndToEndId", &end_to_end_id);
+    xml.push_str("</PmtId>\n");
+    xml.push_str("<Amt><InstdAmt Ccy=\"EUR\">");
+    xml.push_str(&format_cents(request.amount_cents()));
+    xml.push_str("</InstdAmt></Amt>\n");
+    if let Some(bic) = &request.beneficiary.bic {
+        xml.push_str("<CdtrAgt><FinInstnId>");
+        element(xml, "BICFI", bic);
+        xml.push_str("</FinInstnId></CdtrAgt>\n");
+    }
+    xml.push_str("<Cdtr>");
+    element(xml, "Nm", &request.beneficiary.name);
+    xml.push_str("</Cdtr>\n");
+    xml.push_str("<CdtrAcct><Id>");
+    element(xml, "IBAN", &request.beneficiary.iban);
+    xml.push_str("</Id></CdtrAcct>\n");
+    xml.push_str("<RmtInf>");
+    element(xml, "Ustrd", &format!("MiCA EUR redemption {}", end_to_end_id));
+    xml.push_str("</RmtInf>\n");
+    xml.push_str("</CdtTrfTxInf>\n");
+}
+
+fn element(xml: &mut String, name: &str, value: &str) {
+    xml.push('<');
+    xml.push_str(name);
+    xml.push('>');
+    xml.push_str(&quick_xml::escape::escape(value));
+    xml.push_str("</");
+    xml.push_str(name);
+    xml.push('>');
+}
\ No newline at end of file
@@ -0,0 +1,134 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::SchedulerError;
+
+/// Status of one payout from a pain.002 payment status report
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentStatus {
+    pub end_to_end_id: String,
+    pub status: String,         // ISO status code, e.g. ACCP, ACSC or RJCT
+    pub reason: Option<String>, // Reason code for rejections
+}
+
+impl PaymentStatus {
+    /// Whether the bank rejected the payout
+    pub fn is_rejected(&self) -> bool {
+        self.status == "RJCT"
+    }
+}
+
+/// A booked debit of the reserve account from a camt.053 statement
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BookedPayout {
+    pub end_to_end_id: String,
+    pub booking_date: String,
+    pub bank_reference: String, // The bank's reference of the entry (AcctSvcrRef)
+}
+
+// Walk an XML document, calling `on_event` with the element path and its text for
+// every text node, and with `None` when the element at the end of the path closes
+fn walk(xml: &str, mut on_event: impl FnMut(&[String], Option<&str>)) -> Result<(), SchedulerError> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    let mut path: Vec<String> = Vec::new();
+
+    loop {
+        match reader.read_event().map_err(|e| SchedulerError::InvalidReport(e.to_string()))? {
+            Event::Start(start) => {
+                path.push(String::from_utf8_lossy(start.local_name().as_ref()).into_owned());
+            }
+            Event::Text(text) => {
+                let text = text.unescape().map_err(|e| SchedulerError::InvalidReport(e.to_string()))?;
+                on_event(&path, Some(&text));
+            }
+            Event::End(_) => {
+                on_event(&path, None);
+                path.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn ends_with(path: &[String], suffix: &[&str]) -> bool {
+    path.len() >= suffix.len()
+        && path[path.len() - suffix.len()..].iter().zip(suffix).all(|(a, b)| a == b)
+}
+
+fn empty_status() -> PaymentStatus {
+    PaymentStatus { end_to_end_id: String::new(), status: String::new(), reason: None }
+}
+
+/// Extract per-transaction statuses from a pain.002 payment status report
+pub fn parse_pain002(xml: &str) -> Result<Vec<PaymentStatus>, SchedulerError> {
+    let mut statuses = Vec::new();
+    let mut current = empty_status();
+
+    walk(xml, |path, text| match text {
+        Some(text) => {
+            if ends_with(path, &["TxInfAndSts", "OrgnlEndToEndId"]) {
+                current.end_to_end_id = text.to_string();
+            } else if ends_with(path, &["TxInfAndSts", "TxSts"]) {
+                current.status = text.to_string();
+            } else if ends_with(path, &["TxInfAndSts", "StsRsnInf", "Rsn", "Cd"]) {
+                current.reason = Some(text.to_string());
+            }
+        }
+        None if path.last().map(String::as_str) == Some("TxInfAndSts") => {
+            let status = std::mem::replace(&mut current, empty_status());
+            if !status.end_to_end_id.is_empty() {
+                statuses.push(status);
+            }
+        }
+        None => {}
+    })?;
+    Ok(statuses)
+}
+
+/// Extract booked debits carrying an end-to-end ID from a camt.053 bank statement
+pub fn parse_camt053(xml: &str) -> Result<Vec<BookedPayout>, SchedulerError> {
+    let mut payouts = Vec::new();
+    let mut is_debit = false;
+    let mut is_booked = false;
+    let mut booking_date = String::new();
+    let mut bank_reference = String::new();
+    let mut end_to_end_ids: Vec<String> = Vec::new();
+
+    walk(xml, |path, text| match text {
+        Some(text) => {
+            if ends_with(path, &["Ntry", "CdtDbtInd"]) {
+                is_debit = text == "DBIT";
+            } else if ends_with(path, &["Ntry", "Sts"]) || ends_with(path, &["Ntry", "Sts", "Cd"]) {
+                is_booked = text == "BOOK";
+            } else if ends_with(path, &["Ntry", "BookgDt", "Dt"])
+                || ends_with(path, &["Ntry", "BookgDt", "DtTm"])
+            {
+                // Keep the date part of date-times
+                booking_date = text.chars().take(10).collect();
+            } else if ends_with(path, &["Ntry", "AcctSvcrRef"]) {
+                bank_reference = text.to_string();
+            } else if ends_with(path, &["TxDtls", "Refs", "EndToEndId"]) && text != "NOTPROVIDED" {
+                end_to_end_ids.push(text.to_string());
+            }
+        }
+        None if path.last().map(String::as_str) == Some("Ntry") => {
+            if is_debit && is_booked {
+                payouts.extend(end_to_end_ids.iter().map(|end_to_end_id| BookedPayout {
+                    end_to_end_id: end_to_end_id.clone(),
+                    booking_date: booking_date.clone(),
+                    bank_reference: bank_reference.clone(),
+                }));
+            }
+            end_to_end_ids.clear();
+            is_debit = false;
+            is_booked = false;
+            booking_date.clear();
+            bank_reference.clear();
+        }
+        None => {}
+    })?;
+    Ok(payouts)
+}
\ No newline at end of file
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::Pubkey;
+use mica_eur::{sepa_reference_hash, OperationKind, OperationReceipt, EUR_DECIMALS};
+
+use crate::SchedulerError;
+
+/// Bank account a redemption is paid out to, held off-chain by the issuer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Beneficiary {
+    pub name: String,
+    pub iban: String,
+    pub bic: Option<String>,
+}
+
+/// Where a redemption request is in the payout lifecycle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequestStatus {
+    /// Waiting to be included in a payment batch
+    Open,
+    /// Included in the batch file with this message ID
+    Submitted { message_id: String },
+    /// Debited from the reserve account according to the bank statement, under
+    /// the bank's reference recorded on-chain with `record_payout_reference`
+    Settled { booking_date: String, bank_reference: String },
+    /// Rejected by the bank; the reason code comes from the status report
+    Rejected { reason: String },
+}
+
+/// A redemption waiting for (or going through) its SEPA payout
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedemptionRequest {
+    pub operation_id: [u8; 16], // Operation ID of the on-chain redemption
+    pub mint: Pubkey,
+    pub holder: Pubkey,         // Signer of the redemption
+    pub amount: u64,            // Redeemed amount in token units
+    pub requested_at: i64,      // Unix time of the on-chain redemption
+    pub beneficiary: Beneficiary,
+    pub status: RequestStatus,
+}
+
+impl RedemptionRequest {
+    /// Open request for an on-chain redemption receipt
+    pub fn from_receipt(
+        receipt: &OperationReceipt,
+        beneficiary: Beneficiary,
+    ) -> Result<Self, SchedulerError> {
+        if receipt.kind != OperationKind::Redemption {
+            return Err(SchedulerError::NotARedemption);
+        }
+        Ok(Self {
+            operation_id: receipt.operation_id,
+            mint: receipt.mint,
+            holder: receipt.actor,
+            amount: receipt.amount,
+            requested_at: receipt.timestamp,
+            beneficiary,
+            status: RequestStatus::Open,
+        })
+    }
+
+    /// SEPA end-to-end ID identifying the payout: the hex operation ID (32 characters)
+    pub fn end_to_end_id(&self) -> String {
+        self.operation_id.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Hash `record_payout_reference` binds a settled payout with, `None` until the
+    /// payout is settled under a bank reference
+    pub fn payout_reference_hash(&self) -> Option<[u8; 32]> {
+        match &self.status {
+            RequestStatus::Settled { bank_reference, .. } if !bank_reference.is_empty() => {
+                Some(sepa_reference_hash(bank_reference))
+            }
+            _ => None,
+        }
+    }
+
+    /// Payout amount in euro cents. Token units below one cent are not paid out.
+    pub fn amount_cents(&self) -> u64 {
+        self.amount / 10u64.pow(EUR_DECIMALS as u32 - 2)
+    }
+}
+
+/// Format euro cents as a SEPA amount, e.g. `1234.50`
+pub fn format_cents(cents: u64) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Storage of redemption requests. Implementations back it with the issuer's
+/// database and mirror status changes to the on-chain receipts' indexer.
+pub trait RequestStore {
+    /// Requests still waiting for a payment batch
+    fn open_requests(&self) -> Vec<RedemptionRequest>;
+
+    /// Look a request up by its end-to-end ID
+    fn find(&self, end_to_end_id: &str) -> Option<RedemptionRequest>;
+
+    /// Record a new status for a request
+    fn set_status(&mut self, operation_id: [u8; 16], status: RequestStatus) -> Result<(), SchedulerError>;
+}
+
+/// In-memory `RequestStore`, for tooling and dry runs
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    pub requests: Vec<RedemptionRequest>,
+}
+
+impl RequestStore for MemoryStore {
+    fn open_requests(&self) -> Vec<RedemptionRequest> {
+        self.requests
+            .iter()
+            .filter(|request| request.status == RequestStatus::Open)
+            .cloned()
+            .collect()
+    }
+
+    fn find(&self, end_to_end_id: &str) -> Option<RedemptionRequest> {
+        self.requests
+            .iter()
+            .find(|request| request.end_to_end_id() == end_to_end_id)
+            .cloned()
+    }
+
+    fn set_status(&mut self, operation_id: [u8; 16], status: RequestStatus) -> Result<(), SchedulerError> {
+        let request = self
+            .requests
+            .iter_mut()
+            .find(|request| request.operation_id == operation_id)
+            .ok_or(SchedulerError::UnknownRequest)?;
+        request.status = status;
+        Ok(())
+    }
+}
\ No newline at end of file
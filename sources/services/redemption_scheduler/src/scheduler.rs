@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::calendar::BankCalendar;
+use crate::pain001::PaymentBatch;
+use crate::reports::{parse_camt053, parse_pain002};
+use crate::request::{RedemptionRequest, RequestStatus, RequestStore};
+use crate::SchedulerError;
+
+/// Counts of requests closed by an ingested report
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IngestSummary {
+    pub settled: usize,
+    pub rejected: usize,
+    pub unmatched: usize, // Report entries without a submitted request
+}
+
+/// Groups open redemption requests into SEPA batches by execution date and
+/// closes them from the bank's status reports and statements
+#[derive(Clone, Debug)]
+pub struct PayoutScheduler {
+    pub calendar: BankCalendar,
+    pub cutoff: NaiveTime,      // UTC cut-off for same-day execution
+    pub message_prefix: String, // Prefix of generated pain.001 message IDs
+}
+
+impl PayoutScheduler {
+    pub fn new(calendar: BankCalendar, cutoff: NaiveTime, message_prefix: impl Into<String>) -> Self {
+        Self { calendar, cutoff, message_prefix: message_prefix.into() }
+    }
+
+    /// Execution date of a request
+    pub fn execution_date(&self, request: &RedemptionRequest) -> Result<NaiveDate, SchedulerError> {
+        let requested_at = DateTime::from_timestamp(request.requested_at, 0)
+            .ok_or(SchedulerError::InvalidTimestamp(request.requested_at))?
+            .naive_utc();
+        Ok(self.calendar.execution_date(requested_at, self.cutoff))
+    }
+
+    /// Batch every open request into one file per execution date and mark the batched
+    /// requests as submitted. Requests whose execution date has passed are executed
+    /// on the earliest date still possible at `now`.
+    pub fn schedule(
+        &self,
+        store: &mut impl RequestStore,
+        now: NaiveDateTime,
+    ) -> Result<Vec<PaymentBatch>, SchedulerError> {
+        let earliest = self.calendar.execution_date(now, self.cutoff);
+
+        let mut by_date: BTreeMap<NaiveDate, Vec<RedemptionRequest>> = BTreeMap::new();
+        for request in store.open_requests() {
+            if request.amount_cents() == 0 {
+                continue;
+            }
+            let date = self.execution_date(&request)?.max(earliest);
+            by_date.entry(date).or_default().push(request);
+        }
+
+        let mut batches = Vec::new();
+        for (execution_date, requests) in by_date {
+            let message_id = format!(
+                "{}-{}-{}",
+                self.message_prefix,
+                execution_date.format("%Y%m%d"),
+                now.and_utc().timestamp()
+            );
+            for request in &requests {
+                store.set_status(
+                    request.operation_id,
+                    RequestStatus::Submitted { message_id: message_id.clone() },
+                )?;
+            }
+            batches.push(PaymentBatch { message_id, created_at: now, execution_date, requests });
+        }
+        Ok(batches)
+    }
+
+    /// Close rejected payouts from a pain.002 payment status report
+    pub fn ingest_status_report(
+        &self,
+        store: &mut impl RequestStore,
+        xml: &str,
+    ) -> Result<IngestSummary, SchedulerError> {
+        let mut summary = IngestSummary::default();
+        for status in parse_pain002(xml)? {
+            match submitted(store, &status.end_to_end_id) {
+                Some(request) if status.is_rejected() => {
+                    let reason = status.reason.unwrap_or_else(|| "RJCT".to_string());
+                    store.set_status(request.operation_id, RequestStatus::Rejected { reason })?;
+                    summary.rejected += 1;
+                }
+                Some(_) => {}
+                None => summary.unmatched += 1,
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Settle payouts booked on a camt.053 statement of the reserve account
+    pub fn ingest_statement(
+        &self,
+        store: &mut impl RequestStore,
+        xml: &str,
+    ) -> Result<IngestSummary, SchedulerError> {
+        let mut summary = IngestSummary::default();
+        for payout in parse_camt053(xml)? {
+            match submitted(store, &payout.end_to_end_id) {
+                Some(request) => {
+                    store.set_status(
+                        request.operation_id,
+                        RequestStatus::Settled {
+                            booking_date: payout.booking_date,
+                            bank_reference: payout.bank_reference,
+                        },
+                    )?;
+                    summary.settled += 1;
+                }
+                None => summary.unmatched += 1,
+            }
+        }
+        Ok(summary)
+    }
+}
+
+// A request awaiting confirmation from the bank
+fn submitted(store: &impl RequestStore, end_to_end_id: &str) -> Option<RedemptionRequest> {
+    store
+        .find(end_to_end_id)
+        .filter(|request| matches!(request.status, RequestStatus::Submitted { .. }))
+}
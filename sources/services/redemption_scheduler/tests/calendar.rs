@@ -0,0 +1,115 @@
+//! TARGET2 closing days and cut-off handling of the SEPA business-day calendar.
+//! Easter falls on 5 April in 2026, 28 March in 2027 and 16 April in 2028.
+
+use chrono::{NaiveDate, NaiveTime};
+use redemption_scheduler::BankCalendar;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn cutoff() -> NaiveTime {
+    NaiveTime::from_hms_opt(14, 0, 0).unwrap()
+}
+
+#[test]
+fn easter_closing_days_follow_the_moving_feast() {
+    let calendar = BankCalendar::target2();
+
+    for (good_friday, easter_monday) in [
+        (date(2026, 4, 3), date(2026, 4, 6)),
+        (date(2027, 3, 26), date(2027, 3, 29)),
+        (date(2028, 4, 14), date(2028, 4, 17)),
+    ] {
+        assert!(!calendar.is_business_day(good_friday), "{good_friday}");
+        assert!(!calendar.is_business_day(easter_monday), "{easter_monday}");
+    }
+    // Maundy Thursday and the Tuesday after Easter are open
+    assert!(calendar.is_business_day(date(2026, 4, 2)));
+    assert!(calendar.is_business_day(date(2026, 4, 7)));
+    // Ascension and Whit Monday are not TARGET2 holidays
+    assert!(calendar.is_business_day(date(2026, 5, 14)));
+    assert!(calendar.is_business_day(date(2026, 5, 25)));
+}
+
+#[test]
+fn fixed_closing_days_are_skipped() {
+    let calendar = BankCalendar::target2();
+
+    for holiday in [
+        date(2026, 1, 1),
+        date(2026, 5, 1),
+        date(2026, 12, 25),
+        date(2025, 12, 26),
+    ] {
+        assert!(!calendar.is_business_day(holiday), "{holiday}");
+    }
+    assert!(calendar.is_business_day(date(2026, 12, 24)));
+    assert!(calendar.is_business_day(date(2026, 12, 31)));
+}
+
+#[test]
+fn next_business_day_spans_the_easter_weekend() {
+    let calendar = BankCalendar::target2();
+
+    assert_eq!(
+        calendar.next_business_day(date(2026, 4, 3)),
+        date(2026, 4, 7)
+    );
+    assert_eq!(
+        calendar.next_business_day(date(2026, 4, 7)),
+        date(2026, 4, 7)
+    );
+    // 25 December 2026 is a Friday and 26 December a Saturday
+    assert_eq!(
+        calendar.next_business_day(date(2026, 12, 25)),
+        date(2026, 12, 28)
+    );
+    // 1 January 2027 is a Friday
+    assert_eq!(
+        calendar.next_business_day(date(2027, 1, 1)),
+        date(2027, 1, 4)
+    );
+}
+
+#[test]
+fn execution_date_respects_the_cutoff() {
+    let calendar = BankCalendar::target2();
+    let at = |day: NaiveDate, hour: u32| day.and_hms_opt(hour, 0, 0).unwrap();
+
+    // Before the cut-off on a business day: same day
+    assert_eq!(
+        calendar.execution_date(at(date(2026, 4, 2), 9), cutoff()),
+        date(2026, 4, 2)
+    );
+    // At or after the cut-off on Maundy Thursday: the Tuesday after Easter
+    assert_eq!(
+        calendar.execution_date(at(date(2026, 4, 2), 14), cutoff()),
+        date(2026, 4, 7)
+    );
+    // Requested on a closing day before the cut-off: next business day
+    assert_eq!(
+        calendar.execution_date(at(date(2026, 4, 6), 8), cutoff()),
+        date(2026, 4, 7)
+    );
+    // Christmas Eve afternoon
+    assert_eq!(
+        calendar.execution_date(at(date(2026, 12, 24), 15), cutoff()),
+        date(2026, 12, 28)
+    );
+}
+
+#[test]
+fn bank_holidays_extend_the_target2_calendar() {
+    // German Unity Day 2025 was a Friday; TARGET2 itself was open
+    let unity_day = date(2025, 10, 3);
+    assert!(BankCalendar::target2().is_business_day(unity_day));
+
+    let calendar = BankCalendar::target2().with_holidays([unity_day]);
+    assert!(!calendar.is_business_day(unity_day));
+    assert_eq!(calendar.next_business_day(unity_day), date(2025, 10, 6));
+    assert_eq!(
+        calendar.execution_date(date(2025, 10, 2).and_hms_opt(16, 30, 0).unwrap(), cutoff()),
+        date(2025, 10, 6)
+    );
+}
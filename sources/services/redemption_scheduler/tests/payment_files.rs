@@ -0,0 +1,124 @@
+//! pain.001 batch files: the generated file is compared with a golden copy and
+//! validated with `xmllint` against `tests/fixtures/pain.001.001.09.xsd`, the
+//! part of the ISO 20022 schema covering the elements the scheduler writes.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anchor_lang::prelude::Pubkey;
+use chrono::NaiveDate;
+use redemption_scheduler::*;
+
+const SCHEMA: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/pain.001.001.09.xsd"
+);
+const GOLDEN: &str = include_str!("fixtures/pain001-batch.xml");
+
+// Validate `xml` against the schema, returning xmllint's report on failure
+fn validate(xml: &str) -> Result<(), String> {
+    let mut xmllint = Command::new("xmllint")
+        .args(["--noout", "--schema", SCHEMA, "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("xmllint validates pain.001 files against the schema (install libxml2-utils)");
+    xmllint
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(xml.as_bytes())
+        .unwrap();
+    let output = xmllint.wait_with_output().unwrap();
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+}
+
+fn debtor() -> DebtorAccount {
+    DebtorAccount {
+        name: "MiCA EUR Issuer GmbH".to_string(),
+        iban: "DE89370400440532013000".to_string(),
+        bic: "COBADEFFXXX".to_string(),
+    }
+}
+
+fn request(id: u8, amount: u64, name: &str, iban: &str, bic: Option<&str>) -> RedemptionRequest {
+    RedemptionRequest {
+        operation_id: [id; 16],
+        mint: Pubkey::new_unique(),
+        holder: Pubkey::new_unique(),
+        amount,
+        requested_at: 1_775_120_400, // 2026-04-02 09:00 UTC
+        beneficiary: Beneficiary {
+            name: name.to_string(),
+            iban: iban.to_string(),
+            bic: bic.map(str::to_string),
+        },
+        status: RequestStatus::Open,
+    }
+}
+
+fn batch() -> PaymentBatch {
+    PaymentBatch {
+        message_id: "MICAEUR-20260407-1775232000".to_string(),
+        created_at: NaiveDate::from_ymd_opt(2026, 4, 3)
+            .unwrap()
+            .and_hms_opt(16, 0, 0)
+            .unwrap(),
+        execution_date: NaiveDate::from_ymd_opt(2026, 4, 7).unwrap(),
+        requests: vec![
+            // Token units below one cent are not paid out
+            request(
+                0xa1,
+                1_250_500_999,
+                "Erika Mustermann",
+                "DE02120300000000202051",
+                None,
+            ),
+            request(
+                0xb2,
+                40_000_000,
+                "Müller & Söhne <KG>",
+                "FR1420041010050500013M02606",
+                Some("BNPAFRPP"),
+            ),
+        ],
+    }
+}
+
+#[test]
+fn pain001_matches_the_golden_file() {
+    let batch = batch();
+
+    assert_eq!(batch.total_cents(), 129_050);
+    assert_eq!(batch.to_pain001(&debtor()), GOLDEN);
+}
+
+#[test]
+fn pain001_is_valid_against_the_schema() {
+    validate(GOLDEN).unwrap();
+    validate(&batch().to_pain001(&debtor())).unwrap();
+
+    let mut single = batch();
+    single.requests.truncate(1);
+    validate(&single.to_pain001(&debtor())).unwrap();
+}
+
+#[test]
+fn schema_rejects_malformed_accounts() {
+    let mut batch = batch();
+    batch.requests[0].beneficiary.iban = "de02120300000000202051".to_string();
+    let report = validate(&batch.to_pain001(&debtor())).unwrap_err();
+    assert!(report.contains("IBAN"), "{report}");
+
+    let debtor = DebtorAccount {
+        bic: "COBADE".to_string(),
+        ..debtor()
+    };
+    let report = validate(&self::batch().to_pain001(&debtor)).unwrap_err();
+    assert!(report.contains("BICFI"), "{report}");
+}
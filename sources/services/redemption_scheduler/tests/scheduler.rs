@@ -0,0 +1,221 @@
+//! Payout lifecycle against the in-memory store: batching by execution date,
+//! rejections from the pain.002 report in `tests/fixtures/pain002.xml` and
+//! settlement from the camt.053 statement in `tests/fixtures/camt053-payouts.xml`.
+
+use anchor_lang::prelude::Pubkey;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use mica_eur::{sepa_reference_hash, OperationKind, OperationReceipt};
+use redemption_scheduler::*;
+
+const PAIN002: &str = include_str!("fixtures/pain002.xml");
+const CAMT053: &str = include_str!("fixtures/camt053-payouts.xml");
+
+const APRIL_1_1600: i64 = 1_775_059_200;
+const APRIL_2_0900: i64 = 1_775_120_400;
+const APRIL_2_1000: i64 = 1_775_124_000;
+const APRIL_2_1500: i64 = 1_775_142_000;
+
+fn utc(timestamp: i64) -> NaiveDateTime {
+    DateTime::from_timestamp(timestamp, 0).unwrap().naive_utc()
+}
+
+fn scheduler() -> PayoutScheduler {
+    PayoutScheduler::new(
+        BankCalendar::target2(),
+        NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+        "MICAEUR",
+    )
+}
+
+fn receipt(id: u8, kind: OperationKind, amount: u64, timestamp: i64) -> OperationReceipt {
+    OperationReceipt {
+        operation_id: [id; 16],
+        kind,
+        mint: Pubkey::new_unique(),
+        actor: Pubkey::new_unique(),
+        source: Pubkey::new_unique(),
+        destination: Pubkey::default(),
+        amount,
+        slot: 1,
+        timestamp,
+        rule_set_version: 1,
+    }
+}
+
+fn request(id: u8, amount: u64, timestamp: i64) -> RedemptionRequest {
+    let beneficiary = Beneficiary {
+        name: format!("Holder {id}"),
+        iban: "DE02120300000000202051".to_string(),
+        bic: None,
+    };
+    RedemptionRequest::from_receipt(
+        &receipt(id, OperationKind::Redemption, amount, timestamp),
+        beneficiary,
+    )
+    .unwrap()
+}
+
+fn status(store: &MemoryStore, id: u8) -> RequestStatus {
+    store
+        .requests
+        .iter()
+        .find(|r| r.operation_id == [id; 16])
+        .unwrap()
+        .status
+        .clone()
+}
+
+#[test]
+fn only_redemption_receipts_become_requests() {
+    let beneficiary = Beneficiary {
+        name: "Holder".to_string(),
+        iban: "DE02120300000000202051".to_string(),
+        bic: None,
+    };
+    let mint = receipt(1, OperationKind::Mint, 1_000_000, APRIL_2_0900);
+
+    assert!(matches!(
+        RedemptionRequest::from_receipt(&mint, beneficiary),
+        Err(SchedulerError::NotARedemption)
+    ));
+}
+
+#[test]
+fn payouts_are_batched_settled_and_rejected() {
+    let scheduler = scheduler();
+    let mut store = MemoryStore {
+        requests: vec![
+            request(1, 1_250_500_000, APRIL_2_0900),
+            request(2, 40_000_000, APRIL_1_1600),
+            request(4, 5_000, APRIL_2_0900), // Half a cent: nothing to pay out
+        ],
+    };
+
+    // Both requests are due on 2 April: the one from the previous afternoon
+    // rolled over, the morning one made the cut-off
+    let batches = scheduler.schedule(&mut store, utc(APRIL_2_1000)).unwrap();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].message_id, "MICAEUR-20260402-1775124000");
+    assert_eq!(
+        batches[0].execution_date,
+        NaiveDate::from_ymd_opt(2026, 4, 2).unwrap()
+    );
+    assert_eq!(batches[0].total_cents(), 129_050);
+    let submitted = RequestStatus::Submitted {
+        message_id: batches[0].message_id.clone(),
+    };
+    assert_eq!(status(&store, 1), submitted);
+    assert_eq!(status(&store, 2), submitted);
+    assert_eq!(status(&store, 4), RequestStatus::Open);
+
+    let summary = scheduler.ingest_status_report(&mut store, PAIN002).unwrap();
+    assert_eq!(
+        summary,
+        IngestSummary {
+            settled: 0,
+            rejected: 1,
+            unmatched: 1
+        }
+    );
+    assert_eq!(status(&store, 1), submitted);
+    assert_eq!(
+        status(&store, 2),
+        RequestStatus::Rejected {
+            reason: "AC04".to_string()
+        }
+    );
+
+    // The rejected payout's debit is not a settlement
+    let summary = scheduler.ingest_statement(&mut store, CAMT053).unwrap();
+    assert_eq!(
+        summary,
+        IngestSummary {
+            settled: 1,
+            rejected: 0,
+            unmatched: 1
+        }
+    );
+    assert_eq!(
+        status(&store, 1),
+        RequestStatus::Settled {
+            booking_date: "2026-04-02".to_string(),
+            bank_reference: "COBA-260402-77311".to_string(),
+        }
+    );
+    let settled = store.find("01010101010101010101010101010101").unwrap();
+    assert_eq!(
+        settled.payout_reference_hash(),
+        Some(sepa_reference_hash("COBA-260402-77311"))
+    );
+    assert_eq!(
+        store
+            .find("02020202020202020202020202020202")
+            .unwrap()
+            .payout_reference_hash(),
+        None
+    );
+}
+
+#[test]
+fn afternoon_requests_before_easter_wait_for_tuesday() {
+    let scheduler = scheduler();
+    let mut store = MemoryStore {
+        requests: vec![request(3, 2_000_000, APRIL_2_1500)],
+    };
+
+    let batches = scheduler
+        .schedule(&mut store, utc(APRIL_2_1500 + 300))
+        .unwrap();
+
+    assert_eq!(batches.len(), 1);
+    assert_eq!(
+        batches[0].execution_date,
+        NaiveDate::from_ymd_opt(2026, 4, 7).unwrap()
+    );
+    assert_eq!(
+        batches[0].requests[0].end_to_end_id(),
+        "03030303030303030303030303030303"
+    );
+    // Nothing is left to batch on the next run
+    assert!(scheduler
+        .schedule(&mut store, utc(APRIL_2_1500 + 600))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn overdue_requests_move_to_the_earliest_possible_date() {
+    let scheduler = scheduler();
+    let mut store = MemoryStore {
+        requests: vec![
+            request(1, 1_000_000, APRIL_1_1600),
+            request(2, 1_000_000, APRIL_2_0900),
+        ],
+    };
+
+    // Scheduled on Good Friday: both go out on the Tuesday after Easter
+    let good_friday = NaiveDate::from_ymd_opt(2026, 4, 3)
+        .unwrap()
+        .and_hms_opt(9, 0, 0)
+        .unwrap();
+    let batches = scheduler.schedule(&mut store, good_friday).unwrap();
+
+    assert_eq!(batches.len(), 1);
+    assert_eq!(
+        batches[0].execution_date,
+        NaiveDate::from_ymd_opt(2026, 4, 7).unwrap()
+    );
+    assert_eq!(batches[0].requests.len(), 2);
+}
+
+#[test]
+fn malformed_reports_are_rejected() {
+    let scheduler = scheduler();
+    let mut store = MemoryStore::default();
+    let truncated = &PAIN002[..PAIN002.find("</TxInfAndSts>").unwrap()];
+
+    assert!(matches!(
+        scheduler.ingest_status_report(&mut store, &format!("{truncated}</TxSts>")),
+        Err(SchedulerError::InvalidReport(_))
+    ));
+}
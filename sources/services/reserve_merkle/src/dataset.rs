@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{from_hex, to_hex, MerkleError, ReserveEntry, ReserveTree};
+
+/// Version of the dataset layout
+pub const DATASET_VERSION: u32 = 1;
+
+/// A deposit of the dataset with its leaf and inclusion proof, hashes in hex
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetEntry {
+    #[serde(flatten)]
+    pub entry: ReserveEntry,
+    pub leaf: String,
+    pub proof: Vec<String>,
+}
+
+/// Everything needed to rebuild and check a reserve proof: the deposits in tree
+/// order, the root and total posted on-chain, and each deposit's proof
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReserveDataset {
+    pub version: u32,
+    pub merkle_root: String,
+    pub reserve_total: u64,
+    pub entries: Vec<DatasetEntry>,
+}
+
+impl ReserveDataset {
+    pub fn from_tree(tree: &ReserveTree) -> Self {
+        let entries = tree
+            .entries()
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| DatasetEntry {
+                entry: entry.clone(),
+                leaf: to_hex(&tree.leaves()[index]),
+                proof: tree.proof(index).unwrap_or_default().iter().map(to_hex).collect(),
+            })
+            .collect();
+        Self {
+            version: DATASET_VERSION,
+            merkle_root: to_hex(&tree.root()),
+            reserve_total: tree.reserve_total(),
+            entries,
+        }
+    }
+
+    /// Rebuild the tree from the deposits, failing unless the recorded root,
+    /// total, leaves and proofs are the ones the deposits produce
+    pub fn to_tree(&self) -> Result<ReserveTree, MerkleError> {
+        if self.version != DATASET_VERSION {
+            return Err(MerkleError::InvalidDataset(format!("version {}", self.version)));
+        }
+        let tree = ReserveTree::build(self.entries.iter().map(|entry| entry.entry.clone()).collect())?;
+        if from_hex(&self.merkle_root) != Some(tree.root()) {
+            return Err(MerkleError::InvalidDataset("merkle root does not match the deposits".to_string()));
+        }
+        if self.reserve_total != tree.reserve_total() {
+            return Err(MerkleError::InvalidDataset("reserve total does not match the deposits".to_string()));
+        }
+        if *self != Self::from_tree(&tree) {
+            return Err(MerkleError::InvalidDataset("leaves or proofs do not match the deposits".to_string()));
+        }
+        Ok(tree)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("dataset serializes")
+    }
+
+    /// Parse a dataset, checking it against its deposits
+    pub fn from_json(json: &str) -> Result<Self, MerkleError> {
+        let dataset: Self = serde_json::from_str(json).map_err(|error| MerkleError::InvalidDataset(error.to_string()))?;
+        dataset.to_tree()?;
+        Ok(dataset)
+    }
+}
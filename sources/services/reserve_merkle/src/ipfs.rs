@@ -0,0 +1,31 @@
+use crate::{MerkleError, ReserveDataset, ReserveTree};
+
+/// IPFS API calls pinning needs. Implemented by the deployment over its IPFS node
+/// or pinning service, which also owns credentials and retries.
+pub trait IpfsClient {
+    /// Add `content` as a file named `name` and pin it, returning its CID
+    fn add_and_pin(&self, name: &str, content: &[u8]) -> Result<String, String>;
+}
+
+/// A pinned reserve proof: the inputs of `update_reserve_proof`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PinnedReserveProof {
+    pub merkle_root: [u8; 32],
+    pub ipfs_cid: String,
+    pub reserve_total: u64,
+}
+
+/// Pin the dataset of `tree` as JSON, named after its root
+pub fn pin_dataset<C: IpfsClient>(client: &C, tree: &ReserveTree) -> Result<PinnedReserveProof, MerkleError> {
+    let dataset = ReserveDataset::from_tree(tree);
+    let name = format!("reserve-{}.json", dataset.merkle_root);
+    let ipfs_cid = client.add_and_pin(&name, dataset.to_json().as_bytes()).map_err(MerkleError::Ipfs)?;
+    if ipfs_cid.is_empty() {
+        return Err(MerkleError::Ipfs("empty CID".to_string()));
+    }
+    Ok(PinnedReserveProof {
+        merkle_root: tree.root(),
+        ipfs_cid,
+        reserve_total: tree.reserve_total(),
+    })
+}
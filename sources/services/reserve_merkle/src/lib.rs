@@ -0,0 +1,65 @@
+//! Reserve proof trees for MiCA EUR, built off-chain.
+//!
+//! The deposits backing the reserve are hashed into the same tree the program
+//! verifies against (`create_reserve_leaf` and `calculate_merkle_root`), so the
+//! root posted with `update_reserve_proof` can be rebuilt by anyone holding the
+//! dataset, and every depositor can prove their deposit with
+//! `verify_reserve_inclusion`:
+//!
+//! - `tree`: reserve entries, the tree over them and inclusion proofs
+//! - `dataset`: the JSON dataset of a tree, root and per-leaf proofs included
+//! - `ipfs`: pinning the dataset, whose CID goes on-chain next to the root
+
+pub mod dataset;
+pub mod ipfs;
+pub mod tree;
+
+pub use dataset::*;
+pub use ipfs::*;
+pub use tree::*;
+
+/// Errors of reserve tree building
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MerkleError {
+    /// Two entries share a deposit ID
+    DuplicateDeposit(String),
+    /// The amounts add up to more than a `u64` holds
+    TotalOverflow,
+    /// A statement deposit cannot become a reserve entry
+    InvalidDeposit(String),
+    /// A dataset could not be read, or does not match the tree it describes
+    InvalidDataset(String),
+    /// Pinning the dataset failed
+    Ipfs(String),
+}
+
+impl std::fmt::Display for MerkleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MerkleError::DuplicateDeposit(deposit_id) => write!(f, "duplicate deposit {}", deposit_id),
+            MerkleError::TotalOverflow => write!(f, "reserve total overflows"),
+            MerkleError::InvalidDeposit(error) => write!(f, "invalid deposit: {}", error),
+            MerkleError::InvalidDataset(error) => write!(f, "invalid dataset: {}", error),
+            MerkleError::Ipfs(error) => write!(f, "IPFS error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for MerkleError {}
+
+/// Lowercase hex encoding of a hash
+pub fn to_hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hash from 64 hex characters
+pub fn from_hex(value: &str) -> Option<[u8; 32]> {
+    if value.len() != 64 || !value.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
@@ -0,0 +1,130 @@
+use mica_eur::{calculate_merkle_root, create_reserve_leaf, merkle_levels, merkle_proof, verify_merkle_proof, EUR_DECIMALS};
+use reserve_statements::Deposit;
+use serde::{Deserialize, Serialize};
+
+use crate::MerkleError;
+
+/// Token units per euro cent
+const TOKEN_UNITS_PER_CENT: u64 = 10u64.pow(EUR_DECIMALS as u32 - 2);
+
+/// One deposit backing the reserve, a leaf of the tree
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReserveEntry {
+    pub deposit_id: String,
+    pub amount: u64,                  // In token units
+    pub timestamp: i64,               // Booking time, Unix seconds
+}
+
+impl ReserveEntry {
+    /// Leaf of the entry, as `create_reserve_leaf` hashes it on-chain
+    pub fn leaf(&self) -> [u8; 32] {
+        create_reserve_leaf(&self.deposit_id, self.amount, self.timestamp)
+    }
+
+    /// Entry for a deposit read from a bank statement, identified by the bank's
+    /// reference and booked at midnight UTC of its booking date
+    pub fn from_statement(deposit: &Deposit) -> Result<Self, MerkleError> {
+        if deposit.currency != "EUR" {
+            return Err(MerkleError::InvalidDeposit(format!(
+                "{} is in {}",
+                deposit.bank_reference, deposit.currency
+            )));
+        }
+        if deposit.bank_reference.is_empty() {
+            return Err(MerkleError::InvalidDeposit("deposit without a bank reference".to_string()));
+        }
+        let amount = deposit
+            .amount_cents
+            .checked_mul(TOKEN_UNITS_PER_CENT)
+            .ok_or(MerkleError::TotalOverflow)?;
+        let timestamp = parse_booking_date(&deposit.booking_date).ok_or_else(|| {
+            MerkleError::InvalidDeposit(format!("booking date {:?}", deposit.booking_date))
+        })?;
+        Ok(Self {
+            deposit_id: deposit.bank_reference.clone(),
+            amount,
+            timestamp,
+        })
+    }
+}
+
+/// The reserve tree over a set of entries, in the order given
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReserveTree {
+    entries: Vec<ReserveEntry>,
+    levels: Vec<Vec<[u8; 32]>>,
+    reserve_total: u64,
+}
+
+impl ReserveTree {
+    /// Build the tree; deposit IDs must be unique
+    pub fn build(entries: Vec<ReserveEntry>) -> Result<Self, MerkleError> {
+        let mut reserve_total = 0u64;
+        for (index, entry) in entries.iter().enumerate() {
+            if entries[..index].iter().any(|known| known.deposit_id == entry.deposit_id) {
+                return Err(MerkleError::DuplicateDeposit(entry.deposit_id.clone()));
+            }
+            reserve_total = reserve_total.checked_add(entry.amount).ok_or(MerkleError::TotalOverflow)?;
+        }
+        let leaves: Vec<[u8; 32]> = entries.iter().map(ReserveEntry::leaf).collect();
+        Ok(Self {
+            levels: merkle_levels(&leaves),
+            entries,
+            reserve_total,
+        })
+    }
+
+    /// Build the tree over the deposits of bank statements
+    pub fn from_statements(deposits: &[Deposit]) -> Result<Self, MerkleError> {
+        Self::build(deposits.iter().map(ReserveEntry::from_statement).collect::<Result<_, _>>()?)
+    }
+
+    pub fn entries(&self) -> &[ReserveEntry] {
+        &self.entries
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.levels[0]
+    }
+
+    /// Root to post with `update_reserve_proof`, all zeros for no entries
+    pub fn root(&self) -> [u8; 32] {
+        calculate_merkle_root(self.leaves())
+    }
+
+    /// Sum of the entries, the `reserve_total_eur` to post with the root
+    pub fn reserve_total(&self) -> u64 {
+        self.reserve_total
+    }
+
+    /// Inclusion proof of the entry at `index`, as `verify_reserve_inclusion` takes it
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        (index < self.entries.len()).then(|| merkle_proof(&self.levels, index))
+    }
+
+    /// Index of the entry with `deposit_id`
+    pub fn position(&self, deposit_id: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.deposit_id == deposit_id)
+    }
+
+    /// Whether `proof` proves `leaf` against this tree's root
+    pub fn verify(&self, leaf: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+        verify_merkle_proof(proof, &self.root(), leaf)
+    }
+}
+
+// Unix time of midnight UTC of a YYYY-MM-DD date
+fn parse_booking_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Days from the civil calendar (Howard Hinnant's algorithm)
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some((era * 146_097 + day_of_era - 719_468) * 86_400)
+}
@@ -0,0 +1,263 @@
+//! The reserve tree against the vector the on-chain verifier is tested with,
+//! `sol-programs/mica_eur/tests/vectors/reserve-tree-v1.json`, plus entries built
+//! from bank statement deposits and the datasets pinned next to the root.
+
+use std::cell::RefCell;
+
+use reserve_merkle::*;
+use reserve_statements::Deposit;
+use serde_json::Value;
+
+const VECTORS: &str =
+    include_str!("../../../sol-programs/mica_eur/tests/vectors/reserve-tree-v1.json");
+
+fn vector() -> Value {
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+    assert_eq!(vectors["version"], 1);
+    vectors["reserve_tree"].clone()
+}
+
+fn text(value: &Value) -> &str {
+    value.as_str().unwrap()
+}
+
+fn vector_tree(vector: &Value) -> ReserveTree {
+    let entries = vector["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| ReserveEntry {
+            deposit_id: text(&entry["deposit_id"]).to_string(),
+            amount: text(&entry["amount"]).parse().unwrap(),
+            timestamp: text(&entry["timestamp"]).parse().unwrap(),
+        })
+        .collect();
+    ReserveTree::build(entries).unwrap()
+}
+
+fn deposit(bank_reference: &str, amount_cents: u64, currency: &str, booking_date: &str) -> Deposit {
+    Deposit {
+        amount_cents,
+        currency: currency.to_string(),
+        booking_date: booking_date.to_string(),
+        bank_reference: bank_reference.to_string(),
+        remittance_info: "MICA-DEP 7F3KQ9ZD".to_string(),
+        debtor_name: None,
+        debtor_iban: None,
+    }
+}
+
+#[test]
+fn tree_matches_the_on_chain_vector() {
+    let vector = vector();
+    let tree = vector_tree(&vector);
+
+    assert_eq!(to_hex(&tree.root()), text(&vector["merkle_root"]));
+    assert_eq!(
+        tree.reserve_total().to_string(),
+        text(&vector["reserve_total"])
+    );
+    for (index, entry) in vector["entries"].as_array().unwrap().iter().enumerate() {
+        let proof: Vec<&str> = entry["proof"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(text)
+            .collect();
+        assert_eq!(to_hex(&tree.leaves()[index]), text(&entry["leaf"]));
+        assert_eq!(
+            tree.proof(index)
+                .unwrap()
+                .iter()
+                .map(to_hex)
+                .collect::<Vec<_>>(),
+            proof
+        );
+        assert!(tree.verify(&tree.leaves()[index], &tree.proof(index).unwrap()));
+    }
+    assert_eq!(tree.proof(tree.entries().len()), None);
+    assert_eq!(tree.position("COBA-260415-10388"), Some(2));
+}
+
+#[test]
+fn dataset_round_trips_and_rejects_tampering() {
+    let tree = vector_tree(&vector());
+    let dataset = ReserveDataset::from_tree(&tree);
+    let json = dataset.to_json();
+
+    assert_eq!(ReserveDataset::from_json(&json), Ok(dataset.clone()));
+    assert_eq!(dataset.to_tree(), Ok(tree));
+
+    let mut inflated = dataset.clone();
+    inflated.entries[1].entry.amount += 1;
+    assert!(matches!(
+        inflated.to_tree(),
+        Err(MerkleError::InvalidDataset(_))
+    ));
+
+    let mut total = dataset.clone();
+    total.reserve_total += 1;
+    assert_eq!(
+        total.to_tree(),
+        Err(MerkleError::InvalidDataset(
+            "reserve total does not match the deposits".to_string()
+        ))
+    );
+
+    let mut proof = dataset.clone();
+    proof.entries[4].proof.clear();
+    assert_eq!(
+        proof.to_tree(),
+        Err(MerkleError::InvalidDataset(
+            "leaves or proofs do not match the deposits".to_string()
+        ))
+    );
+
+    let mut version = dataset;
+    version.version = 2;
+    assert!(ReserveDataset::from_json(&version.to_json()).is_err());
+}
+
+#[test]
+fn duplicate_deposits_and_overflowing_totals_are_rejected() {
+    let entry = |deposit_id: &str, amount| ReserveEntry {
+        deposit_id: deposit_id.to_string(),
+        amount,
+        timestamp: 0,
+    };
+
+    assert_eq!(
+        ReserveTree::build(vec![entry("A", 1), entry("B", 2), entry("A", 3)]),
+        Err(MerkleError::DuplicateDeposit("A".to_string()))
+    );
+    assert_eq!(
+        ReserveTree::build(vec![entry("A", u64::MAX), entry("B", 1)]),
+        Err(MerkleError::TotalOverflow)
+    );
+    assert_eq!(ReserveTree::build(vec![]).unwrap().root(), [0; 32]);
+}
+
+#[test]
+fn statement_deposits_are_booked_at_midnight_utc() {
+    let tree = ReserveTree::from_statements(&[
+        deposit("COBA-260414-10021", 9_950, "EUR", "2026-04-14"),
+        // Leap day, in a leap year divisible by 400
+        deposit("COBA-000229-00001", 1, "EUR", "2000-02-29"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        tree.entries(),
+        [
+            ReserveEntry {
+                deposit_id: "COBA-260414-10021".to_string(),
+                amount: 99_500_000,
+                timestamp: 1_776_124_800,
+            },
+            ReserveEntry {
+                deposit_id: "COBA-000229-00001".to_string(),
+                amount: 10_000,
+                timestamp: 951_782_400,
+            },
+        ]
+    );
+}
+
+#[test]
+fn unusable_statement_deposits_are_rejected() {
+    let invalid = |deposit: Deposit| match ReserveEntry::from_statement(&deposit) {
+        Err(MerkleError::InvalidDeposit(reason)) => reason,
+        other => panic!("{other:?}"),
+    };
+
+    assert_eq!(
+        invalid(deposit("COBA-1", 100, "USD", "2026-04-14")),
+        "COBA-1 is in USD"
+    );
+    assert_eq!(
+        invalid(deposit("", 100, "EUR", "2026-04-14")),
+        "deposit without a bank reference"
+    );
+    assert_eq!(
+        invalid(deposit("COBA-1", 100, "EUR", "2026-13-01")),
+        "booking date \"2026-13-01\""
+    );
+    assert_eq!(
+        invalid(deposit("COBA-1", 100, "EUR", "14.04.2026")),
+        "booking date \"14.04.2026\""
+    );
+    assert_eq!(
+        ReserveEntry::from_statement(&deposit("COBA-1", u64::MAX, "EUR", "2026-04-14")),
+        Err(MerkleError::TotalOverflow)
+    );
+}
+
+#[test]
+fn hashes_round_trip_through_hex() {
+    let hash = [0xa5; 32];
+
+    assert_eq!(from_hex(&to_hex(&hash)), Some(hash));
+    assert_eq!(from_hex(&"A5".repeat(32)), Some(hash));
+    assert_eq!(from_hex(&"a5".repeat(31)), None);
+    assert_eq!(from_hex(&"zz".repeat(32)), None);
+}
+
+#[derive(Default)]
+struct FakeIpfs {
+    cid: String,
+    pinned: RefCell<Vec<(String, Vec<u8>)>>,
+}
+
+impl IpfsClient for FakeIpfs {
+    fn add_and_pin(&self, name: &str, content: &[u8]) -> Result<String, String> {
+        if self.cid == "unreachable" {
+            return Err("connection refused".to_string());
+        }
+        self.pinned
+            .borrow_mut()
+            .push((name.to_string(), content.to_vec()));
+        Ok(self.cid.clone())
+    }
+}
+
+#[test]
+fn datasets_are_pinned_under_their_root() {
+    let tree = vector_tree(&vector());
+    let ipfs = FakeIpfs {
+        cid: "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+        ..FakeIpfs::default()
+    };
+
+    let pinned = pin_dataset(&ipfs, &tree).unwrap();
+
+    assert_eq!(
+        pinned,
+        PinnedReserveProof {
+            merkle_root: tree.root(),
+            ipfs_cid: ipfs.cid.clone(),
+            reserve_total: tree.reserve_total(),
+        }
+    );
+    let (name, content) = ipfs.pinned.borrow()[0].clone();
+    assert_eq!(name, format!("reserve-{}.json", to_hex(&tree.root())));
+    assert_eq!(
+        ReserveDataset::from_json(std::str::from_utf8(&content).unwrap())
+            .unwrap()
+            .to_tree(),
+        Ok(tree.clone())
+    );
+
+    let empty = FakeIpfs::default();
+    assert_eq!(
+        pin_dataset(&empty, &tree),
+        Err(MerkleError::Ipfs("empty CID".to_string()))
+    );
+    let unreachable = FakeIpfs {
+        cid: "unreachable".to_string(),
+        ..FakeIpfs::default()
+    };
+    assert_eq!(
+        pin_dataset(&unreachable, &tree),
+        Err(MerkleError::Ipfs("connection refused".to_string()))
+    );
+}
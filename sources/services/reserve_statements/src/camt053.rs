@@ -0,0 +1,91 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::{parse_amount_cents, Deposit, StatementError};
+
+// Fields of the entry being read
+#[derive(Default)]
+struct Entry {
+    amount: String,
+    currency: String,
+    is_credit: bool,
+    is_booked: bool,
+    booking_date: String,
+    bank_reference: String,
+    remittance_info: Vec<String>,
+    debtor_name: Option<String>,
+    debtor_iban: Option<String>,
+}
+
+fn ends_with(path: &[String], suffix: &[&str]) -> bool {
+    path.len() >= suffix.len()
+        && path[path.len() - suffix.len()..].iter().zip(suffix).all(|(a, b)| a == b)
+}
+
+fn invalid(error: impl std::fmt::Display) -> StatementError {
+    StatementError::InvalidStatement(error.to_string())
+}
+
+/// Extract the booked credits of a camt.053 bank-to-customer statement
+pub fn parse_camt053_deposits(xml: &str) -> Result<Vec<Deposit>, StatementError> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    let mut path: Vec<String> = Vec::new();
+    let mut entry = Entry::default();
+    let mut deposits = Vec::new();
+
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(start) => {
+                path.push(String::from_utf8_lossy(start.local_name().as_ref()).into_owned());
+                if ends_with(&path, &["Ntry", "Amt"]) {
+                    if let Some(currency) = start.try_get_attribute("Ccy").map_err(invalid)? {
+                        entry.currency = currency.unescape_value().map_err(invalid)?.into_owned();
+                    }
+                }
+            }
+            Event::Text(text) => {
+                let text = text.unescape().map_err(invalid)?;
+                if ends_with(&path, &["Ntry", "Amt"]) {
+                    entry.amount = text.into_owned();
+                } else if ends_with(&path, &["Ntry", "CdtDbtInd"]) {
+                    entry.is_credit = text == "CRDT";
+                } else if ends_with(&path, &["Ntry", "Sts"]) || ends_with(&path, &["Ntry", "Sts", "Cd"]) {
+                    entry.is_booked = text == "BOOK";
+                } else if ends_with(&path, &["Ntry", "BookgDt", "Dt"])
+                    || ends_with(&path, &["Ntry", "BookgDt", "DtTm"])
+                {
+                    entry.booking_date = text.chars().take(10).collect();
+                } else if ends_with(&path, &["Ntry", "AcctSvcrRef"]) {
+                    entry.bank_reference = text.into_owned();
+                } else if ends_with(&path, &["RmtInf", "Ustrd"]) {
+                    entry.remittance_info.push(text.into_owned());
+                } else if ends_with(&path, &["Dbtr", "Nm"]) || ends_with(&path, &["Dbtr", "Pty", "Nm"]) {
+                    entry.debtor_name = Some(text.into_owned());
+                } else if ends_with(&path, &["DbtrAcct", "Id", "IBAN"]) {
+                    entry.debtor_iban = Some(text.into_owned());
+                }
+            }
+            Event::End(_) => {
+                if path.last().map(String::as_str) == Some("Ntry") {
+                    let entry = std::mem::take(&mut entry);
+                    if entry.is_credit && entry.is_booked {
+                        deposits.push(Deposit {
+                            amount_cents: parse_amount_cents(&entry.amount)?,
+                            currency: entry.currency,
+                            booking_date: entry.booking_date,
+                            bank_reference: entry.bank_reference,
+                            remittance_info: entry.remittance_info.join(" "),
+                            debtor_name: entry.debtor_name,
+                            debtor_iban: entry.debtor_iban,
+                        });
+                    }
+                }
+                path.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(deposits)
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+//! Bank statement ingestion for MiCA EUR reserve deposits.
+//!
+//! Fiat deposits into the reserve account are read from the bank's end-of-day
+//! statements, matched to the deposit intents announced by customers, and the
+//! matched deposits are handed to a `DepositSink` that registers them against
+//! the reserve. Everything else ends up in an unmatched-entry report.
+//!
+//! - `camt053`: ISO 20022 camt.053 statement parsing
+//! - `mt940`: SWIFT MT940 statement parsing
+//! - `matching`: deposit intents, matching and unmatched-entry reporting
+
+pub mod camt053;
+pub mod matching;
+pub mod mt940;
+
+pub use camt053::*;
+pub use matching::*;
+pub use mt940::*;
+
+/// An inbound credit on the reserve account
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deposit {
+    pub amount_cents: u64,
+    pub currency: String,
+    pub booking_date: String,          // YYYY-MM-DD
+    pub bank_reference: String,        // Reference the bank gave the entry
+    pub remittance_info: String,       // Reference text entered by the payer
+    pub debtor_name: Option<String>,
+    pub debtor_iban: Option<String>,
+}
+
+/// Errors of statement ingestion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatementError {
+    /// The statement could not be parsed
+    InvalidStatement(String),
+    /// An amount is not a valid decimal with at most two fraction digits
+    InvalidAmount(String),
+}
+
+impl std::fmt::Display for StatementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementError::InvalidStatement(error) => write!(f, "invalid statement: {}", error),
+            StatementError::InvalidAmount(amount) => write!(f, "invalid amount {:?}", amount),
+        }
+    }
+}
+
+impl std::error::Error for StatementError {}
+
+/// Parse a decimal amount such as `1234.5` or `1234,56` into cents
+pub fn parse_amount_cents(amount: &str) -> Result<u64, StatementError> {
+    let invalid = || StatementError::InvalidAmount(amount.to_string());
+    let amount = amount.trim();
+    let (units, fraction) = match amount.find(['.', ',']) {
+        Some(index) => (&amount[..index], &amount[index + 1..]),
+        None => (amount, ""),
+    };
+    if units.is_empty()
+        || fraction.len() > 2
+        || !units.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let units: u64 = units.parse().map_err(|_| invalid())?;
+    let fraction: u64 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+    units.checked_mul(100).and_then(|c| c.checked_add(fraction)).ok_or_else(invalid)
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::Deposit;
+
+/// Deposit announced by a customer before wiring funds to the reserve account
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepositIntent {
+    pub reference: String,   // Code the customer must quote in the remittance information
+    pub holder: Pubkey,      // Wallet the EUR tokens are minted to
+    pub amount_cents: u64,   // Announced amount
+}
+
+/// A deposit matched to its intent
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchedDeposit {
+    pub deposit: Deposit,
+    pub intent: DepositIntent,
+}
+
+/// Why a statement entry could not be matched
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnmatchedReason {
+    /// The deposit is not in euro
+    WrongCurrency,
+    /// No open intent reference appears in the remittance information
+    UnknownReference,
+    /// The reference matches an intent announced with a different amount
+    AmountMismatch { reference: String, expected_cents: u64 },
+    /// The sink refused to register the deposit
+    RegistrationFailed(String),
+}
+
+/// A statement entry left for manual review
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnmatchedEntry {
+    pub deposit: Deposit,
+    pub reason: UnmatchedReason,
+}
+
+/// Outcome of ingesting one statement
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IngestReport {
+    pub registered: Vec<MatchedDeposit>,
+    pub unmatched: Vec<UnmatchedEntry>,
+    pub open_intents: Vec<DepositIntent>, // Intents still waiting for funds
+}
+
+impl IngestReport {
+    /// Total registered against the reserve, in cents
+    pub fn registered_cents(&self) -> u64 {
+        self.registered.iter().map(|m| m.deposit.amount_cents).sum()
+    }
+}
+
+/// Receiver of matched deposits, e.g. a client submitting `register_reserve_deposit`
+pub trait DepositSink {
+    type Error: std::fmt::Display;
+
+    fn register_reserve_deposit(&mut self, deposit: &MatchedDeposit) -> Result<(), Self::Error>;
+}
+
+// References are compared without case and whitespace, as banks reformat them
+fn normalize(reference: &str) -> String {
+    reference
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Match `deposits` against `intents`, register every match with `sink` and
+/// report what is left. Each intent is consumed by at most one deposit.
+pub fn ingest_deposits(
+    deposits: Vec<Deposit>,
+    intents: Vec<DepositIntent>,
+    sink: &mut impl DepositSink,
+) -> IngestReport {
+    let mut report = IngestReport::default();
+    let mut intents: Vec<Option<DepositIntent>> = intents.into_iter().map(Some).collect();
+
+    for deposit in deposits {
+        if deposit.currency != "EUR" {
+            report.unmatched.push(UnmatchedEntry { deposit, reason: UnmatchedReason::WrongCurrency });
+            continue;
+        }
+
+        let remittance = normalize(&deposit.remittance_info);
+        let position = intents.iter().position(|intent| {
+            intent
+                .as_ref()
+                .is_some_and(|intent| remittance.contains(&normalize(&intent.reference)))
+        });
+        let Some(position) = position else {
+            report.unmatched.push(UnmatchedEntry { deposit, reason: UnmatchedReason::UnknownReference });
+            continue;
+        };
+
+        let intent = intents[position].clone().expect("matched intent is open");
+        if intent.amount_cents != deposit.amount_cents {
+            report.unmatched.push(UnmatchedEntry {
+                deposit,
+                reason: UnmatchedReason::AmountMismatch {
+                    reference: intent.reference,
+                    expected_cents: intent.amount_cents,
+                },
+            });
+            continue;
+        }
+
+        let matched = MatchedDeposit { deposit, intent };
+        match sink.register_reserve_deposit(&matched) {
+            Ok(()) => {
+                intents[position] = None;
+                report.registered.push(matched);
+            }
+            Err(error) => report.unmatched.push(UnmatchedEntry {
+                deposit: matched.deposit,
+                reason: UnmatchedReason::RegistrationFailed(error.to_string()),
+            }),
+        }
+    }
+
+    report.open_intents = intents.into_iter().flatten().collect();
+    report
+}
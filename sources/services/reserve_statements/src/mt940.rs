@@ -0,0 +1,120 @@
+use crate::{parse_amount_cents, Deposit, StatementError};
+
+// A `:61:` statement line and the `:86:` information following it
+struct Line {
+    booking_date: String,
+    is_credit: bool,
+    amount: String,
+    customer_reference: String,
+    bank_reference: String,
+    information: String,
+}
+
+/// Extract the credits of an MT940 customer statement. The statement currency is
+/// taken from the opening balance (`:60F:` / `:60M:`).
+pub fn parse_mt940_deposits(statement: &str) -> Result<Vec<Deposit>, StatementError> {
+    let mut currency = String::new();
+    let mut lines: Vec<Line> = Vec::new();
+    let mut in_information = false;
+
+    for raw in statement.lines() {
+        let raw = raw.trim_end();
+        if let Some(balance) = raw.strip_prefix(":60F:").or_else(|| raw.strip_prefix(":60M:")) {
+            // D/C mark, YYMMDD, then the currency code
+            currency = balance.get(7..10).unwrap_or_default().to_string();
+            in_information = false;
+        } else if let Some(line) = raw.strip_prefix(":61:") {
+            lines.push(parse_statement_line(line)?);
+            in_information = false;
+        } else if let Some(information) = raw.strip_prefix(":86:") {
+            if let Some(line) = lines.last_mut() {
+                line.information = information.to_string();
+                in_information = true;
+            }
+        } else if raw.starts_with(':') || raw.starts_with('-') {
+            in_information = false;
+        } else if in_information {
+            // Continuation of a multi-line `:86:` field
+            if let Some(line) = lines.last_mut() {
+                line.information.push_str(raw);
+            }
+        }
+    }
+
+    lines
+        .into_iter()
+        .filter(|line| line.is_credit)
+        .map(|line| {
+            Ok(Deposit {
+                amount_cents: parse_amount_cents(&line.amount)?,
+                currency: currency.clone(),
+                booking_date: line.booking_date,
+                bank_reference: line.bank_reference,
+                remittance_info: if line.information.is_empty() {
+                    line.customer_reference
+                } else {
+                    line.information
+                },
+                debtor_name: None,
+                debtor_iban: None,
+            })
+        })
+        .collect()
+}
+
+// `YYMMDD[MMDD](C|D|RC|RD)[funds code]amount(N|F|S)xxx customer ref[//bank ref]`
+fn parse_statement_line(line: &str) -> Result<Line, StatementError> {
+    let invalid = || StatementError::InvalidStatement(format!("bad :61: line {:?}", line));
+
+    let value_date = line.get(..6).ok_or_else(invalid)?;
+    if !value_date.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let booking_date = format!("20{}-{}-{}", &value_date[..2], &value_date[2..4], &value_date[4..6]);
+
+    let mut rest = &line[6..];
+    // Optional entry date
+    if rest.len() >= 4 && rest[..4].chars().all(|c| c.is_ascii_digit()) {
+        rest = &rest[4..];
+    }
+
+    // Reversals are corrections, never deposits
+    let is_credit = if let Some(r) = rest.strip_prefix("RC").or_else(|| rest.strip_prefix("RD")) {
+        rest = r;
+        false
+    } else if let Some(r) = rest.strip_prefix('C') {
+        rest = r;
+        true
+    } else if let Some(r) = rest.strip_prefix('D') {
+        rest = r;
+        false
+    } else {
+        return Err(invalid());
+    };
+
+    // Optional funds code (third character of the currency code)
+    if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        rest = &rest[1..];
+    }
+
+    let amount_end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == ','))
+        .ok_or_else(invalid)?;
+    let amount = rest[..amount_end].to_string();
+
+    // Transaction type identification: one letter and a three character code
+    let references = rest.get(amount_end + 4..).ok_or_else(invalid)?;
+    let (customer_reference, bank_reference) = match references.split_once("//") {
+        Some((customer, bank)) => (customer.to_string(), bank.to_string()),
+        None => (references.to_string(), String::new()),
+    };
+
+    Ok(Line {
+        booking_date,
+        is_credit,
+        amount,
+        customer_reference,
+        bank_reference,
+        information: String::new(),
+    })
+}
\ No newline at end of file
@@ -0,0 +1,245 @@
+//! Ingestion of the sample statements in `tests/fixtures`: a camt.053.001.08
+//! and an MT940 end-of-day statement of the reserve account, both carrying
+//! booked credits, debits, a pending or reversed entry and a reference the bank
+//! split over two lines.
+
+use anchor_lang::prelude::Pubkey;
+use reserve_statements::*;
+
+const CAMT053: &str = include_str!("fixtures/camt053.xml");
+const MT940: &str = include_str!("fixtures/mt940.sta");
+
+#[derive(Default)]
+struct RecordingSink {
+    registered: Vec<String>,
+    refuse: Option<String>, // Reference the sink fails to register
+}
+
+impl DepositSink for RecordingSink {
+    type Error = String;
+
+    fn register_reserve_deposit(&mut self, deposit: &MatchedDeposit) -> Result<(), String> {
+        if self.refuse.as_deref() == Some(deposit.intent.reference.as_str()) {
+            return Err("simulated RPC failure".to_string());
+        }
+        self.registered.push(deposit.intent.reference.clone());
+        Ok(())
+    }
+}
+
+fn intent(reference: &str, amount_cents: u64) -> DepositIntent {
+    DepositIntent {
+        reference: reference.to_string(),
+        holder: Pubkey::new_unique(),
+        amount_cents,
+    }
+}
+
+fn references(deposits: &[Deposit]) -> Vec<&str> {
+    deposits.iter().map(|d| d.bank_reference.as_str()).collect()
+}
+
+#[test]
+fn camt053_yields_booked_credits_only() {
+    let deposits = parse_camt053_deposits(CAMT053).unwrap();
+
+    // The debit (0003) and the pending credit (0004) are skipped
+    assert_eq!(
+        references(&deposits),
+        [
+            "BANKREF-0001",
+            "BANKREF-0002",
+            "BANKREF-0005",
+            "BANKREF-0006"
+        ]
+    );
+    assert_eq!(
+        deposits[0],
+        Deposit {
+            amount_cents: 250_000,
+            currency: "EUR".to_string(),
+            booking_date: "2026-04-15".to_string(),
+            bank_reference: "BANKREF-0001".to_string(),
+            remittance_info: "MICA-DEP 7F3K Q9ZD".to_string(),
+            debtor_name: Some("Erika Mustermann".to_string()),
+            debtor_iban: Some("DE02120300000000202051".to_string()),
+        }
+    );
+}
+
+#[test]
+fn camt053_reads_date_times_and_short_fractions() {
+    let deposits = parse_camt053_deposits(CAMT053).unwrap();
+
+    assert_eq!(deposits[1].amount_cents, 9_950);
+    assert_eq!(deposits[1].booking_date, "2026-04-15");
+    assert_eq!(
+        deposits[1].debtor_name.as_deref(),
+        Some("ACME Treasury GmbH")
+    );
+    assert_eq!(deposits[1].debtor_iban, None);
+    assert_eq!(deposits[2].currency, "USD");
+}
+
+#[test]
+fn camt053_rejects_malformed_documents() {
+    let truncated = &CAMT053[..CAMT053.find("</Ntry>").unwrap()];
+    assert!(matches!(
+        parse_camt053_deposits(&format!("{truncated}</Amt>")),
+        Err(StatementError::InvalidStatement(_))
+    ));
+
+    let bad_amount = CAMT053.replace(">2500.00<", ">2500.001<");
+    assert_eq!(
+        parse_camt053_deposits(&bad_amount),
+        Err(StatementError::InvalidAmount("2500.001".to_string()))
+    );
+}
+
+#[test]
+fn mt940_yields_credits_without_reversals() {
+    let deposits = parse_mt940_deposits(MT940).unwrap();
+
+    // The debit (0102) and the reversed credit (0103) are skipped
+    assert_eq!(references(&deposits), ["BANKREF-0101", ""]);
+    assert_eq!(
+        deposits[0],
+        Deposit {
+            amount_cents: 250_000,
+            currency: "EUR".to_string(),
+            booking_date: "2026-04-15".to_string(),
+            bank_reference: "BANKREF-0101".to_string(),
+            remittance_info: "166?00SEPA-GUTSCHRIFT?20MICA-DEP 7F3KQ9ZD?32ERIKA MUSTERMANN"
+                .to_string(),
+            debtor_name: None,
+            debtor_iban: None,
+        }
+    );
+}
+
+#[test]
+fn mt940_falls_back_to_the_customer_reference() {
+    let deposits = parse_mt940_deposits(MT940).unwrap();
+
+    // Funds code `R`, one fraction digit, no `:86:` field and no bank reference
+    assert_eq!(deposits[1].amount_cents, 9_950);
+    assert_eq!(deposits[1].currency, "EUR");
+    assert_eq!(deposits[1].remittance_info, "MICA-DEP-DDDD4444");
+}
+
+#[test]
+fn mt940_rejects_malformed_statement_lines() {
+    for line in [
+        ":61:2604X5C1,00NTRF",
+        ":61:260415X1,00NTRF",
+        ":61:260415C1,00",
+    ] {
+        let statement = MT940.replace(":61:260415D750,00NTRFRDM-0042//BANKREF-0102", line);
+        assert!(
+            matches!(
+                parse_mt940_deposits(&statement),
+                Err(StatementError::InvalidStatement(_))
+            ),
+            "{line}"
+        );
+    }
+}
+
+#[test]
+fn camt053_deposits_are_matched_to_intents() {
+    let deposits = parse_camt053_deposits(CAMT053).unwrap();
+    let intents = vec![
+        intent("mica-dep 7f3kq9zd", 250_000),
+        intent("MICA-DEP BBBB2222", 120_000),
+        intent("MICA-DEP CCCC3333", 100_001),
+        intent("MICA-DEP AAAA1111", 1_000_000),
+    ];
+    let mut sink = RecordingSink::default();
+
+    let report = ingest_deposits(deposits, intents, &mut sink);
+
+    assert_eq!(sink.registered, ["mica-dep 7f3kq9zd"]);
+    assert_eq!(report.registered_cents(), 250_000);
+    let reasons: Vec<_> = report
+        .unmatched
+        .iter()
+        .map(|entry| (entry.deposit.bank_reference.as_str(), &entry.reason))
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            ("BANKREF-0002", &UnmatchedReason::UnknownReference),
+            ("BANKREF-0005", &UnmatchedReason::WrongCurrency),
+            (
+                "BANKREF-0006",
+                &UnmatchedReason::AmountMismatch {
+                    reference: "MICA-DEP CCCC3333".to_string(),
+                    expected_cents: 100_001,
+                }
+            ),
+        ]
+    );
+    // The pending deposit's intent stays open, as do the mismatched ones
+    let open: Vec<_> = report
+        .open_intents
+        .iter()
+        .map(|i| i.reference.as_str())
+        .collect();
+    assert_eq!(
+        open,
+        [
+            "MICA-DEP BBBB2222",
+            "MICA-DEP CCCC3333",
+            "MICA-DEP AAAA1111"
+        ]
+    );
+}
+
+#[test]
+fn mt940_deposits_are_matched_to_intents() {
+    let deposits = parse_mt940_deposits(MT940).unwrap();
+    let intents = vec![
+        intent("MICA-DEP 7F3K Q9ZD", 250_000),
+        intent("MICA-DEP-DDDD4444", 9_950),
+    ];
+    let mut sink = RecordingSink {
+        refuse: Some("MICA-DEP-DDDD4444".to_string()),
+        ..Default::default()
+    };
+
+    let report = ingest_deposits(deposits, intents, &mut sink);
+
+    assert_eq!(sink.registered, ["MICA-DEP 7F3K Q9ZD"]);
+    assert_eq!(report.unmatched.len(), 1);
+    assert_eq!(
+        report.unmatched[0].reason,
+        UnmatchedReason::RegistrationFailed("simulated RPC failure".to_string())
+    );
+    // A failed registration leaves the intent open for the next run
+    assert_eq!(report.open_intents.len(), 1);
+    assert_eq!(report.open_intents[0].reference, "MICA-DEP-DDDD4444");
+}
+
+#[test]
+fn each_intent_is_consumed_once() {
+    let mut deposits = parse_mt940_deposits(MT940).unwrap();
+    deposits.truncate(1);
+    deposits.push(Deposit {
+        bank_reference: "BANKREF-DUP".to_string(),
+        ..deposits[0].clone()
+    });
+    let mut sink = RecordingSink::default();
+
+    let report = ingest_deposits(
+        deposits,
+        vec![intent("MICA-DEP 7F3KQ9ZD", 250_000)],
+        &mut sink,
+    );
+
+    assert_eq!(report.registered.len(), 1);
+    assert_eq!(report.unmatched[0].deposit.bank_reference, "BANKREF-DUP");
+    assert_eq!(
+        report.unmatched[0].reason,
+        UnmatchedReason::UnknownReference
+    );
+}
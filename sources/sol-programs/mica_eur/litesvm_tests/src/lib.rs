@@ -1,53 +1,635 @@
-use litesvm::{ProgramTestContext, ProgramTest};
-use anchor_lang::solana_program::{pubkey::Pubkey, system_instruction};
-use solana_sdk::{signature::Keypair, transaction::Transaction};
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_litesvm_setup() {
-        let program_id = Pubkey::new_unique();
-        
-        let mut program_test = ProgramTest::new("mica_eur", program_id, None);
-        let payer = Keypair::new();
-        
-        program_test.add_account(
-            payer.pubkey(),
-            solana_sdk::account::Account {
-                lamports: 1_000_000_000, // 1 SOL
-                data: vec![],
-                owner: solana_sdk::system_program::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
-        
-        let mut context = program_test.start_with_context();
-        println!("LiteSVM context successfully created!");
-        
-        // Send a simple transaction to verify everything is working
-        let recipient = Pubkey::new_unique();
-        let transfer_ix = system_instruction::transfer(
-            &payer.pubkey(),
-            &recipient,
-            100_000, // 0.0001 SOL
-        );
-        
-        let tx = Transaction::new_signed_with_payer(
-            &[transfer_ix],
-            Some(&payer.pubkey()),
-            &[&payer],
-            context.last_blockhash,
-        );
-        
-        context.banks_client.process_transaction(tx).unwrap();
-        println!("Transaction successfully processed!");
-        
-        // Verify balances
-        let recipient_account = context.banks_client.get_account(recipient).unwrap().unwrap();
-        assert_eq!(recipient_account.lamports, 100_000);
-        println!("Recipient balance verification passed!");
-    }
-} 
\ No newline at end of file
+//! LiteSVM harness for end-to-end tests of the MiCA EUR programs.
+//!
+//! Both programs are loaded from `target/deploy`, so build them first with
+//! `anchor build`; a build older than either program's sources is refused rather
+//! than tested. Instructions are built on the Solana 1.18 types the programs
+//! use, mostly by `mica_eur_client`, and converted to LiteSVM's Solana 2.x types
+//! when sent.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{ed25519_program, hash};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, spl_associated_token_account};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::StateWithExtensions,
+    state::{Account as TokenAccountState, AccountState},
+};
+use anchor_spl::token_2022::ID as TOKEN_2022_ID;
+use litesvm::types::TransactionResult;
+use litesvm::LiteSVM;
+use mica_eur::{KycStatus, OperationKind};
+use mica_eur_client::{instructions, pda, KycSigner, MintTokensOptions};
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+/// Country every test user is registered in
+pub const COUNTRY: &str = "DE";
+/// One euro in base units
+pub const EUR: u64 = 10u64.pow(mica_eur::EUR_DECIMALS as u32);
+/// Validity of the test users' verification
+pub const KYC_EXPIRY_DAYS: i64 = 365;
+
+/// Unix time the tests start at
+pub const START_TIME: i64 = 1_767_225_600;
+
+/// A LiteSVM instance with both programs loaded and an EUR mint set up: the KYC
+/// oracle is initialized, `COUNTRY` is allowed and the transfer hook's extra
+/// account metas exist. One key acts as issuer, freeze authority, permanent
+/// delegate and KYC oracle authority, and pays for every transaction.
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub issuer: Keypair,
+    pub mint: Pubkey,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(sdk_key(&mica_eur::ID), program_path("mica_eur"))
+            .expect("mica_eur.so, run `anchor build` first");
+        svm.add_program_from_file(sdk_key(&mica_eur_transfer_hook::ID), program_path("mica_eur_transfer_hook"))
+            .expect("mica_eur_transfer_hook.so, run `anchor build` first");
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = START_TIME;
+        svm.set_sysvar(&clock);
+
+        let issuer = Keypair::new();
+        svm.airdrop(&issuer.pubkey(), 100 * LAMPORTS_PER_SOL).expect("airdrop");
+        let mint = Keypair::new();
+        let mut harness = Self {
+            svm,
+            issuer,
+            mint: key(&mint),
+        };
+
+        let issuer = harness.issuer_key();
+        harness
+            .send(&[initialize_kyc_oracle(&issuer), set_country_policy(&issuer, COUNTRY, true, 0)], &[])
+            .expect("KYC oracle setup");
+        let initialize_mint = instructions::initialize_euro_mint(
+            &issuer,
+            &harness.mint,
+            &issuer,
+            &issuer,
+            "https://example.com/whitepaper.pdf".to_string(),
+            mica_eur::EUR_DECIMALS,
+        );
+        harness.send(&[initialize_mint], &[&mint]).expect("mint setup");
+        let metas = initialize_extra_account_meta_list(&issuer, &harness.mint);
+        harness.send(&[metas], &[]).expect("transfer hook setup");
+        harness
+    }
+
+    pub fn issuer_key(&self) -> Pubkey {
+        key(&self.issuer)
+    }
+
+    /// Send `instructions` paid by the issuer and signed by it and `signers`
+    pub fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> TransactionResult {
+        let instructions: Vec<_> = instructions.iter().map(sdk_instruction).collect();
+        let mut keypairs = vec![&self.issuer];
+        keypairs.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.issuer.pubkey()),
+            &keypairs,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(transaction);
+        // Identical transactions sent later must not be rejected as duplicates
+        self.svm.expire_blockhash();
+        result
+    }
+
+    /// Move the cluster clock `seconds` forward
+    pub fn advance_clock(&mut self, seconds: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp += seconds;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// A new wallet with enough SOL for its own accounts
+    pub fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.svm.airdrop(&wallet.pubkey(), LAMPORTS_PER_SOL).expect("airdrop");
+        wallet
+    }
+
+    /// A wallet registered in `COUNTRY` with its transfer record, verified at
+    /// `verification_level` or left pending if `None`
+    pub fn register_user(&mut self, verification_level: Option<u8>) -> Keypair {
+        let user = self.wallet();
+        let user_key = key(&user);
+        let issuer = self.issuer_key();
+        let register = instructions::register_kyc_user(
+            &issuer,
+            KycSigner::OracleAuthority,
+            &user_key,
+            "50010517".to_string(),
+            // Every wallet has its own bank account
+            user_key.to_bytes(),
+            COUNTRY.to_string(),
+            "test-provider".to_string(),
+        );
+        let transfer_record = initialize_transfer_record(&issuer, &user_key);
+        self.send(&[register, transfer_record], &[]).expect("KYC registration");
+        if let Some(level) = verification_level {
+            let verify = instructions::update_kyc_status(
+                &issuer,
+                KycSigner::OracleAuthority,
+                &user_key,
+                KycStatus::Verified,
+                level,
+                KYC_EXPIRY_DAYS,
+                self.compliance_history(&user_key),
+            );
+            self.send(&[verify], &[]).expect("KYC verification");
+        }
+        user
+    }
+
+    /// Create the associated token account of `owner`, frozen like every new
+    /// account of the mint
+    pub fn token_account(&mut self, owner: &Pubkey) -> Pubkey {
+        let create = spl_associated_token_account::instruction::create_associated_token_account(
+            &self.issuer_key(),
+            owner,
+            &self.mint,
+            &TOKEN_2022_ID,
+        );
+        self.send(&[create], &[]).expect("token account");
+        get_associated_token_address_with_program_id(owner, &self.mint, &TOKEN_2022_ID)
+    }
+
+    /// Mint `amount` to `token_account` of `user` as the issuer
+    pub fn mint_to(&mut self, user: &Pubkey, token_account: &Pubkey, amount: u64, operation_id: u8) -> TransactionResult {
+        let mint = instructions::mint_tokens(
+            &self.issuer_key(),
+            &self.mint,
+            token_account,
+            user,
+            COUNTRY,
+            amount,
+            [operation_id; 16],
+            MintTokensOptions::default(),
+        );
+        self.send(&[mint], &[])
+    }
+
+    /// Move `amount` between two token accounts with `transfer_checked`, signed by
+    /// the source owner, passing the accounts the transfer hook resolves
+    pub fn transfer(&mut self, owner: &Keypair, source: &Pubkey, destination: &Pubkey, amount: u64) -> TransactionResult {
+        let owner_key = key(owner);
+        let mut transfer = spl_token_2022::instruction::transfer_checked(
+            &TOKEN_2022_ID,
+            source,
+            &self.mint,
+            destination,
+            &owner_key,
+            &[],
+            amount,
+            mica_eur::EUR_DECIMALS,
+        )
+        .expect("transfer instruction");
+        transfer.accounts.extend(self.hook_accounts(source, destination));
+        self.send(&[transfer], &[owner])
+    }
+
+    /// Extra accounts of the transfer hook for a transfer from `source` to
+    /// `destination`, in the order of its extra account meta list, followed by the
+    /// hook program and the list itself
+    pub fn hook_accounts(&self, source: &Pubkey, destination: &Pubkey) -> Vec<AccountMeta> {
+        let sender = self.token_state(source).owner;
+        let recipient = self.token_state(destination).owner;
+        instructions::transfer_hook_accounts(&self.mint, source, &sender, destination, &recipient)
+    }
+
+    /// Compliance history of `user`, `None` until it is opened
+    pub fn compliance_history(&self, user: &Pubkey) -> Option<Pubkey> {
+        let compliance_history = pda::compliance_history(user);
+        self.svm.get_account(&sdk_key(&compliance_history)).map(|_| compliance_history)
+    }
+
+    /// Freeze `token_account` by court order; a `frozen_amount` of 0 freezes it whole
+    pub fn freeze(&mut self, token_account: &Pubkey, frozen_amount: u64) -> TransactionResult {
+        let freeze = instructions::freeze_account(
+            &self.issuer_key(),
+            &self.mint,
+            token_account,
+            mica_eur::freeze_reasons::COURT_ORDER,
+            "AG Frankfurt 4 Gs 17/26".to_string(),
+            frozen_amount,
+            0,
+            None,
+            self.compliance_history(&self.token_state(token_account).owner),
+        );
+        self.send(&[freeze], &[])
+    }
+
+    /// Thaw `token_account`, lifting its freeze record if it has one
+    pub fn thaw(&mut self, token_account: &Pubkey) -> TransactionResult {
+        let freeze_record = pda::freeze_record(token_account);
+        let freeze_record = self.svm.get_account(&sdk_key(&freeze_record)).map(|_| freeze_record);
+        let compliance_history = self.compliance_history(&self.token_state(token_account).owner);
+        let thaw = instructions::thaw_account(
+            &self.issuer_key(),
+            &self.mint,
+            token_account,
+            freeze_record,
+            None,
+            compliance_history,
+        );
+        self.send(&[thaw], &[])
+    }
+
+    /// Address of the mint's seizure vault PDA
+    pub fn seizure_vault(&self) -> Pubkey {
+        Pubkey::find_program_address(&[mica_eur::SEIZURE_VAULT_SEED, self.mint.as_ref()], &mica_eur::ID).0
+    }
+
+    /// Set up the escrow vault seized funds are held in, returning its token account
+    pub fn configure_seizure_vault(&mut self) -> Pubkey {
+        let seizure_vault = self.seizure_vault();
+        let vault = self.token_account(&seizure_vault);
+        let accounts = mica_eur::accounts::ConfigureSeizureVault {
+            issuer: self.issuer_key(),
+            mint_info: pda::mint_info(&self.mint),
+            mint: self.mint,
+            seizure_vault,
+            vault,
+            token_program: TOKEN_2022_ID,
+            system_program: system_program::ID,
+        };
+        let configure = instruction(mica_eur::ID, accounts, mica_eur::instruction::ConfigureSeizureVault {});
+        self.send(&[configure], &[]).expect("seizure vault");
+        vault
+    }
+
+    /// Seize `amount` from `from_account` into the seizure vault as the permanent
+    /// Seize `amount` from `from_account` into `to_account` as the permanent delegate
+    pub fn seize(&mut self, from_account: &Pubkey, to_account: &Pubkey, amount: u64, operation_id: u8) -> TransactionResult {
+        let mut seize = instructions::seize_tokens(
+            &self.issuer_key(),
+            &self.mint,
+            from_account,
+            to_account,
+            amount,
+            [operation_id; 16],
+            None,
+            self.compliance_history(&self.token_state(from_account).owner),
+        );
+        seize.accounts.extend(self.hook_accounts(from_account, to_account));
+        self.send(&[seize], &[])
+    }
+
+    /// delegate, passing the accounts the transfer hook resolves
+    pub fn seize_to_vault(&mut self, from_account: &Pubkey, amount: u64, operation_id: u8) -> TransactionResult {
+        let seizure_vault_address = self.seizure_vault();
+        let seizure_vault = self.account::<mica_eur::SeizureVault>(&seizure_vault_address);
+        let seizure_record = Pubkey::find_program_address(
+            &[
+                mica_eur::SEIZURE_RECORD_SEED,
+                self.mint.as_ref(),
+                &seizure_vault.seizure_count.to_le_bytes(),
+            ],
+            &mica_eur::ID,
+        )
+        .0;
+        let accounts = mica_eur::accounts::SeizeToVault {
+            permanent_delegate: self.issuer_key(),
+            mint_info: pda::mint_info(&self.mint),
+            seizure_authority: pda::seizure_authority(&self.mint),
+            mint: self.mint,
+            seizure_vault: seizure_vault_address,
+            vault: seizure_vault.vault,
+            from_account: *from_account,
+            seizure_record,
+            operation_receipt: pda::operation_receipt(&self.mint, OperationKind::Seizure, &self.issuer_key(), &[operation_id; 16]),
+            token_program: TOKEN_2022_ID,
+            system_program: system_program::ID,
+            invocation_guard: pda::invocation_guard(&self.mint),
+            role_assignment: None,
+        };
+        let data = mica_eur::instruction::SeizeToVault {
+            amount,
+            operation_id: [operation_id; 16],
+            case_hash: [0x5e; 32],
+        };
+        let mut seize = instruction(mica_eur::ID, accounts, data);
+        seize.accounts.extend(self.hook_accounts(from_account, &seizure_vault.vault));
+        self.send(&[seize], &[])
+    }
+
+    /// Set up the treasury vault primary issuance goes through, returning its token account
+    pub fn configure_treasury_vault(&mut self) -> Pubkey {
+        let vault = self.token_account(&pda::treasury_vault(&self.mint));
+        let configure = instructions::configure_treasury_vault(&self.issuer_key(), &self.mint, &vault);
+        self.send(&[configure], &[]).expect("treasury vault");
+        vault
+    }
+
+    /// Blacklist `user` for sanctions as a global AML authority held by the
+    /// issuer, registering the authority on first use
+    pub fn blacklist(&mut self, user: &Pubkey) {
+        let issuer = self.issuer_key();
+        if self.svm.get_account(&sdk_key(&pda::aml_authority(&issuer))).is_none() {
+            let register = register_aml_authority(&issuer, &self.mint);
+            self.send(&[register], &[]).expect("AML authority");
+        }
+        let kyc_user = pda::kyc_user(user);
+        let kyc_user = self.svm.get_account(&sdk_key(&kyc_user)).map(|_| kyc_user);
+        let entry = create_blacklist_entry(&issuer, user, kyc_user, self.compliance_history(user));
+        self.send(&[entry], &[]).expect("blacklist entry");
+    }
+
+    /// A fully verified Pyth `PriceUpdateV2` account for `feed_id`, priced at
+    /// `price * 10^exponent` and published at `publish_time`
+    pub fn pyth_price_update(&mut self, feed_id: [u8; 32], price: i64, exponent: i32, publish_time: i64) -> Pubkey {
+        let mut data = hash::hash(b"account:PriceUpdateV2").to_bytes()[..8].to_vec();
+        data.extend_from_slice(self.issuer_key().as_ref()); // write authority
+        data.push(1); // VerificationLevel::Full
+        data.extend_from_slice(&feed_id);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes()); // confidence
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes()); // previous publish time
+        data.extend_from_slice(&price.to_le_bytes()); // EMA price
+        data.extend_from_slice(&0u64.to_le_bytes()); // EMA confidence
+        data.extend_from_slice(&0u64.to_le_bytes()); // posted slot
+
+        let address = Keypair::new().pubkey();
+        let account = solana_sdk::account::Account {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: sdk_key(&mica_eur::PYTH_RECEIVER_PROGRAM_ID),
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm.set_account(address, account).expect("price update");
+        Pubkey::new_from_array(address.to_bytes())
+    }
+
+    /// Overwrite the extra account meta list of the mint with `metas`, as an
+    /// earlier version of the transfer hook would have written it
+    pub fn set_extra_account_metas(&mut self, metas: &[ExtraAccountMeta]) {
+        let address = sdk_key(&pda::extra_account_meta_list(&self.mint));
+        let mut account = self.svm.get_account(&address).expect("extra account meta list exists");
+        account.data = vec![0; ExtraAccountMetaList::size_of(metas.len()).expect("list size")];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut account.data, metas).expect("list data");
+        self.svm.set_account(address, account).expect("extra account meta list");
+    }
+
+    /// Deserialize the program account at `address`
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self.svm.get_account(&sdk_key(address)).expect("account exists");
+        T::try_deserialize(&mut &account.data[..]).expect("account data")
+    }
+
+    /// Base state of the token account at `address`
+    pub fn token_state(&self, address: &Pubkey) -> TokenAccountState {
+        let account = self.svm.get_account(&sdk_key(address)).expect("token account exists");
+        StateWithExtensions::<TokenAccountState>::unpack(&account.data).expect("token account data").base
+    }
+
+    pub fn balance(&self, token_account: &Pubkey) -> u64 {
+        self.token_state(token_account).amount
+    }
+
+    pub fn is_frozen(&self, token_account: &Pubkey) -> bool {
+        self.token_state(token_account).state == AccountState::Frozen
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Assert that a transaction failed with the custom error `code` of a program or
+/// of Token-2022
+pub fn assert_error(result: TransactionResult, code: u32) {
+    match result {
+        Ok(_) => panic!("transaction succeeded, expected error {}", code),
+        Err(failed) => match failed.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+                assert_eq!(actual, code, "logs: {:#?}", failed.meta.logs)
+            }
+            other => panic!("expected error {}, got {:?}, logs: {:#?}", code, other, failed.meta.logs),
+        },
+    }
+}
+
+/// Ed25519 program instruction verifying `signer`'s signature over `message`, with
+/// every input inline as `verify_ed25519_instruction` expects
+pub fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+    const PUBKEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = PUBKEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+    let signature = signer.sign_message(message);
+
+    let mut data = vec![1, 0];
+    for value in [
+        SIGNATURE_OFFSET,
+        u16::MAX,
+        PUBKEY_OFFSET,
+        u16::MAX,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+    Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+}
+
+/// Code of a program error
+pub fn error_code(error: mica_eur::MicaEurError) -> u32 {
+    error.into()
+}
+
+/// The 1.18 key of a LiteSVM keypair
+pub fn key(keypair: &Keypair) -> Pubkey {
+    Pubkey::new_from_array(keypair.pubkey().to_bytes())
+}
+
+/// The LiteSVM key of a 1.18 key
+pub fn sdk_key(key: &Pubkey) -> solana_sdk::pubkey::Pubkey {
+    solana_sdk::pubkey::Pubkey::new_from_array(key.to_bytes())
+}
+
+/// The LiteSVM instruction of a 1.18 instruction
+pub fn sdk_instruction(instruction: &Instruction) -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: sdk_key(&instruction.program_id),
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|meta| solana_sdk::instruction::AccountMeta {
+                pubkey: sdk_key(&meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: instruction.data.clone(),
+    }
+}
+
+// Path of a program built by `anchor build`, refusing a build older than the
+// sources of either program, which would test code no longer in the tree
+fn program_path(name: &str) -> PathBuf {
+    let programs = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let path = programs.join("../../target/deploy").join(format!("{}.so", name));
+    let built = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or_else(|_| panic!("{name}.so, run `anchor build` first"));
+    for sources in ["mica_eur/src", "mica_eur_transfer_hook/src"] {
+        if let Some(changed) = last_modified(&programs.join(sources)) {
+            assert!(built >= changed, "{name}.so is older than {sources}, run `anchor build` again");
+        }
+    }
+    path
+}
+
+// Latest modification time of the files under `dir`
+fn last_modified(dir: &Path) -> Option<SystemTime> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if metadata.is_dir() {
+                last_modified(&entry.path())
+            } else {
+                metadata.modified().ok()
+            }
+        })
+        .max()
+}
+
+fn instruction(program_id: Pubkey, accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn initialize_kyc_oracle(authority: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::InitializeKycOracle {
+        authority: *authority,
+        kyc_oracle_state: pda::kyc_oracle_state(),
+        system_program: system_program::ID,
+        rent: anchor_lang::solana_program::sysvar::rent::ID,
+    };
+    instruction(mica_eur::ID, accounts, mica_eur::instruction::InitializeKycOracle {})
+}
+
+fn set_country_policy(authority: &Pubkey, country_code: &str, allowed: bool, max_transaction_amount: u64) -> Instruction {
+    let accounts = mica_eur::accounts::SetCountryPolicy {
+        authority: *authority,
+        kyc_oracle_state: pda::kyc_oracle_state(),
+        country_policy: pda::country_policy(country_code),
+        system_program: system_program::ID,
+    };
+    let data = mica_eur::instruction::SetCountryPolicy {
+        country_code: country_code.to_string(),
+        allowed,
+        max_transaction_amount,
+    };
+    instruction(mica_eur::ID, accounts, data)
+}
+
+fn initialize_extra_account_meta_list(issuer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = mica_eur_transfer_hook::accounts::InitializeExtraAccountMetaList {
+        issuer: *issuer,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        extra_account_meta_list: pda::extra_account_meta_list(mint),
+        system_program: system_program::ID,
+    };
+    let data = mica_eur_transfer_hook::instruction::InitializeExtraAccountMetaList {};
+    instruction(mica_eur_transfer_hook::ID, accounts, data)
+}
+
+/// Rewrite the transfer hook's extra account meta list of `mint` with the
+/// accounts the current hook needs, signed by `authority`
+pub fn update_extra_account_meta_list(authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = mica_eur_transfer_hook::accounts::UpdateExtraAccountMetaList {
+        issuer: *authority,
+        mint_info: pda::mint_info(mint),
+        mint: *mint,
+        extra_account_meta_list: pda::extra_account_meta_list(mint),
+        system_program: system_program::ID,
+    };
+    let data = mica_eur_transfer_hook::instruction::UpdateExtraAccountMetaList {};
+    instruction(mica_eur_transfer_hook::ID, accounts, data)
+}
+
+fn register_aml_authority(issuer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = mica_eur::accounts::RegisterAmlAuthority {
+        authority: *issuer,
+        aml_authority: pda::aml_authority(issuer),
+        system_program: system_program::ID,
+        approver: *issuer,
+        mint_info: pda::mint_info(mint),
+        regulator_registry: None,
+    };
+    let data = mica_eur::instruction::RegisterAmlAuthority {
+        authority_id: "test-fiu".to_string(),
+        powers: mica_eur::aml_powers::ALL,
+        jurisdiction: String::new(),
+        global: true,
+    };
+    instruction(mica_eur::ID, accounts, data)
+}
+
+fn create_blacklist_entry(
+    authority: &Pubkey,
+    user: &Pubkey,
+    kyc_user: Option<Pubkey>,
+    compliance_history: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mica_eur::accounts::CreateBlacklistEntry {
+        authority: *authority,
+        aml_authority: pda::aml_authority(authority),
+        user: *user,
+        blacklist_entry: pda::blacklist_entry(user),
+        system_program: system_program::ID,
+        kyc_user,
+        compliance_history,
+    };
+    let data = mica_eur::instruction::CreateBlacklistEntry {
+        reason: mica_eur::BlacklistReason::Sanctions,
+        legal_reference: String::new(),
+        expiry_time: 0,
+        review_time: 0,
+    };
+    instruction(mica_eur::ID, accounts, data)
+}
+
+fn initialize_transfer_record(payer: &Pubkey, user: &Pubkey) -> Instruction {
+    let accounts = mica_eur_transfer_hook::accounts::InitializeTransferRecord {
+        payer: *payer,
+        user: *user,
+        transfer_record: pda::transfer_record(user),
+        system_program: system_program::ID,
+    };
+    let data = mica_eur_transfer_hook::instruction::InitializeTransferRecord {};
+    instruction(mica_eur_transfer_hook::ID, accounts, data)
+}
@@ -0,0 +1,1588 @@
+//! End-to-end lifecycle of the EUR mint on LiteSVM: KYC onboarding, minting,
+//! hooked transfers between verified and unverified users, freezes, seizure and
+//! reserve proofs, with the errors and balances expected at each step.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    error::TokenError,
+    extension::{permanent_delegate::PermanentDelegate, BaseStateWithExtensions, StateWithExtensions},
+};
+use mica_eur::{
+    screening_attestation_message, transfer_authorization_message, AccountClass, BondPriceFeed,
+    Escrow, EscrowStatus, MicaEurError, OperationKind, PaymentRequest, PaymentRequestStatus,
+    RegulatorAccess, Relayer, RelayerStatus, ReserveAsset, ReserveAssetCategory, ReserveComposition,
+    ReserveProofEpoch, StandingOrder, StandingOrderStatus,
+};
+use mica_eur_client::{instructions, pda, KycSigner, ReserveProofOptions};
+use mica_eur_litesvm_tests::*;
+use solana_sdk::signature::Keypair;
+
+// A verified user holding `amount` in a token account of their own
+fn funded_user(harness: &mut Harness, amount: u64, operation_id: u8) -> (Keypair, Pubkey) {
+    let user = harness.register_user(Some(2));
+    let token_account = harness.token_account(&key(&user));
+    harness.mint_to(&key(&user), &token_account, amount, operation_id).expect("mint");
+    (user, token_account)
+}
+
+// A token account of `owner` thawed by the freeze authority, ready to receive
+fn open_account(harness: &mut Harness, owner: &Pubkey) -> Pubkey {
+    let token_account = harness.token_account(owner);
+    harness.thaw(&token_account).expect("thaw");
+    token_account
+}
+
+// Record a sanctions screening of `user`, required from verification level 3
+fn screen(harness: &mut Harness, user: &Pubkey) {
+    let issuer = harness.issuer_key();
+    let screener = Keypair::new();
+    let register = instructions::register_screening_provider(&issuer, &key(&screener), "test-screener".to_string());
+    harness.send(&[register], &[]).expect("screening provider");
+    let message = screening_attestation_message(user, START_TIME, &[1; 32]);
+    let attest = instructions::submit_screening_attestation(&issuer, &key(&screener), user, START_TIME, [1; 32]);
+    harness.send(&[ed25519_instruction(&screener, &message), attest], &[]).expect("screening");
+}
+
+// Pass the program ID, which Anchor reads as an absent optional account, in place
+// of `account`: the bypass the seeds constraints on blacklist entries and freeze
+// records keep out
+fn leave_out(instruction: &mut Instruction, account: &Pubkey) {
+    instruction.accounts.iter_mut().filter(|meta| meta.pubkey == *account).for_each(|meta| meta.pubkey = mica_eur::ID);
+}
+
+#[test]
+fn processes_transactions_with_the_programs_loaded() {
+    let mut harness = Harness::new();
+    let recipient = key(&Keypair::new());
+    let transfer = system_instruction::transfer(&harness.issuer_key(), &recipient, 100_000);
+    harness.send(&[transfer], &[]).expect("SOL transfer");
+
+    let account = harness.svm.get_account(&sdk_key(&recipient)).expect("recipient");
+    assert_eq!(account.lamports, 100_000);
+}
+
+#[test]
+fn initializes_the_mint_with_its_authorities() {
+    let harness = Harness::new();
+    let mint_info: mica_eur::MintInfo = harness.account(&pda::mint_info(&harness.mint));
+
+    assert_eq!(mint_info.mint, harness.mint);
+    assert_eq!(mint_info.issuer, harness.issuer_key());
+    assert_eq!(mint_info.freeze_authority, harness.issuer_key());
+    assert_eq!(mint_info.permanent_delegate, harness.issuer_key());
+    assert_eq!(mint_info.decimals, mica_eur::EUR_DECIMALS);
+    assert!(mint_info.is_active);
+    assert!(!mint_info.paused);
+}
+
+#[test]
+fn holders_open_frozen_associated_accounts() {
+    let mut harness = Harness::new();
+    let holder = harness.register_user(Some(2));
+    let create = instructions::create_token_account(&key(&holder), &harness.mint);
+    let token_account = create.accounts[1].pubkey;
+    harness.send(&[create.clone()], &[&holder]).expect("token account");
+
+    assert!(harness.is_frozen(&token_account));
+    let record: mica_eur::HolderRecord = harness.account(&pda::holder_record(&token_account));
+    assert_eq!(record.mint, harness.mint);
+    assert_eq!(record.owner, key(&holder));
+    assert_eq!(record.token_account, token_account);
+
+    // The record is only created once
+    assert!(harness.send(&[create], &[&holder]).is_err());
+
+    // And the first mint thaws the account
+    harness.mint_to(&key(&holder), &token_account, 10 * EUR, 1).expect("mint");
+    assert!(!harness.is_frozen(&token_account));
+}
+
+#[test]
+fn verified_holders_activate_their_accounts_without_the_freeze_authority() {
+    let mut harness = Harness::new();
+    let activate = |harness: &mut Harness, caller: &Keypair, owner: &Pubkey, token_account: &Pubkey| {
+        let instruction = instructions::activate_token_account(&key(caller), &harness.mint, token_account, owner);
+        harness.send(&[instruction], &[caller])
+    };
+
+    // Pending owners stay frozen
+    let pending = harness.register_user(None);
+    let pending_account = harness.token_account(&key(&pending));
+    let result = activate(&mut harness, &pending, &key(&pending), &pending_account);
+    assert_error(result, error_code(MicaEurError::UserNotVerified));
+    assert!(harness.is_frozen(&pending_account));
+
+    // Anyone may activate the account of a verified owner, once
+    let verified = harness.register_user(Some(1));
+    let verified_account = harness.token_account(&key(&verified));
+    let stranger = harness.wallet();
+    activate(&mut harness, &stranger, &key(&verified), &verified_account).expect("activation");
+    assert!(!harness.is_frozen(&verified_account));
+    let result = activate(&mut harness, &stranger, &key(&verified), &verified_account);
+    assert_error(result, error_code(MicaEurError::AccountNotFrozen));
+
+    // A recorded freeze stays frozen, also when the caller leaves the record out
+    harness.freeze(&verified_account, 0).expect("freeze");
+    let result = activate(&mut harness, &stranger, &key(&verified), &verified_account);
+    assert_error(result, error_code(MicaEurError::AccountFrozenByRecord));
+    let mut instruction =
+        instructions::activate_token_account(&key(&stranger), &harness.mint, &verified_account, &key(&verified));
+    let record = pda::freeze_record(&verified_account);
+    leave_out(&mut instruction, &record);
+    let result = harness.send(&[instruction], &[&stranger]);
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    assert!(harness.is_frozen(&verified_account));
+
+    // As does the account of a blacklisted owner
+    let listed = harness.register_user(Some(1));
+    let listed_account = harness.token_account(&key(&listed));
+    harness.blacklist(&key(&listed));
+    let result = activate(&mut harness, &stranger, &key(&listed), &listed_account);
+    assert_error(result, error_code(MicaEurError::UserBlacklisted));
+    let mut instruction =
+        instructions::activate_token_account(&key(&stranger), &harness.mint, &listed_account, &key(&listed));
+    leave_out(&mut instruction, &pda::blacklist_entry(&key(&listed)));
+    let result = harness.send(&[instruction], &[&stranger]);
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    assert!(harness.is_frozen(&listed_account));
+}
+
+#[test]
+fn expired_holders_are_frozen_by_anyone() {
+    let mut harness = Harness::new();
+    let holder = harness.register_user(Some(2));
+    let token_account = harness.token_account(&key(&holder));
+    harness.thaw(&token_account).expect("thaw");
+    let stranger = harness.wallet();
+    let enforce = instructions::enforce_kyc_expiry(&key(&stranger), &harness.mint, &token_account, &key(&holder), None);
+
+    // Verification still valid
+    assert_error(harness.send(&[enforce.clone()], &[&stranger]), error_code(MicaEurError::KycNotExpired));
+
+    harness.advance_clock(KYC_EXPIRY_DAYS * 86400 + 1);
+    harness.send(&[enforce.clone()], &[&stranger]).expect("enforcement");
+    assert!(harness.is_frozen(&token_account));
+    assert_error(harness.send(&[enforce], &[&stranger]), error_code(MicaEurError::AccountAlreadyFrozen));
+}
+
+#[test]
+fn holder_count_follows_opened_and_closed_accounts() {
+    let mut harness = Harness::new();
+    let holder_count = |harness: &Harness| harness.account::<mica_eur::MintInfo>(&pda::mint_info(&harness.mint)).holder_count;
+    let alice = harness.register_user(Some(2));
+    let bob = harness.register_user(Some(2));
+    let mut accounts = Vec::new();
+    for holder in [&alice, &bob] {
+        let create = instructions::create_token_account(&key(holder), &harness.mint);
+        accounts.push(create.accounts[1].pubkey);
+        harness.send(&[create], &[holder]).expect("token account");
+    }
+    assert_eq!(holder_count(&harness), 2);
+
+    // A frozen account cannot be closed
+    let bob_account = accounts[1];
+    let close = instructions::close_token_account(&key(&bob), &harness.mint, &bob_account);
+    assert_error(harness.send(&[close.clone()], &[&bob]), TokenError::AccountFrozen as u32);
+
+    harness.thaw(&bob_account).expect("thaw");
+    harness.send(&[close], &[&bob]).expect("close");
+    assert_eq!(holder_count(&harness), 1);
+    assert!(harness.svm.get_account(&sdk_key(&pda::holder_record(&bob_account))).is_none());
+}
+
+#[test]
+fn mints_only_to_verified_users() {
+    let mut harness = Harness::new();
+
+    // Registered but still pending
+    let pending = harness.register_user(None);
+    let pending_account = harness.token_account(&key(&pending));
+    let result = harness.mint_to(&key(&pending), &pending_account, 100 * EUR, 1);
+    assert_error(result, error_code(MicaEurError::UserNotVerified));
+
+    // Verified below the minting level
+    let basic = harness.register_user(Some(1));
+    let basic_account = harness.token_account(&key(&basic));
+    let result = harness.mint_to(&key(&basic), &basic_account, 100 * EUR, 2);
+    assert_error(result, error_code(MicaEurError::InsufficientVerificationLevel));
+
+    assert_eq!(harness.balance(&pending_account), 0);
+    assert_eq!(harness.balance(&basic_account), 0);
+    assert!(harness.is_frozen(&pending_account));
+
+    // New accounts start frozen and are thawed by the first mint
+    let verified = harness.register_user(Some(2));
+    let verified_account = harness.token_account(&key(&verified));
+    assert!(harness.is_frozen(&verified_account));
+    harness.mint_to(&key(&verified), &verified_account, 100 * EUR, 3).expect("mint");
+    assert_eq!(harness.balance(&verified_account), 100 * EUR);
+    assert!(!harness.is_frozen(&verified_account));
+
+    // Later mints add to the balance; an operation ID is only used once
+    harness.mint_to(&key(&verified), &verified_account, 50 * EUR, 4).expect("second mint");
+    assert_eq!(harness.balance(&verified_account), 150 * EUR);
+    assert!(harness.mint_to(&key(&verified), &verified_account, 50 * EUR, 4).is_err());
+    assert_eq!(harness.balance(&verified_account), 150 * EUR);
+}
+
+#[test]
+fn blacklisted_users_are_not_minted_to_and_do_not_redeem() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = harness.token_account(&key(&bob));
+    harness.blacklist(&key(&alice));
+    harness.blacklist(&key(&bob));
+
+    let result = harness.mint_to(&key(&bob), &bob_account, 100 * EUR, 2);
+    assert_error(result, error_code(MicaEurError::UserBlacklisted));
+    let mint = harness.mint;
+    let redeem = |operation_id: u8| {
+        instructions::burn_tokens(
+            &key(&alice),
+            &mint,
+            &alice_account,
+            COUNTRY,
+            40 * EUR,
+            [operation_id; 16],
+            instructions::BurnTokensOptions::default(),
+        )
+    };
+    let result = harness.send(&[redeem(3)], &[&alice]);
+    assert_error(result, error_code(MicaEurError::UserBlacklisted));
+
+    // Leaving the entry out, here as the program ID Anchor reads as an absent
+    // optional account, fails the seeds check instead of skipping the blacklist
+    let mut mint_to = instructions::mint_tokens(
+        &harness.issuer_key(),
+        &mint,
+        &bob_account,
+        &key(&bob),
+        COUNTRY,
+        100 * EUR,
+        [4; 16],
+        instructions::MintTokensOptions::default(),
+    );
+    let entry = pda::blacklist_entry(&key(&bob));
+    leave_out(&mut mint_to, &entry);
+    let result = harness.send(&[mint_to], &[]);
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    let mut redeem = redeem(5);
+    let entry = pda::blacklist_entry(&key(&alice));
+    leave_out(&mut redeem, &entry);
+    let result = harness.send(&[redeem], &[&alice]);
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+
+    assert_eq!(harness.balance(&alice_account), 100 * EUR);
+    assert_eq!(harness.balance(&bob_account), 0);
+}
+
+#[test]
+fn blacklisted_users_neither_transfer_nor_receive_distributions() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let (bob, bob_account) = funded_user(&mut harness, 100 * EUR, 2);
+    harness.blacklist(&key(&bob));
+
+    // The transfer hook refuses a blacklisted party on either side
+    let result = harness.transfer(&alice, &alice_account, &bob_account, 10 * EUR);
+    assert_error(result, error_code(MicaEurError::UserBlacklisted));
+    let result = harness.transfer(&bob, &bob_account, &alice_account, 10 * EUR);
+    assert_error(result, error_code(MicaEurError::UserBlacklisted));
+
+    // Resolving the hook's accounts with the entry left out fails as well
+    let mut transfer = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID,
+        &bob_account,
+        &harness.mint,
+        &alice_account,
+        &key(&bob),
+        &[],
+        10 * EUR,
+        mica_eur::EUR_DECIMALS,
+    )
+    .unwrap();
+    transfer.accounts.extend(harness.hook_accounts(&bob_account, &alice_account));
+    leave_out(&mut transfer, &pda::blacklist_entry(&key(&bob)));
+    assert!(harness.send(&[transfer], &[&bob]).is_err());
+
+    // Treasury distributions check the recipient like mints do
+    let vault = harness.configure_treasury_vault();
+    let issuer = harness.issuer_key();
+    let mint_to_treasury = instructions::mint_to_treasury(&issuer, &harness.mint, &vault, 50 * EUR, [3; 16], None, None);
+    harness.send(&[mint_to_treasury], &[]).expect("treasury mint");
+    let distribute = |harness: &Harness, operation_id: u8| {
+        let mut distribute = instructions::distribute_from_treasury(
+            &issuer,
+            &harness.mint,
+            &vault,
+            &bob_account,
+            &key(&bob),
+            COUNTRY,
+            20 * EUR,
+            [operation_id; 16],
+            instructions::DistributeOptions::default(),
+        );
+        distribute.accounts.extend(harness.hook_accounts(&vault, &bob_account));
+        distribute
+    };
+    let result = harness.send(&[distribute(&harness, 4)], &[]);
+    assert_error(result, error_code(MicaEurError::UserBlacklisted));
+    let mut bypass = distribute(&harness, 5);
+    leave_out(&mut bypass, &pda::blacklist_entry(&key(&bob)));
+    assert_error(harness.send(&[bypass], &[]), anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+
+    assert_eq!(harness.balance(&alice_account), 100 * EUR);
+    assert_eq!(harness.balance(&bob_account), 100 * EUR);
+    assert_eq!(harness.balance(&vault), 50 * EUR);
+}
+
+#[test]
+fn recorded_freezes_hold_against_treasury_distributions() {
+    let mut harness = Harness::new();
+    let alice = harness.register_user(Some(2));
+    let alice_account = open_account(&mut harness, &key(&alice));
+    harness.freeze(&alice_account, 0).expect("freeze");
+    let vault = harness.configure_treasury_vault();
+    let issuer = harness.issuer_key();
+    let mint_to_treasury = instructions::mint_to_treasury(&issuer, &harness.mint, &vault, 50 * EUR, [1; 16], None, None);
+    harness.send(&[mint_to_treasury], &[]).expect("treasury mint");
+    let distribute = |harness: &Harness, operation_id: u8| {
+        let mut distribute = instructions::distribute_from_treasury(
+            &issuer,
+            &harness.mint,
+            &vault,
+            &alice_account,
+            &key(&alice),
+            COUNTRY,
+            20 * EUR,
+            [operation_id; 16],
+            instructions::DistributeOptions::default(),
+        );
+        distribute.accounts.extend(harness.hook_accounts(&vault, &alice_account));
+        distribute
+    };
+
+    // The distribution does not thaw the account, with or without the record
+    let result = harness.send(&[distribute(&harness, 2)], &[]);
+    assert_error(result, error_code(MicaEurError::AccountFrozenByRecord));
+    let mut bypass = distribute(&harness, 3);
+    leave_out(&mut bypass, &pda::freeze_record(&alice_account));
+    assert_error(harness.send(&[bypass], &[]), anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+
+    assert!(harness.is_frozen(&alice_account));
+    assert_eq!(harness.balance(&alice_account), 0);
+    assert_eq!(harness.balance(&vault), 50 * EUR);
+}
+
+#[test]
+fn deactivated_mints_still_redeem() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let deactivate = instructions::deactivate_mint(&harness.issuer_key(), &harness.mint);
+    harness.send(&[deactivate.clone()], &[]).expect("deactivation");
+    assert_error(harness.send(&[deactivate], &[]), error_code(MicaEurError::MintInactive));
+
+    // No new money during wind-down, but holders redeem
+    let result = harness.mint_to(&key(&alice), &alice_account, 10 * EUR, 2);
+    assert_error(result, error_code(MicaEurError::MintInactive));
+    let redeem = instructions::burn_tokens(
+        &key(&alice),
+        &harness.mint,
+        &alice_account,
+        COUNTRY,
+        40 * EUR,
+        [3; 16],
+        instructions::BurnTokensOptions::default(),
+    );
+    harness.send(&[redeem], &[&alice]).expect("redemption");
+    assert_eq!(harness.balance(&alice_account), 60 * EUR);
+
+    let reactivate = instructions::reactivate_mint(&harness.issuer_key(), &harness.mint);
+    harness.send(&[reactivate.clone()], &[]).expect("reactivation");
+    assert_error(harness.send(&[reactivate], &[]), error_code(MicaEurError::MintAlreadyActive));
+    harness.mint_to(&key(&alice), &alice_account, 10 * EUR, 4).expect("mint after reactivation");
+    assert_eq!(harness.balance(&alice_account), 70 * EUR);
+}
+
+#[test]
+fn mints_and_redemptions_are_counted_on_the_mint_info() {
+    let mut harness = Harness::new();
+    let totals = |harness: &Harness| {
+        let mint_info: mica_eur::MintInfo = harness.account(&pda::mint_info(&harness.mint));
+        (mint_info.total_minted, mint_info.total_burned)
+    };
+    let (minted, burned) = totals(&harness);
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let redeem = instructions::burn_tokens(
+        &key(&alice),
+        &harness.mint,
+        &alice_account,
+        COUNTRY,
+        40 * EUR,
+        [2; 16],
+        instructions::BurnTokensOptions::default(),
+    );
+    harness.send(&[redeem], &[&alice]).expect("redemption");
+    assert_eq!(totals(&harness), (minted + 100 * EUR as u128, burned + 40 * EUR as u128));
+}
+
+#[test]
+fn only_the_program_holds_the_mint_authority() {
+    let mut harness = Harness::new();
+    let (_, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let issuer = harness.issuer_key();
+
+    // The issuer's keypair cannot mint around the program's checks
+    let direct_mint = spl_token_2022::instruction::mint_to_checked(
+        &spl_token_2022::ID,
+        &harness.mint,
+        &alice_account,
+        &issuer,
+        &[],
+        10 * EUR,
+        mica_eur::EUR_DECIMALS,
+    )
+    .expect("mint instruction");
+    assert_error(harness.send(&[direct_mint], &[]), TokenError::OwnerMismatch as u32);
+
+    // Nothing left to migrate once the mint info PDA holds the authority
+    let migrate = instructions::migrate_mint_authority(&issuer, &issuer, &harness.mint);
+    assert_error(harness.send(&[migrate], &[]), error_code(MicaEurError::NotMintAuthority));
+}
+
+#[test]
+fn only_the_seizure_authority_is_the_permanent_delegate() {
+    let mut harness = Harness::new();
+    let account = harness.svm.get_account(&sdk_key(&harness.mint)).expect("mint exists");
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data).expect("mint data");
+    let delegate = mint.get_extension::<PermanentDelegate>().expect("permanent delegate").delegate;
+    assert_eq!(Option::<Pubkey>::from(delegate), Some(pda::seizure_authority(&harness.mint)));
+
+    // The mint info PDA no longer holds it, so there is nothing to migrate
+    let migrate = instructions::migrate_permanent_delegate(&harness.issuer_key(), &harness.mint);
+    assert_error(harness.send(&[migrate], &[]), TokenError::OwnerMismatch as u32);
+}
+
+#[test]
+fn extra_account_meta_lists_are_updated_by_the_issuer() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+
+    // A list written by an earlier hook lacks the accounts added since
+    let metas = mica_eur_transfer_hook::extra_account_metas().expect("extra account metas");
+    harness.set_extra_account_metas(&metas[..metas.len() - 2]);
+    assert!(harness.transfer(&alice, &alice_account, &bob_account, 10 * EUR).is_err());
+
+    // Only the issuer rewrites it
+    let stranger = harness.wallet();
+    let update = update_extra_account_meta_list(&key(&stranger), &harness.mint);
+    assert!(harness.send(&[update], &[&stranger]).is_err());
+
+    let update = update_extra_account_meta_list(&harness.issuer_key(), &harness.mint);
+    harness.send(&[update], &[]).expect("update");
+    harness.transfer(&alice, &alice_account, &bob_account, 10 * EUR).expect("transfer");
+    assert_eq!(harness.balance(&bob_account), 10 * EUR);
+}
+
+#[test]
+fn vouchers_mint_each_sepa_deposit_once_to_its_user() {
+    let mut harness = Harness::new();
+    let alice = harness.register_user(Some(2));
+    let alice_account = harness.token_account(&key(&alice));
+    let mallory = harness.register_user(Some(2));
+    let mallory_account = harness.token_account(&key(&mallory));
+    let reference_hash = mica_eur::sepa_reference_hash("E2E-20260301-000042");
+    let create = instructions::create_mint_voucher(&harness.issuer_key(), &harness.mint, &key(&alice), reference_hash, 250 * EUR, None);
+    harness.send(&[create.clone()], &[]).expect("voucher");
+    // The same deposit cannot be recorded twice
+    assert!(harness.send(&[create], &[]).is_err());
+
+    // Only the credited user claims
+    let mint = harness.mint;
+    let claim = |user: &Keypair, token_account: &Pubkey| {
+        instructions::claim_mint_voucher(
+            &key(user),
+            &mint,
+            &reference_hash,
+            token_account,
+            COUNTRY,
+            instructions::ClaimVoucherOptions::default(),
+        )
+    };
+    let stolen = claim(&mallory, &mallory_account);
+    assert!(harness.send(&[stolen], &[&mallory]).is_err());
+    let claimed = claim(&alice, &alice_account);
+    harness.send(&[claimed.clone()], &[&alice]).expect("claim");
+    assert_eq!(harness.balance(&alice_account), 250 * EUR);
+    let voucher: mica_eur::MintVoucher = harness.account(&pda::mint_voucher(&harness.mint, &reference_hash));
+    assert_eq!(voucher.token_account, alice_account);
+    assert_error(harness.send(&[claimed], &[&alice]), error_code(MicaEurError::MintVoucherClaimed));
+}
+
+#[test]
+fn redemptions_are_bound_to_their_sepa_payouts() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let redeem = instructions::burn_tokens(
+        &key(&alice),
+        &harness.mint,
+        &alice_account,
+        COUNTRY,
+        40 * EUR,
+        [2; 16],
+        instructions::BurnTokensOptions::default(),
+    );
+    harness.send(&[redeem], &[&alice]).expect("redemption");
+    let issuer = harness.issuer_key();
+    let reference_hash = mica_eur::sepa_reference_hash("2026030100042");
+
+    // Only redemptions have a payout
+    let mut mint_payout = instructions::record_payout_reference(&issuer, &harness.mint, &issuer, [1; 16], reference_hash);
+    mint_payout.accounts[2].pubkey = pda::operation_receipt(&harness.mint, OperationKind::Mint, &issuer, &[1; 16]);
+    assert_error(harness.send(&[mint_payout], &[]), error_code(MicaEurError::NotARedemption));
+
+    let payout = instructions::record_payout_reference(&issuer, &harness.mint, &key(&alice), [2; 16], reference_hash);
+    harness.send(&[payout.clone()], &[]).expect("payout reference");
+    let record: mica_eur::PayoutReference = harness.account(&pda::payout_reference(&harness.mint, &key(&alice), &[2; 16]));
+    assert_eq!(record.holder, key(&alice));
+    assert_eq!(record.amount, 40 * EUR);
+    assert_eq!(record.reference_hash, reference_hash);
+    // Recorded once
+    assert!(harness.send(&[payout], &[]).is_err());
+}
+
+#[test]
+fn minting_stops_while_the_reserve_proof_is_stale() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let issuer = harness.issuer_key();
+    let mint = harness.mint;
+    let publish = |epoch: u64| {
+        let cash = ReserveAsset { category: ReserveAssetCategory::CashAtBank, isin_hash: [0; 32], amount: 1_000 * EUR, units: 0 };
+        instructions::update_reserve_proof(
+            &issuer,
+            &mint,
+            epoch,
+            [7; 32],
+            "bafyreserve".to_string(),
+            1_000 * EUR,
+            vec![cash],
+            ReserveProofOptions::default(),
+        )
+    };
+
+    let result = harness.send(&[instructions::set_max_reserve_age(&issuer, &mint, -1)], &[]);
+    assert_error(result, error_code(MicaEurError::InvalidMaxReserveAge));
+    harness.send(&[instructions::set_max_reserve_age(&issuer, &mint, 7 * 86_400)], &[]).expect("max reserve age");
+
+    // No proof has been published yet
+    let result = harness.mint_to(&key(&alice), &alice_account, 10 * EUR, 2);
+    assert_error(result, error_code(MicaEurError::ReserveProofStale));
+
+    harness.send(&[publish(0)], &[]).expect("reserve proof");
+    harness.mint_to(&key(&alice), &alice_account, 10 * EUR, 2).expect("mint with a fresh proof");
+
+    harness.advance_clock(7 * 86_400 + 1);
+    let result = harness.mint_to(&key(&alice), &alice_account, 10 * EUR, 3);
+    assert_error(result, error_code(MicaEurError::ReserveProofStale));
+
+    harness.send(&[publish(1)], &[]).expect("reserve proof");
+    harness.mint_to(&key(&alice), &alice_account, 10 * EUR, 3).expect("mint after a new proof");
+    assert_eq!(harness.balance(&alice_account), 120 * EUR);
+}
+
+#[test]
+fn reserve_proofs_are_checked_against_oracle_prices() {
+    let mut harness = Harness::new();
+    funded_user(&mut harness, 1_000 * EUR, 1);
+    let issuer = harness.issuer_key();
+    let mint = harness.mint;
+    let (eur_usd_feed, bond_feed) = ([1; 32], [2; 32]);
+    let bond = mica_eur::isin_hash("DE0001030542");
+    let configure = instructions::configure_reserve_valuation(
+        &issuer,
+        &mint,
+        eur_usd_feed,
+        vec![BondPriceFeed { isin_hash: bond, feed_id: bond_feed }],
+        100,
+        300,
+    );
+    harness.send(&[configure], &[]).expect("reserve valuation");
+
+    // 4,000 bonds at 108.00 USD with EUR/USD at 1.0800 are worth 400,000 EUR
+    let eur_usd = harness.pyth_price_update(eur_usd_feed, 10_800, -4, START_TIME);
+    let bond_price = harness.pyth_price_update(bond_feed, 10_800, -2, START_TIME);
+    let stale = harness.pyth_price_update(bond_feed, 10_800, -2, START_TIME - 301);
+    let options = ReserveProofOptions {
+        valuation_config: Some(pda::reserve_valuation(&mint)),
+        ..Default::default()
+    };
+    let publish = |epoch: u64, bond_amount: u64, options: ReserveProofOptions, price_updates: &[Pubkey]| {
+        let total = 600_000 * EUR + bond_amount;
+        let composition = vec![
+            ReserveAsset { category: ReserveAssetCategory::CashAtBank, isin_hash: [0; 32], amount: 600_000 * EUR, units: 0 },
+            ReserveAsset { category: ReserveAssetCategory::GovernmentBond, isin_hash: bond, amount: bond_amount, units: 4_000 },
+        ];
+        let mut update = instructions::update_reserve_proof(
+            &issuer,
+            &mint,
+            epoch,
+            [7; 32],
+            "bafyreserve".to_string(),
+            total,
+            composition,
+            options,
+        );
+        update.accounts.extend(instructions::price_update_accounts(price_updates));
+        update
+    };
+
+    // Once configured, proofs cannot skip the valuation
+    let result = harness.send(&[publish(0, 400_000 * EUR, ReserveProofOptions::default(), &[])], &[]);
+    assert_error(result, error_code(MicaEurError::ReserveValuationRequired));
+    let result = harness.send(&[publish(0, 400_000 * EUR, options, &[eur_usd, stale])], &[]);
+    assert_error(result, error_code(MicaEurError::StalePriceUpdate));
+    let result = harness.send(&[publish(0, 400_000 * EUR, options, &[bond_price, eur_usd])], &[]);
+    assert_error(result, error_code(MicaEurError::InvalidPriceUpdate));
+
+    // 2% above the oracle valuation is beyond the 1% tolerance
+    let result = harness.send(&[publish(0, 408_000 * EUR, options, &[eur_usd, bond_price])], &[]);
+    assert_error(result, error_code(MicaEurError::ReserveValuationDivergence));
+
+    harness.send(&[publish(0, 402_000 * EUR, options, &[eur_usd, bond_price])], &[]).expect("valued reserve proof");
+    let reserve: ReserveComposition = harness.account(&pda::reserve_composition(&mint));
+    assert_eq!(reserve.valuation.oracle_value, 400_000 * EUR);
+    assert_eq!(reserve.valuation.asserted_value, 402_000 * EUR);
+    assert_eq!(reserve.valuation.eur_usd.price, 10_800);
+    assert_eq!(reserve.valuation.asset_prices[1].price, 10_800);
+}
+
+#[test]
+fn regulators_receive_audit_window_digests_while_granted_access() {
+    let mut harness = Harness::new();
+    let issuer = harness.issuer_key();
+    let mint = harness.mint;
+    let regulator = key(&harness.wallet());
+    let export = |from: i64, event_count: u64, digest: [u8; 32]| {
+        instructions::export_audit_window(&issuer, &mint, &regulator, from, START_TIME, event_count, digest)
+    };
+    let grant = |expires_at: i64| {
+        instructions::grant_regulator_access(&issuer, &mint, &regulator, "529900T8BM49AURSDO55".to_string(), expires_at)
+    };
+
+    let digest = mica_eur::calculate_merkle_root(&[
+        mica_eur::audit_event_hash(b"first event"),
+        mica_eur::audit_event_hash(b"second event"),
+    ]);
+    // No access has been granted yet
+    assert!(harness.send(&[export(START_TIME - 86_400, 2, digest)], &[]).is_err());
+
+    harness.send(&[grant(START_TIME + 86_400)], &[]).expect("regulator access");
+
+    // The window must be closed and its digest match its event count
+    let result = harness.send(&[export(START_TIME, 2, digest)], &[]);
+    assert_error(result, error_code(MicaEurError::InvalidAuditWindow));
+    let result = harness.send(&[export(START_TIME - 86_400, 0, digest)], &[]);
+    assert_error(result, error_code(MicaEurError::InvalidAuditWindow));
+
+    harness.send(&[export(START_TIME - 86_400, 2, digest)], &[]).expect("audit window");
+    harness.send(&[export(START_TIME - 3_600, 0, [0; 32])], &[]).expect("empty audit window");
+    let access: RegulatorAccess = harness.account(&pda::regulator_access(&mint, &regulator));
+    assert_eq!(access.export_count, 2);
+
+    // Access ends when it expires and when it is revoked
+    harness.advance_clock(86_400);
+    let result = harness.send(&[export(START_TIME - 86_400, 2, digest)], &[]);
+    assert_error(result, error_code(MicaEurError::RegulatorAccessInactive));
+    harness.send(&[grant(0)], &[]).expect("renewed access");
+    harness.send(&[instructions::revoke_regulator_access(&issuer, &mint, &regulator)], &[]).expect("revoke");
+    let result = harness.send(&[export(START_TIME - 86_400, 2, digest)], &[]);
+    assert_error(result, error_code(MicaEurError::RegulatorAccessInactive));
+}
+
+#[test]
+fn allowlisted_program_escrows_receive_without_kyc() {
+    let mut harness = Harness::new();
+    let issuer = harness.issuer_key();
+    let mint = harness.mint;
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+
+    // The escrow authority of a DEX, a PDA that never went through KYC
+    let dex = Pubkey::new_unique();
+    let (escrow, bump) = Pubkey::find_program_address(&[b"escrow"], &dex);
+    let escrow_account = open_account(&mut harness, &escrow);
+    let result = harness.transfer(&alice, &alice_account, &escrow_account, 10 * EUR);
+    assert_error(result, error_code(MicaEurError::KycRegistrationMissing));
+
+    // Once the issuer allowlisted the program, its PDAs register with the seeds
+    // deriving them from it
+    harness.send(&[instructions::allowlist_program(&issuer, &mint, &dex)], &[]).expect("allowlist");
+    let register = instructions::register_program_escrow(&issuer, &mint, &dex, &escrow, &[b"escrow", &[bump]]);
+    let wrong_seeds = instructions::register_program_escrow(&issuer, &mint, &dex, &escrow, &[b"vault", &[bump]]);
+    assert_error(harness.send(&[wrong_seeds], &[]), error_code(MicaEurError::InvalidProgramEscrow));
+    harness.send(&[register], &[]).expect("register escrow");
+
+    harness.transfer(&alice, &alice_account, &escrow_account, 10 * EUR).expect("deposit into the escrow");
+    assert_eq!(harness.balance(&escrow_account), 10 * EUR);
+
+    // Delisting the program takes effect on the next transfer
+    harness.send(&[instructions::delist_program(&issuer, &mint, &dex)], &[]).expect("delist");
+    let result = harness.transfer(&alice, &alice_account, &escrow_account, 10 * EUR);
+    assert_error(result, error_code(MicaEurError::KycRegistrationMissing));
+}
+
+#[test]
+fn integrators_check_internal_moves_against_the_transfer_rules() {
+    let mut harness = Harness::new();
+    let mint = harness.mint;
+    let alice = key(&harness.register_user(Some(2)));
+    let bob = key(&harness.register_user(Some(2)));
+    let carol = key(&harness.register_user(None));
+    let dave = key(&harness.wallet());
+
+    let check = instructions::assert_transfer_allowed(&mint, &alice, &bob, 10 * EUR);
+    harness.send(&[check], &[]).expect("move between verified users");
+
+    // The same typed errors as the transfer hook
+    let check = instructions::assert_transfer_allowed(&mint, &alice, &carol, 10 * EUR);
+    assert_error(harness.send(&[check], &[]), error_code(MicaEurError::UserNotVerified));
+    let check = instructions::assert_transfer_allowed(&mint, &dave, &bob, 10 * EUR);
+    assert_error(harness.send(&[check], &[]), error_code(MicaEurError::KycRegistrationMissing));
+}
+
+#[test]
+fn relayers_submit_transfers_the_owner_signed() {
+    let mut harness = Harness::new();
+    let mint = harness.mint;
+    let relayer = harness.wallet();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+    let decimals = mica_eur::EUR_DECIMALS;
+    let approve = instructions::approve_permit_authority(&key(&alice), &mint, &alice_account, 50 * EUR, decimals);
+    harness.send(&[approve], &[&alice]).expect("approve permits");
+
+    let authorized = |harness: &Harness, signer: &Keypair, recipient: &Pubkey, destination: &Pubkey, nonce: [u8; 32]| {
+        let expiry = START_TIME + 3600;
+        let message = transfer_authorization_message(&mint, &key(&alice), recipient, 20 * EUR, &nonce, expiry);
+        let mut transfer = instructions::transfer_with_authorization(
+            &key(&relayer),
+            &mint,
+            &key(&alice),
+            &alice_account,
+            destination,
+            recipient,
+            20 * EUR,
+            nonce,
+            expiry,
+            None,
+        );
+        transfer.accounts.extend(harness.hook_accounts(&alice_account, destination));
+        [ed25519_instruction(signer, &message), transfer]
+    };
+
+    // The relayer pays; only the owner's signature authorizes the transfer
+    let transfer = authorized(&harness, &alice, &key(&bob), &bob_account, [1; 32]);
+    harness.send(&transfer, &[&relayer]).expect("authorized transfer");
+    assert_eq!(harness.balance(&bob_account), 20 * EUR);
+
+    // Nonces are single use, and signatures by anyone else are rejected
+    assert!(harness.send(&transfer, &[&relayer]).is_err());
+    let forged = authorized(&harness, &relayer, &key(&bob), &bob_account, [2; 32]);
+    assert_error(harness.send(&forged, &[&relayer]), error_code(MicaEurError::SignatureMismatch));
+
+    // The transfer hook still checks the recipient
+    let carol = harness.register_user(None);
+    let carol_account = open_account(&mut harness, &key(&carol));
+    let transfer = authorized(&harness, &alice, &key(&carol), &carol_account, [3; 32]);
+    assert_error(harness.send(&transfer, &[&relayer]), error_code(MicaEurError::UserNotVerified));
+
+    // Expired authorizations are refused
+    harness.advance_clock(3601);
+    let transfer = authorized(&harness, &alice, &key(&bob), &bob_account, [4; 32]);
+    assert_error(harness.send(&transfer, &[&relayer]), error_code(MicaEurError::TransferAuthorizationExpired));
+    assert_eq!(harness.balance(&alice_account), 80 * EUR);
+}
+
+#[test]
+fn only_active_registered_relayers_submit_once_the_registry_exists() {
+    let mut harness = Harness::new();
+    let issuer = harness.issuer_key();
+    let mint = harness.mint;
+    let relayer = harness.wallet();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+    let decimals = mica_eur::EUR_DECIMALS;
+    let approve = instructions::approve_permit_authority(&key(&alice), &mint, &alice_account, 50 * EUR, decimals);
+    harness.send(&[approve], &[&alice]).expect("approve permits");
+
+    let min_stake = 100_000_000;
+    harness.send(&[instructions::configure_relayer_registry(&issuer, &mint, min_stake)], &[]).expect("registry");
+    let relayer_record = pda::relayer(&mint, &key(&relayer));
+    let authorized = |harness: &Harness, nonce: [u8; 32], relayer_record: Option<Pubkey>| {
+        let expiry = START_TIME + 3600;
+        let message = transfer_authorization_message(&mint, &key(&alice), &key(&bob), 10 * EUR, &nonce, expiry);
+        let mut transfer = instructions::transfer_with_authorization(
+            &key(&relayer),
+            &mint,
+            &key(&alice),
+            &alice_account,
+            &bob_account,
+            &key(&bob),
+            10 * EUR,
+            nonce,
+            expiry,
+            relayer_record,
+        );
+        transfer.accounts.extend(harness.hook_accounts(&alice_account, &bob_account));
+        [ed25519_instruction(&alice, &message), transfer]
+    };
+
+    let transfer = authorized(&harness, [1; 32], None);
+    assert_error(harness.send(&transfer, &[&relayer]), error_code(MicaEurError::RelayerNotRegistered));
+
+    // Registration takes the registry's minimum stake
+    let register = instructions::register_relayer(&key(&relayer), &mint, min_stake - 1);
+    assert_error(harness.send(&[register], &[&relayer]), error_code(MicaEurError::RelayerStakeTooLow));
+    let register = instructions::register_relayer(&key(&relayer), &mint, min_stake);
+    harness.send(&[register], &[&relayer]).expect("register relayer");
+
+    let transfer = authorized(&harness, [1; 32], Some(relayer_record));
+    harness.send(&transfer, &[&relayer]).expect("transfer by a registered relayer");
+    let record: Relayer = harness.account(&relayer_record);
+    assert_eq!((record.stake, record.submission_count, record.submitted_volume), (min_stake, 1, 10 * EUR));
+
+    // A suspended relayer can neither submit nor take its stake back
+    let suspend = instructions::set_relayer_status(&issuer, &mint, &key(&relayer), RelayerStatus::Suspended);
+    harness.send(&[suspend], &[]).expect("suspend relayer");
+    let transfer = authorized(&harness, [2; 32], Some(relayer_record));
+    assert_error(harness.send(&transfer, &[&relayer]), error_code(MicaEurError::RelayerSuspended));
+    let deregister = instructions::deregister_relayer(&key(&relayer), &mint);
+    assert_error(harness.send(&[deregister.clone()], &[&relayer]), error_code(MicaEurError::RelayerSuspended));
+
+    let reinstate = instructions::set_relayer_status(&issuer, &mint, &key(&relayer), RelayerStatus::Active);
+    harness.send(&[reinstate], &[]).expect("reinstate relayer");
+    harness.send(&[deregister], &[&relayer]).expect("deregister relayer");
+    assert_eq!(harness.balance(&bob_account), 10 * EUR);
+}
+
+#[test]
+fn keepers_crank_due_standing_orders() {
+    let mut harness = Harness::new();
+    let mint = harness.mint;
+    let keeper = harness.wallet();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+
+    // Rent of 30 EUR a day, three times
+    let day = 86_400;
+    let create = instructions::create_standing_order(
+        &key(&alice),
+        &mint,
+        &alice_account,
+        &bob_account,
+        &key(&bob),
+        7,
+        30 * EUR,
+        day,
+        START_TIME + day,
+        START_TIME + 3 * day,
+    );
+    harness.send(&[create], &[&alice]).expect("create standing order");
+
+    let execute = |harness: &Harness| {
+        let mut execute = instructions::execute_standing_order(
+            &key(&keeper),
+            &mint,
+            &key(&alice),
+            7,
+            &alice_account,
+            &key(&bob),
+            &bob_account,
+        );
+        execute.accounts.extend(harness.hook_accounts(&alice_account, &bob_account));
+        execute
+    };
+    let result = harness.send(&[execute(&harness)], &[&keeper]);
+    assert_error(result, error_code(MicaEurError::StandingOrderNotDue));
+
+    harness.advance_clock(day);
+    harness.send(&[execute(&harness)], &[&keeper]).expect("first execution");
+    assert_eq!(harness.balance(&bob_account), 30 * EUR);
+
+    // Skipped executions are not paid
+    harness.send(&[instructions::skip_standing_order(&key(&alice), 7)], &[&alice]).expect("skip");
+    harness.advance_clock(day);
+    let result = harness.send(&[execute(&harness)], &[&keeper]);
+    assert_error(result, error_code(MicaEurError::StandingOrderNotDue));
+
+    harness.advance_clock(day);
+    harness.send(&[execute(&harness)], &[&keeper]).expect("last execution");
+    let order: StandingOrder = harness.account(&pda::standing_order(&key(&alice), 7));
+    assert_eq!((order.execution_count, order.status), (2, StandingOrderStatus::Completed));
+    assert_eq!(harness.balance(&alice_account), 40 * EUR);
+}
+
+#[test]
+fn commerce_escrows_release_to_the_seller_or_refund_the_buyer() {
+    let mut harness = Harness::new();
+    let mint = harness.mint;
+    let issuer = harness.issuer_key();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+    let arbiter = harness.wallet();
+
+    let vault = harness.token_account(&pda::escrow_vault(&mint));
+    let configure = instructions::configure_escrow_vault(&issuer, &mint, &vault);
+    harness.send(&[configure], &[]).expect("escrow vault");
+
+    let week = 7 * 86_400;
+    let open = |harness: &Harness, escrow_id: u64, amount: u64| {
+        let mut open = instructions::open_escrow(
+            &key(&alice),
+            &mint,
+            &vault,
+            &alice_account,
+            &key(&bob),
+            &bob_account,
+            escrow_id,
+            amount,
+            &key(&arbiter),
+            [escrow_id as u8; 32],
+            START_TIME + week,
+        );
+        open.accounts.extend(harness.hook_accounts(&alice_account, &vault));
+        open
+    };
+    harness.send(&[open(&harness, 1, 40 * EUR)], &[&alice]).expect("open first escrow");
+    harness.send(&[open(&harness, 2, 20 * EUR)], &[&alice]).expect("open second escrow");
+    assert_eq!(harness.balance(&vault), 60 * EUR);
+
+    // Only the buyer or the arbiter releases to the seller
+    let release = |harness: &Harness, authority: &Pubkey, escrow_id: u64| {
+        let mut release =
+            instructions::release_escrow(authority, &mint, &vault, &key(&alice), escrow_id, &bob_account);
+        release.accounts.extend(harness.hook_accounts(&vault, &bob_account));
+        release
+    };
+    let result = harness.send(&[release(&harness, &key(&bob), 1)], &[&bob]);
+    assert_error(result, error_code(MicaEurError::UnauthorizedEscrowResolution));
+    harness.send(&[release(&harness, &key(&arbiter), 1)], &[&arbiter]).expect("release");
+    assert_eq!(harness.balance(&bob_account), 40 * EUR);
+    let result = harness.send(&[release(&harness, &key(&alice), 1)], &[&alice]);
+    assert_error(result, error_code(MicaEurError::EscrowNotOpen));
+
+    // The buyer takes an escrow back only once it expired
+    let refund = |harness: &Harness| {
+        let mut refund = instructions::refund_escrow(&key(&alice), &mint, &vault, &key(&alice), 2, &alice_account);
+        refund.accounts.extend(harness.hook_accounts(&vault, &alice_account));
+        refund
+    };
+    let result = harness.send(&[refund(&harness)], &[&alice]);
+    assert_error(result, error_code(MicaEurError::UnauthorizedEscrowResolution));
+    harness.advance_clock(week);
+    harness.send(&[refund(&harness)], &[&alice]).expect("refund");
+    assert_eq!(harness.balance(&alice_account), 60 * EUR);
+    assert_eq!(harness.balance(&vault), 0);
+
+    let escrow: Escrow = harness.account(&pda::escrow(&key(&alice), 2));
+    assert_eq!((escrow.status, escrow.resolved_by), (EscrowStatus::Refunded, key(&alice)));
+}
+
+#[test]
+fn payment_requests_link_payments_to_invoices() {
+    let mut harness = Harness::new();
+    let mint = harness.mint;
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let shop = harness.register_user(Some(2));
+    let shop_account = open_account(&mut harness, &key(&shop));
+
+    let memo_hash = [7u8; 32];
+    let day = 86_400;
+    let create = instructions::create_payment_request(
+        &key(&shop),
+        &mint,
+        &shop_account,
+        1,
+        25 * EUR,
+        memo_hash,
+        START_TIME + day,
+    );
+    harness.send(&[create], &[&shop]).expect("create payment request");
+
+    let pay = |harness: &Harness, request_id: u64| {
+        let mut pay =
+            instructions::pay_request(&key(&alice), &mint, &key(&shop), request_id, &alice_account, &shop_account);
+        pay.accounts.extend(harness.hook_accounts(&alice_account, &shop_account));
+        pay
+    };
+    harness.send(&[pay(&harness, 1)], &[&alice]).expect("pay request");
+    assert_eq!(harness.balance(&shop_account), 25 * EUR);
+    let request: PaymentRequest = harness.account(&pda::payment_request(&key(&shop), 1));
+    assert_eq!(request.status, PaymentRequestStatus::Paid);
+    assert_eq!((request.paid_by, request.memo_hash), (key(&alice), memo_hash));
+
+    // A request is paid once, and not after it expired
+    harness.advance_clock(1);
+    let result = harness.send(&[pay(&harness, 1)], &[&alice]);
+    assert_error(result, error_code(MicaEurError::PaymentRequestNotOpen));
+    let create = instructions::create_payment_request(
+        &key(&shop),
+        &mint,
+        &shop_account,
+        2,
+        25 * EUR,
+        memo_hash,
+        START_TIME + day,
+    );
+    harness.send(&[create], &[&shop]).expect("create second request");
+    harness.advance_clock(day);
+    let result = harness.send(&[pay(&harness, 2)], &[&alice]);
+    assert_error(result, error_code(MicaEurError::PaymentRequestExpired));
+    assert_eq!(harness.balance(&alice_account), 75 * EUR);
+}
+
+#[test]
+fn fast_lane_institutions_skip_transaction_limits_within_their_daily_cap() {
+    let mut harness = Harness::new();
+    let mint = harness.mint;
+    let issuer = harness.issuer_key();
+    let bank = harness.register_user(Some(3));
+    let bank_account = open_account(&mut harness, &key(&bank));
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+
+    // Level 3 needs a recent sanctions screening, the class a KYB link
+    screen(&mut harness, &key(&bank));
+    let reclassify = instructions::reclassify_account(
+        &issuer,
+        KycSigner::OracleAuthority,
+        &key(&bank),
+        AccountClass::SupervisedInstitution,
+        [2; 32],
+        None,
+    );
+    harness.send(&[reclassify], &[]).expect("reclassify");
+    for operation_id in 1..=3 {
+        harness.mint_to(&key(&bank), &bank_account, 100_000 * EUR, operation_id).expect("mint");
+    }
+
+    // Interbank-sized transfers exceed the tier's transaction limit until whitelisted
+    let result = harness.transfer(&bank, &bank_account, &bob_account, 250_000 * EUR);
+    assert_error(result, error_code(MicaEurError::TransactionAmountExceedsMaximum));
+    let lei = *b"529900T8BM49AURSDO55";
+    let whitelist = instructions::whitelist_institution(&issuer, &mint, &key(&bank), lei, 280_000 * EUR);
+    harness.send(&[whitelist], &[]).expect("whitelist");
+    harness.transfer(&bank, &bank_account, &bob_account, 250_000 * EUR).expect("fast-lane transfer");
+    assert_eq!(harness.balance(&bob_account), 250_000 * EUR);
+
+    // The daily cap still holds; removal puts the bank back on its tier's limits
+    let result = harness.transfer(&bank, &bank_account, &bob_account, 40_000 * EUR);
+    assert_error(result, error_code(MicaEurError::DailyTransferLimitExceeded));
+    harness.send(&[instructions::remove_institution(&issuer, &mint, &key(&bank))], &[]).expect("remove");
+    harness.transfer(&bank, &bank_account, &bob_account, 40_000 * EUR).expect("transfer within tier limits");
+}
+
+#[test]
+fn transfers_are_capped_at_the_sender_class_transaction_limit() {
+    let mut harness = Harness::new();
+    let issuer = harness.issuer_key();
+    let corporate = harness.register_user(Some(3));
+    let corporate_account = open_account(&mut harness, &key(&corporate));
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+    screen(&mut harness, &key(&corporate));
+    for operation_id in 1..=2 {
+        harness.mint_to(&key(&corporate), &corporate_account, 15_000 * EUR, operation_id).expect("mint");
+    }
+
+    // Level 3 allows 100,000 EUR per transfer, the retail class only 10,000
+    let result = harness.transfer(&corporate, &corporate_account, &bob_account, 20_000 * EUR);
+    assert_error(result, error_code(MicaEurError::TransactionAmountExceedsMaximum));
+    harness.transfer(&corporate, &corporate_account, &bob_account, 10_000 * EUR).expect("retail-sized transfer");
+
+    let reclassify = instructions::reclassify_account(
+        &issuer,
+        KycSigner::OracleAuthority,
+        &key(&corporate),
+        AccountClass::Institutional,
+        [2; 32],
+        None,
+    );
+    harness.send(&[reclassify], &[]).expect("reclassify");
+    harness.transfer(&corporate, &corporate_account, &bob_account, 20_000 * EUR).expect("institutional transfer");
+    assert_eq!(harness.balance(&bob_account), 30_000 * EUR);
+}
+
+#[test]
+fn large_transfers_need_travel_rule_data_attached_by_the_originator() {
+    let mut harness = Harness::new();
+    let mint = harness.mint;
+    let (alice, alice_account) = funded_user(&mut harness, 200 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+    let configure = instructions::configure_travel_rule(&harness.issuer_key(), &mint, 50 * EUR);
+    harness.send(&[configure], &[]).expect("travel rule");
+
+    let result = harness.transfer(&alice, &alice_account, &bob_account, 60 * EUR);
+    assert_error(result, error_code(MicaEurError::TravelRuleRecordRequired));
+    let attach =
+        instructions::attach_travel_rule_info(&key(&alice), &alice_account, &bob_account, 60 * EUR, [1; 32], [2; 32]);
+    harness.send(&[attach], &[&alice]).expect("attach");
+    let result = harness.transfer(&alice, &alice_account, &bob_account, 70 * EUR);
+    assert_error(result, error_code(MicaEurError::TravelRuleRecordMismatch));
+    harness.transfer(&alice, &alice_account, &bob_account, 60 * EUR).expect("transfer with travel-rule data");
+    let record: mica_eur_transfer_hook::TravelRuleRecord =
+        harness.account(&pda::travel_rule_record(&alice_account, &bob_account));
+    assert_eq!((record.amount, record.consumed_at), (60 * EUR, START_TIME));
+
+    // A record covers one transfer; small ones need none
+    let result = harness.transfer(&alice, &alice_account, &bob_account, 60 * EUR);
+    assert_error(result, error_code(MicaEurError::TravelRuleRecordMismatch));
+    harness.transfer(&alice, &alice_account, &bob_account, 20 * EUR).expect("transfer below the threshold");
+    assert_eq!(harness.balance(&bob_account), 80 * EUR);
+}
+
+#[test]
+fn winding_down_lets_holders_return_tokens_to_the_issuer() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let (_, bob_account) = funded_user(&mut harness, 10 * EUR, 2);
+    let issuer = harness.issuer_key();
+    let redemption_account = harness.token_account(&issuer);
+    harness.thaw(&redemption_account).expect("thaw");
+
+    let too_soon = instructions::initiate_wind_down(&issuer, &harness.mint, &redemption_account, START_TIME + 86_400);
+    assert_error(harness.send(&[too_soon], &[]), error_code(MicaEurError::InvalidRedemptionDeadline));
+    let deadline = START_TIME + mica_eur::MIN_REDEMPTION_PERIOD;
+    let wind_down = instructions::initiate_wind_down(&issuer, &harness.mint, &redemption_account, deadline);
+    harness.send(&[wind_down], &[]).expect("wind-down");
+    let mint_info: mica_eur::MintInfo = harness.account(&pda::mint_info(&harness.mint));
+    assert!(!mint_info.is_active);
+    assert_eq!(mint_info.redemption_deadline, deadline);
+
+    // No minting and no transfers between holders, not even after a reactivation attempt
+    let result = harness.mint_to(&key(&alice), &alice_account, 10 * EUR, 3);
+    assert_error(result, error_code(MicaEurError::MintInactive));
+    let result = harness.transfer(&alice, &alice_account, &bob_account, 10 * EUR);
+    assert_error(result, error_code(MicaEurError::MintInactive));
+    let reactivate = instructions::reactivate_mint(&issuer, &harness.mint);
+    assert_error(harness.send(&[reactivate], &[]), error_code(MicaEurError::MintWindingDown));
+
+    // Returns to the issuer go through even once the holder's KYC has lapsed
+    harness.advance_clock(KYC_EXPIRY_DAYS * 86400 + 1);
+    harness.transfer(&alice, &alice_account, &redemption_account, 100 * EUR).expect("return");
+    assert_eq!(harness.balance(&redemption_account), 100 * EUR);
+}
+
+#[test]
+fn treasury_funds_are_distributed_to_verified_users() {
+    let mut harness = Harness::new();
+    let vault = harness.configure_treasury_vault();
+    let issuer = harness.issuer_key();
+    let mint_to_treasury = instructions::mint_to_treasury(&issuer, &harness.mint, &vault, 100 * EUR, [1; 16], None, None);
+    harness.send(&[mint_to_treasury], &[]).expect("treasury mint");
+    assert_eq!(harness.balance(&vault), 100 * EUR);
+
+    let distribute = |harness: &Harness, distributor: &Pubkey, user: &Keypair, token_account: &Pubkey, operation_id: u8| {
+        let mut distribute = instructions::distribute_from_treasury(
+            distributor,
+            &harness.mint,
+            &vault,
+            token_account,
+            &key(user),
+            COUNTRY,
+            30 * EUR,
+            [operation_id; 16],
+            instructions::DistributeOptions::default(),
+        );
+        distribute.accounts.extend(harness.hook_accounts(&vault, token_account));
+        distribute
+    };
+
+    // Recipients are checked like mint recipients
+    let pending = harness.register_user(None);
+    let pending_account = harness.token_account(&key(&pending));
+    let to_pending = distribute(&harness, &issuer, &pending, &pending_account, 2);
+    let result = harness.send(&[to_pending], &[]);
+    assert_error(result, error_code(MicaEurError::UserNotVerified));
+
+    // Only DISTRIBUTOR role holders hand funds out
+    let alice = harness.register_user(Some(2));
+    let alice_account = harness.token_account(&key(&alice));
+    let stranger = harness.wallet();
+    let by_stranger = distribute(&harness, &key(&stranger), &alice, &alice_account, 3);
+    let result = harness.send(&[by_stranger], &[&stranger]);
+    assert_error(result, error_code(MicaEurError::MissingRole));
+
+    let to_alice = distribute(&harness, &issuer, &alice, &alice_account, 4);
+    harness.send(&[to_alice], &[]).expect("distribution");
+    assert_eq!(harness.balance(&alice_account), 30 * EUR);
+    assert_eq!(harness.balance(&vault), 70 * EUR);
+    assert!(!harness.is_frozen(&alice_account));
+    let treasury: mica_eur::TreasuryVault = harness.account(&pda::treasury_vault(&harness.mint));
+    assert_eq!((treasury.minted_amount, treasury.distributed_amount), (100 * EUR, 30 * EUR));
+}
+
+#[test]
+fn transfers_require_verified_parties() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+
+    // A new account stays frozen until thawed or minted to
+    let bob = harness.register_user(Some(2));
+    let bob_account = harness.token_account(&key(&bob));
+    let result = harness.transfer(&alice, &alice_account, &bob_account, 10 * EUR);
+    assert_error(result, TokenError::AccountFrozen as u32);
+    harness.thaw(&bob_account).expect("thaw");
+
+    harness.transfer(&alice, &alice_account, &bob_account, 40 * EUR).expect("transfer between verified users");
+    assert_eq!(harness.balance(&alice_account), 60 * EUR);
+    assert_eq!(harness.balance(&bob_account), 40 * EUR);
+
+    // The transfer hook rejects a pending recipient
+    let carol = harness.register_user(None);
+    let carol_account = open_account(&mut harness, &key(&carol));
+    let result = harness.transfer(&alice, &alice_account, &carol_account, 10 * EUR);
+    assert_error(result, error_code(MicaEurError::UserNotVerified));
+
+    // And a recipient who never registered
+    let dave = harness.wallet();
+    let dave_account = open_account(&mut harness, &key(&dave));
+    let result = harness.transfer(&alice, &alice_account, &dave_account, 10 * EUR);
+    assert_error(result, error_code(MicaEurError::KycRegistrationMissing));
+
+    assert_eq!(harness.balance(&alice_account), 60 * EUR);
+    assert_eq!(harness.balance(&carol_account), 0);
+    assert_eq!(harness.balance(&dave_account), 0);
+}
+
+#[test]
+fn frozen_accounts_cannot_move_funds_until_thawed() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+
+    harness.freeze(&alice_account, 0).expect("freeze");
+    assert!(harness.is_frozen(&alice_account));
+    let record: mica_eur::FreezeRecord = harness.account(&pda::freeze_record(&alice_account));
+    assert!(record.is_active);
+    assert_eq!(record.reason_code, mica_eur::freeze_reasons::COURT_ORDER);
+
+    // Frozen in both directions, and a second freeze is refused
+    let result = harness.transfer(&alice, &alice_account, &bob_account, 10 * EUR);
+    assert_error(result, TokenError::AccountFrozen as u32);
+    let result = harness.transfer(&bob, &bob_account, &alice_account, 0);
+    assert_error(result, TokenError::AccountFrozen as u32);
+    assert_error(harness.freeze(&alice_account, 0), error_code(MicaEurError::AccountAlreadyFrozen));
+
+    harness.thaw(&alice_account).expect("thaw");
+    assert!(!harness.is_frozen(&alice_account));
+    let record: mica_eur::FreezeRecord = harness.account(&pda::freeze_record(&alice_account));
+    assert!(!record.is_active);
+
+    harness.transfer(&alice, &alice_account, &bob_account, 10 * EUR).expect("transfer after thaw");
+    assert_eq!(harness.balance(&alice_account), 90 * EUR);
+    assert_eq!(harness.balance(&bob_account), 10 * EUR);
+}
+
+#[test]
+fn recorded_freezes_hold_against_mints_and_redemptions() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let (bob, bob_account) = funded_user(&mut harness, 100 * EUR, 2);
+    let mint = harness.mint;
+    let redeem = |owner: &Keypair, token_account: &Pubkey, amount: u64, operation_id: u8| {
+        instructions::burn_tokens(
+            &key(owner),
+            &mint,
+            token_account,
+            COUNTRY,
+            amount,
+            [operation_id; 16],
+            instructions::BurnTokensOptions::default(),
+        )
+    };
+
+    // A full freeze: issuance does not thaw the account and nothing is redeemed
+    harness.freeze(&alice_account, 0).expect("freeze");
+    let result = harness.mint_to(&key(&alice), &alice_account, 10 * EUR, 3);
+    assert_error(result, error_code(MicaEurError::AccountFrozenByRecord));
+    let mut bypass = instructions::mint_tokens(
+        &harness.issuer_key(),
+        &mint,
+        &alice_account,
+        &key(&alice),
+        COUNTRY,
+        10 * EUR,
+        [8; 16],
+        instructions::MintTokensOptions::default(),
+    );
+    leave_out(&mut bypass, &pda::freeze_record(&alice_account));
+    assert_error(harness.send(&[bypass], &[]), anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    assert!(harness.is_frozen(&alice_account));
+    let result = harness.send(&[redeem(&alice, &alice_account, 10 * EUR, 4)], &[&alice]);
+    assert_error(result, error_code(MicaEurError::AccountFrozenByRecord));
+
+    // A partial freeze locks its amount against redemption
+    harness.freeze(&bob_account, 60 * EUR).expect("partial freeze");
+    let result = harness.send(&[redeem(&bob, &bob_account, 50 * EUR, 5)], &[&bob]);
+    assert_error(result, error_code(MicaEurError::FrozenAmountLocked));
+
+    // Passing the program ID in place of the record fails its seeds check
+    let mut bypass = redeem(&bob, &bob_account, 50 * EUR, 6);
+    let record = pda::freeze_record(&bob_account);
+    leave_out(&mut bypass, &record);
+    let result = harness.send(&[bypass], &[&bob]);
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+
+    harness.send(&[redeem(&bob, &bob_account, 40 * EUR, 7)], &[&bob]).expect("redeem the free balance");
+    assert_eq!(harness.balance(&alice_account), 100 * EUR);
+    assert_eq!(harness.balance(&bob_account), 60 * EUR);
+}
+
+#[test]
+fn compliance_history_records_freezes_and_thaws() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let open = instructions::open_compliance_history(&harness.issuer_key(), &key(&alice));
+    harness.send(&[open], &[]).expect("open history");
+
+    harness.freeze(&alice_account, 0).expect("freeze");
+    harness.thaw(&alice_account).expect("thaw");
+
+    let history: mica_eur::ComplianceHistory = harness.account(&pda::compliance_history(&key(&alice)));
+    assert_eq!(history.user, key(&alice));
+    assert_eq!(history.total_entries, 2);
+    let actions: Vec<_> = history.chronological().map(|entry| entry.action).collect();
+    assert_eq!(
+        actions,
+        [
+            mica_eur::ComplianceAction::Frozen {
+                reason_code: mica_eur::freeze_reasons::COURT_ORDER,
+                frozen_amount: 0,
+            },
+            mica_eur::ComplianceAction::Thawed,
+        ]
+    );
+    assert!(history.chronological().all(|entry| entry.actor == harness.issuer_key()));
+}
+
+#[test]
+fn partially_frozen_funds_stay_locked_until_seized() {
+    let mut harness = Harness::new();
+    let (alice, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+
+    // Only 60 EUR are locked: the account stays usable for the rest
+    harness.freeze(&alice_account, 60 * EUR).expect("partial freeze");
+    assert!(!harness.is_frozen(&alice_account));
+    let result = harness.transfer(&alice, &alice_account, &bob_account, 50 * EUR);
+    assert_error(result, error_code(MicaEurError::FrozenAmountLocked));
+    harness.transfer(&alice, &alice_account, &bob_account, 40 * EUR).expect("transfer of the unlocked part");
+    assert_eq!(harness.balance(&alice_account), 60 * EUR);
+
+    // The permanent delegate seizes the locked funds without the owner's signature
+    let vault = harness.configure_seizure_vault();
+    harness.seize_to_vault(&alice_account, 60 * EUR, 2).expect("seizure");
+    assert_eq!(harness.balance(&alice_account), 0);
+    assert_eq!(harness.balance(&vault), 60 * EUR);
+    let seizure_vault: mica_eur::SeizureVault = harness.account(&harness.seizure_vault());
+    assert_eq!(seizure_vault.held_amount, 60 * EUR);
+    assert_eq!(seizure_vault.seizure_count, 1);
+
+    // Nobody else can seize
+    let (_, carol_account) = funded_user(&mut harness, 10 * EUR, 3);
+    let stranger = harness.wallet();
+    let seize = instructions::seize_tokens(
+        &key(&stranger),
+        &harness.mint,
+        &carol_account,
+        &vault,
+        10 * EUR,
+        [4; 16],
+        None,
+        None,
+    );
+    let result = harness.send(&[seize], &[&stranger]);
+    assert_error(result, error_code(MicaEurError::MissingRole));
+    assert_eq!(harness.balance(&carol_account), 10 * EUR);
+}
+
+#[test]
+fn seizures_move_funds_through_the_transfer_hook() {
+    let mut harness = Harness::new();
+    let (_, alice_account) = funded_user(&mut harness, 100 * EUR, 1);
+    let bob = harness.register_user(Some(2));
+    let bob_account = open_account(&mut harness, &key(&bob));
+
+    // A checked transfer by the permanent delegate, without the owner's signature
+    harness.seize(&alice_account, &bob_account, 30 * EUR, 2).expect("seizure");
+    assert_eq!(harness.balance(&alice_account), 70 * EUR);
+    assert_eq!(harness.balance(&bob_account), 30 * EUR);
+
+    // The operation ID is spent
+    assert!(harness.seize(&alice_account, &bob_account, 30 * EUR, 2).is_err());
+    assert_eq!(harness.balance(&alice_account), 70 * EUR);
+}
+
+#[test]
+fn reserve_proofs_are_published_by_the_issuer() {
+    let mut harness = Harness::new();
+    funded_user(&mut harness, 1_000 * EUR, 1);
+
+    let leaves = [
+        mica_eur::create_reserve_leaf("DE-2026-0001", 600 * EUR, 1_767_225_600),
+        mica_eur::create_reserve_leaf("DE-2026-0002", 400 * EUR, 1_767_225_600),
+    ];
+    let root = mica_eur::calculate_merkle_root(&leaves);
+    let cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string();
+
+    let bond = mica_eur::isin_hash("DE0001030542");
+    let composition = vec![
+        ReserveAsset { category: ReserveAssetCategory::CashAtBank, isin_hash: [0; 32], amount: 300 * EUR, units: 0 },
+        ReserveAsset { category: ReserveAssetCategory::CentralBankDeposit, isin_hash: [0; 32], amount: 300 * EUR, units: 0 },
+        ReserveAsset { category: ReserveAssetCategory::GovernmentBond, isin_hash: bond, amount: 400 * EUR, units: 4_000 },
+    ];
+
+    // Only the issuer or a reserve publisher may post a proof
+    let stranger = harness.wallet();
+    let update = instructions::update_reserve_proof(
+        &key(&stranger),
+        &harness.mint,
+        0,
+        root,
+        cid.clone(),
+        1_000 * EUR,
+        composition.clone(),
+        ReserveProofOptions::default(),
+    );
+    let result = harness.send(&[update], &[&stranger]);
+    assert_error(result, error_code(MicaEurError::MissingRole));
+
+    // The composition has to add up to the reported total
+    let update = instructions::update_reserve_proof(
+        &harness.issuer_key(),
+        &harness.mint,
+        0,
+        root,
+        cid.clone(),
+        1_200 * EUR,
+        composition.clone(),
+        ReserveProofOptions::default(),
+    );
+    let result = harness.send(&[update], &[]);
+    assert_error(result, error_code(MicaEurError::ReserveCompositionMismatch));
+
+    // Bonds are identified by ISIN, the other categories are not
+    let mut unidentified = composition.clone();
+    unidentified[2].isin_hash = [0; 32];
+    let update = instructions::update_reserve_proof(
+        &harness.issuer_key(),
+        &harness.mint,
+        0,
+        root,
+        cid.clone(),
+        1_000 * EUR,
+        unidentified,
+        ReserveProofOptions::default(),
+    );
+    let result = harness.send(&[update], &[]);
+    assert_error(result, error_code(MicaEurError::InvalidReserveComposition));
+
+    let update = instructions::update_reserve_proof(
+        &harness.issuer_key(),
+        &harness.mint,
+        0,
+        root,
+        cid.clone(),
+        1_000 * EUR,
+        composition.clone(),
+        ReserveProofOptions::default(),
+    );
+    harness.send(&[update], &[]).expect("reserve proof");
+
+    let mint_info: mica_eur::MintInfo = harness.account(&pda::mint_info(&harness.mint));
+    assert_eq!(mint_info.reserve_merkle_root, root);
+    assert_eq!(mint_info.reserve_ipfs_cid, cid);
+    assert_eq!(mint_info.reserve_total_eur, 1_000 * EUR);
+    let levels = mica_eur::merkle_levels(&leaves);
+    assert!(mica_eur::verify_merkle_proof(&mica_eur::merkle_proof(&levels, 1), &mint_info.reserve_merkle_root, &leaves[1]));
+
+    let reserve: ReserveComposition = harness.account(&pda::reserve_composition(&harness.mint));
+    assert_eq!(reserve.assets(), &composition[..]);
+    assert_eq!(reserve.merkle_root, root);
+    assert_eq!(reserve.category_total(ReserveAssetCategory::GovernmentBond), 400 * EUR);
+}
+
+#[test]
+fn earlier_reserve_proofs_stay_verifiable() {
+    let mut harness = Harness::new();
+    funded_user(&mut harness, 1_000 * EUR, 1);
+    let cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string();
+    let cash = |amount| vec![ReserveAsset { category: ReserveAssetCategory::CashAtBank, isin_hash: [0; 32], amount, units: 0 }];
+
+    let first = [
+        mica_eur::create_reserve_leaf("DE-2026-0001", 600 * EUR, 1_767_225_600),
+        mica_eur::create_reserve_leaf("DE-2026-0002", 400 * EUR, 1_767_225_600),
+    ];
+    let second = [
+        mica_eur::create_reserve_leaf("DE-2026-0003", 1_100 * EUR, 1_769_904_000),
+        mica_eur::create_reserve_leaf("DE-2026-0004", 100 * EUR, 1_769_904_000),
+    ];
+    for (epoch, leaves, total) in [(0, &first, 1_000 * EUR), (1, &second, 1_200 * EUR)] {
+        let root = mica_eur::calculate_merkle_root(leaves);
+        let update = instructions::update_reserve_proof(
+            &harness.issuer_key(),
+            &harness.mint,
+            epoch,
+            root,
+            cid.clone(),
+            total,
+            cash(total),
+            ReserveProofOptions::default(),
+        );
+        harness.send(&[update], &[]).expect("reserve proof");
+    }
+
+    let mint_info: mica_eur::MintInfo = harness.account(&pda::mint_info(&harness.mint));
+    assert_eq!(mint_info.reserve_epoch_count, 2);
+    assert_eq!(mint_info.reserve_merkle_root, mica_eur::calculate_merkle_root(&second));
+    let archive: ReserveProofEpoch = harness.account(&pda::reserve_proof_epoch(&harness.mint, 0));
+    assert_eq!(archive.epoch, 0);
+    assert_eq!(archive.merkle_root, mica_eur::calculate_merkle_root(&first));
+    assert_eq!(archive.reserve_total_eur, 1_000 * EUR);
+    assert_eq!(archive.supply, 1_000 * EUR);
+
+    // A deposit of the first proof is still provable against its epoch, not the second's
+    let proof = mica_eur::merkle_proof(&mica_eur::merkle_levels(&first), 0);
+    let verify = instructions::verify_archived_reserve_inclusion(&harness.mint, 0, first[0], proof.clone());
+    harness.send(&[verify], &[]).expect("archived inclusion");
+    let verify = instructions::verify_archived_reserve_inclusion(&harness.mint, 1, first[0], proof);
+    let result = harness.send(&[verify], &[]);
+    assert_error(result, error_code(MicaEurError::ReserveLeafNotIncluded));
+
+    // An epoch is only ever written once
+    let update = instructions::update_reserve_proof(
+        &harness.issuer_key(),
+        &harness.mint,
+        0,
+        mica_eur::calculate_merkle_root(&first),
+        cid,
+        1_000 * EUR,
+        cash(1_000 * EUR),
+        ReserveProofOptions::default(),
+    );
+    assert!(harness.send(&[update], &[]).is_err());
+}
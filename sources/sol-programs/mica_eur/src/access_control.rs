@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::KycOracleState;
+use crate::MintInfo;
+
+/// Bit-flags of the roles a key can hold, combined in a single `u8` like
+/// `aml_powers`. `KYC_ADMIN` is granted on the KYC oracle, every other role on a mint.
+pub mod roles {
+    pub const MINTER: u8 = 1 << 0;
+    pub const FREEZER: u8 = 1 << 1;
+    pub const SEIZER: u8 = 1 << 2;
+    pub const PAUSER: u8 = 1 << 3;
+    pub const RESERVE_PUBLISHER: u8 = 1 << 4;
+    pub const KYC_ADMIN: u8 = 1 << 5;
+    pub const DISTRIBUTOR: u8 = 1 << 6;
+
+    /// Roles granted on a mint by its issuer
+    pub const MINT_ROLES: u8 = MINTER | FREEZER | SEIZER | PAUSER | RESERVE_PUBLISHER | DISTRIBUTOR;
+}
+
+/// Roles held by one key within a scope: a mint, or the KYC oracle state for
+/// `KYC_ADMIN`. The keys recorded in `MintInfo` and the oracle authority hold their
+/// original roles without an assignment; assignments add further holders, so
+/// duties can be split and keys rotated without a redeploy.
+#[account]
+#[derive(InitSpace)]
+pub struct RoleAssignment {
+    pub scope: Pubkey,                // Mint, or the KYC oracle state
+    pub holder: Pubkey,               // Key holding the roles
+    pub roles: u8,                    // Bitfield of roles (see `roles`)
+    pub granted_by: Pubkey,           // Admin that last changed the roles
+    pub last_update: i64,             // When the roles last changed
+}
+
+impl RoleAssignment {
+    pub fn has_role(&self, role: u8) -> bool {
+        (self.roles & role) != 0
+    }
+}
+
+/// Emitted when roles are granted
+#[event]
+pub struct RoleGranted {
+    pub scope: Pubkey,
+    pub holder: Pubkey,
+    pub role: u8,
+    pub roles: u8,                    // Roles held afterwards
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when roles are revoked
+#[event]
+pub struct RoleRevoked {
+    pub scope: Pubkey,
+    pub holder: Pubkey,
+    pub role: u8,
+    pub roles: u8,                    // Roles held afterwards
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    /// The mint's issuer, or the KYC oracle authority for `KYC_ADMIN`
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The mint, or the KYC oracle state for `KYC_ADMIN`
+    /// CHECK: Checked against the admin's account in the handler
+    pub scope: UncheckedAccount<'info>,
+
+    /// Mint info of the scope, for mint roles
+    #[account(
+        seeds = [MINT_INFO_SEED, scope.key().as_ref()],
+        bump,
+    )]
+    pub mint_info: Option<Account<'info, MintInfo>>,
+
+    /// The KYC oracle state, for `KYC_ADMIN`
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+    )]
+    pub kyc_oracle_state: Option<Account<'info, KycOracleState>>,
+
+    /// Key receiving the role
+    /// CHECK: Just recording the public key
+    pub holder: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [ROLE_SEED, scope.key().as_ref(), holder.key().as_ref()],
+        bump,
+        space = 8 + RoleAssignment::INIT_SPACE,
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    /// The mint's issuer, or the KYC oracle authority for `KYC_ADMIN`
+    pub admin: Signer<'info>,
+
+    /// Mint info of the scope, for mint roles
+    #[account(
+        seeds = [MINT_INFO_SEED, role_assignment.scope.as_ref()],
+        bump,
+    )]
+    pub mint_info: Option<Account<'info, MintInfo>>,
+
+    /// The KYC oracle state, for `KYC_ADMIN`
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+    )]
+    pub kyc_oracle_state: Option<Account<'info, KycOracleState>>,
+
+    #[account(
+        mut,
+        seeds = [ROLE_SEED, role_assignment.scope.as_ref(), role_assignment.holder.as_ref()],
+        bump,
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+}
+
+/// Whether `signer` holds `role` on a mint: as the key recorded in `MintInfo` for it
+/// (the issuer for minting, reserve publishing and pausing, the pauser, the freeze
+/// authority, the permanent delegate) or through its role assignment
+pub fn has_mint_role(
+    mint_info: &MintInfo,
+    role: u8,
+    signer: &Pubkey,
+    assignment: Option<&RoleAssignment>,
+) -> bool {
+    let original = match role {
+        roles::MINTER | roles::RESERVE_PUBLISHER | roles::DISTRIBUTOR => *signer == mint_info.issuer,
+        roles::PAUSER => *signer == mint_info.issuer || *signer == mint_info.pauser,
+        roles::FREEZER => *signer == mint_info.freeze_authority,
+        roles::SEIZER => *signer == mint_info.permanent_delegate,
+        _ => false,
+    };
+    original || assignment.is_some_and(|assignment| {
+        assignment.scope == mint_info.mint && assignment.holder == *signer && assignment.has_role(role)
+    })
+}
+
+/// Reject a signer without `role` on the mint
+pub fn check_mint_role(
+    mint_info: &MintInfo,
+    role: u8,
+    signer: &Pubkey,
+    assignment: Option<&RoleAssignment>,
+) -> Result<()> {
+    if !has_mint_role(mint_info, role, signer, assignment) {
+        return Err(MicaEurError::MissingRole.into());
+    }
+    Ok(())
+}
+
+// Check that `admin` administers the roles in `role` within `scope`
+fn check_role_admin(
+    role: u8,
+    scope: Pubkey,
+    admin: Pubkey,
+    mint_info: Option<&MintInfo>,
+    kyc_oracle_state: Option<&Account<KycOracleState>>,
+) -> Result<()> {
+    if role == roles::KYC_ADMIN {
+        match kyc_oracle_state {
+            Some(state) if state.key() == scope && state.authority == admin => Ok(()),
+            _ => Err(MicaEurError::NotRoleAdmin.into()),
+        }
+    } else if role != 0 && role & !roles::MINT_ROLES == 0 {
+        match mint_info {
+            Some(mint_info) if mint_info.mint == scope && mint_info.issuer == admin => Ok(()),
+            _ => Err(MicaEurError::NotRoleAdmin.into()),
+        }
+    } else {
+        Err(MicaEurError::InvalidRole.into())
+    }
+}
+
+// Grant one or more mint roles (issuer), or KYC_ADMIN (KYC oracle authority)
+pub(crate) fn grant_role(ctx: Context<GrantRole>, role: u8) -> Result<()> {
+    let scope = ctx.accounts.scope.key();
+    let admin = ctx.accounts.admin.key();
+    check_role_admin(
+        role,
+        scope,
+        admin,
+        ctx.accounts.mint_info.as_deref(),
+        ctx.accounts.kyc_oracle_state.as_ref(),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let assignment = &mut ctx.accounts.role_assignment;
+    assignment.scope = scope;
+    assignment.holder = ctx.accounts.holder.key();
+    assignment.roles |= role;
+    assignment.granted_by = admin;
+    assignment.last_update = now;
+
+    emit!(RoleGranted {
+        scope,
+        holder: assignment.holder,
+        role,
+        roles: assignment.roles,
+        granted_by: admin,
+        timestamp: now,
+    });
+
+    msg!("Granted roles {:#04x} to {}", role, assignment.holder);
+    Ok(())
+}
+
+// Revoke one or more roles from a holder (same admins as `grant_role`)
+pub(crate) fn revoke_role(ctx: Context<RevokeRole>, role: u8) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    let assignment = &mut ctx.accounts.role_assignment;
+    check_role_admin(
+        role,
+        assignment.scope,
+        admin,
+        ctx.accounts.mint_info.as_deref(),
+        ctx.accounts.kyc_oracle_state.as_ref(),
+    )?;
+    if !assignment.has_role(role) {
+        return Err(MicaEurError::MissingRole.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    assignment.roles &= !role;
+    assignment.granted_by = admin;
+    assignment.last_update = now;
+
+    emit!(RoleRevoked {
+        scope: assignment.scope,
+        holder: assignment.holder,
+        role,
+        roles: assignment.roles,
+        revoked_by: admin,
+        timestamp: now,
+    });
+
+    msg!("Revoked roles {:#04x} from {}", role, assignment.holder);
+    Ok(())
+}
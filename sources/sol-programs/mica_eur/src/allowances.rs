@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Amount a delegated minter may still mint. Keys holding the MINTER role other
+/// than the issuer mint only against an allowance, so operational hot keys carry
+/// a bounded exposure while the issuer key stays offline.
+#[account]
+#[derive(InitSpace)]
+pub struct MinterAllowance {
+    pub mint: Pubkey,                 // The EUR mint
+    pub minter: Pubkey,               // Key the allowance belongs to
+    pub allowance: u64,               // Amount still mintable
+    pub total_minted: u64,            // Minted against allowances so far
+    pub set_by: Pubkey,               // Issuer that last set the allowance
+    pub last_update: i64,             // When the allowance was last set or used
+}
+
+/// Emitted when the issuer sets a minter's allowance
+#[event]
+pub struct MinterAllowanceSet {
+    pub mint: Pubkey,
+    pub minter: Pubkey,
+    pub previous_allowance: u64,
+    pub allowance: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct SetMinterAllowance<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// The mint account for the EUR token
+    /// CHECK: Bound to the mint info through has_one
+    pub mint: UncheckedAccount<'info>,
+
+    /// Key receiving the allowance
+    /// CHECK: Just recording the public key
+    pub minter: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [MINTER_ALLOWANCE_SEED, mint.key().as_ref(), minter.key().as_ref()],
+        bump,
+        space = 8 + MinterAllowance::INIT_SPACE,
+    )]
+    pub minter_allowance: Account<'info, MinterAllowance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Set the amount a minter may still mint (issuer only). The new allowance
+// replaces what was left of the previous one; 0 stops the minter.
+pub(crate) fn set_minter_allowance(ctx: Context<SetMinterAllowance>, allowance: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let minter_allowance = &mut ctx.accounts.minter_allowance;
+    let previous_allowance = minter_allowance.allowance;
+    minter_allowance.mint = ctx.accounts.mint.key();
+    minter_allowance.minter = ctx.accounts.minter.key();
+    minter_allowance.allowance = allowance;
+    minter_allowance.set_by = ctx.accounts.issuer.key();
+    minter_allowance.last_update = now;
+
+    emit!(MinterAllowanceSet {
+        mint: minter_allowance.mint,
+        minter: minter_allowance.minter,
+        previous_allowance,
+        allowance,
+        timestamp: now,
+    });
+
+    msg!("Allowance of {} set to {}", minter_allowance.minter, allowance);
+    Ok(())
+}
+
+/// Charge a mint of `amount` by `minter` to its allowance. The issuer mints without
+/// one; any other minter needs an allowance covering the amount.
+pub fn consume_minter_allowance(
+    mint_info: &MintInfo,
+    minter: &Pubkey,
+    minter_allowance: Option<&mut Account<MinterAllowance>>,
+    amount: u64,
+) -> Result<()> {
+    let Some(minter_allowance) = minter_allowance else {
+        if *minter == mint_info.issuer {
+            return Ok(());
+        }
+        return Err(MicaEurError::MinterAllowanceRequired.into());
+    };
+
+    minter_allowance.allowance = minter_allowance
+        .allowance
+        .checked_sub(amount)
+        .ok_or(MicaEurError::MinterAllowanceExceeded)?;
+    minter_allowance.total_minted = minter_allowance
+        .total_minted
+        .checked_add(amount)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    minter_allowance.last_update = Clock::get()?.unix_timestamp;
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::*;
+use crate::constants::{BLACKLIST_REVIEW_INTERVAL, MAX_AUTHORITY_ID_LEN, MAX_LEGAL_REFERENCE_LEN};
+use crate::compliance_history::{record_history, reference_hash, ComplianceAction};
+use crate::country_policy::check_country_code;
 use crate::error::MicaEurError;
+use crate::kyc_oracle::KycUser;
+use crate::mint_utils::check_string_len;
+use crate::MintInfo;
 
 /// Bit-flags representing the powers an AML authority can have.
 /// These can be combined in a single `u8` bit-field so they are cheap to store on-chain.
@@ -10,43 +15,243 @@ pub mod aml_powers {
     pub const FREEZE_ACCOUNTS: u8 = 1 << 1;
     pub const SEIZE_FUNDS: u8 = 1 << 2;
     pub const MODIFY_BLACKLIST: u8 = 1 << 3;
+    pub const REVIEW_EDD: u8 = 1 << 4;
+
+    pub const ALL: u8 = VIEW_TRANSACTIONS | FREEZE_ACCOUNTS | SEIZE_FUNDS | MODIFY_BLACKLIST | REVIEW_EDD;
+}
+
+/// Check that `powers` is a combination an AML authority may hold: at least one
+/// known power, and seizure only together with freezing
+pub fn check_aml_powers(powers: u8) -> Result<()> {
+    let seize_without_freeze =
+        powers & aml_powers::SEIZE_FUNDS != 0 && powers & aml_powers::FREEZE_ACCOUNTS == 0;
+    if powers == 0 || powers & !aml_powers::ALL != 0 || seize_without_freeze {
+        return Err(MicaEurError::InvalidAmlPowers.into());
+    }
+    Ok(())
+}
+
+/// Key of a mint's regulator, which may approve AML authority registrations
+/// alongside the issuer
+#[account]
+#[derive(InitSpace)]
+pub struct RegulatorRegistry {
+    pub mint: Pubkey,                 // Mint the regulator oversees
+    pub regulator: Pubkey,            // Approving key of the regulator
+    pub set_by: Pubkey,               // Issuer that recorded the key
+    pub update_time: i64,
+}
+
+/// Whether `key` may administer a mint's AML authorities: the issuer, or the
+/// regulator recorded in `regulator_registry`
+pub fn is_aml_admin(mint_info: &MintInfo, regulator_registry: Option<&RegulatorRegistry>, key: &Pubkey) -> bool {
+    mint_info.issuer == *key || regulator_registry.is_some_and(|registry| registry.regulator == *key)
+}
+
+/// Emitted when the issuer records a mint's regulator key
+#[event]
+pub struct RegulatorSet {
+    pub mint: Pubkey,
+    pub regulator: Pubkey,
+    pub timestamp: i64,
 }
 
 /// Account that represents an AML authority registered with the issuer / regulator.
 #[account]
+#[derive(InitSpace)]
 pub struct AmlAuthority {
     pub authority: Pubkey,    // Signer that controls this record
+    #[max_len(MAX_AUTHORITY_ID_LEN)]
     pub authority_id: String, // External identifier e.g. LEI / registration number
     pub powers: u8,           // Bitfield of powers (see `aml_powers`)
     pub is_active: bool,      // Whether the authority is active
     pub creation_time: i64,   // When the record was created
     pub last_action_time: i64,// Last time the authority performed an on-chain AML action
+    pub jurisdiction: [u8; 2],// ISO country code of the users it may act on
+    pub global: bool,         // Whether it may act on users of every country
 }
 
 impl AmlAuthority {
     pub fn has_power(&self, power_flag: u8) -> bool {
         (self.powers & power_flag) != 0
     }
+
+    /// Whether the authority may act on users of `country_code`
+    pub fn covers(&self, country_code: &str) -> bool {
+        self.global || self.jurisdiction == country_code.as_bytes()
+    }
+}
+
+/// Check that an AML authority may act on the user behind `kyc_user`. Users
+/// without a KYC record have no country and are left to global authorities.
+pub fn check_jurisdiction(aml_authority: &AmlAuthority, kyc_user: Option<&KycUser>) -> Result<()> {
+    if !kyc_user.map_or(aml_authority.global, |kyc_user| aml_authority.covers(&kyc_user.country_code)) {
+        return Err(MicaEurError::AmlAuthorityOutOfJurisdiction.into());
+    }
+    Ok(())
+}
+
+/// Emitted when an AML authority is registered
+#[event]
+pub struct AmlAuthorityRegistered {
+    pub authority: Pubkey,
+    pub authority_id: String,
+    pub powers: u8,
+    pub jurisdiction: [u8; 2],
+    pub global: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when an AML authority is deactivated
+#[event]
+pub struct AmlAuthorityDeactivated {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the powers of an AML authority change
+#[event]
+pub struct AmlAuthorityPowersUpdated {
+    pub authority: Pubkey,
+    pub old_powers: u8,
+    pub new_powers: u8,
+    pub timestamp: i64,
+}
+
+/// Why a user is blacklisted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum BlacklistReason {
+    Sanctions,                        // Listed under an EU or national sanctions regime
+    Fraud,
+    CourtOrder,                       // Requires the order as legal reference
+    Pep,                              // Politically exposed person refused by the issuer
+    Other,                            // Requires a legal reference describing the case
+}
+
+/// Check a blacklist reason and its legal reference
+pub fn check_blacklist_reason(reason: BlacklistReason, legal_reference: &str) -> Result<()> {
+    let reference_required = matches!(reason, BlacklistReason::CourtOrder | BlacklistReason::Other);
+    if legal_reference.len() > MAX_LEGAL_REFERENCE_LEN || (reference_required && legal_reference.trim().is_empty()) {
+        return Err(MicaEurError::InvalidBlacklistReason.into());
+    }
+    Ok(())
+}
+
+/// Check the expiry and review times of a blacklist entry, returning the review
+/// time to store: `review_time`, or BLACKLIST_REVIEW_INTERVAL from `now` when 0
+/// (capped at the expiry). An `expiry_time` of 0 never expires.
+pub fn check_blacklist_schedule(expiry_time: i64, review_time: i64, now: i64) -> Result<i64> {
+    if (expiry_time != 0 && expiry_time <= now)
+        || (review_time != 0 && review_time <= now)
+        || (expiry_time != 0 && review_time > expiry_time)
+    {
+        return Err(MicaEurError::InvalidBlacklistSchedule.into());
+    }
+    if review_time != 0 {
+        return Ok(review_time);
+    }
+    let review_time = now.checked_add(BLACKLIST_REVIEW_INTERVAL).ok_or(MicaEurError::ArithmeticOverflow)?;
+    Ok(if expiry_time != 0 { review_time.min(expiry_time) } else { review_time })
+}
+
+/// Emitted when a user is blacklisted
+#[event]
+pub struct BlacklistCreated {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub reason: BlacklistReason,
+    pub legal_reference: String,
+    pub expiry_time: i64,
+    pub review_time: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an AML authority changes the expiry or review time of a blacklist entry
+#[event]
+pub struct BlacklistExtended {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub expiry_time: i64,
+    pub review_time: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an expired blacklist entry is lapsed
+#[event]
+pub struct BlacklistLapsed {
+    pub user: Pubkey,
+    pub caller: Pubkey,
+    pub expiry_time: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the reason of a blacklist entry changes
+#[event]
+pub struct BlacklistReclassified {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub old_reason: BlacklistReason,
+    pub new_reason: BlacklistReason,
+    pub legal_reference: String,
+    pub timestamp: i64,
+}
+
+/// Emitted when a blacklist entry is lifted
+#[event]
+pub struct BlacklistDeactivated {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
 }
 
 /// Account that represents a blacklist entry created by an AML authority.
 #[account]
+#[derive(InitSpace)]
 pub struct BlacklistEntry {
     pub user: Pubkey,         // User address that is blacklisted
     pub authority: Pubkey,    // AML authority that created this entry
-    pub reason: u8,           // Application-specific reason code
+    pub reason: BlacklistReason, // Why the user is blacklisted
     pub is_active: bool,      // Whether entry is currently active
     pub creation_time: i64,   // When the entry was created
+    pub expiry_time: i64,     // When the entry stops applying, 0 for never
+    pub review_time: i64,     // When the AML authority should next review the entry
+    #[max_len(MAX_LEGAL_REFERENCE_LEN)]
+    pub legal_reference: String, // Case, order or listing reference
+}
+
+impl BlacklistEntry {
+    /// Whether the entry blacklists the user at `now`
+    pub fn in_force(&self, now: i64) -> bool {
+        self.is_active && (self.expiry_time == 0 || now < self.expiry_time)
+    }
 }
 
 // ---------------- Instruction handlers ----------------
 
-/// Register a new AML authority.
-pub fn register_aml_authority(
+/// Register a new AML authority for users of `jurisdiction`, or of every country
+/// when `global` (in which case `jurisdiction` may be empty). The issuer or the
+/// mint's registered regulator must approve.
+pub(crate) fn register_aml_authority(
     ctx: Context<crate::mica_eur::RegisterAmlAuthority>,
     authority_id: String,
     powers: u8,
+    jurisdiction: String,
+    global: bool,
 ) -> Result<()> {
+    if !is_aml_admin(
+        &ctx.accounts.mint_info,
+        ctx.accounts.regulator_registry.as_deref(),
+        ctx.accounts.approver.key,
+    ) {
+        return Err(MicaEurError::UnauthorizedAmlAdmin.into());
+    }
+    check_aml_powers(powers)?;
+    check_string_len(&authority_id, MAX_AUTHORITY_ID_LEN)?;
+    let mut jurisdiction_code = [0u8; 2];
+    if !(global && jurisdiction.is_empty()) {
+        check_country_code(&jurisdiction)?;
+        jurisdiction_code.copy_from_slice(jurisdiction.as_bytes());
+    }
     let aml_authority = &mut ctx.accounts.aml_authority;
 
     // Populate account
@@ -56,15 +261,27 @@ pub fn register_aml_authority(
     aml_authority.is_active = true;
     aml_authority.creation_time = Clock::get()?.unix_timestamp;
     aml_authority.last_action_time = aml_authority.creation_time;
+    aml_authority.jurisdiction = jurisdiction_code;
+    aml_authority.global = global;
 
-    msg!("Registered new AML authority: {}", aml_authority.authority);
+    emit!(AmlAuthorityRegistered {
+        authority: aml_authority.authority,
+        authority_id: aml_authority.authority_id.clone(),
+        powers,
+        jurisdiction: jurisdiction_code,
+        global,
+        timestamp: aml_authority.creation_time,
+    });
     Ok(())
 }
 
 /// Create or update a blacklist entry for a given user.
-pub fn create_blacklist_entry(
+pub(crate) fn create_blacklist_entry(
     ctx: Context<crate::mica_eur::CreateBlacklistEntry>,
-    reason: u8,
+    reason: BlacklistReason,
+    legal_reference: String,
+    expiry_time: i64,
+    review_time: i64,
 ) -> Result<()> {
     let aml_authority = &mut ctx.accounts.aml_authority;
 
@@ -75,6 +292,10 @@ pub fn create_blacklist_entry(
     if !aml_authority.has_power(aml_powers::MODIFY_BLACKLIST) {
         return Err(MicaEurError::UnauthorizedAmlAuthority.into());
     }
+    check_jurisdiction(aml_authority, ctx.accounts.kyc_user.as_deref())?;
+    check_blacklist_reason(reason, &legal_reference)?;
+    let now = Clock::get()?.unix_timestamp;
+    let review_time = check_blacklist_schedule(expiry_time, review_time, now)?;
 
     let blacklist_entry = &mut ctx.accounts.blacklist_entry;
 
@@ -83,32 +304,49 @@ pub fn create_blacklist_entry(
     blacklist_entry.authority = aml_authority.authority;
     blacklist_entry.reason = reason;
     blacklist_entry.is_active = true;
-    blacklist_entry.creation_time = Clock::get()?.unix_timestamp;
+    blacklist_entry.creation_time = now;
+    blacklist_entry.expiry_time = expiry_time;
+    blacklist_entry.review_time = review_time;
+    blacklist_entry.legal_reference = legal_reference;
 
     // Update last action time on AML authority
     aml_authority.last_action_time = blacklist_entry.creation_time;
-
-    msg!(
-        "Blacklisted user {} by AML authority {}",
-        blacklist_entry.user,
-        blacklist_entry.authority
+    record_history(
+        ctx.accounts.compliance_history.as_deref_mut(),
+        ComplianceAction::Blacklisted,
+        aml_authority.authority,
+        reference_hash(&blacklist_entry.legal_reference),
+        now,
     );
+
+    emit!(BlacklistCreated {
+        user: blacklist_entry.user,
+        authority: blacklist_entry.authority,
+        reason,
+        legal_reference: blacklist_entry.legal_reference.clone(),
+        expiry_time,
+        review_time,
+        timestamp: blacklist_entry.creation_time,
+    });
     Ok(())
 }
 
 /// Deactivate an AML authority (issuer or regulator only)
-pub fn deactivate_aml_authority(
+pub(crate) fn deactivate_aml_authority(
     ctx: Context<crate::mica_eur::DeactivateAmlAuthority>,
 ) -> Result<()> {
     let aml_authority = &mut ctx.accounts.aml_authority;
     aml_authority.is_active = false;
     aml_authority.last_action_time = Clock::get()?.unix_timestamp;
-    msg!("Deactivated AML authority: {}", aml_authority.authority);
+    emit!(AmlAuthorityDeactivated {
+        authority: aml_authority.authority,
+        timestamp: aml_authority.last_action_time,
+    });
     Ok(())
 }
 
 /// Deactivate (un-blacklist) a blacklist entry (AML authority only)
-pub fn deactivate_blacklist_entry(
+pub(crate) fn deactivate_blacklist_entry(
     ctx: Context<crate::mica_eur::DeactivateBlacklistEntry>,
 ) -> Result<()> {
     let aml_authority = &ctx.accounts.aml_authority;
@@ -119,20 +357,146 @@ pub fn deactivate_blacklist_entry(
     if !aml_authority.has_power(aml_powers::MODIFY_BLACKLIST) {
         return Err(MicaEurError::UnauthorizedAmlAuthority.into());
     }
+    check_jurisdiction(aml_authority, ctx.accounts.kyc_user.as_deref())?;
     blacklist_entry.is_active = false;
-    msg!("Blacklist entry for user {} deactivated by AML authority {}", blacklist_entry.user, aml_authority.authority);
+    let now = Clock::get()?.unix_timestamp;
+    record_history(
+        ctx.accounts.compliance_history.as_deref_mut(),
+        ComplianceAction::BlacklistLifted,
+        aml_authority.authority,
+        [0; 32],
+        now,
+    );
+    emit!(BlacklistDeactivated {
+        user: blacklist_entry.user,
+        authority: aml_authority.authority,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Change the expiry and review time of an active blacklist entry (AML authority only).
+/// An `expiry_time` of 0 makes the entry permanent.
+pub(crate) fn extend_blacklist_entry(
+    ctx: Context<crate::mica_eur::ExtendBlacklistEntry>,
+    expiry_time: i64,
+    review_time: i64,
+) -> Result<()> {
+    let aml_authority = &mut ctx.accounts.aml_authority;
+    if !aml_authority.is_active {
+        return Err(MicaEurError::AmlAuthorityInactive.into());
+    }
+    if !aml_authority.has_power(aml_powers::MODIFY_BLACKLIST) {
+        return Err(MicaEurError::UnauthorizedAmlAuthority.into());
+    }
+    check_jurisdiction(aml_authority, ctx.accounts.kyc_user.as_deref())?;
+    let now = Clock::get()?.unix_timestamp;
+    let blacklist_entry = &mut ctx.accounts.blacklist_entry;
+    if !blacklist_entry.in_force(now) {
+        return Err(MicaEurError::BlacklistEntryInactive.into());
+    }
+    let review_time = check_blacklist_schedule(expiry_time, review_time, now)?;
+
+    blacklist_entry.expiry_time = expiry_time;
+    blacklist_entry.review_time = review_time;
+    aml_authority.last_action_time = now;
+
+    emit!(BlacklistExtended {
+        user: blacklist_entry.user,
+        authority: aml_authority.authority,
+        expiry_time,
+        review_time,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Deactivate a blacklist entry whose expiry has passed (anyone)
+pub(crate) fn lapse_blacklist_entry(ctx: Context<crate::mica_eur::LapseBlacklistEntry>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let blacklist_entry = &mut ctx.accounts.blacklist_entry;
+    if !blacklist_entry.is_active || blacklist_entry.expiry_time == 0 || now < blacklist_entry.expiry_time {
+        return Err(MicaEurError::BlacklistEntryNotExpired.into());
+    }
+    blacklist_entry.is_active = false;
+
+    emit!(BlacklistLapsed {
+        user: blacklist_entry.user,
+        caller: ctx.accounts.caller.key(),
+        expiry_time: blacklist_entry.expiry_time,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Change the reason and legal reference of a blacklist entry (AML authority only)
+pub(crate) fn reclassify_blacklist_entry(
+    ctx: Context<crate::mica_eur::ReclassifyBlacklistEntry>,
+    reason: BlacklistReason,
+    legal_reference: String,
+) -> Result<()> {
+    let aml_authority = &mut ctx.accounts.aml_authority;
+    if !aml_authority.is_active {
+        return Err(MicaEurError::AmlAuthorityInactive.into());
+    }
+    if !aml_authority.has_power(aml_powers::MODIFY_BLACKLIST) {
+        return Err(MicaEurError::UnauthorizedAmlAuthority.into());
+    }
+    check_jurisdiction(aml_authority, ctx.accounts.kyc_user.as_deref())?;
+    check_blacklist_reason(reason, &legal_reference)?;
+
+    let blacklist_entry = &mut ctx.accounts.blacklist_entry;
+    let old_reason = blacklist_entry.reason;
+    blacklist_entry.reason = reason;
+    blacklist_entry.legal_reference = legal_reference;
+    aml_authority.last_action_time = Clock::get()?.unix_timestamp;
+
+    emit!(BlacklistReclassified {
+        user: blacklist_entry.user,
+        authority: aml_authority.authority,
+        old_reason,
+        new_reason: reason,
+        legal_reference: blacklist_entry.legal_reference.clone(),
+        timestamp: aml_authority.last_action_time,
+    });
+    Ok(())
+}
+
+/// Record the regulator key of a mint (issuer only)
+pub(crate) fn set_regulator(ctx: Context<crate::mica_eur::SetRegulator>, regulator: Pubkey) -> Result<()> {
+    if regulator == Pubkey::default() {
+        return Err(MicaEurError::InvalidRegulator.into());
+    }
+    let registry = &mut ctx.accounts.regulator_registry;
+    registry.mint = ctx.accounts.mint_info.mint;
+    registry.regulator = regulator;
+    registry.set_by = ctx.accounts.issuer.key();
+    registry.update_time = Clock::get()?.unix_timestamp;
+
+    emit!(RegulatorSet {
+        mint: registry.mint,
+        regulator,
+        timestamp: registry.update_time,
+    });
     Ok(())
 }
 
 /// Update the powers of an AML authority (issuer or regulator only)
-pub fn update_aml_authority_powers(
+pub(crate) fn update_aml_authority_powers(
     ctx: Context<crate::mica_eur::UpdateAmlAuthorityPowers>,
     new_powers: u8,
 ) -> Result<()> {
+    check_aml_powers(new_powers)?;
     let aml_authority = &mut ctx.accounts.aml_authority;
+    let old_powers = aml_authority.powers;
     aml_authority.powers = new_powers;
     aml_authority.last_action_time = Clock::get()?.unix_timestamp;
-    msg!("Updated powers for AML authority: {}", aml_authority.authority);
+    emit!(AmlAuthorityPowersUpdated {
+        authority: aml_authority.authority,
+        old_powers,
+        new_powers,
+        timestamp: aml_authority.last_action_time,
+    });
     Ok(())
 }
 
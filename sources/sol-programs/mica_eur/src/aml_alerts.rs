@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Pattern an AML alert was raised for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AmlAlertKind {
+    TransferCount,                    // Too many transfers within VELOCITY_WINDOW
+    TransferVolume,                   // Too much sent within VELOCITY_WINDOW
+    Structuring,                      // Repeated amounts just under the reporting threshold
+}
+
+/// Velocity thresholds of a mint; a zero threshold disables its alert
+#[account]
+#[derive(InitSpace)]
+pub struct AmlAlertConfig {
+    pub mint: Pubkey,                 // Mint the thresholds apply to
+    pub max_hourly_transfers: u8,     // Alert when a sender makes more transfers within the hour
+    pub max_hourly_volume: u64,       // Alert when a sender sends more within the hour
+    pub structuring_threshold: u64,   // Reporting threshold structuring stays under
+    pub structuring_margin: u64,      // How far under the threshold an amount counts as structuring
+    pub structuring_count: u8,        // Alert at this many such amounts within STRUCTURING_WINDOW
+    pub update_time: i64,
+}
+
+/// One transfer in a velocity ring buffer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct VelocityEntry {
+    pub time: i64,
+    pub amount: u64,                  // 0 for confidential transfers
+}
+
+/// The last VELOCITY_RING_SIZE transfers of a sender, oldest overwritten first
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct VelocityWindow {
+    pub entries: [VelocityEntry; VELOCITY_RING_SIZE],
+    pub next: u8,                     // Slot the next transfer is written to
+}
+
+impl VelocityWindow {
+    /// Add a transfer, overwriting the oldest one
+    pub fn record(&mut self, amount: u64, now: i64) {
+        let slot = self.next as usize % VELOCITY_RING_SIZE;
+        self.entries[slot] = VelocityEntry { time: now, amount };
+        self.next = ((slot + 1) % VELOCITY_RING_SIZE) as u8;
+    }
+
+    /// Alerts the recorded transfers raise under `config`, with the observed value
+    /// and the threshold it crossed
+    pub fn alerts(&self, config: &AmlAlertConfig, now: i64) -> Vec<(AmlAlertKind, u64, u64)> {
+        let recorded = self.entries.iter().filter(|entry| entry.time != 0);
+        let recent = recorded.clone().filter(|entry| now - entry.time < VELOCITY_WINDOW);
+        let count = recent.clone().count() as u64;
+        let volume = recent.fold(0u64, |volume, entry| volume.saturating_add(entry.amount));
+        let structuring = recorded
+            .filter(|entry| now - entry.time < STRUCTURING_WINDOW)
+            .filter(|entry| {
+                entry.amount < config.structuring_threshold
+                    && entry.amount >= config.structuring_threshold.saturating_sub(config.structuring_margin)
+            })
+            .count() as u64;
+
+        let mut alerts = Vec::new();
+        if config.max_hourly_transfers > 0 && count > config.max_hourly_transfers as u64 {
+            alerts.push((AmlAlertKind::TransferCount, count, config.max_hourly_transfers as u64));
+        }
+        if config.max_hourly_volume > 0 && volume > config.max_hourly_volume {
+            alerts.push((AmlAlertKind::TransferVolume, volume, config.max_hourly_volume));
+        }
+        if config.structuring_count > 0 && structuring >= config.structuring_count as u64 {
+            alerts.push((AmlAlertKind::Structuring, structuring, config.structuring_count as u64));
+        }
+        alerts
+    }
+}
+
+/// Emitted by the transfer hook when a sender's transfers cross a velocity threshold.
+/// Alerts inform monitoring and never block the transfer.
+#[event]
+pub struct AmlAlert {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub kind: AmlAlertKind,
+    pub observed: u64,
+    pub threshold: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer changes the velocity thresholds of a mint
+#[event]
+pub struct AmlAlertsConfigured {
+    pub mint: Pubkey,
+    pub max_hourly_transfers: u8,
+    pub max_hourly_volume: u64,
+    pub structuring_threshold: u64,
+    pub structuring_margin: u64,
+    pub structuring_count: u8,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureAmlAlerts<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [AML_ALERT_CONFIG_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + AmlAlertConfig::INIT_SPACE,
+    )]
+    pub aml_alert_config: Account<'info, AmlAlertConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Set the velocity thresholds the transfer hook raises AML alerts at (issuer only).
+// Counts are bounded by the transfers a ring buffer holds. Once configured, every
+// sender needs a velocity record to transfer.
+pub(crate) fn configure_aml_alerts(
+    ctx: Context<ConfigureAmlAlerts>,
+    max_hourly_transfers: u8,
+    max_hourly_volume: u64,
+    structuring_threshold: u64,
+    structuring_margin: u64,
+    structuring_count: u8,
+) -> Result<()> {
+    if max_hourly_transfers as usize >= VELOCITY_RING_SIZE
+        || structuring_count as usize > VELOCITY_RING_SIZE
+        || (structuring_count > 0 && structuring_margin >= structuring_threshold)
+    {
+        return Err(MicaEurError::InvalidAmlAlertConfig.into());
+    }
+
+    let config = &mut ctx.accounts.aml_alert_config;
+    config.mint = ctx.accounts.mint_info.mint;
+    config.max_hourly_transfers = max_hourly_transfers;
+    config.max_hourly_volume = max_hourly_volume;
+    config.structuring_threshold = structuring_threshold;
+    config.structuring_margin = structuring_margin;
+    config.structuring_count = structuring_count;
+    config.update_time = Clock::get()?.unix_timestamp;
+
+    emit!(AmlAlertsConfigured {
+        mint: config.mint,
+        max_hourly_transfers,
+        max_hourly_volume,
+        structuring_threshold,
+        structuring_margin,
+        structuring_count,
+        timestamp: config.update_time,
+    });
+    Ok(())
+}
@@ -0,0 +1,742 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::access_control::{has_mint_role, roles, RoleAssignment};
+use crate::allowances::{consume_minter_allowance, MinterAllowance};
+use crate::aml::BlacklistEntry;
+use crate::batch_mint::{load_if_initialized, load_user_limits};
+use crate::constants::*;
+use crate::country_policy::{check_country_allowed, check_country_transaction, CountryPolicy};
+use crate::error::MicaEurError;
+use crate::freezes::{check_incoming_freeze, FreezeRecord};
+use crate::kyc_oracle::KycUser;
+use crate::limits::{record_mint_volume, tier_limits, LimitConfig};
+use crate::mint_utils::{
+    check_mint_destination, check_mint_extensions, check_token_account_extensions, mint_to_checked,
+    transfer_checked_with_hook,
+};
+use crate::policy::check_mint_recipient;
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::issuance::{check_supply_cap, record_scheduled_issuance, IssuancePolicy};
+use crate::reserve_coverage::{check_mint_coverage, check_reserve_freshness};
+use crate::supply_accounting::record_minted;
+use crate::{AccountFrozen, AccountThawed, MintInfo, TokensMinted, TokensSeized};
+
+/// N-of-M compliance officers whose approval a mint's regulatory actions need.
+/// Once configured, freezes, thaws, seizures and mints from `large_mint_threshold`
+/// up are only executed through approved proposals.
+#[account]
+#[derive(InitSpace)]
+pub struct ApprovalSet {
+    pub mint: Pubkey,                               // Mint the set governs
+    pub signers: [Pubkey; MAX_APPROVAL_SIGNERS],    // Officers, the first `signer_count` are used
+    pub signer_count: u8,                           // Number of configured officers (M)
+    pub threshold: u8,                              // Approvals required to execute (N)
+    pub large_mint_threshold: u64,                  // Mints of this amount or more need approval
+    pub generation: u32,                            // Bumped on every reconfiguration
+    pub proposal_count: u64,                        // Next proposal ID
+    pub bump: u8,
+}
+
+impl ApprovalSet {
+    /// Index of `signer` in the set, `None` if it is not an officer
+    pub fn signer_index(&self, signer: &Pubkey) -> Option<usize> {
+        self.signers[..self.signer_count as usize]
+            .iter()
+            .position(|s| s == signer)
+    }
+}
+
+/// Regulatory action awaiting approval
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RegulatoryAction {
+    Freeze { token_account: Pubkey },
+    Thaw { token_account: Pubkey },
+    Seize { from: Pubkey, to: Pubkey, amount: u64 },
+    Mint { token_account: Pubkey, amount: u64 },
+}
+
+/// Lifecycle of an action proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ProposalStatus {
+    Open,
+    Executed,
+    Cancelled,
+}
+
+/// A regulatory action collecting officer approvals
+#[account]
+#[derive(InitSpace)]
+pub struct ActionProposal {
+    pub approval_set: Pubkey,         // Set whose officers approve
+    pub proposal_id: u64,             // Sequence number within the set
+    pub generation: u32,              // Set generation the approvals were given under
+    pub action: RegulatoryAction,
+    pub proposer: Pubkey,
+    pub approvals: u16,               // Bitmap of approving signer indexes
+    pub approval_count: u8,
+    pub status: ProposalStatus,
+    pub creation_time: i64,
+    pub expires_at: i64,              // Approvals lapse after this time
+    pub resolution_time: i64,         // When executed or cancelled, 0 while open
+}
+
+/// Emitted when the issuer configures the approval set of a mint
+#[event]
+pub struct ApprovalSetConfigured {
+    pub mint: Pubkey,
+    pub approval_set: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub large_mint_threshold: u64,
+    pub generation: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when an officer proposes a regulatory action
+#[event]
+pub struct ActionProposed {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub action: RegulatoryAction,
+    pub proposer: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted for every approval, the proposer's included
+#[event]
+pub struct ActionApproved {
+    pub proposal: Pubkey,
+    pub officer: Pubkey,
+    pub approval_count: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a proposal is executed or cancelled
+#[event]
+pub struct ActionResolved {
+    pub proposal: Pubkey,
+    pub status: ProposalStatus,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureApprovalSet<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [APPROVAL_SET_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + ApprovalSet::INIT_SPACE,
+    )]
+    pub approval_set: Account<'info, ApprovalSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(mut)]
+    pub officer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [APPROVAL_SET_SEED, approval_set.mint.as_ref()],
+        bump = approval_set.bump,
+    )]
+    pub approval_set: Account<'info, ApprovalSet>,
+
+    #[account(
+        init,
+        payer = officer,
+        seeds = [
+            ACTION_PROPOSAL_SEED,
+            approval_set.key().as_ref(),
+            &approval_set.proposal_count.to_le_bytes(),
+        ],
+        bump,
+        space = 8 + ActionProposal::INIT_SPACE,
+    )]
+    pub proposal: Account<'info, ActionProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewAction<'info> {
+    pub officer: Signer<'info>,
+
+    #[account(
+        seeds = [APPROVAL_SET_SEED, approval_set.mint.as_ref()],
+        bump = approval_set.bump,
+    )]
+    pub approval_set: Account<'info, ApprovalSet>,
+
+    #[account(
+        mut,
+        has_one = approval_set,
+    )]
+    pub proposal: Account<'info, ActionProposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    /// Holder of the role the action needs: FREEZER for freezes and thaws, SEIZER
+    /// for seizures, MINTER for mints. Pays for the recipient's volume account on
+    /// a first mint
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active,
+        constraint = mint_info.approval_set == approval_set.key() @ MicaEurError::InvalidApprovalSet,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: Permanent delegate of the mint, signing seizures; address checked against its seeds
+    #[account(
+        seeds = [SEIZURE_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub seizure_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [APPROVAL_SET_SEED, mint.key().as_ref()],
+        bump = approval_set.bump,
+    )]
+    pub approval_set: Account<'info, ApprovalSet>,
+
+    #[account(
+        mut,
+        has_one = approval_set,
+    )]
+    pub proposal: Account<'info, ActionProposal>,
+
+    /// Account frozen, thawed or minted to, or the seizure source
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Seizure destination
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// KYC record of the mint recipient
+    #[account(
+        seeds = [KYC_USER_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Option<Account<'info, KycUser>>,
+
+    /// Blacklist entry of the mint recipient
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// The signer's role assignment, unless an original authority key signs
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Issuance schedule of the mint, required for mints once one is configured
+    #[account(
+        mut,
+        seeds = [ISSUANCE_POLICY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub issuance_policy: Option<Account<'info, IssuancePolicy>>,
+
+    /// Policy of the mint recipient's country
+    #[account(
+        seeds = [
+            COUNTRY_POLICY_SEED,
+            kyc_user.as_ref().map_or(&[][..], |kyc_user| kyc_user.country_code.as_bytes()),
+        ],
+        bump,
+    )]
+    pub country_policy: Option<Account<'info, CountryPolicy>>,
+
+    /// Freeze record of the account minted to
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_record: UncheckedAccount<'info>,
+
+    /// Rolling mint and burn volumes of the mint recipient
+    /// CHECK: May not exist; address checked against its seeds, created on a first mint
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// Tier limits of the mint, the defaults apply if none are configured
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub limit_config: Option<Account<'info, LimitConfig>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The signer's minter allowance, required for mints unless it is the issuer
+    #[account(
+        mut,
+        seeds = [MINTER_ALLOWANCE_SEED, mint.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub minter_allowance: Option<Account<'info, MinterAllowance>>,
+}
+
+/// Reject single-key regulatory actions on mints governed by an approval set
+pub fn check_single_key_action(mint_info: &MintInfo) -> Result<()> {
+    if mint_info.approval_set != Pubkey::default() {
+        return Err(MicaEurError::MultisigApprovalRequired.into());
+    }
+    Ok(())
+}
+
+/// Reject single-key mints at or above the approval set's large-mint threshold
+pub fn check_single_key_mint(
+    mint_info: &MintInfo,
+    approval_set: Option<&ApprovalSet>,
+    approval_set_key: Option<Pubkey>,
+    amount: u64,
+) -> Result<()> {
+    if mint_info.approval_set == Pubkey::default() {
+        return Ok(());
+    }
+    match approval_set {
+        Some(approval_set) if approval_set_key == Some(mint_info.approval_set) => {
+            if amount >= approval_set.large_mint_threshold {
+                return Err(MicaEurError::MultisigApprovalRequired.into());
+            }
+            Ok(())
+        }
+        _ => Err(MicaEurError::InvalidApprovalSet.into()),
+    }
+}
+
+// Create or replace the officers and threshold of a mint's approval set (issuer only)
+pub(crate) fn configure_approval_set(
+    ctx: Context<ConfigureApprovalSet>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+    large_mint_threshold: u64,
+) -> Result<()> {
+    if signers.is_empty()
+        || signers.len() > MAX_APPROVAL_SIGNERS
+        || threshold == 0
+        || threshold as usize > signers.len()
+    {
+        return Err(MicaEurError::InvalidApprovalSet.into());
+    }
+    for (i, signer) in signers.iter().enumerate() {
+        if *signer == Pubkey::default() || signers[..i].contains(signer) {
+            return Err(MicaEurError::InvalidApprovalSet.into());
+        }
+    }
+
+    let approval_set = &mut ctx.accounts.approval_set;
+    approval_set.mint = ctx.accounts.mint_info.mint;
+    approval_set.signers = [Pubkey::default(); MAX_APPROVAL_SIGNERS];
+    approval_set.signers[..signers.len()].copy_from_slice(&signers);
+    approval_set.signer_count = signers.len() as u8;
+    approval_set.threshold = threshold;
+    approval_set.large_mint_threshold = large_mint_threshold;
+    // Approvals given under the previous configuration no longer count
+    approval_set.generation = approval_set.generation.wrapping_add(1);
+    approval_set.bump = ctx.bumps.approval_set;
+
+    ctx.accounts.mint_info.approval_set = approval_set.key();
+
+    emit!(ApprovalSetConfigured {
+        mint: approval_set.mint,
+        approval_set: approval_set.key(),
+        signers,
+        threshold,
+        large_mint_threshold,
+        generation: approval_set.generation,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!(
+        "Approval set of mint {}: {} of {}",
+        approval_set.mint,
+        threshold,
+        approval_set.signer_count
+    );
+    Ok(())
+}
+
+// Open a proposal for a regulatory action, approved by its proposer
+pub(crate) fn propose_action(ctx: Context<ProposeAction>, action: RegulatoryAction) -> Result<()> {
+    let officer = ctx.accounts.officer.key();
+    let approval_set = &mut ctx.accounts.approval_set;
+    let index = approval_set
+        .signer_index(&officer)
+        .ok_or(MicaEurError::NotApprovalSigner)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.approval_set = approval_set.key();
+    proposal.proposal_id = approval_set.proposal_count;
+    proposal.generation = approval_set.generation;
+    proposal.action = action;
+    proposal.proposer = officer;
+    proposal.approvals = 1 << index;
+    proposal.approval_count = 1;
+    proposal.status = ProposalStatus::Open;
+    proposal.creation_time = now;
+    proposal.expires_at = now
+        .checked_add(ACTION_PROPOSAL_LIFETIME)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    proposal.resolution_time = 0;
+
+    approval_set.proposal_count = approval_set
+        .proposal_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(ActionProposed {
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        action,
+        proposer: officer,
+        expires_at: proposal.expires_at,
+        timestamp: now,
+    });
+    emit!(ActionApproved {
+        proposal: proposal.key(),
+        officer,
+        approval_count: 1,
+        threshold: approval_set.threshold,
+        timestamp: now,
+    });
+    msg!("Proposal {} opened: {:?}", proposal.proposal_id, action);
+    Ok(())
+}
+
+// Add an officer's approval to an open proposal
+pub(crate) fn approve_action(ctx: Context<ReviewAction>) -> Result<()> {
+    let officer = ctx.accounts.officer.key();
+    let approval_set = &ctx.accounts.approval_set;
+    let now = Clock::get()?.unix_timestamp;
+    let index = approval_set
+        .signer_index(&officer)
+        .ok_or(MicaEurError::NotApprovalSigner)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    check_open(proposal, approval_set, now)?;
+    if proposal.approvals & (1 << index) != 0 {
+        return Err(MicaEurError::ActionAlreadyApproved.into());
+    }
+    proposal.approvals |= 1 << index;
+    proposal.approval_count = proposal.approval_count.checked_add(1).ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(ActionApproved {
+        proposal: proposal.key(),
+        officer,
+        approval_count: proposal.approval_count,
+        threshold: approval_set.threshold,
+        timestamp: now,
+    });
+    msg!(
+        "Proposal {} approved by {} ({}/{})",
+        proposal.proposal_id,
+        officer,
+        proposal.approval_count,
+        approval_set.threshold
+    );
+    Ok(())
+}
+
+// Withdraw an open proposal (any officer of the set)
+pub(crate) fn cancel_action(ctx: Context<ReviewAction>) -> Result<()> {
+    let officer = ctx.accounts.officer.key();
+    if ctx.accounts.approval_set.signer_index(&officer).is_none() {
+        return Err(MicaEurError::NotApprovalSigner.into());
+    }
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.status != ProposalStatus::Open {
+        return Err(MicaEurError::ProposalNotOpen.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    proposal.status = ProposalStatus::Cancelled;
+    proposal.resolution_time = now;
+
+    emit!(ActionResolved {
+        proposal: proposal.key(),
+        status: ProposalStatus::Cancelled,
+        by: officer,
+        timestamp: now,
+    });
+    msg!("Proposal {} cancelled", proposal.proposal_id);
+    Ok(())
+}
+
+// Carry out a proposal that reached the threshold. The remaining accounts are the
+// transfer hook's extra accounts when the action is a seizure.
+pub(crate) fn execute_action<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteAction<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &ctx.accounts.proposal;
+    check_open(proposal, &ctx.accounts.approval_set, now)?;
+    if proposal.approval_count < ctx.accounts.approval_set.threshold {
+        return Err(MicaEurError::ApprovalThresholdNotMet.into());
+    }
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+
+    let mint_info = &ctx.accounts.mint_info;
+    let authority = ctx.accounts.authority.key();
+    let role_assignment = ctx.accounts.role_assignment.as_deref();
+    let token_account_key = ctx.accounts.token_account.key();
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    // The mint info PDA holds the Token-2022 authorities and signs every action but
+    // seizures, which the seizure authority PDA signs as the permanent delegate
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    match proposal.action {
+        RegulatoryAction::Freeze { token_account } => {
+            if !has_mint_role(mint_info, roles::FREEZER, &authority, role_assignment) {
+                return Err(MicaEurError::MissingRole.into());
+            }
+            if token_account_key != token_account {
+                return Err(MicaEurError::ActionAccountMismatch.into());
+            }
+            let cpi_accounts = token_2022::FreezeAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint_info.to_account_info(),
+            };
+            token_2022::freeze_account(CpiContext::new_with_signer(token_program, cpi_accounts, signer))?;
+            emit!(AccountFrozen {
+                mint: mint_info.mint,
+                token_account,
+                authority,
+                timestamp: now,
+            });
+        }
+        RegulatoryAction::Thaw { token_account } => {
+            if !has_mint_role(mint_info, roles::FREEZER, &authority, role_assignment) {
+                return Err(MicaEurError::MissingRole.into());
+            }
+            if token_account_key != token_account {
+                return Err(MicaEurError::ActionAccountMismatch.into());
+            }
+            let cpi_accounts = token_2022::ThawAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint_info.to_account_info(),
+            };
+            token_2022::thaw_account(CpiContext::new_with_signer(token_program, cpi_accounts, signer))?;
+            emit!(AccountThawed {
+                mint: mint_info.mint,
+                token_account,
+                authority,
+                timestamp: now,
+            });
+        }
+        RegulatoryAction::Seize { from, to, amount } => {
+            if mint_info.paused {
+                return Err(MicaEurError::TokenPaused.into());
+            }
+            let destination = ctx
+                .accounts
+                .destination_token_account
+                .as_ref()
+                .ok_or(MicaEurError::ActionAccountMismatch)?;
+            if !has_mint_role(mint_info, roles::SEIZER, &authority, role_assignment) {
+                return Err(MicaEurError::MissingRole.into());
+            }
+            if token_account_key != from || destination.key() != to {
+                return Err(MicaEurError::ActionAccountMismatch.into());
+            }
+            let bump = [ctx.bumps.seizure_authority];
+            let seeds = &[SEIZURE_AUTHORITY_SEED, mint_key.as_ref(), &bump];
+            let signer = &[&seeds[..]];
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: destination.to_account_info(),
+                authority: ctx.accounts.seizure_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer)
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+            transfer_checked_with_hook(cpi_ctx, amount, mint_info.decimals)?;
+            emit!(TokensSeized {
+                mint: mint_info.mint,
+                from,
+                to,
+                amount,
+                permanent_delegate: authority,
+                operation_id: [0u8; 16],
+                timestamp: now,
+            });
+        }
+        RegulatoryAction::Mint { token_account, amount } => {
+            if mint_info.paused {
+                return Err(MicaEurError::TokenPaused.into());
+            }
+            if mint_info.minting_halted {
+                return Err(MicaEurError::MintingHalted.into());
+            }
+            check_reserve_freshness(mint_info, now)?;
+            check_mint_coverage(mint_info, ctx.accounts.mint.supply, amount)?;
+            check_supply_cap(mint_info, ctx.accounts.mint.supply, amount)?;
+            record_scheduled_issuance(mint_info, ctx.accounts.issuance_policy.as_mut(), amount, now)?;
+            if !has_mint_role(mint_info, roles::MINTER, &authority, role_assignment) {
+                return Err(MicaEurError::MissingRole.into());
+            }
+            if token_account_key != token_account {
+                return Err(MicaEurError::ActionAccountMismatch.into());
+            }
+            let kyc_user = ctx
+                .accounts
+                .kyc_user
+                .as_ref()
+                .ok_or(MicaEurError::UserNotVerified)?;
+            let blacklist_entry =
+                load_if_initialized::<BlacklistEntry>(&ctx.accounts.blacklist_entry)?;
+            check_mint_recipient(kyc_user, blacklist_entry.as_ref())?;
+            check_mint_destination(mint_info, &kyc_user.user, &token_account)?;
+            let country_policy = ctx
+                .accounts
+                .country_policy
+                .as_ref()
+                .ok_or(MicaEurError::UnsupportedCountry)?;
+            check_country_allowed(country_policy, &kyc_user.country_code)?;
+            check_country_transaction(country_policy, kyc_user, amount)?;
+            check_mint_extensions(&ctx.accounts.mint.to_account_info())?;
+            check_token_account_extensions(&ctx.accounts.token_account.to_account_info())?;
+            // Funds cannot go into an account under a recorded freeze; below, only the
+            // default frozen state of a new account is lifted
+            let freeze_record = load_if_initialized::<FreezeRecord>(&ctx.accounts.freeze_record)?;
+            check_incoming_freeze(freeze_record.as_ref(), now)?;
+            let mut user_limits = load_user_limits(
+                &ctx.accounts.user_limits,
+                &kyc_user.user,
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            )?;
+            let limits = tier_limits(
+                ctx.accounts.limit_config.as_deref(),
+                kyc_user.verification_level,
+                mint_info.decimals,
+            );
+            record_mint_volume(&mut user_limits, kyc_user.user, limits, amount)?;
+            user_limits.try_serialize(&mut &mut ctx.accounts.user_limits.try_borrow_mut_data()?[..])?;
+            // Delegated minters mint against their allowance
+            consume_minter_allowance(mint_info, &authority, ctx.accounts.minter_allowance.as_mut(), amount)?;
+
+            // New token accounts start frozen, and Token-2022 does not mint to frozen accounts
+            if ctx.accounts.token_account.is_frozen() {
+                let cpi_accounts = token_2022::ThawAccount {
+                    account: ctx.accounts.token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    authority: ctx.accounts.mint_info.to_account_info(),
+                };
+                token_2022::thaw_account(CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer))?;
+            }
+            let cpi_accounts = token_2022::MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.mint_info.to_account_info(),
+            };
+            mint_to_checked(
+                CpiContext::new_with_signer(token_program, cpi_accounts, signer),
+                amount,
+                mint_info.decimals,
+            )?;
+            emit!(TokensMinted {
+                mint: mint_info.mint,
+                token_account,
+                recipient: kyc_user.user,
+                amount,
+                issuer: authority,
+                operation_id: [0u8; 16],
+                timestamp: now,
+            });
+            record_minted(&mut ctx.accounts.mint_info, amount)?;
+        }
+    }
+
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.status = ProposalStatus::Executed;
+    proposal.resolution_time = now;
+
+    emit!(ActionResolved {
+        proposal: proposal.key(),
+        status: ProposalStatus::Executed,
+        by: authority,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// A proposal accepts approvals and execution while open, unexpired and given
+// under the current configuration of its set
+fn check_open(proposal: &ActionProposal, approval_set: &ApprovalSet, now: i64) -> Result<()> {
+    if proposal.status != ProposalStatus::Open {
+        return Err(MicaEurError::ProposalNotOpen.into());
+    }
+    if proposal.generation != approval_set.generation {
+        return Err(MicaEurError::ApprovalSetChanged.into());
+    }
+    if now > proposal.expires_at {
+        return Err(MicaEurError::ProposalExpired.into());
+    }
+    Ok(())
+}
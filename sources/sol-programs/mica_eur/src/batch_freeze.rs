@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+
+use crate::access_control::{has_mint_role, roles, RoleAssignment};
+use crate::batch_mint::{create_pda, load_pda, mint_token_account};
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::freezes::{lift_freeze, record_freeze, FreezeRecord};
+use crate::MintInfo;
+
+/// Emitted for each token account of a batch freeze or thaw
+#[event]
+pub struct BatchFreezeResult {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub authority: Pubkey,
+    pub frozen: bool,                 // True for a batch freeze, false for a thaw
+    pub applied: bool,                // False if the account already was frozen or thawed
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct BatchFreezeAccounts<'info> {
+    /// The freeze authority or another holder of the FREEZER role
+    #[account(mut)]
+    pub freeze_authority: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active,
+        constraint = mint_info.approval_set == Pubkey::default() @ MicaEurError::MultisigApprovalRequired,
+        constraint = has_mint_role(&mint_info, roles::FREEZER, freeze_authority.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// The mint account for the EUR token
+    /// CHECK: Validated by token_program
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The signer's role assignment, unless it is the freeze authority
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), freeze_authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct BatchThawAccounts<'info> {
+    /// The freeze authority or another holder of the FREEZER role
+    pub freeze_authority: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active,
+        constraint = mint_info.approval_set == Pubkey::default() @ MicaEurError::MultisigApprovalRequired,
+        constraint = has_mint_role(&mint_info, roles::FREEZER, freeze_authority.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// The mint account for the EUR token
+    /// CHECK: Validated by token_program
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// The signer's role assignment, unless it is the freeze authority
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), freeze_authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+// Check the remaining accounts hold whole (token account, freeze record) pairs
+fn check_batch_freeze_accounts(accounts: &[AccountInfo]) -> Result<()> {
+    if accounts.is_empty()
+        || !accounts.len().is_multiple_of(BATCH_FREEZE_ACCOUNTS_PER_TARGET)
+        || accounts.len() > MAX_BATCH_FREEZE_ACCOUNTS * BATCH_FREEZE_ACCOUNTS_PER_TARGET
+    {
+        return Err(MicaEurError::InvalidBatchFreeze.into());
+    }
+    Ok(())
+}
+
+// Freeze up to MAX_BATCH_FREEZE_ACCOUNTS token accounts for one reason, e.g. a
+// sanctions list update (freeze authority or freezers). Remaining accounts come in
+// pairs: token account and its freeze record PDA, created if missing. Every freeze
+// is full and without expiry; accounts already under a freeze in force are left as
+// they are and reported as not applied.
+pub(crate) fn batch_freeze_accounts<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchFreezeAccounts<'info>>,
+    reason_code: u8,
+    legal_reference: String,
+) -> Result<()> {
+    check_batch_freeze_accounts(ctx.remaining_accounts)?;
+    let now = Clock::get()?.unix_timestamp;
+    let mint_key = ctx.accounts.mint.key();
+    let authority = ctx.accounts.freeze_authority.key();
+
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    let mut applied_count: u32 = 0;
+    for pair in ctx.remaining_accounts.chunks(BATCH_FREEZE_ACCOUNTS_PER_TARGET) {
+        let [token_account, freeze_record] = pair else {
+            return Err(MicaEurError::InvalidBatchFreeze.into());
+        };
+        let (owner, is_frozen) = mint_token_account(token_account, &mint_key)?;
+        let record_seeds: &[&[u8]] = &[FREEZE_RECORD_SEED, token_account.key.as_ref()];
+        let mut record = match load_pda::<FreezeRecord>(freeze_record, record_seeds)? {
+            Some(record) => record,
+            None => {
+                create_pda(
+                    freeze_record,
+                    record_seeds,
+                    8 + FreezeRecord::INIT_SPACE,
+                    ctx.accounts.freeze_authority.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                )?;
+                FreezeRecord::try_deserialize_unchecked(&mut &freeze_record.try_borrow_data()?[..])?
+            }
+        };
+
+        let applied = !record.in_force(now);
+        if applied {
+            record_freeze(
+                &mut record,
+                mint_key,
+                token_account.key(),
+                owner,
+                authority,
+                reason_code,
+                legal_reference.clone(),
+                0,
+                0,
+                now,
+            )?;
+            record.try_serialize(&mut &mut freeze_record.try_borrow_mut_data()?[..])?;
+
+            // New token accounts start frozen and already carry the flag
+            if !is_frozen {
+                let cpi_accounts = token_2022::FreezeAccount {
+                    account: token_account.clone(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    authority: ctx.accounts.mint_info.to_account_info(),
+                };
+                token_2022::freeze_account(CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer,
+                ))?;
+            }
+            applied_count += 1;
+        }
+
+        emit!(BatchFreezeResult {
+            mint: mint_key,
+            token_account: token_account.key(),
+            authority,
+            frozen: true,
+            applied,
+            timestamp: now,
+        });
+    }
+
+    msg!("Batch froze {} of {} accounts", applied_count, ctx.remaining_accounts.len() / BATCH_FREEZE_ACCOUNTS_PER_TARGET);
+    Ok(())
+}
+
+// Thaw up to MAX_BATCH_FREEZE_ACCOUNTS token accounts, e.g. after a delisting
+// (freeze authority or freezers). Remaining accounts come in pairs: token account
+// and its freeze record PDA, which need not exist. Recorded freezes are lifted;
+// accounts neither frozen nor under a recorded freeze are reported as not applied.
+pub(crate) fn batch_thaw_accounts<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchThawAccounts<'info>>,
+) -> Result<()> {
+    check_batch_freeze_accounts(ctx.remaining_accounts)?;
+    let now = Clock::get()?.unix_timestamp;
+    let mint_key = ctx.accounts.mint.key();
+    let authority = ctx.accounts.freeze_authority.key();
+
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    let mut applied_count: u32 = 0;
+    for pair in ctx.remaining_accounts.chunks(BATCH_FREEZE_ACCOUNTS_PER_TARGET) {
+        let [token_account, freeze_record] = pair else {
+            return Err(MicaEurError::InvalidBatchFreeze.into());
+        };
+        let (_, is_frozen) = mint_token_account(token_account, &mint_key)?;
+        let record_seeds: &[&[u8]] = &[FREEZE_RECORD_SEED, token_account.key.as_ref()];
+        let recorded = match load_pda::<FreezeRecord>(freeze_record, record_seeds)? {
+            Some(mut record) if record.is_active => {
+                lift_freeze(&mut record, authority, now);
+                record.try_serialize(&mut &mut freeze_record.try_borrow_mut_data()?[..])?;
+                true
+            }
+            _ => false,
+        };
+
+        if is_frozen {
+            let cpi_accounts = token_2022::ThawAccount {
+                account: token_account.clone(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint_info.to_account_info(),
+            };
+            token_2022::thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            ))?;
+        }
+
+        let applied = recorded || is_frozen;
+        if applied {
+            applied_count += 1;
+        }
+        emit!(BatchFreezeResult {
+            mint: mint_key,
+            token_account: token_account.key(),
+            authority,
+            frozen: false,
+            applied,
+            timestamp: now,
+        });
+    }
+
+    msg!("Batch thawed {} of {} accounts", applied_count, ctx.remaining_accounts.len() / BATCH_FREEZE_ACCOUNTS_PER_TARGET);
+    Ok(())
+}
@@ -0,0 +1,360 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::StateWithExtensions, state::Account as TokenAccountState,
+};
+use anchor_spl::token_2022::{self, Token2022};
+
+use crate::access_control::{has_mint_role, roles, RoleAssignment};
+use crate::allowances::{consume_minter_allowance, MinterAllowance};
+use crate::aml::BlacklistEntry;
+use crate::approvals::{check_single_key_mint, ApprovalSet};
+use crate::constants::*;
+use crate::country_policy::{check_country_allowed, check_country_transaction, CountryPolicy};
+use crate::error::MicaEurError;
+use crate::freezes::{check_incoming_freeze, FreezeRecord};
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::issuance::{check_supply_cap, record_scheduled_issuance, IssuancePolicy};
+use crate::kyc_oracle::KycUser;
+use crate::limits::{record_mint_volume, tier_limits, LimitConfig, UserLimits};
+use crate::mint_utils::{check_mint_destination, check_mint_extensions, check_token_account_extensions, mint_to_checked};
+use crate::operations::{record_operation, OperationDetails, OperationKind, OperationReceipt};
+use crate::policy::check_mint_recipient;
+use crate::reserve_coverage::{check_mint_coverage, check_reserve_freshness, mint_supply};
+use crate::supply_accounting::record_minted;
+use crate::MintInfo;
+
+/// Emitted once per batch mint, in place of one `TokensMinted` per recipient
+#[event]
+pub struct BatchTokensMinted {
+    pub mint: Pubkey,
+    pub recipients: u32,
+    pub total_amount: u64,
+    pub issuer: Pubkey,
+    pub operation_id: [u8; 16],
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(amounts: Vec<u64>, operation_id: [u8; 16])]
+pub struct BatchMintTokens<'info> {
+    /// The issuer or another holder of the MINTER role
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+        constraint = !mint_info.minting_halted @ MicaEurError::MintingHalted,
+        constraint = has_mint_role(&mint_info, roles::MINTER, issuer.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// The mint account for the EUR token
+    /// CHECK: Validated by token_program
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Receipt for the client-supplied operation ID, rejects reuse
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            OPERATION_RECEIPT_SEED,
+            mint.key().as_ref(),
+            &[OperationKind::Mint as u8],
+            issuer.key().as_ref(),
+            &operation_id,
+        ],
+        bump,
+        space = 8 + OperationReceipt::INIT_SPACE,
+    )]
+    pub operation_receipt: Account<'info, OperationReceipt>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Approval set of the mint, required once one is configured
+    pub approval_set: Option<Account<'info, ApprovalSet>>,
+
+    /// Tier limits of the mint, the defaults apply if none are configured
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub limit_config: Option<Account<'info, LimitConfig>>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// The signer's role assignment, unless it is the issuer
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), issuer.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// The signer's allowance, required unless it is the issuer
+    #[account(
+        mut,
+        seeds = [MINTER_ALLOWANCE_SEED, mint.key().as_ref(), issuer.key().as_ref()],
+        bump,
+    )]
+    pub minter_allowance: Option<Account<'info, MinterAllowance>>,
+
+    /// Issuance schedule of the mint, required once one is configured
+    #[account(
+        mut,
+        seeds = [ISSUANCE_POLICY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub issuance_policy: Option<Account<'info, IssuancePolicy>>,
+}
+
+/// Load a program account passed as a remaining account, checking it sits at the
+/// PDA of `seeds`. `None` if the account does not exist yet.
+pub fn load_pda<T: AccountDeserialize>(account: &AccountInfo, seeds: &[&[u8]]) -> Result<Option<T>> {
+    let (expected, _) = Pubkey::find_program_address(seeds, &crate::ID);
+    if account.key() != expected {
+        return Err(MicaEurError::BatchAccountMismatch.into());
+    }
+    load_if_initialized(account)
+}
+
+/// Load a program account whose address the account constraints already checked.
+/// `None` if the account does not exist yet.
+pub fn load_if_initialized<T: AccountDeserialize>(account: &AccountInfo) -> Result<Option<T>> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(T::try_deserialize(&mut &account.try_borrow_data()?[..])?))
+}
+
+/// Create the program account at the PDA of `seeds` passed as a remaining account,
+/// rent paid by `payer`
+pub fn create_pda<'info>(
+    account: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    let (_, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    let bump = [bump];
+    let signer_seeds = [seeds, &[&bump[..]]].concat();
+    let signer = &[&signer_seeds[..]];
+    create_account(
+        CpiContext::new_with_signer(
+            system_program,
+            CreateAccount {
+                from: payer,
+                to: account.clone(),
+            },
+            signer,
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )
+}
+
+/// Load the recipient's mint volumes, creating its account on a first mint like
+/// `init_if_needed` does for `mint_tokens`
+pub fn load_user_limits<'info>(
+    account: &AccountInfo<'info>,
+    user: &Pubkey,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<UserLimits> {
+    if let Some(user_limits) = load_pda::<UserLimits>(account, &[USER_LIMITS_SEED, user.as_ref()])? {
+        return Ok(user_limits);
+    }
+
+    create_pda(
+        account,
+        &[USER_LIMITS_SEED, user.as_ref()],
+        8 + UserLimits::INIT_SPACE,
+        payer,
+        system_program,
+    )?;
+    UserLimits::try_deserialize_unchecked(&mut &account.try_borrow_data()?[..])
+}
+
+/// Owner of a token account of the mint passed as a remaining account, and whether
+/// it is frozen
+pub fn mint_token_account(account: &AccountInfo, mint: &Pubkey) -> Result<(Pubkey, bool)> {
+    if account.owner != &Token2022::id() {
+        return Err(MicaEurError::TokenAccountMintMismatch.into());
+    }
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    if state.base.mint != *mint {
+        return Err(MicaEurError::TokenAccountMintMismatch.into());
+    }
+    Ok((state.base.owner, state.base.is_frozen()))
+}
+
+// Mint to up to MAX_BATCH_MINT_RECIPIENTS recipients in one instruction (issuer or
+// minters). `amounts[i]` goes to the i-th group of BATCH_MINT_ACCOUNTS_PER_RECIPIENT
+// remaining accounts: token account, KYC user, blacklist entry, user limits, country
+// policy and freeze record, the last five at their PDAs whether or not they exist.
+// Every recipient passes the `mint_tokens` checks before anything is minted; approval, coverage, supply cap,
+// schedule and allowance checks apply to the batch total.
+pub(crate) fn batch_mint_tokens<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchMintTokens<'info>>,
+    amounts: Vec<u64>,
+    operation_id: [u8; 16],
+) -> Result<()> {
+    let recipients = ctx.remaining_accounts;
+    if amounts.is_empty()
+        || amounts.len() > MAX_BATCH_MINT_RECIPIENTS
+        || recipients.len() != amounts.len() * BATCH_MINT_ACCOUNTS_PER_RECIPIENT
+    {
+        return Err(MicaEurError::InvalidBatchMint.into());
+    }
+    let mut total_amount: u64 = 0;
+    for amount in &amounts {
+        if *amount == 0 {
+            return Err(MicaEurError::InvalidAmount.into());
+        }
+        total_amount = total_amount.checked_add(*amount).ok_or(MicaEurError::ArithmeticOverflow)?;
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    check_mint_extensions(&ctx.accounts.mint)?;
+    check_single_key_mint(
+        &ctx.accounts.mint_info,
+        ctx.accounts.approval_set.as_deref(),
+        ctx.accounts.approval_set.as_ref().map(|a| a.key()),
+        total_amount,
+    )?;
+    let now = Clock::get()?.unix_timestamp;
+    let supply = mint_supply(&ctx.accounts.mint.to_account_info())?;
+    check_reserve_freshness(&ctx.accounts.mint_info, now)?;
+    check_mint_coverage(&ctx.accounts.mint_info, supply, total_amount)?;
+    check_supply_cap(&ctx.accounts.mint_info, supply, total_amount)?;
+    record_scheduled_issuance(&ctx.accounts.mint_info, ctx.accounts.issuance_policy.as_mut(), total_amount, now)?;
+    consume_minter_allowance(
+        &ctx.accounts.mint_info,
+        ctx.accounts.issuer.key,
+        ctx.accounts.minter_allowance.as_mut(),
+        total_amount,
+    )?;
+
+    // One pass over the recipients: KYC, blacklist, country, freezes and daily limits
+    let mut frozen = Vec::with_capacity(amounts.len());
+    for (group, amount) in recipients.chunks(BATCH_MINT_ACCOUNTS_PER_RECIPIENT).zip(&amounts) {
+        let [token_account, kyc_user, blacklist_entry, user_limits, country_policy, freeze_record] = group
+        else {
+            return Err(MicaEurError::InvalidBatchMint.into());
+        };
+        // The KYC record sits at the PDA of the token account's owner
+        let (owner, is_frozen) = mint_token_account(token_account, &mint_key)?;
+        check_token_account_extensions(token_account)?;
+        check_mint_destination(&ctx.accounts.mint_info, &owner, token_account.key)?;
+        let kyc_user = load_pda::<KycUser>(kyc_user, &[KYC_USER_SEED, owner.as_ref()])?
+            .ok_or(MicaEurError::UserNotVerified)?;
+        let blacklist_entry = load_pda::<BlacklistEntry>(blacklist_entry, &[BLACKLIST_SEED, owner.as_ref()])?;
+        check_mint_recipient(&kyc_user, blacklist_entry.as_ref())?;
+        let country_policy = load_pda::<CountryPolicy>(
+            country_policy,
+            &[COUNTRY_POLICY_SEED, kyc_user.country_code.as_bytes()],
+        )?
+        .ok_or(MicaEurError::UnsupportedCountry)?;
+        check_country_allowed(&country_policy, &kyc_user.country_code)?;
+        check_country_transaction(&country_policy, &kyc_user, *amount)?;
+        // Only the default frozen state of a new account is lifted below
+        let freeze_record =
+            load_pda::<FreezeRecord>(freeze_record, &[FREEZE_RECORD_SEED, token_account.key.as_ref()])?;
+        check_incoming_freeze(freeze_record.as_ref(), now)?;
+        frozen.push(is_frozen);
+
+        let mut limits = load_user_limits(
+            user_limits,
+            &kyc_user.user,
+            ctx.accounts.issuer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+        let tier = tier_limits(
+            ctx.accounts.limit_config.as_deref(),
+            kyc_user.verification_level,
+            ctx.accounts.mint_info.decimals,
+        );
+        record_mint_volume(&mut limits, kyc_user.user, tier, *amount)?;
+        limits.try_serialize(&mut &mut user_limits.try_borrow_mut_data()?[..])?;
+    }
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+
+    // The mint info PDA holds the mint and freeze authority
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    let token_accounts = recipients.iter().step_by(BATCH_MINT_ACCOUNTS_PER_RECIPIENT);
+    for ((token_account, amount), frozen) in token_accounts.zip(&amounts).zip(frozen) {
+        // New token accounts start frozen, and Token-2022 does not mint to frozen accounts
+        if frozen {
+            let cpi_accounts = token_2022::ThawAccount {
+                account: token_account.clone(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint_info.to_account_info(),
+            };
+            token_2022::thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            ))?;
+        }
+
+        let cpi_accounts = token_2022::MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: token_account.clone(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        mint_to_checked(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+            *amount,
+            ctx.accounts.mint_info.decimals,
+        )?;
+    }
+    record_minted(&mut ctx.accounts.mint_info, total_amount)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    record_operation(
+        &mut ctx.accounts.operation_receipt,
+        operation_id,
+        OperationDetails {
+            kind: OperationKind::Mint,
+            mint: mint_key,
+            actor: ctx.accounts.issuer.key(),
+            source: Pubkey::default(),
+            destination: Pubkey::default(),
+            amount: total_amount,
+        },
+    )?;
+
+    emit!(BatchTokensMinted {
+        mint: mint_key,
+        recipients: amounts.len() as u32,
+        total_amount,
+        issuer: ctx.accounts.issuer.key(),
+        operation_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Batch minted {} to {} recipients", total_amount, amounts.len());
+    Ok(())
+}
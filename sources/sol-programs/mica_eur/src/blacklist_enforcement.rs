@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::aml::BlacklistEntry;
+use crate::compliance_history::{record_history, reference_hash, ComplianceAction, ComplianceHistory};
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::freezes::{freeze_reasons, record_freeze, FreezeRecord};
+use crate::MintInfo;
+
+/// Emitted when a blacklisted user's token account is frozen on the strength of
+/// its blacklist entry
+#[event]
+pub struct BlacklistEnforced {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub user: Pubkey,
+    pub aml_authority: Pubkey,        // AML authority that blacklisted the user
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct EnforceBlacklist<'info> {
+    /// Anyone, paying for the freeze record
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// The mint account for the EUR token
+    /// CHECK: Validated by token_program
+    pub mint: UncheckedAccount<'info>,
+
+    /// The blacklisted user's token account
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Blacklist entry of the token account's owner
+    #[account(
+        seeds = [BLACKLIST_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+        space = 8 + FreezeRecord::INIT_SPACE,
+    )]
+    pub freeze_record: Account<'info, FreezeRecord>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The owner's compliance history, recorded into once opened
+    #[account(
+        mut,
+        seeds = [COMPLIANCE_HISTORY_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub compliance_history: Option<Account<'info, ComplianceHistory>>,
+}
+
+// Freeze a blacklisted user's token account (anyone). The active blacklist entry is
+// the authorization: the mint info PDA signs the freeze, recorded as a full freeze
+// by the AML authority behind the entry, so blacklisting takes effect on funds
+// without waiting for a freezer. Lifting it is a regular thaw.
+pub(crate) fn enforce_blacklist(ctx: Context<EnforceBlacklist>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if !ctx.accounts.blacklist_entry.in_force(now) {
+        return Err(MicaEurError::BlacklistEntryInactive.into());
+    }
+    let mint_key = ctx.accounts.mint.key();
+    let aml_authority = ctx.accounts.blacklist_entry.authority;
+    record_freeze(
+        &mut ctx.accounts.freeze_record,
+        mint_key,
+        ctx.accounts.token_account.key(),
+        ctx.accounts.token_account.owner,
+        aml_authority,
+        freeze_reasons::BLACKLISTED,
+        String::new(),
+        0,
+        0,
+        now,
+    )?;
+
+    // New token accounts start frozen and already carry the flag
+    if !ctx.accounts.token_account.is_frozen() {
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = token_2022::FreezeAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+
+    record_history(
+        ctx.accounts.compliance_history.as_deref_mut(),
+        ComplianceAction::BlacklistEnforced,
+        aml_authority,
+        reference_hash(&ctx.accounts.blacklist_entry.legal_reference),
+        now,
+    );
+
+    emit!(BlacklistEnforced {
+        mint: mint_key,
+        token_account: ctx.accounts.token_account.key(),
+        user: ctx.accounts.blacklist_entry.user,
+        aml_authority,
+        caller: ctx.accounts.caller.key(),
+        timestamp: now,
+    });
+
+    msg!("Blacklist enforced on {}", ctx.accounts.token_account.key());
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash;
+
+use crate::constants::*;
+
+/// Canonical encoder for everything the program and its off-chain tooling hash or
+/// sign. The layout is the reference other implementations are checked against
+/// with the golden vectors in `tests/vectors/encoding-v1.json`:
+///
+/// - every encoding starts with the artifact's domain tag, raw ASCII ending in
+///   `-v<version>`; a layout change gets a new tag, never a new meaning for an old one
+/// - integers are little-endian at their full width (`u8`, `u32`, `u64`, `i64`)
+/// - public keys and hashes are their 32 raw bytes
+/// - strings are UTF-8, preceded by their byte length as a `u32`
+///
+/// Fields follow in the order of the artifact's encode function, without padding.
+/// Hashes are SHA-256 over the encoding; Merkle leaves put `MERKLE_LEAF_PREFIX`
+/// in front of it (see `merkle`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LeafEncoder {
+    bytes: Vec<u8>,
+}
+
+impl LeafEncoder {
+    pub fn new(domain: &[u8]) -> Self {
+        Self { bytes: domain.to_vec() }
+    }
+
+    pub fn u8(mut self, value: u8) -> Self {
+        self.bytes.push(value);
+        self
+    }
+
+    pub fn u32(mut self, value: u32) -> Self {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn u64(mut self, value: u64) -> Self {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn i64(mut self, value: i64) -> Self {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn pubkey(mut self, value: &Pubkey) -> Self {
+        self.bytes.extend_from_slice(value.as_ref());
+        self
+    }
+
+    pub fn bytes32(mut self, value: &[u8; 32]) -> Self {
+        self.bytes.extend_from_slice(value);
+        self
+    }
+
+    pub fn str(mut self, value: &str) -> Self {
+        self = self.u32(value.len() as u32);
+        self.bytes.extend_from_slice(value.as_bytes());
+        self
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn hash(self) -> [u8; 32] {
+        hash::hash(&self.bytes).to_bytes()
+    }
+}
+
+/// Encoding of a fiat deposit leaf in the reserve tree
+pub fn encode_reserve_leaf(deposit_id: &str, amount: u64, timestamp: i64) -> Vec<u8> {
+    LeafEncoder::new(RESERVE_LEAF_DOMAIN)
+        .str(deposit_id)
+        .u64(amount)
+        .i64(timestamp)
+        .finish()
+}
+
+/// Encoding of a SEPA (SCT or SCT Inst) transaction reference, whose hash binds a
+/// mint voucher to its deposit and a redemption to its payout
+pub fn encode_sepa_reference(reference: &str) -> Vec<u8> {
+    LeafEncoder::new(SEPA_REFERENCE_DOMAIN).str(reference).finish()
+}
+
+/// Encoding of a holder's balance, the liability side, in a snapshot tree
+pub fn encode_snapshot_leaf(owner: &Pubkey, balance: u64) -> Vec<u8> {
+    LeafEncoder::new(SNAPSHOT_LEAF_DOMAIN)
+        .pubkey(owner)
+        .u64(balance)
+        .finish()
+}
+
+/// Encoding of the payload a screening provider signs for one attestation
+pub fn encode_screening_attestation(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    screened_at: i64,
+    result_hash: &[u8; 32],
+) -> Vec<u8> {
+    LeafEncoder::new(SCREENING_ATTESTATION_DOMAIN)
+        .pubkey(program_id)
+        .pubkey(user)
+        .i64(screened_at)
+        .bytes32(result_hash)
+        .finish()
+}
+
+/// Encoding of the payload an owner signs to authorize a relayed transfer
+pub fn encode_transfer_authorization(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    nonce: &[u8; 32],
+    expiry: i64,
+) -> Vec<u8> {
+    LeafEncoder::new(TRANSFER_AUTHORIZATION_DOMAIN)
+        .pubkey(program_id)
+        .pubkey(mint)
+        .pubkey(owner)
+        .pubkey(recipient)
+        .u64(amount)
+        .bytes32(nonce)
+        .i64(expiry)
+        .finish()
+}
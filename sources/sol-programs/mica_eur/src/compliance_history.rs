@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::{KycStatus, KycUser};
+
+/// Compliance measure recorded in a user's history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub enum ComplianceAction {
+    #[default]
+    None,                             // Slot not written yet
+    KycStatusChanged { from: KycStatus, to: KycStatus },
+    Frozen { reason_code: u8, frozen_amount: u64 },
+    Thawed,
+    Blacklisted,
+    BlacklistLifted,
+    BlacklistEnforced,
+    KycExpiryEnforced,
+    Seized { amount: u64 },
+}
+
+/// One measure in a compliance history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct HistoryEntry {
+    pub action: ComplianceAction,
+    pub actor: Pubkey,                // Authority that took the measure
+    pub timestamp: i64,
+    pub reference_hash: [u8; 32],     // SHA-256 of the legal reference, zero if none
+}
+
+/// The last COMPLIANCE_HISTORY_SIZE compliance measures concerning a user, oldest
+/// overwritten first. Instructions record into it once it has been opened, so the
+/// history does not depend on how long RPC nodes keep the events.
+#[account]
+#[derive(InitSpace)]
+pub struct ComplianceHistory {
+    pub user: Pubkey,                 // Wallet the history belongs to
+    pub entries: [HistoryEntry; COMPLIANCE_HISTORY_SIZE],
+    pub next: u8,                     // Slot the next entry is written to
+    pub total_entries: u64,           // Entries ever recorded, including overwritten ones
+}
+
+impl ComplianceHistory {
+    /// Add an entry, overwriting the oldest one
+    pub fn record(&mut self, action: ComplianceAction, actor: Pubkey, reference_hash: [u8; 32], now: i64) {
+        let slot = self.next as usize % COMPLIANCE_HISTORY_SIZE;
+        self.entries[slot] = HistoryEntry {
+            action,
+            actor,
+            timestamp: now,
+            reference_hash,
+        };
+        self.next = ((slot + 1) % COMPLIANCE_HISTORY_SIZE) as u8;
+        self.total_entries = self.total_entries.saturating_add(1);
+    }
+
+    /// Recorded entries, oldest first
+    pub fn chronological(&self) -> impl Iterator<Item = &HistoryEntry> {
+        let (newer, older) = self.entries.split_at(self.next as usize % COMPLIANCE_HISTORY_SIZE);
+        older.iter().chain(newer).filter(|entry| entry.action != ComplianceAction::None)
+    }
+}
+
+/// Hash of a legal reference as kept in history entries, zero for none
+pub fn reference_hash(legal_reference: &str) -> [u8; 32] {
+    if legal_reference.is_empty() {
+        return [0; 32];
+    }
+    hash::hash(legal_reference.as_bytes()).to_bytes()
+}
+
+/// Record a measure in the user's history if the instruction was given one
+pub fn record_history(
+    history: Option<&mut ComplianceHistory>,
+    action: ComplianceAction,
+    actor: Pubkey,
+    reference_hash: [u8; 32],
+    now: i64,
+) {
+    if let Some(history) = history {
+        history.record(action, actor, reference_hash, now);
+    }
+}
+
+/// Emitted when a user's compliance history is opened
+#[event]
+pub struct ComplianceHistoryOpened {
+    pub user: Pubkey,
+    pub compliance_history: Pubkey,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct OpenComplianceHistory<'info> {
+    /// Anyone, paying for the history
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// KYC record of the user
+    #[account(
+        seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [COMPLIANCE_HISTORY_SEED, kyc_user.user.as_ref()],
+        bump,
+        space = 8 + ComplianceHistory::INIT_SPACE,
+    )]
+    pub compliance_history: Account<'info, ComplianceHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Open the compliance history of a registered user (anyone)
+pub(crate) fn open_compliance_history(ctx: Context<OpenComplianceHistory>) -> Result<()> {
+    let compliance_history = &mut ctx.accounts.compliance_history;
+    compliance_history.user = ctx.accounts.kyc_user.user;
+
+    emit!(ComplianceHistoryOpened {
+        user: compliance_history.user,
+        compliance_history: compliance_history.key(),
+        payer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Check that a history passed next to a token account belongs to its owner
+pub fn check_history_owner(history: Option<&ComplianceHistory>, owner: &Pubkey) -> Result<()> {
+    match history {
+        Some(history) if history.user != *owner => Err(MicaEurError::ComplianceHistoryMismatch.into()),
+        _ => Ok(()),
+    }
+}
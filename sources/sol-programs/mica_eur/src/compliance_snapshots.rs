@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::KycOracleState;
+use crate::MintInfo;
+
+/// Figures of one reporting period, published by the issuer for national competent
+/// authorities. Supply, verified users and the reserve are read from the chain; the
+/// freeze and seizure counts are the issuer's own figures for the period.
+#[account]
+#[derive(InitSpace)]
+pub struct ComplianceSnapshot {
+    pub mint: Pubkey,                 // The EUR mint
+    pub period: u32,                  // Reporting period, e.g. 20263 for Q3 2026
+    pub total_supply: u64,            // Supply when published
+    pub verified_users: u64,          // Verified users of the KYC oracle when published
+    pub frozen_accounts: u64,         // Token accounts frozen at the end of the period
+    pub seizure_count: u64,           // Seizures during the period
+    pub reserve_merkle_root: [u8; 32],// Latest reserve root when published
+    pub last_reserve_update: i64,     // When that root was published
+    pub published_by: Pubkey,         // Issuer that published the snapshot
+    pub publish_time: i64,            // When it was published
+}
+
+/// Emitted when the issuer publishes a compliance snapshot
+#[event]
+pub struct ComplianceSnapshotPublished {
+    pub mint: Pubkey,
+    pub period: u32,
+    pub total_supply: u64,
+    pub verified_users: u64,
+    pub frozen_accounts: u64,
+    pub seizure_count: u64,
+    pub reserve_merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct PublishComplianceSnapshot<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    /// One snapshot per period; once published it cannot be changed
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [COMPLIANCE_SNAPSHOT_SEED, mint.key().as_ref(), &period.to_le_bytes()],
+        bump,
+        space = 8 + ComplianceSnapshot::INIT_SPACE,
+    )]
+    pub compliance_snapshot: Account<'info, ComplianceSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Publish the compliance figures of a reporting period (issuer only)
+pub(crate) fn publish_compliance_snapshot(
+    ctx: Context<PublishComplianceSnapshot>,
+    period: u32,
+    frozen_accounts: u64,
+    seizure_count: u64,
+) -> Result<()> {
+    if period == 0 {
+        return Err(MicaEurError::InvalidComplianceSnapshot.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let mint_info = &ctx.accounts.mint_info;
+    let snapshot = &mut ctx.accounts.compliance_snapshot;
+    snapshot.mint = mint_info.mint;
+    snapshot.period = period;
+    snapshot.total_supply = ctx.accounts.mint.supply;
+    snapshot.verified_users = ctx.accounts.kyc_oracle_state.verified_user_count;
+    snapshot.frozen_accounts = frozen_accounts;
+    snapshot.seizure_count = seizure_count;
+    snapshot.reserve_merkle_root = mint_info.reserve_merkle_root;
+    snapshot.last_reserve_update = mint_info.last_reserve_update;
+    snapshot.published_by = ctx.accounts.issuer.key();
+    snapshot.publish_time = now;
+
+    emit!(ComplianceSnapshotPublished {
+        mint: snapshot.mint,
+        period,
+        total_supply: snapshot.total_supply,
+        verified_users: snapshot.verified_users,
+        frozen_accounts,
+        seizure_count,
+        reserve_merkle_root: snapshot.reserve_merkle_root,
+        timestamp: now,
+    });
+
+    msg!("Compliance snapshot for period {} published", period);
+    Ok(())
+}
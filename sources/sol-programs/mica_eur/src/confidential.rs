@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::confidential_transfer::instruction::{approve_account, update_mint},
+    solana_zk_token_sdk::zk_token_elgamal::pod::ElGamalPubkey,
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::aml::{aml_powers, AmlAuthority, BlacklistEntry};
+use crate::batch_mint::load_if_initialized;
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::KycUser;
+use crate::policy::check_transfer_party;
+use crate::MintInfo;
+
+/// Emitted when confidential transfers are enabled on a mint
+#[event]
+pub struct ConfidentialTransfersEnabled {
+    pub mint: Pubkey,
+    pub auditor: Pubkey,              // AML authority holding the auditor key
+    pub auditor_elgamal_pubkey: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when a holder's token account is approved for confidential balances
+#[event]
+pub struct ConfidentialAccountApproved {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct EnableConfidentialTransfers<'info> {
+    pub issuer: Signer<'info>,
+
+    /// The AML authority holding the auditor ElGamal key
+    pub auditor: Signer<'info>,
+
+    #[account(
+        seeds = [AML_AUTHORITY_SEED, auditor.key().as_ref()],
+        bump,
+        constraint = aml_authority.authority == auditor.key(),
+    )]
+    pub aml_authority: Account<'info, AmlAuthority>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveConfidentialAccount<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.confidential_transfers @ MicaEurError::ConfidentialTransfersDisabled,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account the owner has configured for confidential balances
+    #[account(
+        mut,
+        constraint = token_account.owner == owner.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    /// Blacklist entry of the owner
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+}
+
+// Enable confidential transfers on a mint (issuer and auditor). Amounts stay
+// encrypted to the public but are also encrypted to the auditor key, so the
+// compliance function can decrypt every balance and transfer. Transfer fees must
+// be off, as the mint has no key to withdraw confidentially withheld fees to.
+pub(crate) fn enable_confidential_transfers(
+    ctx: Context<EnableConfidentialTransfers>,
+    auditor_elgamal_pubkey: [u8; 32],
+) -> Result<()> {
+    let aml_authority = &ctx.accounts.aml_authority;
+    if !aml_authority.is_active {
+        return Err(MicaEurError::AmlAuthorityInactive.into());
+    }
+    if !aml_authority.has_power(aml_powers::VIEW_TRANSACTIONS) {
+        return Err(MicaEurError::UnauthorizedAmlAuthority.into());
+    }
+    if auditor_elgamal_pubkey == [0; 32] {
+        return Err(MicaEurError::InvalidAuditorKey.into());
+    }
+    if ctx.accounts.mint_info.transfer_fee_bps > 0 {
+        return Err(MicaEurError::ConfidentialTransferFeeConflict.into());
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let mint_info_key = ctx.accounts.mint_info.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+
+    invoke_signed(
+        &update_mint(
+            ctx.accounts.token_program.key,
+            &mint_key,
+            &mint_info_key,
+            &[],
+            false,
+            Some(ElGamalPubkey(auditor_elgamal_pubkey)),
+        )?,
+        &[ctx.accounts.mint.to_account_info(), ctx.accounts.mint_info.to_account_info()],
+        &[&seeds[..]],
+    )?;
+
+    let mint_info = &mut ctx.accounts.mint_info;
+    mint_info.confidential_transfers = true;
+    mint_info.confidential_auditor = ctx.accounts.auditor.key();
+    mint_info.auditor_elgamal_pubkey = auditor_elgamal_pubkey;
+
+    emit!(ConfidentialTransfersEnabled {
+        mint: mint_key,
+        auditor: mint_info.confidential_auditor,
+        auditor_elgamal_pubkey,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Confidential transfers enabled for {}", mint_key);
+    Ok(())
+}
+
+// Approve the owner's configured token account for confidential balances (owner).
+// The owner must pass the same checks the transfer hook applies to each party.
+pub(crate) fn approve_confidential_account(ctx: Context<ApproveConfidentialAccount>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    let now = Clock::get()?.unix_timestamp;
+    let blacklist_entry = load_if_initialized::<BlacklistEntry>(&ctx.accounts.blacklist_entry)?;
+    let blacklisted = blacklist_entry.is_some_and(|entry| entry.in_force(now));
+    check_transfer_party(owner, Some(&ctx.accounts.kyc_user), blacklisted, None)?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let mint_info_key = ctx.accounts.mint_info.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+
+    invoke_signed(
+        &approve_account(
+            ctx.accounts.token_program.key,
+            &ctx.accounts.token_account.key(),
+            &mint_key,
+            &mint_info_key,
+            &[],
+        )?,
+        &[
+            ctx.accounts.token_account.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.mint_info.to_account_info(),
+        ],
+        &[&seeds[..]],
+    )?;
+
+    emit!(ConfidentialAccountApproved {
+        mint: mint_key,
+        token_account: ctx.accounts.token_account.key(),
+        owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -7,33 +7,229 @@ pub const KYC_ORACLE_STATE_SEED: &[u8] = b"kyc-oracle-state";
 pub const KYC_USER_SEED: &[u8] = b"kyc-user";
 pub const AML_AUTHORITY_SEED: &[u8] = b"aml-authority";
 pub const BLACKLIST_SEED: &[u8] = b"blacklist";
+pub const STANDING_ORDER_SEED: &[u8] = b"standing-order";
+pub const SCREENING_PROVIDER_SEED: &[u8] = b"screening-provider";
+pub const OPERATION_RECEIPT_SEED: &[u8] = b"operation-receipt";
+pub const STAGED_POLICY_SEED: &[u8] = b"staged-policy";
+pub const MIGRATION_SEED: &[u8] = b"migration";
+pub const MIGRATION_RECORD_SEED: &[u8] = b"migration-record";
+pub const EDD_CONFIG_SEED: &[u8] = b"edd-config";
+pub const PENDING_APPROVAL_SEED: &[u8] = b"pending-approval";
+pub const APPROVAL_SET_SEED: &[u8] = b"approval-set";
+pub const ACTION_PROPOSAL_SEED: &[u8] = b"action-proposal";
+pub const LIMIT_CONFIG_SEED: &[u8] = b"limit-config";
+pub const USER_LIMITS_SEED: &[u8] = b"user-limits";
+pub const INVOCATION_GUARD_SEED: &[u8] = b"invocation-guard";
+pub const AUDITOR_ATTESTATION_SEED: &[u8] = b"auditor-attestation";
+pub const SNAPSHOT_REGISTRY_SEED: &[u8] = b"snapshot-registry";
+pub const BALANCE_SNAPSHOT_SEED: &[u8] = b"balance-snapshot";
+pub const FAST_LANE_INSTITUTION_SEED: &[u8] = b"fast-lane-institution";
+pub const KYC_PROVIDER_SEED: &[u8] = b"kyc-provider";
+pub const RECONCILIATION_LOG_SEED: &[u8] = b"reconciliation-log";
+pub const RECONCILIATION_RECORD_SEED: &[u8] = b"reconciliation-record";
+pub const TRAVEL_RULE_CONFIG_SEED: &[u8] = b"travel-rule-config";
+pub const SEIZURE_VAULT_SEED: &[u8] = b"seizure-vault";
+pub const SEIZURE_RECORD_SEED: &[u8] = b"seizure-record";
+pub const FREEZE_RECORD_SEED: &[u8] = b"freeze-record";
+pub const RATE_HISTORY_SEED: &[u8] = b"rate-history";
+pub const RATE_CHANGE_SEED: &[u8] = b"rate-change";
+pub const ROLE_SEED: &[u8] = b"role";
+pub const MINTER_ALLOWANCE_SEED: &[u8] = b"minter-allowance";
+pub const ISSUANCE_POLICY_SEED: &[u8] = b"issuance-policy";
+pub const TIMELOCK_SEED: &[u8] = b"timelock";
+pub const TIMELOCK_OPERATION_SEED: &[u8] = b"timelock-operation";
+pub const WHITEPAPER_VERSION_SEED: &[u8] = b"whitepaper-version";
+pub const COMPLIANCE_SNAPSHOT_SEED: &[u8] = b"compliance-snapshot";
+pub const COUNTRY_POLICY_SEED: &[u8] = b"country-policy";
+pub const IBAN_REGISTRY_SEED: &[u8] = b"iban-registry";
+pub const RECOVERY_REQUEST_SEED: &[u8] = b"recovery-request";
+pub const APPROVED_COUNTERPARTY_SEED: &[u8] = b"approved-counterparty";
+pub const AML_ALERT_CONFIG_SEED: &[u8] = b"aml-alert-config";
+pub const SAR_REFERENCE_SEED: &[u8] = b"sar-reference";
+pub const REGULATOR_REGISTRY_SEED: &[u8] = b"regulator-registry";
+pub const HOLDER_RECORD_SEED: &[u8] = b"holder-record";
+pub const COMPLIANCE_HISTORY_SEED: &[u8] = b"compliance-history";
+pub const TREASURY_VAULT_SEED: &[u8] = b"treasury-vault";
+pub const MINT_VOUCHER_SEED: &[u8] = b"mint-voucher";
+pub const PAYOUT_REFERENCE_SEED: &[u8] = b"payout-reference";
+pub const RESERVE_COMPOSITION_SEED: &[u8] = b"reserve-composition";
+pub const RESERVE_PROOF_EPOCH_SEED: &[u8] = b"reserve-proof-epoch";
+pub const RESERVE_VALUATION_SEED: &[u8] = b"reserve-valuation";
+pub const REGULATOR_ACCESS_SEED: &[u8] = b"regulator-access";
+pub const PROGRAM_ALLOWLIST_SEED: &[u8] = b"program-allowlist";
+pub const PROGRAM_ESCROW_SEED: &[u8] = b"program-escrow";
+pub const PERMIT_AUTHORITY_SEED: &[u8] = b"permit-authority";
+pub const PERMIT_NONCE_SEED: &[u8] = b"permit-nonce";
+pub const RELAYER_REGISTRY_SEED: &[u8] = b"relayer-registry";
+pub const RELAYER_SEED: &[u8] = b"relayer";
+pub const ESCROW_VAULT_SEED: &[u8] = b"escrow-vault";
+pub const ESCROW_SEED: &[u8] = b"escrow";
+pub const PAYMENT_REQUEST_SEED: &[u8] = b"payment-request";
+pub const SEIZURE_AUTHORITY_SEED: &[u8] = b"seizure-authority";
+
+// Program every EUR mint's TransferHook extension points to (mica_eur_transfer_hook)
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = pubkey!("Gc7Yf6JL6QJkawfRe6G8TG6X9JE6L4FUqvmosCeKd5m7");
+
+// Pyth pull oracle receiver, owner of the price updates reserves are valued with
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
 
 // Verification levels for KYC
 pub const MIN_VERIFICATION_LEVEL_FOR_TRANSFERS: u8 = 1;
 pub const MIN_VERIFICATION_LEVEL_FOR_MINT_REDEEM: u8 = 2;
 
-// Supported countries (ISO 3166-1 alpha-2 codes) for MiCA compliance
-// This is a whitelist of countries that are supported
-pub const SUPPORTED_COUNTRIES: [&str; 27] = [
-    // EU member states
-    "AT", "BE", "BG", "HR", "CY", "CZ", "DK", "EE", "FI", "FR", 
-    "DE", "GR", "HU", "IE", "IT", "LV", "LT", "LU", "MT", "NL", 
-    "PL", "PT", "RO", "SK", "SI", "ES", "SE",
-];
+// Sanctions screening
+// Verification level from which a recent screening attestation is required
+pub const SANCTIONS_SCREENING_MIN_LEVEL: u8 = 3;
+// Maximum age of a screening attestation for those levels (30 days)
+pub const MAX_SANCTIONS_SCREEN_AGE: i64 = 30 * 86_400;
+// Domain separator of the message signed by screening providers
+pub const SCREENING_ATTESTATION_DOMAIN: &[u8] = b"mica-eur-screening-v1";
+
+// Domain separator of the message owners sign to authorize a relayed transfer
+pub const TRANSFER_AUTHORIZATION_DOMAIN: &[u8] = b"mica-eur-transfer-authorization-v1";
+
+// Maximum transaction amount (in whole EUR, scaled by the mint's decimals)
+pub const MAX_TRANSACTION_EUR: u64 = 100_000;
+
+// Per-class maximum transaction amounts (in whole EUR)
+// 10,000 EUR for retail accounts, 1,000,000 EUR for institutional accounts
+pub const RETAIL_MAX_TRANSACTION_EUR: u64 = 10_000;
+pub const INSTITUTIONAL_MAX_TRANSACTION_EUR: u64 = 1_000_000;
+
+// CPI levels a guarded settlement instruction may be invoked through
+// (1: called by one counterpart program, e.g. an escrow or DvP settlement)
+pub const MAX_SETTLEMENT_CPI_DEPTH: usize = 1;
+
+// Length of the window daily volume limits are measured over (24 hours)
+pub const DAILY_LIMIT_WINDOW: i64 = 86_400;
+
+// Standing orders
+// Shortest allowed interval between two executions (1 day)
+pub const MIN_STANDING_ORDER_INTERVAL: i64 = 86_400;
+// Delay before a keeper may retry an execution that failed for lack of funds (1 hour)
+pub const STANDING_ORDER_RETRY_DELAY: i64 = 3_600;
+// Failed attempts after which the current execution is given up and the order moves on
+pub const STANDING_ORDER_MAX_RETRIES: u8 = 3;
+
+// Enhanced due diligence
+// Shortest review window a mint can be configured with before auto-reject (1 day)
+pub const MIN_EDD_APPROVAL_WINDOW: i64 = 86_400;
+
+// Multi-officer approval of regulatory actions
+// Largest number of officers in an approval set
+pub const MAX_APPROVAL_SIGNERS: usize = 10;
+// Time officers have to approve and execute a proposal (7 days)
+pub const ACTION_PROPOSAL_LIFETIME: i64 = 7 * 86_400;
+
+// Amount Token-2022 passes to the transfer hook for a confidential transfer,
+// whose amount is encrypted
+pub const CONFIDENTIAL_TRANSFER_AMOUNT: u64 = u64::MAX;
+
+// Account freezes
+// Longest legal reference stored on a freeze record, in bytes
+pub const MAX_LEGAL_REFERENCE_LEN: usize = 64;
+
+// Stored strings, in bytes
+pub const MAX_COUNTRY_CODE_LEN: usize = 2;
+pub const MAX_BLZ_LEN: usize = 8;
+// KYC and screening provider names
+pub const MAX_PROVIDER_NAME_LEN: usize = 32;
+// External identifier of an AML authority (a LEI has 20 characters)
+pub const MAX_AUTHORITY_ID_LEN: usize = 32;
+// IPFS CID of a reserve proof (a base32 CIDv1 has 59 characters)
+pub const MAX_IPFS_CID_LEN: usize = 64;
+
+// Blacklisting
+// Review date given to a blacklist entry created or extended without one (1 year)
+pub const BLACKLIST_REVIEW_INTERVAL: i64 = 365 * 86_400;
+
+// Reserve proof attestation
+// Longest time an auditor may be given to confirm a posted reserve proof (30 days)
+pub const MAX_RESERVE_ATTESTATION_WINDOW: i64 = 30 * 86_400;
+
+// Reserve coverage of the supply that counts as fully collateralized (100%)
+pub const FULL_COVERAGE_BPS: u64 = 10_000;
 
-// Maximum transaction amount (in token units)
-// 100,000 EUR with 9 decimals
-pub const MAX_TRANSACTION_AMOUNT: u64 = 100_000 * 1_000_000_000;
+// Reserve composition
+// Most positions one reserve composition lists
+pub const MAX_RESERVE_ASSETS: usize = 16;
 
-// Decimals for the EUR token
-pub const EUR_DECIMALS: u8 = 9;
+// Program allowlist
+// Most programs whose PDAs one mint allows to hold tokens
+pub const MAX_ALLOWLISTED_PROGRAMS: usize = 16;
+
+// Batch mints
+// Most recipients of one batch mint, bounded by the transaction's account limit
+pub const MAX_BATCH_MINT_RECIPIENTS: usize = 8;
+// Remaining accounts per recipient: token account, KYC user, blacklist entry, user limits,
+// country policy, freeze record
+pub const BATCH_MINT_ACCOUNTS_PER_RECIPIENT: usize = 6;
+
+// Batch freezes and thaws
+// Most token accounts one batch freeze or thaw handles
+pub const MAX_BATCH_FREEZE_ACCOUNTS: usize = 16;
+// Remaining accounts per token account: the token account and its freeze record
+pub const BATCH_FREEZE_ACCOUNTS_PER_TARGET: usize = 2;
+
+// Supply cap and issuance schedule
+// Delay before a raised or lifted supply cap takes effect (7 days)
+pub const SUPPLY_CAP_RAISE_DELAY: i64 = 7 * 86_400;
+// Length of one period of the issuance schedule (30 days)
+pub const ISSUANCE_PERIOD: i64 = 30 * 86_400;
+
+// Timelock
+// Shortest and longest delay of a mint's timelock (1 and 30 days)
+pub const MIN_TIMELOCK_DELAY: i64 = 86_400;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 86_400;
+// Longest whitepaper URI a revision can set
+pub const MAX_WHITEPAPER_URI_LEN: usize = 128;
+
+// Token metadata
+// Symbol of every EUR mint
+pub const TOKEN_SYMBOL: &str = "EURm";
+// Longest name, URI, custom key or value written to the token metadata
+pub const MAX_METADATA_VALUE_LEN: usize = 200;
+
+// Wind-down
+// Shortest redemption period a wind-down can give holders (30 days)
+pub const MIN_REDEMPTION_PERIOD: i64 = 30 * 86_400;
+
+// Account recovery
+// Notice between a recovery request and the transfer of the funds (7 days)
+pub const RECOVERY_WAITING_PERIOD: i64 = 7 * 86_400;
+
+// AML velocity alerts
+// Transfers of a sender kept in its velocity ring buffer
+pub const VELOCITY_RING_SIZE: usize = 16;
+// Window transfer counts and volumes are measured over (1 hour)
+pub const VELOCITY_WINDOW: i64 = 3_600;
+// Window structuring patterns are looked for in (24 hours)
+pub const STRUCTURING_WINDOW: i64 = 86_400;
+
+// Compliance history
+// Status changes, freezes and enforcement actions kept per user
+pub const COMPLIANCE_HISTORY_SIZE: usize = 16;
+
+// Domain separator of holder-balance snapshot leaves
+pub const SNAPSHOT_LEAF_DOMAIN: &[u8] = b"mica-eur-snapshot-v1";
+
+// Domain separator of reserve deposit leaves
+pub const RESERVE_LEAF_DOMAIN: &[u8] = b"mica-eur-reserve-v1";
+
+// Domain separator of the event hashes audit window digests are built from
+pub const AUDIT_EVENT_DOMAIN: &[u8] = b"mica-eur-audit-event-v1";
+
+// Domain separator of the SEPA references mint vouchers and payouts are bound to
+pub const SEPA_REFERENCE_DOMAIN: &[u8] = b"mica-eur-sepa-reference-v1";
+
+// Decimals a EUR mint may be created with: euro cents, micro-euros, or the
+// 9 decimals of the first deployments
+pub const SUPPORTED_EUR_DECIMALS: [u8; 3] = [2, 6, 9];
+
+// Default decimals for new EUR mints
+pub const EUR_DECIMALS: u8 = 6;
 
 // Reference the Token 2022 Program ID
 pub fn token_2022_program_id() -> Pubkey {
     TOKEN_2022_PROGRAM_ID
 }
-
-// Helper function to check if a country is supported
-pub fn is_country_supported(country_code: &str) -> bool {
-    SUPPORTED_COUNTRIES.contains(&country_code)
-} 
\ No newline at end of file
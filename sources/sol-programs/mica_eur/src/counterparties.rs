@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Kind of regulated entity behind an approved counterparty
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum CounterpartyKind {
+    Exchange,
+    PaymentServiceProvider,
+    Merchant,
+}
+
+/// A regulated venue or merchant wallet the issuer approved. The transfer hook
+/// applies its rules whenever it is the sender or the recipient of a transfer.
+#[account]
+#[derive(InitSpace)]
+pub struct ApprovedCounterparty {
+    pub mint: Pubkey,                 // Mint the approval applies to
+    pub wallet: Pubkey,               // Wallet of the approved entity
+    pub kind: CounterpartyKind,
+    pub lei: [u8; 20],                // Legal Entity Identifier of the entity
+    pub daily_limit_exempt: bool,     // Transfers with it do not count against the other side's daily limit
+    pub max_transfer_amount: u64,     // Largest single transfer with it, 0 for no cap
+    pub registered_by: Pubkey,        // Issuer that approved it
+    pub last_update: i64,
+}
+
+impl ApprovedCounterparty {
+    /// Check a transfer of `amount` with the counterparty against its cap.
+    /// `amount` is `None` for confidential transfers, which a cap rejects.
+    pub fn check_transfer(&self, amount: Option<u64>) -> Result<()> {
+        if self.max_transfer_amount > 0 && amount.is_none_or(|amount| amount > self.max_transfer_amount) {
+            return Err(MicaEurError::CounterpartyTransferLimitExceeded.into());
+        }
+        Ok(())
+    }
+}
+
+/// Emitted when the issuer approves a counterparty or changes its rules
+#[event]
+pub struct CounterpartyRegistered {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub kind: CounterpartyKind,
+    pub lei: [u8; 20],
+    pub daily_limit_exempt: bool,
+    pub max_transfer_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer withdraws a counterparty's approval
+#[event]
+pub struct CounterpartyRemoved {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RegisterCounterparty<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [APPROVED_COUNTERPARTY_SEED, mint_info.mint.as_ref(), wallet.as_ref()],
+        bump,
+        space = 8 + ApprovedCounterparty::INIT_SPACE,
+    )]
+    pub approved_counterparty: Account<'info, ApprovedCounterparty>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveCounterparty<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        close = issuer,
+        seeds = [APPROVED_COUNTERPARTY_SEED, mint_info.mint.as_ref(), approved_counterparty.wallet.as_ref()],
+        bump,
+    )]
+    pub approved_counterparty: Account<'info, ApprovedCounterparty>,
+}
+
+// Approve a regulated venue or merchant wallet, or change its rules (issuer only)
+pub(crate) fn register_counterparty(
+    ctx: Context<RegisterCounterparty>,
+    wallet: Pubkey,
+    kind: CounterpartyKind,
+    lei: [u8; 20],
+    daily_limit_exempt: bool,
+    max_transfer_amount: u64,
+) -> Result<()> {
+    if wallet == Pubkey::default() || !lei.iter().all(u8::is_ascii_alphanumeric) {
+        return Err(MicaEurError::InvalidCounterparty.into());
+    }
+
+    let counterparty = &mut ctx.accounts.approved_counterparty;
+    counterparty.mint = ctx.accounts.mint_info.mint;
+    counterparty.wallet = wallet;
+    counterparty.kind = kind;
+    counterparty.lei = lei;
+    counterparty.daily_limit_exempt = daily_limit_exempt;
+    counterparty.max_transfer_amount = max_transfer_amount;
+    counterparty.registered_by = ctx.accounts.issuer.key();
+    counterparty.last_update = Clock::get()?.unix_timestamp;
+
+    emit!(CounterpartyRegistered {
+        mint: counterparty.mint,
+        wallet,
+        kind,
+        lei,
+        daily_limit_exempt,
+        max_transfer_amount,
+        timestamp: counterparty.last_update,
+    });
+    Ok(())
+}
+
+// Withdraw a counterparty's approval and refund its record (issuer only)
+pub(crate) fn remove_counterparty(ctx: Context<RemoveCounterparty>) -> Result<()> {
+    emit!(CounterpartyRemoved {
+        mint: ctx.accounts.approved_counterparty.mint,
+        wallet: ctx.accounts.approved_counterparty.wallet,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::{KycOracleState, KycUser};
+
+/// Whether users of a country may be onboarded, and the largest single mint or
+/// burn they may make. Replaces a compile-time country list so the oracle
+/// authority can follow regulatory changes without redeploying the program.
+#[account]
+#[derive(InitSpace)]
+pub struct CountryPolicy {
+    pub country_code: [u8; 2],        // ISO 3166-1 alpha-2 code
+    pub allowed: bool,                // New users of the country may register
+    pub max_transaction_amount: u64,  // Largest single mint or burn, 0 for no country limit
+    pub set_by: Pubkey,               // Oracle authority that last changed the policy
+    pub last_update: i64,
+}
+
+/// Emitted when the oracle authority sets the policy of a country
+#[event]
+pub struct CountryPolicySet {
+    pub country_code: String,
+    pub allowed: bool,
+    pub max_transaction_amount: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(country_code: String)]
+pub struct SetCountryPolicy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+        has_one = authority,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [COUNTRY_POLICY_SEED, country_code.as_bytes()],
+        bump,
+        space = 8 + CountryPolicy::INIT_SPACE,
+    )]
+    pub country_policy: Account<'info, CountryPolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Check that a country code is two upper-case ASCII letters
+pub fn check_country_code(country_code: &str) -> Result<()> {
+    if country_code.len() != 2 || !country_code.bytes().all(|b| b.is_ascii_uppercase()) {
+        return Err(MicaEurError::InvalidCountryCode.into());
+    }
+    Ok(())
+}
+
+/// Check that `country_policy` is the policy of `country_code` and that it lets
+/// users of the country register or receive newly minted tokens
+pub fn check_country_allowed(country_policy: &CountryPolicy, country_code: &str) -> Result<()> {
+    if country_policy.country_code != country_code.as_bytes() {
+        return Err(MicaEurError::CountryPolicyMismatch.into());
+    }
+    if !country_policy.allowed {
+        return Err(MicaEurError::UnsupportedCountry.into());
+    }
+    Ok(())
+}
+
+/// Check a single mint or burn against the limit of the user's country
+pub fn check_country_transaction(
+    country_policy: &CountryPolicy,
+    kyc_user: &KycUser,
+    amount: u64,
+) -> Result<()> {
+    if country_policy.country_code != kyc_user.country_code.as_bytes() {
+        return Err(MicaEurError::CountryPolicyMismatch.into());
+    }
+    if country_policy.max_transaction_amount > 0 && amount > country_policy.max_transaction_amount {
+        return Err(MicaEurError::TransactionAmountExceedsMaximum.into());
+    }
+    Ok(())
+}
+
+// Allow or deny a country and set its per-transaction limit (KYC oracle authority
+// only). Denying a country stops new registrations and mints to its users; they
+// keep their registration and can still redeem.
+pub(crate) fn set_country_policy(
+    ctx: Context<SetCountryPolicy>,
+    country_code: String,
+    allowed: bool,
+    max_transaction_amount: u64,
+) -> Result<()> {
+    check_country_code(&country_code)?;
+
+    let country_policy = &mut ctx.accounts.country_policy;
+    country_policy.country_code.copy_from_slice(country_code.as_bytes());
+    country_policy.allowed = allowed;
+    country_policy.max_transaction_amount = max_transaction_amount;
+    country_policy.set_by = ctx.accounts.authority.key();
+    country_policy.last_update = Clock::get()?.unix_timestamp;
+
+    emit!(CountryPolicySet {
+        country_code,
+        allowed,
+        max_transaction_amount,
+        authority: country_policy.set_by,
+        timestamp: country_policy.last_update,
+    });
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::error::MicaEurError;
+
+// Layout of the Ed25519 program instruction data
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| MicaEurError::InvalidSignatureInstruction.into())
+}
+
+/// Check that the instruction right before the current one is an Ed25519 program
+/// instruction verifying exactly one signature by `signer` over `message`.
+/// The signature itself is verified by the Ed25519 program; this only binds its
+/// inputs to what the calling instruction expects.
+pub fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        return Err(MicaEurError::MissingSignatureInstruction.into());
+    }
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    if ix.program_id != ed25519_program::ID {
+        return Err(MicaEurError::MissingSignatureInstruction.into());
+    }
+
+    let data = &ix.data;
+    if data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE || data[0] != 1 {
+        return Err(MicaEurError::InvalidSignatureInstruction.into());
+    }
+
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_ix_index = read_u16(data, offsets + 2)?;
+    let pubkey_offset = read_u16(data, offsets + 4)? as usize;
+    let pubkey_ix_index = read_u16(data, offsets + 6)?;
+    let message_offset = read_u16(data, offsets + 8)? as usize;
+    let message_size = read_u16(data, offsets + 10)? as usize;
+    let message_ix_index = read_u16(data, offsets + 12)?;
+
+    // All signature inputs must live in the Ed25519 instruction itself
+    if [signature_ix_index, pubkey_ix_index, message_ix_index]
+        .iter()
+        .any(|index| *index != u16::MAX)
+    {
+        return Err(MicaEurError::InvalidSignatureInstruction.into());
+    }
+
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_SIZE)
+        .ok_or(MicaEurError::InvalidSignatureInstruction)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(MicaEurError::InvalidSignatureInstruction)?;
+
+    if signed_pubkey != signer.as_ref() || signed_message != message {
+        return Err(MicaEurError::SignatureMismatch.into());
+    }
+
+    Ok(())
+}
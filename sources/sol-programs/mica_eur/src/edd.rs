@@ -0,0 +1,664 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::aml::{aml_powers, AmlAuthority};
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::{KycOracleState, KycUser};
+use crate::mint_utils::transfer_checked_with_hook;
+use crate::policy::{check_transfer_parties, edd_reason};
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::MintInfo;
+
+/// Enhanced-due-diligence settings of a mint. The config PDA also owns the escrow
+/// vault holding the funds of transfers waiting for review.
+#[account]
+#[derive(InitSpace)]
+pub struct EddConfig {
+    pub mint: Pubkey,                // Mint the settings apply to
+    pub vault: Pubkey,               // Escrow token account owned by this PDA
+    pub transfer_threshold: u64,     // Transfers of this amount or more need review, 0 disables
+    pub approval_window: i64,        // Seconds a compliance officer has to decide
+    pub pending_count: u64,          // Transfers currently held in escrow
+    pub bump: u8,
+}
+
+/// Why a transfer was routed to review
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum EddReason {
+    FlaggedSender,
+    FlaggedRecipient,
+    LargeTransfer,
+}
+
+/// Lifecycle of a transfer held for review
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PendingApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Expired,
+}
+
+/// A transfer held in escrow until a compliance officer approves or rejects it
+#[account]
+#[derive(InitSpace)]
+pub struct PendingApproval {
+    pub mint: Pubkey,                     // Mint of the escrowed tokens
+    pub request_id: u64,                  // Sender-chosen ID, part of the PDA seeds
+    pub sender: Pubkey,                   // Wallet that requested the transfer
+    pub sender_token_account: Pubkey,     // Refund destination
+    pub recipient: Pubkey,                // Wallet the transfer is for
+    pub recipient_token_account: Pubkey,  // Payout destination on approval
+    pub amount: u64,                      // Escrowed amount
+    pub reason: EddReason,                // Why review is required
+    pub status: PendingApprovalStatus,
+    pub request_time: i64,                // When the funds entered escrow
+    pub expires_at: i64,                  // Auto-reject time
+    pub reviewer: Pubkey,                 // Officer who decided, default if none
+    pub decision_reason: u8,              // Officer's reason code on rejection
+    pub decision_time: i64,               // When the request left the queue, 0 while pending
+}
+
+/// Emitted when the KYC oracle sets or clears a user's EDD flag
+#[event]
+pub struct EddFlagChanged {
+    pub user: Pubkey,
+    pub edd_required: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer changes the EDD settings of a mint
+#[event]
+pub struct EddConfigured {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub transfer_threshold: u64,
+    pub approval_window: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a transfer enters the review queue
+#[event]
+pub struct EddApprovalRequested {
+    pub pending_approval: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub reason: EddReason,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a transfer leaves the review queue
+#[event]
+pub struct EddApprovalResolved {
+    pub pending_approval: Pubkey,
+    pub status: PendingApprovalStatus,
+    pub reviewer: Pubkey,            // Default for expiries
+    pub decision_reason: u8,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct SetEddFlag<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+        has_one = authority,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    #[account(
+        mut,
+        seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureEdd<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [EDD_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+        space = 8 + EddConfig::INIT_SPACE,
+    )]
+    pub edd_config: Account<'info, EddConfig>,
+
+    /// Escrow vault, created beforehand with the config PDA as owner
+    #[account(
+        mut,
+        constraint = vault.owner == edd_config.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = vault.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct RequestEddTransfer<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [EDD_CONFIG_SEED, mint.key().as_ref()],
+        bump = edd_config.bump,
+        has_one = vault,
+    )]
+    pub edd_config: Account<'info, EddConfig>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, sender.key().as_ref()],
+        bump,
+    )]
+    pub sender_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, recipient_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub recipient_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == sender.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = sender_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = recipient_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = sender,
+        seeds = [PENDING_APPROVAL_SEED, sender.key().as_ref(), &request_id.to_le_bytes()],
+        bump,
+        space = 8 + PendingApproval::INIT_SPACE,
+    )]
+    pub pending_approval: Account<'info, PendingApproval>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewEddTransfer<'info> {
+    pub officer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [AML_AUTHORITY_SEED, officer.key().as_ref()],
+        bump,
+        constraint = aml_authority.authority == officer.key(),
+    )]
+    pub aml_authority: Account<'info, AmlAuthority>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [EDD_CONFIG_SEED, mint.key().as_ref()],
+        bump = edd_config.bump,
+        has_one = vault,
+    )]
+    pub edd_config: Account<'info, EddConfig>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pending_approval.mint == mint.key(),
+    )]
+    pub pending_approval: Account<'info, PendingApproval>,
+
+    /// Recipient's account on approval, sender's account on rejection
+    #[account(mut)]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireEddTransfer<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [EDD_CONFIG_SEED, mint.key().as_ref()],
+        bump = edd_config.bump,
+        has_one = vault,
+    )]
+    pub edd_config: Account<'info, EddConfig>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pending_approval.mint == mint.key(),
+    )]
+    pub pending_approval: Account<'info, PendingApproval>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.key() == pending_approval.sender_token_account
+            @ MicaEurError::EddDestinationMismatch,
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+}
+
+// Set or clear the EDD flag of a KYC user
+pub(crate) fn set_edd_flag(ctx: Context<SetEddFlag>, edd_required: bool) -> Result<()> {
+    let kyc_user = &mut ctx.accounts.kyc_user;
+    kyc_user.edd_required = edd_required;
+
+    emit!(EddFlagChanged {
+        user: kyc_user.user,
+        edd_required,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("EDD flag of {} set to {}", kyc_user.user, edd_required);
+    Ok(())
+}
+
+// Create or update the EDD settings of a mint and make sure its vault is usable
+pub(crate) fn configure_edd(
+    ctx: Context<ConfigureEdd>,
+    transfer_threshold: u64,
+    approval_window: i64,
+) -> Result<()> {
+    if approval_window < MIN_EDD_APPROVAL_WINDOW {
+        return Err(MicaEurError::InvalidEddConfig.into());
+    }
+
+    let edd_config = &mut ctx.accounts.edd_config;
+    // The vault holds escrowed funds and cannot be swapped while any are pending
+    if edd_config.pending_count > 0 && edd_config.vault != ctx.accounts.vault.key() {
+        return Err(MicaEurError::InvalidEddConfig.into());
+    }
+    edd_config.mint = ctx.accounts.mint.key();
+    edd_config.vault = ctx.accounts.vault.key();
+    edd_config.transfer_threshold = transfer_threshold;
+    edd_config.approval_window = approval_window;
+    edd_config.bump = ctx.bumps.edd_config;
+
+    // New token accounts start frozen; the mint info PDA holds the freeze authority
+    if ctx.accounts.vault.is_frozen() {
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = token_2022::ThawAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+
+    emit!(EddConfigured {
+        mint: edd_config.mint,
+        vault: edd_config.vault,
+        transfer_threshold,
+        approval_window,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("EDD configured for mint {}", edd_config.mint);
+    Ok(())
+}
+
+// Move a transfer that needs review into escrow. The remaining accounts are the
+// transfer hook's extra accounts for the sender -> vault transfer.
+pub(crate) fn request_edd_transfer<'info>(
+    ctx: Context<'_, '_, '_, 'info, RequestEddTransfer<'info>>,
+    request_id: u64,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Err(MicaEurError::InvalidAmount.into());
+    }
+
+    let sender_kyc_user = &ctx.accounts.sender_kyc_user;
+    let recipient_kyc_user = &ctx.accounts.recipient_kyc_user;
+    check_transfer_parties(sender_kyc_user, recipient_kyc_user)?;
+
+    let reason = match edd_reason(
+        sender_kyc_user,
+        recipient_kyc_user,
+        amount,
+        ctx.accounts.edd_config.transfer_threshold,
+    ) {
+        Some(reason) => reason,
+        None => return Err(MicaEurError::EddNotRequired.into()),
+    };
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.sender_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.sender.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let now = Clock::get()?.unix_timestamp;
+    let edd_config = &mut ctx.accounts.edd_config;
+    edd_config.pending_count = edd_config
+        .pending_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    let expires_at = now
+        .checked_add(edd_config.approval_window)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    let pending_approval = &mut ctx.accounts.pending_approval;
+    pending_approval.mint = ctx.accounts.mint.key();
+    pending_approval.request_id = request_id;
+    pending_approval.sender = ctx.accounts.sender.key();
+    pending_approval.sender_token_account = ctx.accounts.sender_token_account.key();
+    pending_approval.recipient = ctx.accounts.recipient_token_account.owner;
+    pending_approval.recipient_token_account = ctx.accounts.recipient_token_account.key();
+    pending_approval.amount = amount;
+    pending_approval.reason = reason;
+    pending_approval.status = PendingApprovalStatus::Pending;
+    pending_approval.request_time = now;
+    pending_approval.expires_at = expires_at;
+    pending_approval.reviewer = Pubkey::default();
+    pending_approval.decision_reason = 0;
+    pending_approval.decision_time = 0;
+
+    emit!(EddApprovalRequested {
+        pending_approval: pending_approval.key(),
+        sender: pending_approval.sender,
+        recipient: pending_approval.recipient,
+        amount,
+        reason,
+        expires_at,
+        timestamp: now,
+    });
+    msg!("Transfer of {} held for EDD review: {:?}", amount, reason);
+    Ok(())
+}
+
+// Release an escrowed transfer to the recipient (compliance officer)
+pub(crate) fn approve_edd_transfer<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReviewEddTransfer<'info>>,
+) -> Result<()> {
+    check_reviewer(&ctx.accounts.aml_authority)?;
+    let now = Clock::get()?.unix_timestamp;
+    let pending_approval = &ctx.accounts.pending_approval;
+    if pending_approval.status != PendingApprovalStatus::Pending {
+        return Err(MicaEurError::EddApprovalNotPending.into());
+    }
+    if now > pending_approval.expires_at {
+        return Err(MicaEurError::EddApprovalExpired.into());
+    }
+    if ctx.accounts.destination_token_account.key() != pending_approval.recipient_token_account {
+        return Err(MicaEurError::EddDestinationMismatch.into());
+    }
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    release_escrow(
+        &ctx.accounts.edd_config,
+        ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.mint,
+        ctx.accounts.destination_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.remaining_accounts,
+        pending_approval.amount,
+        ctx.accounts.mint_info.decimals,
+    )?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let reviewer = ctx.accounts.officer.key();
+    ctx.accounts.aml_authority.last_action_time = now;
+    resolve(
+        &mut ctx.accounts.edd_config,
+        &mut ctx.accounts.pending_approval,
+        PendingApprovalStatus::Approved,
+        reviewer,
+        0,
+        now,
+    )
+}
+
+// Refund an escrowed transfer to the sender (compliance officer)
+pub(crate) fn reject_edd_transfer<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReviewEddTransfer<'info>>,
+    decision_reason: u8,
+) -> Result<()> {
+    check_reviewer(&ctx.accounts.aml_authority)?;
+    let now = Clock::get()?.unix_timestamp;
+    let pending_approval = &ctx.accounts.pending_approval;
+    if pending_approval.status != PendingApprovalStatus::Pending {
+        return Err(MicaEurError::EddApprovalNotPending.into());
+    }
+    if ctx.accounts.destination_token_account.key() != pending_approval.sender_token_account {
+        return Err(MicaEurError::EddDestinationMismatch.into());
+    }
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    release_escrow(
+        &ctx.accounts.edd_config,
+        ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.mint,
+        ctx.accounts.destination_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.remaining_accounts,
+        pending_approval.amount,
+        ctx.accounts.mint_info.decimals,
+    )?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let reviewer = ctx.accounts.officer.key();
+    ctx.accounts.aml_authority.last_action_time = now;
+    resolve(
+        &mut ctx.accounts.edd_config,
+        &mut ctx.accounts.pending_approval,
+        PendingApprovalStatus::Rejected,
+        reviewer,
+        decision_reason,
+        now,
+    )
+}
+
+// Refund a transfer nobody decided on in time (anyone)
+pub(crate) fn expire_edd_transfer<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExpireEddTransfer<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pending_approval = &ctx.accounts.pending_approval;
+    if pending_approval.status != PendingApprovalStatus::Pending {
+        return Err(MicaEurError::EddApprovalNotPending.into());
+    }
+    if now <= pending_approval.expires_at {
+        return Err(MicaEurError::EddApprovalNotExpired.into());
+    }
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    release_escrow(
+        &ctx.accounts.edd_config,
+        ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.mint,
+        ctx.accounts.sender_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.remaining_accounts,
+        pending_approval.amount,
+        ctx.accounts.mint_info.decimals,
+    )?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    resolve(
+        &mut ctx.accounts.edd_config,
+        &mut ctx.accounts.pending_approval,
+        PendingApprovalStatus::Expired,
+        Pubkey::default(),
+        0,
+        now,
+    )
+}
+
+// Reviews are reserved to active AML authorities holding the EDD review power
+fn check_reviewer(aml_authority: &AmlAuthority) -> Result<()> {
+    if !aml_authority.is_active {
+        return Err(MicaEurError::AmlAuthorityInactive.into());
+    }
+    if !aml_authority.has_power(aml_powers::REVIEW_EDD) {
+        return Err(MicaEurError::UnauthorizedAmlAuthority.into());
+    }
+    Ok(())
+}
+
+// Transfer escrowed funds out of the vault, signing with the config PDA
+#[allow(clippy::too_many_arguments)]
+fn release_escrow<'info>(
+    edd_config: &Account<'info, EddConfig>,
+    vault: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let mint_key = mint.key();
+    let bump = [edd_config.bump];
+    let seeds = &[EDD_CONFIG_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = token_2022::TransferChecked {
+        from: vault,
+        mint: mint.to_account_info(),
+        to: destination,
+        authority: edd_config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer)
+        .with_remaining_accounts(remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, decimals)
+}
+
+// Take a request out of the queue
+fn resolve(
+    edd_config: &mut EddConfig,
+    pending_approval: &mut Account<PendingApproval>,
+    status: PendingApprovalStatus,
+    reviewer: Pubkey,
+    decision_reason: u8,
+    now: i64,
+) -> Result<()> {
+    edd_config.pending_count = edd_config.pending_count.saturating_sub(1);
+    pending_approval.status = status;
+    pending_approval.reviewer = reviewer;
+    pending_approval.decision_reason = decision_reason;
+    pending_approval.decision_time = now;
+
+    emit!(EddApprovalResolved {
+        pending_approval: pending_approval.key(),
+        status,
+        reviewer,
+        decision_reason,
+        timestamp: now,
+    });
+    msg!("EDD request {} resolved: {:?}", pending_approval.key(), status);
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+/// Errors of the MiCA EUR and transfer hook programs. Each variant's code is
+/// `6000 + position` and integrators match on it, so variants are only ever
+/// appended; the SDK decoder documents every code.
 #[error_code]
 pub enum MicaEurError {
     #[msg("User is not KYC verified")]
@@ -52,4 +55,550 @@ pub enum MicaEurError {
     
     #[msg("Blacklist entry already exists for this user")]
     BlacklistEntryAlreadyExists,
-} 
\ No newline at end of file
+    
+    #[msg("Standing order schedule is invalid")]
+    InvalidStandingOrderSchedule,
+    
+    #[msg("Standing order is not active")]
+    StandingOrderNotActive,
+    
+    #[msg("Standing order is not paused")]
+    StandingOrderNotPaused,
+    
+    #[msg("Standing order execution is not due yet")]
+    StandingOrderNotDue,
+    
+    #[msg("Token account does not belong to the expected mint")]
+    TokenAccountMintMismatch,
+    
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    
+    #[msg("Account is already in the requested class")]
+    AccountClassUnchanged,
+    
+    #[msg("Account class requires a linked KYB record")]
+    KybLinkRequired,
+    
+    #[msg("Account class requires incoming transfer memos on the token account")]
+    IncomingMemoRequired,
+    
+    #[msg("Screening provider is not active")]
+    ScreeningProviderInactive,
+    
+    #[msg("Screening attestation is older than the recorded one or dated in the future")]
+    StaleScreeningAttestation,
+    
+    #[msg("Sanctions screening is missing or outdated for this verification level")]
+    SanctionsScreeningOutdated,
+    
+    #[msg("Expected Ed25519 signature instruction is missing")]
+    MissingSignatureInstruction,
+    
+    #[msg("Ed25519 signature instruction is malformed")]
+    InvalidSignatureInstruction,
+    
+    #[msg("Signed key or message does not match the expected values")]
+    SignatureMismatch,
+    
+    #[msg("Operation ID must not be zero")]
+    InvalidOperationId,
+    
+    #[msg("Operation ID has already been used")]
+    OperationAlreadyProcessed,
+    
+    #[msg("User is blacklisted")]
+    UserBlacklisted,
+    
+    #[msg("Transfer hook invoked outside of a token transfer")]
+    NotTransferring,
+    
+    #[msg("Staged rule set thresholds are invalid")]
+    InvalidStagedPolicy,
+    
+    #[msg("Mint is inactive")]
+    MintInactive,
+    
+    #[msg("Migration target mint is invalid")]
+    InvalidMigrationTarget,
+    
+    #[msg("Migration is closed")]
+    MigrationClosed,
+    
+    #[msg("Token account has no balance to migrate")]
+    NothingToMigrate,
+    
+    #[msg("Token activity is paused")]
+    TokenPaused,
+    
+    #[msg("Token is already in the requested pause state")]
+    PauseStateUnchanged,
+    
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Transfer requires enhanced due diligence approval")]
+    EddApprovalRequired,
+    
+    #[msg("Transfer does not require enhanced due diligence")]
+    EddNotRequired,
+    
+    #[msg("Invalid enhanced due diligence settings")]
+    InvalidEddConfig,
+    
+    #[msg("EDD request is not pending")]
+    EddApprovalNotPending,
+    
+    #[msg("EDD request has expired")]
+    EddApprovalExpired,
+    
+    #[msg("EDD request has not expired yet")]
+    EddApprovalNotExpired,
+    
+    #[msg("Token account does not match the EDD request")]
+    EddDestinationMismatch,
+    
+    #[msg("Invalid approval set")]
+    InvalidApprovalSet,
+    
+    #[msg("Action requires approval from the mint's approval set")]
+    MultisigApprovalRequired,
+    
+    #[msg("Signer is not an officer of the approval set")]
+    NotApprovalSigner,
+    
+    #[msg("Officer has already approved this proposal")]
+    ActionAlreadyApproved,
+    
+    #[msg("Proposal is not open")]
+    ProposalNotOpen,
+    
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    
+    #[msg("Proposal was approved under a previous approval set configuration")]
+    ApprovalSetChanged,
+    
+    #[msg("Proposal has not reached its approval threshold")]
+    ApprovalThresholdNotMet,
+    
+    #[msg("Accounts do not match the proposed action")]
+    ActionAccountMismatch,
+    
+    #[msg("Daily mint limit exceeded")]
+    DailyMintLimitExceeded,
+    
+    #[msg("Daily burn limit exceeded")]
+    DailyBurnLimitExceeded,
+    
+    #[msg("Daily transfer limit exceeded")]
+    DailyTransferLimitExceeded,
+    
+    #[msg("Sender has no transfer record to track its daily volume")]
+    TransferRecordRequired,
+    
+    #[msg("Instruction re-entered while a guarded instruction is in progress")]
+    ReentrantInvocation,
+    
+    #[msg("Instruction invoked through too many CPI levels")]
+    InvocationTooDeep,
+    
+    #[msg("Transfer hook invoked as a top-level instruction")]
+    HookInvokedDirectly,
+    
+    #[msg("Transfer hook account is owned by an unexpected program")]
+    HookAccountOwnerMismatch,
+    
+    #[msg("Wallet has no KYC registration")]
+    KycRegistrationMissing,
+    
+    #[msg("Staged rule set version must be newer than the active one")]
+    StagedPolicyVersionNotNewer,
+    
+    #[msg("Migration target mint is inactive")]
+    MigrationTargetInactive,
+    
+    #[msg("Migration target mint belongs to another issuer")]
+    MigrationIssuerMismatch,
+    
+    #[msg("Mint does not match the migration's target mint")]
+    MigrationMintMismatch,
+    
+    #[msg("Invalid reserve auditor or attestation window")]
+    InvalidReserveAuditor,
+    
+    #[msg("Signer is not the mint's reserve auditor")]
+    NotReserveAuditor,
+    
+    #[msg("Merkle root does not match the posted reserve proof")]
+    ReserveRootMismatch,
+    
+    #[msg("Attestation window for the posted reserve proof has elapsed")]
+    AttestationWindowElapsed,
+    
+    #[msg("Minting is halted until the reserve covers the supply again")]
+    MintingHalted,
+    
+    #[msg("Mint would exceed the reported reserve")]
+    ReserveShortfall,
+    
+    #[msg("Snapshot is empty, out of slot order or taken in the future")]
+    InvalidSnapshot,
+    
+    #[msg("Only rejected or expired KYC users can be closed")]
+    KycUserNotClosable,
+    
+    #[msg("KYC user still holds tokens")]
+    KycUserHasBalance,
+    
+    #[msg("Wallet is not in the supervised institution account class")]
+    NotASupervisedInstitution,
+    
+    #[msg("Institution needs a valid LEI and a daily cap")]
+    InvalidInstitution,
+    
+    #[msg("Account carries a Token-2022 extension the program does not support")]
+    UnsupportedTokenExtension,
+    
+    #[msg("Signer is neither the KYC oracle authority nor a KYC provider")]
+    NotKycAuthority,
+    
+    #[msg("KYC user was registered by another provider")]
+    KycProviderMismatch,
+    
+    #[msg("Invalid KYC provider key")]
+    InvalidKycProvider,
+    
+    #[msg("Reconciliation record shows no discrepancy or has an invalid detection time")]
+    InvalidReconciliationRecord,
+    
+    #[msg("Transfer above the travel-rule threshold needs a travel-rule record")]
+    TravelRuleRecordRequired,
+    
+    #[msg("Travel-rule record was already used or is for another amount")]
+    TravelRuleRecordMismatch,
+    
+    #[msg("Travel-rule record needs an amount and originator and beneficiary hashes")]
+    InvalidTravelRuleData,
+    
+    #[msg("Seizure vault still holds funds and cannot be swapped")]
+    SeizureVaultInUse,
+    
+    #[msg("Seizure has already been released or forfeited")]
+    SeizureNotHeld,
+    
+    #[msg("Release destination account is missing")]
+    SeizureDestinationMissing,
+    
+    #[msg("Resolution hash must be set")]
+    InvalidSeizureResolution,
+    
+    #[msg("Invalid freeze reason, legal reference or expiry")]
+    InvalidFreezeRecord,
+    
+    #[msg("Freeze has no expiry or has not expired yet")]
+    FreezeNotExpired,
+    
+    #[msg("Token account is frozen")]
+    AccountFrozenByRecord,
+    
+    #[msg("Transfer would dip into the frozen part of the balance")]
+    FrozenAmountLocked,
+    
+    #[msg("Transfer fee above 100% or without a maximum")]
+    InvalidTransferFee,
+    
+    #[msg("Fee treasury does not match the mint's configured treasury")]
+    FeeTreasuryMismatch,
+    
+    #[msg("Confidential transfers are not enabled for this mint")]
+    ConfidentialTransfersDisabled,
+    
+    #[msg("Auditor ElGamal key must be set")]
+    InvalidAuditorKey,
+    
+    #[msg("Transfer fees and confidential transfers cannot both be enabled")]
+    ConfidentialTransferFeeConflict,
+    
+    #[msg("Signer does not hold the role required for this action")]
+    MissingRole,
+    
+    #[msg("Only the issuer, or the KYC oracle authority for KYC_ADMIN, administers this role")]
+    NotRoleAdmin,
+    
+    #[msg("Unknown role or roles of different scopes combined")]
+    InvalidRole,
+    
+    #[msg("Minter has no allowance for this mint")]
+    MinterAllowanceRequired,
+    
+    #[msg("Mint exceeds the minter's remaining allowance")]
+    MinterAllowanceExceeded,
+    
+    #[msg("Leaf is not included in the published reserve proof")]
+    ReserveLeafNotIncluded,
+    
+    #[msg("Batch mint recipients do not match the amounts or exceed the maximum")]
+    InvalidBatchMint,
+    
+    #[msg("Batch account is not the expected PDA")]
+    BatchAccountMismatch,
+    
+    #[msg("Batch freeze accounts are empty, incomplete or exceed the maximum")]
+    InvalidBatchFreeze,
+    
+    #[msg("Blacklist entry is not active")]
+    BlacklistEntryInactive,
+    
+    #[msg("Mint would exceed the supply cap")]
+    SupplyCapExceeded,
+    
+    #[msg("Mint would exceed the issuance of the current period")]
+    IssuanceScheduleExceeded,
+    
+    #[msg("The mint's issuance policy must be passed")]
+    IssuancePolicyRequired,
+    
+    #[msg("No queued supply cap change is due")]
+    SupplyCapChangeNotDue,
+    
+    #[msg("Change must be queued on the mint's timelock")]
+    TimelockRequired,
+    
+    #[msg("Timelock delay is outside the allowed range")]
+    InvalidTimelockDelay,
+    
+    #[msg("Invalid timelocked change")]
+    InvalidTimelockAction,
+    
+    #[msg("Timelock operation is not queued")]
+    TimelockOperationNotQueued,
+    
+    #[msg("Timelock operation's delay has not passed")]
+    TimelockOperationNotReady,
+    
+    #[msg("Account does not match the timelocked change")]
+    TimelockAccountMismatch,
+    
+    #[msg("White paper URI is empty or too long, or its content hash is missing")]
+    InvalidWhitepaper,
+    
+    #[msg("Token metadata is missing or already initialized, or a field is empty or too long")]
+    InvalidMetadataField,
+    
+    #[msg("Invalid compliance snapshot period")]
+    InvalidComplianceSnapshot,
+    
+    #[msg("Country policy does not match the user's country")]
+    CountryPolicyMismatch,
+    
+    #[msg("IBAN is already linked to another wallet")]
+    IbanAlreadyLinked,
+    
+    #[msg("Invalid IBAN hash")]
+    InvalidIbanHash,
+    
+    #[msg("IBAN cannot be relinked to this wallet")]
+    InvalidIbanRelink,
+    
+    #[msg("A recovery of this token account is already pending")]
+    RecoveryAlreadyRequested,
+    
+    #[msg("Recovery request is not pending")]
+    RecoveryNotPending,
+    
+    #[msg("Recovery has not been approved by an AML authority")]
+    RecoveryNotApproved,
+    
+    #[msg("Recovery waiting period has not passed")]
+    RecoveryNotDue,
+    
+    #[msg("Account does not match the recovery request")]
+    RecoveryAccountMismatch,
+    
+    #[msg("Token account is frozen under an order that blocks recovery")]
+    RecoveryBlockedByFreeze,
+    
+    #[msg("Transfer exceeds the limit set for the approved counterparty")]
+    CounterpartyTransferLimitExceeded,
+    
+    #[msg("Invalid approved counterparty")]
+    InvalidCounterparty,
+    
+    #[msg("Invalid AML alert thresholds")]
+    InvalidAmlAlertConfig,
+    
+    #[msg("Sender must have a velocity record while AML alerts are configured")]
+    VelocityRecordRequired,
+    
+    #[msg("Invalid SAR reference: empty hash or filing date in the future")]
+    InvalidSarReference,
+    
+    #[msg("Invalid blacklist reason: legal reference missing or too long")]
+    InvalidBlacklistReason,
+    
+    #[msg("Invalid blacklist schedule: expiry or review time in the past, or review after expiry")]
+    InvalidBlacklistSchedule,
+    
+    #[msg("Blacklist entry has no expiry or has not expired yet")]
+    BlacklistEntryNotExpired,
+    
+    #[msg("The user is outside the AML authority's jurisdiction")]
+    AmlAuthorityOutOfJurisdiction,
+    
+    #[msg("Invalid AML powers: empty, unknown bits, or seizure without freezing")]
+    InvalidAmlPowers,
+    
+    #[msg("AML authority administration needs the issuer or the registered regulator")]
+    UnauthorizedAmlAdmin,
+    
+    #[msg("Invalid regulator key")]
+    InvalidRegulator,
+    
+    #[msg("Mint decimals must be 2, 6 or 9")]
+    UnsupportedDecimals,
+    
+    #[msg("The mint only mints to the recipient's associated token account")]
+    AssociatedTokenAccountRequired,
+    
+    #[msg("KYC verification has not expired")]
+    KycNotExpired,
+    
+    #[msg("String exceeds its maximum stored length")]
+    StringTooLong,
+    
+    #[msg("Bank code must be an 8-digit German BLZ")]
+    InvalidBlz,
+    
+    #[msg("Verification provider must be 1-32 letters, digits, spaces or -_.")]
+    InvalidVerificationProvider,
+    
+    #[msg("IPFS CID must be a CIDv0 (Qm...) or base32 CIDv1 (b...)")]
+    InvalidIpfsCid,
+    
+    #[msg("KYC status cannot change from the current status to the requested one")]
+    InvalidKycStatusTransition,
+    
+    #[msg("Compliance history does not belong to the token account owner")]
+    ComplianceHistoryMismatch,
+    
+    #[msg("Mint is already active")]
+    MintAlreadyActive,
+    
+    #[msg("Mint was retired by a migration")]
+    MintMigrated,
+    
+    #[msg("Wind-down was already initiated")]
+    WindDownAlreadyInitiated,
+    
+    #[msg("Redemption deadline leaves holders too little time")]
+    InvalidRedemptionDeadline,
+    
+    #[msg("Mint is winding down")]
+    MintWindingDown,
+    
+    #[msg("Treasury vault holds undistributed funds")]
+    TreasuryVaultInUse,
+    
+    #[msg("Mint voucher was already claimed")]
+    MintVoucherClaimed,
+    
+    #[msg("SEPA reference hash is missing")]
+    InvalidSepaReference,
+    
+    #[msg("Operation is not a redemption")]
+    NotARedemption,
+    
+    #[msg("Reserve composition is empty, too long, or has an invalid position")]
+    InvalidReserveComposition,
+    
+    #[msg("Reserve composition does not add up to the reserve total")]
+    ReserveCompositionMismatch,
+    
+    #[msg("Reserve proof is older than the mint's maximum reserve age")]
+    ReserveProofStale,
+    
+    #[msg("Maximum reserve age must not be negative")]
+    InvalidMaxReserveAge,
+    
+    #[msg("Invalid reserve valuation feeds, tolerance or price age")]
+    InvalidReserveValuation,
+    
+    #[msg("Reserve proofs of this mint need its valuation config and price updates")]
+    ReserveValuationRequired,
+    
+    #[msg("Price update is not a verified Pyth update of the expected feed")]
+    InvalidPriceUpdate,
+    
+    #[msg("Price update is older than the valuation's maximum price age")]
+    StalePriceUpdate,
+    
+    #[msg("Reserve position has no configured price feed")]
+    UnpricedReserveAsset,
+    
+    #[msg("Asserted reserve value diverges from the oracle valuation beyond the tolerance")]
+    ReserveValuationDivergence,
+    
+    #[msg("Regulator access is revoked or expired")]
+    RegulatorAccessInactive,
+    
+    #[msg("Audit window is empty or in the future, or its digest does not match its event count")]
+    InvalidAuditWindow,
+    
+    #[msg("Program cannot be allowlisted")]
+    InvalidAllowlistedProgram,
+    
+    #[msg("Program is already allowlisted")]
+    ProgramAlreadyAllowlisted,
+    
+    #[msg("Program allowlist is full")]
+    ProgramAllowlistFull,
+    
+    #[msg("Program is not allowlisted")]
+    ProgramNotAllowlisted,
+    
+    #[msg("Seeds do not derive the escrow authority from the program")]
+    InvalidProgramEscrow,
+    
+    #[msg("Transfer authorization has expired")]
+    TransferAuthorizationExpired,
+    
+    #[msg("Relayer stake is below the registry minimum")]
+    RelayerStakeTooLow,
+    
+    #[msg("Relayer is not registered for this mint")]
+    RelayerNotRegistered,
+    
+    #[msg("Relayer is suspended")]
+    RelayerSuspended,
+    
+    #[msg("Relayer already has this status")]
+    RelayerStatusUnchanged,
+    
+    #[msg("Escrow parties or expiry are invalid")]
+    InvalidEscrow,
+    
+    #[msg("Escrow vault still holds escrowed funds")]
+    EscrowVaultInUse,
+    
+    #[msg("Escrow was already released or refunded")]
+    EscrowNotOpen,
+    
+    #[msg("Signer may not release or refund this escrow")]
+    UnauthorizedEscrowResolution,
+    
+    #[msg("Token account is not the escrow's release or refund destination")]
+    EscrowDestinationMismatch,
+    
+    #[msg("Payment request needs a memo hash and a future expiry")]
+    InvalidPaymentRequest,
+    
+    #[msg("Payment request was already paid")]
+    PaymentRequestNotOpen,
+    
+    #[msg("Payment request has expired")]
+    PaymentRequestExpired,
+    
+    #[msg("Tier limits exceed their daily caps or fall below a lower tier's")]
+    InvalidTierLimits,
+}
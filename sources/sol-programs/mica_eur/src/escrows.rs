@@ -0,0 +1,460 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::batch_mint::load_pda;
+use crate::constants::*;
+use crate::edd::EddConfig;
+use crate::error::MicaEurError;
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::kyc_oracle::KycUser;
+use crate::limits::{tier_limits, LimitConfig};
+use crate::mint_utils::transfer_checked_with_hook;
+use crate::policy::{check_edd_clearance, check_transfer_parties};
+use crate::MintInfo;
+
+/// Vault holding the funds of a mint's commerce escrows. The PDA owns the vault
+/// token account; the transfer hook only checks the buyer or seller on the other
+/// side of its transfers.
+#[account]
+#[derive(InitSpace)]
+pub struct EscrowVault {
+    pub mint: Pubkey,                 // Mint the vault holds
+    pub vault: Pubkey,                // Token account owned by this PDA
+    pub held_amount: u64,             // Funds of open escrows
+    pub open_count: u64,              // Escrows not yet released or refunded
+    pub bump: u8,
+}
+
+/// Lifecycle of a commerce escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum EscrowStatus {
+    Open,
+    Released,
+    Refunded,
+}
+
+/// Payment held until the buyer or the arbiter releases it to the seller, or the
+/// seller or the arbiter refunds it. The buyer may take it back once it expires.
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub mint: Pubkey,                 // Mint of the escrowed tokens
+    pub escrow_id: u64,               // Buyer-chosen ID, part of the PDA seeds
+    pub buyer: Pubkey,                // Wallet that paid into escrow
+    pub buyer_token_account: Pubkey,  // Refund destination
+    pub seller: Pubkey,               // Wallet the payment is for
+    pub seller_token_account: Pubkey, // Release destination
+    pub arbiter: Pubkey,              // May release or refund, default if none
+    pub amount: u64,                  // Escrowed amount
+    pub terms_hash: [u8; 32],         // Hash of the order or contract terms
+    pub status: EscrowStatus,
+    pub opened_at: i64,
+    pub expires_at: i64,              // From then on the buyer may take the funds back
+    pub resolved_by: Pubkey,          // Who released or refunded, default while open
+    pub resolved_at: i64,
+}
+
+/// Emitted when the issuer sets up the escrow vault
+#[event]
+pub struct EscrowVaultConfigured {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a buyer pays into escrow
+#[event]
+pub struct EscrowOpened {
+    pub escrow: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub arbiter: Pubkey,
+    pub amount: u64,
+    pub terms_hash: [u8; 32],
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an escrow is released to the seller or refunded to the buyer
+#[event]
+pub struct EscrowResolved {
+    pub escrow: Pubkey,
+    pub status: EscrowStatus,
+    pub resolved_by: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureEscrowVault<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [ESCROW_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        space = 8 + EscrowVault::INIT_SPACE,
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+
+    /// Vault, created beforehand with the escrow vault PDA as owner
+    #[account(
+        mut,
+        constraint = vault.owner == escrow_vault.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = vault.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct OpenEscrow<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_VAULT_SEED, mint.key().as_ref()],
+        bump = escrow_vault.bump,
+        has_one = vault,
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, seller_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub seller_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == buyer.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = buyer_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = seller_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [EDD_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub edd_config: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub limit_config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [ESCROW_SEED, buyer.key().as_ref(), &escrow_id.to_le_bytes()],
+        bump,
+        space = 8 + Escrow::INIT_SPACE,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveEscrow<'info> {
+    /// Buyer or arbiter on release; seller, arbiter or, once expired, the buyer on refund
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_VAULT_SEED, mint.key().as_ref()],
+        bump = escrow_vault.bump,
+        has_one = vault,
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, escrow.buyer.as_ref(), &escrow.escrow_id.to_le_bytes()],
+        bump,
+        has_one = mint,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Seller's account on release, buyer's account on refund
+    #[account(mut)]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+}
+
+// Set up the vault commerce escrows hold their funds in (issuer only)
+pub(crate) fn configure_escrow_vault(ctx: Context<ConfigureEscrowVault>) -> Result<()> {
+    let escrow_vault = &mut ctx.accounts.escrow_vault;
+    // The vault cannot be swapped while it holds escrowed funds
+    if escrow_vault.held_amount > 0 && escrow_vault.vault != ctx.accounts.vault.key() {
+        return Err(MicaEurError::EscrowVaultInUse.into());
+    }
+    escrow_vault.mint = ctx.accounts.mint.key();
+    escrow_vault.vault = ctx.accounts.vault.key();
+    escrow_vault.bump = ctx.bumps.escrow_vault;
+
+    // New token accounts start frozen; the mint info PDA holds the freeze authority
+    if ctx.accounts.vault.is_frozen() {
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = token_2022::ThawAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+
+    emit!(EscrowVaultConfigured {
+        mint: escrow_vault.mint,
+        vault: escrow_vault.vault,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Escrow vault configured for mint {}", escrow_vault.mint);
+    Ok(())
+}
+
+// Pay `amount` into escrow for the owner of the seller token account. Both parties
+// are checked here as for a direct transfer, which escrow must not get around;
+// the transfer hook checks the buyer again on the way in. The remaining accounts
+// are the transfer hook's extra accounts for the buyer -> vault transfer.
+pub(crate) fn open_escrow<'info>(
+    ctx: Context<'_, '_, '_, 'info, OpenEscrow<'info>>,
+    escrow_id: u64,
+    amount: u64,
+    arbiter: Pubkey,
+    terms_hash: [u8; 32],
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if amount == 0 {
+        return Err(MicaEurError::InvalidAmount.into());
+    }
+    let seller = ctx.accounts.seller_token_account.owner;
+    let buyer = ctx.accounts.buyer.key();
+    if expires_at <= now || seller == buyer || arbiter == buyer || arbiter == seller {
+        return Err(MicaEurError::InvalidEscrow.into());
+    }
+
+    let buyer_kyc_user = &ctx.accounts.buyer_kyc_user;
+    let seller_kyc_user = &ctx.accounts.seller_kyc_user;
+    check_transfer_parties(buyer_kyc_user, seller_kyc_user)?;
+    let mint = ctx.accounts.mint.key();
+    let threshold = load_pda::<EddConfig>(&ctx.accounts.edd_config, &[EDD_CONFIG_SEED, mint.as_ref()])?
+        .map_or(0, |config| config.transfer_threshold);
+    check_edd_clearance(buyer_kyc_user, seller_kyc_user, amount, threshold)?;
+    let limit_config = load_pda::<LimitConfig>(&ctx.accounts.limit_config, &[LIMIT_CONFIG_SEED, mint.as_ref()])?;
+    tier_limits(limit_config.as_ref(), buyer_kyc_user.verification_level, ctx.accounts.mint_info.decimals)
+        .check_transaction(amount)?;
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.buyer_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.buyer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let escrow_vault = &mut ctx.accounts.escrow_vault;
+    escrow_vault.held_amount = escrow_vault.held_amount.checked_add(amount).ok_or(MicaEurError::ArithmeticOverflow)?;
+    escrow_vault.open_count = escrow_vault.open_count.saturating_add(1);
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.mint = mint;
+    escrow.escrow_id = escrow_id;
+    escrow.buyer = buyer;
+    escrow.buyer_token_account = ctx.accounts.buyer_token_account.key();
+    escrow.seller = seller;
+    escrow.seller_token_account = ctx.accounts.seller_token_account.key();
+    escrow.arbiter = arbiter;
+    escrow.amount = amount;
+    escrow.terms_hash = terms_hash;
+    escrow.status = EscrowStatus::Open;
+    escrow.opened_at = now;
+    escrow.expires_at = expires_at;
+    escrow.resolved_by = Pubkey::default();
+    escrow.resolved_at = 0;
+
+    emit!(EscrowOpened {
+        escrow: escrow.key(),
+        buyer,
+        seller,
+        arbiter,
+        amount,
+        terms_hash,
+        expires_at,
+        timestamp: now,
+    });
+    msg!("Escrow of {} opened by {} for {}", amount, buyer, seller);
+    Ok(())
+}
+
+// Pay the escrowed funds out to the seller (buyer or arbiter). The transfer hook
+// checks the seller; the remaining accounts are its extra accounts for the
+// vault -> seller transfer.
+pub(crate) fn release_escrow<'info>(ctx: Context<'_, '_, '_, 'info, ResolveEscrow<'info>>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    let authority = ctx.accounts.authority.key();
+    if authority != escrow.buyer && (escrow.arbiter == Pubkey::default() || authority != escrow.arbiter) {
+        return Err(MicaEurError::UnauthorizedEscrowResolution.into());
+    }
+    if ctx.accounts.destination_token_account.key() != escrow.seller_token_account {
+        return Err(MicaEurError::EscrowDestinationMismatch.into());
+    }
+    resolve(ctx, EscrowStatus::Released)
+}
+
+// Return the escrowed funds to the buyer (seller or arbiter, or the buyer once the
+// escrow expired). The transfer hook checks the buyer; the remaining accounts are
+// its extra accounts for the vault -> buyer transfer.
+pub(crate) fn refund_escrow<'info>(ctx: Context<'_, '_, '_, 'info, ResolveEscrow<'info>>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    let authority = ctx.accounts.authority.key();
+    let expired = Clock::get()?.unix_timestamp >= escrow.expires_at;
+    let allowed = authority == escrow.seller
+        || (escrow.arbiter != Pubkey::default() && authority == escrow.arbiter)
+        || (authority == escrow.buyer && expired);
+    if !allowed {
+        return Err(MicaEurError::UnauthorizedEscrowResolution.into());
+    }
+    if ctx.accounts.destination_token_account.key() != escrow.buyer_token_account {
+        return Err(MicaEurError::EscrowDestinationMismatch.into());
+    }
+    resolve(ctx, EscrowStatus::Refunded)
+}
+
+// Move the escrowed funds out of the vault, signing with the vault PDA, and close
+// the escrow with `status`
+fn resolve<'info>(ctx: Context<'_, '_, '_, 'info, ResolveEscrow<'info>>, status: EscrowStatus) -> Result<()> {
+    if ctx.accounts.escrow.status != EscrowStatus::Open {
+        return Err(MicaEurError::EscrowNotOpen.into());
+    }
+    let amount = ctx.accounts.escrow.amount;
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.accounts.escrow_vault.bump];
+    let seeds = &[ESCROW_VAULT_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination_token_account.to_account_info(),
+        authority: ctx.accounts.escrow_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let escrow_vault = &mut ctx.accounts.escrow_vault;
+    escrow_vault.held_amount = escrow_vault.held_amount.saturating_sub(amount);
+    escrow_vault.open_count = escrow_vault.open_count.saturating_sub(1);
+
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.status = status;
+    escrow.resolved_by = ctx.accounts.authority.key();
+    escrow.resolved_at = now;
+
+    emit!(EscrowResolved {
+        escrow: escrow.key(),
+        status,
+        resolved_by: escrow.resolved_by,
+        amount,
+        timestamp: now,
+    });
+    msg!("Escrow {} {:?}", escrow.key(), status);
+    Ok(())
+}
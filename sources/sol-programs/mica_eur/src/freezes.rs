@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::StateWithExtensions, state::Account as TokenAccountState,
+};
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+
+/// Reason codes of a freeze. Codes from 128 up are free for issuer-specific use.
+pub mod freeze_reasons {
+    pub const COURT_ORDER: u8 = 1;
+    pub const SANCTIONS: u8 = 2;
+    pub const AML_INVESTIGATION: u8 = 3;
+    pub const FRAUD: u8 = 4;
+    pub const ACCOUNT_COMPROMISE: u8 = 5;
+    pub const REGULATOR_REQUEST: u8 = 6;
+    pub const BLACKLISTED: u8 = 7;
+    pub const WALLET_MIGRATED: u8 = 8;
+}
+
+/// Why, by whom and how far a token account is frozen.
+///
+/// A full freeze without expiry sets the Token-2022 frozen flag, which only the
+/// freeze authority can lift. Freezes with an expiry, and partial freezes locking
+/// `frozen_amount` of the balance, are enforced by the transfer hook and on
+/// redemption instead, so that `auto_thaw` can lift them without the freeze
+/// authority once they expire.
+#[account]
+#[derive(InitSpace)]
+pub struct FreezeRecord {
+    pub mint: Pubkey,                 // The EUR mint
+    pub token_account: Pubkey,        // Frozen token account
+    pub owner: Pubkey,                // Its owner when frozen
+    pub authority: Pubkey,            // Freeze authority that froze it
+    pub reason_code: u8,              // See `freeze_reasons`
+    #[max_len(MAX_LEGAL_REFERENCE_LEN)]
+    pub legal_reference: String,      // Case or order reference
+    pub frozen_amount: u64,           // Balance locked by a partial freeze, 0 for a full freeze
+    pub expires_at: i64,              // When the freeze lapses, 0 for no expiry
+    pub freeze_time: i64,             // When it was frozen
+    pub is_active: bool,              // False once thawed or expired
+    pub lifted_by: Pubkey,            // Freeze authority that thawed it, default when expired
+    pub lifted_time: i64,             // When it was lifted, 0 while active
+}
+
+impl FreezeRecord {
+    /// Whether the freeze restricts the account at `now`
+    pub fn in_force(&self, now: i64) -> bool {
+        self.is_active && (self.expires_at == 0 || now < self.expires_at)
+    }
+
+    /// Whether the freeze is carried by the Token-2022 frozen flag
+    pub fn uses_frozen_flag(&self) -> bool {
+        self.frozen_amount == 0 && self.expires_at == 0
+    }
+}
+
+/// Emitted when a freeze is recorded
+#[event]
+pub struct FreezeRecorded {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub authority: Pubkey,
+    pub reason_code: u8,
+    pub legal_reference: String,
+    pub frozen_amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a recorded freeze is thawed or lapses
+#[event]
+pub struct FreezeLifted {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub lifted_by: Pubkey,            // Default for expiries
+    pub expired: bool,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct AutoThaw<'info> {
+    #[account(
+        mut,
+        seeds = [FREEZE_RECORD_SEED, freeze_record.token_account.as_ref()],
+        bump,
+    )]
+    pub freeze_record: Account<'info, FreezeRecord>,
+}
+
+/// Fill in a new freeze. A record still in force cannot be replaced; it must be
+/// thawed first.
+#[allow(clippy::too_many_arguments)]
+pub fn record_freeze(
+    record: &mut FreezeRecord,
+    mint: Pubkey,
+    token_account: Pubkey,
+    owner: Pubkey,
+    authority: Pubkey,
+    reason_code: u8,
+    legal_reference: String,
+    frozen_amount: u64,
+    expires_at: i64,
+    now: i64,
+) -> Result<()> {
+    if record.in_force(now) {
+        return Err(MicaEurError::AccountAlreadyFrozen.into());
+    }
+    if reason_code == 0
+        || legal_reference.len() > MAX_LEGAL_REFERENCE_LEN
+        || (expires_at != 0 && expires_at <= now)
+    {
+        return Err(MicaEurError::InvalidFreezeRecord.into());
+    }
+
+    record.mint = mint;
+    record.token_account = token_account;
+    record.owner = owner;
+    record.authority = authority;
+    record.reason_code = reason_code;
+    record.legal_reference = legal_reference;
+    record.frozen_amount = frozen_amount;
+    record.expires_at = expires_at;
+    record.freeze_time = now;
+    record.is_active = true;
+    record.lifted_by = Pubkey::default();
+    record.lifted_time = 0;
+
+    emit!(FreezeRecorded {
+        mint,
+        token_account,
+        authority,
+        reason_code,
+        legal_reference: record.legal_reference.clone(),
+        frozen_amount,
+        expires_at,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Mark a freeze as lifted by `lifted_by`, default for an expiry
+pub fn lift_freeze(record: &mut FreezeRecord, lifted_by: Pubkey, now: i64) {
+    record.is_active = false;
+    record.lifted_by = lifted_by;
+    record.lifted_time = now;
+
+    emit!(FreezeLifted {
+        mint: record.mint,
+        token_account: record.token_account,
+        lifted_by,
+        expired: lifted_by == Pubkey::default(),
+        timestamp: now,
+    });
+}
+
+/// Owner of a Token-2022 token account
+pub fn token_account_owner(token_account: &AccountInfo) -> Result<Pubkey> {
+    let data = token_account.try_borrow_data()?;
+    Ok(StateWithExtensions::<TokenAccountState>::unpack(&data)?.base.owner)
+}
+
+/// Reject moving funds out of an account under a freeze in force: anything under a
+/// full freeze, anything leaving `remaining_balance` below the frozen amount under a
+/// partial one
+pub fn check_outgoing_freeze(record: Option<&FreezeRecord>, remaining_balance: u64, now: i64) -> Result<()> {
+    match record {
+        Some(record) if record.in_force(now) => {
+            if record.frozen_amount == 0 {
+                return Err(MicaEurError::AccountFrozenByRecord.into());
+            }
+            if remaining_balance < record.frozen_amount {
+                return Err(MicaEurError::FrozenAmountLocked.into());
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject funds coming into an account under a full freeze
+pub fn check_incoming_freeze(record: Option<&FreezeRecord>, now: i64) -> Result<()> {
+    match record {
+        Some(record) if record.in_force(now) && record.frozen_amount == 0 => {
+            Err(MicaEurError::AccountFrozenByRecord.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+// Lift a freeze whose expiry has passed (anyone)
+pub(crate) fn auto_thaw(ctx: Context<AutoThaw>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let record = &mut ctx.accounts.freeze_record;
+    if !record.is_active || record.expires_at == 0 || now < record.expires_at {
+        return Err(MicaEurError::FreezeNotExpired.into());
+    }
+    lift_freeze(record, Pubkey::default(), now);
+    msg!("Freeze of {} expired", record.token_account);
+    Ok(())
+}
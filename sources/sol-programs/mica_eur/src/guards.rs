@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+
+/// Per-mint flag held while a settlement instruction runs its CPIs. Solana already
+/// rejects A -> B -> A chains, but a program may invoke itself; the flag is written
+/// to the account before the first CPI so a nested invocation sees it.
+#[account]
+#[derive(InitSpace)]
+pub struct InvocationGuard {
+    pub mint: Pubkey,                 // Mint the guard protects
+    pub entered: bool,                // A guarded instruction is in progress
+    pub entered_slot: u64,            // Slot the last guarded instruction started in
+}
+
+/// Reject invocations nested deeper than a direct call or one CPI from a
+/// counterpart program (escrow, DvP settlement)
+pub fn check_stack_height() -> Result<()> {
+    if get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT + MAX_SETTLEMENT_CPI_DEPTH {
+        return Err(MicaEurError::InvocationTooDeep.into());
+    }
+    Ok(())
+}
+
+/// Check the stack height and raise the guard, persisting it before any CPI
+pub fn enter_guard(guard: &mut Account<InvocationGuard>) -> Result<()> {
+    check_stack_height()?;
+    if guard.entered {
+        return Err(MicaEurError::ReentrantInvocation.into());
+    }
+    guard.entered = true;
+    guard.entered_slot = Clock::get()?.slot;
+    guard.exit(&crate::ID)
+}
+
+/// Lower the guard once the CPIs are done. A failing instruction reverts the raised
+/// flag with the rest of the transaction, so the guard never stays up.
+pub fn leave_guard(guard: &mut Account<InvocationGuard>) {
+    guard.entered = false;
+}
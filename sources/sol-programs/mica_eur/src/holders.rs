@@ -0,0 +1,410 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token_2022::{
+    self,
+    spl_token_2022::{
+        self,
+        extension::StateWithExtensions,
+        state::{Account as TokenAccountState, AccountState},
+    },
+};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::aml::BlacklistEntry;
+use crate::batch_mint::load_if_initialized;
+use crate::compliance_history::{record_history, ComplianceAction, ComplianceHistory};
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::freezes::FreezeRecord;
+use crate::kyc_oracle::{KycStatus, KycUser};
+use crate::mint_utils::check_mint_extensions;
+use crate::policy::check_account_holder;
+use crate::MintInfo;
+
+/// Associated token account opened through the program for a holder of the EUR mint.
+/// Records exist while the account is open, so their number, kept in
+/// `MintInfo::holder_count`, follows the holder base for significance monitoring.
+#[account]
+#[derive(InitSpace)]
+pub struct HolderRecord {
+    pub mint: Pubkey,                 // The EUR mint
+    pub owner: Pubkey,                // Wallet owning the token account
+    pub token_account: Pubkey,        // The owner's associated token account
+    pub created_at: i64,              // When the account was created
+}
+
+/// Emitted when a holder's associated token account is created
+#[event]
+pub struct TokenAccountCreated {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub frozen: bool,                 // Whether it starts frozen, as DefaultAccountState requires
+    pub holder_count: u64,            // Holder records of the mint after this one
+    pub timestamp: i64,
+}
+
+/// Emitted when a holder closes a recorded token account
+#[event]
+pub struct TokenAccountClosed {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub holder_count: u64,            // Holder records of the mint left
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct CreateTokenAccount<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// The owner's associated token account, created by this instruction
+    /// CHECK: Address derived from the owner and mint; initialized by the associated token program
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &owner.key(),
+            &mint.key(),
+            &spl_token_2022::ID,
+        )
+    )]
+    pub token_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Must be the mint of `mint_info`; extensions checked in the handler
+    pub mint: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint @ MicaEurError::TokenAccountMintMismatch,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Token program: must be Token-2022
+    #[account(address = spl_token_2022::ID)]
+    pub token_program: Program<'info, token_2022::Token2022>,
+    
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [HOLDER_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+        space = 8 + HolderRecord::INIT_SPACE,
+    )]
+    pub holder_record: Account<'info, HolderRecord>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// Create the owner's associated token account under Token-2022. The mint's
+// DefaultAccountState extension leaves it frozen until the freeze authority thaws
+// it or the first mint does.
+pub(crate) fn create_token_account(ctx: Context<CreateTokenAccount>) -> Result<()> {
+    check_mint_extensions(&ctx.accounts.mint.to_account_info())?;
+
+    associated_token::create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.owner.to_account_info(),
+            associated_token: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+
+    let frozen = {
+        let data = ctx.accounts.token_account.try_borrow_data()?;
+        StateWithExtensions::<TokenAccountState>::unpack(&data)?.base.state == AccountState::Frozen
+    };
+    let now = Clock::get()?.unix_timestamp;
+    let holder_record = &mut ctx.accounts.holder_record;
+    holder_record.mint = ctx.accounts.mint.key();
+    holder_record.owner = ctx.accounts.owner.key();
+    holder_record.token_account = ctx.accounts.token_account.key();
+    holder_record.created_at = now;
+
+    let mint_info = &mut ctx.accounts.mint_info;
+    mint_info.holder_count = mint_info.holder_count.checked_add(1).ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(TokenAccountCreated {
+        mint: holder_record.mint,
+        owner: holder_record.owner,
+        token_account: holder_record.token_account,
+        frozen,
+        holder_count: mint_info.holder_count,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Emitted when a verified holder's token account is thawed for use
+#[event]
+pub struct TokenAccountActivated {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub verification_level: u8,
+    pub activated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ActivateTokenAccount<'info> {
+    /// The freeze authority, or anyone presenting the owner's KYC record
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint @ MicaEurError::TokenAccountMintMismatch,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: Must be the mint of `mint_info`
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// KYC record of the token account's owner
+    #[account(
+        seeds = [KYC_USER_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    /// The owner's blacklist entry
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// The account's freeze record
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_record: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    #[account(address = spl_token_2022::ID)]
+    pub token_program: Program<'info, token_2022::Token2022>,
+}
+
+// Thaw a new token account once its owner may transfer. Only the default frozen
+// state is lifted: an account under a recorded freeze stays frozen until the freeze
+// authority thaws it or the freeze lapses.
+pub(crate) fn activate_token_account(ctx: Context<ActivateTokenAccount>) -> Result<()> {
+    let freeze_record = load_if_initialized::<FreezeRecord>(&ctx.accounts.freeze_record)?;
+    if freeze_record.is_some_and(|record| record.is_active) {
+        return Err(MicaEurError::AccountFrozenByRecord.into());
+    }
+    if !ctx.accounts.token_account.is_frozen() {
+        return Err(MicaEurError::AccountNotFrozen.into());
+    }
+    let blacklist_entry = load_if_initialized::<BlacklistEntry>(&ctx.accounts.blacklist_entry)?;
+    check_account_holder(&ctx.accounts.kyc_user, blacklist_entry.as_ref())?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+    token_2022::thaw_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_2022::ThawAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    emit!(TokenAccountActivated {
+        mint: mint_key,
+        token_account: ctx.accounts.token_account.key(),
+        owner: ctx.accounts.kyc_user.user,
+        verification_level: ctx.accounts.kyc_user.verification_level,
+        activated_by: ctx.accounts.caller.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Emitted when a token account is frozen because its owner's KYC expired
+#[event]
+pub struct KycExpiryEnforced {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub expiry_time: i64,             // When the owner's verification lapsed
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct EnforceKycExpiry<'info> {
+    /// Anyone
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint @ MicaEurError::TokenAccountMintMismatch,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: Must be the mint of `mint_info`
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// KYC record of the token account's owner
+    #[account(
+        seeds = [KYC_USER_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    /// Token program: must be Token-2022
+    #[account(address = spl_token_2022::ID)]
+    pub token_program: Program<'info, token_2022::Token2022>,
+
+    /// The owner's compliance history, recorded into once opened
+    #[account(
+        mut,
+        seeds = [COMPLIANCE_HISTORY_SEED, token_account.owner.as_ref()],
+        bump,
+    )]
+    pub compliance_history: Option<Account<'info, ComplianceHistory>>,
+}
+
+// Freeze the token account of a user whose verification has lapsed (anyone). No
+// freeze record is written: once the owner is verified again, the account is
+// thawed through `activate_token_account` like a new one.
+pub(crate) fn enforce_kyc_expiry(ctx: Context<EnforceKycExpiry>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let kyc_user = &ctx.accounts.kyc_user;
+    let lapsed = kyc_user.status == KycStatus::Verified && kyc_user.expiry_time > 0 && now > kyc_user.expiry_time;
+    if !lapsed && kyc_user.status != KycStatus::Expired {
+        return Err(MicaEurError::KycNotExpired.into());
+    }
+    if ctx.accounts.token_account.is_frozen() {
+        return Err(MicaEurError::AccountAlreadyFrozen.into());
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+    token_2022::freeze_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_2022::FreezeAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    record_history(
+        ctx.accounts.compliance_history.as_deref_mut(),
+        ComplianceAction::KycExpiryEnforced,
+        ctx.accounts.caller.key(),
+        [0; 32],
+        now,
+    );
+
+    emit!(KycExpiryEnforced {
+        mint: mint_key,
+        token_account: ctx.accounts.token_account.key(),
+        owner: kyc_user.user,
+        expiry_time: kyc_user.expiry_time,
+        caller: ctx.accounts.caller.key(),
+        timestamp: now,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseTokenAccount<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = token_account.owner == owner.key() @ MicaEurError::TokenAccountOwnerMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Must be the mint of `mint_info`
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [HOLDER_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+        has_one = owner,
+    )]
+    pub holder_record: Account<'info, HolderRecord>,
+
+    /// Token program: must be Token-2022
+    #[account(address = spl_token_2022::ID)]
+    pub token_program: Program<'info, token_2022::Token2022>,
+}
+
+// Close the owner's token account and its holder record. Token-2022 refuses
+// accounts with a balance, withheld fees or a freeze.
+pub(crate) fn close_token_account(ctx: Context<CloseTokenAccount>) -> Result<()> {
+    token_2022::close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        token_2022::CloseAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    ))?;
+
+    let mint_info = &mut ctx.accounts.mint_info;
+    mint_info.holder_count = mint_info.holder_count.checked_sub(1).ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(TokenAccountClosed {
+        mint: mint_info.mint,
+        owner: ctx.accounts.owner.key(),
+        token_account: ctx.accounts.token_account.key(),
+        holder_count: mint_info.holder_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
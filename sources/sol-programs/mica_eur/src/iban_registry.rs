@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::KycOracleState;
+
+/// Wallet a bank account is linked to. One record per IBAN hash, created when
+/// the first wallet registers with it, so the same bank account cannot back
+/// several KYC identities.
+#[account]
+#[derive(InitSpace)]
+pub struct IbanRegistry {
+    pub iban_hash: [u8; 32],          // SHA-256 hash of the IBAN
+    pub user: Pubkey,                 // Wallet the IBAN is linked to
+    pub link_time: i64,               // When the IBAN was linked to `user`
+    pub relink_count: u32,            // Times the IBAN moved to another wallet
+}
+
+/// Emitted when the oracle authority moves an IBAN to another wallet
+#[event]
+pub struct IbanRelinked {
+    pub iban_hash: [u8; 32],
+    pub old_user: Pubkey,
+    pub new_user: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct RelinkIban<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+        has_one = authority,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    #[account(
+        mut,
+        seeds = [IBAN_REGISTRY_SEED, iban_registry.iban_hash.as_ref()],
+        bump,
+    )]
+    pub iban_registry: Account<'info, IbanRegistry>,
+}
+
+/// Link an IBAN to the wallet registering with it, rejecting IBANs already linked
+/// to another wallet. `iban_registry` is freshly created if the IBAN is new.
+pub fn link_iban(iban_registry: &mut IbanRegistry, iban_hash: [u8; 32], user: Pubkey, now: i64) -> Result<()> {
+    if iban_hash == [0u8; 32] {
+        return Err(MicaEurError::InvalidIbanHash.into());
+    }
+    if iban_registry.user == user {
+        return Ok(());
+    }
+    if iban_registry.user != Pubkey::default() {
+        return Err(MicaEurError::IbanAlreadyLinked.into());
+    }
+    iban_registry.iban_hash = iban_hash;
+    iban_registry.user = user;
+    iban_registry.link_time = now;
+    iban_registry.relink_count = 0;
+    Ok(())
+}
+
+/// Move an IBAN to `new_user`, returning the wallet it was linked to
+pub fn relink(iban_registry: &mut IbanRegistry, new_user: Pubkey, now: i64) -> Result<Pubkey> {
+    if new_user == Pubkey::default() || new_user == iban_registry.user {
+        return Err(MicaEurError::InvalidIbanRelink.into());
+    }
+    let old_user = iban_registry.user;
+    iban_registry.user = new_user;
+    iban_registry.link_time = now;
+    iban_registry.relink_count = iban_registry
+        .relink_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    Ok(old_user)
+}
+
+// Move an IBAN to a new wallet, e.g. after the user lost their key (KYC oracle
+// authority only). The new wallet can then register with the IBAN.
+pub(crate) fn relink_iban(ctx: Context<RelinkIban>, new_user: Pubkey) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let iban_registry = &mut ctx.accounts.iban_registry;
+    let old_user = relink(iban_registry, new_user, now)?;
+
+    emit!(IbanRelinked {
+        iban_hash: iban_registry.iban_hash,
+        old_user,
+        new_user,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+    msg!("IBAN relinked from {} to {}", old_user, new_user);
+    Ok(())
+}
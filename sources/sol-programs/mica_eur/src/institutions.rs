@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::KycUser;
+use crate::policy::AccountClass;
+use crate::MintInfo;
+
+/// A supervised institution (bank, EMI) the issuer admitted to the fast lane.
+/// While its KYC user stays in the `SupervisedInstitution` class, the transfer hook
+/// lets its transfers through without the per-transfer tier limit, holds it to
+/// `daily_cap` instead of the tier's daily limit and reports every transfer it
+/// sends or receives.
+#[account]
+#[derive(InitSpace)]
+pub struct FastLaneInstitution {
+    pub mint: Pubkey,                 // Mint the admission applies to
+    pub wallet: Pubkey,               // Wallet of the institution
+    pub lei: [u8; 20],                // Legal Entity Identifier of the institution
+    pub daily_cap: u64,               // Aggregate it may send within 24 hours
+    pub registered_by: Pubkey,        // Issuer that admitted it
+    pub last_update: i64,
+}
+
+impl FastLaneInstitution {
+    /// Whether the fast lane applies to `kyc_user`, the wallet's KYC user
+    pub fn applies_to(&self, kyc_user: Option<&KycUser>) -> bool {
+        kyc_user.is_some_and(|kyc_user| {
+            kyc_user.user == self.wallet && kyc_user.account_class == AccountClass::SupervisedInstitution
+        })
+    }
+}
+
+/// Emitted when the issuer admits an institution to the fast lane or changes its cap
+#[event]
+pub struct InstitutionWhitelisted {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub lei: [u8; 20],
+    pub daily_cap: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer removes an institution from the fast lane
+#[event]
+pub struct InstitutionRemoved {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Post-trade report of a transfer sent or received by a fast-lane institution,
+/// emitted by the transfer hook. `amount` is `None` for confidential transfers.
+#[event]
+pub struct InstitutionalTransferReported {
+    pub mint: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: Option<u64>,
+    pub fast_lane_sender: bool,
+    pub fast_lane_recipient: bool,
+    pub rule_set_version: u32,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct WhitelistInstitution<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, wallet.as_ref()],
+        bump,
+        constraint = kyc_user.account_class == AccountClass::SupervisedInstitution @ MicaEurError::NotASupervisedInstitution,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [FAST_LANE_INSTITUTION_SEED, mint_info.mint.as_ref(), wallet.as_ref()],
+        bump,
+        space = 8 + FastLaneInstitution::INIT_SPACE,
+    )]
+    pub institution: Account<'info, FastLaneInstitution>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveInstitution<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        close = issuer,
+        seeds = [FAST_LANE_INSTITUTION_SEED, mint_info.mint.as_ref(), institution.wallet.as_ref()],
+        bump,
+    )]
+    pub institution: Account<'info, FastLaneInstitution>,
+}
+
+// Admit a supervised institution to the fast lane, or change its daily cap (issuer only)
+pub(crate) fn whitelist_institution(
+    ctx: Context<WhitelistInstitution>,
+    wallet: Pubkey,
+    lei: [u8; 20],
+    daily_cap: u64,
+) -> Result<()> {
+    if daily_cap == 0 || !lei.iter().all(u8::is_ascii_alphanumeric) {
+        return Err(MicaEurError::InvalidInstitution.into());
+    }
+
+    let institution = &mut ctx.accounts.institution;
+    institution.mint = ctx.accounts.mint_info.mint;
+    institution.wallet = wallet;
+    institution.lei = lei;
+    institution.daily_cap = daily_cap;
+    institution.registered_by = ctx.accounts.issuer.key();
+    institution.last_update = Clock::get()?.unix_timestamp;
+
+    emit!(InstitutionWhitelisted {
+        mint: institution.mint,
+        wallet,
+        lei,
+        daily_cap,
+        timestamp: institution.last_update,
+    });
+    Ok(())
+}
+
+// Remove an institution from the fast lane and refund its record (issuer only)
+pub(crate) fn remove_institution(ctx: Context<RemoveInstitution>) -> Result<()> {
+    emit!(InstitutionRemoved {
+        mint: ctx.accounts.institution.mint,
+        wallet: ctx.accounts.institution.wallet,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::{interest_bearing_mint_update_rate, InterestBearingMintUpdateRate};
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Interest rate history of a mint
+#[account]
+#[derive(InitSpace)]
+pub struct RateHistory {
+    pub mint: Pubkey,                 // The EUR mint
+    pub current_rate: i16,            // Rate in force, in basis points per year
+    pub change_count: u64,            // Rate changes so far, the next change's ID
+    pub last_change_time: i64,        // When the rate last changed
+}
+
+/// One change of the interest rate
+#[account]
+#[derive(InitSpace)]
+pub struct RateChange {
+    pub mint: Pubkey,                 // The EUR mint
+    pub change_id: u64,               // Position in the mint's rate history
+    pub previous_rate: i16,           // Rate before the change, in basis points per year
+    pub new_rate: i16,                // Rate after the change, in basis points per year
+    pub supply: u64,                  // Mint supply when the rate changed
+    pub changed_by: Pubkey,           // Issuer that set it
+    pub change_time: i64,
+}
+
+/// Emitted when the issuer changes the interest rate of a mint
+#[event]
+pub struct InterestRateSet {
+    pub mint: Pubkey,
+    pub change_id: u64,
+    pub previous_rate: i16,
+    pub new_rate: i16,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct SetInterestRate<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [RATE_HISTORY_SEED, mint.key().as_ref()],
+        bump,
+        space = 8 + RateHistory::INIT_SPACE,
+    )]
+    pub rate_history: Account<'info, RateHistory>,
+
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            RATE_CHANGE_SEED,
+            mint.key().as_ref(),
+            &rate_history.change_count.to_le_bytes(),
+        ],
+        bump,
+        space = 8 + RateChange::INIT_SPACE,
+    )]
+    pub rate_change: Account<'info, RateChange>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Set the interest rate of the InterestBearingConfig extension (issuer only) and log
+// the change. The rate only changes the UI amount Token-2022 reports for balances;
+// remuneration itself is distributed off-chain and reported against this history.
+// The rate stays 0 unless remuneration is permitted for the token.
+pub(crate) fn set_interest_rate(ctx: Context<SetInterestRate>, rate: i16) -> Result<()> {
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    interest_bearing_mint_update_rate(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            InterestBearingMintUpdateRate {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                rate_authority: ctx.accounts.mint_info.to_account_info(),
+            },
+            signer,
+        ),
+        rate,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let rate_history = &mut ctx.accounts.rate_history;
+    let change_id = rate_history.change_count;
+    let previous_rate = rate_history.current_rate;
+    rate_history.mint = mint_key;
+    rate_history.current_rate = rate;
+    rate_history.change_count = change_id.checked_add(1).ok_or(MicaEurError::ArithmeticOverflow)?;
+    rate_history.last_change_time = now;
+
+    let rate_change = &mut ctx.accounts.rate_change;
+    rate_change.mint = mint_key;
+    rate_change.change_id = change_id;
+    rate_change.previous_rate = previous_rate;
+    rate_change.new_rate = rate;
+    rate_change.supply = ctx.accounts.mint.supply;
+    rate_change.changed_by = ctx.accounts.issuer.key();
+    rate_change.change_time = now;
+
+    emit!(InterestRateSet {
+        mint: mint_key,
+        change_id,
+        previous_rate,
+        new_rate: rate,
+        timestamp: now,
+    });
+
+    msg!("Interest rate of {} set to {} bps", mint_key, rate);
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::timelock::check_no_timelock;
+use crate::MintInfo;
+
+/// Issuance schedule of a mint: at most `monthly_limit` may be minted per
+/// ISSUANCE_PERIOD. Once configured, every mint counts against it.
+#[account]
+#[derive(InitSpace)]
+pub struct IssuancePolicy {
+    pub mint: Pubkey,                 // The EUR mint
+    pub monthly_limit: u64,           // Most mintable per period, in token units
+    pub period_start: i64,            // Start of the current period
+    pub issued_in_period: u64,        // Minted in the current period
+    pub set_by: Pubkey,               // Issuer that last set the schedule
+    pub last_update: i64,             // When the schedule was last set or used
+}
+
+/// Emitted when a supply cap takes effect
+#[event]
+pub struct SupplyCapSet {
+    pub mint: Pubkey,
+    pub previous_max_supply: u64,
+    pub max_supply: u64,              // 0 for no cap
+    pub timestamp: i64,
+}
+
+/// Emitted when a raised or lifted supply cap is queued behind its delay
+#[event]
+pub struct SupplyCapQueued {
+    pub mint: Pubkey,
+    pub max_supply: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer sets or clears the issuance schedule
+#[event]
+pub struct IssuanceScheduleSet {
+    pub mint: Pubkey,
+    pub monthly_limit: u64,           // 0 once cleared
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct SetSupplyCap<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+}
+
+#[derive(Accounts)]
+pub struct ApplySupplyCap<'info> {
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+}
+
+#[derive(Accounts)]
+pub struct SetIssuanceSchedule<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [ISSUANCE_POLICY_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + IssuancePolicy::INIT_SPACE,
+    )]
+    pub issuance_policy: Account<'info, IssuancePolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Set the supply cap, 0 for none (issuer only). Lowering a cap takes effect at
+// once; raising or lifting one is queued for SUPPLY_CAP_RAISE_DELAY, giving holders
+// notice, and applied with `apply_supply_cap`. A new call replaces a queued change.
+// Mints with a timelock queue raises on it instead.
+pub(crate) fn set_supply_cap(ctx: Context<SetSupplyCap>, max_supply: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mint_info = &mut ctx.accounts.mint_info;
+    let tightens = max_supply != 0 && (mint_info.max_supply == 0 || max_supply <= mint_info.max_supply);
+    if tightens {
+        apply_max_supply(mint_info, max_supply, now);
+        return Ok(());
+    }
+    check_no_timelock(mint_info)?;
+
+    mint_info.pending_max_supply = max_supply;
+    mint_info.supply_cap_effective_at = now
+        .checked_add(SUPPLY_CAP_RAISE_DELAY)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    emit!(SupplyCapQueued {
+        mint: mint_info.mint,
+        max_supply,
+        effective_at: mint_info.supply_cap_effective_at,
+        timestamp: now,
+    });
+
+    msg!("Supply cap of {} queued until {}", max_supply, mint_info.supply_cap_effective_at);
+    Ok(())
+}
+
+// Apply a queued supply cap once its delay has passed (anyone)
+pub(crate) fn apply_supply_cap(ctx: Context<ApplySupplyCap>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mint_info = &mut ctx.accounts.mint_info;
+    if mint_info.supply_cap_effective_at == 0 || now < mint_info.supply_cap_effective_at {
+        return Err(MicaEurError::SupplyCapChangeNotDue.into());
+    }
+    let max_supply = mint_info.pending_max_supply;
+    apply_max_supply(mint_info, max_supply, now);
+    Ok(())
+}
+
+// Set the amount mintable per ISSUANCE_PERIOD (issuer only), 0 to stop counting
+// mints against a schedule. A new schedule starts a new period.
+pub(crate) fn set_issuance_schedule(ctx: Context<SetIssuanceSchedule>, monthly_limit: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let issuance_policy = &mut ctx.accounts.issuance_policy;
+    issuance_policy.mint = ctx.accounts.mint_info.mint;
+    issuance_policy.monthly_limit = monthly_limit;
+    issuance_policy.period_start = now;
+    issuance_policy.issued_in_period = 0;
+    issuance_policy.set_by = ctx.accounts.issuer.key();
+    issuance_policy.last_update = now;
+
+    ctx.accounts.mint_info.issuance_policy = if monthly_limit == 0 {
+        Pubkey::default()
+    } else {
+        issuance_policy.key()
+    };
+
+    emit!(IssuanceScheduleSet {
+        mint: issuance_policy.mint,
+        monthly_limit,
+        timestamp: now,
+    });
+
+    msg!("Issuance schedule set to {} per period", monthly_limit);
+    Ok(())
+}
+
+/// Put a supply cap into effect, replacing any queued one
+pub fn apply_max_supply(mint_info: &mut MintInfo, max_supply: u64, now: i64) {
+    let previous_max_supply = mint_info.max_supply;
+    mint_info.max_supply = max_supply;
+    mint_info.pending_max_supply = 0;
+    mint_info.supply_cap_effective_at = 0;
+    emit!(SupplyCapSet {
+        mint: mint_info.mint,
+        previous_max_supply,
+        max_supply,
+        timestamp: now,
+    });
+    msg!("Supply cap set to {}", max_supply);
+}
+
+/// Reject a mint of `amount` that would take `supply` above the mint's cap
+pub fn check_supply_cap(mint_info: &MintInfo, supply: u64, amount: u64) -> Result<()> {
+    if mint_info.max_supply == 0 {
+        return Ok(());
+    }
+    let supply = supply.checked_add(amount).ok_or(MicaEurError::ArithmeticOverflow)?;
+    if supply > mint_info.max_supply {
+        return Err(MicaEurError::SupplyCapExceeded.into());
+    }
+    Ok(())
+}
+
+/// Count a mint of `amount` against the issuance schedule, once one is configured,
+/// rolling over to a new period when the current one has ended
+pub fn record_scheduled_issuance(
+    mint_info: &MintInfo,
+    issuance_policy: Option<&mut Account<IssuancePolicy>>,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if mint_info.issuance_policy == Pubkey::default() {
+        return Ok(());
+    }
+    let issuance_policy = issuance_policy.ok_or(MicaEurError::IssuancePolicyRequired)?;
+
+    let elapsed = now.saturating_sub(issuance_policy.period_start);
+    if elapsed >= ISSUANCE_PERIOD {
+        issuance_policy.period_start = now - elapsed % ISSUANCE_PERIOD;
+        issuance_policy.issued_in_period = 0;
+    }
+    let issued = issuance_policy
+        .issued_in_period
+        .checked_add(amount)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    if issued > issuance_policy.monthly_limit {
+        return Err(MicaEurError::IssuanceScheduleExceeded.into());
+    }
+    issuance_policy.issued_in_period = issued;
+    issuance_policy.last_update = now;
+    Ok(())
+}
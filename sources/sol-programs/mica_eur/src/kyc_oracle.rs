@@ -1,9 +1,23 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::StateWithExtensions,
+    state::Account as TokenAccountState,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::access_control::{roles, RoleAssignment};
+use crate::compliance_history::{record_history, ComplianceAction, ComplianceHistory};
 use crate::error::MicaEurError;
 use crate::constants::*;
+use crate::country_policy::{check_country_allowed, check_country_code, CountryPolicy};
+use crate::iban_registry::{link_iban, IbanRegistry};
+use crate::policy::{check_class_requirements, AccountClass};
+use crate::mint_utils::check_string_len;
+use crate::MintInfo;
 
 /// KYC status enum
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 pub enum KycStatus {
     Unverified,
     Pending,
@@ -13,23 +27,53 @@ pub enum KycStatus {
     Suspended,
 }
 
+impl KycStatus {
+    /// Whether `update_kyc_status` may move a user from this status to `next`.
+    /// Verified users can be re-verified to renew or change their level; rejected
+    /// users have to be closed and registered again.
+    pub fn can_transition_to(self, next: KycStatus) -> bool {
+        use KycStatus::*;
+        matches!(
+            (self, next),
+            (Unverified, Pending)
+                | (Unverified | Pending, Verified | Rejected)
+                | (Verified, Verified | Expired | Suspended)
+                | (Expired, Pending | Verified)
+                | (Suspended, Verified | Rejected)
+        )
+    }
+}
+
 /// KYC user information
 #[account]
+#[derive(InitSpace)]
 pub struct KycUser {
-    pub authority: Pubkey,           // Authority that can update the KYC status
+    pub authority: Pubkey,           // Oracle authority or provider key that registered the user
     pub user: Pubkey,                // User wallet address
     pub status: KycStatus,           // Current KYC status
     pub verification_level: u8,      // Level of verification (0-3)
     pub verification_time: i64,      // When the verification was last updated
     pub expiry_time: i64,            // When the verification expires
+    #[max_len(MAX_COUNTRY_CODE_LEN)]
     pub country_code: String,        // ISO country code
+    #[max_len(MAX_BLZ_LEN)]
     pub blz: String,                 // Bank code (Bankleitzahl)
     pub iban_hash: [u8; 32],         // SHA-256 hash of the IBAN
+    #[max_len(MAX_PROVIDER_NAME_LEN)]
     pub verification_provider: String, // Which provider verified the KYC
+    pub account_class: AccountClass, // Policy class (Retail, Merchant, ...)
+    pub kyb_hash: [u8; 32],          // Hash of the linked KYB record, zero if none
+    pub class_change_time: i64,      // When the account class last changed
+    pub sanctions_screen_date: i64,  // When the user was last sanctions-screened, 0 if never
+    pub sanctions_screen_hash: [u8; 32], // Hash of the last screening result
+    pub sanctions_screen_provider: Pubkey, // Provider key that attested the last screening
+    pub edd_required: bool,          // Transfers need enhanced due diligence approval
+    pub provider: Pubkey,            // KYC provider that registered the user, default if the oracle authority did
 }
 
 /// KYC Oracle state
 #[account]
+#[derive(InitSpace)]
 pub struct KycOracleState {
     pub authority: Pubkey,           // Authority that can update the KYC oracle
     pub user_count: u64,             // Count of registered users
@@ -37,6 +81,86 @@ pub struct KycOracleState {
     pub last_update_time: i64,       // When the oracle was last updated
 }
 
+/// Verification provider (IDnow, Sumsub, a partner bank, ...) allowed to register
+/// users and update the users it registered with its own key
+#[account]
+#[derive(InitSpace)]
+pub struct KycProvider {
+    pub provider: Pubkey,             // Key the provider signs with
+    pub authority: Pubkey,            // KYC oracle authority that added the provider
+    #[max_len(MAX_PROVIDER_NAME_LEN)]
+    pub name: String,                 // Provider name
+    pub user_count: u64,              // Users registered by the provider
+    pub creation_time: i64,           // When the provider was added
+}
+
+/// Emitted when the KYC oracle is set up
+#[event]
+pub struct KycOracleInitialized {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a user registers for KYC verification
+#[event]
+pub struct KycUserRegistered {
+    pub user: Pubkey,
+    pub country_code: String,
+    pub verification_provider: String,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the KYC oracle changes a user's verification
+#[event]
+pub struct KycStatusUpdated {
+    pub user: Pubkey,
+    pub old_status: KycStatus,
+    pub status: KycStatus,
+    pub verification_level: u8,
+    pub expiry_time: i64,             // Zero unless verified
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a rejected or expired KYC user is closed
+#[event]
+pub struct KycUserClosed {
+    pub user: Pubkey,
+    pub status: KycStatus,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the oracle authority adds a KYC provider
+#[event]
+pub struct KycProviderAdded {
+    pub provider: Pubkey,
+    pub name: String,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the oracle authority removes a KYC provider
+#[event]
+pub struct KycProviderRemoved {
+    pub provider: Pubkey,
+    pub user_count: u64,              // Users the provider had registered
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a user moves to a different account class
+#[event]
+pub struct AccountReclassified {
+    pub user: Pubkey,
+    pub old_class: AccountClass,
+    pub new_class: AccountClass,
+    pub kyb_hash: [u8; 32],
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
 #[derive(Accounts)]
 pub struct InitializeKycOracle<'info> {
     #[account(mut)]
@@ -47,7 +171,7 @@ pub struct InitializeKycOracle<'info> {
         payer = authority,
         seeds = [KYC_ORACLE_STATE_SEED],
         bump,
-        space = 8 + std::mem::size_of::<KycOracleState>(),
+        space = 8 + KycOracleState::INIT_SPACE,
     )]
     pub kyc_oracle_state: Account<'info, KycOracleState>,
     
@@ -56,14 +180,15 @@ pub struct InitializeKycOracle<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(blz: String, iban_hash: [u8; 32], country_code: String)]
 pub struct RegisterKycUser<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
     #[account(
+        mut,
         seeds = [KYC_ORACLE_STATE_SEED],
         bump,
-        constraint = kyc_oracle_state.authority == authority.key(),
     )]
     pub kyc_oracle_state: Account<'info, KycOracleState>,
     
@@ -76,12 +201,44 @@ pub struct RegisterKycUser<'info> {
         payer = authority,
         seeds = [KYC_USER_SEED, user.key().as_ref()],
         bump,
-        space = 8 + std::mem::size_of::<KycUser>() + 100, // extra space for strings
+        space = 8 + KycUser::INIT_SPACE,
     )]
     pub kyc_user: Account<'info, KycUser>,
     
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+    
+    /// The signer's provider record, required unless the oracle authority or a KYC admin signs
+    #[account(
+        mut,
+        seeds = [KYC_PROVIDER_SEED, authority.key().as_ref()],
+        bump,
+    )]
+    pub kyc_provider: Option<Account<'info, KycProvider>>,
+    
+    /// The signer's role assignment, for holders of the KYC_ADMIN role
+    #[account(
+        seeds = [ROLE_SEED, kyc_oracle_state.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+    
+    /// Policy of the user's country, which must allow registrations
+    #[account(
+        seeds = [COUNTRY_POLICY_SEED, country_code.to_ascii_uppercase().as_bytes()],
+        bump,
+    )]
+    pub country_policy: Account<'info, CountryPolicy>,
+    
+    /// Registry record of the IBAN, created on its first registration
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [IBAN_REGISTRY_SEED, iban_hash.as_ref()],
+        bump,
+        space = 8 + IbanRegistry::INIT_SPACE,
+    )]
+    pub iban_registry: Account<'info, IbanRegistry>,
 }
 
 #[derive(Accounts)]
@@ -93,7 +250,6 @@ pub struct UpdateKycStatus<'info> {
         mut,
         seeds = [KYC_ORACLE_STATE_SEED],
         bump,
-        constraint = kyc_oracle_state.authority == authority.key(),
     )]
     pub kyc_oracle_state: Account<'info, KycOracleState>,
     
@@ -101,41 +257,254 @@ pub struct UpdateKycStatus<'info> {
         mut,
         seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
         bump,
-        constraint = kyc_user.authority == authority.key(),
     )]
     pub kyc_user: Account<'info, KycUser>,
+    
+    /// The signer's provider record, required unless the oracle authority or a KYC admin signs
+    #[account(
+        seeds = [KYC_PROVIDER_SEED, authority.key().as_ref()],
+        bump,
+    )]
+    pub kyc_provider: Option<Account<'info, KycProvider>>,
+    
+    /// The signer's role assignment, for holders of the KYC_ADMIN role
+    #[account(
+        seeds = [ROLE_SEED, kyc_oracle_state.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// The user's compliance history, recorded into once opened
+    #[account(
+        mut,
+        seeds = [COMPLIANCE_HISTORY_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub compliance_history: Option<Account<'info, ComplianceHistory>>,
+}
+
+#[derive(Accounts)]
+pub struct ReclassifyAccount<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+    
+    #[account(
+        mut,
+        seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+    
+    /// The user's Token-2022 account on the mint of `mint_info`, required when the
+    /// new class needs incoming memos
+    #[account(
+        owner = spl_token_2022::ID,
+        constraint = token_account.owner == kyc_user.user @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = mint_info.as_ref().is_some_and(|mint_info| token_account.mint == mint_info.mint)
+            @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint info of the token account's mint, required with `token_account`
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub mint_info: Option<Account<'info, MintInfo>>,
+    
+    /// The signer's provider record, required unless the oracle authority or a KYC admin signs
+    #[account(
+        seeds = [KYC_PROVIDER_SEED, authority.key().as_ref()],
+        bump,
+    )]
+    pub kyc_provider: Option<Account<'info, KycProvider>>,
+    
+    /// The signer's role assignment, for holders of the KYC_ADMIN role
+    #[account(
+        seeds = [ROLE_SEED, kyc_oracle_state.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseKycUser<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+    
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// The user's associated token account for the mint, which may not exist
+    /// CHECK: Address derived from the user and mint; parsed in the handler
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &kyc_user.user,
+            &mint.key(),
+            &spl_token_2022::ID,
+        )
+    )]
+    pub token_account: UncheckedAccount<'info>,
+    
+    /// The signer's provider record, required unless the oracle authority or a KYC admin signs
+    #[account(
+        mut,
+        seeds = [KYC_PROVIDER_SEED, authority.key().as_ref()],
+        bump,
+    )]
+    pub kyc_provider: Option<Account<'info, KycProvider>>,
+    
+    /// The signer's role assignment, for holders of the KYC_ADMIN role
+    #[account(
+        seeds = [ROLE_SEED, kyc_oracle_state.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+#[instruction(provider: Pubkey)]
+pub struct AddKycProvider<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+        has_one = authority,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [KYC_PROVIDER_SEED, provider.as_ref()],
+        bump,
+        space = 8 + KycProvider::INIT_SPACE,
+    )]
+    pub kyc_provider: Account<'info, KycProvider>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveKycProvider<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+        has_one = authority,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [KYC_PROVIDER_SEED, kyc_provider.provider.as_ref()],
+        bump,
+    )]
+    pub kyc_provider: Account<'info, KycProvider>,
+}
+
+/// Check that `authority` may manage KYC users: the oracle authority and holders of
+/// the KYC_ADMIN role manage every user, a provider (signing with its `KycProvider`
+/// record) only those it registered. `kyc_user` is `None` for a registration.
+pub fn check_kyc_authority(
+    kyc_oracle_state: &KycOracleState,
+    authority: &Pubkey,
+    kyc_provider: Option<&KycProvider>,
+    kyc_user: Option<&KycUser>,
+    role_assignment: Option<&RoleAssignment>,
+) -> Result<()> {
+    if *authority == kyc_oracle_state.authority {
+        return Ok(());
+    }
+    if role_assignment.is_some_and(|assignment| {
+        assignment.holder == *authority && assignment.has_role(roles::KYC_ADMIN)
+    }) {
+        return Ok(());
+    }
+    if kyc_provider.is_none() {
+        return Err(MicaEurError::NotKycAuthority.into());
+    }
+    if kyc_user.is_some_and(|kyc_user| kyc_user.provider != *authority) {
+        return Err(MicaEurError::KycProviderMismatch.into());
+    }
+    Ok(())
 }
 
 // Initialize KYC Oracle
-pub fn initialize_kyc_oracle(ctx: Context<InitializeKycOracle>) -> Result<()> {
+pub(crate) fn initialize_kyc_oracle(ctx: Context<InitializeKycOracle>) -> Result<()> {
     let kyc_oracle_state = &mut ctx.accounts.kyc_oracle_state;
     kyc_oracle_state.authority = ctx.accounts.authority.key();
     kyc_oracle_state.user_count = 0;
     kyc_oracle_state.verified_user_count = 0;
     kyc_oracle_state.last_update_time = Clock::get()?.unix_timestamp;
     
-    msg!("KYC Oracle initialized");
-    msg!("Authority: {}", kyc_oracle_state.authority);
+    emit!(KycOracleInitialized {
+        authority: kyc_oracle_state.authority,
+        timestamp: kyc_oracle_state.last_update_time,
+    });
     Ok(())
 }
 
 // Register a user for KYC
-pub fn register_kyc_user(
+pub(crate) fn register_kyc_user(
     ctx: Context<RegisterKycUser>,
     blz: String,
     iban_hash: [u8; 32],
     country_code: String,
     verification_provider: String,
 ) -> Result<()> {
-    // Validate country code
-    if country_code.len() != 2 {
-        return Err(MicaEurError::InvalidCountryCode.into());
-    }
+    // The country must be allowed by its policy
+    let country_code = country_code.to_ascii_uppercase();
+    check_country_code(&country_code)?;
+    check_country_allowed(&ctx.accounts.country_policy, &country_code)?;
+    check_blz(&blz)?;
+    check_verification_provider(&verification_provider)?;
 
-    // Check if country is supported
-    if !is_country_supported(&country_code) {
-        return Err(MicaEurError::UnsupportedCountry.into());
-    }
+    check_kyc_authority(
+        &ctx.accounts.kyc_oracle_state,
+        ctx.accounts.authority.key,
+        ctx.accounts.kyc_provider.as_deref(),
+        None,
+        ctx.accounts.role_assignment.as_deref(),
+    )?;
+
+    // The bank account may back only one wallet
+    link_iban(
+        &mut ctx.accounts.iban_registry,
+        iban_hash,
+        ctx.accounts.user.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
 
     // Initialize the KYC user
     let kyc_user = &mut ctx.accounts.kyc_user;
@@ -151,18 +520,43 @@ pub fn register_kyc_user(
     kyc_user.blz = blz;
     kyc_user.iban_hash = iban_hash;
     kyc_user.verification_provider = verification_provider;
+    kyc_user.account_class = AccountClass::Retail;
+    kyc_user.kyb_hash = [0u8; 32];
+    kyc_user.class_change_time = kyc_user.verification_time;
+    kyc_user.sanctions_screen_date = 0;
+    kyc_user.sanctions_screen_hash = [0u8; 32];
+    kyc_user.sanctions_screen_provider = Pubkey::default();
+    kyc_user.edd_required = false;
+    kyc_user.provider = Pubkey::default();
+
+    // Attribute the user to the registering provider
+    if let Some(kyc_provider) = ctx.accounts.kyc_provider.as_mut() {
+        kyc_user.provider = kyc_provider.provider;
+        kyc_provider.user_count = kyc_provider
+            .user_count
+            .checked_add(1)
+            .ok_or(MicaEurError::ArithmeticOverflow)?;
+    }
     
     // Update oracle state
-    kyc_oracle_state.user_count += 1;
+    kyc_oracle_state.user_count = kyc_oracle_state
+        .user_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
     kyc_oracle_state.last_update_time = Clock::get()?.unix_timestamp;
     
-    msg!("User registered for KYC verification: {}", kyc_user.user);
-    msg!("Country code: {}", kyc_user.country_code);
+    emit!(KycUserRegistered {
+        user: kyc_user.user,
+        country_code: kyc_user.country_code.clone(),
+        verification_provider: kyc_user.verification_provider.clone(),
+        authority: kyc_user.authority,
+        timestamp: kyc_oracle_state.last_update_time,
+    });
     Ok(())
 }
 
 // Update KYC status for a user
-pub fn update_kyc_status(
+pub(crate) fn update_kyc_status(
     ctx: Context<UpdateKycStatus>,
     status: KycStatus,
     verification_level: u8,
@@ -177,24 +571,42 @@ pub fn update_kyc_status(
     if status == KycStatus::Verified && expiry_days <= 0 {
         return Err(MicaEurError::InvalidExpiryDate.into());
     }
+
+    check_kyc_authority(
+        &ctx.accounts.kyc_oracle_state,
+        ctx.accounts.authority.key,
+        ctx.accounts.kyc_provider.as_deref(),
+        Some(&ctx.accounts.kyc_user),
+        ctx.accounts.role_assignment.as_deref(),
+    )?;
     
     let kyc_user = &mut ctx.accounts.kyc_user;
     let kyc_oracle_state = &mut ctx.accounts.kyc_oracle_state;
     let clock = Clock::get()?;
     
     // Update the user status
-    let was_verified = kyc_user.status == KycStatus::Verified;
+    let old_status = kyc_user.status;
+    if !old_status.can_transition_to(status) {
+        return Err(MicaEurError::InvalidKycStatusTransition.into());
+    }
+    let was_verified = old_status == KycStatus::Verified;
     kyc_user.status = status;
     kyc_user.verification_level = verification_level;
     kyc_user.verification_time = clock.unix_timestamp;
     
     // Calculate expiry time if status is Verified
     if status == KycStatus::Verified {
-        kyc_user.expiry_time = clock.unix_timestamp + (expiry_days * 86400);
+        kyc_user.expiry_time = expiry_days
+            .checked_mul(86_400)
+            .and_then(|seconds| clock.unix_timestamp.checked_add(seconds))
+            .ok_or(MicaEurError::ArithmeticOverflow)?;
         
         // If this is a new verification, increment the verified count
         if !was_verified {
-            kyc_oracle_state.verified_user_count += 1;
+            kyc_oracle_state.verified_user_count = kyc_oracle_state
+                .verified_user_count
+                .checked_add(1)
+                .ok_or(MicaEurError::ArithmeticOverflow)?;
         }
     } else if was_verified {
         // If user was verified but is no longer, decrement the count
@@ -203,29 +615,185 @@ pub fn update_kyc_status(
     
     // Update oracle state
     kyc_oracle_state.last_update_time = clock.unix_timestamp;
+    record_history(
+        ctx.accounts.compliance_history.as_deref_mut(),
+        ComplianceAction::KycStatusChanged { from: old_status, to: status },
+        ctx.accounts.authority.key(),
+        [0; 32],
+        clock.unix_timestamp,
+    );
     
-    msg!("Updated KYC status for user: {}", kyc_user.user);
-    msg!("New status: {:?}", kyc_user.status);
-    msg!("Verification level: {}", kyc_user.verification_level);
+    emit!(KycStatusUpdated {
+        user: kyc_user.user,
+        old_status,
+        status,
+        verification_level,
+        expiry_time: if status == KycStatus::Verified { kyc_user.expiry_time } else { 0 },
+        authority: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
     
-    if status == KycStatus::Verified {
-        msg!("Expires on: {}", kyc_user.expiry_time);
+    Ok(())
+}
+
+/// Check that a bank code is a German Bankleitzahl: eight ASCII digits
+pub fn check_blz(blz: &str) -> Result<()> {
+    if blz.len() != MAX_BLZ_LEN || !blz.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(MicaEurError::InvalidBlz.into());
+    }
+    Ok(())
+}
+
+/// Check that a verification provider name is non-empty, fits its account space
+/// and only uses ASCII letters, digits, spaces, '-', '_' and '.'
+pub fn check_verification_provider(name: &str) -> Result<()> {
+    let allowed = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b' ' | b'-' | b'_' | b'.');
+    if name.is_empty() || name.len() > MAX_PROVIDER_NAME_LEN || !name.bytes().all(allowed) {
+        return Err(MicaEurError::InvalidVerificationProvider.into());
     }
-    
     Ok(())
 }
 
 // Helper function to check if a user is KYC verified
 pub fn is_kyc_verified(kyc_user: &KycUser) -> bool {
+    is_kyc_verified_at(kyc_user, Clock::get().unwrap().unix_timestamp)
+}
+
+/// Whether a user is KYC verified at `now`, for callers without the Clock sysvar
+pub fn is_kyc_verified_at(kyc_user: &KycUser, now: i64) -> bool {
     if kyc_user.status != KycStatus::Verified {
         return false;
     }
     
     // Check if verification has expired
-    let current_time = Clock::get().unwrap().unix_timestamp;
-    if kyc_user.expiry_time > 0 && current_time > kyc_user.expiry_time {
+    if kyc_user.expiry_time > 0 && now > kyc_user.expiry_time {
         return false;
     }
     
     true
-} 
\ No newline at end of file
+}
+
+// Move a user to another account class after re-running that class's policy checks
+pub(crate) fn reclassify_account(
+    ctx: Context<ReclassifyAccount>,
+    new_class: AccountClass,
+    kyb_hash: [u8; 32],
+) -> Result<()> {
+    check_kyc_authority(
+        &ctx.accounts.kyc_oracle_state,
+        ctx.accounts.authority.key,
+        ctx.accounts.kyc_provider.as_deref(),
+        Some(&ctx.accounts.kyc_user),
+        ctx.accounts.role_assignment.as_deref(),
+    )?;
+
+    let kyc_user = &mut ctx.accounts.kyc_user;
+    let old_class = kyc_user.account_class;
+    
+    if old_class == new_class {
+        return Err(MicaEurError::AccountClassUnchanged.into());
+    }
+    
+    check_class_requirements(
+        kyc_user,
+        new_class,
+        &kyb_hash,
+        ctx.accounts.token_account.as_ref().map(|a| a.as_ref()),
+    )?;
+    
+    let clock = Clock::get()?;
+    kyc_user.account_class = new_class;
+    kyc_user.kyb_hash = kyb_hash;
+    kyc_user.class_change_time = clock.unix_timestamp;
+    
+    emit!(AccountReclassified {
+        user: kyc_user.user,
+        old_class,
+        new_class,
+        kyb_hash,
+        authority: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reclassified user {} from {:?} to {:?}", kyc_user.user, old_class, new_class);
+    Ok(())
+}
+
+// Close a rejected or expired KYC user without tokens and refund its rent to the
+// authority. Only the user's associated token account can be checked on-chain.
+pub(crate) fn close_kyc_user(ctx: Context<CloseKycUser>) -> Result<()> {
+    check_kyc_authority(
+        &ctx.accounts.kyc_oracle_state,
+        ctx.accounts.authority.key,
+        ctx.accounts.kyc_provider.as_deref(),
+        Some(&ctx.accounts.kyc_user),
+        ctx.accounts.role_assignment.as_deref(),
+    )?;
+
+    let kyc_user = &ctx.accounts.kyc_user;
+    if kyc_user.status != KycStatus::Rejected && kyc_user.status != KycStatus::Expired {
+        return Err(MicaEurError::KycUserNotClosable.into());
+    }
+
+    let token_account = &ctx.accounts.token_account;
+    if !token_account.data_is_empty() {
+        let data = token_account.try_borrow_data()?;
+        let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+        if account.base.amount > 0 {
+            return Err(MicaEurError::KycUserHasBalance.into());
+        }
+    }
+
+    let clock = Clock::get()?;
+    let kyc_oracle_state = &mut ctx.accounts.kyc_oracle_state;
+    kyc_oracle_state.user_count = kyc_oracle_state.user_count.saturating_sub(1);
+    kyc_oracle_state.last_update_time = clock.unix_timestamp;
+    if let Some(kyc_provider) = ctx.accounts.kyc_provider.as_mut() {
+        kyc_provider.user_count = kyc_provider.user_count.saturating_sub(1);
+    }
+
+    emit!(KycUserClosed {
+        user: kyc_user.user,
+        status: kyc_user.status,
+        authority: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Let a verification provider register users and update the users it registers
+// (KYC oracle authority only)
+pub(crate) fn add_kyc_provider(ctx: Context<AddKycProvider>, provider: Pubkey, name: String) -> Result<()> {
+    if provider == Pubkey::default() || provider == ctx.accounts.kyc_oracle_state.authority {
+        return Err(MicaEurError::InvalidKycProvider.into());
+    }
+    check_string_len(&name, MAX_PROVIDER_NAME_LEN)?;
+
+    let kyc_provider = &mut ctx.accounts.kyc_provider;
+    kyc_provider.provider = provider;
+    kyc_provider.authority = ctx.accounts.authority.key();
+    kyc_provider.name = name;
+    kyc_provider.user_count = 0;
+    kyc_provider.creation_time = Clock::get()?.unix_timestamp;
+
+    emit!(KycProviderAdded {
+        provider,
+        name: kyc_provider.name.clone(),
+        authority: kyc_provider.authority,
+        timestamp: kyc_provider.creation_time,
+    });
+    Ok(())
+}
+
+// Remove a verification provider and refund its record (KYC oracle authority only).
+// Users it registered keep their attribution and are managed by the oracle authority.
+pub(crate) fn remove_kyc_provider(ctx: Context<RemoveKycProvider>) -> Result<()> {
+    let kyc_provider = &ctx.accounts.kyc_provider;
+    emit!(KycProviderRemoved {
+        provider: kyc_provider.provider,
+        user_count: kyc_provider.user_count,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
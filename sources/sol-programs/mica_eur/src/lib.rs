@@ -1,34 +1,146 @@
+// Instruction handlers live in their subsystem modules under the same name as
+// the program entry points. They are `pub(crate)` and called module-qualified,
+// so the glob re-exports below do not export them next to the entry points.
+
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    token_2022::{self, spl_token_2022::{
-        extension::{
-            default_account_state::DefaultAccountState,
-            metadata_pointer::MetadataPointer,
-            transfer_hook::TransferHook,
-            permanent_delegate::PermanentDelegate,
-            StateWithExtensions,
-        },
-        state::{AccountState, Mint},
-        ID as TOKEN_2022_ID,
-    }},
-    token_interface::Token2022,
-};
-use std::str::FromStr;
+use anchor_spl::token_2022::{self, spl_token_2022::ID as TOKEN_2022_ID};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 mod kyc_oracle;
 mod constants;
 mod error;
 mod mint_utils;
 mod versions;
-mod merkle_info;
+mod codec;
+mod merkle;
 mod aml;
+mod standing_orders;
+mod policy;
+mod ed25519_utils;
+mod screening;
+mod operations;
+mod policy_staging;
+mod migration;
+mod edd;
+mod approvals;
+mod limits;
+mod guards;
+mod reserve_attestation;
+mod reserve_coverage;
+mod snapshots;
+mod institutions;
+mod reconciliation;
+mod travel_rule;
+mod seizure_vault;
+mod freezes;
+mod transfer_fees;
+mod confidential;
+mod interest;
+mod access_control;
+mod allowances;
+mod reserve_inclusion;
+mod batch_mint;
+mod batch_freeze;
+mod blacklist_enforcement;
+mod issuance;
+mod timelock;
+mod whitepaper;
+mod metadata;
+mod compliance_snapshots;
+mod country_policy;
+mod iban_registry;
+mod wallet_migration;
+mod recovery;
+mod counterparties;
+mod aml_alerts;
+mod sar;
+mod views;
+mod holders;
+mod compliance_history;
+mod wind_down;
+mod treasury;
+mod supply_accounting;
+mod mint_authority;
+mod mint_vouchers;
+mod payout_references;
+mod reserve_composition;
+mod reserve_epochs;
+mod reserve_valuation;
+mod regulator_access;
+mod program_allowlist;
+mod transfer_checks;
+mod permits;
+mod relayers;
+mod escrows;
+mod payment_requests;
 
 pub use kyc_oracle::*;
 pub use constants::*;
 pub use error::*;
 pub use mint_utils::*;
 pub use versions::*;
-pub use merkle_info::*;
+pub use codec::*;
+pub use merkle::*;
+pub use aml::*;
+pub use standing_orders::*;
+pub use policy::*;
+pub use ed25519_utils::*;
+pub use screening::*;
+pub use operations::*;
+pub use policy_staging::*;
+pub use migration::*;
+pub use edd::*;
+pub use approvals::*;
+pub use limits::*;
+pub use guards::*;
+pub use reserve_attestation::*;
+pub use reserve_coverage::*;
+pub use snapshots::*;
+pub use institutions::*;
+pub use reconciliation::*;
+pub use travel_rule::*;
+pub use seizure_vault::*;
+pub use freezes::*;
+pub use transfer_fees::*;
+pub use confidential::*;
+pub use interest::*;
+pub use access_control::*;
+pub use allowances::*;
+pub use reserve_inclusion::*;
+pub use batch_mint::*;
+pub use batch_freeze::*;
+pub use blacklist_enforcement::*;
+pub use issuance::*;
+pub use timelock::*;
+pub use whitepaper::*;
+pub use metadata::*;
+pub use compliance_snapshots::*;
+pub use country_policy::*;
+pub use iban_registry::*;
+pub use wallet_migration::*;
+pub use recovery::*;
+pub use counterparties::*;
+pub use aml_alerts::*;
+pub use sar::*;
+pub use views::*;
+pub use holders::*;
+pub use compliance_history::*;
+pub use wind_down::*;
+pub use treasury::*;
+pub use supply_accounting::*;
+pub use mint_authority::*;
+pub use mint_vouchers::*;
+pub use payout_references::*;
+pub use reserve_composition::*;
+pub use reserve_epochs::*;
+pub use reserve_valuation::*;
+pub use regulator_access::*;
+pub use program_allowlist::*;
+pub use transfer_checks::*;
+pub use permits::*;
+pub use relayers::*;
+pub use escrows::*;
+pub use payment_requests::*;
 
 declare_id!("9x3tkUkajECAgPvS59YTAdD7VZRMRckrPxFC4MZspup5");
 
@@ -36,7 +148,7 @@ declare_id!("9x3tkUkajECAgPvS59YTAdD7VZRMRckrPxFC4MZspup5");
 pub mod mica_eur {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
         // Log version information
         versions::log_versions();
         
@@ -49,7 +161,7 @@ pub mod mica_eur {
         kyc_oracle::initialize_kyc_oracle(ctx)
     }
 
-    /// Register a new user for KYC verification
+    /// Register a new user for KYC verification (KYC oracle authority or provider)
     pub fn register_kyc_user(
         ctx: Context<RegisterKycUser>,
         blz: String,
@@ -60,7 +172,7 @@ pub mod mica_eur {
         kyc_oracle::register_kyc_user(ctx, blz, iban_hash, country_code, verification_provider)
     }
 
-    /// Update KYC status for a user
+    /// Update KYC status for a user (KYC oracle authority or the registering provider)
     pub fn update_kyc_status(
         ctx: Context<UpdateKycStatus>,
         status: KycStatus,
@@ -70,10 +182,86 @@ pub mod mica_eur {
         kyc_oracle::update_kyc_status(ctx, status, verification_level, expiry_days)
     }
 
+    /// Close a rejected or expired KYC user holding no tokens, refunding its rent
+    /// (KYC oracle authority or the registering provider)
+    pub fn close_kyc_user(ctx: Context<CloseKycUser>) -> Result<()> {
+        kyc_oracle::close_kyc_user(ctx)
+    }
+
+    /// Let a verification provider key register and update users (KYC oracle
+    /// authority only)
+    pub fn add_kyc_provider(
+        ctx: Context<AddKycProvider>,
+        provider: Pubkey,
+        name: String,
+    ) -> Result<()> {
+        kyc_oracle::add_kyc_provider(ctx, provider, name)
+    }
+
+    /// Remove a verification provider (KYC oracle authority only)
+    pub fn remove_kyc_provider(ctx: Context<RemoveKycProvider>) -> Result<()> {
+        kyc_oracle::remove_kyc_provider(ctx)
+    }
+
+    /// Move a user to another account class, re-checking the class policy
+    pub fn reclassify_account(
+        ctx: Context<ReclassifyAccount>,
+        new_class: AccountClass,
+        kyb_hash: [u8; 32],
+    ) -> Result<()> {
+        kyc_oracle::reclassify_account(ctx, new_class, kyb_hash)
+    }
+
+    /// Admit a supervised institution to the fast lane, or change its daily cap
+    /// (issuer only)
+    pub fn whitelist_institution(
+        ctx: Context<WhitelistInstitution>,
+        wallet: Pubkey,
+        lei: [u8; 20],
+        daily_cap: u64,
+    ) -> Result<()> {
+        institutions::whitelist_institution(ctx, wallet, lei, daily_cap)
+    }
+
+    /// Remove an institution from the fast lane (issuer only)
+    pub fn remove_institution(ctx: Context<RemoveInstitution>) -> Result<()> {
+        institutions::remove_institution(ctx)
+    }
+
+    /// Set the amount from which transfers must carry travel-rule data; 0 disables
+    /// the requirement (issuer only)
+    pub fn configure_travel_rule(ctx: Context<ConfigureTravelRule>, threshold: u64) -> Result<()> {
+        travel_rule::configure_travel_rule(ctx, threshold)
+    }
+
+    /// Approve a sanctions-screening provider signing key (KYC oracle authority only)
+    pub fn register_screening_provider(
+        ctx: Context<RegisterScreeningProvider>,
+        signing_key: Pubkey,
+        name: String,
+    ) -> Result<()> {
+        screening::register_screening_provider(ctx, signing_key, name)
+    }
+
+    /// Stop accepting attestations from a screening provider (KYC oracle authority only)
+    pub fn deactivate_screening_provider(ctx: Context<DeactivateScreeningProvider>) -> Result<()> {
+        screening::deactivate_screening_provider(ctx)
+    }
+
+    /// Record an Ed25519-signed sanctions screening attestation for a user
+    pub fn submit_screening_attestation(
+        ctx: Context<SubmitScreeningAttestation>,
+        screened_at: i64,
+        result_hash: [u8; 32],
+    ) -> Result<()> {
+        screening::submit_screening_attestation(ctx, screened_at, result_hash)
+    }
+
     /// Initialize the EUR stablecoin with Token-2022 extensions
     pub fn initialize_euro_mint(
         ctx: Context<InitializeEuroMint>,
         whitepaper_uri: String,
+        decimals: u8,
     ) -> Result<()> {
         // Log version information for this important operation
         versions::log_versions();
@@ -84,17 +272,21 @@ pub mod mica_eur {
             // We allow it to continue but log a warning
         }
     
-        let mint_key = ctx.accounts.mint.key();
-        let mint_key_ref = mint_key.as_ref();
-        let bump = ctx.bumps.mint_info;
-        let bump_ref = &[bump];
-        
-        let seeds = &[
-            MINT_INFO_SEED,
-            mint_key_ref,
-            bump_ref,
-        ];
-        let _signer = &[&seeds[..]];
+        check_string_len(&whitepaper_uri, MAX_WHITEPAPER_URI_LEN)?;
+
+        // Create the Token-2022 mint with its extensions
+        initialize_mint_with_extensions(
+            MintCreation {
+                payer: ctx.accounts.issuer.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &ctx.accounts.issuer.key(),
+            &ctx.accounts.mint_info.key(),
+            &ctx.accounts.seizure_authority.key(),
+            decimals,
+        )?;
 
         // Store mint info
         let mint_info = &mut ctx.accounts.mint_info;
@@ -106,79 +298,449 @@ pub mod mica_eur {
         mint_info.is_active = true;
         mint_info.creation_time = Clock::get()?.unix_timestamp;
         mint_info.last_reserve_update = Clock::get()?.unix_timestamp;
+        mint_info.rule_set_version = RULE_SET_VERSION;
+        mint_info.rule_set_hash = rule_set_hash();
+        mint_info.paused = false;
+        mint_info.pauser = ctx.accounts.issuer.key();
+        mint_info.approval_set = Pubkey::default();
+        mint_info.reserve_total_eur = 0;
+        mint_info.coverage_auto_pause = false;
+        mint_info.minting_halted = false;
+        mint_info.transfer_fee_bps = 0;
+        mint_info.max_transfer_fee = 0;
+        mint_info.fee_treasury = Pubkey::default();
+        mint_info.confidential_transfers = false;
+        mint_info.confidential_auditor = Pubkey::default();
+        mint_info.auditor_elgamal_pubkey = [0; 32];
+        mint_info.max_supply = 0;
+        mint_info.pending_max_supply = 0;
+        mint_info.supply_cap_effective_at = 0;
+        mint_info.issuance_policy = Pubkey::default();
+        mint_info.timelock = Pubkey::default();
+        mint_info.whitepaper_version = 0;
+        mint_info.decimals = decimals;
+        mint_info.require_associated_accounts = false;
+        mint_info.holder_count = 0;
+        mint_info.migrated_to = Pubkey::default();
+        mint_info.wind_down_started_at = 0;
+        mint_info.redemption_deadline = 0;
+        mint_info.redemption_account = Pubkey::default();
+        mint_info.total_minted = 0;
+        mint_info.total_burned = 0;
+        mint_info.max_reserve_age_seconds = 0;
+        mint_info.reserve_valuation = Pubkey::default();
+        mint_info.relayer_registry = Pubkey::default();
+        mint_info.reserve_epoch_count = 0;
+
+        let invocation_guard = &mut ctx.accounts.invocation_guard;
+        invocation_guard.mint = ctx.accounts.mint.key();
+        invocation_guard.entered = false;
+        invocation_guard.entered_slot = 0;
 
-        // Log the initialization
-        msg!("MiCA EUR Token mint initialized with extensions");
-        msg!("Mint: {}", ctx.accounts.mint.key());
-        msg!("Issuer: {}", ctx.accounts.issuer.key());
-        msg!("Freeze Authority: {}", ctx.accounts.freeze_authority.key());
-        msg!("Permanent Delegate: {}", ctx.accounts.permanent_delegate.key());
+        emit!(EuroMintInitialized {
+            mint: mint_info.mint,
+            issuer: mint_info.issuer,
+            freeze_authority: mint_info.freeze_authority,
+            permanent_delegate: mint_info.permanent_delegate,
+            rule_set_version: mint_info.rule_set_version,
+            decimals: mint_info.decimals,
+            timestamp: mint_info.creation_time,
+        });
 
         Ok(())
     }
 
-    /// Create a token account with DefaultAccountState = Frozen
+    /// Create the owner's associated token account, frozen by DefaultAccountState,
+    /// and record it as a holder of the mint
     pub fn create_token_account(ctx: Context<CreateTokenAccount>) -> Result<()> {
-        // Token account is created with DefaultAccountState extension
-        // and will be initialized as Frozen
-        msg!("Created token account with Frozen default state");
-        Ok(())
+        holders::create_token_account(ctx)
+    }
+
+    /// Thaw a token account still frozen by DefaultAccountState once its owner is
+    /// KYC verified for transfers (anyone, with the owner's KYC record as proof)
+    pub fn activate_token_account(ctx: Context<ActivateTokenAccount>) -> Result<()> {
+        holders::activate_token_account(ctx)
+    }
+
+    /// Freeze the token account of a user whose KYC verification has expired
+    /// (anyone)
+    pub fn enforce_kyc_expiry(ctx: Context<EnforceKycExpiry>) -> Result<()> {
+        holders::enforce_kyc_expiry(ctx)
+    }
+
+    /// Open the compliance history of a registered user, recording the status
+    /// changes, freezes and enforcement actions that concern them (anyone)
+    pub fn open_compliance_history(ctx: Context<OpenComplianceHistory>) -> Result<()> {
+        compliance_history::open_compliance_history(ctx)
+    }
+
+    /// Close an empty token account opened by `create_token_account` and its holder
+    /// record, refunding both to the owner
+    pub fn close_token_account(ctx: Context<CloseTokenAccount>) -> Result<()> {
+        holders::close_token_account(ctx)
     }
     
-    /// Mint tokens to an account (only the issuer can do this)
+    /// Mint tokens to an account (issuer, or minters within their allowance)
     pub fn mint_tokens(
         ctx: Context<MintTokens>,
         amount: u64,
+        operation_id: [u8; 16],
     ) -> Result<()> {
-        // Check if the user has been KYC verified
-        let kyc_user = &ctx.accounts.kyc_user;
-        
-        if !is_kyc_verified(kyc_user) {
-            return Err(MicaEurError::UserNotVerified.into());
-        }
+        // Check the user is not blacklisted, KYC verified at the minting level
+        // (and screened for high tiers)
+        let blacklist_entry = load_if_initialized::<BlacklistEntry>(&ctx.accounts.blacklist_entry)?;
+        check_mint_recipient(&ctx.accounts.kyc_user, blacklist_entry.as_ref())?;
+        // The user's country must allow mints of this size
+        check_country_allowed(&ctx.accounts.country_policy, &ctx.accounts.kyc_user.country_code)?;
+        check_country_transaction(&ctx.accounts.country_policy, &ctx.accounts.kyc_user, amount)?;
+        check_mint_extensions(&ctx.accounts.mint)?;
+        check_token_account_extensions(&ctx.accounts.token_account.to_account_info())?;
+        check_mint_destination(&ctx.accounts.mint_info, &ctx.accounts.kyc_user.user, &ctx.accounts.token_account.key())?;
+
+        // Large mints need the approval set's officers once one is configured
+        check_single_key_mint(
+            &ctx.accounts.mint_info,
+            ctx.accounts.approval_set.as_deref(),
+            ctx.accounts.approval_set.as_ref().map(|a| a.key()),
+            amount,
+        )?;
+
+        // The reserve proof must be recent and, with auto-pause on, cover the supply after
+        // the mint, which must also stay within the supply cap and issuance schedule
+        let now = Clock::get()?.unix_timestamp;
+        let supply = mint_supply(&ctx.accounts.mint.to_account_info())?;
+        check_reserve_freshness(&ctx.accounts.mint_info, now)?;
+        check_mint_coverage(&ctx.accounts.mint_info, supply, amount)?;
+        check_supply_cap(&ctx.accounts.mint_info, supply, amount)?;
+        record_scheduled_issuance(&ctx.accounts.mint_info, ctx.accounts.issuance_policy.as_mut(), amount, now)?;
+
+        // Funds cannot go into an account under a recorded freeze; below, only the
+        // default frozen state of a new account is lifted
+        let freeze_record = load_if_initialized::<FreezeRecord>(&ctx.accounts.freeze_record)?;
+        check_incoming_freeze(freeze_record.as_ref(), now)?;
+
+        // Daily mint volume of the recipient's verification tier
+        let limits = tier_limits(
+            ctx.accounts.limit_config.as_deref(),
+            ctx.accounts.kyc_user.verification_level,
+            ctx.accounts.mint_info.decimals,
+        );
+        record_mint_volume(&mut ctx.accounts.user_limits, ctx.accounts.kyc_user.user, limits, amount)?;
+
+        // Delegated minters mint against their allowance
+        consume_minter_allowance(
+            &ctx.accounts.mint_info,
+            ctx.accounts.issuer.key,
+            ctx.accounts.minter_allowance.as_mut(),
+            amount,
+        )?;
+
+        enter_guard(&mut ctx.accounts.invocation_guard)?;
 
-        // Check verification level (must be at least level 2 for minting)
-        if kyc_user.verification_level < 2 {
-            return Err(MicaEurError::InsufficientVerificationLevel.into());
+        // The mint info PDA holds the mint and freeze authority
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+
+        // New token accounts start frozen, and Token-2022 does not mint to frozen
+        // accounts: thaw it first
+        if ctx.accounts.token_account.is_frozen() {
+            let cpi_accounts = token_2022::ThawAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint_info.to_account_info(),
+            };
+            
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            
+            token_2022::thaw_account(cpi_ctx)?;
         }
 
         // Mint the tokens
         let cpi_accounts = token_2022::MintTo {
             mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.token_account.to_account_info(),
-            authority: ctx.accounts.issuer.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
         };
         
-        let cpi_ctx = CpiContext::new(
+        let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
+            signer,
         );
         
-        token_2022::mint_to(cpi_ctx, amount)?;
+        mint_to_checked(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+        record_minted(&mut ctx.accounts.mint_info, amount)?;
+        leave_guard(&mut ctx.accounts.invocation_guard);
 
-        // Thaw the account before returning
-        let cpi_accounts = token_2022::ThawAccount {
-            account: ctx.accounts.token_account.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            authority: ctx.accounts.freeze_authority.to_account_info(),
-        };
-        
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-        );
-        
-        token_2022::thaw_account(cpi_ctx)?;
+        record_operation(
+            &mut ctx.accounts.operation_receipt,
+            operation_id,
+            OperationDetails {
+                kind: OperationKind::Mint,
+                mint: ctx.accounts.mint.key(),
+                actor: ctx.accounts.issuer.key(),
+                source: Pubkey::default(),
+                destination: ctx.accounts.token_account.key(),
+                amount,
+            },
+        )?;
 
-        msg!("Minted {} tokens to {}", amount, ctx.accounts.token_account.key());
+        emit!(TokensMinted {
+            mint: ctx.accounts.mint.key(),
+            token_account: ctx.accounts.token_account.key(),
+            recipient: ctx.accounts.kyc_user.user,
+            amount,
+            issuer: ctx.accounts.issuer.key(),
+            operation_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
     
+    /// Mint to many verified recipients in one instruction (issuer, or minters within
+    /// their allowance). Recipients are passed as remaining accounts.
+    pub fn batch_mint_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchMintTokens<'info>>,
+        amounts: Vec<u64>,
+        operation_id: [u8; 16],
+    ) -> Result<()> {
+        batch_mint::batch_mint_tokens(ctx, amounts, operation_id)
+    }
+
+    /// Freeze many token accounts for one reason, e.g. a sanctions list update
+    /// (freeze authority or freezers). Token accounts are passed as remaining accounts.
+    pub fn batch_freeze_accounts<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchFreezeAccounts<'info>>,
+        reason_code: u8,
+        legal_reference: String,
+    ) -> Result<()> {
+        batch_freeze::batch_freeze_accounts(ctx, reason_code, legal_reference)
+    }
+
+    /// Thaw many token accounts, lifting their recorded freezes (freeze authority or
+    /// freezers). Token accounts are passed as remaining accounts.
+    pub fn batch_thaw_accounts<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchThawAccounts<'info>>,
+    ) -> Result<()> {
+        batch_freeze::batch_thaw_accounts(ctx)
+    }
+
+    /// Freeze a blacklisted user's token account on the strength of its active
+    /// blacklist entry (anyone)
+    pub fn enforce_blacklist(ctx: Context<EnforceBlacklist>) -> Result<()> {
+        blacklist_enforcement::enforce_blacklist(ctx)
+    }
+
+    /// Set the supply cap (issuer only). Raising or lifting it is queued behind a delay.
+    pub fn set_supply_cap(ctx: Context<SetSupplyCap>, max_supply: u64) -> Result<()> {
+        issuance::set_supply_cap(ctx, max_supply)
+    }
+
+    /// Apply a queued supply cap once its delay has passed (anyone)
+    pub fn apply_supply_cap(ctx: Context<ApplySupplyCap>) -> Result<()> {
+        issuance::apply_supply_cap(ctx)
+    }
+
+    /// Set the monthly issuance schedule (issuer only), 0 to clear it
+    pub fn set_issuance_schedule(ctx: Context<SetIssuanceSchedule>, monthly_limit: u64) -> Result<()> {
+        issuance::set_issuance_schedule(ctx, monthly_limit)
+    }
+
+    /// Put the mint's sensitive parameter changes under a timelock (issuer only)
+    pub fn configure_timelock(ctx: Context<ConfigureTimelock>, delay: i64) -> Result<()> {
+        timelock::configure_timelock(ctx, delay)
+    }
+
+    /// Queue a supply cap, fee, authority, whitepaper or delay change (issuer only)
+    pub fn queue_operation(ctx: Context<QueueOperation>, action: TimelockAction) -> Result<()> {
+        timelock::queue_operation(ctx, action)
+    }
+
+    /// Apply a queued change once the timelock's delay has passed (anyone)
+    pub fn execute_operation(ctx: Context<ExecuteOperation>) -> Result<()> {
+        timelock::execute_operation(ctx)
+    }
+
+    /// Withdraw a queued change (issuer only)
+    pub fn cancel_operation(ctx: Context<CancelOperation>) -> Result<()> {
+        timelock::cancel_operation(ctx)
+    }
+
+    /// Publish a new whitepaper revision (issuer only, through the timelock once one
+    /// is configured)
+    pub fn update_whitepaper_uri(
+        ctx: Context<UpdateWhitepaperUri>,
+        uri: String,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        whitepaper::update_whitepaper_uri(ctx, uri, content_hash)
+    }
+
+    /// Write the name, symbol and URI to the mint's token metadata (issuer only)
+    pub fn initialize_token_metadata(ctx: Context<WriteTokenMetadata>, name: String, uri: String) -> Result<()> {
+        metadata::initialize_token_metadata(ctx, name, uri)
+    }
+
+    /// Set a token metadata field, e.g. the issuer's LEI (issuer only)
+    pub fn update_metadata_field(
+        ctx: Context<WriteTokenMetadata>,
+        field: MetadataField,
+        value: String,
+    ) -> Result<()> {
+        metadata::update_metadata_field(ctx, field, value)
+    }
+
+    /// Publish the compliance figures of a reporting period for NCAs (issuer only)
+    pub fn publish_compliance_snapshot(
+        ctx: Context<PublishComplianceSnapshot>,
+        period: u32,
+        frozen_accounts: u64,
+        seizure_count: u64,
+    ) -> Result<()> {
+        compliance_snapshots::publish_compliance_snapshot(ctx, period, frozen_accounts, seizure_count)
+    }
+
+    /// Allow or deny a country and set its per-transaction limit (KYC oracle authority only)
+    pub fn set_country_policy(
+        ctx: Context<SetCountryPolicy>,
+        country_code: String,
+        allowed: bool,
+        max_transaction_amount: u64,
+    ) -> Result<()> {
+        country_policy::set_country_policy(ctx, country_code, allowed, max_transaction_amount)
+    }
+
+    /// Move an IBAN to a new wallet (KYC oracle authority only)
+    pub fn relink_iban(ctx: Context<RelinkIban>, new_user: Pubkey) -> Result<()> {
+        iban_registry::relink_iban(ctx, new_user)
+    }
+
+    /// Move a verified user to a new wallet, freezing the old wallet's token account
+    /// (new wallet and KYC authority)
+    pub fn migrate_kyc_user(ctx: Context<MigrateKycUser>) -> Result<()> {
+        wallet_migration::migrate_kyc_user(ctx)
+    }
+
+    /// File a request to recover a lost-key token account to a new verified wallet
+    /// (issuer only)
+    pub fn request_recovery(ctx: Context<RequestRecovery>, evidence_hash: [u8; 32]) -> Result<()> {
+        recovery::request_recovery(ctx, evidence_hash)
+    }
+
+    /// Approve a pending recovery (AML authority with the seizure power)
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        recovery::approve_recovery(ctx)
+    }
+
+    /// Move the funds of an approved, due recovery to the new wallet (issuer only)
+    pub fn recover_account<'info>(ctx: Context<'_, '_, '_, 'info, RecoverAccount<'info>>) -> Result<()> {
+        recovery::recover_account(ctx)
+    }
+
+    /// Withdraw a pending recovery (issuer only)
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        recovery::cancel_recovery(ctx)
+    }
+
+    /// Approve a regulated venue or merchant wallet, or change its rules (issuer only)
+    pub fn register_counterparty(
+        ctx: Context<RegisterCounterparty>,
+        wallet: Pubkey,
+        kind: CounterpartyKind,
+        lei: [u8; 20],
+        daily_limit_exempt: bool,
+        max_transfer_amount: u64,
+    ) -> Result<()> {
+        counterparties::register_counterparty(ctx, wallet, kind, lei, daily_limit_exempt, max_transfer_amount)
+    }
+
+    /// Withdraw a counterparty's approval (issuer only)
+    pub fn remove_counterparty(ctx: Context<RemoveCounterparty>) -> Result<()> {
+        counterparties::remove_counterparty(ctx)
+    }
+
+    /// Set the velocity thresholds AML alerts are raised at (issuer only)
+    pub fn configure_aml_alerts(
+        ctx: Context<ConfigureAmlAlerts>,
+        max_hourly_transfers: u8,
+        max_hourly_volume: u64,
+        structuring_threshold: u64,
+        structuring_margin: u64,
+        structuring_count: u8,
+    ) -> Result<()> {
+        aml_alerts::configure_aml_alerts(
+            ctx,
+            max_hourly_transfers,
+            max_hourly_volume,
+            structuring_threshold,
+            structuring_margin,
+            structuring_count,
+        )
+    }
+
+    /// Anchor the hash of a SAR filing against a user (AML authority with VIEW_TRANSACTIONS)
+    pub fn anchor_sar_reference(
+        ctx: Context<AnchorSarReference>,
+        sar_hash: [u8; 32],
+        filing_date: i64,
+    ) -> Result<()> {
+        sar::anchor_sar_reference(ctx, sar_hash, filing_date)
+    }
+
+    /// Return the compliance state of a wallet (read-only, for simulated calls)
+    pub fn get_user_compliance_status(ctx: Context<GetUserComplianceStatus>) -> Result<UserComplianceStatus> {
+        views::get_user_compliance_status(ctx)
+    }
+
+    /// Return the state of a mint (read-only, for simulated calls)
+    pub fn get_mint_status(ctx: Context<GetMintStatus>) -> Result<MintStatus> {
+        views::get_mint_status(ctx)
+    }
+
+    /// Return a mint's cumulative issuance and redemptions against its supply
+    /// (read-only, for simulated calls)
+    pub fn get_outstanding_supply(ctx: Context<GetOutstandingSupply>) -> Result<OutstandingSupply> {
+        views::get_outstanding_supply(ctx)
+    }
+
+    /// Return the reserve backing of a mint (read-only, for simulated calls)
+    pub fn get_reserve_status(ctx: Context<GetReserveStatus>) -> Result<ReserveStatus> {
+        views::get_reserve_status(ctx)
+    }
+
     /// Burn tokens (redeem EUR)
     pub fn burn_tokens(
         ctx: Context<BurnTokens>,
         amount: u64,
+        operation_id: [u8; 16],
     ) -> Result<()> {
+        let blacklist_entry = load_if_initialized::<BlacklistEntry>(&ctx.accounts.blacklist_entry)?;
+        check_redeemer(ctx.accounts.owner.key(), blacklist_entry.as_ref())?;
+        // Users of a denied country can still redeem, within its transaction limit
+        check_country_transaction(&ctx.accounts.country_policy, &ctx.accounts.kyc_user, amount)?;
+
+        // Daily burn volume of the owner's verification tier
+        let limits = tier_limits(
+            ctx.accounts.limit_config.as_deref(),
+            ctx.accounts.kyc_user.verification_level,
+            ctx.accounts.mint_info.decimals,
+        );
+        record_burn_volume(&mut ctx.accounts.user_limits, ctx.accounts.owner.key(), limits, amount)?;
+
+        // Frozen funds cannot be redeemed
+        let freeze_record = load_if_initialized::<FreezeRecord>(&ctx.accounts.freeze_record)?;
+        check_outgoing_freeze(
+            freeze_record.as_ref(),
+            ctx.accounts.token_account.amount.saturating_sub(amount),
+            Clock::get()?.unix_timestamp,
+        )?;
+
         // Burn the tokens
         let cpi_accounts = token_2022::Burn {
             mint: ctx.accounts.mint.to_account_info(),
@@ -191,162 +753,1063 @@ pub mod mica_eur {
             cpi_accounts,
         );
         
-        token_2022::burn(cpi_ctx, amount)?;
+        burn_checked(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+        record_burned(&mut ctx.accounts.mint_info, amount)?;
+
+        record_operation(
+            &mut ctx.accounts.operation_receipt,
+            operation_id,
+            OperationDetails {
+                kind: OperationKind::Redemption,
+                mint: ctx.accounts.mint.key(),
+                actor: ctx.accounts.owner.key(),
+                source: ctx.accounts.token_account.key(),
+                destination: Pubkey::default(),
+                amount,
+            },
+        )?;
 
-        msg!("Burned {} tokens from {}", amount, ctx.accounts.token_account.key());
+        emit!(TokensBurned {
+            mint: ctx.accounts.mint.key(),
+            token_account: ctx.accounts.token_account.key(),
+            owner: ctx.accounts.owner.key(),
+            amount,
+            operation_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
     
-    /// Freeze an account (regulatory action)
-    pub fn freeze_account(ctx: Context<FreezeAccount>) -> Result<()> {
+    /// Freeze an account (regulatory action) for `reason_code` under `legal_reference`.
+    /// A `frozen_amount` of 0 freezes the whole account, anything else only locks that
+    /// part of the balance; an `expires_at` of 0 keeps the freeze until thawed.
+    pub fn freeze_account(
+        ctx: Context<FreezeAccount>,
+        reason_code: u8,
+        legal_reference: String,
+        frozen_amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        record_history(
+            ctx.accounts.compliance_history.as_deref_mut(),
+            ComplianceAction::Frozen { reason_code, frozen_amount },
+            ctx.accounts.freeze_authority.key(),
+            reference_hash(&legal_reference),
+            now,
+        );
+        record_freeze(
+            &mut ctx.accounts.freeze_record,
+            ctx.accounts.mint.key(),
+            ctx.accounts.token_account.key(),
+            ctx.accounts.token_account.owner,
+            ctx.accounts.freeze_authority.key(),
+            reason_code,
+            legal_reference,
+            frozen_amount,
+            expires_at,
+            now,
+        )?;
+
+        // Freezes that can lapse or only lock part of the balance are enforced by
+        // the transfer hook, the Token-2022 flag would outlive them
+        if !ctx.accounts.freeze_record.uses_frozen_flag() {
+            return Ok(());
+        }
+
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
         let cpi_accounts = token_2022::FreezeAccount {
             account: ctx.accounts.token_account.to_account_info(),
             mint: ctx.accounts.mint.to_account_info(),
-            authority: ctx.accounts.freeze_authority.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
         };
         
-        let cpi_ctx = CpiContext::new(
+        let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
+            signer,
         );
         
         token_2022::freeze_account(cpi_ctx)?;
 
-        msg!("Frozen account {}", ctx.accounts.token_account.key());
+        emit!(AccountFrozen {
+            mint: ctx.accounts.mint.key(),
+            token_account: ctx.accounts.token_account.key(),
+            authority: ctx.accounts.freeze_authority.key(),
+            timestamp: now,
+        });
         Ok(())
     }
     
-    /// Thaw (unfreeze) an account
+    /// Thaw (unfreeze) an account, lifting its recorded freeze if it has one
     pub fn thaw_account(ctx: Context<ThawAccount>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let freeze_authority = ctx.accounts.freeze_authority.key();
+        let recorded = match ctx.accounts.freeze_record.as_deref_mut() {
+            Some(record) if record.is_active => {
+                lift_freeze(record, freeze_authority, now);
+                true
+            }
+            _ => false,
+        };
+        record_history(
+            ctx.accounts.compliance_history.as_deref_mut(),
+            ComplianceAction::Thawed,
+            freeze_authority,
+            [0; 32],
+            now,
+        );
+
+        if !ctx.accounts.token_account.is_frozen() {
+            if recorded {
+                return Ok(());
+            }
+            return Err(MicaEurError::AccountNotFrozen.into());
+        }
+
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
         let cpi_accounts = token_2022::ThawAccount {
             account: ctx.accounts.token_account.to_account_info(),
             mint: ctx.accounts.mint.to_account_info(),
-            authority: ctx.accounts.freeze_authority.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
         };
         
-        let cpi_ctx = CpiContext::new(
+        let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
+            signer,
         );
         
         token_2022::thaw_account(cpi_ctx)?;
 
-        msg!("Thawed account {}", ctx.accounts.token_account.key());
+        emit!(AccountThawed {
+            mint: ctx.accounts.mint.key(),
+            token_account: ctx.accounts.token_account.key(),
+            authority: freeze_authority,
+            timestamp: now,
+        });
         Ok(())
     }
+
+    /// Lift a freeze whose expiry has passed (anyone)
+    pub fn auto_thaw(ctx: Context<AutoThaw>) -> Result<()> {
+        freezes::auto_thaw(ctx)
+    }
     
-    /// Seize tokens from an account (regulatory action)
-    pub fn seize_tokens(
-        ctx: Context<SeizeTokens>,
+    /// Seize tokens from an account (regulatory action). The remaining accounts are
+    /// the transfer hook's extra accounts.
+    pub fn seize_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, SeizeTokens<'info>>,
         amount: u64,
+        operation_id: [u8; 16],
     ) -> Result<()> {
-        // The permanent delegate can transfer tokens without the owner's signature
-        let cpi_accounts = token_2022::Transfer {
+        // The permanent delegate, the seizure authority PDA, can transfer tokens without
+        // the owner's signature
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [ctx.bumps.seizure_authority];
+        let seeds = &[SEIZURE_AUTHORITY_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = token_2022::TransferChecked {
             from: ctx.accounts.from_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.to_account.to_account_info(),
-            authority: ctx.accounts.permanent_delegate.to_account_info(),
+            authority: ctx.accounts.seizure_authority.to_account_info(),
         };
         
-        let cpi_ctx = CpiContext::new(
+        let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
-        );
+            signer,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
         
-        token_2022::transfer(cpi_ctx, amount)?;
+        transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+
+        let owner = token_account_owner(&ctx.accounts.from_account)?;
+        check_history_owner(ctx.accounts.compliance_history.as_deref(), &owner)?;
+        record_history(
+            ctx.accounts.compliance_history.as_deref_mut(),
+            ComplianceAction::Seized { amount },
+            ctx.accounts.permanent_delegate.key(),
+            [0; 32],
+            Clock::get()?.unix_timestamp,
+        );
 
-        msg!("Seized {} tokens from {}", amount, ctx.accounts.from_account.key());
+        record_operation(
+            &mut ctx.accounts.operation_receipt,
+            operation_id,
+            OperationDetails {
+                kind: OperationKind::Seizure,
+                mint: ctx.accounts.mint.key(),
+                actor: ctx.accounts.permanent_delegate.key(),
+                source: ctx.accounts.from_account.key(),
+                destination: ctx.accounts.to_account.key(),
+                amount,
+            },
+        )?;
+
+        emit!(TokensSeized {
+            mint: ctx.accounts.mint.key(),
+            from: ctx.accounts.from_account.key(),
+            to: ctx.accounts.to_account.key(),
+            amount,
+            permanent_delegate: ctx.accounts.permanent_delegate.key(),
+            operation_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
     
-    /// Update the reserve proof
+    /// Set up the escrow vault seized funds are held in (issuer only)
+    pub fn configure_seizure_vault(ctx: Context<ConfigureSeizureVault>) -> Result<()> {
+        seizure_vault::configure_seizure_vault(ctx)
+    }
+
+    /// Set up the treasury vault primary issuance goes through (issuer only)
+    pub fn configure_treasury_vault(ctx: Context<ConfigureTreasuryVault>) -> Result<()> {
+        treasury::configure_treasury_vault(ctx)
+    }
+
+    /// Mint into the treasury vault for later distribution (issuer only)
+    pub fn mint_to_treasury(ctx: Context<MintToTreasury>, amount: u64, operation_id: [u8; 16]) -> Result<()> {
+        treasury::mint_to_treasury(ctx, amount, operation_id)
+    }
+
+    /// Distribute treasury funds to a KYC-verified holder (DISTRIBUTOR role)
+    pub fn distribute_from_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeFromTreasury<'info>>,
+        amount: u64,
+        operation_id: [u8; 16],
+    ) -> Result<()> {
+        treasury::distribute_from_treasury(ctx, amount, operation_id)
+    }
+
+    /// Seize tokens into the escrow vault pending legal resolution (permanent delegate)
+    pub fn seize_to_vault<'info>(
+        ctx: Context<'_, '_, '_, 'info, SeizeToVault<'info>>,
+        amount: u64,
+        operation_id: [u8; 16],
+        case_hash: [u8; 32],
+    ) -> Result<()> {
+        seizure_vault::seize_to_vault(ctx, amount, operation_id, case_hash)
+    }
+
+    /// Release a held seizure to the account named in the ruling (permanent delegate)
+    pub fn release_from_vault<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveSeizure<'info>>,
+        resolution_hash: [u8; 32],
+    ) -> Result<()> {
+        seizure_vault::release_from_vault(ctx, resolution_hash)
+    }
+
+    /// Burn a held seizure forfeited under a ruling (permanent delegate)
+    pub fn forfeit_from_vault(ctx: Context<ResolveSeizure>, resolution_hash: [u8; 32]) -> Result<()> {
+        seizure_vault::forfeit_from_vault(ctx, resolution_hash)
+    }
+
+    /// Set the transfer fee, its per-transfer cap and the fee treasury (issuer only).
+    /// Token-2022 applies the new fee two epochs later.
+    pub fn configure_transfer_fee(
+        ctx: Context<ConfigureTransferFee>,
+        transfer_fee_bps: u16,
+        max_transfer_fee: u64,
+    ) -> Result<()> {
+        transfer_fees::configure_transfer_fee(ctx, transfer_fee_bps, max_transfer_fee)
+    }
+
+    /// Withdraw the fees withheld on the mint to the fee treasury (issuer only)
+    pub fn withdraw_withheld_fees(ctx: Context<WithdrawWithheldFees>) -> Result<()> {
+        transfer_fees::withdraw_withheld_fees(ctx)
+    }
+
+    /// Harvest withheld fees from the token accounts passed as remaining accounts
+    /// and withdraw them to the fee treasury (issuer only)
+    pub fn harvest_fees_to_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawWithheldFees<'info>>,
+    ) -> Result<()> {
+        transfer_fees::harvest_fees_to_treasury(ctx)
+    }
+
+    /// Enable confidential transfers with the auditor ElGamal key held by an AML
+    /// authority (issuer and that authority)
+    pub fn enable_confidential_transfers(
+        ctx: Context<EnableConfidentialTransfers>,
+        auditor_elgamal_pubkey: [u8; 32],
+    ) -> Result<()> {
+        confidential::enable_confidential_transfers(ctx, auditor_elgamal_pubkey)
+    }
+
+    /// Approve the owner's token account for confidential balances (owner, who must
+    /// be verified and not blacklisted)
+    pub fn approve_confidential_account(ctx: Context<ApproveConfidentialAccount>) -> Result<()> {
+        confidential::approve_confidential_account(ctx)
+    }
+
+    /// Set the interest rate of the mint, in basis points per year, and log the
+    /// change in its rate history (issuer only)
+    pub fn set_interest_rate(ctx: Context<SetInterestRate>, rate: i16) -> Result<()> {
+        interest::set_interest_rate(ctx, rate)
+    }
+
+    /// Post a new reserve proof (issuer or reserve publishers). It counts as independently attested
+    /// once the reserve auditor confirms the same root with `attest_reserve_proof`. `composition`
+    /// breaks the total down by asset category and must add up to it. Every proof is
+    /// archived in a `ReserveProofEpoch` so earlier ones stay verifiable. Once a valuation
+    /// is configured, the Pyth price updates for EUR/USD and each listed bond follow as
+    /// remaining accounts and the bond total is checked against them.
     pub fn update_reserve_proof(
         ctx: Context<UpdateReserveProof>,
         merkle_root: [u8; 32],
         ipfs_cid: String,
+        reserve_total_eur: u64,
+        composition: Vec<ReserveAsset>,
     ) -> Result<()> {
+        check_ipfs_cid(&ipfs_cid)?;
+        let now = Clock::get()?.unix_timestamp;
+        record_reserve_composition(
+            &mut ctx.accounts.reserve_composition,
+            ctx.accounts.mint.key(),
+            &composition,
+            reserve_total_eur,
+            merkle_root,
+            now,
+        )?;
+        if ctx.accounts.mint_info.reserve_valuation != Pubkey::default() {
+            let config = match &ctx.accounts.valuation_config {
+                Some(config) if config.key() == ctx.accounts.mint_info.reserve_valuation => config,
+                _ => return Err(MicaEurError::ReserveValuationRequired.into()),
+            };
+            ctx.accounts.reserve_composition.valuation = value_reserve(
+                config,
+                &composition,
+                ctx.remaining_accounts,
+                ctx.accounts.mint_info.decimals,
+                now,
+            )?;
+        }
         let mint_info = &mut ctx.accounts.mint_info;
-        let ipfs_cid_clone = ipfs_cid.clone(); // Clone before using
         
         mint_info.reserve_merkle_root = merkle_root;
-        mint_info.reserve_ipfs_cid = ipfs_cid; // Original can be moved here
-        mint_info.last_reserve_update = Clock::get()?.unix_timestamp;
+        mint_info.reserve_ipfs_cid = ipfs_cid.clone();
+        mint_info.reserve_total_eur = reserve_total_eur;
+        mint_info.last_reserve_update = now;
+        let epoch = mint_info.reserve_epoch_count;
+        archive_reserve_proof(
+            &mut ctx.accounts.reserve_proof_epoch,
+            mint_info,
+            ctx.accounts.issuer.key(),
+            ctx.accounts.mint.supply,
+        )?;
         
-        msg!("Reserve proof updated");
-        msg!("Merkle root: {:?}", merkle_root);
-        msg!("IPFS CID: {}", ipfs_cid_clone); // Use the clone
+        emit!(ReserveProofUpdated {
+            mint: mint_info.mint,
+            epoch,
+            merkle_root,
+            ipfs_cid,
+            reserve_total_eur,
+            timestamp: mint_info.last_reserve_update,
+        });
+
+        // The new figure is checked against the supply right away
+        apply_reserve_coverage(mint_info, ctx.accounts.mint.supply)?;
         
         Ok(())
     }
 
-    /// Register an AML authority
-    pub fn register_aml_authority(
-        ctx: Context<RegisterAmlAuthority>,
-        authority_id: String,
-        powers: u8,
+    /// Compare the reported reserve with the mint supply, auto-pausing minting on a
+    /// shortfall if enabled (anyone can call). Returns the current coverage.
+    pub fn check_reserve_coverage(ctx: Context<CheckReserveCoverage>) -> Result<ReserveCoverage> {
+        reserve_coverage::check_reserve_coverage(ctx)
+    }
+
+    /// Turn auto-pausing of minting on reserve shortfall on or off (issuer only)
+    pub fn set_coverage_auto_pause(ctx: Context<SetCoverageAutoPause>, enabled: bool) -> Result<()> {
+        reserve_coverage::set_coverage_auto_pause(ctx, enabled)
+    }
+
+    /// Check reserve proofs against Pyth prices of EUR/USD and the listed bonds, accepting a
+    /// divergence of up to `tolerance_bps` (issuer only)
+    pub fn configure_reserve_valuation(
+        ctx: Context<ConfigureReserveValuation>,
+        eur_usd_feed: [u8; 32],
+        bond_feeds: Vec<BondPriceFeed>,
+        tolerance_bps: u16,
+        max_price_age: i64,
     ) -> Result<()> {
-        aml::register_aml_authority(ctx, authority_id, powers)
+        reserve_valuation::configure_reserve_valuation(ctx, eur_usd_feed, bond_feeds, tolerance_bps, max_price_age)
     }
 
-    /// Create a blacklist entry for a user
-    pub fn create_blacklist_entry(
-        ctx: Context<CreateBlacklistEntry>,
-        reason: u8,
+    /// Block minting while the latest reserve proof is older than `max_reserve_age_seconds`,
+    /// 0 to never block (issuer only)
+    pub fn set_max_reserve_age(ctx: Context<SetMaxReserveAge>, max_reserve_age_seconds: i64) -> Result<()> {
+        reserve_coverage::set_max_reserve_age(ctx, max_reserve_age_seconds)
+    }
+
+    /// Publish the merkle root of holder balances at a slot for off-chain loyalty
+    /// programs (issuer only). Snapshots never pay anything out on-chain.
+    pub fn publish_balance_snapshot(
+        ctx: Context<PublishBalanceSnapshot>,
+        merkle_root: [u8; 32],
+        snapshot_slot: u64,
+        holder_count: u32,
+        total_balance: u64,
     ) -> Result<()> {
-        aml::create_blacklist_entry(ctx, reason)
+        snapshots::publish_balance_snapshot(ctx, merkle_root, snapshot_slot, holder_count, total_balance)
     }
 
-    /// Deactivate an AML authority (issuer or regulator only)
-    pub fn deactivate_aml_authority(
-        ctx: Context<DeactivateAmlAuthority>,
+    /// Record a discrepancy between the chain and the core-banking ledger that
+    /// persisted beyond the reconciliation threshold (issuer only)
+    pub fn record_reconciliation(
+        ctx: Context<RecordReconciliation>,
+        pending_redemptions: u64,
+        registered_deposits: u64,
+        ledger_balance: u64,
+        ledger_hash: [u8; 32],
+        first_detected_time: i64,
     ) -> Result<()> {
-        aml::deactivate_aml_authority(ctx)
+        reconciliation::record_reconciliation(
+            ctx,
+            pending_redemptions,
+            registered_deposits,
+            ledger_balance,
+            ledger_hash,
+            first_detected_time,
+        )
     }
 
-    /// Deactivate (un-blacklist) a blacklist entry (AML authority only)
-    pub fn deactivate_blacklist_entry(
-        ctx: Context<DeactivateBlacklistEntry>,
+    /// Appoint the independent auditor who confirms reserve proofs (issuer only)
+    pub fn configure_reserve_auditor(
+        ctx: Context<ConfigureReserveAuditor>,
+        auditor: Pubkey,
+        attestation_window: i64,
     ) -> Result<()> {
-        aml::deactivate_blacklist_entry(ctx)
+        reserve_attestation::configure_reserve_auditor(ctx, auditor, attestation_window)
     }
 
-    /// Update the powers of an AML authority (issuer or regulator only)
-    pub fn update_aml_authority_powers(
-        ctx: Context<UpdateAmlAuthorityPowers>,
-        new_powers: u8,
+    /// Confirm the reserve proof posted by the issuer (reserve auditor only)
+    pub fn attest_reserve_proof(
+        ctx: Context<AttestReserveProof>,
+        merkle_root: [u8; 32],
     ) -> Result<()> {
-        aml::update_aml_authority_powers(ctx, new_powers)
+        reserve_attestation::attest_reserve_proof(ctx, merkle_root)
     }
 
-    // ---------------- AML context types ----------------
-    #[derive(Accounts)]
-    pub struct RegisterAmlAuthority<'info> {
-        #[account(mut)]
-        pub authority: Signer<'info>,
+    /// Prove a deposit leaf is in the latest reserve proof (anyone), emitting
+    /// `ReserveLeafVerified`; fails if it is not
+    pub fn verify_reserve_inclusion(
+        ctx: Context<VerifyReserveInclusion>,
+        leaf: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        reserve_inclusion::verify_reserve_inclusion(ctx, leaf, proof)
+    }
 
-        #[account(
-            init,
-            payer = authority,
-            seeds = [AML_AUTHORITY_SEED, authority.key().as_ref()],
-            bump,
-            space = 8 + std::mem::size_of::<crate::aml::AmlAuthority>() + 64,
-        )]
-        pub aml_authority: Account<'info, crate::aml::AmlAuthority>,
+    /// Whether a deposit leaf is in the latest reserve proof, for simulation
+    pub fn reserve_inclusion(
+        ctx: Context<VerifyReserveInclusion>,
+        leaf: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<bool> {
+        reserve_inclusion::reserve_inclusion(ctx, leaf, proof)
+    }
 
-        pub system_program: Program<'info, System>,
+    /// Prove a deposit leaf is in the reserve proof archived under `epoch` (anyone),
+    /// emitting `ReserveLeafVerified`; fails if it is not
+    pub fn verify_archived_reserve_inclusion(
+        ctx: Context<VerifyArchivedReserveInclusion>,
+        epoch: u64,
+        leaf: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        reserve_epochs::verify_archived_reserve_inclusion(ctx, epoch, leaf, proof)
     }
 
-    #[derive(Accounts)]
-    pub struct CreateBlacklistEntry<'info> {
-        #[account(mut)]
-        pub authority: Signer<'info>,
+    /// Stage a rule set the transfer hook evaluates in dry-run mode next to the
+    /// active one (issuer only)
+    pub fn stage_policy(
+        ctx: Context<StagePolicy>,
+        rule_set_version: u32,
+        params: PolicyParams,
+    ) -> Result<()> {
+        policy_staging::stage_policy(ctx, rule_set_version, params)
+    }
 
-        #[account(
-            mut,
-            seeds = [AML_AUTHORITY_SEED, authority.key().as_ref()],
-            bump,
-            has_one = authority,
-        )]
-        pub aml_authority: Account<'info, crate::aml::AmlAuthority>,
+    /// Remove the staged rule set (issuer only)
+    pub fn clear_staged_policy(ctx: Context<ClearStagedPolicy>) -> Result<()> {
+        policy_staging::clear_staged_policy(ctx)
+    }
+
+    /// Freeze a mint and open the migration of its holders to a new mint (issuer only)
+    pub fn start_migration(ctx: Context<StartMigration>) -> Result<()> {
+        migration::start_migration(ctx)
+    }
+
+    /// Burn a holder's balance under the old mint and remint it under the new one
+    /// (holder and issuer)
+    pub fn claim_migration(ctx: Context<ClaimMigration>) -> Result<()> {
+        migration::claim_migration(ctx)
+    }
+
+    /// Close migration claims (issuer only)
+    pub fn complete_migration(ctx: Context<CompleteMigration>) -> Result<()> {
+        migration::complete_migration(ctx)
+    }
+
+    /// Set or clear the enhanced-due-diligence flag of a KYC user (KYC oracle authority)
+    pub fn set_edd_flag(ctx: Context<SetEddFlag>, edd_required: bool) -> Result<()> {
+        edd::set_edd_flag(ctx, edd_required)
+    }
+
+    /// Configure the EDD review threshold, window and escrow vault of a mint (issuer only)
+    pub fn configure_edd(
+        ctx: Context<ConfigureEdd>,
+        transfer_threshold: u64,
+        approval_window: i64,
+    ) -> Result<()> {
+        edd::configure_edd(ctx, transfer_threshold, approval_window)
+    }
+
+    /// Hold a transfer needing EDD review in escrow until a compliance officer decides
+    pub fn request_edd_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, RequestEddTransfer<'info>>,
+        request_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        edd::request_edd_transfer(ctx, request_id, amount)
+    }
+
+    /// Release an escrowed transfer to its recipient (compliance officer)
+    pub fn approve_edd_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReviewEddTransfer<'info>>,
+    ) -> Result<()> {
+        edd::approve_edd_transfer(ctx)
+    }
+
+    /// Refund an escrowed transfer to its sender (compliance officer)
+    pub fn reject_edd_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReviewEddTransfer<'info>>,
+        decision_reason: u8,
+    ) -> Result<()> {
+        edd::reject_edd_transfer(ctx, decision_reason)
+    }
+
+    /// Refund an escrowed transfer whose review window has passed (anyone)
+    pub fn expire_edd_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExpireEddTransfer<'info>>,
+    ) -> Result<()> {
+        edd::expire_edd_transfer(ctx)
+    }
+
+    /// Set the daily mint, burn and transfer limits per verification level (issuer only)
+    pub fn set_limits(ctx: Context<SetLimits>, tiers: [TierLimits; 4]) -> Result<()> {
+        limits::set_limits(ctx, tiers)
+    }
+
+    /// Configure the N-of-M officers required for regulatory actions (issuer only)
+    pub fn configure_approval_set(
+        ctx: Context<ConfigureApprovalSet>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+        large_mint_threshold: u64,
+    ) -> Result<()> {
+        approvals::configure_approval_set(ctx, signers, threshold, large_mint_threshold)
+    }
+
+    /// Propose a freeze, thaw, seizure or mint for approval (officer of the set)
+    pub fn propose_action(ctx: Context<ProposeAction>, action: RegulatoryAction) -> Result<()> {
+        approvals::propose_action(ctx, action)
+    }
+
+    /// Approve an open proposal (officer of the set)
+    pub fn approve_action(ctx: Context<ReviewAction>) -> Result<()> {
+        approvals::approve_action(ctx)
+    }
+
+    /// Cancel an open proposal (officer of the set)
+    pub fn cancel_action(ctx: Context<ReviewAction>) -> Result<()> {
+        approvals::cancel_action(ctx)
+    }
+
+    /// Execute a proposal that reached its threshold (key holding the token authority)
+    pub fn execute_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteAction<'info>>,
+    ) -> Result<()> {
+        approvals::execute_action(ctx)
+    }
+
+    /// Record the compliance rule set compiled into this program as the active one
+    /// (issuer only, after a program upgrade)
+    pub fn sync_rule_set(ctx: Context<SyncRuleSet>) -> Result<()> {
+        let mint_info = &mut ctx.accounts.mint_info;
+        mint_info.rule_set_version = RULE_SET_VERSION;
+        mint_info.rule_set_hash = rule_set_hash();
+
+        emit!(RuleSetActivated {
+            mint: mint_info.mint,
+            rule_set_version: mint_info.rule_set_version,
+            rule_set_hash: mint_info.rule_set_hash,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Active compliance rule set version: {}", mint_info.rule_set_version);
+        Ok(())
+    }
+
+    /// Halt minting, redemption, seizure and transfers during an incident
+    /// (issuer, pauser or pauser role holders)
+    pub fn pause_token(ctx: Context<SetTokenPaused>) -> Result<()> {
+        set_token_paused(ctx, true)
+    }
+
+    /// Resume token activity after an incident (issuer, pauser or pauser role holders)
+    pub fn resume_token(ctx: Context<SetTokenPaused>) -> Result<()> {
+        set_token_paused(ctx, false)
+    }
+
+    /// Wind the mint down: minting stops while holders can still redeem (issuer
+    /// only, through the timelock once one is configured)
+    pub fn deactivate_mint(ctx: Context<SetMintActive>) -> Result<()> {
+        check_no_timelock(&ctx.accounts.mint_info)?;
+        let issuer = ctx.accounts.issuer.key();
+        set_mint_active(&mut ctx.accounts.mint_info, false, issuer)
+    }
+
+    /// Resume minting on a deactivated mint that was not migrated (issuer only,
+    /// through the timelock once one is configured)
+    pub fn reactivate_mint(ctx: Context<SetMintActive>) -> Result<()> {
+        check_no_timelock(&ctx.accounts.mint_info)?;
+        let issuer = ctx.accounts.issuer.key();
+        set_mint_active(&mut ctx.accounts.mint_info, true, issuer)
+    }
+
+    /// Start the orderly redemption of the mint: minting stops for good and holders
+    /// may return tokens to the issuer's redemption account until the deadline
+    /// (issuer only)
+    pub fn initiate_wind_down(ctx: Context<InitiateWindDown>, redemption_deadline: i64) -> Result<()> {
+        wind_down::initiate_wind_down(ctx, redemption_deadline)
+    }
+
+    /// Move the Token-2022 mint authority of an existing mint from a keypair to
+    /// the mint info PDA (issuer and current authority)
+    pub fn migrate_mint_authority(ctx: Context<MigrateMintAuthority>) -> Result<()> {
+        mint_authority::migrate_mint_authority(ctx)
+    }
+
+    /// Move the Token-2022 permanent delegate of an existing mint from the mint info
+    /// PDA to the seizure authority PDA (issuer only)
+    pub fn migrate_permanent_delegate(ctx: Context<MigratePermanentDelegate>) -> Result<()> {
+        mint_authority::migrate_permanent_delegate(ctx)
+    }
+
+    /// Record a SEPA deposit credited to `user` as a voucher for a mint of `amount`
+    /// (issuer only)
+    pub fn create_mint_voucher(
+        ctx: Context<CreateMintVoucher>,
+        user: Pubkey,
+        reference_hash: [u8; 32],
+        amount: u64,
+    ) -> Result<()> {
+        mint_vouchers::create_mint_voucher(ctx, user, reference_hash, amount)
+    }
+
+    /// Claim a mint voucher: its amount is minted to the user's token account
+    pub fn claim_mint_voucher(ctx: Context<ClaimMintVoucher>) -> Result<()> {
+        mint_vouchers::claim_mint_voucher(ctx)
+    }
+
+    /// Attach the hashed bank reference of its SEPA payout to a settled redemption
+    /// (issuer only)
+    pub fn record_payout_reference(
+        ctx: Context<RecordPayoutReference>,
+        operation_id: [u8; 16],
+        reference_hash: [u8; 32],
+    ) -> Result<()> {
+        payout_references::record_payout_reference(ctx, operation_id, reference_hash)
+    }
+
+    /// Grant roles to a key: mint roles by the mint's issuer, `KYC_ADMIN` by the KYC
+    /// oracle authority
+    pub fn grant_role(ctx: Context<GrantRole>, role: u8) -> Result<()> {
+        access_control::grant_role(ctx, role)
+    }
+
+    /// Revoke roles from a key (same admins as `grant_role`)
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: u8) -> Result<()> {
+        access_control::revoke_role(ctx, role)
+    }
+
+    /// Set the amount a delegated minter may still mint (issuer only)
+    pub fn set_minter_allowance(ctx: Context<SetMinterAllowance>, allowance: u64) -> Result<()> {
+        allowances::set_minter_allowance(ctx, allowance)
+    }
+
+    /// Appoint the pauser role (issuer only, through the timelock once one is configured)
+    pub fn set_pauser(ctx: Context<SetPauser>, new_pauser: Pubkey) -> Result<()> {
+        check_no_timelock(&ctx.accounts.mint_info)?;
+        let mint_info = &mut ctx.accounts.mint_info;
+        let old_pauser = mint_info.pauser;
+        mint_info.pauser = new_pauser;
+
+        emit!(PauserChanged {
+            mint: mint_info.mint,
+            old_pauser,
+            new_pauser,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Require mints to go to the recipient's associated token account, or lift the
+    /// requirement (issuer only)
+    pub fn set_associated_account_requirement(
+        ctx: Context<SetAssociatedAccountRequirement>,
+        required: bool,
+    ) -> Result<()> {
+        let mint_info = &mut ctx.accounts.mint_info;
+        mint_info.require_associated_accounts = required;
+
+        emit!(AssociatedAccountRequirementChanged {
+            mint: mint_info.mint,
+            required,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Register an AML authority for a jurisdiction or globally (issuer or regulator approval required)
+    pub fn register_aml_authority(
+        ctx: Context<RegisterAmlAuthority>,
+        authority_id: String,
+        powers: u8,
+        jurisdiction: String,
+        global: bool,
+    ) -> Result<()> {
+        aml::register_aml_authority(ctx, authority_id, powers, jurisdiction, global)
+    }
+
+    /// Record the regulator key that may approve AML authority registrations (issuer only)
+    pub fn set_regulator(ctx: Context<SetRegulator>, regulator: Pubkey) -> Result<()> {
+        aml::set_regulator(ctx, regulator)
+    }
+
+    /// Grant a national competent authority read access to the mint's audit exports until
+    /// `expires_at`, 0 for no expiry (issuer only)
+    pub fn grant_regulator_access(
+        ctx: Context<GrantRegulatorAccess>,
+        regulator: Pubkey,
+        authority_id: String,
+        expires_at: i64,
+    ) -> Result<()> {
+        regulator_access::grant_regulator_access(ctx, regulator, authority_id, expires_at)
+    }
+
+    /// Revoke a regulator's read access (issuer only)
+    pub fn revoke_regulator_access(ctx: Context<RevokeRegulatorAccess>) -> Result<()> {
+        regulator_access::revoke_regulator_access(ctx)
+    }
+
+    /// Emit the digest of the program events in [from, to) for a regulator with access, an
+    /// anchor for reconciling its off-chain data pulls (issuer only)
+    pub fn export_audit_window(
+        ctx: Context<ExportAuditWindow>,
+        from: i64,
+        to: i64,
+        event_count: u64,
+        digest: [u8; 32],
+    ) -> Result<()> {
+        regulator_access::export_audit_window(ctx, from, to, event_count, digest)
+    }
+
+    /// Allow a program's PDAs, e.g. a regulated DEX escrow, to hold and move tokens
+    /// without KYC (issuer only)
+    pub fn allowlist_program(ctx: Context<AllowlistProgram>, program: Pubkey) -> Result<()> {
+        program_allowlist::allowlist_program(ctx, program)
+    }
+
+    /// Remove a program from the allowlist (issuer only)
+    pub fn delist_program(ctx: Context<DelistProgram>, program: Pubkey) -> Result<()> {
+        program_allowlist::delist_program(ctx, program)
+    }
+
+    /// Register a PDA of an allowlisted program, proven by its seeds
+    pub fn register_program_escrow(
+        ctx: Context<RegisterProgramEscrow>,
+        program: Pubkey,
+        seeds: Vec<Vec<u8>>,
+    ) -> Result<()> {
+        program_allowlist::register_program_escrow(ctx, program, seeds)
+    }
+
+    /// Check a move of `amount` between two wallets against the token's transfer
+    /// rules, for programs mirroring them on internal accounting moves via CPI
+    pub fn assert_transfer_allowed(
+        ctx: Context<AssertTransferAllowed>,
+        source_owner: Pubkey,
+        dest_owner: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        transfer_checks::assert_transfer_allowed(ctx, source_owner, dest_owner, amount)
+    }
+
+    /// Move tokens on the owner's Ed25519-signed authorization, submitted by a relayer
+    /// paying the fees. Requires the Ed25519 instruction right before this one.
+    pub fn transfer_with_authorization<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferWithAuthorization<'info>>,
+        recipient: Pubkey,
+        amount: u64,
+        nonce: [u8; 32],
+        expiry: i64,
+    ) -> Result<()> {
+        permits::transfer_with_authorization(ctx, recipient, amount, nonce, expiry)
+    }
+
+    /// Set the stake relayers must put up; from then on only active registered
+    /// relayers may submit signed transfer authorizations (issuer only)
+    pub fn configure_relayer_registry(ctx: Context<ConfigureRelayerRegistry>, min_stake: u64) -> Result<()> {
+        relayers::configure_relayer_registry(ctx, min_stake)
+    }
+
+    /// Register as a relayer, staking at least the registry's minimum
+    pub fn register_relayer(ctx: Context<RegisterRelayer>, stake: u64) -> Result<()> {
+        relayers::register_relayer(ctx, stake)
+    }
+
+    /// Suspend or reinstate a relayer (issuer only)
+    pub fn set_relayer_status(ctx: Context<SetRelayerStatus>, status: RelayerStatus) -> Result<()> {
+        relayers::set_relayer_status(ctx, status)
+    }
+
+    /// Leave the relayer registry and take the stake back
+    pub fn deregister_relayer(ctx: Context<DeregisterRelayer>) -> Result<()> {
+        relayers::deregister_relayer(ctx)
+    }
+
+    /// Set up the vault holding the mint's commerce escrows (issuer only)
+    pub fn configure_escrow_vault(ctx: Context<ConfigureEscrowVault>) -> Result<()> {
+        escrows::configure_escrow_vault(ctx)
+    }
+
+    /// Pay into escrow for a seller; both parties are checked as for a direct
+    /// transfer. Remaining accounts: the transfer hook's extra accounts.
+    pub fn open_escrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, OpenEscrow<'info>>,
+        escrow_id: u64,
+        amount: u64,
+        arbiter: Pubkey,
+        terms_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        escrows::open_escrow(ctx, escrow_id, amount, arbiter, terms_hash, expires_at)
+    }
+
+    /// Release an escrow to the seller (buyer or arbiter). Remaining accounts: the
+    /// transfer hook's extra accounts.
+    pub fn release_escrow<'info>(ctx: Context<'_, '_, '_, 'info, ResolveEscrow<'info>>) -> Result<()> {
+        escrows::release_escrow(ctx)
+    }
+
+    /// Refund an escrow to the buyer (seller or arbiter, or the buyer once it
+    /// expired). Remaining accounts: the transfer hook's extra accounts.
+    pub fn refund_escrow<'info>(ctx: Context<'_, '_, '_, 'info, ResolveEscrow<'info>>) -> Result<()> {
+        escrows::refund_escrow(ctx)
+    }
+
+    /// Request a payment into the payee's token account, identified by the hash of
+    /// its remittance information
+    pub fn create_payment_request(
+        ctx: Context<CreatePaymentRequest>,
+        request_id: u64,
+        amount: u64,
+        memo_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        payment_requests::create_payment_request(ctx, request_id, amount, memo_hash, expires_at)
+    }
+
+    /// Pay an open payment request in full. Remaining accounts: the transfer hook's
+    /// extra accounts.
+    pub fn pay_request<'info>(ctx: Context<'_, '_, '_, 'info, PayRequest<'info>>) -> Result<()> {
+        payment_requests::pay_request(ctx)
+    }
+
+    /// Create a blacklist entry for a user
+    pub fn create_blacklist_entry(
+        ctx: Context<CreateBlacklistEntry>,
+        reason: BlacklistReason,
+        legal_reference: String,
+        expiry_time: i64,
+        review_time: i64,
+    ) -> Result<()> {
+        aml::create_blacklist_entry(ctx, reason, legal_reference, expiry_time, review_time)
+    }
+
+    /// Deactivate an AML authority (issuer or regulator only)
+    pub fn deactivate_aml_authority(
+        ctx: Context<DeactivateAmlAuthority>,
+    ) -> Result<()> {
+        aml::deactivate_aml_authority(ctx)
+    }
+
+    /// Deactivate (un-blacklist) a blacklist entry (AML authority only)
+    pub fn deactivate_blacklist_entry(
+        ctx: Context<DeactivateBlacklistEntry>,
+    ) -> Result<()> {
+        aml::deactivate_blacklist_entry(ctx)
+    }
+
+    /// Change the expiry and review time of an active blacklist entry (AML authority only)
+    pub fn extend_blacklist_entry(
+        ctx: Context<ExtendBlacklistEntry>,
+        expiry_time: i64,
+        review_time: i64,
+    ) -> Result<()> {
+        aml::extend_blacklist_entry(ctx, expiry_time, review_time)
+    }
+
+    /// Deactivate a blacklist entry whose expiry has passed (anyone)
+    pub fn lapse_blacklist_entry(ctx: Context<LapseBlacklistEntry>) -> Result<()> {
+        aml::lapse_blacklist_entry(ctx)
+    }
+
+    /// Change the reason and legal reference of a blacklist entry (AML authority only)
+    pub fn reclassify_blacklist_entry(
+        ctx: Context<ReclassifyBlacklistEntry>,
+        reason: BlacklistReason,
+        legal_reference: String,
+    ) -> Result<()> {
+        aml::reclassify_blacklist_entry(ctx, reason, legal_reference)
+    }
+
+    /// Update the powers of an AML authority (issuer or regulator only)
+    pub fn update_aml_authority_powers(
+        ctx: Context<UpdateAmlAuthorityPowers>,
+        new_powers: u8,
+    ) -> Result<()> {
+        aml::update_aml_authority_powers(ctx, new_powers)
+    }
+
+    /// Create a standing order paying a verified payee at a fixed interval
+    pub fn create_standing_order(
+        ctx: Context<CreateStandingOrder>,
+        order_id: u64,
+        amount: u64,
+        interval_seconds: i64,
+        first_execution_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        standing_orders::create_standing_order(
+            ctx,
+            order_id,
+            amount,
+            interval_seconds,
+            first_execution_time,
+            end_time,
+        )
+    }
+
+    /// Pause a standing order (payer only)
+    pub fn pause_standing_order(ctx: Context<ManageStandingOrder>) -> Result<()> {
+        standing_orders::pause_standing_order(ctx)
+    }
+
+    /// Resume a paused standing order (payer only)
+    pub fn resume_standing_order(ctx: Context<ManageStandingOrder>) -> Result<()> {
+        standing_orders::resume_standing_order(ctx)
+    }
+
+    /// Skip the next execution of a standing order (payer only)
+    pub fn skip_standing_order(ctx: Context<ManageStandingOrder>) -> Result<()> {
+        standing_orders::skip_standing_order(ctx)
+    }
+
+    /// Cancel a standing order and close its account (payer only)
+    pub fn cancel_standing_order(ctx: Context<CancelStandingOrder>) -> Result<()> {
+        standing_orders::cancel_standing_order(ctx)
+    }
+
+    /// Execute a due standing order (permissionless keeper crank)
+    pub fn execute_standing_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteStandingOrder<'info>>,
+    ) -> Result<()> {
+        standing_orders::execute_standing_order(ctx)
+    }
+
+    // ---------------- AML context types ----------------
+    #[derive(Accounts)]
+    pub struct RegisterAmlAuthority<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            init,
+            payer = authority,
+            seeds = [AML_AUTHORITY_SEED, authority.key().as_ref()],
+            bump,
+            space = 8 + crate::aml::AmlAuthority::INIT_SPACE,
+        )]
+        pub aml_authority: Account<'info, crate::aml::AmlAuthority>,
+
+        pub system_program: Program<'info, System>,
+
+        /// The issuer or the mint's registered regulator approving the registration
+        pub approver: Signer<'info>,
+
+        #[account(
+            seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+            bump,
+        )]
+        pub mint_info: Account<'info, MintInfo>,
+
+        #[account(
+            seeds = [REGULATOR_REGISTRY_SEED, mint_info.mint.as_ref()],
+            bump,
+        )]
+        pub regulator_registry: Option<Account<'info, crate::aml::RegulatorRegistry>>,
+    }
+
+    // Context for recording a mint's regulator key
+    #[derive(Accounts)]
+    pub struct SetRegulator<'info> {
+        #[account(mut)]
+        pub issuer: Signer<'info>,
+
+        #[account(
+            seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+            bump,
+            has_one = issuer,
+        )]
+        pub mint_info: Account<'info, MintInfo>,
+
+        #[account(
+            init_if_needed,
+            payer = issuer,
+            seeds = [REGULATOR_REGISTRY_SEED, mint_info.mint.as_ref()],
+            bump,
+            space = 8 + crate::aml::RegulatorRegistry::INIT_SPACE,
+        )]
+        pub regulator_registry: Account<'info, crate::aml::RegulatorRegistry>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[derive(Accounts)]
+    pub struct CreateBlacklistEntry<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [AML_AUTHORITY_SEED, authority.key().as_ref()],
+            bump,
+            has_one = authority,
+        )]
+        pub aml_authority: Account<'info, crate::aml::AmlAuthority>,
 
         /// CHECK: only the key is used for PDA seeds
         pub user: UncheckedAccount<'info>,
@@ -356,30 +1819,130 @@ pub mod mica_eur {
             payer = authority,
             seeds = [BLACKLIST_SEED, user.key().as_ref()],
             bump,
-            space = 8 + std::mem::size_of::<crate::aml::BlacklistEntry>(),
+            space = 8 + crate::aml::BlacklistEntry::INIT_SPACE,
         )]
         pub blacklist_entry: Account<'info, crate::aml::BlacklistEntry>,
 
         pub system_program: Program<'info, System>,
+
+        /// KYC record of the user, whose country must fall under the authority's
+        /// jurisdiction; users without one can only be handled by global authorities
+        #[account(
+            seeds = [KYC_USER_SEED, user.key().as_ref()],
+            bump,
+        )]
+        pub kyc_user: Option<Account<'info, KycUser>>,
+
+        /// The user's compliance history, recorded into once opened
+        #[account(
+            mut,
+            seeds = [COMPLIANCE_HISTORY_SEED, user.key().as_ref()],
+            bump,
+        )]
+        pub compliance_history: Option<Account<'info, crate::compliance_history::ComplianceHistory>>,
     }
 
     // Context for deactivating an AML authority
     #[derive(Accounts)]
     pub struct DeactivateAmlAuthority<'info> {
-        #[account(mut)]
-        pub issuer: Signer<'info>,
+        /// The issuer, or the mint's registered regulator
+        pub approver: Signer<'info>,
         #[account(
             mut,
             seeds = [AML_AUTHORITY_SEED, aml_authority.authority.as_ref()],
             bump,
         )]
         pub aml_authority: Account<'info, crate::aml::AmlAuthority>,
-        pub system_program: Program<'info, System>,
+        #[account(
+            seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+            bump,
+            constraint = crate::aml::is_aml_admin(&mint_info, regulator_registry.as_deref(), approver.key)
+                @ MicaEurError::UnauthorizedAmlAdmin,
+        )]
+        pub mint_info: Account<'info, MintInfo>,
+        #[account(
+            seeds = [REGULATOR_REGISTRY_SEED, mint_info.mint.as_ref()],
+            bump,
+        )]
+        pub regulator_registry: Option<Account<'info, crate::aml::RegulatorRegistry>>,
     }
 
     // Context for deactivating a blacklist entry
     #[derive(Accounts)]
     pub struct DeactivateBlacklistEntry<'info> {
+        pub authority: Signer<'info>,
+        #[account(
+            seeds = [AML_AUTHORITY_SEED, authority.key().as_ref()],
+            bump,
+            has_one = authority,
+        )]
+        pub aml_authority: Account<'info, crate::aml::AmlAuthority>,
+        /// CHECK: only the key is used for PDA seeds
+        pub user: UncheckedAccount<'info>,
+        #[account(
+            mut,
+            seeds = [BLACKLIST_SEED, user.key().as_ref()],
+            bump,
+        )]
+        pub blacklist_entry: Account<'info, crate::aml::BlacklistEntry>,
+        /// KYC record of the user, whose country must fall under the authority's
+        /// jurisdiction; users without one can only be handled by global authorities
+        #[account(
+            seeds = [KYC_USER_SEED, user.key().as_ref()],
+            bump,
+        )]
+        pub kyc_user: Option<Account<'info, KycUser>>,
+
+        /// The user's compliance history, recorded into once opened
+        #[account(
+            mut,
+            seeds = [COMPLIANCE_HISTORY_SEED, user.key().as_ref()],
+            bump,
+        )]
+        pub compliance_history: Option<Account<'info, crate::compliance_history::ComplianceHistory>>,
+    }
+
+    // Context for extending a blacklist entry
+    #[derive(Accounts)]
+    pub struct ExtendBlacklistEntry<'info> {
+        pub authority: Signer<'info>,
+        #[account(
+            mut,
+            seeds = [AML_AUTHORITY_SEED, authority.key().as_ref()],
+            bump,
+            has_one = authority,
+        )]
+        pub aml_authority: Account<'info, crate::aml::AmlAuthority>,
+        #[account(
+            mut,
+            seeds = [BLACKLIST_SEED, blacklist_entry.user.as_ref()],
+            bump,
+        )]
+        pub blacklist_entry: Account<'info, crate::aml::BlacklistEntry>,
+        /// KYC record of the user, whose country must fall under the authority's
+        /// jurisdiction; users without one can only be handled by global authorities
+        #[account(
+            seeds = [KYC_USER_SEED, blacklist_entry.user.as_ref()],
+            bump,
+        )]
+        pub kyc_user: Option<Account<'info, KycUser>>,
+    }
+
+    // Context for lapsing an expired blacklist entry
+    #[derive(Accounts)]
+    pub struct LapseBlacklistEntry<'info> {
+        pub caller: Signer<'info>,
+        #[account(
+            mut,
+            seeds = [BLACKLIST_SEED, blacklist_entry.user.as_ref()],
+            bump,
+        )]
+        pub blacklist_entry: Account<'info, crate::aml::BlacklistEntry>,
+    }
+
+    // Context for reclassifying a blacklist entry
+    #[derive(Accounts)]
+    pub struct ReclassifyBlacklistEntry<'info> {
         #[account(mut)]
         pub authority: Signer<'info>,
         #[account(
@@ -395,23 +1958,45 @@ pub mod mica_eur {
             mut,
             seeds = [BLACKLIST_SEED, user.key().as_ref()],
             bump,
+            // Entries created before legal references existed grow to hold one
+            realloc = 8 + crate::aml::BlacklistEntry::INIT_SPACE,
+            realloc::payer = authority,
+            realloc::zero = false,
         )]
         pub blacklist_entry: Account<'info, crate::aml::BlacklistEntry>,
         pub system_program: Program<'info, System>,
+        /// KYC record of the user, whose country must fall under the authority's
+        /// jurisdiction; users without one can only be handled by global authorities
+        #[account(
+            seeds = [KYC_USER_SEED, user.key().as_ref()],
+            bump,
+        )]
+        pub kyc_user: Option<Account<'info, KycUser>>,
     }
 
     // Context for updating AML authority powers
     #[derive(Accounts)]
     pub struct UpdateAmlAuthorityPowers<'info> {
-        #[account(mut)]
-        pub issuer: Signer<'info>,
+        /// The issuer, or the mint's registered regulator
+        pub approver: Signer<'info>,
         #[account(
             mut,
             seeds = [AML_AUTHORITY_SEED, aml_authority.authority.as_ref()],
             bump,
         )]
         pub aml_authority: Account<'info, crate::aml::AmlAuthority>,
-        pub system_program: Program<'info, System>,
+        #[account(
+            seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+            bump,
+            constraint = crate::aml::is_aml_admin(&mint_info, regulator_registry.as_deref(), approver.key)
+                @ MicaEurError::UnauthorizedAmlAdmin,
+        )]
+        pub mint_info: Account<'info, MintInfo>,
+        #[account(
+            seeds = [REGULATOR_REGISTRY_SEED, mint_info.mint.as_ref()],
+            bump,
+        )]
+        pub regulator_registry: Option<Account<'info, crate::aml::RegulatorRegistry>>,
     }
 }
 
@@ -420,17 +2005,173 @@ pub struct Initialize {}
 
 /// MintInfo account to store additional metadata about the EUR token
 #[account]
+#[derive(InitSpace)]
 pub struct MintInfo {
     pub mint: Pubkey,                 // The mint address
     pub issuer: Pubkey,               // Who can mint new tokens
     pub freeze_authority: Pubkey,     // Who can freeze accounts
     pub permanent_delegate: Pubkey,   // Who can seize tokens
+    #[max_len(MAX_WHITEPAPER_URI_LEN)]
     pub whitepaper_uri: String,       // URI to the whitepaper
     pub is_active: bool,              // Whether the token is active
     pub creation_time: i64,           // When the token was created
     pub reserve_merkle_root: [u8; 32],// Merkle root of the reserve proof
+    #[max_len(MAX_IPFS_CID_LEN)]
     pub reserve_ipfs_cid: String,     // IPFS CID of the reserve proof
     pub last_reserve_update: i64,     // When the reserve was last updated
+    pub rule_set_version: u32,        // Version of the active compliance rule set
+    pub rule_set_hash: [u8; 32],      // Hash of the active compliance rule set
+    pub paused: bool,                 // Whether all token activity is halted
+    pub pauser: Pubkey,               // Who besides the issuer can pause and resume
+    pub approval_set: Pubkey,         // N-of-M set gating regulatory actions, default if none
+    pub reserve_total_eur: u64,       // Reserve reported with the latest proof, in token units
+    pub coverage_auto_pause: bool,    // Whether a reserve shortfall halts minting
+    pub minting_halted: bool,         // Minting halted by a reserve shortfall
+    pub transfer_fee_bps: u16,        // Transfer fee in basis points, 0 if none
+    pub max_transfer_fee: u64,        // Cap on the fee of a single transfer, in token units
+    pub fee_treasury: Pubkey,         // Token account withheld fees are withdrawn to
+    pub confidential_transfers: bool, // Whether holders may use confidential balances
+    pub confidential_auditor: Pubkey, // AML authority holding the auditor ElGamal key
+    pub auditor_elgamal_pubkey: [u8; 32], // Key confidential amounts are also encrypted to
+    pub max_supply: u64,              // Cap on the supply in token units, 0 for none
+    pub pending_max_supply: u64,      // Raised cap waiting out SUPPLY_CAP_RAISE_DELAY
+    pub supply_cap_effective_at: i64, // When the pending cap may be applied, 0 if none
+    pub issuance_policy: Pubkey,      // Issuance schedule mints count against, default if none
+    pub timelock: Pubkey,             // Timelock sensitive changes are queued on, default if none
+    pub whitepaper_version: u32,      // Revision of the current whitepaper, 0 for the initial one
+    pub decimals: u8,                 // Decimals of the mint, one of SUPPORTED_EUR_DECIMALS
+    pub require_associated_accounts: bool, // Whether mints only go to the recipient's associated token account
+    pub holder_count: u64,            // Holder records of open token accounts
+    pub migrated_to: Pubkey,          // Mint holders were migrated to, default if none
+    pub wind_down_started_at: i64,    // When orderly redemption began, 0 if not winding down
+    pub redemption_deadline: i64,     // End of the redemption plan, 0 if not winding down
+    pub redemption_account: Pubkey,   // Issuer account holders return tokens to when winding down
+    pub total_minted: u128,           // Tokens ever minted by the program, in token units
+    pub total_burned: u128,           // Tokens ever burned by the program, in token units
+    pub reserve_epoch_count: u64,     // Reserve proofs ever published, the epoch of the next one
+    pub max_reserve_age_seconds: i64, // Oldest reserve proof minting accepts, 0 for no limit
+    pub reserve_valuation: Pubkey,    // Valuation config reserve proofs are checked against, default if none
+    pub relayer_registry: Pubkey,     // Registry relayers must be active in, default if anyone may relay
+}
+
+/// Emitted when a EUR mint is created
+#[event]
+pub struct EuroMintInitialized {
+    pub mint: Pubkey,
+    pub issuer: Pubkey,
+    pub freeze_authority: Pubkey,
+    pub permanent_delegate: Pubkey,
+    pub rule_set_version: u32,
+    pub decimals: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when tokens are minted against received fiat
+#[event]
+pub struct TokensMinted {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub recipient: Pubkey,            // Wallet owning the token account
+    pub amount: u64,
+    pub issuer: Pubkey,
+    pub operation_id: [u8; 16],       // Zero for mints executed through an approval proposal
+    pub timestamp: i64,
+}
+
+/// Emitted when a holder burns tokens to redeem them
+#[event]
+pub struct TokensBurned {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub operation_id: [u8; 16],
+    pub timestamp: i64,
+}
+
+/// Emitted when a token account is frozen by regulatory action
+#[event]
+pub struct AccountFrozen {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a frozen token account is thawed
+#[event]
+pub struct AccountThawed {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the permanent delegate seizes tokens
+#[event]
+pub struct TokensSeized {
+    pub mint: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub permanent_delegate: Pubkey,
+    pub operation_id: [u8; 16],       // Zero for seizures executed through an approval proposal
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer publishes a new reserve proof
+#[event]
+pub struct ReserveProofUpdated {
+    pub mint: Pubkey,
+    pub epoch: u64,                   // Epoch the proof is archived under
+    pub merkle_root: [u8; 32],
+    pub ipfs_cid: String,
+    pub reserve_total_eur: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer hands the pause role to another key
+#[event]
+pub struct PauserChanged {
+    pub mint: Pubkey,
+    pub old_pauser: Pubkey,
+    pub new_pauser: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer changes whether mints need associated token accounts
+#[event]
+pub struct AssociatedAccountRequirementChanged {
+    pub mint: Pubkey,
+    pub required: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when token activity is halted or resumed
+#[event]
+pub struct TokenPauseChanged {
+    pub mint: Pubkey,
+    pub paused: bool,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the mint is deactivated for wind-down or reactivated
+#[event]
+pub struct MintActivationChanged {
+    pub mint: Pubkey,
+    pub is_active: bool,
+    pub by: Pubkey,                   // Issuer, or proposer of the timelock operation
+    pub timestamp: i64,
+}
+
+/// Emitted when a new compliance rule set becomes active for a mint
+#[event]
+pub struct RuleSetActivated {
+    pub mint: Pubkey,
+    pub rule_set_version: u32,
+    pub rule_set_hash: [u8; 32],
+    pub timestamp: i64,
 }
 
 #[derive(Accounts)]
@@ -443,11 +2184,11 @@ pub struct InitializeEuroMint<'info> {
         payer = issuer,
         seeds = [MINT_INFO_SEED, mint.key().as_ref()],
         bump,
-        space = 8 + std::mem::size_of::<MintInfo>() + 256, // Extra space for strings
+        space = 8 + MintInfo::INIT_SPACE,
     )]
     pub mint_info: Account<'info, MintInfo>,
     
-    /// The mint account for the EUR token
+    /// The mint account for the EUR token, created by the instruction
     #[account(mut)]
     pub mint: Signer<'info>,
     
@@ -458,94 +2199,185 @@ pub struct InitializeEuroMint<'info> {
     /// The permanent delegate for the token
     /// CHECK: This is just a public key, not an account we're accessing
     pub permanent_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: Token-2022 permanent delegate of the mint; address checked against its seeds
+    #[account(
+        seeds = [SEIZURE_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub seizure_authority: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
     
     /// Token program: must be Token-2022
-    #[account(constraint = token_program.key == &TOKEN_2022_ID)]
+    #[account(address = TOKEN_2022_ID)]
     pub token_program: Program<'info, token_2022::Token2022>,
     
     pub rent: Sysvar<'info, Rent>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+        space = 8 + InvocationGuard::INIT_SPACE,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
 }
 
-#[derive(Accounts)]
-pub struct CreateTokenAccount<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    #[account(mut)]
-    /// CHECK: Validated by token_program
-    pub token_account: UncheckedAccount<'info>,
-    
-    /// CHECK: Validated by token_program
-    pub mint: UncheckedAccount<'info>,
-    
+#[derive(Accounts)]
+#[instruction(amount: u64, operation_id: [u8; 16])]
+pub struct MintTokens<'info> {
+    /// The issuer or another holder of the MINTER role
+    #[account(
+        mut,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+        constraint = !mint_info.minting_halted @ MicaEurError::MintingHalted,
+        constraint = has_mint_role(&mint_info, roles::MINTER, issuer.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+    
+    /// The mint account for the EUR token
+    #[account(mut)]
+    /// CHECK: Validated by token_program
+    pub mint: UncheckedAccount<'info>,
+    
+    /// The token account to mint to
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = token_account.owner == kyc_user.user @ MicaEurError::TokenAccountOwnerMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The KYC user account (must be verified)
+    #[account(
+        seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+    
+    /// Token program: must be Token-2022
+    #[account(address = TOKEN_2022_ID)]
+    pub token_program: Program<'info, token_2022::Token2022>,
+    
+    /// Receipt for the client-supplied operation ID, rejects reuse
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            OPERATION_RECEIPT_SEED,
+            mint.key().as_ref(),
+            &[OperationKind::Mint as u8],
+            issuer.key().as_ref(),
+            &operation_id,
+        ],
+        bump,
+        space = 8 + OperationReceipt::INIT_SPACE,
+    )]
+    pub operation_receipt: Account<'info, OperationReceipt>,
+    
+    pub system_program: Program<'info, System>,
+
+    /// Blacklist entry of the recipient
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// Approval set of the mint, required once one is configured
+    pub approval_set: Option<Account<'info, ApprovalSet>>,
+
+    /// Rolling mint and burn volumes of the recipient
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [USER_LIMITS_SEED, kyc_user.user.as_ref()],
+        bump,
+        space = 8 + UserLimits::INIT_SPACE,
+    )]
+    pub user_limits: Account<'info, UserLimits>,
+
+    /// Tier limits of the mint, the defaults apply if none are configured
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub limit_config: Option<Account<'info, LimitConfig>>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// The signer's role assignment, unless it is the issuer
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), issuer.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// The signer's allowance, required unless it is the issuer
     #[account(
-        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        mut,
+        seeds = [MINTER_ALLOWANCE_SEED, mint.key().as_ref(), issuer.key().as_ref()],
         bump,
     )]
-    pub mint_info: Account<'info, MintInfo>,
-    
-    pub system_program: Program<'info, System>,
-    
-    /// Token program: must be Token-2022
-    #[account(constraint = token_program.key == &TOKEN_2022_ID)]
-    pub token_program: Program<'info, token_2022::Token2022>,
-    
-    pub rent: Sysvar<'info, Rent>,
-}
+    pub minter_allowance: Option<Account<'info, MinterAllowance>>,
 
-#[derive(Accounts)]
-pub struct MintTokens<'info> {
+    /// Issuance schedule of the mint, required once one is configured
     #[account(
         mut,
-        constraint = mint_info.issuer == issuer.key(),
-        constraint = mint_info.is_active,
+        seeds = [ISSUANCE_POLICY_SEED, mint.key().as_ref()],
+        bump,
     )]
-    pub issuer: Signer<'info>,
-    
+    pub issuance_policy: Option<Account<'info, IssuancePolicy>>,
+
+    /// Policy of the user's country
     #[account(
-        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        seeds = [COUNTRY_POLICY_SEED, kyc_user.country_code.as_bytes()],
         bump,
-        constraint = mint_info.mint == mint.key(),
     )]
-    pub mint_info: Account<'info, MintInfo>,
-    
-    /// The mint account for the EUR token
-    #[account(mut)]
-    /// CHECK: Validated by token_program
-    pub mint: UncheckedAccount<'info>,
-    
-    /// The token account to mint to
-    #[account(mut)]
-    /// CHECK: Validated by token_program
-    pub token_account: UncheckedAccount<'info>,
+    pub country_policy: Account<'info, CountryPolicy>,
 
-    /// The KYC user account (must be verified)
-    pub kyc_user: Account<'info, KycUser>,
-    
-    /// The freeze authority for the token
+    /// Freeze record of the token account
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
     #[account(
-        constraint = freeze_authority.key() == mint_info.freeze_authority
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
     )]
-    /// CHECK: Only using for constraint
-    pub freeze_authority: UncheckedAccount<'info>,
-    
-    /// Token program: must be Token-2022
-    #[account(constraint = token_program.key == &TOKEN_2022_ID)]
-    pub token_program: Program<'info, token_2022::Token2022>,
+    pub freeze_record: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
+#[instruction(amount: u64, operation_id: [u8; 16])]
 pub struct BurnTokens<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    /// Active or winding down: a deactivated mint still redeems
     #[account(
+        mut,
         seeds = [MINT_INFO_SEED, mint.key().as_ref()],
         bump,
-        constraint = mint_info.mint == mint.key(),
-        constraint = mint_info.is_active,
+        has_one = mint,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
     )]
     pub mint_info: Account<'info, MintInfo>,
     
@@ -554,96 +2386,227 @@ pub struct BurnTokens<'info> {
     /// CHECK: Validated by token_program
     pub mint: UncheckedAccount<'info>,
     
-    /// The token account to burn from
-    #[account(mut)]
-    /// CHECK: Validated by token_program
-    pub token_account: UncheckedAccount<'info>,
+    /// The token account to burn from, held by the owner: a delegate cannot burn on
+    /// the holder's behalf, which would bypass the holder's blacklist and limits
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// Token program: must be Token-2022
-    #[account(constraint = token_program.key == &TOKEN_2022_ID)]
+    #[account(address = TOKEN_2022_ID)]
     pub token_program: Program<'info, token_2022::Token2022>,
+    
+    /// Receipt for the client-supplied operation ID, rejects reuse
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            OPERATION_RECEIPT_SEED,
+            mint.key().as_ref(),
+            &[OperationKind::Redemption as u8],
+            owner.key().as_ref(),
+            &operation_id,
+        ],
+        bump,
+        space = 8 + OperationReceipt::INIT_SPACE,
+    )]
+    pub operation_receipt: Account<'info, OperationReceipt>,
+    
+    pub system_program: Program<'info, System>,
+
+    /// Blacklist entry of the owner
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// The owner's KYC record, deciding the applicable limits
+    #[account(
+        seeds = [KYC_USER_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    /// Rolling mint and burn volumes of the owner
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [USER_LIMITS_SEED, owner.key().as_ref()],
+        bump,
+        space = 8 + UserLimits::INIT_SPACE,
+    )]
+    pub user_limits: Account<'info, UserLimits>,
+
+    /// Tier limits of the mint, the defaults apply if none are configured
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub limit_config: Option<Account<'info, LimitConfig>>,
+
+    /// Freeze record of the token account
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_record: UncheckedAccount<'info>,
+
+    /// Policy of the owner's country
+    #[account(
+        seeds = [COUNTRY_POLICY_SEED, kyc_user.country_code.as_bytes()],
+        bump,
+    )]
+    pub country_policy: Account<'info, CountryPolicy>,
 }
 
 #[derive(Accounts)]
 pub struct FreezeAccount<'info> {
-    #[account(
-        mut,
-        constraint = freeze_authority.key() == mint_info.freeze_authority
-    )]
+    /// The freeze authority or another holder of the FREEZER role
+    #[account(mut)]
     pub freeze_authority: Signer<'info>,
     
     #[account(
         seeds = [MINT_INFO_SEED, mint.key().as_ref()],
         bump,
-        constraint = mint_info.mint == mint.key(),
+        has_one = mint,
         constraint = mint_info.is_active,
+        constraint = mint_info.approval_set == Pubkey::default() @ MicaEurError::MultisigApprovalRequired,
+        constraint = has_mint_role(&mint_info, roles::FREEZER, freeze_authority.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
     )]
     pub mint_info: Account<'info, MintInfo>,
     
     /// The mint account for the EUR token
-    #[account(mut)]
     /// CHECK: Validated by token_program
     pub mint: UncheckedAccount<'info>,
     
     /// The token account to freeze
-    #[account(mut)]
-    /// CHECK: Validated by token_program
-    pub token_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// Token program: must be Token-2022
-    #[account(constraint = token_program.key == &TOKEN_2022_ID)]
+    #[account(address = TOKEN_2022_ID)]
     pub token_program: Program<'info, token_2022::Token2022>,
-}
 
-#[derive(Accounts)]
-pub struct ThawAccount<'info> {
+    /// Reason, authority and scope of the freeze
+    #[account(
+        init_if_needed,
+        payer = freeze_authority,
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+        space = 8 + FreezeRecord::INIT_SPACE,
+    )]
+    pub freeze_record: Account<'info, FreezeRecord>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The signer's role assignment, unless it is the freeze authority
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), freeze_authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// The owner's compliance history, recorded into once opened
     #[account(
         mut,
-        constraint = freeze_authority.key() == mint_info.freeze_authority
+        seeds = [COMPLIANCE_HISTORY_SEED, token_account.owner.as_ref()],
+        bump,
     )]
+    pub compliance_history: Option<Account<'info, ComplianceHistory>>,
+}
+
+#[derive(Accounts)]
+pub struct ThawAccount<'info> {
+    /// The freeze authority or another holder of the FREEZER role
     pub freeze_authority: Signer<'info>,
     
     #[account(
         seeds = [MINT_INFO_SEED, mint.key().as_ref()],
         bump,
-        constraint = mint_info.mint == mint.key(),
+        has_one = mint,
         constraint = mint_info.is_active,
+        constraint = mint_info.approval_set == Pubkey::default() @ MicaEurError::MultisigApprovalRequired,
+        constraint = has_mint_role(&mint_info, roles::FREEZER, freeze_authority.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
     )]
     pub mint_info: Account<'info, MintInfo>,
     
     /// The mint account for the EUR token
-    #[account(mut)]
     /// CHECK: Validated by token_program
     pub mint: UncheckedAccount<'info>,
     
     /// The token account to thaw
-    #[account(mut)]
-    /// CHECK: Validated by token_program
-    pub token_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// Token program: must be Token-2022
-    #[account(constraint = token_program.key == &TOKEN_2022_ID)]
+    #[account(address = TOKEN_2022_ID)]
     pub token_program: Program<'info, token_2022::Token2022>,
-}
 
-#[derive(Accounts)]
-pub struct SeizeTokens<'info> {
+    /// The account's freeze record, if it has one
+    #[account(
+        mut,
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_record: Option<Account<'info, FreezeRecord>>,
+
+    /// The signer's role assignment, unless it is the freeze authority
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), freeze_authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// The owner's compliance history, recorded into once opened
     #[account(
         mut,
-        constraint = permanent_delegate.key() == mint_info.permanent_delegate
+        seeds = [COMPLIANCE_HISTORY_SEED, token_account.owner.as_ref()],
+        bump,
     )]
+    pub compliance_history: Option<Account<'info, ComplianceHistory>>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, operation_id: [u8; 16])]
+pub struct SeizeTokens<'info> {
+    /// The permanent delegate or another holder of the SEIZER role
+    #[account(mut)]
     pub permanent_delegate: Signer<'info>,
     
     #[account(
         seeds = [MINT_INFO_SEED, mint.key().as_ref()],
         bump,
-        constraint = mint_info.mint == mint.key(),
+        has_one = mint,
         constraint = mint_info.is_active,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+        constraint = mint_info.approval_set == Pubkey::default() @ MicaEurError::MultisigApprovalRequired,
+        constraint = has_mint_role(&mint_info, roles::SEIZER, permanent_delegate.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
     )]
     pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: Permanent delegate of the mint; address checked against its seeds
+    #[account(
+        seeds = [SEIZURE_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub seizure_authority: UncheckedAccount<'info>,
     
     /// The mint account for the EUR token
-    #[account(mut)]
     /// CHECK: Validated by token_program
     pub mint: UncheckedAccount<'info>,
     
@@ -658,16 +2621,101 @@ pub struct SeizeTokens<'info> {
     pub to_account: UncheckedAccount<'info>,
     
     /// Token program: must be Token-2022
-    #[account(constraint = token_program.key == &TOKEN_2022_ID)]
+    #[account(address = TOKEN_2022_ID)]
     pub token_program: Program<'info, token_2022::Token2022>,
+    
+    /// Receipt for the client-supplied operation ID, rejects reuse
+    #[account(
+        init,
+        payer = permanent_delegate,
+        seeds = [
+            OPERATION_RECEIPT_SEED,
+            mint.key().as_ref(),
+            &[OperationKind::Seizure as u8],
+            permanent_delegate.key().as_ref(),
+            &operation_id,
+        ],
+        bump,
+        space = 8 + OperationReceipt::INIT_SPACE,
+    )]
+    pub operation_receipt: Account<'info, OperationReceipt>,
+    
+    pub system_program: Program<'info, System>,
+
+    /// The signer's role assignment, unless it is the permanent delegate
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), permanent_delegate.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// The owner's compliance history, recorded into once opened
+    #[account(
+        mut,
+        seeds = [COMPLIANCE_HISTORY_SEED, compliance_history.user.as_ref()],
+        bump,
+    )]
+    pub compliance_history: Option<Account<'info, ComplianceHistory>>,
 }
 
 #[derive(Accounts)]
 pub struct UpdateReserveProof<'info> {
+    /// The issuer or another holder of the RESERVE_PUBLISHER role
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
     #[account(
         mut,
-        constraint = issuer.key() == mint_info.issuer
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        constraint = mint_info.is_active,
+        constraint = has_mint_role(&mint_info, roles::RESERVE_PUBLISHER, issuer.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The signer's role assignment, unless it is the issuer
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), issuer.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Breakdown of the reserve by asset category, replaced with every proof
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [RESERVE_COMPOSITION_SEED, mint.key().as_ref()],
+        bump,
+        space = 8 + ReserveComposition::INIT_SPACE,
+    )]
+    pub reserve_composition: Box<Account<'info, ReserveComposition>>,
+
+    /// Archive of this proof, under the mint's next epoch
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [RESERVE_PROOF_EPOCH_SEED, mint.key().as_ref(), &mint_info.reserve_epoch_count.to_le_bytes()],
+        bump,
+        space = 8 + ReserveProofEpoch::INIT_SPACE,
+    )]
+    pub reserve_proof_epoch: Account<'info, ReserveProofEpoch>,
+
+    /// Valuation config of the mint, required once one is configured
+    #[account(
+        seeds = [RESERVE_VALUATION_SEED, mint.key().as_ref()],
+        bump,
     )]
+    pub valuation_config: Option<Box<Account<'info, ReserveValuationConfig>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncRuleSet<'info> {
     pub issuer: Signer<'info>,
     
     #[account(
@@ -675,6 +2723,114 @@ pub struct UpdateReserveProof<'info> {
         seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
         bump,
         constraint = mint_info.is_active,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+}
+
+#[derive(Accounts)]
+pub struct SetTokenPaused<'info> {
+    /// The issuer, the pauser or another holder of the PAUSER role
+    #[account(
+        constraint = has_mint_role(&mint_info, roles::PAUSER, authority.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// The signer's role assignment, if it holds PAUSER through one
+    #[account(
+        seeds = [ROLE_SEED, mint_info.mint.as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintActive<'info> {
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+}
+
+#[derive(Accounts)]
+pub struct SetPauser<'info> {
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+}
+
+#[derive(Accounts)]
+pub struct SetAssociatedAccountRequirement<'info> {
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
     )]
     pub mint_info: Account<'info, MintInfo>,
-} 
\ No newline at end of file
+}
+
+// Deactivate or reactivate a mint. A mint retired by a migration or winding down
+// stays inactive.
+fn set_mint_active(mint_info: &mut MintInfo, is_active: bool, by: Pubkey) -> Result<()> {
+    if mint_info.is_active == is_active {
+        return Err(if is_active { MicaEurError::MintAlreadyActive } else { MicaEurError::MintInactive }.into());
+    }
+    if is_active && mint_info.migrated_to != Pubkey::default() {
+        return Err(MicaEurError::MintMigrated.into());
+    }
+    if is_active && mint_info.wind_down_started_at != 0 {
+        return Err(MicaEurError::MintWindingDown.into());
+    }
+    mint_info.is_active = is_active;
+
+    emit!(MintActivationChanged {
+        mint: mint_info.mint,
+        is_active,
+        by,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Mint {} {}", mint_info.mint, if is_active { "reactivated" } else { "deactivated" });
+    Ok(())
+}
+
+// Flip the pause flag of a mint
+fn set_token_paused(ctx: Context<SetTokenPaused>, paused: bool) -> Result<()> {
+    let mint_info = &mut ctx.accounts.mint_info;
+    if mint_info.paused == paused {
+        return Err(MicaEurError::PauseStateUnchanged.into());
+    }
+    mint_info.paused = paused;
+
+    emit!(TokenPauseChanged {
+        mint: mint_info.mint,
+        paused,
+        by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Token {} {}", mint_info.mint, if paused { "paused" } else { "resumed" });
+    Ok(())
+}
\ No newline at end of file
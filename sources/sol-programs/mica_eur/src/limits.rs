@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Single operation and 24 hour volume caps of one verification level, in token
+/// units
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct TierLimits {
+    pub per_transaction: u64,
+    pub daily_mint: u64,
+    pub daily_burn: u64,
+    pub daily_transfer: u64,
+}
+
+/// Caps applied per verification level (0-3) until the issuer configures its own,
+/// in whole EUR
+pub const DEFAULT_TIER_LIMITS: [TierLimits; 4] = [
+    TierLimits { per_transaction: 0, daily_mint: 0, daily_burn: 0, daily_transfer: 0 },
+    TierLimits { per_transaction: 1_000, daily_mint: 0, daily_burn: 1_000, daily_transfer: 1_000 },
+    TierLimits {
+        per_transaction: RETAIL_MAX_TRANSACTION_EUR,
+        daily_mint: 15_000,
+        daily_burn: 15_000,
+        daily_transfer: 15_000,
+    },
+    TierLimits {
+        per_transaction: MAX_TRANSACTION_EUR,
+        daily_mint: 1_000_000,
+        daily_burn: 1_000_000,
+        daily_transfer: 1_000_000,
+    },
+];
+
+impl TierLimits {
+    /// Reject a single mint, burn or transfer above the tier's cap
+    pub fn check_transaction(&self, amount: u64) -> Result<()> {
+        if amount > self.per_transaction {
+            return Err(MicaEurError::TransactionAmountExceedsMaximum.into());
+        }
+        Ok(())
+    }
+}
+
+/// Volume moved within the current 24 hour window. The window opens with the first
+/// operation after the previous one closed. The volume is accumulated in `u128`, so
+/// any sequence of `u64` amounts is counted against the limit without overflowing.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct RollingVolume {
+    pub window_start: i64,
+    pub volume: u128,
+}
+
+impl RollingVolume {
+    /// Volume still counted in the window at `now`
+    pub fn current(&self, now: i64) -> u128 {
+        if now >= self.window_start.saturating_add(DAILY_LIMIT_WINDOW) {
+            return 0;
+        }
+        self.volume
+    }
+
+    /// Add `amount` to the window, rejecting it with `error` if `limit` would be exceeded
+    pub fn record(&mut self, amount: u64, limit: u64, now: i64, error: MicaEurError) -> Result<()> {
+        if now >= self.window_start.saturating_add(DAILY_LIMIT_WINDOW) {
+            self.window_start = now;
+            self.volume = 0;
+        }
+        let volume = self.volume.checked_add(amount as u128).ok_or(MicaEurError::ArithmeticOverflow)?;
+        if volume > limit as u128 {
+            return Err(error.into());
+        }
+        self.volume = volume;
+        Ok(())
+    }
+}
+
+/// Issuer-configured tier limits of a mint
+#[account]
+#[derive(InitSpace)]
+pub struct LimitConfig {
+    pub mint: Pubkey,                 // Mint the limits apply to
+    pub tiers: [TierLimits; 4],       // Limits indexed by verification level
+    pub update_time: i64,             // When the limits last changed
+}
+
+/// Rolling mint and burn volumes of a KYC user. Transfer volume is tracked by the
+/// transfer hook program on its `TransferRecord`, as the hook cannot write
+/// accounts owned by this program.
+#[account]
+#[derive(InitSpace)]
+pub struct UserLimits {
+    pub user: Pubkey,                 // Wallet the volumes belong to
+    pub minted: RollingVolume,        // Minted to the user in the current window
+    pub burned: RollingVolume,        // Burned by the user in the current window
+}
+
+/// Emitted when the issuer changes the tier limits of a mint
+#[event]
+pub struct LimitsConfigured {
+    pub mint: Pubkey,
+    pub tiers: [TierLimits; 4],
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct SetLimits<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [LIMIT_CONFIG_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + LimitConfig::INIT_SPACE,
+    )]
+    pub limit_config: Account<'info, LimitConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Limits for a verification level, from the mint's configuration if it has one.
+/// The defaults are scaled to the mint's `decimals`; configured limits are already
+/// in token units.
+pub fn tier_limits(config: Option<&LimitConfig>, verification_level: u8, decimals: u8) -> TierLimits {
+    let level = verification_level as usize;
+    match config {
+        Some(config) => config.tiers[level.min(config.tiers.len() - 1)],
+        None => {
+            let tier = DEFAULT_TIER_LIMITS[level.min(DEFAULT_TIER_LIMITS.len() - 1)];
+            TierLimits {
+                per_transaction: eur_to_token_units(tier.per_transaction, decimals),
+                daily_mint: eur_to_token_units(tier.daily_mint, decimals),
+                daily_burn: eur_to_token_units(tier.daily_burn, decimals),
+                daily_transfer: eur_to_token_units(tier.daily_transfer, decimals),
+            }
+        }
+    }
+}
+
+/// Whole EUR in token units of a mint with `decimals`, saturating at `u64::MAX`
+pub fn eur_to_token_units(eur: u64, decimals: u8) -> u64 {
+    eur.saturating_mul(10u64.saturating_pow(decimals as u32))
+}
+
+/// Reject tiers that cannot all be applied as configured: a single operation cap
+/// above one of the tier's daily caps, or a tier with a lower limit than the tier
+/// below it. A daily cap of zero disables the operation for the tier, so the
+/// single operation cap is not compared with it.
+pub fn check_tier_limits(tiers: &[TierLimits; 4]) -> Result<()> {
+    for tier in tiers {
+        let daily_caps = [tier.daily_mint, tier.daily_burn, tier.daily_transfer];
+        if daily_caps.iter().any(|&cap| cap != 0 && tier.per_transaction > cap) {
+            return Err(MicaEurError::InvalidTierLimits.into());
+        }
+    }
+    for pair in tiers.windows(2) {
+        let (lower, higher) = (pair[0], pair[1]);
+        if higher.per_transaction < lower.per_transaction
+            || higher.daily_mint < lower.daily_mint
+            || higher.daily_burn < lower.daily_burn
+            || higher.daily_transfer < lower.daily_transfer
+        {
+            return Err(MicaEurError::InvalidTierLimits.into());
+        }
+    }
+    Ok(())
+}
+
+// Set the tier limits of a mint (issuer only)
+pub(crate) fn set_limits(ctx: Context<SetLimits>, tiers: [TierLimits; 4]) -> Result<()> {
+    check_tier_limits(&tiers)?;
+
+    let limit_config = &mut ctx.accounts.limit_config;
+    limit_config.mint = ctx.accounts.mint_info.mint;
+    limit_config.tiers = tiers;
+    limit_config.update_time = Clock::get()?.unix_timestamp;
+
+    emit!(LimitsConfigured {
+        mint: limit_config.mint,
+        tiers,
+        timestamp: limit_config.update_time,
+    });
+    Ok(())
+}
+
+/// Count a mint against the recipient's transaction and daily mint limits
+pub fn record_mint_volume(
+    user_limits: &mut UserLimits,
+    user: Pubkey,
+    limits: TierLimits,
+    amount: u64,
+) -> Result<()> {
+    limits.check_transaction(amount)?;
+    user_limits.user = user;
+    user_limits.minted.record(
+        amount,
+        limits.daily_mint,
+        Clock::get()?.unix_timestamp,
+        MicaEurError::DailyMintLimitExceeded,
+    )
+}
+
+/// Count a burn against the owner's transaction and daily burn limits
+pub fn record_burn_volume(
+    user_limits: &mut UserLimits,
+    user: Pubkey,
+    limits: TierLimits,
+    amount: u64,
+) -> Result<()> {
+    limits.check_transaction(amount)?;
+    user_limits.user = user;
+    user_limits.burned.record(
+        amount,
+        limits.daily_burn,
+        Clock::get()?.unix_timestamp,
+        MicaEurError::DailyBurnLimitExceeded,
+    )
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash;
+
+use crate::codec::{encode_reserve_leaf, encode_snapshot_leaf};
+
+/// Prefix of hashed leaves, keeping them apart from internal nodes
+pub const MERKLE_LEAF_PREFIX: u8 = 0x00;
+/// Prefix of hashed internal nodes
+pub const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+/// Hash an encoded artifact into a leaf
+pub fn hash_leaf(encoding: &[u8]) -> [u8; 32] {
+    hash::hashv(&[&[MERKLE_LEAF_PREFIX], encoding]).to_bytes()
+}
+
+/// Hash two children into their parent. The pair is sorted first, so proofs need
+/// no left/right flags.
+pub fn hash_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    hash::hashv(&[&[MERKLE_NODE_PREFIX], low, high]).to_bytes()
+}
+
+/// Every level of the tree over `leaves`, leaves first and root last. An unpaired
+/// last node moves up unchanged.
+pub fn merkle_levels(leaves: &[[u8; 32]]) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves.to_vec()];
+    while levels.last().is_some_and(|level| level.len() > 1) {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_node(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Calculate a Merkle root from a set of leaves, all zeros for none
+pub fn calculate_merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    merkle_levels(leaves)
+        .last()
+        .and_then(|level| level.first().copied())
+        .unwrap_or([0; 32])
+}
+
+/// Proof of the leaf at `index` in a tree built by `merkle_levels`
+pub fn merkle_proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    for level in &levels[..levels.len().saturating_sub(1)] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    proof
+}
+
+/// Verify a Merkle proof against a root
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: &[u8; 32]) -> bool {
+    let computed = proof.iter().fold(*leaf, |current, sibling| hash_node(&current, sibling));
+    &computed == root
+}
+
+/// Create a leaf for the reserve Merkle tree
+pub fn create_reserve_leaf(
+    deposit_id: &str,
+    amount: u64,
+    timestamp: i64,
+) -> [u8; 32] {
+    hash_leaf(&encode_reserve_leaf(deposit_id, amount, timestamp))
+}
+
+/// Create a leaf for a holder-balance snapshot tree
+pub fn create_snapshot_leaf(owner: &Pubkey, balance: u64) -> [u8; 32] {
+    hash_leaf(&encode_snapshot_leaf(owner, balance))
+}
@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use anchor_spl::token_2022_extensions::{
+    token_metadata_initialize, token_metadata_update_field, TokenMetadataInitialize,
+    TokenMetadataUpdateField,
+};
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Keys of the custom fields of the token metadata
+pub mod metadata_keys {
+    /// Legal Entity Identifier of the issuer
+    pub const LEI: &str = "lei";
+    /// Authorisation of the issuer by its national competent authority
+    pub const REGULATOR_LICENSE_ID: &str = "regulator_license_id";
+}
+
+/// Token metadata field the issuer can set. The symbol stays `TOKEN_SYMBOL`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum MetadataField {
+    Name,
+    Uri,
+    /// Custom field, see `metadata_keys`
+    Key(String),
+}
+
+/// Emitted when a token metadata field is written
+#[event]
+pub struct TokenMetadataUpdated {
+    pub mint: Pubkey,
+    pub field: MetadataField,
+    pub value: String,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct WriteTokenMetadata<'info> {
+    /// Pays the rent of the grown mint account
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// The mint account for the EUR token, holding its own metadata
+    /// CHECK: Validated by token_program
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Metadata currently stored on the mint, `None` before it is initialized
+fn stored_metadata(mint: &AccountInfo) -> Result<Option<TokenMetadata>> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(state.get_variable_len_extension::<TokenMetadata>().ok())
+}
+
+// Top up the mint's lamports so it stays rent-exempt once its metadata goes from
+// `current` to `updated`. Token-2022 reallocates the mint but leaves funding it to
+// the caller.
+fn fund_metadata<'info>(
+    accounts: &WriteTokenMetadata<'info>,
+    current: Option<&TokenMetadata>,
+    updated: &TokenMetadata,
+) -> Result<()> {
+    let mint = accounts.mint.to_account_info();
+    let current_len = current.map(TokenMetadata::tlv_size_of).transpose()?.unwrap_or(0);
+    let len = (mint.data_len() + updated.tlv_size_of()?).saturating_sub(current_len);
+    let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(mint.lamports());
+    if shortfall == 0 {
+        return Ok(());
+    }
+    transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.issuer.to_account_info(),
+                to: mint,
+            },
+        ),
+        shortfall,
+    )
+}
+
+fn check_metadata_value(value: &str) -> Result<()> {
+    if value.is_empty() || value.len() > MAX_METADATA_VALUE_LEN {
+        return Err(MicaEurError::InvalidMetadataField.into());
+    }
+    Ok(())
+}
+
+// Write the name, `TOKEN_SYMBOL` and URI to the mint's TokenMetadata extension
+// (issuer only). The mint info PDA becomes the metadata's update authority, so
+// later changes go through `update_metadata_field`.
+pub(crate) fn initialize_token_metadata(ctx: Context<WriteTokenMetadata>, name: String, uri: String) -> Result<()> {
+    check_metadata_value(&name)?;
+    check_metadata_value(&uri)?;
+    if stored_metadata(&ctx.accounts.mint)?.is_some() {
+        return Err(MicaEurError::InvalidMetadataField.into());
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey(ctx.accounts.mint_info.key()),
+        mint: mint_key,
+        name: name.clone(),
+        symbol: TOKEN_SYMBOL.to_string(),
+        uri: uri.clone(),
+        additional_metadata: Vec::new(),
+    };
+    fund_metadata(ctx.accounts, None, &metadata)?;
+
+    // The mint info PDA holds the mint authority
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: ctx.accounts.mint.to_account_info(),
+                update_authority: ctx.accounts.mint_info.to_account_info(),
+                mint_authority: ctx.accounts.mint_info.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer,
+        ),
+        name.clone(),
+        TOKEN_SYMBOL.to_string(),
+        uri.clone(),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    emit!(TokenMetadataUpdated {
+        mint: mint_key,
+        field: MetadataField::Name,
+        value: name,
+        timestamp: now,
+    });
+    emit!(TokenMetadataUpdated {
+        mint: mint_key,
+        field: MetadataField::Uri,
+        value: uri,
+        timestamp: now,
+    });
+
+    msg!("Token metadata of {} initialized", mint_key);
+    Ok(())
+}
+
+// Set the name, URI or a custom field such as the LEI of the mint's token metadata
+// (issuer only)
+pub(crate) fn update_metadata_field(ctx: Context<WriteTokenMetadata>, field: MetadataField, value: String) -> Result<()> {
+    check_metadata_value(&value)?;
+    let token_field = match &field {
+        MetadataField::Name => Field::Name,
+        MetadataField::Uri => Field::Uri,
+        MetadataField::Key(key) => {
+            check_metadata_value(key)?;
+            Field::Key(key.clone())
+        }
+    };
+
+    let current = stored_metadata(&ctx.accounts.mint)?.ok_or(MicaEurError::InvalidMetadataField)?;
+    let mut updated = current.clone();
+    updated.update(token_field.clone(), value.clone());
+    fund_metadata(ctx.accounts, Some(&current), &updated)?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataUpdateField {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: ctx.accounts.mint.to_account_info(),
+                update_authority: ctx.accounts.mint_info.to_account_info(),
+            },
+            signer,
+        ),
+        token_field,
+        value.clone(),
+    )?;
+
+    emit!(TokenMetadataUpdated {
+        mint: mint_key,
+        field,
+        value,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Token metadata field of {} updated", mint_key);
+    Ok(())
+}
@@ -0,0 +1,360 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::aml::BlacklistEntry;
+use crate::batch_mint::load_if_initialized;
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::freezes::{check_incoming_freeze, FreezeRecord};
+use crate::kyc_oracle::KycUser;
+use crate::mint_utils::{burn_checked, check_mint_extensions, check_token_account_extensions, mint_to_checked};
+use crate::policy::check_mint_recipient;
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::supply_accounting::{record_burned, record_minted};
+use crate::MintInfo;
+
+/// Migration of every holder of an old mint to a new mint, used when an
+/// extension chosen at launch (which Token-2022 makes immutable) must change
+#[account]
+#[derive(InitSpace)]
+pub struct Migration {
+    pub old_mint: Pubkey,             // Mint being retired
+    pub new_mint: Pubkey,             // Mint holders move to
+    pub issuer: Pubkey,               // Issuer of both mints
+    pub is_open: bool,                // Whether holders can still claim
+    pub holder_count: u64,            // Number of token accounts migrated
+    pub migrated_amount: u64,         // Total amount burnt and reminted
+    pub start_time: i64,              // When the old mint was frozen
+    pub end_time: i64,                // When claims were closed (0 while open)
+}
+
+/// Per-holder record of a completed migration; its existence prevents double claims
+#[account]
+#[derive(InitSpace)]
+pub struct MigrationRecord {
+    pub holder: Pubkey,               // Owner of both token accounts
+    pub old_token_account: Pubkey,    // Token account burnt under the old mint
+    pub new_token_account: Pubkey,    // Token account credited under the new mint
+    pub amount: u64,                  // Amount migrated
+    pub claim_time: i64,              // When the holder claimed
+}
+
+#[event]
+pub struct MigrationStarted {
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderMigrated {
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationCompleted {
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub holder_count: u64,
+    pub migrated_amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct StartMigration<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, old_mint_info.mint.as_ref()],
+        bump,
+        constraint = old_mint_info.is_active @ MicaEurError::MintInactive,
+        has_one = issuer,
+    )]
+    pub old_mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, new_mint_info.mint.as_ref()],
+        bump,
+        constraint = new_mint_info.is_active @ MicaEurError::MigrationTargetInactive,
+        constraint = new_mint_info.issuer == issuer.key() @ MicaEurError::MigrationIssuerMismatch,
+        constraint = new_mint_info.mint != old_mint_info.mint @ MicaEurError::InvalidMigrationTarget,
+    )]
+    pub new_mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [MIGRATION_SEED, old_mint_info.mint.as_ref()],
+        bump,
+        space = 8 + Migration::INIT_SPACE,
+    )]
+    pub migration: Account<'info, Migration>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimMigration<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// Issuer running the migration, co-signing each claim
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MIGRATION_SEED, old_mint.key().as_ref()],
+        bump,
+        constraint = migration.is_open @ MicaEurError::MigrationClosed,
+        constraint = migration.new_mint == new_mint.key() @ MicaEurError::MigrationMintMismatch,
+        has_one = issuer,
+    )]
+    pub migration: Account<'info, Migration>,
+
+    #[account(mut)]
+    pub old_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub new_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, new_mint.key().as_ref()],
+        bump,
+    )]
+    pub new_mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        token::mint = old_mint,
+        token::authority = holder,
+    )]
+    pub old_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = new_mint,
+        token::authority = holder,
+    )]
+    pub new_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, holder.key().as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    /// Blacklist entry of the holder
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, holder.key().as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = holder,
+        seeds = [MIGRATION_RECORD_SEED, old_mint.key().as_ref(), old_token_account.key().as_ref()],
+        bump,
+        space = 8 + MigrationRecord::INIT_SPACE,
+    )]
+    pub migration_record: Account<'info, MigrationRecord>,
+
+    pub token_program: Program<'info, token_2022::Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, new_mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// Mint info of the retired mint, whose redemptions count the burned balance
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, old_mint.key().as_ref()],
+        bump,
+    )]
+    pub old_mint_info: Account<'info, MintInfo>,
+
+    /// Freeze record of the new token account
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, new_token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_record: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteMigration<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MIGRATION_SEED, migration.old_mint.as_ref()],
+        bump,
+        constraint = migration.is_open @ MicaEurError::MigrationClosed,
+        has_one = issuer,
+    )]
+    pub migration: Account<'info, Migration>,
+}
+
+// Freeze the old mint and open claims for migrating to the new one
+pub(crate) fn start_migration(ctx: Context<StartMigration>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // An inactive mint rejects mints and transfers, and a migrated one cannot be
+    // reactivated
+    let old_mint_info = &mut ctx.accounts.old_mint_info;
+    old_mint_info.is_active = false;
+    old_mint_info.migrated_to = ctx.accounts.new_mint_info.mint;
+
+    let migration = &mut ctx.accounts.migration;
+    migration.old_mint = old_mint_info.mint;
+    migration.new_mint = ctx.accounts.new_mint_info.mint;
+    migration.issuer = ctx.accounts.issuer.key();
+    migration.is_open = true;
+    migration.holder_count = 0;
+    migration.migrated_amount = 0;
+    migration.start_time = now;
+    migration.end_time = 0;
+
+    emit!(MigrationStarted {
+        old_mint: migration.old_mint,
+        new_mint: migration.new_mint,
+        timestamp: now,
+    });
+
+    msg!("Migration started from {} to {}", migration.old_mint, migration.new_mint);
+    Ok(())
+}
+
+// Burn a holder's whole balance under the old mint and remint it under the new one
+pub(crate) fn claim_migration(ctx: Context<ClaimMigration>) -> Result<()> {
+    let amount = ctx.accounts.old_token_account.amount;
+    if amount == 0 {
+        return Err(MicaEurError::NothingToMigrate.into());
+    }
+
+    // The new mint's compliance rules apply to the holder
+    let blacklist_entry = load_if_initialized::<BlacklistEntry>(&ctx.accounts.blacklist_entry)?;
+    check_mint_recipient(&ctx.accounts.kyc_user, blacklist_entry.as_ref())?;
+    check_mint_extensions(&ctx.accounts.new_mint.to_account_info())?;
+    check_token_account_extensions(&ctx.accounts.new_token_account.to_account_info())?;
+    let now = Clock::get()?.unix_timestamp;
+    // Funds cannot go into an account under a recorded freeze; below, only the
+    // default frozen state of a new account is lifted
+    let freeze_record = load_if_initialized::<FreezeRecord>(&ctx.accounts.freeze_record)?;
+    check_incoming_freeze(freeze_record.as_ref(), now)?;
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+
+    let cpi_accounts = token_2022::Burn {
+        mint: ctx.accounts.old_mint.to_account_info(),
+        from: ctx.accounts.old_token_account.to_account_info(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    // The retired mint may predate configurable decimals, so its own are used
+    burn_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        amount,
+        ctx.accounts.old_mint.decimals,
+    )?;
+    record_burned(&mut ctx.accounts.old_mint_info, amount)?;
+
+    // The new mint info PDA holds the mint and freeze authorities of the new mint
+    let new_mint_key = ctx.accounts.new_mint.key();
+    let bump = [ctx.bumps.new_mint_info];
+    let seeds = &[MINT_INFO_SEED, new_mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    // New token accounts start frozen, and Token-2022 does not mint to frozen accounts
+    if ctx.accounts.new_token_account.is_frozen() {
+        let cpi_accounts = token_2022::ThawAccount {
+            account: ctx.accounts.new_token_account.to_account_info(),
+            mint: ctx.accounts.new_mint.to_account_info(),
+            authority: ctx.accounts.new_mint_info.to_account_info(),
+        };
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+
+    let cpi_accounts = token_2022::MintTo {
+        mint: ctx.accounts.new_mint.to_account_info(),
+        to: ctx.accounts.new_token_account.to_account_info(),
+        authority: ctx.accounts.new_mint_info.to_account_info(),
+    };
+    mint_to_checked(
+        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+        amount,
+        ctx.accounts.new_mint_info.decimals,
+    )?;
+    record_minted(&mut ctx.accounts.new_mint_info, amount)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let record = &mut ctx.accounts.migration_record;
+    record.holder = ctx.accounts.holder.key();
+    record.old_token_account = ctx.accounts.old_token_account.key();
+    record.new_token_account = ctx.accounts.new_token_account.key();
+    record.amount = amount;
+    record.claim_time = now;
+
+    let migration = &mut ctx.accounts.migration;
+    migration.holder_count = migration
+        .holder_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    migration.migrated_amount = migration
+        .migrated_amount
+        .checked_add(amount)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(HolderMigrated {
+        old_mint: migration.old_mint,
+        new_mint: migration.new_mint,
+        holder: record.holder,
+        amount,
+        timestamp: now,
+    });
+
+    msg!("Migrated {} tokens for {}", amount, record.holder);
+    Ok(())
+}
+
+// Close claims once every holder has migrated
+pub(crate) fn complete_migration(ctx: Context<CompleteMigration>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let migration = &mut ctx.accounts.migration;
+    migration.is_open = false;
+    migration.end_time = now;
+
+    emit!(MigrationCompleted {
+        old_mint: migration.old_mint,
+        new_mint: migration.new_mint,
+        holder_count: migration.holder_count,
+        migrated_amount: migration.migrated_amount,
+        timestamp: now,
+    });
+
+    msg!(
+        "Migration completed: {} holders, {} tokens",
+        migration.holder_count,
+        migration.migrated_amount
+    );
+    Ok(())
+}
\ No newline at end of file
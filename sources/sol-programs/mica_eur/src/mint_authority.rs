@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, spl_token_2022::instruction::AuthorityType, Token2022};
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Emitted when the Token-2022 authorities of an existing mint move to its mint info PDA
+#[event]
+pub struct MintAuthorityMigrated {
+    pub mint: Pubkey,
+    pub previous_authority: Pubkey,   // Keypair that held the mint authority
+    pub mint_info: Pubkey,            // PDA now holding it
+    pub freeze_authority_migrated: bool, // Whether the freeze authority moved as well
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMintAuthority<'info> {
+    pub issuer: Signer<'info>,
+
+    /// Keypair currently holding the Token-2022 mint authority, often the issuer
+    pub current_authority: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = issuer,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        constraint = mint.mint_authority == Some(current_authority.key()).into() @ MicaEurError::NotMintAuthority,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+// Hand the Token-2022 mint authority of a mint created before it was held by the
+// program over to the mint info PDA (issuer and current authority), so tokens are
+// only ever minted through the program's checks. The freeze authority follows when
+// the same keypair holds it.
+pub(crate) fn migrate_mint_authority(ctx: Context<MigrateMintAuthority>) -> Result<()> {
+    let current_authority = ctx.accounts.current_authority.key();
+    let mint_info = ctx.accounts.mint_info.key();
+
+    let set_authority = |authority_type| {
+        let cpi_accounts = token_2022::SetAuthority {
+            current_authority: ctx.accounts.current_authority.to_account_info(),
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::set_authority(cpi_ctx, authority_type, Some(mint_info))
+    };
+    set_authority(AuthorityType::MintTokens)?;
+    let freeze_authority_migrated = ctx.accounts.mint.freeze_authority == Some(current_authority).into();
+    if freeze_authority_migrated {
+        set_authority(AuthorityType::FreezeAccount)?;
+    }
+
+    emit!(MintAuthorityMigrated {
+        mint: ctx.accounts.mint.key(),
+        previous_authority: current_authority,
+        mint_info,
+        freeze_authority_migrated,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Mint authority of {} moved to {}", ctx.accounts.mint.key(), mint_info);
+    Ok(())
+}
+
+/// Emitted when the permanent delegate of an existing mint moves to its seizure
+/// authority PDA
+#[event]
+pub struct PermanentDelegateMigrated {
+    pub mint: Pubkey,
+    pub seizure_authority: Pubkey,    // PDA now holding it
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct MigratePermanentDelegate<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = issuer,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: New permanent delegate of the mint; address checked against its seeds
+    #[account(
+        seeds = [SEIZURE_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub seizure_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+// Hand the permanent delegate of a mint created while the mint info PDA held it
+// over to the seizure authority PDA (issuer only). The transfer hook only skips its
+// party checks for transfers the seizure authority signs, so seizures and
+// recoveries on such a mint need this first.
+pub(crate) fn migrate_permanent_delegate(ctx: Context<MigratePermanentDelegate>) -> Result<()> {
+    let mint_key = ctx.accounts.mint.key();
+    let seizure_authority = ctx.accounts.seizure_authority.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = token_2022::SetAuthority {
+        current_authority: ctx.accounts.mint_info.to_account_info(),
+        account_or_mint: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_2022::set_authority(cpi_ctx, AuthorityType::PermanentDelegate, Some(seizure_authority))?;
+
+    emit!(PermanentDelegateMigrated {
+        mint: mint_key,
+        seizure_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Permanent delegate of {} moved to {}", mint_key, seizure_authority);
+    Ok(())
+}
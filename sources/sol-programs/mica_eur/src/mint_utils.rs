@@ -1,44 +1,290 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::spl_token_2022::{
-    extension::{
-        default_account_state::DefaultAccountState,
-        permanent_delegate::PermanentDelegate,
-        metadata_pointer::MetadataPointer,
-        StateWithExtensions,
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::{
+    self,
+    spl_token_2022::{
+        self,
+        extension::{
+            confidential_transfer, confidential_transfer_fee, BaseStateWithExtensions,
+            ExtensionType, StateWithExtensions,
+        },
+        solana_zk_token_sdk::zk_token_elgamal::pod::ElGamalPubkey,
+        state::{Account as TokenAccountState, AccountState, Mint as MintState},
     },
-    state::{AccountState, Mint},
+};
+use anchor_spl::token_2022_extensions::{
+    default_account_state_initialize, interest_bearing_mint_initialize,
+    metadata_pointer_initialize, permanent_delegate_initialize, transfer_fee_initialize,
+    transfer_hook_initialize, DefaultAccountStateInitialize, InterestBearingMintInitialize,
+    MetadataPointerInitialize, PermanentDelegateInitialize, TransferFeeInitialize,
+    TransferHookInitialize,
 };
 
 use crate::constants::*;
 use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Extensions every EUR mint is created with. The transfer fee and interest rate
+/// start at zero until the issuer sets them, and confidential transfers stay
+/// unusable until the issuer enables them with an auditor key.
+pub const MINT_EXTENSIONS: [ExtensionType; 8] = [
+    ExtensionType::DefaultAccountState,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TransferHook,
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::ConfidentialTransferMint,
+    ExtensionType::ConfidentialTransferFeeConfig,
+    ExtensionType::InterestBearingConfig,
+];
+
+/// Extensions a mint may carry: `MINT_EXTENSIONS` plus token metadata stored on the
+/// mint itself through the metadata pointer
+pub const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 9] = [
+    ExtensionType::DefaultAccountState,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TransferHook,
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::ConfidentialTransferMint,
+    ExtensionType::ConfidentialTransferFeeConfig,
+    ExtensionType::InterestBearingConfig,
+    ExtensionType::TokenMetadata,
+];
+
+/// Extensions a token account may carry: those Token-2022 adds for the mint's
+/// extensions and those an owner can enable on their own account
+pub const SUPPORTED_TOKEN_ACCOUNT_EXTENSIONS: [ExtensionType; 7] = [
+    ExtensionType::ImmutableOwner,
+    ExtensionType::TransferHookAccount,
+    ExtensionType::TransferFeeAmount,
+    ExtensionType::ConfidentialTransferAccount,
+    ExtensionType::ConfidentialTransferFeeAmount,
+    ExtensionType::MemoTransfer,
+    ExtensionType::CpiGuard,
+];
+
+/// Size of a Token-2022 mint account carrying `extensions`
+pub fn get_mint_size_with_extensions(extensions: &[ExtensionType]) -> Result<usize> {
+    ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)
+        .map_err(Into::into)
+}
 
-/// Initialize a token mint with extensions
+/// Accounts needed to create a mint
+pub struct MintCreation<'info> {
+    pub payer: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Create and initialize a Token-2022 mint with `MINT_EXTENSIONS`. New token accounts
+/// start frozen, the metadata pointer targets the mint itself and transfers are
+/// routed through the transfer hook program. `token_authority` (the mint info PDA)
+/// is the mint and freeze authority and the authority of the transfer fee,
+/// confidential transfer and interest rate configuration, so the program decides
+/// which role holders may use them. `seizure_authority`, a PDA the program only
+/// signs with for seizures and recoveries, is the permanent delegate. The `issuer`
+/// manages the metadata and transfer hook pointers.
 pub fn initialize_mint_with_extensions(
-    mint: &AccountInfo,
-    mint_authority: &Pubkey,
-    freeze_authority: &Pubkey,
-    permanent_delegate: &Pubkey,
+    accounts: MintCreation,
+    issuer: &Pubkey,
+    token_authority: &Pubkey,
+    seizure_authority: &Pubkey,
     decimals: u8,
 ) -> Result<()> {
     // Validate parameters
-    if decimals != EUR_DECIMALS {
-        msg!("Warning: Using non-standard decimals for EUR token");
+    if !SUPPORTED_EUR_DECIMALS.contains(&decimals) {
+        return Err(MicaEurError::UnsupportedDecimals.into());
     }
 
+    let space = get_mint_size_with_extensions(&MINT_EXTENSIONS)?;
+    system_program::create_account(
+        CpiContext::new(
+            accounts.system_program.clone(),
+            system_program::CreateAccount {
+                from: accounts.payer.clone(),
+                to: accounts.mint.clone(),
+            },
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        accounts.token_program.key,
+    )?;
+
+    // Extensions must be initialized before the mint itself
+    default_account_state_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            DefaultAccountStateInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        &AccountState::Frozen,
+    )?;
+    permanent_delegate_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            PermanentDelegateInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        seizure_authority,
+    )?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        Some(*issuer),
+        Some(accounts.mint.key()),
+    )?;
+    transfer_hook_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            TransferHookInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        Some(*issuer),
+        Some(TRANSFER_HOOK_PROGRAM_ID),
+    )?;
+    transfer_fee_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            TransferFeeInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        Some(token_authority),
+        Some(token_authority),
+        0,
+        0,
+    )?;
+    // Accounts can configure confidential balances only once approved by
+    // `token_authority`, and Token-2022 only allows confidential transfers on a mint
+    // with a transfer fee alongside a confidential fee configuration. Its withdrawal
+    // key stays unset, as transfer fees and confidential transfers are never enabled
+    // together.
+    invoke(
+        &confidential_transfer::instruction::initialize_mint(
+            accounts.token_program.key,
+            accounts.mint.key,
+            Some(*token_authority),
+            false,
+            None,
+        )?,
+        std::slice::from_ref(&accounts.mint),
+    )?;
+    invoke(
+        &confidential_transfer_fee::instruction::initialize_confidential_transfer_fee_config(
+            accounts.token_program.key,
+            accounts.mint.key,
+            None,
+            ElGamalPubkey::default(),
+        )?,
+        std::slice::from_ref(&accounts.mint),
+    )?;
+    interest_bearing_mint_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            InterestBearingMintInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        Some(*token_authority),
+        0,
+    )?;
+    token_2022::initialize_mint2(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            token_2022::InitializeMint2 { mint: accounts.mint.clone() },
+        ),
+        decimals,
+        token_authority,
+        Some(token_authority),
+    )?;
+
     // Log the key parameters
-    msg!("Initializing mint with extensions:");
-    msg!("Mint: {}", mint.key());
-    msg!("Mint Authority: {}", mint_authority);
-    msg!("Freeze Authority: {}", freeze_authority);
-    msg!("Permanent Delegate: {}", permanent_delegate);
+    msg!("Initialized mint with extensions:");
+    msg!("Mint: {}", accounts.mint.key());
+    msg!("Token Authority: {}", token_authority);
     msg!("Decimals: {}", decimals);
 
     Ok(())
 }
 
-/// Check if a transaction exceeds the maximum allowed amount
-pub fn check_transaction_amount(amount: u64) -> Result<()> {
-    if amount > MAX_TRANSACTION_AMOUNT {
+/// Reject a mint carrying an extension outside `SUPPORTED_MINT_EXTENSIONS`
+pub fn check_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+    check_extensions(&mint.get_extension_types()?, &SUPPORTED_MINT_EXTENSIONS)
+}
+
+/// Reject a token account carrying an extension outside
+/// `SUPPORTED_TOKEN_ACCOUNT_EXTENSIONS`
+pub fn check_token_account_extensions(token_account: &AccountInfo) -> Result<()> {
+    let data = token_account.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    check_extensions(&account.get_extension_types()?, &SUPPORTED_TOKEN_ACCOUNT_EXTENSIONS)
+}
+
+fn check_extensions(extensions: &[ExtensionType], supported: &[ExtensionType]) -> Result<()> {
+    if extensions.iter().any(|extension| !supported.contains(extension)) {
+        return Err(MicaEurError::UnsupportedTokenExtension.into());
+    }
+    Ok(())
+}
+
+/// Check that new tokens for `owner` may be minted into `token_account`: once the
+/// mint requires associated token accounts, only the owner's one may receive them
+pub fn check_mint_destination(mint_info: &MintInfo, owner: &Pubkey, token_account: &Pubkey) -> Result<()> {
+    if mint_info.require_associated_accounts
+        && *token_account != get_associated_token_address_with_program_id(owner, &mint_info.mint, &spl_token_2022::ID)
+    {
+        return Err(MicaEurError::AssociatedTokenAccountRequired.into());
+    }
+    Ok(())
+}
+
+/// Reject a string longer than the `max_len` bytes its account reserves for it
+pub fn check_string_len(value: &str, max_len: usize) -> Result<()> {
+    if value.len() > max_len {
+        return Err(MicaEurError::StringTooLong.into());
+    }
+    Ok(())
+}
+
+/// Check that a reserve proof CID is a CIDv0 (46 base58btc characters starting
+/// with "Qm") or a CIDv1 in the lower-case base32 multibase ("b" prefix)
+pub fn check_ipfs_cid(cid: &str) -> Result<()> {
+    const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    let valid = if let Some(rest) = cid.strip_prefix('b') {
+        cid.len() <= MAX_IPFS_CID_LEN
+            && !rest.is_empty()
+            && rest.bytes().all(|b| b.is_ascii_lowercase() || (b'2'..=b'7').contains(&b))
+    } else {
+        cid.len() == 46 && cid.starts_with("Qm") && cid.bytes().all(|b| BASE58.contains(&b))
+    };
+    if !valid {
+        return Err(MicaEurError::InvalidIpfsCid.into());
+    }
+    Ok(())
+}
+
+/// Check if a transaction exceeds the maximum allowed amount on a mint with `decimals`
+pub fn check_transaction_amount(amount: u64, decimals: u8) -> Result<()> {
+    if amount > crate::limits::eur_to_token_units(MAX_TRANSACTION_EUR, decimals) {
         return Err(MicaEurError::TransactionAmountExceedsMaximum.into());
     }
     Ok(())
@@ -57,4 +303,77 @@ pub fn find_kyc_oracle_state_pda(program_id: &Pubkey) -> (Pubkey, u8) {
 /// Helper function to derive the KYC user PDA
 pub fn find_kyc_user_pda(user: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[KYC_USER_SEED, user.as_ref()], program_id)
-} 
\ No newline at end of file
+}
+
+/// Helper function to derive the KYC provider PDA
+pub fn find_kyc_provider_pda(provider: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[KYC_PROVIDER_SEED, provider.as_ref()], program_id)
+}
+
+/// Transfer tokens with `transfer_checked`, forwarding the remaining accounts of the
+/// CPI context so the mint's TransferHook can resolve its extra account metas
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, token_2022::TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Mint tokens with `mint_to_checked`, so a mint whose decimals differ from the
+/// mint info's fails in the token program
+pub fn mint_to_checked<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, token_2022::MintTo<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::mint_to_checked(
+        ctx.program.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.to.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.to, ctx.accounts.mint, ctx.accounts.authority],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Burn tokens with `burn_checked`, validating the mint's decimals like `mint_to_checked`
+pub fn burn_checked<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, token_2022::Burn<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::burn_checked(
+        ctx.program.key,
+        ctx.accounts.from.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.from, ctx.accounts.mint, ctx.accounts.authority],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
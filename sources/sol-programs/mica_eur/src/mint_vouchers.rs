@@ -0,0 +1,331 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::aml::BlacklistEntry;
+use crate::approvals::{check_single_key_mint, ApprovalSet};
+use crate::batch_mint::load_if_initialized;
+use crate::codec::encode_sepa_reference;
+use crate::constants::*;
+use crate::country_policy::{check_country_allowed, check_country_transaction, CountryPolicy};
+use crate::error::MicaEurError;
+use crate::freezes::{check_incoming_freeze, FreezeRecord};
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::issuance::{check_supply_cap, record_scheduled_issuance, IssuancePolicy};
+use crate::kyc_oracle::KycUser;
+use crate::limits::{record_mint_volume, tier_limits, LimitConfig, UserLimits};
+use crate::mint_utils::{check_mint_destination, check_mint_extensions, check_token_account_extensions, mint_to_checked};
+use crate::policy::check_mint_recipient;
+use crate::reserve_coverage::{check_mint_coverage, check_reserve_freshness};
+use crate::supply_accounting::record_minted;
+use crate::MintInfo;
+
+/// Fiat deposit the issuer received for a user, claimable once as a mint of the
+/// same amount. The PDA is derived from the deposit's SEPA reference hash, so
+/// each deposit backs at most one mint.
+#[account]
+#[derive(InitSpace)]
+pub struct MintVoucher {
+    pub mint: Pubkey,                 // The EUR mint
+    pub user: Pubkey,                 // Wallet the deposit is credited to
+    pub reference_hash: [u8; 32],     // `sepa_reference_hash` of the credit transfer
+    pub amount: u64,                  // Deposited amount, in token units
+    pub created_by: Pubkey,           // Issuer who recorded the deposit
+    pub created_at: i64,
+    pub claimed_at: i64,              // 0 until claimed
+    pub token_account: Pubkey,        // Account minted to, default until claimed
+}
+
+/// Emitted when the issuer records a deposit as a mint voucher
+#[event]
+pub struct MintVoucherCreated {
+    pub mint: Pubkey,
+    pub voucher: Pubkey,
+    pub user: Pubkey,
+    pub reference_hash: [u8; 32],
+    pub amount: u64,
+    pub issuer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a user claims a voucher and its amount is minted
+#[event]
+pub struct MintVoucherClaimed {
+    pub mint: Pubkey,
+    pub voucher: Pubkey,
+    pub user: Pubkey,
+    pub token_account: Pubkey,
+    pub reference_hash: [u8; 32],
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, reference_hash: [u8; 32])]
+pub struct CreateMintVoucher<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Fails if the deposit was already recorded
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [MINT_VOUCHER_SEED, mint.key().as_ref(), &reference_hash],
+        bump,
+        space = 8 + MintVoucher::INIT_SPACE,
+    )]
+    pub voucher: Account<'info, MintVoucher>,
+
+    /// Approval set of the mint, required once one is configured
+    pub approval_set: Option<Account<'info, ApprovalSet>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimMintVoucher<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+        constraint = !mint_info.minting_halted @ MicaEurError::MintingHalted,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VOUCHER_SEED, mint.key().as_ref(), &voucher.reference_hash],
+        bump,
+        has_one = mint,
+        has_one = user,
+        constraint = voucher.claimed_at == 0 @ MicaEurError::MintVoucherClaimed,
+    )]
+    pub voucher: Account<'info, MintVoucher>,
+
+    /// The user's token account to mint to
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = token_account.owner == user.key() @ MicaEurError::TokenAccountOwnerMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The KYC user account (must be verified)
+    #[account(
+        seeds = [KYC_USER_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    /// Blacklist entry of the user
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// Policy of the user's country
+    #[account(
+        seeds = [COUNTRY_POLICY_SEED, kyc_user.country_code.as_bytes()],
+        bump,
+    )]
+    pub country_policy: Account<'info, CountryPolicy>,
+
+    /// Rolling mint and burn volumes of the user
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [USER_LIMITS_SEED, user.key().as_ref()],
+        bump,
+        space = 8 + UserLimits::INIT_SPACE,
+    )]
+    pub user_limits: Account<'info, UserLimits>,
+
+    /// Tier limits of the mint, the defaults apply if none are configured
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub limit_config: Option<Account<'info, LimitConfig>>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// Issuance schedule of the mint, required once one is configured
+    #[account(
+        mut,
+        seeds = [ISSUANCE_POLICY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub issuance_policy: Option<Account<'info, IssuancePolicy>>,
+
+    /// Freeze record of the token account
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_record: UncheckedAccount<'info>,
+}
+
+/// Hash vouchers and payout references are bound to: SHA-256 of the
+/// `encode_sepa_reference` encoding of the transaction reference
+pub fn sepa_reference_hash(reference: &str) -> [u8; 32] {
+    hash::hash(&encode_sepa_reference(reference)).to_bytes()
+}
+
+// Record a fiat deposit credited to `user` as a mint voucher (issuer only). Large
+// amounts need the approval set's officers once one is configured, as direct
+// mints do.
+pub(crate) fn create_mint_voucher(
+    ctx: Context<CreateMintVoucher>,
+    user: Pubkey,
+    reference_hash: [u8; 32],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Err(MicaEurError::InvalidAmount.into());
+    }
+    if reference_hash == [0; 32] {
+        return Err(MicaEurError::InvalidSepaReference.into());
+    }
+    check_single_key_mint(
+        &ctx.accounts.mint_info,
+        ctx.accounts.approval_set.as_deref(),
+        ctx.accounts.approval_set.as_ref().map(|a| a.key()),
+        amount,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let voucher = &mut ctx.accounts.voucher;
+    voucher.mint = ctx.accounts.mint.key();
+    voucher.user = user;
+    voucher.reference_hash = reference_hash;
+    voucher.amount = amount;
+    voucher.created_by = ctx.accounts.issuer.key();
+    voucher.created_at = now;
+    voucher.claimed_at = 0;
+    voucher.token_account = Pubkey::default();
+
+    emit!(MintVoucherCreated {
+        mint: voucher.mint,
+        voucher: voucher.key(),
+        user,
+        reference_hash,
+        amount,
+        issuer: voucher.created_by,
+        timestamp: now,
+    });
+    msg!("Mint voucher of {} created for {}", amount, user);
+    Ok(())
+}
+
+// Mint a voucher's amount to the user it was created for (the user), under the
+// recipient checks, limits and supply checks of `mint_tokens`
+pub(crate) fn claim_mint_voucher(ctx: Context<ClaimMintVoucher>) -> Result<()> {
+    let amount = ctx.accounts.voucher.amount;
+    let blacklist_entry = load_if_initialized::<BlacklistEntry>(&ctx.accounts.blacklist_entry)?;
+    check_mint_recipient(&ctx.accounts.kyc_user, blacklist_entry.as_ref())?;
+    check_country_allowed(&ctx.accounts.country_policy, &ctx.accounts.kyc_user.country_code)?;
+    check_country_transaction(&ctx.accounts.country_policy, &ctx.accounts.kyc_user, amount)?;
+    check_mint_extensions(&ctx.accounts.mint.to_account_info())?;
+    check_token_account_extensions(&ctx.accounts.token_account.to_account_info())?;
+    check_mint_destination(&ctx.accounts.mint_info, &ctx.accounts.kyc_user.user, &ctx.accounts.token_account.key())?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let supply = ctx.accounts.mint.supply;
+    check_reserve_freshness(&ctx.accounts.mint_info, now)?;
+    check_mint_coverage(&ctx.accounts.mint_info, supply, amount)?;
+    check_supply_cap(&ctx.accounts.mint_info, supply, amount)?;
+    record_scheduled_issuance(&ctx.accounts.mint_info, ctx.accounts.issuance_policy.as_mut(), amount, now)?;
+    // Funds cannot go into an account under a recorded freeze; below, only the
+    // default frozen state of a new account is lifted
+    let freeze_record = load_if_initialized::<FreezeRecord>(&ctx.accounts.freeze_record)?;
+    check_incoming_freeze(freeze_record.as_ref(), now)?;
+
+    let limits = tier_limits(
+        ctx.accounts.limit_config.as_deref(),
+        ctx.accounts.kyc_user.verification_level,
+        ctx.accounts.mint_info.decimals,
+    );
+    record_mint_volume(&mut ctx.accounts.user_limits, ctx.accounts.kyc_user.user, limits, amount)?;
+
+    // The mint info PDA holds the mint and freeze authority
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    // New token accounts start frozen, and Token-2022 does not mint to frozen accounts
+    if ctx.accounts.token_account.is_frozen() {
+        let cpi_accounts = token_2022::ThawAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+    let cpi_accounts = token_2022::MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.mint_info.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    mint_to_checked(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    record_minted(&mut ctx.accounts.mint_info, amount)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let voucher_key = ctx.accounts.voucher.key();
+    let voucher = &mut ctx.accounts.voucher;
+    voucher.claimed_at = now;
+    voucher.token_account = ctx.accounts.token_account.key();
+
+    emit!(MintVoucherClaimed {
+        mint: mint_key,
+        voucher: voucher_key,
+        user: voucher.user,
+        token_account: voucher.token_account,
+        reference_hash: voucher.reference_hash,
+        amount,
+        timestamp: now,
+    });
+    msg!("Mint voucher claimed: {} minted to {}", amount, voucher.token_account);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MicaEurError;
+use crate::policy::RULE_SET_VERSION;
+
+/// Kind of issuer operation an operation ID was used for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum OperationKind {
+    Mint,
+    Redemption,
+    Seizure,
+    TreasuryMint,
+    Distribution,
+}
+
+/// Receipt of an issuer operation, keyed by its kind, the signer and the
+/// client-supplied operation UUID. Its existence makes re-submitting the same
+/// operation fail, so back-office systems can safely retry after an RPC timeout.
+#[account]
+#[derive(InitSpace)]
+pub struct OperationReceipt {
+    pub operation_id: [u8; 16],  // Client-supplied UUID
+    pub kind: OperationKind,     // Which instruction consumed the ID
+    pub mint: Pubkey,            // The EUR mint
+    pub actor: Pubkey,           // Signer that submitted the operation
+    pub source: Pubkey,          // Token account debited (default for mints)
+    pub destination: Pubkey,     // Token account credited (default for redemptions)
+    pub amount: u64,             // Amount in token units
+    pub slot: u64,               // Slot the operation executed in
+    pub timestamp: i64,          // When the operation executed
+    pub rule_set_version: u32,   // Compliance rule set the operation was checked against
+}
+
+/// Parties and amount of an operation being recorded
+pub struct OperationDetails {
+    pub kind: OperationKind,
+    pub mint: Pubkey,
+    pub actor: Pubkey,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// Record an operation on its freshly created receipt
+pub fn record_operation(
+    receipt: &mut Account<OperationReceipt>,
+    operation_id: [u8; 16],
+    details: OperationDetails,
+) -> Result<()> {
+    if operation_id == [0u8; 16] {
+        return Err(MicaEurError::InvalidOperationId.into());
+    }
+
+    let clock = Clock::get()?;
+    receipt.operation_id = operation_id;
+    receipt.kind = details.kind;
+    receipt.mint = details.mint;
+    receipt.actor = details.actor;
+    receipt.source = details.source;
+    receipt.destination = details.destination;
+    receipt.amount = details.amount;
+    receipt.slot = clock.slot;
+    receipt.timestamp = clock.unix_timestamp;
+    receipt.rule_set_version = RULE_SET_VERSION;
+
+    Ok(())
+}
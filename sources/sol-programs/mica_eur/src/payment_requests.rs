@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::mint_utils::transfer_checked_with_hook;
+use crate::MintInfo;
+
+/// Lifecycle of a payment request
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PaymentRequestStatus {
+    Open,
+    Paid,
+}
+
+/// Invoice a payee issues for wallets to pay. The memo hash identifies the invoice
+/// in the payee's books, so the payment can be reconciled like a SEPA credit
+/// transfer carrying its remittance information.
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentRequest {
+    pub mint: Pubkey,                 // Mint the request is payable in
+    pub request_id: u64,              // Payee-chosen ID, part of the PDA seeds
+    pub payee: Pubkey,                // Wallet requesting the payment
+    pub payee_token_account: Pubkey,  // Where the payment goes
+    pub amount: u64,                  // Requested amount
+    pub memo_hash: [u8; 32],          // Hash of the invoice's remittance information
+    pub status: PaymentRequestStatus,
+    pub created_at: i64,
+    pub expires_at: i64,              // Payable until then
+    pub paid_by: Pubkey,              // Payer, default while open
+    pub paid_at: i64,
+}
+
+/// Emitted when a payee issues a payment request
+#[event]
+pub struct PaymentRequestCreated {
+    pub payment_request: Pubkey,
+    pub payee: Pubkey,
+    pub amount: u64,
+    pub memo_hash: [u8; 32],
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a payment request is paid, linking the payment to the invoice
+#[event]
+pub struct PaymentRequestPaid {
+    pub payment_request: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub amount: u64,
+    pub memo_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct CreatePaymentRequest<'info> {
+    #[account(mut)]
+    pub payee: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        constraint = payee_token_account.owner == payee.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = payee_token_account.mint == mint_info.mint @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub payee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payee,
+        seeds = [PAYMENT_REQUEST_SEED, payee.key().as_ref(), &request_id.to_le_bytes()],
+        bump,
+        space = 8 + PaymentRequest::INIT_SPACE,
+    )]
+    pub payment_request: Account<'info, PaymentRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayRequest<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [PAYMENT_REQUEST_SEED, payment_request.payee.as_ref(), &payment_request.request_id.to_le_bytes()],
+        bump,
+        has_one = mint,
+        has_one = payee_token_account,
+    )]
+    pub payment_request: Account<'info, PaymentRequest>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = payer_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+}
+
+// Issue a request for `amount` to be paid into the payee's token account until
+// `expires_at`, identified by the hash of its remittance information
+pub(crate) fn create_payment_request(
+    ctx: Context<CreatePaymentRequest>,
+    request_id: u64,
+    amount: u64,
+    memo_hash: [u8; 32],
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if amount == 0 {
+        return Err(MicaEurError::InvalidAmount.into());
+    }
+    if memo_hash == [0; 32] || expires_at <= now {
+        return Err(MicaEurError::InvalidPaymentRequest.into());
+    }
+
+    let request = &mut ctx.accounts.payment_request;
+    request.mint = ctx.accounts.mint_info.mint;
+    request.request_id = request_id;
+    request.payee = ctx.accounts.payee.key();
+    request.payee_token_account = ctx.accounts.payee_token_account.key();
+    request.amount = amount;
+    request.memo_hash = memo_hash;
+    request.status = PaymentRequestStatus::Open;
+    request.created_at = now;
+    request.expires_at = expires_at;
+
+    emit!(PaymentRequestCreated {
+        payment_request: request.key(),
+        payee: request.payee,
+        amount,
+        memo_hash,
+        expires_at,
+        timestamp: now,
+    });
+    msg!("Payment request of {} created by {}", amount, request.payee);
+    Ok(())
+}
+
+// Pay an open request in full. The transfer runs through the transfer hook, which
+// checks both parties; the remaining accounts are its extra accounts.
+pub(crate) fn pay_request<'info>(ctx: Context<'_, '_, '_, 'info, PayRequest<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let request = &ctx.accounts.payment_request;
+    if request.status != PaymentRequestStatus::Open {
+        return Err(MicaEurError::PaymentRequestNotOpen.into());
+    }
+    if now > request.expires_at {
+        return Err(MicaEurError::PaymentRequestExpired.into());
+    }
+    let amount = request.amount;
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.payee_token_account.to_account_info(),
+        authority: ctx.accounts.payer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let request = &mut ctx.accounts.payment_request;
+    request.status = PaymentRequestStatus::Paid;
+    request.paid_by = ctx.accounts.payer.key();
+    request.paid_at = now;
+
+    emit!(PaymentRequestPaid {
+        payment_request: request.key(),
+        payer: request.paid_by,
+        payee: request.payee,
+        amount,
+        memo_hash: request.memo_hash,
+        timestamp: now,
+    });
+    msg!("Payment request of {} paid by {}", amount, request.paid_by);
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::operations::{OperationKind, OperationReceipt};
+use crate::MintInfo;
+
+/// Fiat leg of a redemption: the bank reference of the SEPA payout settling it,
+/// keyed by the redeemer and operation ID like the redemption's operation receipt. Together with mint vouchers it
+/// ties both sides of the peg to the bank's own records.
+#[account]
+#[derive(InitSpace)]
+pub struct PayoutReference {
+    pub mint: Pubkey,                 // The EUR mint
+    pub operation_id: [u8; 16],       // Operation ID of the redemption
+    pub holder: Pubkey,               // Signer of the redemption
+    pub amount: u64,                  // Redeemed amount, in token units
+    pub reference_hash: [u8; 32],     // `sepa_reference_hash` of the payout's bank reference
+    pub recorded_by: Pubkey,          // Issuer who recorded the payout
+    pub recorded_at: i64,
+}
+
+/// Emitted when the issuer records the payout of a redemption
+#[event]
+pub struct PayoutReferenceRecorded {
+    pub mint: Pubkey,
+    pub operation_id: [u8; 16],
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub reference_hash: [u8; 32],
+    pub issuer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(operation_id: [u8; 16])]
+pub struct RecordPayoutReference<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// Receipt of the settled redemption
+    #[account(
+        seeds = [
+            OPERATION_RECEIPT_SEED,
+            mint_info.mint.as_ref(),
+            &[operation_receipt.kind as u8],
+            operation_receipt.actor.as_ref(),
+            &operation_id,
+        ],
+        bump,
+        constraint = operation_receipt.kind == OperationKind::Redemption @ MicaEurError::NotARedemption,
+    )]
+    pub operation_receipt: Account<'info, OperationReceipt>,
+
+    /// Fails if the redemption's payout was already recorded
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            PAYOUT_REFERENCE_SEED,
+            mint_info.mint.as_ref(),
+            operation_receipt.actor.as_ref(),
+            &operation_id,
+        ],
+        bump,
+        space = 8 + PayoutReference::INIT_SPACE,
+    )]
+    pub payout_reference: Account<'info, PayoutReference>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Attach the bank reference of the SEPA payout to a settled redemption (issuer only)
+pub(crate) fn record_payout_reference(
+    ctx: Context<RecordPayoutReference>,
+    operation_id: [u8; 16],
+    reference_hash: [u8; 32],
+) -> Result<()> {
+    if reference_hash == [0; 32] {
+        return Err(MicaEurError::InvalidSepaReference.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let receipt = &ctx.accounts.operation_receipt;
+    let payout_reference = &mut ctx.accounts.payout_reference;
+    payout_reference.mint = receipt.mint;
+    payout_reference.operation_id = operation_id;
+    payout_reference.holder = receipt.actor;
+    payout_reference.amount = receipt.amount;
+    payout_reference.reference_hash = reference_hash;
+    payout_reference.recorded_by = ctx.accounts.issuer.key();
+    payout_reference.recorded_at = now;
+
+    emit!(PayoutReferenceRecorded {
+        mint: payout_reference.mint,
+        operation_id,
+        holder: payout_reference.holder,
+        amount: payout_reference.amount,
+        reference_hash,
+        issuer: payout_reference.recorded_by,
+        timestamp: now,
+    });
+    msg!("Payout recorded for redemption of {} by {}", payout_reference.amount, payout_reference.holder);
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::codec::encode_transfer_authorization;
+use crate::constants::*;
+use crate::ed25519_utils::verify_ed25519_instruction;
+use crate::error::MicaEurError;
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::mint_utils::transfer_checked_with_hook;
+use crate::relayers::{check_relayer, Relayer, RelayerActivity};
+use crate::MintInfo;
+
+/// A consumed transfer authorization. Its existence at the PDA of the owner and
+/// nonce is what keeps the signature from being replayed.
+#[account]
+#[derive(InitSpace)]
+pub struct PermitNonce {
+    pub owner: Pubkey,                // Wallet that signed the authorization
+    pub nonce: [u8; 32],              // Owner-chosen nonce, part of the PDA seeds
+    pub used_at: i64,                 // When the authorization was executed
+}
+
+/// Emitted when a relayer executes a signed transfer authorization
+#[event]
+pub struct TransferWithAuthorizationExecuted {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub nonce: [u8; 32],
+    pub relayer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Message an owner signs to authorize a transfer of `mint`
+pub fn transfer_authorization_message(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    nonce: &[u8; 32],
+    expiry: i64,
+) -> Vec<u8> {
+    encode_transfer_authorization(&crate::ID, mint, owner, recipient, amount, nonce, expiry)
+}
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey, amount: u64, nonce: [u8; 32])]
+pub struct TransferWithAuthorization<'info> {
+    /// Submits the authorization and pays the fees and the nonce rent
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Signed the authorization, checked against the Ed25519 instruction
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = source_token.owner == owner.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = source_token.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = destination_token.owner == recipient @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = destination_token.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Delegate the owner approved for authorized transfers; address checked against its seeds
+    #[account(
+        seeds = [PERMIT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub permit_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = relayer,
+        seeds = [PERMIT_NONCE_SEED, owner.key().as_ref(), nonce.as_ref()],
+        bump,
+        space = 8 + PermitNonce::INIT_SPACE,
+    )]
+    pub permit_nonce: Account<'info, PermitNonce>,
+
+    /// CHECK: Instructions sysvar, used to inspect the Ed25519 instruction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Record of the relayer, required once the mint has a relayer registry
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, mint.key().as_ref(), relayer.key().as_ref()],
+        bump,
+    )]
+    pub relayer_record: Option<Account<'info, Relayer>>,
+}
+
+// Move tokens on an owner's signed authorization, submitted by a relayer paying the
+// fees. The owner approves the mint's permit authority as delegate once; the
+// transfer runs through the transfer hook like any other, so both parties are
+// checked there. Once the mint has a relayer registry, only its active relayers
+// may submit. Remaining accounts: the transfer hook's extra accounts.
+pub(crate) fn transfer_with_authorization<'info>(
+    ctx: Context<'_, '_, '_, 'info, TransferWithAuthorization<'info>>,
+    recipient: Pubkey,
+    amount: u64,
+    nonce: [u8; 32],
+    expiry: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if amount == 0 {
+        return Err(MicaEurError::InvalidAmount.into());
+    }
+    if now > expiry {
+        return Err(MicaEurError::TransferAuthorizationExpired.into());
+    }
+
+    let relayer = ctx.accounts.relayer.key();
+    check_relayer(&ctx.accounts.mint_info, ctx.accounts.relayer_record.as_deref(), &relayer)?;
+
+    let mint = ctx.accounts.mint.key();
+    let owner = ctx.accounts.owner.key();
+    let message = transfer_authorization_message(&mint, &owner, &recipient, amount, &nonce, expiry);
+    verify_ed25519_instruction(&ctx.accounts.instructions, &owner, &message)?;
+
+    let permit_nonce = &mut ctx.accounts.permit_nonce;
+    permit_nonce.owner = owner;
+    permit_nonce.nonce = nonce;
+    permit_nonce.used_at = now;
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+
+    // Sign as the delegate with the permit authority PDA seeds
+    let bump = [ctx.bumps.permit_authority];
+    let seeds = &[PERMIT_AUTHORITY_SEED, mint.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.source_token.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination_token.to_account_info(),
+        authority: ctx.accounts.permit_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    if let Some(relayer_record) = ctx.accounts.relayer_record.as_mut() {
+        relayer_record.record_submission(amount, now);
+        emit!(RelayerActivity {
+            mint,
+            relayer,
+            amount,
+            submission_count: relayer_record.submission_count,
+            submitted_volume: relayer_record.submitted_volume,
+            timestamp: now,
+        });
+    }
+
+    emit!(TransferWithAuthorizationExecuted {
+        mint,
+        owner,
+        recipient,
+        amount,
+        nonce,
+        relayer,
+        timestamp: now,
+    });
+    msg!("Authorized transfer of {} from {} to {}", amount, owner, recipient);
+    Ok(())
+}
@@ -0,0 +1,402 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{memo_transfer::MemoTransfer, BaseStateWithExtensions, StateWithExtensions},
+    state::Account as TokenAccountState,
+};
+
+use crate::aml::BlacklistEntry;
+use crate::constants::*;
+use crate::edd::EddReason;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::{is_kyc_verified, KycStatus, KycUser};
+use crate::limits::eur_to_token_units;
+use crate::policy_staging::StagedPolicy;
+
+/// Stable identifier of a compliance rule and the version of its logic
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RuleId {
+    pub id: u16,
+    pub version: u16,
+}
+
+/// Compliance rules evaluated by the policy engine. An id is never reused; changing
+/// what a rule checks bumps its version and `RULE_SET_VERSION`.
+pub mod rules {
+    use super::RuleId;
+
+    pub const KYC_VERIFIED: RuleId = RuleId { id: 1, version: 1 };
+    pub const MIN_VERIFICATION_LEVEL: RuleId = RuleId { id: 2, version: 1 };
+    pub const SANCTIONS_SCREENING_RECENCY: RuleId = RuleId { id: 3, version: 1 };
+    pub const KYB_LINK: RuleId = RuleId { id: 4, version: 1 };
+    pub const INCOMING_MEMO: RuleId = RuleId { id: 5, version: 1 };
+    pub const NOT_BLACKLISTED: RuleId = RuleId { id: 6, version: 1 };
+    pub const EDD_CLEARANCE: RuleId = RuleId { id: 7, version: 1 };
+    pub const CLASS_TRANSACTION_LIMIT: RuleId = RuleId { id: 8, version: 1 };
+}
+
+/// Version of the rule set compiled into this program
+pub const RULE_SET_VERSION: u32 = 4;
+
+/// Rules making up the active rule set
+pub const ACTIVE_RULES: [RuleId; 8] = [
+    rules::KYC_VERIFIED,
+    rules::MIN_VERIFICATION_LEVEL,
+    rules::SANCTIONS_SCREENING_RECENCY,
+    rules::KYB_LINK,
+    rules::INCOMING_MEMO,
+    rules::NOT_BLACKLISTED,
+    rules::EDD_CLEARANCE,
+    rules::CLASS_TRANSACTION_LIMIT,
+];
+
+/// Hash identifying the active rule set: its version and every rule id and version
+pub fn rule_set_hash() -> [u8; 32] {
+    let mut data = Vec::with_capacity(4 + ACTIVE_RULES.len() * 4);
+    data.extend_from_slice(&RULE_SET_VERSION.to_le_bytes());
+    for rule in ACTIVE_RULES.iter() {
+        data.extend_from_slice(&rule.id.to_le_bytes());
+        data.extend_from_slice(&rule.version.to_le_bytes());
+    }
+    hash::hash(&data).to_bytes()
+}
+
+/// Tunable thresholds of a rule set. The active rule set uses `ACTIVE_POLICY_PARAMS`;
+/// a staged rule set carries its own in a `StagedPolicy` account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct PolicyParams {
+    pub min_transfer_level: u8,             // Lowest KYC level allowed to send or receive
+    pub sanctions_screening_min_level: u8,  // KYC level from which screening is required
+    pub max_sanctions_screen_age: i64,      // Oldest accepted screening, in seconds
+    pub enforce_blacklist: bool,            // Whether blacklisted wallets are rejected
+}
+
+/// Thresholds of the rule set compiled into this program
+pub const ACTIVE_POLICY_PARAMS: PolicyParams = PolicyParams {
+    min_transfer_level: MIN_VERIFICATION_LEVEL_FOR_TRANSFERS,
+    sanctions_screening_min_level: SANCTIONS_SCREENING_MIN_LEVEL,
+    max_sanctions_screen_age: MAX_SANCTIONS_SCREEN_AGE,
+    enforce_blacklist: true,
+};
+
+/// Outcome of a policy evaluation for one subject
+#[event]
+pub struct ComplianceVerdict {
+    pub subject: Pubkey,              // Wallet the rules were evaluated for
+    pub passed: bool,
+    pub failed_rule: Option<RuleId>,  // First rule that rejected the subject
+    pub rule_set_version: u32,
+    pub timestamp: i64,
+}
+
+type RuleResult = std::result::Result<(), (RuleId, MicaEurError)>;
+
+fn rule(passed: bool, rule: RuleId, error: MicaEurError) -> RuleResult {
+    if passed {
+        Ok(())
+    } else {
+        Err((rule, error))
+    }
+}
+
+// Emit the verdict of an evaluation and turn a rejecting rule into its error
+fn conclude(subject: Pubkey, outcome: RuleResult) -> Result<()> {
+    let failed_rule = outcome.as_ref().err().map(|(rule, _)| *rule);
+    emit!(ComplianceVerdict {
+        subject,
+        passed: failed_rule.is_none(),
+        failed_rule,
+        rule_set_version: RULE_SET_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    if let Some(rule) = failed_rule {
+        msg!("Compliance rule {} v{} rejected {}", rule.id, rule.version, subject);
+    }
+    outcome.map_err(|(_, error)| error.into())
+}
+
+/// Emitted when a staged rule set would have decided differently from the active one
+#[event]
+pub struct PolicyDivergence {
+    pub subject: Pubkey,
+    pub active_failed_rule: Option<RuleId>,  // `None` if the active rule set passed
+    pub staged_failed_rule: Option<RuleId>,  // `None` if the staged rule set passed
+    pub rule_set_version: u32,
+    pub staged_rule_set_version: u32,
+    pub timestamp: i64,
+}
+
+/// Class of a KYC user, deciding which compliance policy applies to it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum AccountClass {
+    Retail,
+    Merchant,
+    Institutional,
+    SupervisedInstitution,
+}
+
+/// Compliance requirements attached to an account class
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ClassPolicy {
+    pub min_verification_level: u8,  // Lowest KYC level allowed in the class
+    pub max_transaction_eur: u64,    // Largest single operation in whole EUR
+    pub requires_kyb: bool,          // A KYB (business verification) record must be linked
+    pub requires_incoming_memo: bool,// Incoming transfers must carry a memo
+}
+
+impl AccountClass {
+    /// Policy enforced for accounts of this class
+    pub fn policy(&self) -> ClassPolicy {
+        match self {
+            AccountClass::Retail => ClassPolicy {
+                min_verification_level: MIN_VERIFICATION_LEVEL_FOR_TRANSFERS,
+                max_transaction_eur: RETAIL_MAX_TRANSACTION_EUR,
+                requires_kyb: false,
+                requires_incoming_memo: false,
+            },
+            AccountClass::Merchant => ClassPolicy {
+                min_verification_level: MIN_VERIFICATION_LEVEL_FOR_MINT_REDEEM,
+                max_transaction_eur: MAX_TRANSACTION_EUR,
+                requires_kyb: true,
+                requires_incoming_memo: true,
+            },
+            AccountClass::Institutional => ClassPolicy {
+                min_verification_level: 3,
+                max_transaction_eur: INSTITUTIONAL_MAX_TRANSACTION_EUR,
+                requires_kyb: true,
+                requires_incoming_memo: false,
+            },
+            // Banks and EMIs admitted to the fast lane are held to a daily cap instead
+            AccountClass::SupervisedInstitution => ClassPolicy {
+                min_verification_level: 3,
+                max_transaction_eur: u64::MAX,
+                requires_kyb: true,
+                requires_incoming_memo: false,
+            },
+        }
+    }
+}
+
+// High verification tiers need a recent sanctions screening attestation
+fn screening_rules(kyc_user: &KycUser, params: &PolicyParams, now: i64) -> RuleResult {
+    if kyc_user.verification_level < params.sanctions_screening_min_level {
+        return Ok(());
+    }
+    rule(
+        kyc_user.sanctions_screen_date != 0
+            && now.saturating_sub(kyc_user.sanctions_screen_date) <= params.max_sanctions_screen_age,
+        rules::SANCTIONS_SCREENING_RECENCY,
+        MicaEurError::SanctionsScreeningOutdated,
+    )
+}
+
+// A user must be verified, at `min_level` or above, and recently screened if required
+fn party_rules(kyc_user: &KycUser, min_level: u8, params: &PolicyParams, now: i64) -> RuleResult {
+    rule(
+        kyc_user.status == KycStatus::Verified,
+        rules::KYC_VERIFIED,
+        MicaEurError::UserNotVerified,
+    )?;
+    rule(
+        is_kyc_verified(kyc_user),
+        rules::KYC_VERIFIED,
+        MicaEurError::UserVerificationExpired,
+    )?;
+    rule(
+        kyc_user.verification_level >= min_level,
+        rules::MIN_VERIFICATION_LEVEL,
+        MicaEurError::InsufficientVerificationLevel,
+    )?;
+    screening_rules(kyc_user, params, now)
+}
+
+// A blacklist entry in force rejects the wallet
+fn blacklist_rules(blacklist_entry: Option<&BlacklistEntry>, now: i64) -> RuleResult {
+    rule(
+        !blacklist_entry.is_some_and(|entry| entry.in_force(now)),
+        rules::NOT_BLACKLISTED,
+        MicaEurError::UserBlacklisted,
+    )
+}
+
+/// Check that a user may receive newly minted tokens
+pub fn check_mint_recipient(
+    kyc_user: &KycUser,
+    blacklist_entry: Option<&BlacklistEntry>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let outcome = blacklist_rules(blacklist_entry, now).and_then(|_| {
+        party_rules(kyc_user, MIN_VERIFICATION_LEVEL_FOR_MINT_REDEEM, &ACTIVE_POLICY_PARAMS, now)
+    });
+    conclude(kyc_user.user, outcome)
+}
+
+/// Check that a user's token account may be activated for transfers
+pub fn check_account_holder(
+    kyc_user: &KycUser,
+    blacklist_entry: Option<&BlacklistEntry>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let outcome = blacklist_rules(blacklist_entry, now).and_then(|_| {
+        party_rules(kyc_user, MIN_VERIFICATION_LEVEL_FOR_TRANSFERS, &ACTIVE_POLICY_PARAMS, now)
+    });
+    conclude(kyc_user.user, outcome)
+}
+
+/// Check that a wallet redeeming tokens is not blacklisted
+pub fn check_redeemer(wallet: Pubkey, blacklist_entry: Option<&BlacklistEntry>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    conclude(wallet, blacklist_rules(blacklist_entry, now))
+}
+
+/// Check that both parties of a transfer may send and receive tokens
+pub fn check_transfer_parties(sender: &KycUser, recipient: &KycUser) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    conclude(
+        sender.user,
+        party_rules(sender, MIN_VERIFICATION_LEVEL_FOR_TRANSFERS, &ACTIVE_POLICY_PARAMS, now),
+    )?;
+    conclude(
+        recipient.user,
+        party_rules(recipient, MIN_VERIFICATION_LEVEL_FOR_TRANSFERS, &ACTIVE_POLICY_PARAMS, now),
+    )
+}
+
+// Rules for one party of a token transfer under the given thresholds
+fn transfer_party_rules(
+    kyc_user: Option<&KycUser>,
+    blacklisted: bool,
+    params: &PolicyParams,
+    now: i64,
+) -> RuleResult {
+    rule(
+        !(params.enforce_blacklist && blacklisted),
+        rules::NOT_BLACKLISTED,
+        MicaEurError::UserBlacklisted,
+    )?;
+    match kyc_user {
+        Some(kyc_user) => party_rules(kyc_user, params.min_transfer_level, params, now),
+        None => Err((rules::KYC_VERIFIED, MicaEurError::KycRegistrationMissing)),
+    }
+}
+
+/// Check one party of a token transfer seen by the transfer hook. `kyc_user` is
+/// `None` when the wallet never registered for KYC. When a staged rule set is
+/// given it is evaluated too, and a `PolicyDivergence` is emitted if it would
+/// have decided differently; only the active rule set is enforced.
+pub fn check_transfer_party(
+    wallet: Pubkey,
+    kyc_user: Option<&KycUser>,
+    blacklisted: bool,
+    staged: Option<&StagedPolicy>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let outcome = transfer_party_rules(kyc_user, blacklisted, &ACTIVE_POLICY_PARAMS, now);
+
+    if let Some(staged) = staged {
+        let active_failed_rule = outcome.as_ref().err().map(|(rule, _)| *rule);
+        let staged_failed_rule = transfer_party_rules(kyc_user, blacklisted, &staged.params, now)
+            .err()
+            .map(|(rule, _)| rule);
+        if active_failed_rule.is_some() != staged_failed_rule.is_some() {
+            emit!(PolicyDivergence {
+                subject: wallet,
+                active_failed_rule,
+                staged_failed_rule,
+                rule_set_version: RULE_SET_VERSION,
+                staged_rule_set_version: staged.rule_set_version,
+                timestamp: now,
+            });
+        }
+    }
+
+    conclude(wallet, outcome)
+}
+
+/// Why a transfer needs enhanced due diligence, `None` if it may settle directly.
+/// A `threshold` of zero only routes flagged users to review.
+pub fn edd_reason(sender: &KycUser, recipient: &KycUser, amount: u64, threshold: u64) -> Option<EddReason> {
+    if sender.edd_required {
+        Some(EddReason::FlaggedSender)
+    } else if recipient.edd_required {
+        Some(EddReason::FlaggedRecipient)
+    } else if threshold > 0 && amount >= threshold {
+        Some(EddReason::LargeTransfer)
+    } else {
+        None
+    }
+}
+
+/// Check that a direct transfer does not need EDD review; such transfers must go
+/// through the pre-approval queue instead
+pub fn check_edd_clearance(
+    sender: &KycUser,
+    recipient: &KycUser,
+    amount: u64,
+    threshold: u64,
+) -> Result<()> {
+    conclude(
+        sender.user,
+        rule(
+            edd_reason(sender, recipient, amount, threshold).is_none(),
+            rules::EDD_CLEARANCE,
+            MicaEurError::EddApprovalRequired,
+        ),
+    )
+}
+
+/// Check that a transfer of `amount` stays within the largest single operation of
+/// the sender's account class, on a mint with `decimals`
+pub fn check_class_transaction(sender: &KycUser, amount: u64, decimals: u8) -> Result<()> {
+    let max_transaction = eur_to_token_units(sender.account_class.policy().max_transaction_eur, decimals);
+    conclude(
+        sender.user,
+        rule(
+            amount <= max_transaction,
+            rules::CLASS_TRANSACTION_LIMIT,
+            MicaEurError::TransactionAmountExceedsMaximum,
+        ),
+    )
+}
+
+/// Check that a KYC user satisfies every requirement of `class`. `token_account`,
+/// the user's Token-2022 account on a MiCA EUR mint, is only read when the class
+/// requires incoming memos.
+pub fn check_class_requirements(
+    kyc_user: &KycUser,
+    class: AccountClass,
+    kyb_hash: &[u8; 32],
+    token_account: Option<&AccountInfo>,
+) -> Result<()> {
+    let policy = class.policy();
+
+    let memo_enabled = match token_account {
+        Some(token_account) if policy.requires_incoming_memo => {
+            let data = token_account.try_borrow_data()?;
+            StateWithExtensions::<TokenAccountState>::unpack(&data)?
+                .get_extension::<MemoTransfer>()
+                .map(|ext| bool::from(ext.require_incoming_transfer_memos))
+                .unwrap_or(false)
+        }
+        _ => false,
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let outcome = party_rules(kyc_user, policy.min_verification_level, &ACTIVE_POLICY_PARAMS, now)
+        .and_then(|_| {
+            rule(
+                !policy.requires_kyb || *kyb_hash != [0u8; 32],
+                rules::KYB_LINK,
+                MicaEurError::KybLinkRequired,
+            )
+        })
+        .and_then(|_| {
+            rule(
+                !policy.requires_incoming_memo || memo_enabled,
+                rules::INCOMING_MEMO,
+                MicaEurError::IncomingMemoRequired,
+            )
+        });
+
+    conclude(kyc_user.user, outcome)
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::policy::{PolicyParams, RULE_SET_VERSION};
+use crate::MintInfo;
+
+/// Rule set evaluated in dry-run mode next to the active one. The transfer hook
+/// never enforces it; it only reports transfers it would have decided differently.
+#[account]
+#[derive(InitSpace)]
+pub struct StagedPolicy {
+    pub mint: Pubkey,                 // The EUR mint
+    pub rule_set_version: u32,        // Version the staged rule set would activate as
+    pub params: PolicyParams,         // Thresholds under evaluation
+    pub staged_by: Pubkey,            // Issuer that staged the rule set
+    pub staged_time: i64,             // When the rule set was (re)staged
+}
+
+#[event]
+pub struct PolicyStaged {
+    pub mint: Pubkey,
+    pub rule_set_version: u32,
+    pub params: PolicyParams,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StagedPolicyCleared {
+    pub mint: Pubkey,
+    pub rule_set_version: u32,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct StagePolicy<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        constraint = mint_info.is_active,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [STAGED_POLICY_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + StagedPolicy::INIT_SPACE,
+    )]
+    pub staged_policy: Account<'info, StagedPolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClearStagedPolicy<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        close = issuer,
+        seeds = [STAGED_POLICY_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub staged_policy: Account<'info, StagedPolicy>,
+}
+
+// Stage a rule set for dry-run evaluation against live transfers, replacing any staged one
+pub(crate) fn stage_policy(
+    ctx: Context<StagePolicy>,
+    rule_set_version: u32,
+    params: PolicyParams,
+) -> Result<()> {
+    if rule_set_version <= RULE_SET_VERSION {
+        return Err(MicaEurError::StagedPolicyVersionNotNewer.into());
+    }
+    if params.min_transfer_level > 3
+        || params.sanctions_screening_min_level > 3
+        || params.max_sanctions_screen_age <= 0
+    {
+        return Err(MicaEurError::InvalidStagedPolicy.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let staged_policy = &mut ctx.accounts.staged_policy;
+    staged_policy.mint = ctx.accounts.mint_info.mint;
+    staged_policy.rule_set_version = rule_set_version;
+    staged_policy.params = params;
+    staged_policy.staged_by = ctx.accounts.issuer.key();
+    staged_policy.staged_time = now;
+
+    emit!(PolicyStaged {
+        mint: staged_policy.mint,
+        rule_set_version,
+        params,
+        timestamp: now,
+    });
+
+    msg!("Staged compliance rule set version {} for dry-run", rule_set_version);
+    Ok(())
+}
+
+// Stop the dry-run evaluation of the staged rule set
+pub(crate) fn clear_staged_policy(ctx: Context<ClearStagedPolicy>) -> Result<()> {
+    emit!(StagedPolicyCleared {
+        mint: ctx.accounts.staged_policy.mint,
+        rule_set_version: ctx.accounts.staged_policy.rule_set_version,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Cleared staged compliance rule set version {}", ctx.accounts.staged_policy.rule_set_version);
+    Ok(())
+}
\ No newline at end of file
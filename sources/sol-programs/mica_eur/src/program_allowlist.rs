@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Programs the issuer allows to hold tokens of a mint, e.g. the escrow of a
+/// regulated DEX. Token accounts owned by a registered PDA of one of them skip the
+/// transfer hook's party checks; the other side of the transfer is still checked.
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramAllowlist {
+    pub mint: Pubkey,                                      // Mint the allowlist applies to
+    pub programs: [Pubkey; MAX_ALLOWLISTED_PROGRAMS],      // Allowlisted program ids
+    pub program_count: u8,                                 // Number of programs in use
+    pub updated_by: Pubkey,                                // Issuer that last changed it
+    pub last_update: i64,
+}
+
+impl ProgramAllowlist {
+    /// The allowlisted program ids
+    pub fn programs(&self) -> &[Pubkey] {
+        &self.programs[..self.program_count as usize]
+    }
+
+    /// Whether `program` is allowlisted
+    pub fn contains(&self, program: &Pubkey) -> bool {
+        self.programs().contains(program)
+    }
+}
+
+/// A PDA of an allowlisted program, registered with the seeds proving its
+/// derivation. The transfer hook looks it up by the owner of a token account.
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramEscrow {
+    pub mint: Pubkey,                 // Mint the registration applies to
+    pub authority: Pubkey,            // The PDA owning the escrow token accounts
+    pub program: Pubkey,              // Program the PDA is derived from
+    pub registered_by: Pubkey,        // Who paid for the registration
+    pub registered_at: i64,
+}
+
+/// Whether `escrow` is a registered PDA of a program still on the allowlist
+pub fn is_allowlisted_escrow(allowlist: Option<&ProgramAllowlist>, escrow: Option<&ProgramEscrow>) -> bool {
+    match (allowlist, escrow) {
+        (Some(allowlist), Some(escrow)) => allowlist.contains(&escrow.program),
+        _ => false,
+    }
+}
+
+/// Emitted when the issuer allowlists a program
+#[event]
+pub struct ProgramAllowlisted {
+    pub mint: Pubkey,
+    pub program: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer removes a program from the allowlist
+#[event]
+pub struct ProgramDelisted {
+    pub mint: Pubkey,
+    pub program: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a PDA of an allowlisted program is registered
+#[event]
+pub struct ProgramEscrowRegistered {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub program: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct AllowlistProgram<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [PROGRAM_ALLOWLIST_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + ProgramAllowlist::INIT_SPACE,
+    )]
+    pub program_allowlist: Box<Account<'info, ProgramAllowlist>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DelistProgram<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_ALLOWLIST_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub program_allowlist: Box<Account<'info, ProgramAllowlist>>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterProgramEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub program_allowlist: Box<Account<'info, ProgramAllowlist>>,
+
+    /// CHECK: Checked in the handler to derive from the given program and seeds
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [PROGRAM_ESCROW_SEED, mint_info.mint.as_ref(), authority.key().as_ref()],
+        bump,
+        space = 8 + ProgramEscrow::INIT_SPACE,
+    )]
+    pub program_escrow: Account<'info, ProgramEscrow>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Allow a program's PDAs to hold and move tokens without KYC (issuer only)
+pub(crate) fn allowlist_program(ctx: Context<AllowlistProgram>, program: Pubkey) -> Result<()> {
+    if program == Pubkey::default() || program == crate::ID {
+        return Err(MicaEurError::InvalidAllowlistedProgram.into());
+    }
+
+    let allowlist = &mut ctx.accounts.program_allowlist;
+    if allowlist.contains(&program) {
+        return Err(MicaEurError::ProgramAlreadyAllowlisted.into());
+    }
+    let count = allowlist.program_count as usize;
+    if count == MAX_ALLOWLISTED_PROGRAMS {
+        return Err(MicaEurError::ProgramAllowlistFull.into());
+    }
+    allowlist.mint = ctx.accounts.mint_info.mint;
+    allowlist.programs[count] = program;
+    allowlist.program_count += 1;
+    allowlist.updated_by = ctx.accounts.issuer.key();
+    allowlist.last_update = Clock::get()?.unix_timestamp;
+
+    emit!(ProgramAllowlisted {
+        mint: allowlist.mint,
+        program,
+        timestamp: allowlist.last_update,
+    });
+    Ok(())
+}
+
+// Remove a program from the allowlist; its registered PDAs are checked like any
+// other wallet from the next transfer on (issuer only)
+pub(crate) fn delist_program(ctx: Context<DelistProgram>, program: Pubkey) -> Result<()> {
+    let allowlist = &mut ctx.accounts.program_allowlist;
+    let index = allowlist
+        .programs()
+        .iter()
+        .position(|listed| *listed == program)
+        .ok_or(MicaEurError::ProgramNotAllowlisted)?;
+    let last = allowlist.program_count as usize - 1;
+    allowlist.programs[index] = allowlist.programs[last];
+    allowlist.programs[last] = Pubkey::default();
+    allowlist.program_count -= 1;
+    allowlist.updated_by = ctx.accounts.issuer.key();
+    allowlist.last_update = Clock::get()?.unix_timestamp;
+
+    emit!(ProgramDelisted {
+        mint: allowlist.mint,
+        program,
+        timestamp: allowlist.last_update,
+    });
+    Ok(())
+}
+
+// Register a PDA of an allowlisted program. `seeds`, bump included, must derive
+// the authority from the program (anyone can pay for it).
+pub(crate) fn register_program_escrow(
+    ctx: Context<RegisterProgramEscrow>,
+    program: Pubkey,
+    seeds: Vec<Vec<u8>>,
+) -> Result<()> {
+    if !ctx.accounts.program_allowlist.contains(&program) {
+        return Err(MicaEurError::ProgramNotAllowlisted.into());
+    }
+    let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    let derived = Pubkey::create_program_address(&seeds, &program)
+        .map_err(|_| MicaEurError::InvalidProgramEscrow)?;
+    let authority = ctx.accounts.authority.key();
+    if derived != authority {
+        return Err(MicaEurError::InvalidProgramEscrow.into());
+    }
+
+    let escrow = &mut ctx.accounts.program_escrow;
+    escrow.mint = ctx.accounts.mint_info.mint;
+    escrow.authority = authority;
+    escrow.program = program;
+    escrow.registered_by = ctx.accounts.payer.key();
+    escrow.registered_at = Clock::get()?.unix_timestamp;
+
+    emit!(ProgramEscrowRegistered {
+        mint: escrow.mint,
+        authority,
+        program,
+        timestamp: escrow.registered_at,
+    });
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Sequence of reconciliation records written for a mint
+#[account]
+#[derive(InitSpace)]
+pub struct ReconciliationLog {
+    pub mint: Pubkey,                 // The EUR mint
+    pub record_count: u64,            // Records written so far, the next record's ID
+    pub last_record_time: i64,        // When the latest record was written
+}
+
+/// A discrepancy between the chain and the issuer's core-banking ledger that
+/// persisted beyond the reconciliation daemon's threshold. All amounts are in
+/// token units.
+#[account]
+#[derive(InitSpace)]
+pub struct ReconciliationRecord {
+    pub mint: Pubkey,                 // The EUR mint
+    pub record_id: u64,               // Position in the mint's record sequence
+    pub supply: u64,                  // Mint supply, read on-chain when recording
+    pub pending_redemptions: u64,     // Redeemed on-chain, not yet paid out
+    pub registered_deposits: u64,     // Received in the reserve account, not yet minted
+    pub ledger_balance: u64,          // Reserve account balance in the core-banking ledger
+    pub discrepancy: i64,             // Ledger balance minus what the chain accounts for
+    pub ledger_hash: [u8; 32],        // Hash of the ledger extract the figures come from
+    pub first_detected_time: i64,     // When the daemon first saw the discrepancy
+    pub record_time: i64,             // When the record was written
+}
+
+/// Emitted when a persisting reconciliation discrepancy is recorded
+#[event]
+pub struct ReconciliationDiscrepancyRecorded {
+    pub mint: Pubkey,
+    pub record_id: u64,
+    pub supply: u64,
+    pub ledger_balance: u64,
+    pub discrepancy: i64,
+    pub first_detected_time: i64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct RecordReconciliation<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = issuer,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [RECONCILIATION_LOG_SEED, mint.key().as_ref()],
+        bump,
+        space = 8 + ReconciliationLog::INIT_SPACE,
+    )]
+    pub reconciliation_log: Account<'info, ReconciliationLog>,
+
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            RECONCILIATION_RECORD_SEED,
+            mint.key().as_ref(),
+            &reconciliation_log.record_count.to_le_bytes(),
+        ],
+        bump,
+        space = 8 + ReconciliationRecord::INIT_SPACE,
+    )]
+    pub reconciliation_record: Account<'info, ReconciliationRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Ledger balance minus the fiat the chain accounts for: the supply, redemptions
+/// still to be paid out and deposits still to be minted
+pub fn reconciliation_discrepancy(
+    supply: u64,
+    pending_redemptions: u64,
+    registered_deposits: u64,
+    ledger_balance: u64,
+) -> Result<i64> {
+    let expected = supply as i128 + pending_redemptions as i128 + registered_deposits as i128;
+    i64::try_from(ledger_balance as i128 - expected).map_err(|_| MicaEurError::ArithmeticOverflow.into())
+}
+
+// Record a discrepancy the reconciliation daemon has seen persist since
+// `first_detected_time` (issuer only). Balanced figures are not recorded.
+pub(crate) fn record_reconciliation(
+    ctx: Context<RecordReconciliation>,
+    pending_redemptions: u64,
+    registered_deposits: u64,
+    ledger_balance: u64,
+    ledger_hash: [u8; 32],
+    first_detected_time: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let supply = ctx.accounts.mint.supply;
+    let discrepancy =
+        reconciliation_discrepancy(supply, pending_redemptions, registered_deposits, ledger_balance)?;
+    if discrepancy == 0 || first_detected_time <= 0 || first_detected_time > now {
+        return Err(MicaEurError::InvalidReconciliationRecord.into());
+    }
+
+    let log = &mut ctx.accounts.reconciliation_log;
+    let record = &mut ctx.accounts.reconciliation_record;
+    record.mint = ctx.accounts.mint_info.mint;
+    record.record_id = log.record_count;
+    record.supply = supply;
+    record.pending_redemptions = pending_redemptions;
+    record.registered_deposits = registered_deposits;
+    record.ledger_balance = ledger_balance;
+    record.discrepancy = discrepancy;
+    record.ledger_hash = ledger_hash;
+    record.first_detected_time = first_detected_time;
+    record.record_time = now;
+
+    log.mint = record.mint;
+    log.record_count = log
+        .record_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    log.last_record_time = now;
+
+    emit!(ReconciliationDiscrepancyRecorded {
+        mint: record.mint,
+        record_id: record.record_id,
+        supply,
+        ledger_balance,
+        discrepancy,
+        first_detected_time,
+        timestamp: now,
+    });
+    Ok(())
+}
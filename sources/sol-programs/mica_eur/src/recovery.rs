@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::aml::{aml_powers, AmlAuthority};
+use crate::batch_mint::load_pda;
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::freezes::{freeze_reasons, FreezeRecord};
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::kyc_oracle::{is_kyc_verified, KycUser};
+use crate::mint_utils::transfer_checked_with_hook;
+use crate::MintInfo;
+
+/// Lifecycle of a recovery request
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RecoveryStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+/// Request to move a customer's funds from a token account they lost the key of
+/// to a new verified wallet. The issuer files it, an AML authority approves it,
+/// and the funds move once the waiting period has passed.
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryRequest {
+    pub mint: Pubkey,                 // The EUR mint
+    pub source_token_account: Pubkey, // Account the funds are recovered from
+    pub old_owner: Pubkey,            // Its owner when the request was filed
+    pub destination_token_account: Pubkey, // Account of the new verified wallet
+    pub new_owner: Pubkey,            // Owner of the destination account
+    pub evidence_hash: [u8; 32],      // Hash of the identity evidence behind the request
+    pub requested_by: Pubkey,         // Issuer that filed the request
+    pub request_time: i64,
+    pub eta: i64,                     // Earliest execution time
+    pub approved_by: Pubkey,          // AML authority that approved it, default until approved
+    pub approval_time: i64,           // 0 until approved
+    pub status: RecoveryStatus,
+    pub recovered_amount: u64,        // Amount moved, 0 until executed
+    pub resolution_time: i64,         // When executed or cancelled, 0 while pending
+}
+
+/// Emitted when the issuer files a recovery request
+#[event]
+pub struct RecoveryRequested {
+    pub mint: Pubkey,
+    pub recovery_request: Pubkey,
+    pub source_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an AML authority approves a recovery
+#[event]
+pub struct RecoveryApproved {
+    pub recovery_request: Pubkey,
+    pub aml_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when recovered funds are moved or a request is cancelled
+#[event]
+pub struct RecoveryResolved {
+    pub recovery_request: Pubkey,
+    pub status: RecoveryStatus,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct RequestRecovery<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Account whose owner lost the key
+    #[account(
+        constraint = source_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Account of the customer's new wallet
+    #[account(
+        constraint = destination_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// KYC record of the new wallet
+    #[account(
+        seeds = [KYC_USER_SEED, destination_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub new_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [RECOVERY_REQUEST_SEED, source_token_account.key().as_ref()],
+        bump,
+        space = 8 + RecoveryRequest::INIT_SPACE,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    pub officer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [AML_AUTHORITY_SEED, officer.key().as_ref()],
+        bump,
+        constraint = aml_authority.authority == officer.key(),
+    )]
+    pub aml_authority: Account<'info, AmlAuthority>,
+
+    #[account(mut)]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverAccount<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: Permanent delegate of the mint; address checked against its seeds
+    #[account(
+        seeds = [SEIZURE_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub seizure_authority: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [RECOVERY_REQUEST_SEED, source_token_account.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = source_token_account @ MicaEurError::RecoveryAccountMismatch,
+        has_one = destination_token_account @ MicaEurError::RecoveryAccountMismatch,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    #[account(mut)]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// KYC record of the new wallet, which must still be verified
+    #[account(
+        seeds = [KYC_USER_SEED, destination_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub new_kyc_user: Account<'info, KycUser>,
+
+    /// Freeze record of the source account, which may not exist
+    /// CHECK: Address derived from the source account; parsed in the handler
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, source_token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_record: UncheckedAccount<'info>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, recovery_request.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(mut)]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+}
+
+fn check_pending(recovery_request: &RecoveryRequest) -> Result<()> {
+    if recovery_request.status != RecoveryStatus::Pending {
+        return Err(MicaEurError::RecoveryNotPending.into());
+    }
+    Ok(())
+}
+
+// File a request to recover the funds of a token account to the customer's new
+// verified wallet (issuer only). The request can be executed once an AML
+// authority approved it and the waiting period has passed.
+pub(crate) fn request_recovery(ctx: Context<RequestRecovery>, evidence_hash: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let request = &mut ctx.accounts.recovery_request;
+    if request.request_time != 0 && request.status == RecoveryStatus::Pending {
+        return Err(MicaEurError::RecoveryAlreadyRequested.into());
+    }
+    let source = &ctx.accounts.source_token_account;
+    let destination = &ctx.accounts.destination_token_account;
+    if source.key() == destination.key() || source.owner == destination.owner {
+        return Err(MicaEurError::RecoveryAccountMismatch.into());
+    }
+    if !is_kyc_verified(&ctx.accounts.new_kyc_user) {
+        return Err(MicaEurError::UserNotVerified.into());
+    }
+
+    request.mint = ctx.accounts.mint.key();
+    request.source_token_account = source.key();
+    request.old_owner = source.owner;
+    request.destination_token_account = destination.key();
+    request.new_owner = destination.owner;
+    request.evidence_hash = evidence_hash;
+    request.requested_by = ctx.accounts.issuer.key();
+    request.request_time = now;
+    request.eta = now
+        .checked_add(RECOVERY_WAITING_PERIOD)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    request.approved_by = Pubkey::default();
+    request.approval_time = 0;
+    request.status = RecoveryStatus::Pending;
+    request.recovered_amount = 0;
+    request.resolution_time = 0;
+
+    emit!(RecoveryRequested {
+        mint: request.mint,
+        recovery_request: request.key(),
+        source_token_account: request.source_token_account,
+        destination_token_account: request.destination_token_account,
+        evidence_hash,
+        eta: request.eta,
+        timestamp: now,
+    });
+    msg!("Recovery of {} requested until {}", request.source_token_account, request.eta);
+    Ok(())
+}
+
+// Approve a pending recovery (active AML authorities holding the seizure power)
+pub(crate) fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+    let aml_authority = &mut ctx.accounts.aml_authority;
+    if !aml_authority.is_active {
+        return Err(MicaEurError::AmlAuthorityInactive.into());
+    }
+    if !aml_authority.has_power(aml_powers::SEIZE_FUNDS) {
+        return Err(MicaEurError::UnauthorizedAmlAuthority.into());
+    }
+    let request = &mut ctx.accounts.recovery_request;
+    check_pending(request)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    request.approved_by = aml_authority.authority;
+    request.approval_time = now;
+    aml_authority.last_action_time = now;
+
+    emit!(RecoveryApproved {
+        recovery_request: request.key(),
+        aml_authority: request.approved_by,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Move the whole balance of the source account to the new wallet (issuer only),
+// through the permanent delegate, once the request is approved and due. A frozen
+// source is thawed for the transfer and frozen again; a freeze recorded for any
+// reason other than the key loss blocks the recovery. The remaining accounts are
+// the transfer hook's extra accounts for the transfer.
+pub(crate) fn recover_account<'info>(ctx: Context<'_, '_, '_, 'info, RecoverAccount<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let request = &ctx.accounts.recovery_request;
+    check_pending(request)?;
+    if request.approved_by == Pubkey::default() {
+        return Err(MicaEurError::RecoveryNotApproved.into());
+    }
+    if now < request.eta {
+        return Err(MicaEurError::RecoveryNotDue.into());
+    }
+    if ctx.accounts.destination_token_account.owner != request.new_owner {
+        return Err(MicaEurError::RecoveryAccountMismatch.into());
+    }
+    if !is_kyc_verified(&ctx.accounts.new_kyc_user) {
+        return Err(MicaEurError::UserNotVerified.into());
+    }
+    let source_key = ctx.accounts.source_token_account.key();
+    let freeze_record = load_pda::<FreezeRecord>(
+        &ctx.accounts.freeze_record,
+        &[FREEZE_RECORD_SEED, source_key.as_ref()],
+    )?;
+    if freeze_record.is_some_and(|record| {
+        record.in_force(now)
+            && record.reason_code != freeze_reasons::WALLET_MIGRATED
+            && record.reason_code != freeze_reasons::ACCOUNT_COMPROMISE
+    }) {
+        return Err(MicaEurError::RecoveryBlockedByFreeze.into());
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let source = ctx.accounts.source_token_account.to_account_info();
+    let amount = ctx.accounts.source_token_account.amount;
+    let frozen = ctx.accounts.source_token_account.is_frozen();
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    if frozen {
+        let cpi_accounts = token_2022::ThawAccount {
+            account: source.clone(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::thaw_account(CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer))?;
+    }
+
+    // The permanent delegate, the seizure authority PDA, moves the funds, so the hook
+    // skips its party checks: the source is usually frozen for the key loss
+    let seizure_bump = [ctx.bumps.seizure_authority];
+    let seizure_seeds = &[SEIZURE_AUTHORITY_SEED, mint_key.as_ref(), &seizure_bump];
+    let seizure_signer = &[&seizure_seeds[..]];
+    let cpi_accounts = token_2022::TransferChecked {
+        from: source.clone(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination_token_account.to_account_info(),
+        authority: ctx.accounts.seizure_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, seizure_signer)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+
+    if frozen {
+        let cpi_accounts = token_2022::FreezeAccount {
+            account: source,
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::freeze_account(CpiContext::new_with_signer(token_program, cpi_accounts, signer))?;
+    }
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let request = &mut ctx.accounts.recovery_request;
+    request.status = RecoveryStatus::Executed;
+    request.recovered_amount = amount;
+    request.resolution_time = now;
+
+    emit!(RecoveryResolved {
+        recovery_request: request.key(),
+        status: RecoveryStatus::Executed,
+        amount,
+        timestamp: now,
+    });
+    msg!("Recovered {} from {} to {}", amount, source_key, request.destination_token_account);
+    Ok(())
+}
+
+// Withdraw a pending recovery (issuer only)
+pub(crate) fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+    let request = &mut ctx.accounts.recovery_request;
+    check_pending(request)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    request.status = RecoveryStatus::Cancelled;
+    request.resolution_time = now;
+
+    emit!(RecoveryResolved {
+        recovery_request: request.key(),
+        status: RecoveryStatus::Cancelled,
+        amount: 0,
+        timestamp: now,
+    });
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::mint_utils::check_string_len;
+use crate::MintInfo;
+
+/// Read access the issuer grants a national competent authority. Audit windows
+/// exported for it anchor the data the authority pulls off-chain.
+#[account]
+#[derive(InitSpace)]
+pub struct RegulatorAccess {
+    pub mint: Pubkey,                 // Mint the access covers
+    pub regulator: Pubkey,            // Key of the authority
+    #[max_len(MAX_AUTHORITY_ID_LEN)]
+    pub authority_id: String,         // External identifier, e.g. LEI
+    pub granted_by: Pubkey,           // Issuer who granted the access
+    pub granted_at: i64,
+    pub expires_at: i64,              // 0 for no expiry
+    pub revoked_at: i64,              // 0 unless revoked
+    pub export_count: u64,            // Audit windows exported, the sequence of the next one
+}
+
+impl RegulatorAccess {
+    /// Whether the access is in force at `now`
+    pub fn is_active(&self, now: i64) -> bool {
+        self.revoked_at == 0 && (self.expires_at == 0 || now < self.expires_at)
+    }
+}
+
+/// Emitted when the issuer grants or renews a regulator's read access
+#[event]
+pub struct RegulatorAccessGranted {
+    pub mint: Pubkey,
+    pub regulator: Pubkey,
+    pub authority_id: String,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer revokes a regulator's read access
+#[event]
+pub struct RegulatorAccessRevoked {
+    pub mint: Pubkey,
+    pub regulator: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Digest of the program events in a time window, exported for a regulator
+#[event]
+pub struct AuditWindowExported {
+    pub mint: Pubkey,
+    pub regulator: Pubkey,
+    pub sequence: u64,                // Per-regulator export number, from 0
+    pub from: i64,                    // Window start, inclusive
+    pub to: i64,                      // Window end, exclusive
+    pub event_count: u64,
+    pub digest: [u8; 32],             // Merkle root over the `audit_event_hash`es, zero for no events
+    pub exported_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(regulator: Pubkey)]
+pub struct GrantRegulatorAccess<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [REGULATOR_ACCESS_SEED, mint_info.mint.as_ref(), regulator.as_ref()],
+        bump,
+        space = 8 + RegulatorAccess::INIT_SPACE,
+    )]
+    pub regulator_access: Account<'info, RegulatorAccess>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRegulatorAccess<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        seeds = [REGULATOR_ACCESS_SEED, mint_info.mint.as_ref(), regulator_access.regulator.as_ref()],
+        bump,
+    )]
+    pub regulator_access: Account<'info, RegulatorAccess>,
+}
+
+#[derive(Accounts)]
+pub struct ExportAuditWindow<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        seeds = [REGULATOR_ACCESS_SEED, mint_info.mint.as_ref(), regulator_access.regulator.as_ref()],
+        bump,
+    )]
+    pub regulator_access: Account<'info, RegulatorAccess>,
+}
+
+/// Leaf of an audit window digest: SHA-256 of the domain separator and the event
+/// as logged by `emit!`, its discriminator followed by its fields
+pub fn audit_event_hash(event_data: &[u8]) -> [u8; 32] {
+    hash::hashv(&[AUDIT_EVENT_DOMAIN, event_data]).to_bytes()
+}
+
+// Grant a regulator read access to the mint's audit exports until `expires_at`,
+// 0 for no expiry (issuer only). Granting again renews a revoked or expired access.
+pub(crate) fn grant_regulator_access(
+    ctx: Context<GrantRegulatorAccess>,
+    regulator: Pubkey,
+    authority_id: String,
+    expires_at: i64,
+) -> Result<()> {
+    check_string_len(&authority_id, MAX_AUTHORITY_ID_LEN)?;
+    let now = Clock::get()?.unix_timestamp;
+    if regulator == Pubkey::default() || (expires_at != 0 && expires_at <= now) {
+        return Err(MicaEurError::InvalidExpiryDate.into());
+    }
+
+    let access = &mut ctx.accounts.regulator_access;
+    access.mint = ctx.accounts.mint_info.mint;
+    access.regulator = regulator;
+    access.authority_id = authority_id.clone();
+    access.granted_by = ctx.accounts.issuer.key();
+    access.granted_at = now;
+    access.expires_at = expires_at;
+    access.revoked_at = 0;
+
+    emit!(RegulatorAccessGranted {
+        mint: access.mint,
+        regulator,
+        authority_id,
+        expires_at,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Revoke a regulator's read access (issuer only). Its past exports stay on record.
+pub(crate) fn revoke_regulator_access(ctx: Context<RevokeRegulatorAccess>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let access = &mut ctx.accounts.regulator_access;
+    if !access.is_active(now) {
+        return Err(MicaEurError::RegulatorAccessInactive.into());
+    }
+    access.revoked_at = now;
+
+    emit!(RegulatorAccessRevoked {
+        mint: access.mint,
+        regulator: access.regulator,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Anchor the digest of the program events emitted in [from, to) for a regulator
+// with access (issuer only). The regulator rebuilds the digest from its own pull
+// of the events to reconcile it.
+pub(crate) fn export_audit_window(
+    ctx: Context<ExportAuditWindow>,
+    from: i64,
+    to: i64,
+    event_count: u64,
+    digest: [u8; 32],
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let access = &mut ctx.accounts.regulator_access;
+    if !access.is_active(now) {
+        return Err(MicaEurError::RegulatorAccessInactive.into());
+    }
+    if from >= to || to > now || (event_count == 0) != (digest == [0; 32]) {
+        return Err(MicaEurError::InvalidAuditWindow.into());
+    }
+
+    let sequence = access.export_count;
+    access.export_count = sequence.checked_add(1).ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(AuditWindowExported {
+        mint: access.mint,
+        regulator: access.regulator,
+        sequence,
+        from,
+        to,
+        event_count,
+        digest,
+        exported_by: ctx.accounts.issuer.key(),
+        timestamp: now,
+    });
+    msg!("Audit window {} exported for {}: {} events", sequence, access.regulator, event_count);
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Whether a relayer may submit transactions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RelayerStatus {
+    Active,
+    Suspended,
+}
+
+/// Admission rules for the relayers paying fees on behalf of users. Once it exists,
+/// signed transfer authorizations are only accepted from active registered relayers.
+#[account]
+#[derive(InitSpace)]
+pub struct RelayerRegistry {
+    pub mint: Pubkey,                 // Mint the registry applies to
+    pub min_stake: u64,               // Lamports a relayer must stake to register
+    pub relayer_count: u32,           // Registered relayers
+    pub suspended_count: u32,         // Of which suspended
+    pub updated_by: Pubkey,           // Issuer that last changed the rules
+    pub last_update: i64,
+}
+
+/// A registered relayer. Its stake is held as lamports of this account and
+/// returned when it deregisters.
+#[account]
+#[derive(InitSpace)]
+pub struct Relayer {
+    pub mint: Pubkey,                 // Mint the relayer submits transactions for
+    pub relayer: Pubkey,              // Fee payer key of the relayer
+    pub stake: u64,                   // Lamports staked on registration
+    pub status: RelayerStatus,
+    pub registered_at: i64,
+    pub submission_count: u64,        // Transactions submitted
+    pub submitted_volume: u64,        // Tokens moved by them
+    pub last_submission_at: i64,      // 0 before the first one
+    pub suspended_at: i64,            // 0 unless suspended
+}
+
+impl Relayer {
+    /// Count a submitted transaction moving `amount`
+    pub fn record_submission(&mut self, amount: u64, now: i64) {
+        self.submission_count = self.submission_count.saturating_add(1);
+        self.submitted_volume = self.submitted_volume.saturating_add(amount);
+        self.last_submission_at = now;
+    }
+}
+
+/// Check the relayer submitting a transaction of `mint_info`. Without a registry any
+/// fee payer may relay; with one, `relayer` must be the active record of `submitter`.
+pub fn check_relayer(mint_info: &MintInfo, relayer: Option<&Relayer>, submitter: &Pubkey) -> Result<()> {
+    if mint_info.relayer_registry == Pubkey::default() {
+        return Ok(());
+    }
+    match relayer {
+        Some(relayer) if relayer.relayer == *submitter && relayer.mint == mint_info.mint => {
+            if relayer.status != RelayerStatus::Active {
+                return Err(MicaEurError::RelayerSuspended.into());
+            }
+            Ok(())
+        }
+        _ => Err(MicaEurError::RelayerNotRegistered.into()),
+    }
+}
+
+/// Emitted when the issuer sets the relayer admission rules
+#[event]
+pub struct RelayerRegistryConfigured {
+    pub mint: Pubkey,
+    pub min_stake: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a relayer registers with its stake
+#[event]
+pub struct RelayerRegistered {
+    pub mint: Pubkey,
+    pub relayer: Pubkey,
+    pub stake: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer suspends or reinstates a relayer
+#[event]
+pub struct RelayerStatusChanged {
+    pub mint: Pubkey,
+    pub relayer: Pubkey,
+    pub status: RelayerStatus,
+    pub timestamp: i64,
+}
+
+/// Emitted when a relayer leaves and takes its stake back
+#[event]
+pub struct RelayerDeregistered {
+    pub mint: Pubkey,
+    pub relayer: Pubkey,
+    pub stake: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted for every transaction a registered relayer submits, with its running
+/// totals, so the issuer can spot abusive relayers
+#[event]
+pub struct RelayerActivity {
+    pub mint: Pubkey,
+    pub relayer: Pubkey,
+    pub amount: u64,
+    pub submission_count: u64,
+    pub submitted_volume: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureRelayerRegistry<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [RELAYER_REGISTRY_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + RelayerRegistry::INIT_SPACE,
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_REGISTRY_SEED, relayer_registry.mint.as_ref()],
+        bump,
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        init,
+        payer = relayer,
+        seeds = [RELAYER_SEED, relayer_registry.mint.as_ref(), relayer.key().as_ref()],
+        bump,
+        space = 8 + Relayer::INIT_SPACE,
+    )]
+    pub relayer_record: Account<'info, Relayer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRelayerStatus<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_REGISTRY_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, mint_info.mint.as_ref(), relayer_record.relayer.as_ref()],
+        bump,
+    )]
+    pub relayer_record: Account<'info, Relayer>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterRelayer<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_REGISTRY_SEED, relayer_registry.mint.as_ref()],
+        bump,
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [RELAYER_SEED, relayer_registry.mint.as_ref(), relayer.key().as_ref()],
+        bump,
+    )]
+    pub relayer_record: Account<'info, Relayer>,
+}
+
+// Set the stake relayers must put up (issuer only). From then on signed transfer
+// authorizations are only accepted from active registered relayers.
+pub(crate) fn configure_relayer_registry(ctx: Context<ConfigureRelayerRegistry>, min_stake: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.relayer_registry;
+    registry.mint = ctx.accounts.mint_info.mint;
+    registry.min_stake = min_stake;
+    registry.updated_by = ctx.accounts.issuer.key();
+    registry.last_update = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.mint_info.relayer_registry = registry.key();
+
+    emit!(RelayerRegistryConfigured {
+        mint: registry.mint,
+        min_stake,
+        timestamp: registry.last_update,
+    });
+    Ok(())
+}
+
+// Register as a relayer, staking at least the registry's minimum
+pub(crate) fn register_relayer(ctx: Context<RegisterRelayer>, stake: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.relayer_registry;
+    if stake < registry.min_stake {
+        return Err(MicaEurError::RelayerStakeTooLow.into());
+    }
+    if stake > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.relayer.to_account_info(),
+            to: ctx.accounts.relayer_record.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, stake)?;
+    }
+    registry.relayer_count = registry.relayer_count.saturating_add(1);
+
+    let now = Clock::get()?.unix_timestamp;
+    let relayer = &mut ctx.accounts.relayer_record;
+    relayer.mint = registry.mint;
+    relayer.relayer = ctx.accounts.relayer.key();
+    relayer.stake = stake;
+    relayer.status = RelayerStatus::Active;
+    relayer.registered_at = now;
+
+    emit!(RelayerRegistered {
+        mint: relayer.mint,
+        relayer: relayer.relayer,
+        stake,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Suspend or reinstate a relayer (issuer only). A suspended relayer cannot submit
+// transactions nor withdraw its stake.
+pub(crate) fn set_relayer_status(ctx: Context<SetRelayerStatus>, status: RelayerStatus) -> Result<()> {
+    let relayer = &mut ctx.accounts.relayer_record;
+    if relayer.status == status {
+        return Err(MicaEurError::RelayerStatusUnchanged.into());
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let registry = &mut ctx.accounts.relayer_registry;
+    match status {
+        RelayerStatus::Suspended => {
+            registry.suspended_count = registry.suspended_count.saturating_add(1);
+            relayer.suspended_at = now;
+        }
+        RelayerStatus::Active => {
+            registry.suspended_count = registry.suspended_count.saturating_sub(1);
+            relayer.suspended_at = 0;
+        }
+    }
+    relayer.status = status;
+
+    emit!(RelayerStatusChanged {
+        mint: relayer.mint,
+        relayer: relayer.relayer,
+        status,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Leave the registry, closing the record and returning the stake with its rent
+pub(crate) fn deregister_relayer(ctx: Context<DeregisterRelayer>) -> Result<()> {
+    let relayer = &ctx.accounts.relayer_record;
+    if relayer.status != RelayerStatus::Active {
+        return Err(MicaEurError::RelayerSuspended.into());
+    }
+    let registry = &mut ctx.accounts.relayer_registry;
+    registry.relayer_count = registry.relayer_count.saturating_sub(1);
+
+    emit!(RelayerDeregistered {
+        mint: relayer.mint,
+        relayer: relayer.relayer,
+        stake: relayer.stake,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
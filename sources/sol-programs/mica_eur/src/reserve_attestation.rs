@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Independent sign-off of the reserve proof. The issuer posts the merkle root with
+/// `update_reserve_proof`; the auditor then confirms the same root within the
+/// attestation window. Posting a new root leaves the mint unattested until the
+/// auditor signs again.
+#[account]
+#[derive(InitSpace)]
+pub struct AuditorAttestation {
+    pub mint: Pubkey,                 // Mint whose reserve proof is attested
+    pub auditor: Pubkey,              // Auditor key appointed by the issuer
+    pub attestation_window: i64,      // Time the auditor has after a root is posted
+    pub merkle_root: [u8; 32],        // Root the auditor last confirmed
+    pub root_posted_time: i64,        // When the issuer posted that root
+    pub attested_time: i64,           // When the auditor confirmed it, 0 if never
+}
+
+/// Emitted when the issuer appoints a reserve auditor
+#[event]
+pub struct ReserveAuditorConfigured {
+    pub mint: Pubkey,
+    pub auditor: Pubkey,
+    pub attestation_window: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the auditor confirms the posted reserve proof
+#[event]
+pub struct ReserveProofAttested {
+    pub mint: Pubkey,
+    pub auditor: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub root_posted_time: i64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureReserveAuditor<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [AUDITOR_ATTESTATION_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + AuditorAttestation::INIT_SPACE,
+    )]
+    pub auditor_attestation: Account<'info, AuditorAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestReserveProof<'info> {
+    #[account(
+        constraint = auditor.key() == auditor_attestation.auditor @ MicaEurError::NotReserveAuditor
+    )]
+    pub auditor: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        seeds = [AUDITOR_ATTESTATION_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub auditor_attestation: Account<'info, AuditorAttestation>,
+}
+
+/// Whether the reserve proof currently posted on the mint was confirmed by the
+/// auditor within the attestation window
+pub fn is_reserve_attested(mint_info: &MintInfo, attestation: &AuditorAttestation) -> bool {
+    attestation.mint == mint_info.mint
+        && attestation.attested_time != 0
+        && attestation.merkle_root == mint_info.reserve_merkle_root
+        && attestation.root_posted_time == mint_info.last_reserve_update
+        && attestation.attested_time.saturating_sub(attestation.root_posted_time)
+            <= attestation.attestation_window
+}
+
+// Appoint the reserve auditor and its attestation window (issuer only). The
+// auditor must be a key other than the issuer's.
+pub(crate) fn configure_reserve_auditor(
+    ctx: Context<ConfigureReserveAuditor>,
+    auditor: Pubkey,
+    attestation_window: i64,
+) -> Result<()> {
+    if auditor == Pubkey::default() || auditor == ctx.accounts.mint_info.issuer {
+        return Err(MicaEurError::InvalidReserveAuditor.into());
+    }
+    if attestation_window <= 0 || attestation_window > MAX_RESERVE_ATTESTATION_WINDOW {
+        return Err(MicaEurError::InvalidReserveAuditor.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let attestation = &mut ctx.accounts.auditor_attestation;
+    // A new auditor starts without an attestation of its own
+    if attestation.auditor != auditor {
+        attestation.merkle_root = [0; 32];
+        attestation.root_posted_time = 0;
+        attestation.attested_time = 0;
+    }
+    attestation.mint = ctx.accounts.mint_info.mint;
+    attestation.auditor = auditor;
+    attestation.attestation_window = attestation_window;
+
+    emit!(ReserveAuditorConfigured {
+        mint: attestation.mint,
+        auditor,
+        attestation_window,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Confirm the reserve proof currently posted by the issuer (auditor only)
+pub(crate) fn attest_reserve_proof(ctx: Context<AttestReserveProof>, merkle_root: [u8; 32]) -> Result<()> {
+    let mint_info = &ctx.accounts.mint_info;
+    if merkle_root != mint_info.reserve_merkle_root {
+        return Err(MicaEurError::ReserveRootMismatch.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let attestation = &mut ctx.accounts.auditor_attestation;
+    if now.saturating_sub(mint_info.last_reserve_update) > attestation.attestation_window {
+        return Err(MicaEurError::AttestationWindowElapsed.into());
+    }
+
+    attestation.merkle_root = merkle_root;
+    attestation.root_posted_time = mint_info.last_reserve_update;
+    attestation.attested_time = now;
+
+    emit!(ReserveProofAttested {
+        mint: attestation.mint,
+        auditor: attestation.auditor,
+        merkle_root,
+        root_posted_time: attestation.root_posted_time,
+        timestamp: now,
+    });
+    Ok(())
+}
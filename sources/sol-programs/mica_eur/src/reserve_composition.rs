@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::reserve_valuation::ReserveValuation;
+
+/// Asset category of a reserve position, as MiCA asks issuers to disclose them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub enum ReserveAssetCategory {
+    #[default]
+    CashAtBank,                       // Deposits at credit institutions
+    CentralBankDeposit,               // Deposits at a central bank
+    GovernmentBond,                   // Short-term government bonds, identified by ISIN
+}
+
+/// One position of the reserve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct ReserveAsset {
+    pub category: ReserveAssetCategory,
+    pub isin_hash: [u8; 32],          // `isin_hash` of a bond's ISIN, zero for other categories
+    pub amount: u64,                  // Value of the position, in token units
+    pub units: u64,                   // Nominal units of a bond held, 0 for other categories
+}
+
+/// Breakdown of the reserve total posted with the latest reserve proof
+#[account]
+#[derive(InitSpace)]
+pub struct ReserveComposition {
+    pub mint: Pubkey,                 // The EUR mint
+    pub assets: [ReserveAsset; MAX_RESERVE_ASSETS], // Positions, the first `asset_count` are used
+    pub asset_count: u8,
+    pub reserve_total_eur: u64,       // Sum of the positions, equal to the proof's total
+    pub merkle_root: [u8; 32],        // Root of the proof the breakdown was posted with
+    pub updated_at: i64,
+    pub valuation: ReserveValuation,  // Oracle valuation the proof was checked against
+}
+
+impl ReserveComposition {
+    /// The positions in use
+    pub fn assets(&self) -> &[ReserveAsset] {
+        &self.assets[..self.asset_count as usize]
+    }
+
+    /// Total held in `category`
+    pub fn category_total(&self, category: ReserveAssetCategory) -> u64 {
+        self.assets()
+            .iter()
+            .filter(|asset| asset.category == category)
+            .map(|asset| asset.amount)
+            .sum()
+    }
+}
+
+/// Emitted with every reserve proof, disclosing the totals per asset category
+#[event]
+pub struct ReserveCompositionUpdated {
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub cash_at_bank: u64,
+    pub central_bank_deposits: u64,
+    pub government_bonds: u64,
+    pub asset_count: u8,
+    pub timestamp: i64,
+}
+
+/// Hash a bond position is identified by: SHA-256 of its 12-character ISIN
+pub fn isin_hash(isin: &str) -> [u8; 32] {
+    hash::hash(isin.trim().to_ascii_uppercase().as_bytes()).to_bytes()
+}
+
+// Replace the breakdown of a mint's reserve with `assets`, which must add up to the
+// total of the proof posted with it
+pub fn record_reserve_composition(
+    composition: &mut ReserveComposition,
+    mint: Pubkey,
+    assets: &[ReserveAsset],
+    reserve_total_eur: u64,
+    merkle_root: [u8; 32],
+    now: i64,
+) -> Result<()> {
+    if assets.is_empty() || assets.len() > MAX_RESERVE_ASSETS {
+        return Err(MicaEurError::InvalidReserveComposition.into());
+    }
+    let mut sum: u64 = 0;
+    for asset in assets {
+        let bond = asset.category == ReserveAssetCategory::GovernmentBond;
+        if asset.amount == 0 || (asset.isin_hash != [0; 32]) != bond || (!bond && asset.units != 0) {
+            return Err(MicaEurError::InvalidReserveComposition.into());
+        }
+        sum = sum.checked_add(asset.amount).ok_or(MicaEurError::ArithmeticOverflow)?;
+    }
+    if sum != reserve_total_eur {
+        return Err(MicaEurError::ReserveCompositionMismatch.into());
+    }
+
+    composition.mint = mint;
+    composition.assets = [ReserveAsset::default(); MAX_RESERVE_ASSETS];
+    composition.assets[..assets.len()].copy_from_slice(assets);
+    composition.asset_count = assets.len() as u8;
+    composition.reserve_total_eur = reserve_total_eur;
+    composition.merkle_root = merkle_root;
+    composition.updated_at = now;
+    composition.valuation = ReserveValuation::default();
+
+    emit!(ReserveCompositionUpdated {
+        mint,
+        merkle_root,
+        cash_at_bank: composition.category_total(ReserveAssetCategory::CashAtBank),
+        central_bank_deposits: composition.category_total(ReserveAssetCategory::CentralBankDeposit),
+        government_bonds: composition.category_total(ReserveAssetCategory::GovernmentBond),
+        asset_count: composition.asset_count,
+        timestamp: now,
+    });
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::StateWithExtensions, state::Mint as MintState,
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Coverage of the token supply by the reserve reported with the latest proof
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ReserveCoverage {
+    pub reserve_total_eur: u64,       // Reported reserve, in token units
+    pub supply: u64,                  // Token-2022 mint supply
+    pub coverage_bps: u64,            // Reserve over supply in basis points, u64::MAX for no supply
+}
+
+/// Emitted whenever a coverage check finds the reserve below the supply
+#[event]
+pub struct UnderCollateralized {
+    pub mint: Pubkey,
+    pub reserve_total_eur: u64,
+    pub supply: u64,
+    pub coverage_bps: u64,
+    pub minting_halted: bool,         // Whether minting was auto-paused
+    pub timestamp: i64,
+}
+
+/// Emitted when coverage is back at 100% and auto-paused minting resumes
+#[event]
+pub struct ReserveCoverageRestored {
+    pub mint: Pubkey,
+    pub reserve_total_eur: u64,
+    pub supply: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer changes how old a reserve proof may be for minting
+#[event]
+pub struct MaxReserveAgeChanged {
+    pub mint: Pubkey,
+    pub max_reserve_age_seconds: i64, // 0 if reserve proofs never go stale
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer turns auto-pausing of minting on or off
+#[event]
+pub struct CoverageAutoPauseChanged {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct CheckReserveCoverage<'info> {
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct SetCoverageAutoPause<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxReserveAge<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+}
+
+/// Reserve over supply in basis points. An empty supply is fully covered by any
+/// reserve and reported as `u64::MAX`.
+pub fn coverage_bps(reserve_total_eur: u64, supply: u64) -> u64 {
+    if supply == 0 {
+        return u64::MAX;
+    }
+    let bps = reserve_total_eur as u128 * FULL_COVERAGE_BPS as u128 / supply as u128;
+    bps.min(u64::MAX as u128) as u64
+}
+
+/// Supply of a Token-2022 mint account
+pub fn mint_supply(mint: &AccountInfo) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    Ok(StateWithExtensions::<MintState>::unpack(&data)?.base.supply)
+}
+
+/// Compare the reported reserve with `supply`. Below 100% an `UnderCollateralized`
+/// event is emitted and, if auto-pause is on, minting is halted; minting halted
+/// this way resumes once the reserve covers the supply again.
+pub fn apply_reserve_coverage(mint_info: &mut MintInfo, supply: u64) -> Result<ReserveCoverage> {
+    let coverage = ReserveCoverage {
+        reserve_total_eur: mint_info.reserve_total_eur,
+        supply,
+        coverage_bps: coverage_bps(mint_info.reserve_total_eur, supply),
+    };
+    let now = Clock::get()?.unix_timestamp;
+
+    if coverage.coverage_bps < FULL_COVERAGE_BPS {
+        if mint_info.coverage_auto_pause {
+            mint_info.minting_halted = true;
+        }
+        emit!(UnderCollateralized {
+            mint: mint_info.mint,
+            reserve_total_eur: coverage.reserve_total_eur,
+            supply,
+            coverage_bps: coverage.coverage_bps,
+            minting_halted: mint_info.minting_halted,
+            timestamp: now,
+        });
+    } else if mint_info.minting_halted {
+        mint_info.minting_halted = false;
+        emit!(ReserveCoverageRestored {
+            mint: mint_info.mint,
+            reserve_total_eur: coverage.reserve_total_eur,
+            supply,
+            timestamp: now,
+        });
+    }
+    Ok(coverage)
+}
+
+/// With auto-pause on, reject a mint of `amount` the reported reserve would not cover
+pub fn check_mint_coverage(mint_info: &MintInfo, supply: u64, amount: u64) -> Result<()> {
+    if !mint_info.coverage_auto_pause {
+        return Ok(());
+    }
+    let supply = supply.checked_add(amount).ok_or(MicaEurError::ArithmeticOverflow)?;
+    if supply > mint_info.reserve_total_eur {
+        return Err(MicaEurError::ReserveShortfall.into());
+    }
+    Ok(())
+}
+
+/// Reject a mint while the latest reserve proof is older than the mint's
+/// `max_reserve_age_seconds`, so issuance needs regularly published proofs
+pub fn check_reserve_freshness(mint_info: &MintInfo, now: i64) -> Result<()> {
+    let max_age = mint_info.max_reserve_age_seconds;
+    if max_age > 0 && now.saturating_sub(mint_info.last_reserve_update) > max_age {
+        return Err(MicaEurError::ReserveProofStale.into());
+    }
+    Ok(())
+}
+
+// Re-evaluate coverage against the current supply (anyone can call) and return it
+pub(crate) fn check_reserve_coverage(ctx: Context<CheckReserveCoverage>) -> Result<ReserveCoverage> {
+    let supply = ctx.accounts.mint.supply;
+    apply_reserve_coverage(&mut ctx.accounts.mint_info, supply)
+}
+
+// Turn auto-pausing of minting on shortfall on or off (issuer only). Turning it off
+// also lifts a halt it caused.
+pub(crate) fn set_coverage_auto_pause(ctx: Context<SetCoverageAutoPause>, enabled: bool) -> Result<()> {
+    let mint_info = &mut ctx.accounts.mint_info;
+    mint_info.coverage_auto_pause = enabled;
+    if !enabled {
+        mint_info.minting_halted = false;
+    }
+
+    emit!(CoverageAutoPauseChanged {
+        mint: mint_info.mint,
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Set how old the latest reserve proof may be for new issuance, 0 for no limit
+// (issuer only)
+pub(crate) fn set_max_reserve_age(ctx: Context<SetMaxReserveAge>, max_reserve_age_seconds: i64) -> Result<()> {
+    if max_reserve_age_seconds < 0 {
+        return Err(MicaEurError::InvalidMaxReserveAge.into());
+    }
+    let mint_info = &mut ctx.accounts.mint_info;
+    mint_info.max_reserve_age_seconds = max_reserve_age_seconds;
+
+    emit!(MaxReserveAgeChanged {
+        mint: mint_info.mint,
+        max_reserve_age_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
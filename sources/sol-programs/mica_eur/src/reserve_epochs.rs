@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::merkle::verify_merkle_proof;
+use crate::reserve_inclusion::ReserveLeafVerified;
+use crate::MintInfo;
+
+/// Archived copy of one published reserve proof. `update_reserve_proof` replaces
+/// the proof on the mint info, so every proof is also kept under its epoch, the
+/// mint's count of proofs published before it.
+#[account]
+#[derive(InitSpace)]
+pub struct ReserveProofEpoch {
+    pub mint: Pubkey,                 // The EUR mint
+    pub epoch: u64,                   // Sequence number of the proof, from 0
+    pub merkle_root: [u8; 32],        // Root over the deposit leaves
+    #[max_len(MAX_IPFS_CID_LEN)]
+    pub ipfs_cid: String,             // Where the full tree is pinned
+    pub reserve_total_eur: u64,       // Reported reserve, in token units
+    pub supply: u64,                  // Token-2022 supply when the proof was published
+    pub published_by: Pubkey,         // Issuer or reserve publisher who posted it
+    pub published_at: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct VerifyArchivedReserveInclusion<'info> {
+    #[account(
+        seeds = [RESERVE_PROOF_EPOCH_SEED, reserve_proof_epoch.mint.as_ref(), &epoch.to_le_bytes()],
+        bump,
+    )]
+    pub reserve_proof_epoch: Account<'info, ReserveProofEpoch>,
+}
+
+// Archive the proof just written to `mint_info` under the next epoch and advance
+// the mint's epoch counter
+pub fn archive_reserve_proof(
+    archive: &mut ReserveProofEpoch,
+    mint_info: &mut MintInfo,
+    publisher: Pubkey,
+    supply: u64,
+) -> Result<()> {
+    archive.mint = mint_info.mint;
+    archive.epoch = mint_info.reserve_epoch_count;
+    archive.merkle_root = mint_info.reserve_merkle_root;
+    archive.ipfs_cid = mint_info.reserve_ipfs_cid.clone();
+    archive.reserve_total_eur = mint_info.reserve_total_eur;
+    archive.supply = supply;
+    archive.published_by = publisher;
+    archive.published_at = mint_info.last_reserve_update;
+
+    mint_info.reserve_epoch_count = mint_info
+        .reserve_epoch_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+// Prove a deposit leaf was in the reserve proof of a past epoch (anyone), as
+// `verify_reserve_inclusion` does for the latest one
+pub(crate) fn verify_archived_reserve_inclusion(
+    ctx: Context<VerifyArchivedReserveInclusion>,
+    _epoch: u64,
+    leaf: [u8; 32],
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let archive = &ctx.accounts.reserve_proof_epoch;
+    if archive.merkle_root == [0; 32] || !verify_merkle_proof(&proof, &archive.merkle_root, &leaf) {
+        return Err(MicaEurError::ReserveLeafNotIncluded.into());
+    }
+
+    emit!(ReserveLeafVerified {
+        mint: archive.mint,
+        leaf,
+        merkle_root: archive.merkle_root,
+        last_reserve_update: archive.published_at,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
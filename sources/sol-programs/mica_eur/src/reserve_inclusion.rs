@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::merkle::verify_merkle_proof;
+use crate::MintInfo;
+
+/// Emitted when a deposit leaf is proven to be in the published reserve
+#[event]
+pub struct ReserveLeafVerified {
+    pub mint: Pubkey,
+    pub leaf: [u8; 32],
+    pub merkle_root: [u8; 32],        // Reserve root the leaf was proven against
+    pub last_reserve_update: i64,     // When that root was published
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct VerifyReserveInclusion<'info> {
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+}
+
+/// Whether `leaf` (see `create_reserve_leaf`) is in the mint's latest reserve proof
+pub fn is_reserve_leaf_included(
+    mint_info: &MintInfo,
+    leaf: &[u8; 32],
+    proof: &[[u8; 32]],
+) -> bool {
+    mint_info.reserve_merkle_root != [0; 32]
+        && verify_merkle_proof(proof, &mint_info.reserve_merkle_root, leaf)
+}
+
+// Prove a deposit leaf is in the published reserve (anyone). Leaves a
+// `ReserveLeafVerified` event on-chain a depositor can point to.
+pub(crate) fn verify_reserve_inclusion(
+    ctx: Context<VerifyReserveInclusion>,
+    leaf: [u8; 32],
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let mint_info = &ctx.accounts.mint_info;
+    if !is_reserve_leaf_included(mint_info, &leaf, &proof) {
+        return Err(MicaEurError::ReserveLeafNotIncluded.into());
+    }
+
+    emit!(ReserveLeafVerified {
+        mint: mint_info.mint,
+        leaf,
+        merkle_root: mint_info.reserve_merkle_root,
+        last_reserve_update: mint_info.last_reserve_update,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Read-only counterpart of `verify_reserve_inclusion` for simulation: returns
+// whether the leaf is included instead of failing
+pub(crate) fn reserve_inclusion(
+    ctx: Context<VerifyReserveInclusion>,
+    leaf: [u8; 32],
+    proof: Vec<[u8; 32]>,
+) -> Result<bool> {
+    Ok(is_reserve_leaf_included(&ctx.accounts.mint_info, &leaf, &proof))
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::reserve_composition::{ReserveAsset, ReserveAssetCategory};
+use crate::MintInfo;
+
+/// Pyth price feed a bond position is valued with, quoted in USD per unit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct BondPriceFeed {
+    pub isin_hash: [u8; 32],          // `isin_hash` of the bond
+    pub feed_id: [u8; 32],            // Pyth feed ID of its price
+}
+
+/// Oracles a mint's reserve proofs are checked against. Once configured, every
+/// proof must come with Pyth price updates for EUR/USD and for each bond it lists,
+/// and its asserted bond total must be within `tolerance_bps` of their valuation.
+#[account]
+#[derive(InitSpace)]
+pub struct ReserveValuationConfig {
+    pub mint: Pubkey,                 // The EUR mint
+    pub eur_usd_feed: [u8; 32],       // Pyth feed ID of EUR/USD
+    pub bond_feeds: [BondPriceFeed; MAX_RESERVE_ASSETS], // Feeds, the first `bond_feed_count` are used
+    pub bond_feed_count: u8,
+    pub tolerance_bps: u16,           // Largest accepted divergence from the oracle valuation
+    pub max_price_age: i64,           // Oldest price update accepted, in seconds
+    pub bump: u8,
+}
+
+impl ReserveValuationConfig {
+    /// Feed ID configured for the bond `isin_hash`, `None` if it has none
+    pub fn bond_feed(&self, isin_hash: &[u8; 32]) -> Option<[u8; 32]> {
+        self.bond_feeds[..self.bond_feed_count as usize]
+            .iter()
+            .find(|feed| feed.isin_hash == *isin_hash)
+            .map(|feed| feed.feed_id)
+    }
+}
+
+/// Oracle price as read from a price update: `price * 10^exponent`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Oracle valuation a reserve proof was checked against, zero if the mint has
+/// no valuation configured
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct ReserveValuation {
+    pub eur_usd: OraclePrice,         // USD per EUR
+    pub asset_prices: [OraclePrice; MAX_RESERVE_ASSETS], // Per position, zero for cash positions
+    pub asserted_value: u64,          // Bond total asserted by the proof, in token units
+    pub oracle_value: u64,            // Bond total implied by the prices, in token units
+    pub valued_at: i64,
+}
+
+/// Emitted when the issuer configures the oracles reserve proofs are checked against
+#[event]
+pub struct ReserveValuationConfigured {
+    pub mint: Pubkey,
+    pub eur_usd_feed: [u8; 32],
+    pub bond_feed_count: u8,
+    pub tolerance_bps: u16,
+    pub max_price_age: i64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureReserveValuation<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [RESERVE_VALUATION_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + ReserveValuationConfig::INIT_SPACE,
+    )]
+    pub valuation_config: Box<Account<'info, ReserveValuationConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Pyth receiver account layout, decoded by hand as the SDK crate is not a dependency
+#[derive(AnchorDeserialize)]
+enum VerificationLevel {
+    Partial {
+        #[allow(dead_code)]
+        num_signatures: u8,
+    },
+    Full,
+}
+
+#[derive(AnchorDeserialize)]
+struct PriceFeedMessage {
+    feed_id: [u8; 32],
+    price: i64,
+    _conf: u64,
+    exponent: i32,
+    publish_time: i64,
+}
+
+#[derive(AnchorDeserialize)]
+struct PriceUpdateV2 {
+    _write_authority: Pubkey,
+    verification_level: VerificationLevel,
+    price_message: PriceFeedMessage,
+}
+
+/// Read the price of `feed_id` from a fully verified Pyth `PriceUpdateV2`
+/// account, rejecting prices published more than `max_age` seconds before `now`
+pub fn read_pyth_price(account: &AccountInfo, feed_id: &[u8; 32], now: i64, max_age: i64) -> Result<OraclePrice> {
+    if *account.owner != PYTH_RECEIVER_PROGRAM_ID {
+        return Err(MicaEurError::InvalidPriceUpdate.into());
+    }
+    let data = account.try_borrow_data()?;
+    let discriminator = &hash::hash(b"account:PriceUpdateV2").to_bytes()[..8];
+    if data.len() < 8 || &data[..8] != discriminator {
+        return Err(MicaEurError::InvalidPriceUpdate.into());
+    }
+    let update = PriceUpdateV2::deserialize(&mut &data[8..]).map_err(|_| MicaEurError::InvalidPriceUpdate)?;
+    let message = update.price_message;
+    if !matches!(update.verification_level, VerificationLevel::Full)
+        || message.feed_id != *feed_id
+        || message.price <= 0
+    {
+        return Err(MicaEurError::InvalidPriceUpdate.into());
+    }
+    if now.saturating_sub(message.publish_time) > max_age {
+        return Err(MicaEurError::StalePriceUpdate.into());
+    }
+    Ok(OraclePrice {
+        price: message.price,
+        exponent: message.exponent,
+        publish_time: message.publish_time,
+    })
+}
+
+/// EUR value, in token units of `decimals`, of `units` priced at `price` USD with
+/// `eur_usd` USD per EUR
+pub fn bond_value(units: u64, price: &OraclePrice, eur_usd: &OraclePrice, decimals: u8) -> Result<u64> {
+    let overflow = || error!(MicaEurError::ArithmeticOverflow);
+    let pow = |exponent: i32| 10u128.checked_pow(exponent.unsigned_abs()).ok_or_else(overflow);
+    let mut numerator = (units as u128)
+        .checked_mul(price.price as u128)
+        .ok_or_else(overflow)?;
+    let mut denominator = eur_usd.price as u128;
+    let scale = decimals as i32 + price.exponent - eur_usd.exponent;
+    if scale >= 0 {
+        numerator = numerator.checked_mul(pow(scale)?).ok_or_else(overflow)?;
+    } else {
+        denominator = denominator.checked_mul(pow(scale)?).ok_or_else(overflow)?;
+    }
+    u64::try_from(numerator / denominator).map_err(|_| overflow())
+}
+
+/// Whether `asserted` is within `tolerance_bps` of `oracle`
+pub fn within_tolerance(asserted: u64, oracle: u64, tolerance_bps: u16) -> bool {
+    let divergence = asserted.abs_diff(oracle) as u128;
+    divergence * FULL_COVERAGE_BPS as u128 <= oracle as u128 * tolerance_bps as u128
+}
+
+/// Value the bond positions of `assets` with the price updates in `price_updates`
+/// (EUR/USD first, then one per bond in position order) and check the asserted
+/// bond total against it
+pub fn value_reserve(
+    config: &ReserveValuationConfig,
+    assets: &[ReserveAsset],
+    price_updates: &[AccountInfo],
+    decimals: u8,
+    now: i64,
+) -> Result<ReserveValuation> {
+    let (eur_usd_update, bond_updates) = price_updates.split_first().ok_or(MicaEurError::InvalidPriceUpdate)?;
+    let mut valuation = ReserveValuation {
+        eur_usd: read_pyth_price(eur_usd_update, &config.eur_usd_feed, now, config.max_price_age)?,
+        valued_at: now,
+        ..Default::default()
+    };
+
+    let mut bond_updates = bond_updates.iter();
+    for (i, asset) in assets.iter().enumerate() {
+        if asset.category != ReserveAssetCategory::GovernmentBond {
+            continue;
+        }
+        let feed_id = config.bond_feed(&asset.isin_hash).ok_or(MicaEurError::UnpricedReserveAsset)?;
+        let update = bond_updates.next().ok_or(MicaEurError::InvalidPriceUpdate)?;
+        let price = read_pyth_price(update, &feed_id, now, config.max_price_age)?;
+        let value = bond_value(asset.units, &price, &valuation.eur_usd, decimals)?;
+        valuation.asset_prices[i] = price;
+        valuation.asserted_value = valuation
+            .asserted_value
+            .checked_add(asset.amount)
+            .ok_or(MicaEurError::ArithmeticOverflow)?;
+        valuation.oracle_value = valuation.oracle_value.checked_add(value).ok_or(MicaEurError::ArithmeticOverflow)?;
+    }
+
+    if !within_tolerance(valuation.asserted_value, valuation.oracle_value, config.tolerance_bps) {
+        return Err(MicaEurError::ReserveValuationDivergence.into());
+    }
+    Ok(valuation)
+}
+
+// Configure the oracles reserve proofs are checked against (issuer only). From
+// then on `update_reserve_proof` needs the config and the price updates.
+pub(crate) fn configure_reserve_valuation(
+    ctx: Context<ConfigureReserveValuation>,
+    eur_usd_feed: [u8; 32],
+    bond_feeds: Vec<BondPriceFeed>,
+    tolerance_bps: u16,
+    max_price_age: i64,
+) -> Result<()> {
+    if eur_usd_feed == [0; 32]
+        || bond_feeds.len() > MAX_RESERVE_ASSETS
+        || tolerance_bps as u64 > FULL_COVERAGE_BPS
+        || max_price_age <= 0
+    {
+        return Err(MicaEurError::InvalidReserveValuation.into());
+    }
+    for (i, feed) in bond_feeds.iter().enumerate() {
+        if feed.isin_hash == [0; 32]
+            || feed.feed_id == [0; 32]
+            || bond_feeds[..i].iter().any(|other| other.isin_hash == feed.isin_hash)
+        {
+            return Err(MicaEurError::InvalidReserveValuation.into());
+        }
+    }
+
+    let config = &mut ctx.accounts.valuation_config;
+    config.mint = ctx.accounts.mint_info.mint;
+    config.eur_usd_feed = eur_usd_feed;
+    config.bond_feeds = [BondPriceFeed::default(); MAX_RESERVE_ASSETS];
+    config.bond_feeds[..bond_feeds.len()].copy_from_slice(&bond_feeds);
+    config.bond_feed_count = bond_feeds.len() as u8;
+    config.tolerance_bps = tolerance_bps;
+    config.max_price_age = max_price_age;
+    config.bump = ctx.bumps.valuation_config;
+
+    ctx.accounts.mint_info.reserve_valuation = config.key();
+
+    emit!(ReserveValuationConfigured {
+        mint: config.mint,
+        eur_usd_feed,
+        bond_feed_count: config.bond_feed_count,
+        tolerance_bps,
+        max_price_age,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
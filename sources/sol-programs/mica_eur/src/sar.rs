@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::aml::{aml_powers, check_jurisdiction, AmlAuthority};
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::KycUser;
+
+/// Hash of a suspicious activity report filed with the FIU, anchored against the
+/// user it concerns. Records are never changed or closed, so the filing can later
+/// be shown to have existed in this form when the on-chain measures were taken.
+#[account]
+#[derive(InitSpace)]
+pub struct SarReference {
+    pub kyc_user: Pubkey,             // KYC record of the reported user
+    pub user: Pubkey,                 // Reported wallet
+    pub sar_hash: [u8; 32],           // SHA-256 hash of the filed report
+    pub filing_date: i64,             // When the report was filed with the FIU
+    pub anchored_by: Pubkey,          // AML authority that anchored the hash
+    pub anchor_time: i64,
+}
+
+/// Emitted when an AML authority anchors a SAR filing
+#[event]
+pub struct SarReferenceAnchored {
+    pub sar_reference: Pubkey,
+    pub user: Pubkey,
+    pub sar_hash: [u8; 32],
+    pub filing_date: i64,
+    pub aml_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(sar_hash: [u8; 32])]
+pub struct AnchorSarReference<'info> {
+    #[account(mut)]
+    pub officer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [AML_AUTHORITY_SEED, officer.key().as_ref()],
+        bump,
+        constraint = aml_authority.authority == officer.key(),
+    )]
+    pub aml_authority: Account<'info, AmlAuthority>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        init,
+        payer = officer,
+        seeds = [SAR_REFERENCE_SEED, kyc_user.key().as_ref(), sar_hash.as_ref()],
+        bump,
+        space = 8 + SarReference::INIT_SPACE,
+    )]
+    pub sar_reference: Account<'info, SarReference>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anchor the hash of a SAR filing against a user (AML authority with the
+// VIEW_TRANSACTIONS power, in the user's jurisdiction). The report itself stays
+// off-chain.
+pub(crate) fn anchor_sar_reference(ctx: Context<AnchorSarReference>, sar_hash: [u8; 32], filing_date: i64) -> Result<()> {
+    let aml_authority = &mut ctx.accounts.aml_authority;
+    if !aml_authority.is_active {
+        return Err(MicaEurError::AmlAuthorityInactive.into());
+    }
+    if !aml_authority.has_power(aml_powers::VIEW_TRANSACTIONS) {
+        return Err(MicaEurError::UnauthorizedAmlAuthority.into());
+    }
+    check_jurisdiction(aml_authority, Some(&ctx.accounts.kyc_user))?;
+    let now = Clock::get()?.unix_timestamp;
+    if sar_hash == [0u8; 32] || filing_date <= 0 || filing_date > now {
+        return Err(MicaEurError::InvalidSarReference.into());
+    }
+
+    let sar_reference = &mut ctx.accounts.sar_reference;
+    sar_reference.kyc_user = ctx.accounts.kyc_user.key();
+    sar_reference.user = ctx.accounts.kyc_user.user;
+    sar_reference.sar_hash = sar_hash;
+    sar_reference.filing_date = filing_date;
+    sar_reference.anchored_by = aml_authority.authority;
+    sar_reference.anchor_time = now;
+    aml_authority.last_action_time = now;
+
+    emit!(SarReferenceAnchored {
+        sar_reference: sar_reference.key(),
+        user: sar_reference.user,
+        sar_hash,
+        filing_date,
+        aml_authority: sar_reference.anchored_by,
+        timestamp: now,
+    });
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+use crate::codec::encode_screening_attestation;
+use crate::constants::*;
+use crate::ed25519_utils::verify_ed25519_instruction;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::{KycOracleState, KycUser};
+use crate::mint_utils::check_string_len;
+
+/// External sanctions-screening provider approved by the KYC oracle authority
+#[account]
+#[derive(InitSpace)]
+pub struct ScreeningProvider {
+    pub signing_key: Pubkey,          // Ed25519 key the provider signs attestations with
+    pub authority: Pubkey,            // KYC oracle authority that approved the provider
+    #[max_len(MAX_PROVIDER_NAME_LEN)]
+    pub name: String,                 // Provider name
+    pub is_active: bool,              // Whether attestations are accepted
+    pub attestation_count: u64,       // Number of attestations consumed
+    pub last_attestation_time: i64,   // When an attestation was last consumed
+    pub creation_time: i64,           // When the provider was registered
+}
+
+#[event]
+pub struct ScreeningProviderRegistered {
+    pub signing_key: Pubkey,
+    pub name: String,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ScreeningProviderDeactivated {
+    pub signing_key: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ScreeningAttested {
+    pub user: Pubkey,
+    pub provider: Pubkey,
+    pub screened_at: i64,
+    pub result_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(signing_key: Pubkey)]
+pub struct RegisterScreeningProvider<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+        has_one = authority,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SCREENING_PROVIDER_SEED, signing_key.as_ref()],
+        bump,
+        space = 8 + ScreeningProvider::INIT_SPACE,
+    )]
+    pub screening_provider: Account<'info, ScreeningProvider>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateScreeningProvider<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+        has_one = authority,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    #[account(
+        mut,
+        seeds = [SCREENING_PROVIDER_SEED, screening_provider.signing_key.as_ref()],
+        bump,
+    )]
+    pub screening_provider: Account<'info, ScreeningProvider>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitScreeningAttestation<'info> {
+    /// Relayer submitting the provider-signed attestation
+    pub submitter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SCREENING_PROVIDER_SEED, screening_provider.signing_key.as_ref()],
+        bump,
+    )]
+    pub screening_provider: Account<'info, ScreeningProvider>,
+
+    #[account(
+        mut,
+        seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    /// CHECK: Instructions sysvar, used to inspect the Ed25519 instruction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Message a screening provider signs for one attestation
+pub fn screening_attestation_message(
+    user: &Pubkey,
+    screened_at: i64,
+    result_hash: &[u8; 32],
+) -> Vec<u8> {
+    encode_screening_attestation(&crate::ID, user, screened_at, result_hash)
+}
+
+// Register a screening provider (KYC oracle authority only)
+pub(crate) fn register_screening_provider(
+    ctx: Context<RegisterScreeningProvider>,
+    signing_key: Pubkey,
+    name: String,
+) -> Result<()> {
+    check_string_len(&name, MAX_PROVIDER_NAME_LEN)?;
+    let screening_provider = &mut ctx.accounts.screening_provider;
+    screening_provider.signing_key = signing_key;
+    screening_provider.authority = ctx.accounts.authority.key();
+    screening_provider.name = name;
+    screening_provider.is_active = true;
+    screening_provider.attestation_count = 0;
+    screening_provider.last_attestation_time = 0;
+    screening_provider.creation_time = Clock::get()?.unix_timestamp;
+
+    emit!(ScreeningProviderRegistered {
+        signing_key,
+        name: screening_provider.name.clone(),
+        authority: screening_provider.authority,
+        timestamp: screening_provider.creation_time,
+    });
+    Ok(())
+}
+
+// Stop accepting attestations from a screening provider (KYC oracle authority only)
+pub(crate) fn deactivate_screening_provider(ctx: Context<DeactivateScreeningProvider>) -> Result<()> {
+    let screening_provider = &mut ctx.accounts.screening_provider;
+    screening_provider.is_active = false;
+
+    emit!(ScreeningProviderDeactivated {
+        signing_key: screening_provider.signing_key,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Consume a provider-signed screening attestation into the user's KYC record
+pub(crate) fn submit_screening_attestation(
+    ctx: Context<SubmitScreeningAttestation>,
+    screened_at: i64,
+    result_hash: [u8; 32],
+) -> Result<()> {
+    let screening_provider = &mut ctx.accounts.screening_provider;
+    let kyc_user = &mut ctx.accounts.kyc_user;
+    let now = Clock::get()?.unix_timestamp;
+
+    if !screening_provider.is_active {
+        return Err(MicaEurError::ScreeningProviderInactive.into());
+    }
+
+    // Attestations must be newer than the recorded one and not from the future
+    if screened_at > now || screened_at <= kyc_user.sanctions_screen_date {
+        return Err(MicaEurError::StaleScreeningAttestation.into());
+    }
+
+    let message = screening_attestation_message(&kyc_user.user, screened_at, &result_hash);
+    verify_ed25519_instruction(
+        &ctx.accounts.instructions,
+        &screening_provider.signing_key,
+        &message,
+    )?;
+
+    kyc_user.sanctions_screen_date = screened_at;
+    kyc_user.sanctions_screen_hash = result_hash;
+    kyc_user.sanctions_screen_provider = screening_provider.signing_key;
+
+    screening_provider.attestation_count = screening_provider.attestation_count.saturating_add(1);
+    screening_provider.last_attestation_time = now;
+
+    emit!(ScreeningAttested {
+        user: kyc_user.user,
+        provider: screening_provider.signing_key,
+        screened_at,
+        result_hash,
+    });
+
+    msg!("Sanctions screening recorded for user {} at {}", kyc_user.user, screened_at);
+    Ok(())
+}
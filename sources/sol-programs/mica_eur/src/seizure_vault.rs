@@ -0,0 +1,522 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::access_control::{has_mint_role, roles, RoleAssignment};
+use crate::error::MicaEurError;
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::mint_utils::{burn_checked, transfer_checked_with_hook};
+use crate::operations::{record_operation, OperationDetails, OperationKind, OperationReceipt};
+use crate::supply_accounting::record_burned;
+use crate::{MintInfo, TokensSeized};
+
+/// Escrow of seized funds of a mint. The vault token account is owned by this PDA,
+/// so seized funds only leave it through `release_from_vault` or
+/// `forfeit_from_vault`, each leaving a trail on the seizure's record.
+#[account]
+#[derive(InitSpace)]
+pub struct SeizureVault {
+    pub mint: Pubkey,                 // Mint the vault holds
+    pub vault: Pubkey,                // Escrow token account owned by this PDA
+    pub held_amount: u64,             // Seized funds awaiting legal resolution
+    pub seizure_count: u64,           // Seizures so far, the next seizure's ID
+    pub bump: u8,
+}
+
+/// Outcome of a seizure
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum SeizureStatus {
+    Held,
+    Released,
+    Forfeited,
+}
+
+/// One seizure into the vault and its resolution
+#[account]
+#[derive(InitSpace)]
+pub struct SeizureRecord {
+    pub mint: Pubkey,                 // The EUR mint
+    pub seizure_id: u64,              // Position in the vault's seizure sequence
+    pub source_token_account: Pubkey, // Account the funds were seized from
+    pub source_owner: Pubkey,         // Its owner at the time of seizure
+    pub amount: u64,                  // Amount held
+    pub operation_id: [u8; 16],       // Client-supplied operation UUID
+    pub case_hash: [u8; 32],          // Hash of the order the seizure was made under
+    pub status: SeizureStatus,
+    pub seized_time: i64,
+    pub resolution_hash: [u8; 32],    // Hash of the ruling resolving it, zero while held
+    pub destination: Pubkey,          // Account released to, default while held or forfeited
+    pub resolved_time: i64,           // 0 while held
+}
+
+/// Emitted when the issuer sets up or swaps the seizure vault of a mint
+#[event]
+pub struct SeizureVaultConfigured {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when held funds leave the vault
+#[event]
+pub struct SeizureResolved {
+    pub mint: Pubkey,
+    pub seizure_id: u64,
+    pub status: SeizureStatus,
+    pub amount: u64,
+    pub destination: Pubkey,          // Default for forfeitures
+    pub resolution_hash: [u8; 32],
+    pub permanent_delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureSeizureVault<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [SEIZURE_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        space = 8 + SeizureVault::INIT_SPACE,
+    )]
+    pub seizure_vault: Account<'info, SeizureVault>,
+
+    /// Escrow vault, created beforehand with the seizure vault PDA as owner
+    #[account(
+        mut,
+        constraint = vault.owner == seizure_vault.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = vault.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, operation_id: [u8; 16])]
+pub struct SeizeToVault<'info> {
+    /// The permanent delegate or another holder of the SEIZER role
+    #[account(mut)]
+    pub permanent_delegate: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+        constraint = mint_info.approval_set == Pubkey::default() @ MicaEurError::MultisigApprovalRequired,
+        constraint = has_mint_role(&mint_info, roles::SEIZER, permanent_delegate.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: Permanent delegate of the mint; address checked against its seeds
+    #[account(
+        seeds = [SEIZURE_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub seizure_authority: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [SEIZURE_VAULT_SEED, mint.key().as_ref()],
+        bump = seizure_vault.bump,
+        has_one = vault,
+    )]
+    pub seizure_vault: Account<'info, SeizureVault>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The token account to seize from
+    #[account(
+        mut,
+        constraint = from_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub from_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = permanent_delegate,
+        seeds = [
+            SEIZURE_RECORD_SEED,
+            mint.key().as_ref(),
+            &seizure_vault.seizure_count.to_le_bytes(),
+        ],
+        bump,
+        space = 8 + SeizureRecord::INIT_SPACE,
+    )]
+    pub seizure_record: Account<'info, SeizureRecord>,
+
+    /// Receipt for the client-supplied operation ID, rejects reuse
+    #[account(
+        init,
+        payer = permanent_delegate,
+        seeds = [
+            OPERATION_RECEIPT_SEED,
+            mint.key().as_ref(),
+            &[OperationKind::Seizure as u8],
+            permanent_delegate.key().as_ref(),
+            &operation_id,
+        ],
+        bump,
+        space = 8 + OperationReceipt::INIT_SPACE,
+    )]
+    pub operation_receipt: Account<'info, OperationReceipt>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// The signer's role assignment, unless it is the permanent delegate
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), permanent_delegate.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveSeizure<'info> {
+    /// The permanent delegate or another holder of the SEIZER role
+    pub permanent_delegate: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+        constraint = has_mint_role(&mint_info, roles::SEIZER, permanent_delegate.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: Permanent delegate of the mint; address checked against its seeds
+    #[account(
+        seeds = [SEIZURE_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub seizure_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [SEIZURE_VAULT_SEED, mint.key().as_ref()],
+        bump = seizure_vault.bump,
+        has_one = vault,
+    )]
+    pub seizure_vault: Account<'info, SeizureVault>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEIZURE_RECORD_SEED,
+            mint.key().as_ref(),
+            &seizure_record.seizure_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = mint,
+    )]
+    pub seizure_record: Account<'info, SeizureRecord>,
+
+    /// Account the funds are released to, not used for forfeitures
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// The signer's role assignment, unless it is the permanent delegate
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), permanent_delegate.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+// Create or update the seizure vault of a mint and make sure its vault is usable
+pub(crate) fn configure_seizure_vault(ctx: Context<ConfigureSeizureVault>) -> Result<()> {
+    let seizure_vault = &mut ctx.accounts.seizure_vault;
+    // The vault cannot be swapped while it holds seized funds
+    if seizure_vault.held_amount > 0 && seizure_vault.vault != ctx.accounts.vault.key() {
+        return Err(MicaEurError::SeizureVaultInUse.into());
+    }
+    seizure_vault.mint = ctx.accounts.mint.key();
+    seizure_vault.vault = ctx.accounts.vault.key();
+    seizure_vault.bump = ctx.bumps.seizure_vault;
+
+    // New token accounts start frozen; the mint info PDA holds the freeze authority
+    if ctx.accounts.vault.is_frozen() {
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = token_2022::ThawAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+
+    emit!(SeizureVaultConfigured {
+        mint: seizure_vault.mint,
+        vault: seizure_vault.vault,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Seizure vault configured for mint {}", seizure_vault.mint);
+    Ok(())
+}
+
+// Seize funds into the vault (SEIZER role) under the order hashed in
+// `case_hash`. The remaining accounts are the transfer hook's extra accounts for
+// the source -> vault transfer.
+pub(crate) fn seize_to_vault<'info>(
+    ctx: Context<'_, '_, '_, 'info, SeizeToVault<'info>>,
+    amount: u64,
+    operation_id: [u8; 16],
+    case_hash: [u8; 32],
+) -> Result<()> {
+    if amount == 0 {
+        return Err(MicaEurError::InvalidAmount.into());
+    }
+
+    // The seizure authority PDA is the permanent delegate of the mint
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.seizure_authority];
+    let seeds = &[SEIZURE_AUTHORITY_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.from_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.seizure_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let mint = ctx.accounts.mint.key();
+    let permanent_delegate = ctx.accounts.permanent_delegate.key();
+    let from = ctx.accounts.from_account.key();
+    let vault = ctx.accounts.vault.key();
+    record_operation(
+        &mut ctx.accounts.operation_receipt,
+        operation_id,
+        OperationDetails {
+            kind: OperationKind::Seizure,
+            mint,
+            actor: permanent_delegate,
+            source: from,
+            destination: vault,
+            amount,
+        },
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let seizure_vault = &mut ctx.accounts.seizure_vault;
+    let record = &mut ctx.accounts.seizure_record;
+    record.mint = mint;
+    record.seizure_id = seizure_vault.seizure_count;
+    record.source_token_account = from;
+    record.source_owner = ctx.accounts.from_account.owner;
+    record.amount = amount;
+    record.operation_id = operation_id;
+    record.case_hash = case_hash;
+    record.status = SeizureStatus::Held;
+    record.seized_time = now;
+
+    seizure_vault.held_amount = seizure_vault
+        .held_amount
+        .checked_add(amount)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    seizure_vault.seizure_count = seizure_vault
+        .seizure_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(TokensSeized {
+        mint,
+        from,
+        to: vault,
+        amount,
+        permanent_delegate,
+        operation_id,
+        timestamp: now,
+    });
+    msg!("Seizure {} of {} held in vault", record.seizure_id, amount);
+    Ok(())
+}
+
+// Release held funds to the account named in the ruling hashed in
+// `resolution_hash` (SEIZER role). The remaining accounts are the transfer
+// hook's extra accounts for the vault -> destination transfer.
+pub(crate) fn release_from_vault<'info>(
+    ctx: Context<'_, '_, '_, 'info, ResolveSeizure<'info>>,
+    resolution_hash: [u8; 32],
+) -> Result<()> {
+    check_resolvable(&ctx.accounts.seizure_record, &resolution_hash)?;
+    let destination = ctx
+        .accounts
+        .destination_token_account
+        .as_ref()
+        .ok_or(MicaEurError::SeizureDestinationMissing)?;
+
+    // The permanent delegate, the seizure authority PDA, moves the funds, so the hook
+    // skips its party checks
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.seizure_authority];
+    let seeds = &[SEIZURE_AUTHORITY_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: destination.to_account_info(),
+        authority: ctx.accounts.seizure_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, ctx.accounts.seizure_record.amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let destination = destination.key();
+    let permanent_delegate = ctx.accounts.permanent_delegate.key();
+    resolve(
+        &mut ctx.accounts.seizure_vault,
+        &mut ctx.accounts.seizure_record,
+        SeizureStatus::Released,
+        destination,
+        resolution_hash,
+        permanent_delegate,
+    )
+}
+
+// Burn held funds forfeited under the ruling hashed in `resolution_hash`
+// (SEIZER role). Their fiat counterpart is paid out of the reserve to the
+// authority off-chain.
+pub(crate) fn forfeit_from_vault(ctx: Context<ResolveSeizure>, resolution_hash: [u8; 32]) -> Result<()> {
+    check_resolvable(&ctx.accounts.seizure_record, &resolution_hash)?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.seizure_authority];
+    let seeds = &[SEIZURE_AUTHORITY_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = token_2022::Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.seizure_authority.to_account_info(),
+    };
+    burn_checked(
+        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+        ctx.accounts.seizure_record.amount,
+        ctx.accounts.mint_info.decimals,
+    )?;
+    record_burned(&mut ctx.accounts.mint_info, ctx.accounts.seizure_record.amount)?;
+
+    let permanent_delegate = ctx.accounts.permanent_delegate.key();
+    resolve(
+        &mut ctx.accounts.seizure_vault,
+        &mut ctx.accounts.seizure_record,
+        SeizureStatus::Forfeited,
+        Pubkey::default(),
+        resolution_hash,
+        permanent_delegate,
+    )
+}
+
+// Each seizure is resolved once, under a ruling
+fn check_resolvable(record: &SeizureRecord, resolution_hash: &[u8; 32]) -> Result<()> {
+    if record.status != SeizureStatus::Held {
+        return Err(MicaEurError::SeizureNotHeld.into());
+    }
+    if *resolution_hash == [0; 32] {
+        return Err(MicaEurError::InvalidSeizureResolution.into());
+    }
+    Ok(())
+}
+
+// Close a seizure with its outcome
+fn resolve(
+    seizure_vault: &mut SeizureVault,
+    record: &mut SeizureRecord,
+    status: SeizureStatus,
+    destination: Pubkey,
+    resolution_hash: [u8; 32],
+    permanent_delegate: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    seizure_vault.held_amount = seizure_vault.held_amount.saturating_sub(record.amount);
+    record.status = status;
+    record.resolution_hash = resolution_hash;
+    record.destination = destination;
+    record.resolved_time = now;
+
+    emit!(SeizureResolved {
+        mint: record.mint,
+        seizure_id: record.seizure_id,
+        status,
+        amount: record.amount,
+        destination,
+        resolution_hash,
+        permanent_delegate,
+        timestamp: now,
+    });
+    msg!("Seizure {} resolved: {:?}", record.seizure_id, status);
+    Ok(())
+}
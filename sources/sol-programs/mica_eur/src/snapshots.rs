@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::merkle::{create_snapshot_leaf, verify_merkle_proof};
+use crate::MintInfo;
+
+/// Sequence of holder-balance snapshots published for a mint
+#[account]
+#[derive(InitSpace)]
+pub struct SnapshotRegistry {
+    pub mint: Pubkey,                 // The EUR mint
+    pub snapshot_count: u64,          // Snapshots published so far, the next snapshot's ID
+    pub last_snapshot_slot: u64,      // Slot the latest snapshot was taken at
+}
+
+/// Merkle root of every holder's balance at a slot, for external loyalty programs to
+/// verify off-chain. A snapshot carries no on-chain entitlement and nothing is ever
+/// paid out from it, so holding the token earns no interest (MiCA Art. 50).
+#[account]
+#[derive(InitSpace)]
+pub struct BalanceSnapshot {
+    pub mint: Pubkey,                 // The EUR mint
+    pub snapshot_id: u64,             // Position in the mint's snapshot sequence
+    pub merkle_root: [u8; 32],        // Root over `create_snapshot_leaf(owner, balance)`
+    pub snapshot_slot: u64,           // Slot the balances were read at
+    pub holder_count: u32,            // Number of leaves
+    pub total_balance: u64,           // Sum of the balances in the tree
+    pub publish_time: i64,            // When the issuer published the snapshot
+}
+
+/// Emitted when the issuer publishes a holder-balance snapshot
+#[event]
+pub struct BalanceSnapshotPublished {
+    pub mint: Pubkey,
+    pub snapshot_id: u64,
+    pub merkle_root: [u8; 32],
+    pub snapshot_slot: u64,
+    pub holder_count: u32,
+    pub total_balance: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct PublishBalanceSnapshot<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [SNAPSHOT_REGISTRY_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + SnapshotRegistry::INIT_SPACE,
+    )]
+    pub snapshot_registry: Account<'info, SnapshotRegistry>,
+
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            BALANCE_SNAPSHOT_SEED,
+            mint_info.mint.as_ref(),
+            &snapshot_registry.snapshot_count.to_le_bytes(),
+        ],
+        bump,
+        space = 8 + BalanceSnapshot::INIT_SPACE,
+    )]
+    pub balance_snapshot: Account<'info, BalanceSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Whether `owner` held `balance` in the snapshot, given its merkle proof
+pub fn verify_snapshot_balance(
+    snapshot: &BalanceSnapshot,
+    owner: &Pubkey,
+    balance: u64,
+    proof: &[[u8; 32]],
+) -> bool {
+    let leaf = create_snapshot_leaf(owner, balance);
+    verify_merkle_proof(proof, &snapshot.merkle_root, &leaf)
+}
+
+// Publish the holder-balance snapshot taken at `snapshot_slot` (issuer only).
+// Snapshots are taken in slot order and never in the future.
+pub(crate) fn publish_balance_snapshot(
+    ctx: Context<PublishBalanceSnapshot>,
+    merkle_root: [u8; 32],
+    snapshot_slot: u64,
+    holder_count: u32,
+    total_balance: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let registry = &mut ctx.accounts.snapshot_registry;
+    if merkle_root == [0; 32] || holder_count == 0 {
+        return Err(MicaEurError::InvalidSnapshot.into());
+    }
+    if snapshot_slot > clock.slot
+        || (registry.snapshot_count > 0 && snapshot_slot <= registry.last_snapshot_slot)
+    {
+        return Err(MicaEurError::InvalidSnapshot.into());
+    }
+
+    let snapshot = &mut ctx.accounts.balance_snapshot;
+    snapshot.mint = ctx.accounts.mint_info.mint;
+    snapshot.snapshot_id = registry.snapshot_count;
+    snapshot.merkle_root = merkle_root;
+    snapshot.snapshot_slot = snapshot_slot;
+    snapshot.holder_count = holder_count;
+    snapshot.total_balance = total_balance;
+    snapshot.publish_time = clock.unix_timestamp;
+
+    registry.mint = snapshot.mint;
+    registry.snapshot_count = registry
+        .snapshot_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    registry.last_snapshot_slot = snapshot_slot;
+
+    emit!(BalanceSnapshotPublished {
+        mint: snapshot.mint,
+        snapshot_id: snapshot.snapshot_id,
+        merkle_root,
+        snapshot_slot,
+        holder_count,
+        total_balance,
+        timestamp: snapshot.publish_time,
+    });
+    Ok(())
+}
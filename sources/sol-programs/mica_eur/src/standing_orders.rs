@@ -0,0 +1,491 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::kyc_oracle::KycUser;
+use crate::mint_utils::transfer_checked_with_hook;
+use crate::policy::{check_transfer_parties, RULE_SET_VERSION};
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::MintInfo;
+
+/// Lifecycle of a standing order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum StandingOrderStatus {
+    Active,
+    Paused,
+    Completed,
+    Cancelled,
+}
+
+/// Why a keeper could not execute a due standing order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StandingOrderFailureReason {
+    InsufficientBalance,
+    InsufficientAllowance,
+    AccountFrozen,
+}
+
+/// Recurring payment from a verified payer to a verified payee
+#[account]
+#[derive(InitSpace)]
+pub struct StandingOrder {
+    pub payer: Pubkey,                // Wallet paying the order
+    pub payee: Pubkey,                // Wallet receiving the payments
+    pub mint: Pubkey,                 // The EUR mint
+    pub payer_token_account: Pubkey,  // Source token account (delegated to this PDA)
+    pub payee_token_account: Pubkey,  // Destination token account
+    pub order_id: u64,                // Payer-chosen identifier, part of the PDA seeds
+    pub amount: u64,                  // Amount transferred per execution
+    pub interval_seconds: i64,        // Time between two executions
+    pub next_execution_time: i64,     // When the next execution becomes due
+    pub end_time: i64,                // No execution is scheduled after this time
+    pub status: StandingOrderStatus,  // Current lifecycle status
+    pub execution_count: u64,         // Number of successful executions
+    pub failed_attempts: u8,          // Failed attempts for the currently due execution
+    pub last_attempt_time: i64,       // When a keeper last attempted an execution
+    pub creation_time: i64,           // When the order was created
+    pub bump: u8,                     // PDA bump, used to sign as delegate
+}
+
+impl StandingOrder {
+    /// Whether a keeper may attempt the current execution at `now`
+    pub fn is_due(&self, now: i64) -> bool {
+        if now < self.next_execution_time {
+            return false;
+        }
+        self.failed_attempts == 0
+            || now >= self.last_attempt_time.saturating_add(STANDING_ORDER_RETRY_DELAY)
+    }
+
+    /// Move on to the next scheduled execution, completing the order past its end date
+    pub fn advance_schedule(&mut self) -> Result<()> {
+        self.next_execution_time = self
+            .next_execution_time
+            .checked_add(self.interval_seconds)
+            .ok_or(MicaEurError::ArithmeticOverflow)?;
+        self.failed_attempts = 0;
+        if self.next_execution_time > self.end_time {
+            self.status = StandingOrderStatus::Completed;
+        }
+        Ok(())
+    }
+}
+
+#[event]
+pub struct StandingOrderUpdated {
+    pub standing_order: Pubkey,
+    pub status: StandingOrderStatus,
+    pub next_execution_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StandingOrderExecuted {
+    pub standing_order: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub amount: u64,
+    pub execution_count: u64,
+    pub keeper: Pubkey,
+    pub rule_set_version: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StandingOrderExecutionFailed {
+    pub standing_order: Pubkey,
+    pub reason: StandingOrderFailureReason,
+    pub attempt: u8,
+    pub gave_up: bool,             // The execution was skipped after the last retry
+    pub keeper: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateStandingOrder<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub payer_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, payee_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub payee_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = payer_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = payee_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub payee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [STANDING_ORDER_SEED, payer.key().as_ref(), &order_id.to_le_bytes()],
+        bump,
+        space = 8 + StandingOrder::INIT_SPACE,
+    )]
+    pub standing_order: Account<'info, StandingOrder>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageStandingOrder<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = payer,
+    )]
+    pub standing_order: Account<'info, StandingOrder>,
+}
+
+#[derive(Accounts)]
+pub struct CancelStandingOrder<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = payer,
+        has_one = payer_token_account,
+        close = payer,
+    )]
+    pub standing_order: Account<'info, StandingOrder>,
+
+    #[account(mut)]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteStandingOrder<'info> {
+    /// Anyone may crank a due standing order
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STANDING_ORDER_SEED,
+            standing_order.payer.as_ref(),
+            &standing_order.order_id.to_le_bytes(),
+        ],
+        bump = standing_order.bump,
+        has_one = mint,
+        has_one = payer_token_account,
+        has_one = payee_token_account,
+    )]
+    pub standing_order: Account<'info, StandingOrder>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, standing_order.payer.as_ref()],
+        bump,
+    )]
+    pub payer_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        seeds = [KYC_USER_SEED, standing_order.payee.as_ref()],
+        bump,
+    )]
+    pub payee_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == standing_order.payer @ MicaEurError::TokenAccountOwnerMismatch,
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = payee_token_account.owner == standing_order.payee @ MicaEurError::TokenAccountOwnerMismatch,
+    )]
+    pub payee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+}
+
+fn emit_status(standing_order: &Account<StandingOrder>, timestamp: i64) {
+    emit!(StandingOrderUpdated {
+        standing_order: standing_order.key(),
+        status: standing_order.status,
+        next_execution_time: standing_order.next_execution_time,
+        timestamp,
+    });
+}
+
+// Create a standing order and delegate the scheduled total to it
+pub(crate) fn create_standing_order(
+    ctx: Context<CreateStandingOrder>,
+    order_id: u64,
+    amount: u64,
+    interval_seconds: i64,
+    first_execution_time: i64,
+    end_time: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    if amount == 0
+        || interval_seconds < MIN_STANDING_ORDER_INTERVAL
+        || first_execution_time < now
+        || end_time < first_execution_time
+    {
+        return Err(MicaEurError::InvalidStandingOrderSchedule.into());
+    }
+    crate::mint_utils::check_transaction_amount(amount, ctx.accounts.mint_info.decimals)?;
+    check_transfer_parties(&ctx.accounts.payer_kyc_user, &ctx.accounts.payee_kyc_user)?;
+
+    // The order PDA becomes the delegate of the payer's token account for every
+    // scheduled execution. A token account has a single delegate, so creating
+    // another order on the same account replaces this approval.
+    let executions = (end_time - first_execution_time) / interval_seconds + 1;
+    let allowance = amount
+        .checked_mul(executions as u64)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    let cpi_accounts = token_2022::Approve {
+        to: ctx.accounts.payer_token_account.to_account_info(),
+        delegate: ctx.accounts.standing_order.to_account_info(),
+        authority: ctx.accounts.payer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_2022::approve(cpi_ctx, allowance)?;
+
+    let standing_order = &mut ctx.accounts.standing_order;
+    standing_order.payer = ctx.accounts.payer.key();
+    standing_order.payee = ctx.accounts.payee_token_account.owner;
+    standing_order.mint = ctx.accounts.mint.key();
+    standing_order.payer_token_account = ctx.accounts.payer_token_account.key();
+    standing_order.payee_token_account = ctx.accounts.payee_token_account.key();
+    standing_order.order_id = order_id;
+    standing_order.amount = amount;
+    standing_order.interval_seconds = interval_seconds;
+    standing_order.next_execution_time = first_execution_time;
+    standing_order.end_time = end_time;
+    standing_order.status = StandingOrderStatus::Active;
+    standing_order.execution_count = 0;
+    standing_order.failed_attempts = 0;
+    standing_order.last_attempt_time = 0;
+    standing_order.creation_time = now;
+    standing_order.bump = ctx.bumps.standing_order;
+
+    emit_status(standing_order, now);
+
+    msg!("Standing order {} created by {}", standing_order.key(), standing_order.payer);
+    msg!("Pays {} to {} every {}s", amount, standing_order.payee, interval_seconds);
+    Ok(())
+}
+
+// Pause a standing order; due executions are not attempted while paused
+pub(crate) fn pause_standing_order(ctx: Context<ManageStandingOrder>) -> Result<()> {
+    let standing_order = &mut ctx.accounts.standing_order;
+    if standing_order.status != StandingOrderStatus::Active {
+        return Err(MicaEurError::StandingOrderNotActive.into());
+    }
+    standing_order.status = StandingOrderStatus::Paused;
+
+    emit_status(standing_order, Clock::get()?.unix_timestamp);
+    msg!("Standing order {} paused", standing_order.key());
+    Ok(())
+}
+
+// Resume a paused standing order
+pub(crate) fn resume_standing_order(ctx: Context<ManageStandingOrder>) -> Result<()> {
+    let standing_order = &mut ctx.accounts.standing_order;
+    if standing_order.status != StandingOrderStatus::Paused {
+        return Err(MicaEurError::StandingOrderNotPaused.into());
+    }
+    standing_order.status = StandingOrderStatus::Active;
+
+    emit_status(standing_order, Clock::get()?.unix_timestamp);
+    msg!("Standing order {} resumed", standing_order.key());
+    Ok(())
+}
+
+// Skip the next scheduled execution without paying it
+pub(crate) fn skip_standing_order(ctx: Context<ManageStandingOrder>) -> Result<()> {
+    let standing_order = &mut ctx.accounts.standing_order;
+    if standing_order.status != StandingOrderStatus::Active
+        && standing_order.status != StandingOrderStatus::Paused
+    {
+        return Err(MicaEurError::StandingOrderNotActive.into());
+    }
+    let skipped_time = standing_order.next_execution_time;
+    standing_order.advance_schedule()?;
+
+    emit_status(standing_order, Clock::get()?.unix_timestamp);
+    msg!("Standing order {} skipped execution due at {}", standing_order.key(), skipped_time);
+    Ok(())
+}
+
+// Cancel a standing order, revoke its delegation and refund the rent to the payer
+pub(crate) fn cancel_standing_order(ctx: Context<CancelStandingOrder>) -> Result<()> {
+    let standing_order_key = ctx.accounts.standing_order.key();
+
+    // Only revoke if the delegation still belongs to this order
+    if ctx.accounts.payer_token_account.delegate == Some(standing_order_key).into() {
+        let cpi_accounts = token_2022::Revoke {
+            source: ctx.accounts.payer_token_account.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::revoke(cpi_ctx)?;
+    }
+
+    let standing_order = &mut ctx.accounts.standing_order;
+    standing_order.status = StandingOrderStatus::Cancelled;
+
+    emit_status(standing_order, Clock::get()?.unix_timestamp);
+    msg!("Standing order {} cancelled", standing_order_key);
+    Ok(())
+}
+
+// Execute a due standing order. A lack of funds, allowance or a frozen account is
+// recorded as a failed attempt instead of an error so the retry counter persists.
+pub(crate) fn execute_standing_order<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteStandingOrder<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let standing_order_key = ctx.accounts.standing_order.key();
+    let keeper = ctx.accounts.keeper.key();
+
+    {
+        let standing_order = &ctx.accounts.standing_order;
+        if standing_order.status != StandingOrderStatus::Active {
+            return Err(MicaEurError::StandingOrderNotActive.into());
+        }
+        if !standing_order.is_due(now) {
+            return Err(MicaEurError::StandingOrderNotDue.into());
+        }
+    }
+    check_transfer_parties(&ctx.accounts.payer_kyc_user, &ctx.accounts.payee_kyc_user)?;
+
+    let amount = ctx.accounts.standing_order.amount;
+    let source = &ctx.accounts.payer_token_account;
+    let failure = if source.is_frozen() || ctx.accounts.payee_token_account.is_frozen() {
+        Some(StandingOrderFailureReason::AccountFrozen)
+    } else if source.amount < amount {
+        Some(StandingOrderFailureReason::InsufficientBalance)
+    } else if source.delegate != Some(standing_order_key).into() || source.delegated_amount < amount {
+        Some(StandingOrderFailureReason::InsufficientAllowance)
+    } else {
+        None
+    };
+
+    if let Some(reason) = failure {
+        let standing_order = &mut ctx.accounts.standing_order;
+        standing_order.failed_attempts = standing_order.failed_attempts.saturating_add(1);
+        standing_order.last_attempt_time = now;
+
+        let attempt = standing_order.failed_attempts;
+        let gave_up = attempt >= STANDING_ORDER_MAX_RETRIES;
+        if gave_up {
+            standing_order.advance_schedule()?;
+        }
+
+        emit!(StandingOrderExecutionFailed {
+            standing_order: standing_order_key,
+            reason,
+            attempt,
+            gave_up,
+            keeper,
+            timestamp: now,
+        });
+        msg!("Standing order {} execution failed: {:?}", standing_order_key, reason);
+        return Ok(());
+    }
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+
+    // Sign as the delegate with the order PDA seeds
+    let payer = ctx.accounts.standing_order.payer;
+    let order_id_bytes = ctx.accounts.standing_order.order_id.to_le_bytes();
+    let bump = [ctx.accounts.standing_order.bump];
+    let seeds = &[STANDING_ORDER_SEED, payer.as_ref(), order_id_bytes.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.payee_token_account.to_account_info(),
+        authority: ctx.accounts.standing_order.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let standing_order = &mut ctx.accounts.standing_order;
+    standing_order.execution_count = standing_order
+        .execution_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    standing_order.last_attempt_time = now;
+    standing_order.advance_schedule()?;
+
+    emit!(StandingOrderExecuted {
+        standing_order: standing_order_key,
+        payer,
+        payee: standing_order.payee,
+        amount,
+        execution_count: standing_order.execution_count,
+        keeper,
+        rule_set_version: RULE_SET_VERSION,
+        timestamp: now,
+    });
+    msg!("Standing order {} paid {} to {}", standing_order_key, amount, standing_order.payee);
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Count `amount` minted by the program towards the mint's cumulative issuance
+pub fn record_minted(mint_info: &mut MintInfo, amount: u64) -> Result<()> {
+    mint_info.total_minted = mint_info
+        .total_minted
+        .checked_add(amount as u128)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Count `amount` burned by the program towards the mint's cumulative redemptions
+pub fn record_burned(mint_info: &mut MintInfo, amount: u64) -> Result<()> {
+    mint_info.total_burned = mint_info
+        .total_burned
+        .checked_add(amount as u128)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Supply the program's own mints and burns account for. It differs from the SPL
+/// supply when tokens were minted or burned out of band, e.g. by a holder burning
+/// directly through Token-2022.
+pub fn outstanding_supply(mint_info: &MintInfo) -> i128 {
+    mint_info.total_minted as i128 - mint_info.total_burned as i128
+}
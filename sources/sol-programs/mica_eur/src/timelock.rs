@@ -0,0 +1,371 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::issuance::apply_max_supply;
+use crate::transfer_fees::set_mint_transfer_fee;
+use crate::whitepaper::check_whitepaper;
+use crate::{set_mint_active, MintInfo};
+
+/// Delay a mint's sensitive parameter changes wait out before they take effect.
+/// Once configured, the issuer queues those changes here instead of making them
+/// directly, giving holders notice of what is coming and when.
+#[account]
+#[derive(InitSpace)]
+pub struct Timelock {
+    pub mint: Pubkey,                 // Mint the timelock governs
+    pub delay: i64,                   // Seconds between queueing and execution
+    pub operation_count: u64,         // Next operation ID
+    pub bump: u8,
+}
+
+/// Parameter change that has to wait out a timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub enum TimelockAction {
+    SetSupplyCap { max_supply: u64 },
+    SetTransferFee { transfer_fee_bps: u16, max_transfer_fee: u64, fee_treasury: Pubkey },
+    SetIssuer { issuer: Pubkey },
+    SetFreezeAuthority { freeze_authority: Pubkey },
+    SetPermanentDelegate { permanent_delegate: Pubkey },
+    SetPauser { pauser: Pubkey },
+    SetWhitepaperUri {
+        #[max_len(MAX_WHITEPAPER_URI_LEN)]
+        whitepaper_uri: String,
+        content_hash: [u8; 32],
+    },
+    SetDelay { delay: i64 },
+    DeactivateMint,
+    ReactivateMint,
+}
+
+/// Lifecycle of a timelock operation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum TimelockStatus {
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+/// A queued parameter change
+#[account]
+#[derive(InitSpace)]
+pub struct TimelockOperation {
+    pub timelock: Pubkey,             // Timelock it is queued on
+    pub operation_id: u64,            // Sequence number within the timelock
+    pub action: TimelockAction,
+    pub proposer: Pubkey,             // Issuer that queued it
+    pub status: TimelockStatus,
+    pub queued_time: i64,
+    pub eta: i64,                     // Earliest execution time
+    pub resolution_time: i64,         // When executed or cancelled, 0 while queued
+}
+
+/// Emitted when the issuer puts a mint under a timelock
+#[event]
+pub struct TimelockConfigured {
+    pub mint: Pubkey,
+    pub timelock: Pubkey,
+    pub delay: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a change is queued
+#[event]
+pub struct OperationQueued {
+    pub operation: Pubkey,
+    pub operation_id: u64,
+    pub action: TimelockAction,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued change is executed or cancelled
+#[event]
+pub struct OperationResolved {
+    pub operation: Pubkey,
+    pub status: TimelockStatus,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTimelock<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [TIMELOCK_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + Timelock::INIT_SPACE,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QueueOperation<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+        has_one = timelock,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        mut,
+        seeds = [TIMELOCK_SEED, mint_info.mint.as_ref()],
+        bump = timelock.bump,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            TIMELOCK_OPERATION_SEED,
+            timelock.key().as_ref(),
+            &timelock.operation_count.to_le_bytes(),
+        ],
+        bump,
+        space = 8 + TimelockOperation::INIT_SPACE,
+    )]
+    pub operation: Account<'info, TimelockOperation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOperation<'info> {
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = timelock,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TIMELOCK_SEED, mint.key().as_ref()],
+        bump = timelock.bump,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        mut,
+        has_one = timelock,
+    )]
+    pub operation: Account<'info, TimelockOperation>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    /// Fee treasury of a transfer fee change
+    #[account(
+        constraint = fee_treasury.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub fee_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOperation<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+        has_one = timelock,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        mut,
+        has_one = timelock,
+    )]
+    pub operation: Account<'info, TimelockOperation>,
+}
+
+/// Reject changing a timelocked parameter directly on a mint with a timelock
+pub fn check_no_timelock(mint_info: &MintInfo) -> Result<()> {
+    if mint_info.timelock != Pubkey::default() {
+        return Err(MicaEurError::TimelockRequired.into());
+    }
+    Ok(())
+}
+
+fn check_delay(delay: i64) -> Result<()> {
+    if !(MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&delay) {
+        return Err(MicaEurError::InvalidTimelockDelay.into());
+    }
+    Ok(())
+}
+
+// Put a mint's sensitive parameter changes under a timelock (issuer only). Once set
+// the timelock stays; its delay can only change through a queued `SetDelay`.
+pub(crate) fn configure_timelock(ctx: Context<ConfigureTimelock>, delay: i64) -> Result<()> {
+    check_delay(delay)?;
+    let timelock = &mut ctx.accounts.timelock;
+    timelock.mint = ctx.accounts.mint_info.mint;
+    timelock.delay = delay;
+    timelock.operation_count = 0;
+    timelock.bump = ctx.bumps.timelock;
+
+    ctx.accounts.mint_info.timelock = timelock.key();
+
+    emit!(TimelockConfigured {
+        mint: timelock.mint,
+        timelock: timelock.key(),
+        delay,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Timelock of mint {} set to {}s", timelock.mint, delay);
+    Ok(())
+}
+
+// Queue a parameter change, executable once the timelock's delay has passed (issuer only)
+pub(crate) fn queue_operation(ctx: Context<QueueOperation>, action: TimelockAction) -> Result<()> {
+    match &action {
+        TimelockAction::SetWhitepaperUri { whitepaper_uri, content_hash } => {
+            check_whitepaper(whitepaper_uri, content_hash)?
+        }
+        TimelockAction::SetIssuer { issuer: key }
+        | TimelockAction::SetFreezeAuthority { freeze_authority: key }
+        | TimelockAction::SetPermanentDelegate { permanent_delegate: key }
+        | TimelockAction::SetPauser { pauser: key } => {
+            if *key == Pubkey::default() {
+                return Err(MicaEurError::InvalidTimelockAction.into());
+            }
+        }
+        TimelockAction::SetDelay { delay } => check_delay(*delay)?,
+        TimelockAction::SetSupplyCap { .. }
+        | TimelockAction::SetTransferFee { .. }
+        | TimelockAction::DeactivateMint
+        | TimelockAction::ReactivateMint => {}
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let timelock = &mut ctx.accounts.timelock;
+    let operation = &mut ctx.accounts.operation;
+    operation.timelock = timelock.key();
+    operation.operation_id = timelock.operation_count;
+    operation.action = action.clone();
+    operation.proposer = ctx.accounts.issuer.key();
+    operation.status = TimelockStatus::Queued;
+    operation.queued_time = now;
+    operation.eta = now
+        .checked_add(timelock.delay)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    operation.resolution_time = 0;
+
+    timelock.operation_count = timelock
+        .operation_count
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(OperationQueued {
+        operation: operation.key(),
+        operation_id: operation.operation_id,
+        action,
+        eta: operation.eta,
+        timestamp: now,
+    });
+    msg!("Operation {} queued until {}", operation.operation_id, operation.eta);
+    Ok(())
+}
+
+// Apply a queued change once its delay has passed (anyone)
+pub(crate) fn execute_operation(ctx: Context<ExecuteOperation>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let operation = &ctx.accounts.operation;
+    if operation.status != TimelockStatus::Queued {
+        return Err(MicaEurError::TimelockOperationNotQueued.into());
+    }
+    if now < operation.eta {
+        return Err(MicaEurError::TimelockOperationNotReady.into());
+    }
+
+    let mint_info = &mut ctx.accounts.mint_info;
+    match operation.action.clone() {
+        TimelockAction::SetSupplyCap { max_supply } => apply_max_supply(mint_info, max_supply, now),
+        TimelockAction::SetTransferFee { transfer_fee_bps, max_transfer_fee, fee_treasury } => {
+            if ctx.accounts.fee_treasury.as_ref().map(|a| a.key()) != Some(fee_treasury) {
+                return Err(MicaEurError::TimelockAccountMismatch.into());
+            }
+            set_mint_transfer_fee(
+                mint_info,
+                ctx.bumps.mint_info,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_program,
+                fee_treasury,
+                transfer_fee_bps,
+                max_transfer_fee,
+            )?;
+        }
+        TimelockAction::SetIssuer { issuer } => mint_info.issuer = issuer,
+        TimelockAction::SetFreezeAuthority { freeze_authority } => mint_info.freeze_authority = freeze_authority,
+        TimelockAction::SetPermanentDelegate { permanent_delegate } => {
+            mint_info.permanent_delegate = permanent_delegate
+        }
+        TimelockAction::SetPauser { pauser } => mint_info.pauser = pauser,
+        // Publishing the revision records its version, see `update_whitepaper_uri`
+        TimelockAction::SetWhitepaperUri { .. } => return Err(MicaEurError::InvalidTimelockAction.into()),
+        TimelockAction::SetDelay { delay } => ctx.accounts.timelock.delay = delay,
+        TimelockAction::DeactivateMint => set_mint_active(mint_info, false, operation.proposer)?,
+        TimelockAction::ReactivateMint => set_mint_active(mint_info, true, operation.proposer)?,
+    }
+
+    let operation = &mut ctx.accounts.operation;
+    operation.status = TimelockStatus::Executed;
+    operation.resolution_time = now;
+
+    emit!(OperationResolved {
+        operation: operation.key(),
+        status: TimelockStatus::Executed,
+        timestamp: now,
+    });
+    msg!("Operation {} executed: {:?}", operation.operation_id, operation.action);
+    Ok(())
+}
+
+// Withdraw a queued change (issuer only)
+pub(crate) fn cancel_operation(ctx: Context<CancelOperation>) -> Result<()> {
+    let operation = &mut ctx.accounts.operation;
+    if operation.status != TimelockStatus::Queued {
+        return Err(MicaEurError::TimelockOperationNotQueued.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    operation.status = TimelockStatus::Cancelled;
+    operation.resolution_time = now;
+
+    emit!(OperationResolved {
+        operation: operation.key(),
+        status: TimelockStatus::Cancelled,
+        timestamp: now,
+    });
+    msg!("Operation {} cancelled", operation.operation_id);
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+
+use crate::aml::BlacklistEntry;
+use crate::batch_mint::load_pda;
+use crate::constants::*;
+use crate::counterparties::ApprovedCounterparty;
+use crate::edd::EddConfig;
+use crate::error::MicaEurError;
+use crate::institutions::FastLaneInstitution;
+use crate::kyc_oracle::KycUser;
+use crate::limits::{tier_limits, LimitConfig};
+use crate::policy::{check_class_transaction, check_edd_clearance, check_transfer_party};
+use crate::MintInfo;
+
+#[derive(Accounts)]
+#[instruction(source_owner: Pubkey, dest_owner: Pubkey)]
+pub struct AssertTransferAllowed<'info> {
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [KYC_USER_SEED, source_owner.as_ref()],
+        bump,
+    )]
+    pub sender_kyc_user: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [KYC_USER_SEED, dest_owner.as_ref()],
+        bump,
+    )]
+    pub recipient_kyc_user: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, source_owner.as_ref()],
+        bump,
+    )]
+    pub sender_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, dest_owner.as_ref()],
+        bump,
+    )]
+    pub recipient_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [APPROVED_COUNTERPARTY_SEED, mint_info.mint.as_ref(), source_owner.as_ref()],
+        bump,
+    )]
+    pub sender_counterparty: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [APPROVED_COUNTERPARTY_SEED, mint_info.mint.as_ref(), dest_owner.as_ref()],
+        bump,
+    )]
+    pub recipient_counterparty: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [EDD_CONFIG_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub edd_config: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint_info.mint.as_ref()],
+        bump,
+    )]
+    pub limit_config: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [FAST_LANE_INSTITUTION_SEED, mint_info.mint.as_ref(), source_owner.as_ref()],
+        bump,
+    )]
+    pub sender_institution: UncheckedAccount<'info>,
+}
+
+// Check a move of `amount` between two wallets against the rules the transfer hook
+// applies, failing with the error the hook would raise (anyone, meant for CPI).
+// Nothing is recorded: daily limits and velocity only count real transfers.
+pub(crate) fn assert_transfer_allowed(
+    ctx: Context<AssertTransferAllowed>,
+    source_owner: Pubkey,
+    dest_owner: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let mint_info = &ctx.accounts.mint_info;
+    let mint = mint_info.mint;
+    if mint_info.paused {
+        return Err(MicaEurError::TokenPaused.into());
+    }
+    if !mint_info.is_active {
+        return Err(MicaEurError::MintInactive.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let sender_kyc_user = load_pda::<KycUser>(&ctx.accounts.sender_kyc_user, &[KYC_USER_SEED, source_owner.as_ref()])?;
+    let recipient_kyc_user = load_pda::<KycUser>(&ctx.accounts.recipient_kyc_user, &[KYC_USER_SEED, dest_owner.as_ref()])?;
+    let sender_blacklisted =
+        load_pda::<BlacklistEntry>(&ctx.accounts.sender_blacklist, &[BLACKLIST_SEED, source_owner.as_ref()])?
+            .is_some_and(|entry| entry.in_force(now));
+    let recipient_blacklisted =
+        load_pda::<BlacklistEntry>(&ctx.accounts.recipient_blacklist, &[BLACKLIST_SEED, dest_owner.as_ref()])?
+            .is_some_and(|entry| entry.in_force(now));
+    check_transfer_party(source_owner, sender_kyc_user.as_ref(), sender_blacklisted, None)?;
+    check_transfer_party(dest_owner, recipient_kyc_user.as_ref(), recipient_blacklisted, None)?;
+
+    let counterparties = [
+        load_pda::<ApprovedCounterparty>(
+            &ctx.accounts.sender_counterparty,
+            &[APPROVED_COUNTERPARTY_SEED, mint.as_ref(), source_owner.as_ref()],
+        )?,
+        load_pda::<ApprovedCounterparty>(
+            &ctx.accounts.recipient_counterparty,
+            &[APPROVED_COUNTERPARTY_SEED, mint.as_ref(), dest_owner.as_ref()],
+        )?,
+    ];
+    for counterparty in counterparties.iter().flatten() {
+        counterparty.check_transfer(Some(amount))?;
+    }
+
+    // Both parties passed the checks above, so both are registered
+    if let (Some(sender_kyc_user), Some(recipient_kyc_user)) = (sender_kyc_user.as_ref(), recipient_kyc_user.as_ref()) {
+        let threshold = load_pda::<EddConfig>(&ctx.accounts.edd_config, &[EDD_CONFIG_SEED, mint.as_ref()])?
+            .map_or(0, |config| config.transfer_threshold);
+        check_edd_clearance(sender_kyc_user, recipient_kyc_user, amount, threshold)?;
+
+        // Fast-lane institutions have no transaction limit, other senders are held
+        // to their tier's and their class's
+        let institution = load_pda::<FastLaneInstitution>(
+            &ctx.accounts.sender_institution,
+            &[FAST_LANE_INSTITUTION_SEED, mint.as_ref(), source_owner.as_ref()],
+        )?;
+        if !institution.is_some_and(|institution| institution.applies_to(Some(sender_kyc_user))) {
+            let limit_config = load_pda::<LimitConfig>(&ctx.accounts.limit_config, &[LIMIT_CONFIG_SEED, mint.as_ref()])?;
+            tier_limits(limit_config.as_ref(), sender_kyc_user.verification_level, mint_info.decimals)
+                .check_transaction(amount)?;
+            check_class_transaction(sender_kyc_user, amount, mint_info.decimals)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        transfer_fee::{TransferFeeConfig, MAX_FEE_BASIS_POINTS},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::{Account as TokenAccountState, Mint as MintState},
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::{
+    harvest_withheld_tokens_to_mint, transfer_fee_set, withdraw_withheld_tokens_from_mint,
+    HarvestWithheldTokensToMint, TransferFeeSetTransferFee, WithdrawWithheldTokensFromMint,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::timelock::check_no_timelock;
+use crate::MintInfo;
+
+/// Emitted when the issuer changes the transfer fee of a mint
+#[event]
+pub struct TransferFeeConfigured {
+    pub mint: Pubkey,
+    pub transfer_fee_bps: u16,
+    pub max_transfer_fee: u64,
+    pub fee_treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when withheld fees are withdrawn to the fee treasury
+#[event]
+pub struct WithheldFeesWithdrawn {
+    pub mint: Pubkey,
+    pub fee_treasury: Pubkey,
+    pub amount: u64,
+    pub harvested_accounts: u32,      // Token accounts harvested first, 0 for a plain withdrawal
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTransferFee<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account withheld fees are withdrawn to
+    #[account(
+        constraint = fee_treasury.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub fee_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWithheldFees<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        has_one = fee_treasury @ MicaEurError::FeeTreasuryMismatch,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub fee_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Withheld fees accumulated by the mint itself
+fn withheld_on_mint(mint: &AccountInfo) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let config = state.get_extension::<TransferFeeConfig>()?;
+    Ok(u64::from(config.withheld_amount))
+}
+
+// Withdraw the fees withheld on the mint to the treasury, signing with the mint info PDA
+fn withdraw_to_treasury<'info>(
+    accounts: &WithdrawWithheldFees<'info>,
+    mint_info_bump: u8,
+) -> Result<u64> {
+    let amount = withheld_on_mint(&accounts.mint.to_account_info())?;
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let mint_key = accounts.mint.key();
+    let bump = [mint_info_bump];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    withdraw_withheld_tokens_from_mint(CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        WithdrawWithheldTokensFromMint {
+            token_program_id: accounts.token_program.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            destination: accounts.fee_treasury.to_account_info(),
+            authority: accounts.mint_info.to_account_info(),
+        },
+        signer,
+    ))?;
+    Ok(amount)
+}
+
+// Set the transfer fee and fee treasury of a mint (issuer only). Token-2022 applies
+// a new fee from two epochs after it is set, so holders see changes coming. Mints
+// with a timelock queue fee changes on it instead.
+pub(crate) fn configure_transfer_fee(
+    ctx: Context<ConfigureTransferFee>,
+    transfer_fee_bps: u16,
+    max_transfer_fee: u64,
+) -> Result<()> {
+    check_no_timelock(&ctx.accounts.mint_info)?;
+    let fee_treasury = ctx.accounts.fee_treasury.key();
+    set_mint_transfer_fee(
+        &mut ctx.accounts.mint_info,
+        ctx.bumps.mint_info,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        fee_treasury,
+        transfer_fee_bps,
+        max_transfer_fee,
+    )
+}
+
+/// Validate and set the transfer fee and fee treasury of a mint, signing with the
+/// mint info PDA holding the fee authority
+pub fn set_mint_transfer_fee<'info>(
+    mint_info: &mut Account<'info, MintInfo>,
+    mint_info_bump: u8,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Program<'info, Token2022>,
+    fee_treasury: Pubkey,
+    transfer_fee_bps: u16,
+    max_transfer_fee: u64,
+) -> Result<()> {
+    if transfer_fee_bps > MAX_FEE_BASIS_POINTS || (transfer_fee_bps > 0 && max_transfer_fee == 0) {
+        return Err(MicaEurError::InvalidTransferFee.into());
+    }
+    if transfer_fee_bps > 0 && mint_info.confidential_transfers {
+        return Err(MicaEurError::ConfidentialTransferFeeConflict.into());
+    }
+
+    let mint_key = mint.key();
+    let bump = [mint_info_bump];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    transfer_fee_set(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferFeeSetTransferFee {
+                token_program_id: token_program.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: mint_info.to_account_info(),
+            },
+            signer,
+        ),
+        transfer_fee_bps,
+        max_transfer_fee,
+    )?;
+
+    mint_info.transfer_fee_bps = transfer_fee_bps;
+    mint_info.max_transfer_fee = max_transfer_fee;
+    mint_info.fee_treasury = fee_treasury;
+
+    emit!(TransferFeeConfigured {
+        mint: mint_key,
+        transfer_fee_bps,
+        max_transfer_fee,
+        fee_treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Transfer fee of {} set to {} bps, at most {}", mint_key, transfer_fee_bps, max_transfer_fee);
+    Ok(())
+}
+
+// Withdraw the fees withheld on the mint to the fee treasury (issuer only)
+pub(crate) fn withdraw_withheld_fees(ctx: Context<WithdrawWithheldFees>) -> Result<()> {
+    let amount = withdraw_to_treasury(ctx.accounts, ctx.bumps.mint_info)?;
+
+    emit!(WithheldFeesWithdrawn {
+        mint: ctx.accounts.mint.key(),
+        fee_treasury: ctx.accounts.fee_treasury.key(),
+        amount,
+        harvested_accounts: 0,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Withdrew {} in withheld fees", amount);
+    Ok(())
+}
+
+// Harvest the fees withheld on the token accounts passed as remaining accounts into
+// the mint, then withdraw everything to the fee treasury (issuer only). Harvesting
+// needs no authority, so accounts of any holder can be swept.
+pub(crate) fn harvest_fees_to_treasury<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawWithheldFees<'info>>,
+) -> Result<()> {
+    let mint_key = ctx.accounts.mint.key();
+    for source in ctx.remaining_accounts.iter() {
+        let data = source.try_borrow_data()?;
+        let source_account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+        if source.owner != &Token2022::id() || source_account.base.mint != mint_key {
+            return Err(MicaEurError::TokenAccountMintMismatch.into());
+        }
+    }
+
+    if !ctx.remaining_accounts.is_empty() {
+        harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                HarvestWithheldTokensToMint {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            ctx.remaining_accounts.to_vec(),
+        )?;
+    }
+
+    let amount = withdraw_to_treasury(ctx.accounts, ctx.bumps.mint_info)?;
+
+    emit!(WithheldFeesWithdrawn {
+        mint: mint_key,
+        fee_treasury: ctx.accounts.fee_treasury.key(),
+        amount,
+        harvested_accounts: ctx.remaining_accounts.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Harvested {} accounts and withdrew {} in withheld fees", ctx.remaining_accounts.len(), amount);
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::MintInfo;
+
+/// Travel-rule threshold of a mint (FATF Recommendation 16, EU TFR). Transfers of
+/// at least `threshold` only pass the transfer hook with a travel-rule record the
+/// originator attached beforehand, carrying hashed originator and beneficiary data.
+#[account]
+#[derive(InitSpace)]
+pub struct TravelRuleConfig {
+    pub mint: Pubkey,                 // Mint the threshold applies to
+    pub threshold: u64,               // Smallest transfer needing a record, 0 to disable
+    pub updated_by: Pubkey,           // Issuer that last set it
+    pub last_update: i64,
+}
+
+impl TravelRuleConfig {
+    /// Whether a transfer of `amount` needs a travel-rule record. `amount` is `None`
+    /// for confidential transfers, which cannot be told apart from large ones.
+    pub fn applies_to(&self, amount: Option<u64>) -> bool {
+        self.threshold > 0 && amount.is_none_or(|amount| amount >= self.threshold)
+    }
+}
+
+/// Emitted when the issuer sets the travel-rule threshold
+#[event]
+pub struct TravelRuleConfigured {
+    pub mint: Pubkey,
+    pub threshold: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTravelRule<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [TRAVEL_RULE_CONFIG_SEED, mint_info.mint.as_ref()],
+        bump,
+        space = 8 + TravelRuleConfig::INIT_SPACE,
+    )]
+    pub travel_rule_config: Account<'info, TravelRuleConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Set the amount from which transfers must carry travel-rule data (issuer only)
+pub(crate) fn configure_travel_rule(ctx: Context<ConfigureTravelRule>, threshold: u64) -> Result<()> {
+    let config = &mut ctx.accounts.travel_rule_config;
+    config.mint = ctx.accounts.mint_info.mint;
+    config.threshold = threshold;
+    config.updated_by = ctx.accounts.issuer.key();
+    config.last_update = Clock::get()?.unix_timestamp;
+
+    emit!(TravelRuleConfigured {
+        mint: config.mint,
+        threshold,
+        timestamp: config.last_update,
+    });
+    Ok(())
+}
@@ -0,0 +1,511 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::access_control::{has_mint_role, roles, RoleAssignment};
+use crate::aml::BlacklistEntry;
+use crate::approvals::{check_single_key_mint, ApprovalSet};
+use crate::batch_mint::load_if_initialized;
+use crate::constants::*;
+use crate::country_policy::{check_country_allowed, check_country_transaction, CountryPolicy};
+use crate::error::MicaEurError;
+use crate::freezes::{check_incoming_freeze, FreezeRecord};
+use crate::guards::{enter_guard, leave_guard, InvocationGuard};
+use crate::issuance::{check_supply_cap, record_scheduled_issuance, IssuancePolicy};
+use crate::kyc_oracle::KycUser;
+use crate::limits::{record_mint_volume, tier_limits, LimitConfig, UserLimits};
+use crate::mint_utils::{
+    check_mint_destination, check_mint_extensions, check_token_account_extensions, mint_to_checked,
+    transfer_checked_with_hook,
+};
+use crate::operations::{record_operation, OperationDetails, OperationKind, OperationReceipt};
+use crate::policy::check_mint_recipient;
+use crate::reserve_coverage::{check_mint_coverage, check_reserve_freshness};
+use crate::supply_accounting::record_minted;
+use crate::MintInfo;
+
+/// Treasury of a mint's primary issuance. The issuer mints into the vault token
+/// account, owned by this PDA, and DISTRIBUTOR role holders hand the funds out to
+/// KYC-checked recipients, keeping money creation and distribution apart.
+#[account]
+#[derive(InitSpace)]
+pub struct TreasuryVault {
+    pub mint: Pubkey,                 // Mint the treasury holds
+    pub vault: Pubkey,                // Token account owned by this PDA
+    pub minted_amount: u64,           // Minted into the vault so far
+    pub distributed_amount: u64,      // Distributed out of the vault so far
+    pub bump: u8,
+}
+
+/// Emitted when the issuer sets up or swaps the treasury vault of a mint
+#[event]
+pub struct TreasuryVaultConfigured {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the issuer mints into the treasury
+#[event]
+pub struct TreasuryMinted {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub issuer: Pubkey,
+    pub operation_id: [u8; 16],
+    pub timestamp: i64,
+}
+
+/// Emitted when treasury funds are distributed to a holder
+#[event]
+pub struct TreasuryDistributed {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub recipient: Pubkey,            // Wallet owning the token account
+    pub amount: u64,
+    pub distributor: Pubkey,
+    pub operation_id: [u8; 16],
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTreasuryVault<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [TREASURY_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        space = 8 + TreasuryVault::INIT_SPACE,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    /// Vault token account, created beforehand with the treasury vault PDA as owner
+    #[account(
+        mut,
+        constraint = vault.owner == treasury_vault.key() @ MicaEurError::TokenAccountOwnerMismatch,
+        constraint = vault.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, operation_id: [u8; 16])]
+pub struct MintToTreasury<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        has_one = issuer,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+        constraint = !mint_info.minting_halted @ MicaEurError::MintingHalted,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, mint.key().as_ref()],
+        bump = treasury_vault.bump,
+        has_one = vault,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receipt for the client-supplied operation ID, rejects reuse
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            OPERATION_RECEIPT_SEED,
+            mint.key().as_ref(),
+            &[OperationKind::TreasuryMint as u8],
+            issuer.key().as_ref(),
+            &operation_id,
+        ],
+        bump,
+        space = 8 + OperationReceipt::INIT_SPACE,
+    )]
+    pub operation_receipt: Account<'info, OperationReceipt>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// Approval set of the mint, required once one is configured
+    pub approval_set: Option<Account<'info, ApprovalSet>>,
+
+    /// Issuance schedule of the mint, required once one is configured
+    #[account(
+        mut,
+        seeds = [ISSUANCE_POLICY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub issuance_policy: Option<Account<'info, IssuancePolicy>>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, operation_id: [u8; 16])]
+pub struct DistributeFromTreasury<'info> {
+    /// The issuer or another holder of the DISTRIBUTOR role
+    #[account(mut)]
+    pub distributor: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+        constraint = mint_info.is_active @ MicaEurError::MintInactive,
+        constraint = !mint_info.paused @ MicaEurError::TokenPaused,
+        constraint = has_mint_role(&mint_info, roles::DISTRIBUTOR, distributor.key, role_assignment.as_deref())
+            @ MicaEurError::MissingRole,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, mint.key().as_ref()],
+        bump = treasury_vault.bump,
+        has_one = vault,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The token account to distribute to
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = token_account.owner == kyc_user.user @ MicaEurError::TokenAccountOwnerMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The KYC user account (must be verified)
+    #[account(
+        seeds = [KYC_USER_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub kyc_user: Account<'info, KycUser>,
+
+    /// Blacklist entry of the recipient
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// Policy of the user's country
+    #[account(
+        seeds = [COUNTRY_POLICY_SEED, kyc_user.country_code.as_bytes()],
+        bump,
+    )]
+    pub country_policy: Account<'info, CountryPolicy>,
+
+    /// Rolling mint and burn volumes of the recipient
+    #[account(
+        init_if_needed,
+        payer = distributor,
+        seeds = [USER_LIMITS_SEED, kyc_user.user.as_ref()],
+        bump,
+        space = 8 + UserLimits::INIT_SPACE,
+    )]
+    pub user_limits: Account<'info, UserLimits>,
+
+    /// Tier limits of the mint, the defaults apply if none are configured
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub limit_config: Option<Account<'info, LimitConfig>>,
+
+    /// Receipt for the client-supplied operation ID, rejects reuse
+    #[account(
+        init,
+        payer = distributor,
+        seeds = [
+            OPERATION_RECEIPT_SEED,
+            mint.key().as_ref(),
+            &[OperationKind::Distribution as u8],
+            distributor.key().as_ref(),
+            &operation_id,
+        ],
+        bump,
+        space = 8 + OperationReceipt::INIT_SPACE,
+    )]
+    pub operation_receipt: Account<'info, OperationReceipt>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Reentrancy guard of the mint
+    #[account(
+        mut,
+        seeds = [INVOCATION_GUARD_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub invocation_guard: Account<'info, InvocationGuard>,
+
+    /// The signer's role assignment, unless it is the issuer
+    #[account(
+        seeds = [ROLE_SEED, mint.key().as_ref(), distributor.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// Freeze record of the recipient token account
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_record: UncheckedAccount<'info>,
+}
+
+// Set up or swap the treasury vault primary issuance goes through (issuer only)
+pub(crate) fn configure_treasury_vault(ctx: Context<ConfigureTreasuryVault>) -> Result<()> {
+    let treasury_vault = &mut ctx.accounts.treasury_vault;
+    // The vault cannot be swapped while it holds undistributed funds
+    let undistributed = treasury_vault.minted_amount > treasury_vault.distributed_amount;
+    if undistributed && treasury_vault.vault != ctx.accounts.vault.key() {
+        return Err(MicaEurError::TreasuryVaultInUse.into());
+    }
+    treasury_vault.mint = ctx.accounts.mint.key();
+    treasury_vault.vault = ctx.accounts.vault.key();
+    treasury_vault.bump = ctx.bumps.treasury_vault;
+
+    // New token accounts start frozen; the mint info PDA holds the freeze authority
+    if ctx.accounts.vault.is_frozen() {
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = token_2022::ThawAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+
+    emit!(TreasuryVaultConfigured {
+        mint: treasury_vault.mint,
+        vault: treasury_vault.vault,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Treasury vault configured for mint {}", treasury_vault.mint);
+    Ok(())
+}
+
+// Mint into the treasury vault (issuer only). The supply checks of `mint_tokens`
+// apply here; the recipient checks apply when the funds are distributed.
+pub(crate) fn mint_to_treasury(ctx: Context<MintToTreasury>, amount: u64, operation_id: [u8; 16]) -> Result<()> {
+    if amount == 0 {
+        return Err(MicaEurError::InvalidAmount.into());
+    }
+    check_mint_extensions(&ctx.accounts.mint.to_account_info())?;
+    check_single_key_mint(
+        &ctx.accounts.mint_info,
+        ctx.accounts.approval_set.as_deref(),
+        ctx.accounts.approval_set.as_ref().map(|a| a.key()),
+        amount,
+    )?;
+    let now = Clock::get()?.unix_timestamp;
+    let supply = ctx.accounts.mint.supply;
+    check_reserve_freshness(&ctx.accounts.mint_info, now)?;
+    check_mint_coverage(&ctx.accounts.mint_info, supply, amount)?;
+    check_supply_cap(&ctx.accounts.mint_info, supply, amount)?;
+    record_scheduled_issuance(&ctx.accounts.mint_info, ctx.accounts.issuance_policy.as_mut(), amount, now)?;
+
+    // The mint info PDA holds the mint authority
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    let cpi_accounts = token_2022::MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.mint_info.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    mint_to_checked(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    record_minted(&mut ctx.accounts.mint_info, amount)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let issuer = ctx.accounts.issuer.key();
+    let vault = ctx.accounts.vault.key();
+    record_operation(
+        &mut ctx.accounts.operation_receipt,
+        operation_id,
+        OperationDetails {
+            kind: OperationKind::TreasuryMint,
+            mint: mint_key,
+            actor: issuer,
+            source: Pubkey::default(),
+            destination: vault,
+            amount,
+        },
+    )?;
+
+    let treasury_vault = &mut ctx.accounts.treasury_vault;
+    treasury_vault.minted_amount = treasury_vault
+        .minted_amount
+        .checked_add(amount)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(TreasuryMinted {
+        mint: mint_key,
+        vault,
+        amount,
+        issuer,
+        operation_id,
+        timestamp: now,
+    });
+    msg!("Minted {} into the treasury", amount);
+    Ok(())
+}
+
+// Distribute treasury funds to a verified holder (DISTRIBUTOR role), under the
+// recipient checks and limits of `mint_tokens`. The remaining accounts are the
+// transfer hook's extra accounts for the vault -> recipient transfer.
+pub(crate) fn distribute_from_treasury<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeFromTreasury<'info>>,
+    amount: u64,
+    operation_id: [u8; 16],
+) -> Result<()> {
+    if amount == 0 {
+        return Err(MicaEurError::InvalidAmount.into());
+    }
+    let blacklist_entry = load_if_initialized::<BlacklistEntry>(&ctx.accounts.blacklist_entry)?;
+    check_mint_recipient(&ctx.accounts.kyc_user, blacklist_entry.as_ref())?;
+    check_country_allowed(&ctx.accounts.country_policy, &ctx.accounts.kyc_user.country_code)?;
+    check_country_transaction(&ctx.accounts.country_policy, &ctx.accounts.kyc_user, amount)?;
+    check_token_account_extensions(&ctx.accounts.token_account.to_account_info())?;
+    check_mint_destination(&ctx.accounts.mint_info, &ctx.accounts.kyc_user.user, &ctx.accounts.token_account.key())?;
+    let now = Clock::get()?.unix_timestamp;
+    // Funds cannot go into an account under a recorded freeze; below, only the
+    // default frozen state of a new account is lifted
+    let freeze_record = load_if_initialized::<FreezeRecord>(&ctx.accounts.freeze_record)?;
+    check_incoming_freeze(freeze_record.as_ref(), now)?;
+
+    // Counted against the recipient's daily mint volume, as a direct mint would be
+    let limits = tier_limits(
+        ctx.accounts.limit_config.as_deref(),
+        ctx.accounts.kyc_user.verification_level,
+        ctx.accounts.mint_info.decimals,
+    );
+    record_mint_volume(&mut ctx.accounts.user_limits, ctx.accounts.kyc_user.user, limits, amount)?;
+
+    // The mint info PDA holds the freeze authority; the treasury vault PDA owns the
+    // vault and moves the funds, the hook checking the recipient again
+    let mint_key = ctx.accounts.mint.key();
+    let bump = [ctx.bumps.mint_info];
+    let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+    let vault_bump = [ctx.accounts.treasury_vault.bump];
+    let vault_seeds = &[TREASURY_VAULT_SEED, mint_key.as_ref(), &vault_bump];
+    let vault_signer = &[&vault_seeds[..]];
+
+    enter_guard(&mut ctx.accounts.invocation_guard)?;
+    if ctx.accounts.token_account.is_frozen() {
+        let cpi_accounts = token_2022::ThawAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+    let cpi_accounts = token_2022::TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.treasury_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, vault_signer)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint_info.decimals)?;
+    leave_guard(&mut ctx.accounts.invocation_guard);
+
+    let distributor = ctx.accounts.distributor.key();
+    let token_account = ctx.accounts.token_account.key();
+    record_operation(
+        &mut ctx.accounts.operation_receipt,
+        operation_id,
+        OperationDetails {
+            kind: OperationKind::Distribution,
+            mint: mint_key,
+            actor: distributor,
+            source: ctx.accounts.vault.key(),
+            destination: token_account,
+            amount,
+        },
+    )?;
+
+    let treasury_vault = &mut ctx.accounts.treasury_vault;
+    treasury_vault.distributed_amount = treasury_vault
+        .distributed_amount
+        .checked_add(amount)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+
+    emit!(TreasuryDistributed {
+        mint: mint_key,
+        token_account,
+        recipient: ctx.accounts.kyc_user.user,
+        amount,
+        distributor,
+        operation_id,
+        timestamp: now,
+    });
+    msg!("Distributed {} from the treasury to {}", amount, token_account);
+    Ok(())
+}
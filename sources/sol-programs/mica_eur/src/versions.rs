@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program_pack::Pack;
 
 /// Program version
 pub const PROGRAM_VERSION: &str = "0.1.0";
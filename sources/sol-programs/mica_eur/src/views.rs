@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::aml::{BlacklistEntry, BlacklistReason};
+use crate::batch_mint::load_pda;
+use crate::constants::*;
+use crate::kyc_oracle::{is_kyc_verified, KycStatus, KycUser};
+use crate::limits::UserLimits;
+use crate::policy::AccountClass;
+use crate::reserve_attestation::{is_reserve_attested, AuditorAttestation};
+use crate::reserve_coverage::coverage_bps;
+use crate::supply_accounting::outstanding_supply;
+use crate::MintInfo;
+
+/// Compliance state of a wallet, as returned by `get_user_compliance_status`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct UserComplianceStatus {
+    pub user: Pubkey,
+    pub kyc_status: Option<KycStatus>, // None without a KYC record
+    pub verified: bool,               // Verified and not expired
+    pub verification_level: u8,
+    pub expiry_time: i64,
+    pub account_class: Option<AccountClass>,
+    pub country_code: String,
+    pub edd_required: bool,
+    pub blacklisted: bool,            // Blacklist entry in force
+    pub blacklist_reason: Option<BlacklistReason>,
+    pub minted_in_window: u128,       // Rolling volumes counted against the user's limits
+    pub burned_in_window: u128,
+}
+
+/// State of a mint, as returned by `get_mint_status`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MintStatus {
+    pub mint: Pubkey,
+    pub issuer: Pubkey,
+    pub is_active: bool,
+    pub paused: bool,
+    pub minting_halted: bool,
+    pub supply: u64,
+    pub max_supply: u64,              // 0 for no cap
+    pub holder_count: u64,            // Open token accounts with a holder record
+    pub transfer_fee_bps: u16,
+    pub max_transfer_fee: u64,
+    pub confidential_transfers: bool,
+    pub rule_set_version: u32,
+    pub whitepaper_version: u32,
+    pub approval_set: Pubkey,
+    pub timelock: Pubkey,
+    pub redemption_deadline: i64,     // 0 unless winding down
+}
+
+/// Supply reconciliation of a mint, as returned by `get_outstanding_supply`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OutstandingSupply {
+    pub mint: Pubkey,
+    pub total_minted: u128,           // Tokens ever minted by the program
+    pub total_burned: u128,           // Tokens ever burned by the program
+    pub outstanding: i128,            // Minted less burned
+    pub supply: u64,                  // Token-2022 mint supply
+    pub reconciled: bool,             // Whether the supply matches the outstanding amount
+}
+
+/// Reserve backing of a mint, as returned by `get_reserve_status`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ReserveStatus {
+    pub mint: Pubkey,
+    pub reserve_merkle_root: [u8; 32],
+    pub reserve_ipfs_cid: String,
+    pub reserve_total_eur: u64,
+    pub last_reserve_update: i64,
+    pub supply: u64,
+    pub coverage_bps: u64,            // u64::MAX for no supply
+    pub coverage_auto_pause: bool,
+    pub attested: bool,               // Whether the auditor confirmed the current root
+}
+
+#[derive(Accounts)]
+pub struct GetUserComplianceStatus<'info> {
+    /// CHECK: Only the key is used for the PDA seeds
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [KYC_USER_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub kyc_user: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [USER_LIMITS_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetMintStatus<'info> {
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct GetOutstandingSupply<'info> {
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct GetReserveStatus<'info> {
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: May not exist; address checked against its seeds, parsed in the handler
+    #[account(
+        seeds = [AUDITOR_ATTESTATION_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub auditor_attestation: UncheckedAccount<'info>,
+}
+
+// Compliance state of a wallet in one simulated call (read-only, anyone). Records
+// the wallet does not have read as their defaults.
+pub(crate) fn get_user_compliance_status(ctx: Context<GetUserComplianceStatus>) -> Result<UserComplianceStatus> {
+    let user = ctx.accounts.user.key();
+    let now = Clock::get()?.unix_timestamp;
+    let kyc_user = load_pda::<KycUser>(&ctx.accounts.kyc_user, &[KYC_USER_SEED, user.as_ref()])?;
+    let blacklist_entry =
+        load_pda::<BlacklistEntry>(&ctx.accounts.blacklist_entry, &[BLACKLIST_SEED, user.as_ref()])?
+            .filter(|entry| entry.in_force(now));
+    let user_limits = load_pda::<UserLimits>(&ctx.accounts.user_limits, &[USER_LIMITS_SEED, user.as_ref()])?;
+
+    Ok(UserComplianceStatus {
+        user,
+        kyc_status: kyc_user.as_ref().map(|kyc_user| kyc_user.status),
+        verified: kyc_user.as_ref().is_some_and(is_kyc_verified),
+        verification_level: kyc_user.as_ref().map_or(0, |kyc_user| kyc_user.verification_level),
+        expiry_time: kyc_user.as_ref().map_or(0, |kyc_user| kyc_user.expiry_time),
+        account_class: kyc_user.as_ref().map(|kyc_user| kyc_user.account_class),
+        country_code: kyc_user.as_ref().map(|kyc_user| kyc_user.country_code.clone()).unwrap_or_default(),
+        edd_required: kyc_user.as_ref().is_some_and(|kyc_user| kyc_user.edd_required),
+        blacklisted: blacklist_entry.is_some(),
+        blacklist_reason: blacklist_entry.map(|entry| entry.reason),
+        minted_in_window: user_limits.as_ref().map_or(0, |limits| limits.minted.current(now)),
+        burned_in_window: user_limits.as_ref().map_or(0, |limits| limits.burned.current(now)),
+    })
+}
+
+// State of a mint in one simulated call (read-only, anyone)
+pub(crate) fn get_mint_status(ctx: Context<GetMintStatus>) -> Result<MintStatus> {
+    let mint_info = &ctx.accounts.mint_info;
+    Ok(MintStatus {
+        mint: mint_info.mint,
+        issuer: mint_info.issuer,
+        is_active: mint_info.is_active,
+        paused: mint_info.paused,
+        minting_halted: mint_info.minting_halted,
+        supply: ctx.accounts.mint.supply,
+        max_supply: mint_info.max_supply,
+        holder_count: mint_info.holder_count,
+        transfer_fee_bps: mint_info.transfer_fee_bps,
+        max_transfer_fee: mint_info.max_transfer_fee,
+        confidential_transfers: mint_info.confidential_transfers,
+        rule_set_version: mint_info.rule_set_version,
+        whitepaper_version: mint_info.whitepaper_version,
+        approval_set: mint_info.approval_set,
+        timelock: mint_info.timelock,
+        redemption_deadline: mint_info.redemption_deadline,
+    })
+}
+
+// Cumulative issuance and redemptions against the Token-2022 supply (read-only,
+// anyone). A mismatch means supply changed outside the program, e.g. a holder
+// burning directly through Token-2022.
+pub(crate) fn get_outstanding_supply(ctx: Context<GetOutstandingSupply>) -> Result<OutstandingSupply> {
+    let mint_info = &ctx.accounts.mint_info;
+    let outstanding = outstanding_supply(mint_info);
+    let supply = ctx.accounts.mint.supply;
+    Ok(OutstandingSupply {
+        mint: mint_info.mint,
+        total_minted: mint_info.total_minted,
+        total_burned: mint_info.total_burned,
+        outstanding,
+        supply,
+        reconciled: outstanding == supply as i128,
+    })
+}
+
+// Reserve backing of a mint in one simulated call (read-only, anyone). Unlike
+// `check_reserve_coverage` it never halts or resumes minting.
+pub(crate) fn get_reserve_status(ctx: Context<GetReserveStatus>) -> Result<ReserveStatus> {
+    let mint_info = &ctx.accounts.mint_info;
+    let supply = ctx.accounts.mint.supply;
+    let attestation = load_pda::<AuditorAttestation>(
+        &ctx.accounts.auditor_attestation,
+        &[AUDITOR_ATTESTATION_SEED, mint_info.mint.as_ref()],
+    )?;
+
+    Ok(ReserveStatus {
+        mint: mint_info.mint,
+        reserve_merkle_root: mint_info.reserve_merkle_root,
+        reserve_ipfs_cid: mint_info.reserve_ipfs_cid.clone(),
+        reserve_total_eur: mint_info.reserve_total_eur,
+        last_reserve_update: mint_info.last_reserve_update,
+        supply,
+        coverage_bps: coverage_bps(mint_info.reserve_total_eur, supply),
+        coverage_auto_pause: mint_info.coverage_auto_pause,
+        attested: attestation.is_some_and(|attestation| is_reserve_attested(mint_info, &attestation)),
+    })
+}
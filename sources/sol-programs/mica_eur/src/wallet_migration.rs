@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::access_control::RoleAssignment;
+use crate::aml::BlacklistEntry;
+use crate::batch_mint::load_pda;
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::freezes::{freeze_reasons, record_freeze, FreezeRecord};
+use crate::iban_registry::{link_iban, relink, IbanRegistry};
+use crate::kyc_oracle::{check_kyc_authority, is_kyc_verified, KycOracleState, KycProvider, KycUser};
+use crate::limits::UserLimits;
+use crate::MintInfo;
+
+/// Emitted when a verified user moves to a new wallet
+#[event]
+pub struct KycUserMigrated {
+    pub old_user: Pubkey,
+    pub new_user: Pubkey,
+    pub mint: Pubkey,
+    pub frozen_token_account: Pubkey, // Token account of the old wallet frozen by the migration
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct MigrateKycUser<'info> {
+    /// KYC authority co-signing the migration, paying for the new records
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The user's new wallet
+    pub new_user: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ORACLE_STATE_SEED],
+        bump,
+    )]
+    pub kyc_oracle_state: Account<'info, KycOracleState>,
+
+    /// KYC record of the old wallet, closed by the migration
+    #[account(
+        mut,
+        close = authority,
+        seeds = [KYC_USER_SEED, old_kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub old_kyc_user: Account<'info, KycUser>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [KYC_USER_SEED, new_user.key().as_ref()],
+        bump,
+        space = 8 + KycUser::INIT_SPACE,
+    )]
+    pub new_kyc_user: Account<'info, KycUser>,
+
+    /// Blacklist entry of the old wallet, which may not exist
+    /// CHECK: Address derived from the old wallet; parsed in the handler
+    #[account(
+        seeds = [BLACKLIST_SEED, old_kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// Rolling volumes of the old wallet, which may not exist
+    /// CHECK: Address derived from the old wallet; parsed in the handler
+    #[account(
+        seeds = [USER_LIMITS_SEED, old_kyc_user.user.as_ref()],
+        bump,
+    )]
+    pub old_user_limits: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [USER_LIMITS_SEED, new_user.key().as_ref()],
+        bump,
+        space = 8 + UserLimits::INIT_SPACE,
+    )]
+    pub new_user_limits: Account<'info, UserLimits>,
+
+    /// Registry record of the user's IBAN, created if the user registered before
+    /// the registry existed
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [IBAN_REGISTRY_SEED, old_kyc_user.iban_hash.as_ref()],
+        bump,
+        space = 8 + IbanRegistry::INIT_SPACE,
+    )]
+    pub iban_registry: Account<'info, IbanRegistry>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// The mint account for the EUR token
+    /// CHECK: Validated by token_program
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token account of the old wallet, frozen by the migration
+    #[account(
+        mut,
+        constraint = old_token_account.mint == mint.key() @ MicaEurError::TokenAccountMintMismatch,
+        constraint = old_token_account.owner == old_kyc_user.user @ MicaEurError::TokenAccountOwnerMismatch,
+    )]
+    pub old_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [FREEZE_RECORD_SEED, old_token_account.key().as_ref()],
+        bump,
+        space = 8 + FreezeRecord::INIT_SPACE,
+    )]
+    pub freeze_record: Account<'info, FreezeRecord>,
+
+    /// Token program: must be Token-2022
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The signer's provider record, required unless the oracle authority or a KYC admin signs
+    #[account(
+        seeds = [KYC_PROVIDER_SEED, authority.key().as_ref()],
+        bump,
+    )]
+    pub kyc_provider: Option<Account<'info, KycProvider>>,
+
+    /// The signer's role assignment, for holders of the KYC_ADMIN role
+    #[account(
+        seeds = [ROLE_SEED, kyc_oracle_state.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+// Move a verified user to a new wallet, e.g. after losing the old key, without
+// redoing KYC. The new wallet and a KYC authority sign; the old wallet cannot.
+// The verification, account class, screening and rolling volumes carry over to
+// the new wallet's records, the IBAN is relinked and the old KYC record closed.
+// The old wallet's token account of `mint` is frozen so its balance can only be
+// moved to the new wallet through `recover_account`.
+pub(crate) fn migrate_kyc_user(ctx: Context<MigrateKycUser>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let old = &ctx.accounts.old_kyc_user;
+    let old_user = old.user;
+    let new_user = ctx.accounts.new_user.key();
+    check_kyc_authority(
+        &ctx.accounts.kyc_oracle_state,
+        ctx.accounts.authority.key,
+        ctx.accounts.kyc_provider.as_deref(),
+        Some(old),
+        ctx.accounts.role_assignment.as_deref(),
+    )?;
+    if !is_kyc_verified(old) {
+        return Err(MicaEurError::UserNotVerified.into());
+    }
+
+    // A blacklisted user cannot shed the entry by changing wallets
+    let blacklist_entry = load_pda::<BlacklistEntry>(
+        &ctx.accounts.blacklist_entry,
+        &[BLACKLIST_SEED, old_user.as_ref()],
+    )?;
+    if blacklist_entry.is_some_and(|entry| entry.in_force(now)) {
+        return Err(MicaEurError::UserBlacklisted.into());
+    }
+
+    // Verification and history move over unchanged
+    let new = &mut ctx.accounts.new_kyc_user;
+    new.set_inner((**old).clone());
+    new.user = new_user;
+
+    // Volumes already used in the current windows still count
+    let old_limits = load_pda::<UserLimits>(
+        &ctx.accounts.old_user_limits,
+        &[USER_LIMITS_SEED, old_user.as_ref()],
+    )?;
+    let new_limits = &mut ctx.accounts.new_user_limits;
+    new_limits.user = new_user;
+    if let Some(old_limits) = old_limits {
+        new_limits.minted = old_limits.minted;
+        new_limits.burned = old_limits.burned;
+    }
+
+    // The IBAN follows the user
+    let iban_registry = &mut ctx.accounts.iban_registry;
+    if iban_registry.user == Pubkey::default() {
+        link_iban(iban_registry, old.iban_hash, new_user, now)?;
+    } else if iban_registry.user == old_user {
+        relink(iban_registry, new_user, now)?;
+    } else {
+        return Err(MicaEurError::IbanAlreadyLinked.into());
+    }
+
+    // Freeze the old wallet's funds; a freeze already in force keeps its record
+    let mint_key = ctx.accounts.mint.key();
+    let old_token_account = &ctx.accounts.old_token_account;
+    if !ctx.accounts.freeze_record.in_force(now) {
+        record_freeze(
+            &mut ctx.accounts.freeze_record,
+            mint_key,
+            old_token_account.key(),
+            old_user,
+            ctx.accounts.authority.key(),
+            freeze_reasons::WALLET_MIGRATED,
+            String::new(),
+            0,
+            0,
+            now,
+        )?;
+    }
+    if !old_token_account.is_frozen() {
+        let bump = [ctx.bumps.mint_info];
+        let seeds = &[MINT_INFO_SEED, mint_key.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = token_2022::FreezeAccount {
+            account: old_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_info.to_account_info(),
+        };
+        token_2022::freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+
+    emit!(KycUserMigrated {
+        old_user,
+        new_user,
+        mint: mint_key,
+        frozen_token_account: old_token_account.key(),
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+    msg!("KYC user {} migrated to {}", old_user, new_user);
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::timelock::{TimelockAction, TimelockOperation, TimelockStatus};
+use crate::MintInfo;
+
+/// One published revision of a mint's crypto-asset white paper. Revisions are
+/// numbered from 1; revision 0 is the URI set at initialization.
+#[account]
+#[derive(InitSpace)]
+pub struct WhitepaperVersion {
+    pub mint: Pubkey,                 // The EUR mint
+    pub version: u32,                 // Revision number
+    #[max_len(MAX_WHITEPAPER_URI_LEN)]
+    pub uri: String,                  // Where the revision is published
+    pub content_hash: [u8; 32],       // SHA-256 of the published document
+    pub published_by: Pubkey,         // Issuer that published it
+    pub publish_time: i64,            // When it became the current white paper
+}
+
+/// Emitted when a new white paper revision takes effect
+#[event]
+pub struct WhitepaperUpdated {
+    pub mint: Pubkey,
+    pub version: u32,
+    pub uri: String,
+    pub content_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct UpdateWhitepaperUri<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint_info.mint.as_ref()],
+        bump,
+        has_one = issuer,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [
+            WHITEPAPER_VERSION_SEED,
+            mint_info.mint.as_ref(),
+            // Wraps instead of panicking; the handler rejects the overflow
+            &mint_info.whitepaper_version.wrapping_add(1).to_le_bytes(),
+        ],
+        bump,
+        space = 8 + WhitepaperVersion::INIT_SPACE,
+    )]
+    pub whitepaper_version: Account<'info, WhitepaperVersion>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Queued `SetWhitepaperUri` operation, required once the mint has a timelock
+    #[account(mut)]
+    pub timelock_operation: Option<Account<'info, TimelockOperation>>,
+}
+
+/// Reject a white paper URI or content hash that cannot be recorded
+pub fn check_whitepaper(uri: &str, content_hash: &[u8; 32]) -> Result<()> {
+    if uri.is_empty() || uri.len() > MAX_WHITEPAPER_URI_LEN || *content_hash == [0; 32] {
+        return Err(MicaEurError::InvalidWhitepaper.into());
+    }
+    Ok(())
+}
+
+// Publish a new white paper revision (issuer only). On a mint with a timelock the
+// revision must have been queued as a `SetWhitepaperUri` operation whose delay has
+// passed; publishing it executes that operation.
+pub(crate) fn update_whitepaper_uri(
+    ctx: Context<UpdateWhitepaperUri>,
+    uri: String,
+    content_hash: [u8; 32],
+) -> Result<()> {
+    check_whitepaper(&uri, &content_hash)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    if ctx.accounts.mint_info.timelock != Pubkey::default() {
+        let operation = ctx
+            .accounts
+            .timelock_operation
+            .as_mut()
+            .ok_or(MicaEurError::TimelockRequired)?;
+        let queued = TimelockAction::SetWhitepaperUri {
+            whitepaper_uri: uri.clone(),
+            content_hash,
+        };
+        if operation.timelock != ctx.accounts.mint_info.timelock || operation.action != queued {
+            return Err(MicaEurError::TimelockAccountMismatch.into());
+        }
+        if operation.status != TimelockStatus::Queued {
+            return Err(MicaEurError::TimelockOperationNotQueued.into());
+        }
+        if now < operation.eta {
+            return Err(MicaEurError::TimelockOperationNotReady.into());
+        }
+        operation.status = TimelockStatus::Executed;
+        operation.resolution_time = now;
+        emit!(crate::OperationResolved {
+            operation: operation.key(),
+            status: TimelockStatus::Executed,
+            timestamp: now,
+        });
+    }
+
+    let mint_info = &mut ctx.accounts.mint_info;
+    mint_info.whitepaper_version = mint_info
+        .whitepaper_version
+        .checked_add(1)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    mint_info.whitepaper_uri = uri.clone();
+
+    let whitepaper_version = &mut ctx.accounts.whitepaper_version;
+    whitepaper_version.mint = mint_info.mint;
+    whitepaper_version.version = mint_info.whitepaper_version;
+    whitepaper_version.uri = uri.clone();
+    whitepaper_version.content_hash = content_hash;
+    whitepaper_version.published_by = ctx.accounts.issuer.key();
+    whitepaper_version.publish_time = now;
+
+    emit!(WhitepaperUpdated {
+        mint: mint_info.mint,
+        version: whitepaper_version.version,
+        uri,
+        content_hash,
+        timestamp: now,
+    });
+
+    msg!("White paper of {} updated to revision {}", mint_info.mint, whitepaper_version.version);
+    Ok(())
+}
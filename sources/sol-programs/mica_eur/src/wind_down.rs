@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::error::MicaEurError;
+use crate::MintInfo;
+
+/// Emitted when the issuer starts the orderly redemption of a mint
+#[event]
+pub struct WindDownInitiated {
+    pub mint: Pubkey,
+    pub issuer: Pubkey,
+    pub redemption_account: Pubkey,   // Issuer account holders return tokens to
+    pub redemption_deadline: i64,
+    pub outstanding_supply: u64,      // Supply left to redeem
+    pub holder_count: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct InitiateWindDown<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        has_one = issuer,
+        has_one = mint,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Issuer token account holders may send their tokens back to
+    #[account(
+        token::mint = mint,
+        constraint = redemption_account.owner == issuer.key() @ MicaEurError::TokenAccountOwnerMismatch,
+    )]
+    pub redemption_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Whether `token_account` is the redemption account of a mint winding down
+pub fn is_redemption_account(mint_info: &MintInfo, token_account: &Pubkey) -> bool {
+    mint_info.wind_down_started_at != 0 && mint_info.redemption_account == *token_account
+}
+
+// Put the mint into orderly redemption (issuer only): minting stops for good, holders
+// may return their tokens to `redemption_account` whatever their KYC standing, and
+// redemptions stay open. The deadline announces the end of the redemption plan and
+// must leave holders at least MIN_REDEMPTION_PERIOD.
+pub(crate) fn initiate_wind_down(ctx: Context<InitiateWindDown>, redemption_deadline: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mint_info = &mut ctx.accounts.mint_info;
+    if mint_info.wind_down_started_at != 0 {
+        return Err(MicaEurError::WindDownAlreadyInitiated.into());
+    }
+    if mint_info.migrated_to != Pubkey::default() {
+        return Err(MicaEurError::MintMigrated.into());
+    }
+    let earliest = now
+        .checked_add(MIN_REDEMPTION_PERIOD)
+        .ok_or(MicaEurError::ArithmeticOverflow)?;
+    if redemption_deadline < earliest {
+        return Err(MicaEurError::InvalidRedemptionDeadline.into());
+    }
+
+    mint_info.is_active = false;
+    mint_info.wind_down_started_at = now;
+    mint_info.redemption_deadline = redemption_deadline;
+    mint_info.redemption_account = ctx.accounts.redemption_account.key();
+
+    emit!(WindDownInitiated {
+        mint: mint_info.mint,
+        issuer: mint_info.issuer,
+        redemption_account: mint_info.redemption_account,
+        redemption_deadline,
+        outstanding_supply: ctx.accounts.mint.supply,
+        holder_count: mint_info.holder_count,
+        timestamp: now,
+    });
+    msg!("Mint {} winding down, redemptions until {}", mint_info.mint, redemption_deadline);
+    Ok(())
+}
@@ -0,0 +1,629 @@
+//! Negative tests of the account constraints: each case builds the accounts of an
+//! instruction, swaps in one account an attacker controls and expects Anchor to
+//! reject the context before any handler runs.
+
+use std::collections::BTreeSet;
+use std::sync::Once;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::solana_program::program_utils::limited_deserialize;
+use anchor_lang::solana_program::system_instruction::SystemInstruction;
+use anchor_lang::solana_program::entrypoint::{ProgramResult, SUCCESS};
+use anchor_lang::solana_program::{bpf_loader_upgradeable, system_program};
+use anchor_lang::{AccountSerialize, Accounts, Discriminator};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        immutable_owner::ImmutableOwner, non_transferable::NonTransferableAccount,
+        BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+    },
+    state::{Account as TokenAccountState, AccountState},
+};
+use ::mica_eur::mica_eur::{DeactivateAmlAuthority, UpdateAmlAuthorityPowers};
+use ::mica_eur::*;
+
+// `Rent::get` for `init_if_needed` accounts that already exist, and the system
+// program's `Assign` for `init` accounts funded and sized ahead of time
+struct RentStub;
+
+impl SyscallStubs for RentStub {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id != system_program::ID {
+            return Ok(());
+        }
+        if let Ok(SystemInstruction::Assign { owner }) = limited_deserialize(&instruction.data, 1024) {
+            let key = instruction.accounts[0].pubkey;
+            if let Some(info) = account_infos.iter().find(|info| *info.key == key) {
+                info.assign(&owner);
+            }
+        }
+        Ok(())
+    }
+}
+
+static STUBS: Once = Once::new();
+
+fn account(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> AccountInfo<'static> {
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(RentStub));
+    });
+    let lamports = Rent::default().minimum_balance(data.len()).max(1);
+    AccountInfo::new(
+        Box::leak(Box::new(key)),
+        false,
+        false,
+        Box::leak(Box::new(lamports)),
+        Box::leak(data.into_boxed_slice()),
+        Box::leak(Box::new(owner)),
+        false,
+        0,
+    )
+}
+
+fn signer(key: Pubkey) -> AccountInfo<'static> {
+    let mut info = account(key, System::id(), vec![]);
+    info.is_signer = true;
+    info.is_writable = true;
+    info
+}
+
+fn writable(mut info: AccountInfo<'static>) -> AccountInfo<'static> {
+    info.is_writable = true;
+    info
+}
+
+fn program(id: Pubkey) -> AccountInfo<'static> {
+    let mut info = account(id, bpf_loader_upgradeable::ID, vec![]);
+    info.executable = true;
+    info
+}
+
+// An account of the program holding `state`
+fn state<T: AccountSerialize>(key: Pubkey, state: &T) -> AccountInfo<'static> {
+    let mut data = vec![];
+    state.try_serialize(&mut data).unwrap();
+    account(key, ::mica_eur::ID, data)
+}
+
+// An existing `init_if_needed` account of the program, all fields zero
+fn zeroed<T: Discriminator>(key: Pubkey, size: usize) -> AccountInfo<'static> {
+    let mut data = vec![0; 8 + size];
+    data[..8].copy_from_slice(&T::DISCRIMINATOR);
+    writable(account(key, ::mica_eur::ID, data))
+}
+
+// A not yet created `init` account, funded and sized ahead of time
+fn uncreated(key: Pubkey, size: usize) -> AccountInfo<'static> {
+    writable(account(key, System::id(), vec![0; 8 + size]))
+}
+
+// A program account not created yet, such as a blacklist entry never written
+fn missing(key: Pubkey) -> AccountInfo<'static> {
+    account(key, System::id(), vec![])
+}
+
+// The program's stand-in for an absent optional account
+fn none() -> AccountInfo<'static> {
+    account(::mica_eur::ID, Pubkey::default(), vec![])
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &::mica_eur::ID).0
+}
+
+// Token account extensions the fixtures can carry. Initializing one needs its
+// state type, so each supported extension is listed here rather than taking any
+// `ExtensionType`.
+#[derive(Clone, Copy)]
+enum AccountExtension {
+    ImmutableOwner,
+    NonTransferable,
+}
+
+impl AccountExtension {
+    fn extension_type(self) -> ExtensionType {
+        match self {
+            AccountExtension::ImmutableOwner => ExtensionType::ImmutableOwner,
+            AccountExtension::NonTransferable => ExtensionType::NonTransferableAccount,
+        }
+    }
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, extensions: &[AccountExtension]) -> Vec<u8> {
+    let base = TokenAccountState {
+        mint,
+        owner,
+        state: AccountState::Initialized,
+        ..Default::default()
+    };
+    if extensions.is_empty() {
+        let mut data = vec![0; TokenAccountState::LEN];
+        base.pack_into_slice(&mut data);
+        return data;
+    }
+
+    let types: Vec<ExtensionType> = extensions.iter().map(|extension| extension.extension_type()).collect();
+    let len = ExtensionType::try_calculate_account_len::<TokenAccountState>(&types).unwrap();
+    let mut data = vec![0; len];
+    let mut account = StateWithExtensionsMut::<TokenAccountState>::unpack_uninitialized(&mut data).unwrap();
+    for extension in extensions {
+        match extension {
+            AccountExtension::ImmutableOwner => {
+                account.init_extension::<ImmutableOwner>(true).unwrap();
+            }
+            AccountExtension::NonTransferable => {
+                account.init_extension::<NonTransferableAccount>(true).unwrap();
+            }
+        }
+    }
+    account.base = base;
+    account.pack_base();
+    account.init_account_type().unwrap();
+    data
+}
+
+fn mint_info(mint: Pubkey, issuer: Pubkey, freeze_authority: Pubkey) -> MintInfo {
+    MintInfo {
+        mint,
+        issuer,
+        freeze_authority,
+        permanent_delegate: issuer,
+        whitepaper_uri: String::new(),
+        is_active: true,
+        creation_time: 0,
+        reserve_merkle_root: [0; 32],
+        reserve_ipfs_cid: String::new(),
+        last_reserve_update: 0,
+        rule_set_version: RULE_SET_VERSION,
+        rule_set_hash: [0; 32],
+        paused: false,
+        pauser: Pubkey::default(),
+        approval_set: Pubkey::default(),
+        reserve_total_eur: 0,
+        coverage_auto_pause: false,
+        minting_halted: false,
+        transfer_fee_bps: 0,
+        max_transfer_fee: 0,
+        fee_treasury: Pubkey::default(),
+        confidential_transfers: false,
+        confidential_auditor: Pubkey::default(),
+        auditor_elgamal_pubkey: [0; 32],
+        max_supply: 0,
+        pending_max_supply: 0,
+        supply_cap_effective_at: 0,
+        issuance_policy: Pubkey::default(),
+        timelock: Pubkey::default(),
+        whitepaper_version: 0,
+        decimals: EUR_DECIMALS,
+        require_associated_accounts: false,
+        holder_count: 0,
+        migrated_to: Pubkey::default(),
+        wind_down_started_at: 0,
+        redemption_deadline: 0,
+        redemption_account: Pubkey::default(),
+        total_minted: 0,
+        total_burned: 0,
+        reserve_epoch_count: 0,
+        max_reserve_age_seconds: 0,
+        reserve_valuation: Pubkey::default(),
+        relayer_registry: Pubkey::default(),
+    }
+}
+
+fn kyc_user(user: Pubkey) -> KycUser {
+    KycUser {
+        authority: Pubkey::new_unique(),
+        user,
+        status: KycStatus::Verified,
+        verification_level: 2,
+        verification_time: 0,
+        expiry_time: i64::MAX,
+        country_code: "DE".to_string(),
+        blz: String::new(),
+        iban_hash: [0; 32],
+        verification_provider: String::new(),
+        account_class: AccountClass::Retail,
+        kyb_hash: [0; 32],
+        class_change_time: 0,
+        sanctions_screen_date: 0,
+        sanctions_screen_hash: [0; 32],
+        sanctions_screen_provider: Pubkey::default(),
+        edd_required: false,
+        provider: Pubkey::default(),
+    }
+}
+
+fn try_accounts<T, B>(accounts: Vec<AccountInfo<'static>>, ix_data: &[u8]) -> Result<T>
+where
+    T: Accounts<'static, B>,
+    B: Default,
+{
+    let mut accounts: &'static [AccountInfo<'static>] = Box::leak(accounts.into_boxed_slice());
+    T::try_accounts(&::mica_eur::ID, &mut accounts, ix_data, &mut B::default(), &mut BTreeSet::new())
+}
+
+fn error_code<T>(result: Result<T>) -> u32 {
+    match result.map(|_| ()).map_err(ProgramError::from) {
+        Err(ProgramError::Custom(code)) => code,
+        other => panic!("expected a custom error, got {other:?}"),
+    }
+}
+
+fn constraint_seeds() -> u32 {
+    ErrorCode::ConstraintSeeds.into()
+}
+
+// Approver, AML authority record, mint info and regulator registry of the AML
+// management contexts, with `regulator` recorded in the registry if given
+fn aml_accounts(signer_key: Pubkey, issuer: Pubkey, regulator: Option<Pubkey>) -> Vec<AccountInfo<'static>> {
+    let mint = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let aml_authority = AmlAuthority {
+        authority,
+        authority_id: "EU_REGULATOR_001".to_string(),
+        powers: aml_powers::MODIFY_BLACKLIST,
+        is_active: true,
+        creation_time: 0,
+        last_action_time: 0,
+        jurisdiction: *b"DE",
+        global: false,
+    };
+    vec![
+        signer(signer_key),
+        writable(state(pda(&[AML_AUTHORITY_SEED, authority.as_ref()]), &aml_authority)),
+        state(pda(&[MINT_INFO_SEED, mint.as_ref()]), &mint_info(mint, issuer, issuer)),
+        regulator.map_or_else(none, |regulator| {
+            let registry = RegulatorRegistry {
+                mint,
+                regulator,
+                set_by: issuer,
+                update_time: 0,
+            };
+            state(pda(&[REGULATOR_REGISTRY_SEED, mint.as_ref()]), &registry)
+        }),
+    ]
+}
+
+fn unauthorized_aml_admin() -> u32 {
+    MicaEurError::UnauthorizedAmlAdmin.into()
+}
+
+#[test]
+fn deactivate_aml_authority_requires_the_issuer_or_regulator() {
+    let issuer = Pubkey::new_unique();
+    let regulator = Pubkey::new_unique();
+    let accounts = aml_accounts(issuer, issuer, None);
+    assert!(try_accounts::<DeactivateAmlAuthority, _>(accounts, &[]).is_ok());
+    let accounts = aml_accounts(regulator, issuer, Some(regulator));
+    assert!(try_accounts::<DeactivateAmlAuthority, _>(accounts, &[]).is_ok());
+
+    let accounts = aml_accounts(Pubkey::new_unique(), issuer, None);
+    let result = try_accounts::<DeactivateAmlAuthority, _>(accounts, &[]);
+    assert_eq!(error_code(result), unauthorized_aml_admin());
+
+    let accounts = aml_accounts(Pubkey::new_unique(), issuer, Some(regulator));
+    let result = try_accounts::<DeactivateAmlAuthority, _>(accounts, &[]);
+    assert_eq!(error_code(result), unauthorized_aml_admin());
+}
+
+#[test]
+fn update_aml_authority_powers_requires_the_issuer_or_regulator() {
+    let issuer = Pubkey::new_unique();
+    let regulator = Pubkey::new_unique();
+    let accounts = aml_accounts(issuer, issuer, None);
+    assert!(try_accounts::<UpdateAmlAuthorityPowers, _>(accounts, &[]).is_ok());
+    let accounts = aml_accounts(regulator, issuer, Some(regulator));
+    assert!(try_accounts::<UpdateAmlAuthorityPowers, _>(accounts, &[]).is_ok());
+
+    let accounts = aml_accounts(Pubkey::new_unique(), issuer, None);
+    let result = try_accounts::<UpdateAmlAuthorityPowers, _>(accounts, &[]);
+    assert_eq!(error_code(result), unauthorized_aml_admin());
+
+    let accounts = aml_accounts(Pubkey::new_unique(), issuer, Some(regulator));
+    let result = try_accounts::<UpdateAmlAuthorityPowers, _>(accounts, &[]);
+    assert_eq!(error_code(result), unauthorized_aml_admin());
+}
+
+#[test]
+fn aml_admin_rejects_a_forged_regulator_registry() {
+    let issuer = Pubkey::new_unique();
+    let attacker = Pubkey::new_unique();
+    let mut accounts = aml_accounts(attacker, issuer, None);
+    let forged = RegulatorRegistry {
+        mint: Pubkey::new_unique(),
+        regulator: attacker,
+        set_by: attacker,
+        update_time: 0,
+    };
+    accounts[3] = state(Pubkey::new_unique(), &forged);
+    let result = try_accounts::<DeactivateAmlAuthority, _>(accounts, &[]);
+    assert_eq!(error_code(result), constraint_seeds());
+}
+
+#[test]
+fn freeze_account_requires_the_freezer_role() {
+    let mint = Pubkey::new_unique();
+    let issuer = Pubkey::new_unique();
+    let freeze_authority = Pubkey::new_unique();
+    let officer = Pubkey::new_unique();
+    let frozen_account = Pubkey::new_unique();
+    let assignment = |holder: Pubkey, roles: u8| {
+        state(
+            pda(&[ROLE_SEED, mint.as_ref(), holder.as_ref()]),
+            &RoleAssignment {
+                scope: mint,
+                holder,
+                roles,
+                granted_by: issuer,
+                last_update: 0,
+            },
+        )
+    };
+    let accounts = |signer_key, role_assignment| {
+        vec![
+            signer(signer_key),
+            state(pda(&[MINT_INFO_SEED, mint.as_ref()]), &mint_info(mint, issuer, freeze_authority)),
+            account(mint, spl_token_2022::ID, vec![]),
+            writable(account(frozen_account, spl_token_2022::ID, token_account(mint, Pubkey::new_unique(), &[]))),
+            program(spl_token_2022::ID),
+            zeroed::<FreezeRecord>(
+                pda(&[FREEZE_RECORD_SEED, frozen_account.as_ref()]),
+                FreezeRecord::INIT_SPACE,
+            ),
+            program(System::id()),
+            role_assignment,
+            none(),
+        ]
+    };
+
+    let valid = accounts(freeze_authority, none());
+    assert!(try_accounts::<FreezeAccount, _>(valid, &[]).is_ok());
+
+    let granted = accounts(officer, assignment(officer, roles::FREEZER));
+    assert!(try_accounts::<FreezeAccount, _>(granted, &[]).is_ok());
+
+    let other_role = accounts(officer, assignment(officer, roles::MINTER));
+    let result = try_accounts::<FreezeAccount, _>(other_role, &[]);
+    assert_eq!(error_code(result), u32::from(MicaEurError::MissingRole));
+
+    let forged = accounts(issuer, none());
+    let result = try_accounts::<FreezeAccount, _>(forged, &[]);
+    assert_eq!(error_code(result), u32::from(MicaEurError::MissingRole));
+}
+
+// Accounts of `mint_tokens` minting to `token_account_owner`'s account for `user`
+fn mint_tokens_accounts(user: Pubkey, token_account_owner: Pubkey) -> Vec<AccountInfo<'static>> {
+    let mint = Pubkey::new_unique();
+    let issuer = Pubkey::new_unique();
+    let freeze_authority = Pubkey::new_unique();
+    let operation_id = [7u8; 16];
+    let token_account_key = Pubkey::new_unique();
+    vec![
+        signer(issuer),
+        writable(state(pda(&[MINT_INFO_SEED, mint.as_ref()]), &mint_info(mint, issuer, freeze_authority))),
+        writable(account(mint, spl_token_2022::ID, vec![])),
+        writable(account(
+            token_account_key,
+            spl_token_2022::ID,
+            token_account(mint, token_account_owner, &[]),
+        )),
+        state(pda(&[KYC_USER_SEED, user.as_ref()]), &kyc_user(user)),
+        program(spl_token_2022::ID),
+        uncreated(
+            pda(&[
+                OPERATION_RECEIPT_SEED,
+                mint.as_ref(),
+                &[OperationKind::Mint as u8],
+                issuer.as_ref(),
+                &operation_id,
+            ]),
+            OperationReceipt::INIT_SPACE,
+        ),
+        program(System::id()),
+        missing(pda(&[BLACKLIST_SEED, user.as_ref()])),
+        none(),
+        zeroed::<UserLimits>(
+            pda(&[USER_LIMITS_SEED, user.as_ref()]),
+            UserLimits::INIT_SPACE,
+        ),
+        none(),
+        zeroed::<InvocationGuard>(
+            pda(&[INVOCATION_GUARD_SEED, mint.as_ref()]),
+            InvocationGuard::INIT_SPACE,
+        ),
+        none(),
+        none(),
+        none(),
+        state(
+            pda(&[COUNTRY_POLICY_SEED, b"DE"]),
+            &CountryPolicy {
+                country_code: *b"DE",
+                allowed: true,
+                max_transaction_amount: 0,
+                set_by: Pubkey::new_unique(),
+                last_update: 0,
+            },
+        ),
+        missing(pda(&[FREEZE_RECORD_SEED, token_account_key.as_ref()])),
+    ]
+}
+
+// `amount` and `operation_id` arguments shared by `mint_tokens` and `burn_tokens`
+fn operation_args() -> Vec<u8> {
+    let mut data = 1_000u64.to_le_bytes().to_vec();
+    data.extend_from_slice(&[7u8; 16]);
+    data
+}
+
+#[test]
+fn mint_tokens_rejects_a_token_account_of_another_wallet() {
+    let user = Pubkey::new_unique();
+    let accounts = mint_tokens_accounts(user, user);
+    assert!(try_accounts::<MintTokens, _>(accounts, &operation_args()).is_ok());
+
+    let accounts = mint_tokens_accounts(user, Pubkey::new_unique());
+    let result = try_accounts::<MintTokens, _>(accounts, &operation_args());
+    assert_eq!(error_code(result), u32::from(MicaEurError::TokenAccountOwnerMismatch));
+}
+
+#[test]
+fn mint_tokens_requires_the_recipient_blacklist_entry() {
+    let user = Pubkey::new_unique();
+    let mut accounts = mint_tokens_accounts(user, user);
+    accounts[8] = none();
+    let result = try_accounts::<MintTokens, _>(accounts, &operation_args());
+    assert_eq!(error_code(result), constraint_seeds());
+
+    let mut accounts = mint_tokens_accounts(user, user);
+    accounts[8] = missing(pda(&[BLACKLIST_SEED, Pubkey::new_unique().as_ref()]));
+    let result = try_accounts::<MintTokens, _>(accounts, &operation_args());
+    assert_eq!(error_code(result), constraint_seeds());
+}
+
+#[test]
+fn mint_tokens_requires_the_token_account_freeze_record() {
+    let user = Pubkey::new_unique();
+    let mut accounts = mint_tokens_accounts(user, user);
+    *accounts.last_mut().unwrap() = none();
+    let result = try_accounts::<MintTokens, _>(accounts, &operation_args());
+    assert_eq!(error_code(result), constraint_seeds());
+}
+
+// Accounts of `burn_tokens` signed by `owner`, burning from `token_account_owner`'s
+// account
+fn burn_tokens_accounts(owner: Pubkey, token_account_owner: Pubkey) -> Vec<AccountInfo<'static>> {
+    let mint = Pubkey::new_unique();
+    let issuer = Pubkey::new_unique();
+    let operation_id = [7u8; 16];
+    let token_account_key = Pubkey::new_unique();
+    vec![
+        writable(signer(owner)),
+        writable(state(pda(&[MINT_INFO_SEED, mint.as_ref()]), &mint_info(mint, issuer, issuer))),
+        writable(account(mint, spl_token_2022::ID, vec![])),
+        writable(account(
+            token_account_key,
+            spl_token_2022::ID,
+            token_account(mint, token_account_owner, &[]),
+        )),
+        program(spl_token_2022::ID),
+        uncreated(
+            pda(&[
+                OPERATION_RECEIPT_SEED,
+                mint.as_ref(),
+                &[OperationKind::Redemption as u8],
+                owner.as_ref(),
+                &operation_id,
+            ]),
+            OperationReceipt::INIT_SPACE,
+        ),
+        program(System::id()),
+        missing(pda(&[BLACKLIST_SEED, owner.as_ref()])),
+        state(pda(&[KYC_USER_SEED, owner.as_ref()]), &kyc_user(owner)),
+        zeroed::<UserLimits>(
+            pda(&[USER_LIMITS_SEED, owner.as_ref()]),
+            UserLimits::INIT_SPACE,
+        ),
+        none(),
+        missing(pda(&[FREEZE_RECORD_SEED, token_account_key.as_ref()])),
+        state(
+            pda(&[COUNTRY_POLICY_SEED, b"DE"]),
+            &CountryPolicy {
+                country_code: *b"DE",
+                allowed: true,
+                max_transaction_amount: 0,
+                set_by: Pubkey::new_unique(),
+                last_update: 0,
+            },
+        ),
+    ]
+}
+
+#[test]
+fn burn_tokens_rejects_a_token_account_of_another_wallet() {
+    let owner = Pubkey::new_unique();
+    let accounts = burn_tokens_accounts(owner, owner);
+    assert!(try_accounts::<BurnTokens, _>(accounts, &operation_args()).is_ok());
+
+    // A delegate signing for a blacklisted holder would be checked under its own
+    // blacklist entry and limits
+    let accounts = burn_tokens_accounts(owner, Pubkey::new_unique());
+    let result = try_accounts::<BurnTokens, _>(accounts, &operation_args());
+    assert_eq!(error_code(result), u32::from(ErrorCode::ConstraintTokenOwner));
+}
+
+#[test]
+fn mints_go_to_the_associated_token_account_once_required() {
+    let user = Pubkey::new_unique();
+    let mut info = mint_info(Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let associated = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &user,
+        &info.mint,
+        &anchor_spl::token_2022::ID,
+    );
+    let other = Pubkey::new_unique();
+    assert!(check_mint_destination(&info, &user, &other).is_ok());
+
+    info.require_associated_accounts = true;
+    assert!(check_mint_destination(&info, &user, &associated).is_ok());
+    let result = check_mint_destination(&info, &user, &other);
+    assert_eq!(error_code(result), u32::from(MicaEurError::AssociatedTokenAccountRequired));
+}
+
+#[test]
+fn unsupported_token_account_extensions_are_rejected() {
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    let supported = account(
+        Pubkey::new_unique(),
+        spl_token_2022::ID,
+        token_account(mint, owner, &[AccountExtension::ImmutableOwner]),
+    );
+    assert!(check_token_account_extensions(&supported).is_ok());
+
+    let unsupported = account(
+        Pubkey::new_unique(),
+        spl_token_2022::ID,
+        token_account(mint, owner, &[AccountExtension::ImmutableOwner, AccountExtension::NonTransferable]),
+    );
+    let result = check_token_account_extensions(&unsupported);
+    assert_eq!(error_code(result), u32::from(MicaEurError::UnsupportedTokenExtension));
+}
+
+#[test]
+fn accounts_have_room_for_their_longest_strings() {
+    let key = Pubkey::new_unique();
+    let mint_info = MintInfo {
+        whitepaper_uri: "u".repeat(MAX_WHITEPAPER_URI_LEN),
+        reserve_ipfs_cid: "b".repeat(MAX_IPFS_CID_LEN),
+        ..mint_info(key, key, key)
+    };
+    assert!(mint_info.try_to_vec().unwrap().len() <= MintInfo::INIT_SPACE);
+
+    let kyc_user = KycUser {
+        country_code: "D".repeat(MAX_COUNTRY_CODE_LEN),
+        blz: "5".repeat(MAX_BLZ_LEN),
+        verification_provider: "p".repeat(MAX_PROVIDER_NAME_LEN),
+        ..kyc_user(key)
+    };
+    assert!(kyc_user.try_to_vec().unwrap().len() <= KycUser::INIT_SPACE);
+
+    assert_eq!(
+        error_code(check_string_len(&"p".repeat(MAX_PROVIDER_NAME_LEN + 1), MAX_PROVIDER_NAME_LEN)),
+        u32::from(MicaEurError::StringTooLong)
+    );
+}
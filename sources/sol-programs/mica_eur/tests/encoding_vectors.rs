@@ -0,0 +1,127 @@
+//! Golden vectors of the hashed-artifact encoding. `tests/vectors/encoding-v1.json`
+//! is the shared reference: other implementations load the same file and must
+//! produce the same bytes and hashes. Integers are given as decimal strings so
+//! JavaScript can read them without losing precision.
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash;
+use serde_json::Value;
+use ::mica_eur::*;
+
+const VECTORS: &str = include_str!("vectors/encoding-v1.json");
+
+fn vectors(artifact: &str) -> Vec<Value> {
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+    assert_eq!(vectors["version"], 1);
+    let cases = vectors[artifact].as_array().unwrap().clone();
+    assert!(!cases.is_empty(), "no vectors for {artifact}");
+    cases
+}
+
+fn text(case: &Value, field: &str) -> String {
+    case[field].as_str().unwrap_or_else(|| panic!("missing {field}")).to_string()
+}
+
+fn number<T: FromStr>(case: &Value, field: &str) -> T {
+    text(case, field).parse().ok().unwrap_or_else(|| panic!("bad {field}"))
+}
+
+fn pubkey(case: &Value, field: &str) -> Pubkey {
+    Pubkey::from_str(&text(case, field)).unwrap()
+}
+
+fn bytes(case: &Value, field: &str) -> Vec<u8> {
+    let hex = text(case, field);
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn bytes32(case: &Value, field: &str) -> [u8; 32] {
+    bytes(case, field).try_into().unwrap()
+}
+
+fn check(case: &Value, encoding: Vec<u8>) {
+    assert_eq!(encoding, bytes(case, "encoding"), "encoding of {case}");
+    assert_eq!(hash::hash(&encoding).to_bytes(), bytes32(case, "hash"), "hash of {case}");
+}
+
+// Merkle leaves are the encoding hashed behind the leaf prefix
+fn check_leaf(case: &Value, encoding: Vec<u8>, leaf: [u8; 32]) {
+    assert_eq!(leaf, hash_leaf(&encoding), "leaf of {case}");
+    assert_eq!(leaf, bytes32(case, "leaf"), "leaf of {case}");
+    check(case, encoding);
+}
+
+#[test]
+fn reserve_leaves_match_vectors() {
+    for case in vectors("reserve_leaf") {
+        let deposit_id = text(&case, "deposit_id");
+        let amount = number(&case, "amount");
+        let timestamp = number(&case, "timestamp");
+        check_leaf(
+            &case,
+            encode_reserve_leaf(&deposit_id, amount, timestamp),
+            create_reserve_leaf(&deposit_id, amount, timestamp),
+        );
+    }
+}
+
+#[test]
+fn snapshot_leaves_match_vectors() {
+    for case in vectors("snapshot_leaf") {
+        let owner = pubkey(&case, "owner");
+        let balance = number(&case, "balance");
+        check_leaf(
+            &case,
+            encode_snapshot_leaf(&owner, balance),
+            create_snapshot_leaf(&owner, balance),
+        );
+    }
+}
+
+#[test]
+fn screening_attestations_match_vectors() {
+    for case in vectors("screening_attestation") {
+        let program_id = pubkey(&case, "program_id");
+        let user = pubkey(&case, "user");
+        let screened_at = number(&case, "screened_at");
+        let result_hash = bytes32(&case, "result_hash");
+        let encoding = encode_screening_attestation(&program_id, &user, screened_at, &result_hash);
+        if program_id == ::mica_eur::ID {
+            assert_eq!(screening_attestation_message(&user, screened_at, &result_hash), encoding);
+        }
+        check(&case, encoding);
+    }
+}
+
+
+#[test]
+fn sepa_references_match_vectors() {
+    for case in vectors("sepa_reference") {
+        let end_to_end_id = text(&case, "end_to_end_id");
+        assert_eq!(sepa_reference_hash(&end_to_end_id), bytes32(&case, "hash"));
+        check(&case, encode_sepa_reference(&end_to_end_id));
+    }
+}
+
+#[test]
+fn transfer_authorizations_match_vectors() {
+    for case in vectors("transfer_authorization") {
+        let program_id = pubkey(&case, "program_id");
+        let mint = pubkey(&case, "mint");
+        let owner = pubkey(&case, "owner");
+        let recipient = pubkey(&case, "recipient");
+        let amount = number(&case, "amount");
+        let nonce = bytes32(&case, "nonce");
+        let expiry = number(&case, "expiry");
+        let encoding = encode_transfer_authorization(&program_id, &mint, &owner, &recipient, amount, &nonce, expiry);
+        if program_id == ::mica_eur::ID {
+            assert_eq!(transfer_authorization_message(&mint, &owner, &recipient, amount, &nonce, expiry), encoding);
+        }
+        check(&case, encoding);
+    }
+}
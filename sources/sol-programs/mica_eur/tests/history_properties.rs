@@ -0,0 +1,40 @@
+//! Property tests of the compliance history ring buffer: it keeps the latest
+//! entries in the order they were recorded and counts the overwritten ones.
+
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use ::mica_eur::*;
+
+fn empty_history() -> ComplianceHistory {
+    ComplianceHistory {
+        user: Pubkey::new_unique(),
+        entries: [HistoryEntry::default(); COMPLIANCE_HISTORY_SIZE],
+        next: 0,
+        total_entries: 0,
+    }
+}
+
+proptest! {
+    #[test]
+    fn the_history_keeps_the_latest_entries_in_order(amounts in prop::collection::vec(any::<u64>(), 0..4 * COMPLIANCE_HISTORY_SIZE)) {
+        let mut history = empty_history();
+        let actor = Pubkey::new_unique();
+        for (index, amount) in amounts.iter().enumerate() {
+            history.record(ComplianceAction::Seized { amount: *amount }, actor, [0; 32], index as i64);
+        }
+
+        let kept = amounts.len().saturating_sub(COMPLIANCE_HISTORY_SIZE);
+        let expected: Vec<_> = amounts[kept..].iter().map(|amount| ComplianceAction::Seized { amount: *amount }).collect();
+        let recorded: Vec<_> = history.chronological().map(|entry| entry.action).collect();
+        prop_assert_eq!(recorded, expected);
+        prop_assert!(history.chronological().map(|entry| entry.timestamp).eq(kept as i64..amounts.len() as i64));
+        prop_assert_eq!(history.total_entries, amounts.len() as u64);
+    }
+}
+
+#[test]
+fn references_are_hashed_and_empty_ones_left_blank() {
+    assert_eq!(reference_hash(""), [0; 32]);
+    assert_ne!(reference_hash("AG Frankfurt 4 Gs 17/26"), [0; 32]);
+    assert_ne!(reference_hash("AG Frankfurt 4 Gs 17/26"), reference_hash("AG Frankfurt 4 Gs 17/27"));
+}
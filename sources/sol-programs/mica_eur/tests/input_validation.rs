@@ -0,0 +1,93 @@
+//! Tests of the validation applied to inputs before they are stored: bank codes,
+//! verification provider names, reserve proof CIDs and KYC status transitions.
+
+use anchor_lang::error::Error;
+use proptest::prelude::*;
+use ::mica_eur::*;
+
+fn error_code(result: anchor_lang::Result<()>) -> Option<u32> {
+    match result {
+        Ok(()) => None,
+        Err(Error::AnchorError(error)) => Some(error.error_code_number),
+        Err(error) => panic!("unexpected error {:?}", error),
+    }
+}
+
+proptest! {
+    #[test]
+    fn a_blz_is_eight_digits(blz in "[0-9]{8}", other in "\\PC{0,12}") {
+        prop_assert_eq!(error_code(check_blz(&blz)), None);
+        let valid = other.len() == 8 && other.bytes().all(|b| b.is_ascii_digit());
+        prop_assert_eq!(error_code(check_blz(&other)).is_none(), valid);
+    }
+
+    #[test]
+    fn provider_names_use_a_plain_charset(name in "[A-Za-z0-9 ._-]{1,32}", control in "[\\x00-\\x1f]") {
+        prop_assert_eq!(error_code(check_verification_provider(&name)), None);
+        prop_assert_eq!(
+            error_code(check_verification_provider(&format!("{}{}", &name[..name.len().min(31)], control))),
+            Some(u32::from(MicaEurError::InvalidVerificationProvider))
+        );
+    }
+}
+
+#[test]
+fn provider_names_are_bounded() {
+    assert_eq!(
+        error_code(check_verification_provider("")),
+        Some(u32::from(MicaEurError::InvalidVerificationProvider))
+    );
+    assert_eq!(
+        error_code(check_verification_provider(&"p".repeat(MAX_PROVIDER_NAME_LEN + 1))),
+        Some(u32::from(MicaEurError::InvalidVerificationProvider))
+    );
+}
+
+#[test]
+fn reserve_cids_are_cidv0_or_base32_cidv1() {
+    for cid in [
+        "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    ] {
+        assert_eq!(error_code(check_ipfs_cid(cid)), None, "{}", cid);
+    }
+    for cid in [
+        "",
+        "b",
+        "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd",
+        "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0",
+        "bafyBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI",
+        "https://ipfs.io/ipfs/bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    ] {
+        assert_eq!(error_code(check_ipfs_cid(cid)), Some(u32::from(MicaEurError::InvalidIpfsCid)), "{}", cid);
+    }
+    assert_eq!(
+        error_code(check_ipfs_cid(&format!("b{}", "a".repeat(MAX_IPFS_CID_LEN)))),
+        Some(u32::from(MicaEurError::InvalidIpfsCid))
+    );
+}
+
+#[test]
+fn kyc_statuses_follow_the_transition_table() {
+    use KycStatus::*;
+    let statuses = [Unverified, Pending, Verified, Rejected, Expired, Suspended];
+    let allowed = [
+        (Unverified, Pending),
+        (Unverified, Verified),
+        (Unverified, Rejected),
+        (Pending, Verified),
+        (Pending, Rejected),
+        (Verified, Verified),
+        (Verified, Expired),
+        (Verified, Suspended),
+        (Expired, Pending),
+        (Expired, Verified),
+        (Suspended, Verified),
+        (Suspended, Rejected),
+    ];
+    for from in statuses {
+        for to in statuses {
+            assert_eq!(from.can_transition_to(to), allowed.contains(&(from, to)), "{:?} -> {:?}", from, to);
+        }
+    }
+}
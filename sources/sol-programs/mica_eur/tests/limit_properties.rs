@@ -0,0 +1,150 @@
+//! Property tests of the rolling limit windows: recording never overflows or
+//! panics, the counted volume stays within the limit, and a window resets once a
+//! full day has passed since it opened. Configured tiers must be applicable as
+//! given.
+
+use anchor_lang::error::Error;
+use proptest::prelude::*;
+use ::mica_eur::*;
+
+const LIMIT_ERROR: MicaEurError = MicaEurError::DailyMintLimitExceeded;
+
+// Error code of a failed `record`, `None` if it succeeded
+fn error_code(result: anchor_lang::Result<()>) -> Option<u32> {
+    match result {
+        Ok(()) => None,
+        Err(Error::AnchorError(error)) => Some(error.error_code_number),
+        Err(error) => panic!("unexpected error {:?}", error),
+    }
+}
+
+// Operations as (amount, seconds since the previous one)
+fn operations() -> impl Strategy<Value = Vec<(u64, i64)>> {
+    prop::collection::vec((any::<u64>(), 0..2 * DAILY_LIMIT_WINDOW), 1..64)
+}
+
+proptest! {
+    #[test]
+    fn the_counted_volume_never_exceeds_the_limit(limit in any::<u64>(), start in 0..i64::MAX / 2, operations in operations()) {
+        let mut volume = RollingVolume::default();
+        let mut now = start;
+        for (amount, elapsed) in operations {
+            now += elapsed;
+            let before = volume.current(now);
+            match error_code(volume.record(amount, limit, now, LIMIT_ERROR)) {
+                None => prop_assert_eq!(volume.current(now), before + amount as u128),
+                Some(code) => {
+                    prop_assert_eq!(code, u32::from(LIMIT_ERROR));
+                    prop_assert_eq!(volume.current(now), before);
+                }
+            }
+            prop_assert!(volume.current(now) <= limit as u128);
+        }
+    }
+
+    #[test]
+    fn amounts_past_u64_max_hit_the_limit_instead_of_overflowing(first in 1..u64::MAX, now in 0..i64::MAX / 2) {
+        let mut volume = RollingVolume::default();
+        volume.record(first, u64::MAX, now, LIMIT_ERROR).unwrap();
+        let rest = u64::MAX - first + 1;
+        prop_assert_eq!(error_code(volume.record(rest, u64::MAX, now, LIMIT_ERROR)), Some(u32::from(LIMIT_ERROR)));
+        prop_assert_eq!(volume.current(now), first as u128);
+    }
+
+    #[test]
+    fn the_window_resets_a_day_after_it_opened(
+        limit in 1..u64::MAX,
+        opened in 0..i64::MAX / 2,
+        within in 0..DAILY_LIMIT_WINDOW,
+        after in 0..DAILY_LIMIT_WINDOW,
+    ) {
+        let mut volume = RollingVolume::default();
+        volume.record(limit, limit, opened, LIMIT_ERROR).unwrap();
+
+        // Full until the day is over
+        prop_assert_eq!(volume.current(opened + within), limit as u128);
+        prop_assert_eq!(
+            error_code(volume.record(1, limit, opened + within, LIMIT_ERROR)),
+            Some(u32::from(LIMIT_ERROR))
+        );
+
+        // Then empty, and the next operation opens a new window
+        let next_day = opened + DAILY_LIMIT_WINDOW + after;
+        prop_assert_eq!(volume.current(next_day), 0);
+        volume.record(limit, limit, next_day, LIMIT_ERROR).unwrap();
+        prop_assert_eq!(volume.window_start, next_day);
+        prop_assert_eq!(volume.volume, limit as u128);
+    }
+
+    #[test]
+    fn windows_at_the_end_of_time_do_not_overflow(opened in i64::MAX - DAILY_LIMIT_WINDOW..=i64::MAX, amount in any::<u64>()) {
+        let mut volume = RollingVolume { window_start: opened, volume: 0 };
+        let _ = volume.record(amount, u64::MAX, opened, LIMIT_ERROR);
+        prop_assert!(volume.current(i64::MAX) <= amount as u128);
+    }
+
+    #[test]
+    fn tier_limits_fall_back_to_the_highest_tier(level in any::<u8>()) {
+        let limits = tier_limits(None, level, 0);
+        prop_assert_eq!(limits, DEFAULT_TIER_LIMITS[(level as usize).min(DEFAULT_TIER_LIMITS.len() - 1)]);
+    }
+
+    #[test]
+    fn default_tier_limits_scale_with_the_mint_decimals(level in 0u8..4, decimals in prop::sample::select(SUPPORTED_EUR_DECIMALS.to_vec())) {
+        let limits = tier_limits(None, level, decimals);
+        let unit = 10u64.pow(decimals as u32);
+        let tier = DEFAULT_TIER_LIMITS[level as usize];
+        prop_assert_eq!(limits.per_transaction, tier.per_transaction * unit);
+        prop_assert_eq!(limits.daily_mint, tier.daily_mint * unit);
+        prop_assert_eq!(limits.daily_burn, tier.daily_burn * unit);
+        prop_assert_eq!(limits.daily_transfer, tier.daily_transfer * unit);
+    }
+
+    #[test]
+    fn transactions_above_the_tier_cap_are_rejected(per_transaction in any::<u64>(), amount in any::<u64>()) {
+        let limits = TierLimits { per_transaction, ..DEFAULT_TIER_LIMITS[0] };
+        let expected = (amount > per_transaction).then_some(u32::from(MicaEurError::TransactionAmountExceedsMaximum));
+        prop_assert_eq!(error_code(limits.check_transaction(amount)), expected);
+    }
+
+    #[test]
+    fn a_single_operation_cap_above_a_daily_cap_is_rejected(
+        daily in 1..u64::MAX / 2,
+        excess in 1..u64::MAX / 2,
+        cap in 0usize..3,
+    ) {
+        let mut tier = TierLimits {
+            per_transaction: daily + excess,
+            daily_mint: u64::MAX,
+            daily_burn: u64::MAX,
+            daily_transfer: u64::MAX,
+        };
+        match cap {
+            0 => tier.daily_mint = daily,
+            1 => tier.daily_burn = daily,
+            _ => tier.daily_transfer = daily,
+        }
+        prop_assert_eq!(error_code(check_tier_limits(&[tier; 4])), Some(u32::from(MicaEurError::InvalidTierLimits)));
+    }
+
+    #[test]
+    fn a_tier_below_the_previous_one_is_rejected(level in 1usize..4, field in 0usize..4, raise in 1..900u64) {
+        let mut tiers = [TierLimits { per_transaction: 100, daily_mint: 1_000, daily_burn: 1_000, daily_transfer: 1_000 }; 4];
+        prop_assert_eq!(error_code(check_tier_limits(&tiers)), None);
+
+        let lower = &mut tiers[level - 1];
+        match field {
+            0 => lower.per_transaction += raise,
+            1 => lower.daily_mint += raise,
+            2 => lower.daily_burn += raise,
+            _ => lower.daily_transfer += raise,
+        }
+        prop_assert_eq!(error_code(check_tier_limits(&tiers)), Some(u32::from(MicaEurError::InvalidTierLimits)));
+    }
+
+    #[test]
+    fn the_default_tiers_are_valid_limits(decimals in prop::sample::select(SUPPORTED_EUR_DECIMALS.to_vec())) {
+        let tiers = [0, 1, 2, 3].map(|level| tier_limits(None, level, decimals));
+        prop_assert_eq!(error_code(check_tier_limits(&tiers)), None);
+    }
+}
@@ -0,0 +1,91 @@
+//! Property tests of the merkle module: every leaf of any tree proves against its
+//! root, and nothing else does, including proofs that were tampered with.
+
+use proptest::prelude::*;
+use ::mica_eur::*;
+
+fn leaves() -> impl Strategy<Value = Vec<[u8; 32]>> {
+    prop::collection::vec(any::<[u8; 32]>(), 1..64)
+}
+
+proptest! {
+    #[test]
+    fn every_leaf_proves_against_the_root(leaves in leaves()) {
+        let levels = merkle_levels(&leaves);
+        let root = calculate_merkle_root(&leaves);
+        for (index, leaf) in leaves.iter().enumerate() {
+            prop_assert!(verify_merkle_proof(&merkle_proof(&levels, index), &root, leaf));
+        }
+    }
+
+    #[test]
+    fn odd_sized_trees_prove_every_leaf(pairs in prop::collection::vec(any::<([u8; 32], [u8; 32])>(), 0..32), last in any::<[u8; 32]>()) {
+        // The unpaired last leaf moves up unchanged and is proven by fewer siblings
+        let mut leaves: Vec<[u8; 32]> = pairs.iter().flat_map(|(a, b)| [*a, *b]).collect();
+        leaves.push(last);
+        let levels = merkle_levels(&leaves);
+        let root = calculate_merkle_root(&leaves);
+        for (index, leaf) in leaves.iter().enumerate() {
+            prop_assert!(verify_merkle_proof(&merkle_proof(&levels, index), &root, leaf));
+        }
+    }
+
+    #[test]
+    fn a_tampered_proof_does_not_verify(
+        leaves in prop::collection::vec(any::<[u8; 32]>(), 2..64),
+        index in any::<prop::sample::Index>(),
+        step in any::<prop::sample::Index>(),
+        bit in 0usize..256,
+    ) {
+        let levels = merkle_levels(&leaves);
+        let root = calculate_merkle_root(&leaves);
+        let index = index.index(leaves.len());
+        let mut proof = merkle_proof(&levels, index);
+        let step = step.index(proof.len());
+        proof[step][bit / 8] ^= 1 << (bit % 8);
+        prop_assert!(!verify_merkle_proof(&proof, &root, &leaves[index]));
+    }
+
+    #[test]
+    fn a_truncated_proof_does_not_verify(leaves in prop::collection::vec(any::<[u8; 32]>(), 2..64), index in any::<prop::sample::Index>()) {
+        let levels = merkle_levels(&leaves);
+        let root = calculate_merkle_root(&leaves);
+        let index = index.index(leaves.len());
+        let proof = merkle_proof(&levels, index);
+        prop_assert!(!verify_merkle_proof(&proof[..proof.len() - 1], &root, &leaves[index]));
+    }
+
+    #[test]
+    fn a_foreign_leaf_does_not_prove(leaves in leaves(), foreign in any::<[u8; 32]>(), index in any::<prop::sample::Index>()) {
+        prop_assume!(!leaves.contains(&foreign));
+        let levels = merkle_levels(&leaves);
+        let root = calculate_merkle_root(&leaves);
+        let proof = merkle_proof(&levels, index.index(leaves.len()));
+        prop_assert!(!verify_merkle_proof(&proof, &root, &foreign));
+    }
+
+    #[test]
+    fn node_hashing_is_commutative(a in any::<[u8; 32]>(), b in any::<[u8; 32]>()) {
+        prop_assert_eq!(hash_node(&a, &b), hash_node(&b, &a));
+    }
+
+    #[test]
+    fn leaves_and_nodes_are_domain_separated(a in any::<[u8; 32]>(), b in any::<[u8; 32]>()) {
+        // The 64-byte concatenation of two children, hashed as a leaf, is not their parent
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        prop_assert_ne!(hash_leaf(&[low, high].concat()), hash_node(&a, &b));
+    }
+
+    #[test]
+    fn an_internal_node_does_not_prove_as_a_leaf(leaves in prop::collection::vec(any::<[u8; 32]>(), 2..64)) {
+        // Second preimage: pass off the children of the first parent as one leaf's
+        // encoding, with the rest of the proof above that parent
+        let levels = merkle_levels(&leaves);
+        let root = calculate_merkle_root(&leaves);
+        let proof = merkle_proof(&levels, 0);
+        let (low, high) = if leaves[0] <= leaves[1] { (leaves[0], leaves[1]) } else { (leaves[1], leaves[0]) };
+        let forged = hash_leaf(&[low, high].concat());
+        prop_assert!(verify_merkle_proof(&proof[1..], &root, &levels[1][0]));
+        prop_assert!(!verify_merkle_proof(&proof[1..], &root, &forged));
+    }
+}
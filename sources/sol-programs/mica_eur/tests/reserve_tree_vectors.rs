@@ -0,0 +1,90 @@
+//! Golden vector of a reserve tree. `tests/vectors/reserve-tree-v1.json` holds five
+//! deposits with their leaves, proofs and root, computed outside this crate; the
+//! off-chain tree builder (`reserve_merkle`) is tested against the same file, so
+//! the root it posts is the one `verify_reserve_inclusion` checks proofs against.
+
+use ::mica_eur::*;
+use serde_json::Value;
+
+const VECTORS: &str = include_str!("vectors/reserve-tree-v1.json");
+
+fn tree() -> Value {
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+    assert_eq!(vectors["version"], 1);
+    vectors["reserve_tree"].clone()
+}
+
+fn text(value: &Value) -> &str {
+    value.as_str().unwrap()
+}
+
+fn bytes32(value: &Value) -> [u8; 32] {
+    let hex = text(value);
+    let bytes: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    bytes.try_into().unwrap()
+}
+
+fn proof(entry: &Value) -> Vec<[u8; 32]> {
+    entry["proof"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(bytes32)
+        .collect()
+}
+
+fn leaves(tree: &Value) -> Vec<[u8; 32]> {
+    tree["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            create_reserve_leaf(
+                text(&entry["deposit_id"]),
+                text(&entry["amount"]).parse().unwrap(),
+                text(&entry["timestamp"]).parse().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn reserve_tree_matches_vector() {
+    let tree = tree();
+    let leaves = leaves(&tree);
+    let levels = merkle_levels(&leaves);
+    let root = bytes32(&tree["merkle_root"]);
+
+    assert_eq!(calculate_merkle_root(&leaves), root);
+    for (index, entry) in tree["entries"].as_array().unwrap().iter().enumerate() {
+        assert_eq!(leaves[index], bytes32(&entry["leaf"]), "leaf of {entry}");
+        assert_eq!(
+            merkle_proof(&levels, index),
+            proof(entry),
+            "proof of {entry}"
+        );
+        assert!(verify_merkle_proof(&proof(entry), &root, &leaves[index]));
+    }
+}
+
+#[test]
+fn vector_proofs_do_not_prove_other_leaves() {
+    let tree = tree();
+    let leaves = leaves(&tree);
+    let root = bytes32(&tree["merkle_root"]);
+    let entries = tree["entries"].as_array().unwrap();
+
+    for (index, entry) in entries.iter().enumerate() {
+        for (other, leaf) in leaves.iter().enumerate() {
+            assert_eq!(
+                verify_merkle_proof(&proof(entry), &root, leaf),
+                index == other
+            );
+        }
+    }
+    // The unpaired last deposit is proven by a single sibling
+    assert_eq!(proof(entries.last().unwrap()).len(), 1);
+}
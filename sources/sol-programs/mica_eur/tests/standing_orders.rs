@@ -0,0 +1,87 @@
+//! Schedule of a standing order through its lifecycle: when a keeper may execute
+//! it, how failed attempts are retried, and when it completes.
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::*;
+use ::mica_eur::*;
+
+const START: i64 = 1_700_000_000;
+const DAY: i64 = 86_400;
+
+fn order(end_time: i64) -> StandingOrder {
+    StandingOrder {
+        payer: Pubkey::new_unique(),
+        payee: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        payer_token_account: Pubkey::new_unique(),
+        payee_token_account: Pubkey::new_unique(),
+        order_id: 1,
+        amount: 100,
+        interval_seconds: DAY,
+        next_execution_time: START,
+        end_time,
+        status: StandingOrderStatus::Active,
+        execution_count: 0,
+        failed_attempts: 0,
+        last_attempt_time: 0,
+        creation_time: START - DAY,
+        bump: 255,
+    }
+}
+
+#[test]
+fn an_order_is_due_from_its_next_execution_time() {
+    let order = order(START + 10 * DAY);
+    assert!(!order.is_due(START - 1));
+    assert!(order.is_due(START));
+    assert!(order.is_due(START + DAY));
+}
+
+#[test]
+fn a_failed_attempt_is_retried_after_the_retry_delay() {
+    let mut order = order(START + 10 * DAY);
+    order.failed_attempts = 1;
+    order.last_attempt_time = START;
+
+    assert!(!order.is_due(START + STANDING_ORDER_RETRY_DELAY - 1));
+    assert!(order.is_due(START + STANDING_ORDER_RETRY_DELAY));
+}
+
+#[test]
+fn advancing_moves_to_the_next_interval_and_clears_failed_attempts() {
+    let mut order = order(START + 10 * DAY);
+    order.failed_attempts = STANDING_ORDER_MAX_RETRIES;
+
+    order.advance_schedule().unwrap();
+    assert_eq!(order.next_execution_time, START + DAY);
+    assert_eq!(order.failed_attempts, 0);
+    assert_eq!(order.status, StandingOrderStatus::Active);
+    assert!(!order.is_due(START + DAY - 1));
+    assert!(order.is_due(START + DAY));
+}
+
+#[test]
+fn an_order_completes_once_the_next_execution_falls_after_its_end() {
+    let mut order = order(START + DAY);
+
+    // The execution on the end date itself still happens
+    order.advance_schedule().unwrap();
+    assert_eq!(order.status, StandingOrderStatus::Active);
+
+    order.advance_schedule().unwrap();
+    assert_eq!(order.status, StandingOrderStatus::Completed);
+}
+
+#[test]
+fn advancing_past_the_end_of_time_fails_instead_of_wrapping() {
+    let mut order = order(i64::MAX);
+    order.next_execution_time = i64::MAX - 1;
+
+    match order.advance_schedule() {
+        Err(Error::AnchorError(error)) => {
+            assert_eq!(error.error_code_number, u32::from(MicaEurError::ArithmeticOverflow))
+        }
+        other => panic!("expected an overflow error, got {:?}", other),
+    }
+    assert_eq!(order.next_execution_time, i64::MAX - 1);
+}
@@ -0,0 +1,950 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Account as TokenAccountState,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use mica_eur::{
+    check_class_transaction, check_edd_clearance, check_incoming_freeze, check_outgoing_freeze,
+    check_transfer_party, is_allowlisted_escrow, is_redemption_account, tier_limits, AmlAlert,
+    AmlAlertConfig, ApprovedCounterparty, BlacklistEntry, EddConfig, FastLaneInstitution,
+    FreezeRecord, InstitutionalTransferReported, KycUser, LimitConfig, MicaEurError, MintInfo,
+    ProgramAllowlist, ProgramEscrow, RollingVolume, StagedPolicy, TravelRuleConfig, VelocityWindow,
+    AML_ALERT_CONFIG_SEED, APPROVED_COUNTERPARTY_SEED, BLACKLIST_SEED, CONFIDENTIAL_TRANSFER_AMOUNT,
+    EDD_CONFIG_SEED, ESCROW_VAULT_SEED, FAST_LANE_INSTITUTION_SEED, FREEZE_RECORD_SEED,
+    KYC_USER_SEED, LIMIT_CONFIG_SEED, MINT_INFO_SEED, PROGRAM_ALLOWLIST_SEED, PROGRAM_ESCROW_SEED,
+    RULE_SET_VERSION, SEIZURE_AUTHORITY_SEED, STAGED_POLICY_SEED, TRAVEL_RULE_CONFIG_SEED,
+    TREASURY_VAULT_SEED,
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("Gc7Yf6JL6QJkawfRe6G8TG6X9JE6L4FUqvmosCeKd5m7");
+
+// Fixed by the SPL transfer hook interface
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+pub const TRANSFER_RECORD_SEED: &[u8] = b"transfer-record";
+pub const TRAVEL_RULE_RECORD_SEED: &[u8] = b"travel-rule-record";
+pub const VELOCITY_RECORD_SEED: &[u8] = b"velocity-record";
+
+// Offset of the owner field in a token account
+const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
+
+// Account indexes in the transfer hook `Execute` instruction
+const SOURCE_INDEX: u8 = 0;
+const MINT_INDEX: u8 = 1;
+const DESTINATION_INDEX: u8 = 2;
+const MICA_EUR_PROGRAM_INDEX: u8 = 5;
+
+/// The hook runs inside every Token-2022 transfer, so it must be a program of its
+/// own: the main program moves hooked tokens itself (seizures, standing orders) and
+/// Solana rejects a CPI chain that re-enters a program.
+#[program]
+pub mod mica_eur_transfer_hook {
+    use super::*;
+
+    /// Create the extra account meta list the transfer hook needs (issuer only)
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        let metas = extra_account_metas()?;
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)?;
+
+        msg!("Transfer hook extra account metas initialized for mint {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
+    /// Rewrite the extra account meta list of a mint with the accounts this version
+    /// of the hook needs, resizing it (issuer only). Mints created by an earlier
+    /// version stop transferring until their list is updated.
+    pub fn update_extra_account_meta_list(
+        ctx: Context<UpdateExtraAccountMetaList>,
+    ) -> Result<()> {
+        let metas = extra_account_metas()?;
+        let size = ExtraAccountMetaList::size_of(metas.len())?;
+        let list = ctx.accounts.extra_account_meta_list.to_account_info();
+
+        // Grow before rewriting, shrink after, so the list always fits its account
+        if size > list.data_len() {
+            let shortfall = Rent::get()?.minimum_balance(size).saturating_sub(list.lamports());
+            if shortfall > 0 {
+                let cpi_accounts = system_program::Transfer {
+                    from: ctx.accounts.issuer.to_account_info(),
+                    to: list.clone(),
+                };
+                let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+                system_program::transfer(cpi_ctx, shortfall)?;
+            }
+            list.realloc(size, false)?;
+        }
+        ExtraAccountMetaList::update::<ExecuteInstruction>(&mut list.try_borrow_mut_data()?, &metas)?;
+        if size < list.data_len() {
+            list.realloc(size, false)?;
+        }
+
+        msg!("Transfer hook extra account metas updated for mint {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
+    /// Create the transfer record of a wallet (anyone can pay for it)
+    pub fn initialize_transfer_record(ctx: Context<InitializeTransferRecord>) -> Result<()> {
+        ctx.accounts.transfer_record.user = ctx.accounts.user.key();
+        Ok(())
+    }
+
+    /// Attach hashed originator and beneficiary data to the next transfer of `amount`
+    /// from `source_token` to `destination_token`, as the travel rule requires above
+    /// the mint's threshold (owner of the source account only). Replaces a record
+    /// not used yet.
+    pub fn attach_travel_rule_info(
+        ctx: Context<AttachTravelRuleInfo>,
+        amount: u64,
+        originator_hash: [u8; 32],
+        beneficiary_hash: [u8; 32],
+    ) -> Result<()> {
+        if amount == 0 || originator_hash == [0; 32] || beneficiary_hash == [0; 32] {
+            return Err(MicaEurError::InvalidTravelRuleData.into());
+        }
+        let record = &mut ctx.accounts.travel_rule_record;
+        record.source_token = ctx.accounts.source_token.key();
+        record.destination_token = ctx.accounts.destination_token.key();
+        record.originator = ctx.accounts.originator.key();
+        record.amount = amount;
+        record.originator_hash = originator_hash;
+        record.beneficiary_hash = beneficiary_hash;
+        record.attached_at = Clock::get()?.unix_timestamp;
+        record.consumed_at = 0;
+        Ok(())
+    }
+
+    /// Create the velocity record of a wallet (anyone can pay for it)
+    pub fn initialize_velocity_record(ctx: Context<InitializeVelocityRecord>) -> Result<()> {
+        ctx.accounts.velocity_record.user = ctx.accounts.user.key();
+        Ok(())
+    }
+
+    /// SPL transfer hook `Execute`: reject transfers when either party is unverified,
+    /// expired or blacklisted. A staged rule set, if any, is evaluated alongside but
+    /// never enforced.
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        check_is_transferring(&ctx.accounts.source_token.to_account_info())?;
+
+        // Nothing moves while the token is paused, seizures included
+        if ctx.accounts.mint_info.paused {
+            return Err(MicaEurError::TokenPaused.into());
+        }
+
+        // The seizure authority PDA is the permanent delegate. The program only signs
+        // with it for seizures and recoveries; every other transfer it makes, like any
+        // holder's, goes through the checks below.
+        let seizure = ctx.accounts.owner.key() == ctx.accounts.seizure_authority.key();
+
+        // During a wind-down holders may return tokens to the issuer's redemption
+        // account whatever their KYC standing or limits
+        let redemption = is_redemption_account(&ctx.accounts.mint_info, &ctx.accounts.destination_token.key());
+
+        // An inactive mint no longer moves, except by regulatory seizure or redemption
+        if !ctx.accounts.mint_info.is_active && !seizure && !redemption {
+            return Err(MicaEurError::MintInactive.into());
+        }
+
+        // Regulatory seizures and recoveries by the permanent delegate are not subject
+        // to party checks
+        if seizure {
+            msg!("Transfer by permanent delegate, party checks skipped");
+            return Ok(());
+        }
+
+        // Recorded freezes: partial ones lock part of the balance, which the source
+        // holds after the transfer here; timed full ones block both directions
+        let now = Clock::get()?.unix_timestamp;
+        let source_freeze = load::<FreezeRecord>(&ctx.accounts.source_freeze_record)?;
+        check_outgoing_freeze(source_freeze.as_ref(), ctx.accounts.source_token.amount, now)?;
+        let confidential = amount == CONFIDENTIAL_TRANSFER_AMOUNT;
+        if confidential && source_freeze.as_ref().is_some_and(|freeze| freeze.in_force(now)) {
+            // The locked part cannot be told apart from an encrypted amount
+            return Err(MicaEurError::FrozenAmountLocked.into());
+        }
+        let destination_freeze = load::<FreezeRecord>(&ctx.accounts.destination_freeze_record)?;
+        check_incoming_freeze(destination_freeze.as_ref(), now)?;
+
+        // Returns to the redemption account only keep out blacklisted senders
+        if redemption {
+            if is_blacklisted(&ctx.accounts.sender_blacklist, now)? {
+                return Err(MicaEurError::UserBlacklisted.into());
+            }
+            msg!("Return to the redemption account, party checks skipped");
+            return Ok(());
+        }
+
+        let staged_policy = load::<StagedPolicy>(&ctx.accounts.staged_policy)?;
+        let sender = ctx.accounts.source_token.owner;
+        let recipient = ctx.accounts.destination_token.owner;
+        let sender_kyc_user = load::<KycUser>(&ctx.accounts.sender_kyc_user)?;
+        let recipient_kyc_user = load::<KycUser>(&ctx.accounts.recipient_kyc_user)?;
+
+        // The EDD escrow vault is owned by the config PDA. Funds entering it were
+        // requested by a checked sender, funds leaving it were reviewed; only the
+        // other party is checked.
+        let escrow = ctx.accounts.edd_config.key();
+
+        // The commerce escrow vault is owned by its PDA. The program checks both
+        // parties when an escrow is opened; here only the other party is checked.
+        let escrow_vault = ctx.accounts.escrow_vault.key();
+
+        // The treasury vault is owned by its PDA and holds funds the issuer minted;
+        // distributions out of it are checked on the recipient's side
+        let treasury_vault = ctx.accounts.treasury_vault.key();
+
+        // Registered PDAs of allowlisted programs, such as a regulated DEX escrow,
+        // hold funds on behalf of checked users; only the other party is checked
+        let allowlist = load::<ProgramAllowlist>(&ctx.accounts.program_allowlist)?;
+        let sender_exempt = sender == escrow
+            || sender == escrow_vault
+            || sender == treasury_vault
+            || is_allowlisted_escrow(
+                allowlist.as_ref(),
+                load::<ProgramEscrow>(&ctx.accounts.sender_program_escrow)?.as_ref(),
+            );
+        let recipient_exempt = recipient == escrow
+            || recipient == escrow_vault
+            || is_allowlisted_escrow(
+                allowlist.as_ref(),
+                load::<ProgramEscrow>(&ctx.accounts.recipient_program_escrow)?.as_ref(),
+            );
+        if !sender_exempt {
+            check_transfer_party(
+                sender,
+                sender_kyc_user.as_ref(),
+                is_blacklisted(&ctx.accounts.sender_blacklist, now)?,
+                staged_policy.as_ref(),
+            )?;
+        }
+        if !recipient_exempt {
+            check_transfer_party(
+                recipient,
+                recipient_kyc_user.as_ref(),
+                is_blacklisted(&ctx.accounts.recipient_blacklist, now)?,
+                staged_policy.as_ref(),
+            )?;
+        }
+
+        // Approved counterparties cap single transfers with them; confidential
+        // transfers cannot be checked against a cap
+        let counterparties = [
+            load::<ApprovedCounterparty>(&ctx.accounts.sender_counterparty)?,
+            load::<ApprovedCounterparty>(&ctx.accounts.recipient_counterparty)?,
+        ];
+        let transfer_amount = (!confidential).then_some(amount);
+        for counterparty in counterparties.iter().flatten() {
+            counterparty.check_transfer(transfer_amount)?;
+        }
+
+        // Large transfers need travel-rule data attached by the originator. Senders
+        // exempt above are program PDAs paying out funds received under the rule.
+        let travel_rule = load::<TravelRuleConfig>(&ctx.accounts.travel_rule_config)?;
+        if !sender_exempt && travel_rule.is_some_and(|config| config.applies_to(transfer_amount)) {
+            consume_travel_rule_record(
+                &ctx.accounts.travel_rule_record,
+                ctx.accounts.mint.key(),
+                recipient,
+                transfer_amount,
+                now,
+            )?;
+        }
+
+        // Fast-lane institutions are reported after every transfer they send or receive
+        let sender_institution = load::<FastLaneInstitution>(&ctx.accounts.sender_institution)?
+            .filter(|institution| institution.applies_to(sender_kyc_user.as_ref()));
+        let fast_lane_recipient = load::<FastLaneInstitution>(&ctx.accounts.recipient_institution)?
+            .is_some_and(|institution| institution.applies_to(recipient_kyc_user.as_ref()));
+        if sender_institution.is_some() || fast_lane_recipient {
+            emit!(InstitutionalTransferReported {
+                mint: ctx.accounts.mint.key(),
+                sender,
+                recipient,
+                amount: transfer_amount,
+                fast_lane_sender: sender_institution.is_some(),
+                fast_lane_recipient,
+                rule_set_version: RULE_SET_VERSION,
+                timestamp: now,
+            });
+        }
+
+        // Sender velocity, raising AML alerts at the mint's thresholds
+        if !sender_exempt {
+            track_velocity(
+                &ctx.accounts.sender_velocity_record,
+                load::<AmlAlertConfig>(&ctx.accounts.aml_alert_config)?.as_ref(),
+                sender,
+                transfer_amount.unwrap_or(0),
+                now,
+            )?;
+        }
+
+        // Confidential transfers carry no readable amount: the parties are checked
+        // above, the amount is left to the auditor holding the mint's auditor key
+        if confidential {
+            msg!("Confidential transfer, amount checks skipped");
+            return Ok(());
+        }
+
+        // Direct transfers needing review must go through the pre-approval queue
+        if let (Some(sender_kyc_user), Some(recipient_kyc_user)) =
+            (sender_kyc_user.as_ref(), recipient_kyc_user.as_ref())
+        {
+            let threshold = load::<EddConfig>(&ctx.accounts.edd_config)?
+                .map_or(0, |config| config.transfer_threshold);
+            check_edd_clearance(sender_kyc_user, recipient_kyc_user, amount, threshold)?;
+        }
+
+        // Senders other than the escrow are held to their class's and tier's transaction
+        // limits and to their tier's daily transfer limit, unless an approved counterparty on either side exempts
+        // the transfer from the latter. Fast-lane institutions have no transaction
+        // limit and are always held to their own daily cap.
+        let exempt = counterparties.iter().flatten().any(|counterparty| counterparty.daily_limit_exempt);
+        let daily_limit = match (sender_kyc_user.as_ref(), sender_institution.as_ref()) {
+            (_, Some(institution)) => Some(institution.daily_cap),
+            (Some(sender_kyc_user), None) if sender != escrow => {
+                let limit_config = load::<LimitConfig>(&ctx.accounts.limit_config)?;
+                let decimals = ctx.accounts.mint_info.decimals;
+                let limits = tier_limits(limit_config.as_ref(), sender_kyc_user.verification_level, decimals);
+                limits.check_transaction(amount)?;
+                check_class_transaction(sender_kyc_user, amount, decimals)?;
+                (!exempt).then_some(limits.daily_transfer)
+            }
+            _ => None,
+        };
+
+        stamp_transfer_record(&ctx.accounts.sender_transfer_record, amount, true, daily_limit, now)?;
+        stamp_transfer_record(&ctx.accounts.recipient_transfer_record, amount, false, None, now)?;
+
+        Ok(())
+    }
+}
+
+/// Per-user transfer history stamped by the transfer hook
+#[account]
+#[derive(InitSpace)]
+pub struct TransferRecord {
+    pub user: Pubkey,                 // Wallet the record belongs to
+    pub sent_count: u64,              // Number of outgoing transfers
+    pub received_count: u64,          // Number of incoming transfers
+    pub total_sent: u64,              // Cumulative amount sent
+    pub total_received: u64,          // Cumulative amount received
+    pub last_transfer_time: i64,      // When the user last sent or received tokens
+    pub rule_set_version: u32,        // Rule set that allowed the last transfer
+    pub sent_today: RollingVolume,    // Sent within the current daily limit window
+}
+
+/// Travel-rule data (FATF Recommendation 16, EU TFR) for the next large transfer
+/// between two token accounts. The hook marks it used so it covers one transfer.
+#[account]
+#[derive(InitSpace)]
+pub struct TravelRuleRecord {
+    pub source_token: Pubkey,         // Originator's token account
+    pub destination_token: Pubkey,    // Beneficiary's token account
+    pub originator: Pubkey,           // Owner of the source account that attached it
+    pub amount: u64,                  // Amount of the transfer it covers
+    pub originator_hash: [u8; 32],    // Hash of the originator's name, account and address
+    pub beneficiary_hash: [u8; 32],   // Hash of the beneficiary's name and account
+    pub attached_at: i64,
+    pub consumed_at: i64,             // When the transfer used it, 0 before
+}
+
+/// Emitted when a transfer uses a travel-rule record, for the originator's and the
+/// beneficiary's service providers to match it with the data exchanged off-chain
+#[event]
+pub struct TravelRuleRecordConsumed {
+    pub mint: Pubkey,
+    pub source_token: Pubkey,
+    pub destination_token: Pubkey,
+    pub originator: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: Option<u64>,
+    pub originator_hash: [u8; 32],
+    pub beneficiary_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Recent outgoing transfers of a wallet, kept by the transfer hook to detect
+/// velocity and structuring patterns
+#[account]
+#[derive(InitSpace)]
+pub struct VelocityRecord {
+    pub user: Pubkey,                 // Wallet the record belongs to
+    pub window: VelocityWindow,       // Its last transfers
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(
+        mut,
+        constraint = issuer.key() == mint_info.issuer
+    )]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+        constraint = mint_info.mint == mint.key(),
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Initialized in the handler with the TLV account resolution layout
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateExtraAccountMetaList<'info> {
+    #[account(
+        mut,
+        constraint = issuer.key() == mint_info.issuer
+    )]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+        constraint = mint_info.mint == mint.key(),
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Resized and rewritten in the handler with the TLV account resolution layout
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTransferRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only the key is used for the PDA seeds
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [TRANSFER_RECORD_SEED, user.key().as_ref()],
+        bump,
+        space = 8 + TransferRecord::INIT_SPACE,
+    )]
+    pub transfer_record: Account<'info, TransferRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttachTravelRuleInfo<'info> {
+    #[account(mut)]
+    pub originator: Signer<'info>,
+
+    #[account(token::authority = originator)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(token::mint = source_token.mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = originator,
+        seeds = [TRAVEL_RULE_RECORD_SEED, source_token.key().as_ref(), destination_token.key().as_ref()],
+        bump,
+        space = 8 + TravelRuleRecord::INIT_SPACE,
+    )]
+    pub travel_rule_record: Account<'info, TravelRuleRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVelocityRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only the key is used for the PDA seeds
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [VELOCITY_RECORD_SEED, user.key().as_ref()],
+        bump,
+        space = 8 + VelocityRecord::INIT_SPACE,
+    )]
+    pub velocity_record: Account<'info, VelocityRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts of the SPL transfer hook `Execute` instruction. The first five are fixed
+/// by the interface, the rest are resolved from the extra account meta list.
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Owner or delegate of the source account, validated by Token-2022
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Address checked against its seeds
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: The main program, owner of the compliance accounts below
+    #[account(address = mica_eur::ID)]
+    pub mica_eur_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MINT_INFO_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub mint_info: Account<'info, MintInfo>,
+
+    /// CHECK: May not exist for unregistered wallets; address checked against its seeds
+    #[account(
+        seeds = [KYC_USER_SEED, source_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub sender_kyc_user: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist for unregistered wallets; address checked against its seeds
+    #[account(
+        seeds = [KYC_USER_SEED, destination_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub recipient_kyc_user: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for blacklisted wallets; address checked against its seeds
+    #[account(
+        seeds = [BLACKLIST_SEED, source_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub sender_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for blacklisted wallets; address checked against its seeds
+    #[account(
+        seeds = [BLACKLIST_SEED, destination_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub recipient_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists while a rule set is staged; address checked against its seeds
+    #[account(
+        seeds = [STAGED_POLICY_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub staged_policy: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists once EDD is configured; address checked against its seeds
+    #[account(
+        seeds = [EDD_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub edd_config: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists once the issuer configured limits; address checked against its seeds
+    #[account(
+        seeds = [LIMIT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub limit_config: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist yet; address checked against its seeds
+    #[account(
+        mut,
+        seeds = [TRANSFER_RECORD_SEED, source_token.owner.as_ref()],
+        bump,
+    )]
+    pub sender_transfer_record: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist yet; address checked against its seeds
+    #[account(
+        mut,
+        seeds = [TRANSFER_RECORD_SEED, destination_token.owner.as_ref()],
+        bump,
+    )]
+    pub recipient_transfer_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for fast-lane institutions; address checked against its seeds
+    #[account(
+        seeds = [FAST_LANE_INSTITUTION_SEED, mint.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub sender_institution: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for fast-lane institutions; address checked against its seeds
+    #[account(
+        seeds = [FAST_LANE_INSTITUTION_SEED, mint.key().as_ref(), destination_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub recipient_institution: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists once the issuer set a travel-rule threshold; address checked against its seeds
+    #[account(
+        seeds = [TRAVEL_RULE_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub travel_rule_config: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists once the originator attached travel-rule data; address checked against its seeds
+    #[account(
+        mut,
+        seeds = [TRAVEL_RULE_RECORD_SEED, source_token.key().as_ref(), destination_token.key().as_ref()],
+        bump,
+    )]
+    pub travel_rule_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for frozen accounts; address checked against its seeds
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, source_token.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub source_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for frozen accounts; address checked against its seeds
+    #[account(
+        seeds = [FREEZE_RECORD_SEED, destination_token.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub destination_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for approved counterparties; address checked against its seeds
+    #[account(
+        seeds = [APPROVED_COUNTERPARTY_SEED, mint.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub sender_counterparty: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for approved counterparties; address checked against its seeds
+    #[account(
+        seeds = [APPROVED_COUNTERPARTY_SEED, mint.key().as_ref(), destination_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub recipient_counterparty: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists once the issuer configured AML alerts; address checked against its seeds
+    #[account(
+        seeds = [AML_ALERT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub aml_alert_config: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist yet; address checked against its seeds
+    #[account(
+        mut,
+        seeds = [VELOCITY_RECORD_SEED, source_token.owner.as_ref()],
+        bump,
+    )]
+    pub sender_velocity_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists once the issuer allowlisted a program; address checked against its seeds
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub program_allowlist: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for registered program PDAs; address checked against its seeds
+    #[account(
+        seeds = [PROGRAM_ESCROW_SEED, mint.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub sender_program_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Only exists for registered program PDAs; address checked against its seeds
+    #[account(
+        seeds = [PROGRAM_ESCROW_SEED, mint.key().as_ref(), destination_token.owner.as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub recipient_program_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the commerce escrow vault, compared by address only
+    #[account(
+        seeds = [ESCROW_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub escrow_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Permanent delegate of the mint, compared by address only
+    #[account(
+        seeds = [SEIZURE_AUTHORITY_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub seizure_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the treasury vault, compared by address only
+    #[account(
+        seeds = [TREASURY_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = mica_eur::ID,
+    )]
+    pub treasury_vault: UncheckedAccount<'info>,
+}
+
+// PDA seeds `[prefix, owner of the token account at account_index]`
+fn owner_seeds(prefix: &[u8], account_index: u8) -> Vec<Seed> {
+    vec![
+        Seed::Literal { bytes: prefix.to_vec() },
+        Seed::AccountData {
+            account_index,
+            data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+            length: 32,
+        },
+    ]
+}
+
+// PDA seeds `[prefix, mint, owner of the token account at account_index]`
+fn mint_owner_seeds(prefix: &[u8], account_index: u8) -> Vec<Seed> {
+    vec![
+        Seed::Literal { bytes: prefix.to_vec() },
+        Seed::AccountKey { index: MINT_INDEX },
+        Seed::AccountData {
+            account_index,
+            data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+            length: 32,
+        },
+    ]
+}
+
+// PDA seeds `[prefix, mint]`
+fn mint_seeds(prefix: &[u8]) -> Vec<Seed> {
+    vec![
+        Seed::Literal { bytes: prefix.to_vec() },
+        Seed::AccountKey { index: MINT_INDEX },
+    ]
+}
+
+// PDA seeds `[prefix, source token account, destination token account]`
+fn token_pair_seeds(prefix: &[u8]) -> Vec<Seed> {
+    vec![
+        Seed::Literal { bytes: prefix.to_vec() },
+        Seed::AccountKey { index: SOURCE_INDEX },
+        Seed::AccountKey { index: DESTINATION_INDEX },
+    ]
+}
+
+// PDA seeds `[prefix, key of the token account at account_index]`
+fn token_account_seeds(prefix: &[u8], account_index: u8) -> Vec<Seed> {
+    vec![
+        Seed::Literal { bytes: prefix.to_vec() },
+        Seed::AccountKey { index: account_index },
+    ]
+}
+
+// Account of the main program derived from `seeds`
+fn mica_eur_pda(seeds: &[Seed]) -> Result<ExtraAccountMeta> {
+    Ok(ExtraAccountMeta::new_external_pda_with_seeds(MICA_EUR_PROGRAM_INDEX, seeds, false, false)?)
+}
+
+/// Extra accounts Token-2022 resolves for every transfer, in `TransferHook` order
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&mica_eur::ID, false, false)?,
+        mica_eur_pda(&mint_seeds(MINT_INFO_SEED))?,
+        mica_eur_pda(&owner_seeds(KYC_USER_SEED, SOURCE_INDEX))?,
+        mica_eur_pda(&owner_seeds(KYC_USER_SEED, DESTINATION_INDEX))?,
+        mica_eur_pda(&owner_seeds(BLACKLIST_SEED, SOURCE_INDEX))?,
+        mica_eur_pda(&owner_seeds(BLACKLIST_SEED, DESTINATION_INDEX))?,
+        mica_eur_pda(&mint_seeds(STAGED_POLICY_SEED))?,
+        mica_eur_pda(&mint_seeds(EDD_CONFIG_SEED))?,
+        mica_eur_pda(&mint_seeds(LIMIT_CONFIG_SEED))?,
+        ExtraAccountMeta::new_with_seeds(&owner_seeds(TRANSFER_RECORD_SEED, SOURCE_INDEX), false, true)?,
+        ExtraAccountMeta::new_with_seeds(&owner_seeds(TRANSFER_RECORD_SEED, DESTINATION_INDEX), false, true)?,
+        mica_eur_pda(&mint_owner_seeds(FAST_LANE_INSTITUTION_SEED, SOURCE_INDEX))?,
+        mica_eur_pda(&mint_owner_seeds(FAST_LANE_INSTITUTION_SEED, DESTINATION_INDEX))?,
+        mica_eur_pda(&mint_seeds(TRAVEL_RULE_CONFIG_SEED))?,
+        ExtraAccountMeta::new_with_seeds(&token_pair_seeds(TRAVEL_RULE_RECORD_SEED), false, true)?,
+        mica_eur_pda(&token_account_seeds(FREEZE_RECORD_SEED, SOURCE_INDEX))?,
+        mica_eur_pda(&token_account_seeds(FREEZE_RECORD_SEED, DESTINATION_INDEX))?,
+        mica_eur_pda(&mint_owner_seeds(APPROVED_COUNTERPARTY_SEED, SOURCE_INDEX))?,
+        mica_eur_pda(&mint_owner_seeds(APPROVED_COUNTERPARTY_SEED, DESTINATION_INDEX))?,
+        mica_eur_pda(&mint_seeds(AML_ALERT_CONFIG_SEED))?,
+        ExtraAccountMeta::new_with_seeds(&owner_seeds(VELOCITY_RECORD_SEED, SOURCE_INDEX), false, true)?,
+        mica_eur_pda(&mint_seeds(PROGRAM_ALLOWLIST_SEED))?,
+        mica_eur_pda(&mint_owner_seeds(PROGRAM_ESCROW_SEED, SOURCE_INDEX))?,
+        mica_eur_pda(&mint_owner_seeds(PROGRAM_ESCROW_SEED, DESTINATION_INDEX))?,
+        mica_eur_pda(&mint_seeds(ESCROW_VAULT_SEED))?,
+        mica_eur_pda(&mint_seeds(SEIZURE_AUTHORITY_SEED))?,
+        mica_eur_pda(&mint_seeds(TREASURY_VAULT_SEED))?,
+    ])
+}
+
+// Deserialize an account of `T`'s program, `None` if it was never created
+fn load<T: AccountDeserialize + Owner>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.data_is_empty() {
+        return Ok(None);
+    }
+    if info.owner != &T::owner() {
+        return Err(MicaEurError::HookAccountOwnerMismatch.into());
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut &data[..])?))
+}
+
+// Whether a blacklist entry in force exists
+fn is_blacklisted(info: &AccountInfo, now: i64) -> Result<bool> {
+    Ok(load::<BlacklistEntry>(info)?.is_some_and(|entry| entry.in_force(now)))
+}
+
+// Stamp a transfer on a user's record. A sender with a `daily_limit` must have a
+// record to track its volume; other records are stamped if they exist.
+fn stamp_transfer_record(
+    info: &AccountInfo,
+    amount: u64,
+    outgoing: bool,
+    daily_limit: Option<u64>,
+    now: i64,
+) -> Result<()> {
+    let mut record = match load::<TransferRecord>(info)? {
+        Some(record) => record,
+        None if daily_limit.is_some() => return Err(MicaEurError::TransferRecordRequired.into()),
+        None => return Ok(()),
+    };
+    if let Some(daily_limit) = daily_limit {
+        record.sent_today.record(amount, daily_limit, now, MicaEurError::DailyTransferLimitExceeded)?;
+    }
+    if outgoing {
+        record.sent_count = record.sent_count.saturating_add(1);
+        record.total_sent = record.total_sent.saturating_add(amount);
+    } else {
+        record.received_count = record.received_count.saturating_add(1);
+        record.total_received = record.total_received.saturating_add(amount);
+    }
+    record.last_transfer_time = now;
+    record.rule_set_version = RULE_SET_VERSION;
+
+    let mut data = info.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])
+}
+
+// Mark the travel-rule record of a transfer of `amount` used. Confidential
+// transfers, with no readable amount, match a record of any amount.
+fn consume_travel_rule_record(
+    info: &AccountInfo,
+    mint: Pubkey,
+    beneficiary: Pubkey,
+    amount: Option<u64>,
+    now: i64,
+) -> Result<()> {
+    let mut record = load::<TravelRuleRecord>(info)?.ok_or(MicaEurError::TravelRuleRecordRequired)?;
+    if record.consumed_at != 0 || amount.is_some_and(|amount| amount != record.amount) {
+        return Err(MicaEurError::TravelRuleRecordMismatch.into());
+    }
+    record.consumed_at = now;
+    emit!(TravelRuleRecordConsumed {
+        mint,
+        source_token: record.source_token,
+        destination_token: record.destination_token,
+        originator: record.originator,
+        beneficiary,
+        amount,
+        originator_hash: record.originator_hash,
+        beneficiary_hash: record.beneficiary_hash,
+        timestamp: now,
+    });
+
+    let mut data = info.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])
+}
+
+// Add a transfer to the sender's velocity record and emit the AML alerts it
+// raises. Senders need a record once the mint has alert thresholds.
+fn track_velocity(
+    info: &AccountInfo,
+    config: Option<&AmlAlertConfig>,
+    sender: Pubkey,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+    let mut record = load::<VelocityRecord>(info)?.ok_or(MicaEurError::VelocityRecordRequired)?;
+    record.window.record(amount, now);
+    for (kind, observed, threshold) in record.window.alerts(config, now) {
+        emit!(AmlAlert {
+            mint: config.mint,
+            wallet: sender,
+            kind,
+            observed,
+            threshold,
+            timestamp: now,
+        });
+    }
+
+    let mut data = info.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])
+}
+
+// The hook must only run from within a Token-2022 transfer: never as a top-level
+// instruction, and only while the source account is flagged as transferring
+fn check_is_transferring(source_token: &AccountInfo) -> Result<()> {
+    if get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Err(MicaEurError::HookInvokedDirectly.into());
+    }
+    let data = source_token.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+    if !bool::from(extension.transferring) {
+        return Err(MicaEurError::NotTransferring.into());
+    }
+    Ok(())
+}
\ No newline at end of file